@@ -0,0 +1,39 @@
+-- Report schedules and run history
+-- Version: 013
+-- Description: Store cron-based report schedules and record each scheduled run
+
+-- Scheduled report definitions (request holds the serialized ReportRequest)
+CREATE TABLE IF NOT EXISTS report_schedules (
+    id UUID PRIMARY KEY,
+    name VARCHAR(100) NOT NULL UNIQUE,
+    cron_expression VARCHAR(100) NOT NULL,
+    request JSONB NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    last_run_at TIMESTAMP WITH TIME ZONE,
+    next_run_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_report_schedules_next_run_at ON report_schedules(next_run_at) WHERE enabled;
+
+-- Run history is kept after a schedule is removed
+CREATE TABLE IF NOT EXISTS report_schedule_runs (
+    id UUID PRIMARY KEY,
+    schedule_id UUID REFERENCES report_schedules(id) ON DELETE SET NULL,
+    schedule_name VARCHAR(100) NOT NULL,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    finished_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    status VARCHAR(20) NOT NULL, -- success, failed
+    output_path TEXT,
+    error_message TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_report_schedule_runs_name_started ON report_schedule_runs(schedule_name, started_at DESC);
+
+-- DOWN
+-- Rollback migration
+DROP INDEX IF EXISTS idx_report_schedule_runs_name_started;
+DROP TABLE IF EXISTS report_schedule_runs;
+DROP INDEX IF EXISTS idx_report_schedules_next_run_at;
+DROP TABLE IF EXISTS report_schedules;
@@ -0,0 +1,32 @@
+-- Audit trail
+-- Version: 014
+-- Description: Persist audit events recorded by the CLI handlers and security services
+
+CREATE TABLE IF NOT EXISTS audit_events (
+    id UUID PRIMARY KEY,
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    user_id UUID,
+    username VARCHAR(100),
+    action VARCHAR(50) NOT NULL,
+    resource VARCHAR(100),
+    resource_id VARCHAR(255),
+    severity VARCHAR(20) NOT NULL, -- low, medium, high, critical
+    details JSONB NOT NULL DEFAULT '{}',
+    ip_address VARCHAR(64),
+    user_agent TEXT,
+    success BOOLEAN NOT NULL DEFAULT TRUE,
+    error_message TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_events_occurred_at ON audit_events(occurred_at DESC);
+CREATE INDEX IF NOT EXISTS idx_audit_events_resource ON audit_events(resource, resource_id);
+CREATE INDEX IF NOT EXISTS idx_audit_events_username ON audit_events(username);
+CREATE INDEX IF NOT EXISTS idx_audit_events_action ON audit_events(action);
+
+-- DOWN
+-- Rollback migration
+DROP INDEX IF EXISTS idx_audit_events_action;
+DROP INDEX IF EXISTS idx_audit_events_username;
+DROP INDEX IF EXISTS idx_audit_events_resource;
+DROP INDEX IF EXISTS idx_audit_events_occurred_at;
+DROP TABLE IF EXISTS audit_events;
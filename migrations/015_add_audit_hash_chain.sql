@@ -0,0 +1,32 @@
+-- Audit hash chain
+-- Version: 015
+-- Description: Chain audit events with content hashes and store signed checkpoints
+
+-- Existing rows receive sequence numbers in insertion order but stay unsealed
+ALTER TABLE audit_events ADD COLUMN IF NOT EXISTS sequence BIGSERIAL;
+ALTER TABLE audit_events ADD COLUMN IF NOT EXISTS previous_hash VARCHAR(64);
+ALTER TABLE audit_events ADD COLUMN IF NOT EXISTS content_hash VARCHAR(64);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_audit_events_sequence ON audit_events(sequence);
+
+CREATE TABLE IF NOT EXISTS audit_checkpoints (
+    id UUID PRIMARY KEY,
+    sequence BIGINT NOT NULL UNIQUE,
+    event_id UUID NOT NULL,
+    event_timestamp TIMESTAMP WITH TIME ZONE NOT NULL,
+    content_hash VARCHAR(64) NOT NULL,
+    previous_signature VARCHAR(64),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    signature VARCHAR(64) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_checkpoints_created_at ON audit_checkpoints(created_at);
+
+-- DOWN
+-- Rollback migration
+DROP INDEX IF EXISTS idx_audit_checkpoints_created_at;
+DROP TABLE IF EXISTS audit_checkpoints;
+DROP INDEX IF EXISTS idx_audit_events_sequence;
+ALTER TABLE audit_events DROP COLUMN IF EXISTS content_hash;
+ALTER TABLE audit_events DROP COLUMN IF EXISTS previous_hash;
+ALTER TABLE audit_events DROP COLUMN IF EXISTS sequence;
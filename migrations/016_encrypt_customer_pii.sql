@@ -0,0 +1,34 @@
+-- Customer PII encryption
+-- Version: 016
+-- Description: Widen PII columns for ciphertext and add blind index columns for lookups
+
+-- Ciphertext is longer than the plaintext limits
+ALTER TABLE customers ALTER COLUMN email TYPE TEXT;
+ALTER TABLE customers ALTER COLUMN phone TYPE TEXT;
+ALTER TABLE customers ALTER COLUMN tax_id TYPE TEXT;
+ALTER TABLE customer_addresses ALTER COLUMN address_line1 TYPE TEXT;
+ALTER TABLE customer_addresses ALTER COLUMN address_line2 TYPE TEXT;
+ALTER TABLE customer_addresses ALTER COLUMN postal_code TYPE TEXT;
+
+-- HMAC-SHA256 of the normalized value; NULL for rows not yet encrypted
+ALTER TABLE customers ADD COLUMN IF NOT EXISTS email_hash VARCHAR(64);
+ALTER TABLE customers ADD COLUMN IF NOT EXISTS phone_hash VARCHAR(64);
+
+-- Email uniqueness moves to the blind index since ciphertext is randomized
+CREATE UNIQUE INDEX IF NOT EXISTS idx_customers_email_hash ON customers(email_hash);
+CREATE INDEX IF NOT EXISTS idx_customers_phone_hash ON customers(phone_hash);
+DROP INDEX IF EXISTS idx_customer_addresses_postal;
+
+-- DOWN
+-- Rollback migration (encrypted values must be decrypted first)
+CREATE INDEX IF NOT EXISTS idx_customer_addresses_postal ON customer_addresses(postal_code);
+DROP INDEX IF EXISTS idx_customers_phone_hash;
+DROP INDEX IF EXISTS idx_customers_email_hash;
+ALTER TABLE customers DROP COLUMN IF EXISTS phone_hash;
+ALTER TABLE customers DROP COLUMN IF EXISTS email_hash;
+ALTER TABLE customer_addresses ALTER COLUMN postal_code TYPE VARCHAR(20);
+ALTER TABLE customer_addresses ALTER COLUMN address_line2 TYPE VARCHAR(255);
+ALTER TABLE customer_addresses ALTER COLUMN address_line1 TYPE VARCHAR(255);
+ALTER TABLE customers ALTER COLUMN tax_id TYPE VARCHAR(100);
+ALTER TABLE customers ALTER COLUMN phone TYPE VARCHAR(50);
+ALTER TABLE customers ALTER COLUMN email TYPE VARCHAR(255);
@@ -0,0 +1,44 @@
+-- Monitoring
+-- Version: 017
+-- Description: Persist metrics, health check results and alerts recorded by `erp system monitor`
+
+CREATE TABLE IF NOT EXISTS monitoring_metrics (
+    id BIGSERIAL PRIMARY KEY,
+    kind VARCHAR(20) NOT NULL, -- system, application, security
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    data JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS health_check_results (
+    id BIGSERIAL PRIMARY KEY,
+    service VARCHAR(100) NOT NULL,
+    status VARCHAR(20) NOT NULL, -- Healthy, Warning, Critical, Down
+    checked_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    response_time_ms BIGINT
+);
+
+CREATE TABLE IF NOT EXISTS monitoring_alerts (
+    id UUID PRIMARY KEY,
+    raised_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    severity VARCHAR(20) NOT NULL,
+    category VARCHAR(20) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL,
+    metrics JSONB NOT NULL DEFAULT '{}',
+    resolved BOOLEAN NOT NULL DEFAULT FALSE,
+    resolved_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_monitoring_metrics_kind_recorded_at ON monitoring_metrics(kind, recorded_at DESC);
+CREATE INDEX IF NOT EXISTS idx_health_check_results_service ON health_check_results(service, checked_at DESC);
+CREATE INDEX IF NOT EXISTS idx_monitoring_alerts_resolved ON monitoring_alerts(resolved, raised_at DESC);
+
+-- DOWN
+-- Rollback migration
+DROP INDEX IF EXISTS idx_monitoring_alerts_resolved;
+DROP INDEX IF EXISTS idx_health_check_results_service;
+DROP INDEX IF EXISTS idx_monitoring_metrics_kind_recorded_at;
+DROP TABLE IF EXISTS monitoring_alerts;
+DROP TABLE IF EXISTS health_check_results;
+DROP TABLE IF EXISTS monitoring_metrics;
@@ -0,0 +1,18 @@
+-- Rate limits
+-- Version: 018
+-- Description: Persist rate limit events so limits and login lockouts hold across CLI invocations
+
+CREATE TABLE IF NOT EXISTS rate_limit_events (
+    id BIGSERIAL PRIMARY KEY,
+    identifier VARCHAR(255) NOT NULL, -- login:<user>, export:<user>, password_reset
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_rate_limit_events_identifier ON rate_limit_events(identifier, occurred_at DESC);
+CREATE INDEX IF NOT EXISTS idx_rate_limit_events_occurred_at ON rate_limit_events(occurred_at);
+
+-- DOWN
+-- Rollback migration
+DROP INDEX IF EXISTS idx_rate_limit_events_occurred_at;
+DROP INDEX IF EXISTS idx_rate_limit_events_identifier;
+DROP TABLE IF EXISTS rate_limit_events;
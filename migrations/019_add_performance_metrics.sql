@@ -0,0 +1,41 @@
+-- Performance metrics
+-- Version: 019
+-- Description: Persist per-command timings from --profile so trends survive across releases
+
+CREATE TABLE IF NOT EXISTS performance_metrics (
+    id BIGSERIAL PRIMARY KEY,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    endpoint VARCHAR(255) NOT NULL, -- command path, e.g. "reports financial-overview"
+    method VARCHAR(32) NOT NULL,
+    response_time_ms BIGINT NOT NULL,
+    status_code INTEGER NOT NULL,
+    request_size_bytes BIGINT NOT NULL DEFAULT 0,
+    response_size_bytes BIGINT NOT NULL DEFAULT 0,
+    user_id VARCHAR(255),
+    session_id VARCHAR(255),
+    error_message TEXT,
+    query_count BIGINT NOT NULL DEFAULT 0,
+    query_time_ms DOUBLE PRECISION NOT NULL DEFAULT 0,
+    peak_memory_kb BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_performance_metrics_endpoint ON performance_metrics(endpoint, recorded_at DESC);
+CREATE INDEX IF NOT EXISTS idx_performance_metrics_recorded_at ON performance_metrics(recorded_at);
+
+CREATE TABLE IF NOT EXISTS performance_snapshots (
+    id BIGSERIAL PRIMARY KEY,
+    kind VARCHAR(32) NOT NULL, -- aggregated, system
+    endpoint VARCHAR(255),
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    data JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_performance_snapshots_kind ON performance_snapshots(kind, recorded_at DESC);
+
+-- DOWN
+-- Rollback migration
+DROP INDEX IF EXISTS idx_performance_snapshots_kind;
+DROP TABLE IF EXISTS performance_snapshots;
+DROP INDEX IF EXISTS idx_performance_metrics_recorded_at;
+DROP INDEX IF EXISTS idx_performance_metrics_endpoint;
+DROP TABLE IF EXISTS performance_metrics;
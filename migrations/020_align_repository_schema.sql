@@ -0,0 +1,67 @@
+-- Migration: align_repository_schema
+-- Version: 020
+-- Description: Convert the migration-003 sales and stock columns to the enum types and column names the Postgres repositories use
+
+-- Enum labels follow the sqlx `rename_all = "lowercase"` models
+CREATE TYPE stock_movement_type AS ENUM ('in', 'out', 'adjustment', 'transfer', 'damaged', 'expired');
+CREATE TYPE order_status AS ENUM ('draft', 'pending', 'confirmed', 'processing', 'shipped', 'delivered', 'cancelled', 'returned');
+CREATE TYPE payment_status AS ENUM ('pending', 'paid', 'partiallypaid', 'overdue', 'failed', 'refunded');
+CREATE TYPE payment_method AS ENUM ('cash', 'creditcard', 'debitcard', 'banktransfer', 'check', 'paypal', 'crypto');
+
+ALTER TYPE product_status RENAME VALUE 'out_of_stock' TO 'outofstock';
+
+-- Stock movements
+ALTER TABLE stock_movements
+    ALTER COLUMN movement_type TYPE stock_movement_type
+    USING LOWER(movement_type)::stock_movement_type;
+
+-- Sales orders: snake_case labels such as 'partially_paid' map to the enum label
+ALTER TABLE sales_orders ALTER COLUMN status DROP DEFAULT;
+ALTER TABLE sales_orders ALTER COLUMN payment_status DROP DEFAULT;
+
+ALTER TABLE sales_orders
+    ALTER COLUMN status TYPE order_status
+        USING REPLACE(LOWER(status), '_', '')::order_status,
+    ALTER COLUMN payment_status TYPE payment_status
+        USING REPLACE(LOWER(payment_status), '_', '')::payment_status,
+    ALTER COLUMN payment_method TYPE payment_method
+        USING REPLACE(LOWER(payment_method), '_', '')::payment_method;
+
+ALTER TABLE sales_orders ALTER COLUMN status SET DEFAULT 'draft';
+ALTER TABLE sales_orders ALTER COLUMN payment_status SET DEFAULT 'pending';
+
+-- Orders store the address text they were placed with
+ALTER TABLE sales_orders ADD COLUMN shipping_address TEXT;
+ALTER TABLE sales_orders ADD COLUMN billing_address TEXT;
+
+-- Sales order items
+ALTER TABLE sales_order_items RENAME COLUMN discount_amount TO discount;
+ALTER TABLE sales_order_items RENAME COLUMN total_price TO line_total;
+
+-- DOWN
+ALTER TABLE sales_order_items RENAME COLUMN line_total TO total_price;
+ALTER TABLE sales_order_items RENAME COLUMN discount TO discount_amount;
+
+ALTER TABLE sales_orders DROP COLUMN IF EXISTS billing_address;
+ALTER TABLE sales_orders DROP COLUMN IF EXISTS shipping_address;
+
+ALTER TABLE sales_orders ALTER COLUMN status DROP DEFAULT;
+ALTER TABLE sales_orders ALTER COLUMN payment_status DROP DEFAULT;
+
+ALTER TABLE sales_orders
+    ALTER COLUMN status TYPE VARCHAR(50) USING status::text,
+    ALTER COLUMN payment_status TYPE VARCHAR(50) USING payment_status::text,
+    ALTER COLUMN payment_method TYPE VARCHAR(50) USING payment_method::text;
+
+ALTER TABLE sales_orders ALTER COLUMN status SET DEFAULT 'draft';
+ALTER TABLE sales_orders ALTER COLUMN payment_status SET DEFAULT 'pending';
+
+ALTER TABLE stock_movements
+    ALTER COLUMN movement_type TYPE VARCHAR(20) USING movement_type::text;
+
+ALTER TYPE product_status RENAME VALUE 'outofstock' TO 'out_of_stock';
+
+DROP TYPE IF EXISTS payment_method;
+DROP TYPE IF EXISTS payment_status;
+DROP TYPE IF EXISTS order_status;
+DROP TYPE IF EXISTS stock_movement_type;
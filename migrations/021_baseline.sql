@@ -0,0 +1,453 @@
+-- Migration: baseline
+-- Version: 021
+-- Description: Squashed schema of migrations up to 020, generated by `erp migrate baseline`
+-- baseline
+--
+-- Fresh databases apply only this file. Databases that already ran the
+-- earlier migrations keep that history and record this file without running it.
+
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+CREATE TYPE order_status AS ENUM ('draft', 'pending', 'confirmed', 'processing', 'shipped', 'delivered', 'cancelled', 'returned');
+
+CREATE TYPE payment_method AS ENUM ('cash', 'creditcard', 'debitcard', 'banktransfer', 'check', 'paypal', 'crypto');
+
+CREATE TYPE payment_status AS ENUM ('pending', 'paid', 'partiallypaid', 'overdue', 'failed', 'refunded');
+
+CREATE TYPE product_status AS ENUM ('active', 'inactive', 'discontinued', 'outofstock');
+
+CREATE TYPE stock_movement_type AS ENUM ('in', 'out', 'adjustment', 'transfer', 'damaged', 'expired');
+
+CREATE SEQUENCE audit_events_sequence_seq AS bigint INCREMENT BY 1 START WITH 1;
+
+CREATE SEQUENCE health_check_results_id_seq AS bigint INCREMENT BY 1 START WITH 1;
+
+CREATE SEQUENCE monitoring_metrics_id_seq AS bigint INCREMENT BY 1 START WITH 1;
+
+CREATE SEQUENCE performance_metrics_id_seq AS bigint INCREMENT BY 1 START WITH 1;
+
+CREATE SEQUENCE performance_snapshots_id_seq AS bigint INCREMENT BY 1 START WITH 1;
+
+CREATE SEQUENCE rate_limit_events_id_seq AS bigint INCREMENT BY 1 START WITH 1;
+
+CREATE TABLE audit_checkpoints (
+    id uuid NOT NULL,
+    sequence bigint NOT NULL,
+    event_id uuid NOT NULL,
+    event_timestamp timestamp with time zone NOT NULL,
+    content_hash character varying(64) NOT NULL,
+    previous_signature character varying(64),
+    created_at timestamp with time zone NOT NULL,
+    signature character varying(64) NOT NULL
+);
+
+CREATE TABLE audit_events (
+    id uuid NOT NULL,
+    occurred_at timestamp with time zone NOT NULL,
+    user_id uuid,
+    username character varying(100),
+    action character varying(50) NOT NULL,
+    resource character varying(100),
+    resource_id character varying(255),
+    severity character varying(20) NOT NULL,
+    details jsonb DEFAULT '{}'::jsonb NOT NULL,
+    ip_address character varying(64),
+    user_agent text,
+    success boolean DEFAULT true NOT NULL,
+    error_message text,
+    sequence bigint DEFAULT nextval('audit_events_sequence_seq'::regclass) NOT NULL,
+    previous_hash character varying(64),
+    content_hash character varying(64)
+);
+
+CREATE TABLE customer_addresses (
+    id uuid NOT NULL,
+    customer_id uuid NOT NULL,
+    address_type character varying(20) DEFAULT 'billing'::character varying NOT NULL,
+    address_line1 text NOT NULL,
+    address_line2 text,
+    city character varying(100),
+    state_province character varying(100),
+    postal_code text,
+    country character varying(100) DEFAULT 'US'::character varying,
+    is_default boolean DEFAULT false NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL
+);
+
+CREATE TABLE customers (
+    id uuid NOT NULL,
+    name character varying(255) NOT NULL,
+    email text,
+    phone text,
+    customer_type character varying(50) DEFAULT 'regular'::character varying,
+    company character varying(255),
+    tax_id text,
+    credit_limit numeric(10,2) DEFAULT 0.00,
+    current_balance numeric(10,2) DEFAULT 0.00,
+    notes text,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    updated_at timestamp with time zone DEFAULT now() NOT NULL,
+    status character varying(20) DEFAULT 'active'::character varying,
+    first_name character varying(255),
+    last_name character varying(255),
+    customer_code character varying(100),
+    company_name character varying(255),
+    email_hash character varying(64),
+    phone_hash character varying(64)
+);
+
+CREATE TABLE health_check_results (
+    id bigint DEFAULT nextval('health_check_results_id_seq'::regclass) NOT NULL,
+    service character varying(100) NOT NULL,
+    status character varying(20) NOT NULL,
+    checked_at timestamp with time zone NOT NULL,
+    details jsonb DEFAULT '{}'::jsonb NOT NULL,
+    response_time_ms bigint
+);
+
+CREATE TABLE monitoring_alerts (
+    id uuid NOT NULL,
+    raised_at timestamp with time zone NOT NULL,
+    severity character varying(20) NOT NULL,
+    category character varying(20) NOT NULL,
+    title character varying(255) NOT NULL,
+    description text NOT NULL,
+    metrics jsonb DEFAULT '{}'::jsonb NOT NULL,
+    resolved boolean DEFAULT false NOT NULL,
+    resolved_at timestamp with time zone
+);
+
+CREATE TABLE monitoring_metrics (
+    id bigint DEFAULT nextval('monitoring_metrics_id_seq'::regclass) NOT NULL,
+    kind character varying(20) NOT NULL,
+    recorded_at timestamp with time zone NOT NULL,
+    data jsonb NOT NULL
+);
+
+CREATE TABLE performance_metrics (
+    id bigint DEFAULT nextval('performance_metrics_id_seq'::regclass) NOT NULL,
+    recorded_at timestamp with time zone DEFAULT now() NOT NULL,
+    endpoint character varying(255) NOT NULL,
+    method character varying(32) NOT NULL,
+    response_time_ms bigint NOT NULL,
+    status_code integer NOT NULL,
+    request_size_bytes bigint DEFAULT 0 NOT NULL,
+    response_size_bytes bigint DEFAULT 0 NOT NULL,
+    user_id character varying(255),
+    session_id character varying(255),
+    error_message text,
+    query_count bigint DEFAULT 0 NOT NULL,
+    query_time_ms double precision DEFAULT 0 NOT NULL,
+    peak_memory_kb bigint DEFAULT 0 NOT NULL
+);
+
+CREATE TABLE performance_snapshots (
+    id bigint DEFAULT nextval('performance_snapshots_id_seq'::regclass) NOT NULL,
+    kind character varying(32) NOT NULL,
+    endpoint character varying(255),
+    recorded_at timestamp with time zone NOT NULL,
+    data jsonb NOT NULL
+);
+
+CREATE TABLE products (
+    id uuid NOT NULL,
+    sku character varying(100) NOT NULL,
+    name character varying(255) NOT NULL,
+    description text,
+    category character varying(100) DEFAULT 'general'::character varying NOT NULL,
+    price numeric(14,2) NOT NULL,
+    cost numeric(14,2),
+    quantity integer DEFAULT 0 NOT NULL,
+    min_stock_level integer DEFAULT 0 NOT NULL,
+    max_stock_level integer,
+    status product_status DEFAULT 'active'::product_status NOT NULL,
+    is_taxable boolean DEFAULT true NOT NULL,
+    weight numeric(10,3),
+    dimensions character varying(100),
+    barcode character varying(255),
+    supplier_id uuid,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    updated_at timestamp with time zone DEFAULT now() NOT NULL
+);
+
+CREATE TABLE rate_limit_events (
+    id bigint DEFAULT nextval('rate_limit_events_id_seq'::regclass) NOT NULL,
+    identifier character varying(255) NOT NULL,
+    occurred_at timestamp with time zone DEFAULT now() NOT NULL
+);
+
+CREATE TABLE report_schedule_runs (
+    id uuid NOT NULL,
+    schedule_id uuid,
+    schedule_name character varying(100) NOT NULL,
+    started_at timestamp with time zone NOT NULL,
+    finished_at timestamp with time zone NOT NULL,
+    status character varying(20) NOT NULL,
+    output_path text,
+    error_message text
+);
+
+CREATE TABLE report_schedules (
+    id uuid NOT NULL,
+    name character varying(100) NOT NULL,
+    cron_expression character varying(100) NOT NULL,
+    request jsonb NOT NULL,
+    enabled boolean DEFAULT true NOT NULL,
+    last_run_at timestamp with time zone,
+    next_run_at timestamp with time zone NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    updated_at timestamp with time zone DEFAULT now() NOT NULL
+);
+
+CREATE TABLE sales_order_items (
+    id uuid NOT NULL,
+    order_id uuid NOT NULL,
+    product_id uuid NOT NULL,
+    quantity integer NOT NULL,
+    unit_price numeric(14,2) NOT NULL,
+    discount_percentage numeric(5,2) DEFAULT 0 NOT NULL,
+    discount numeric(14,2) DEFAULT 0 NOT NULL,
+    line_total numeric(14,2) NOT NULL,
+    notes text,
+    created_at timestamp with time zone DEFAULT now() NOT NULL
+);
+
+CREATE TABLE sales_orders (
+    id uuid NOT NULL,
+    customer_id uuid NOT NULL,
+    order_number character varying(100) NOT NULL,
+    status order_status DEFAULT 'draft'::order_status NOT NULL,
+    order_date timestamp with time zone DEFAULT now() NOT NULL,
+    required_date timestamp with time zone,
+    shipped_date timestamp with time zone,
+    subtotal numeric(14,2) DEFAULT 0 NOT NULL,
+    tax_rate numeric(5,4) DEFAULT 0 NOT NULL,
+    tax_amount numeric(14,2) DEFAULT 0 NOT NULL,
+    discount_percentage numeric(5,2) DEFAULT 0 NOT NULL,
+    discount_amount numeric(14,2) DEFAULT 0 NOT NULL,
+    shipping_amount numeric(14,2) DEFAULT 0 NOT NULL,
+    total_amount numeric(14,2) DEFAULT 0 NOT NULL,
+    payment_method payment_method,
+    payment_status payment_status DEFAULT 'pending'::payment_status,
+    shipping_address_id uuid,
+    billing_address_id uuid,
+    notes text,
+    internal_notes text,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    updated_at timestamp with time zone DEFAULT now() NOT NULL,
+    shipping_address text,
+    billing_address text
+);
+
+CREATE TABLE stock_movements (
+    id uuid NOT NULL,
+    product_id uuid NOT NULL,
+    movement_type stock_movement_type NOT NULL,
+    quantity integer NOT NULL,
+    reason text,
+    reference_id uuid,
+    user_id uuid,
+    created_at timestamp with time zone DEFAULT now() NOT NULL
+);
+
+CREATE TABLE users (
+    id character varying(36) NOT NULL,
+    username character varying(255) NOT NULL,
+    email character varying(255) NOT NULL,
+    password_hash character varying(255) NOT NULL,
+    full_name character varying(255),
+    role character varying(50) DEFAULT 'user'::character varying NOT NULL,
+    created_at timestamp with time zone DEFAULT now() NOT NULL,
+    updated_at timestamp with time zone DEFAULT now() NOT NULL,
+    is_active boolean DEFAULT true NOT NULL
+);
+
+ALTER SEQUENCE audit_events_sequence_seq OWNED BY audit_events.sequence;
+
+ALTER SEQUENCE health_check_results_id_seq OWNED BY health_check_results.id;
+
+ALTER SEQUENCE monitoring_metrics_id_seq OWNED BY monitoring_metrics.id;
+
+ALTER SEQUENCE performance_metrics_id_seq OWNED BY performance_metrics.id;
+
+ALTER SEQUENCE performance_snapshots_id_seq OWNED BY performance_snapshots.id;
+
+ALTER SEQUENCE rate_limit_events_id_seq OWNED BY rate_limit_events.id;
+
+COMMENT ON COLUMN customers.customer_type IS 'Customer type: individual, business, wholesale, or retail';
+
+ALTER TABLE audit_checkpoints ADD CONSTRAINT audit_checkpoints_pkey PRIMARY KEY (id);
+
+ALTER TABLE audit_checkpoints ADD CONSTRAINT audit_checkpoints_sequence_key UNIQUE (sequence);
+
+ALTER TABLE audit_events ADD CONSTRAINT audit_events_pkey PRIMARY KEY (id);
+
+ALTER TABLE customer_addresses ADD CONSTRAINT customer_addresses_pkey PRIMARY KEY (id);
+
+ALTER TABLE customers ADD CONSTRAINT customers_pkey PRIMARY KEY (id);
+
+ALTER TABLE customers ADD CONSTRAINT customers_email_key UNIQUE (email);
+
+ALTER TABLE health_check_results ADD CONSTRAINT health_check_results_pkey PRIMARY KEY (id);
+
+ALTER TABLE monitoring_alerts ADD CONSTRAINT monitoring_alerts_pkey PRIMARY KEY (id);
+
+ALTER TABLE monitoring_metrics ADD CONSTRAINT monitoring_metrics_pkey PRIMARY KEY (id);
+
+ALTER TABLE performance_metrics ADD CONSTRAINT performance_metrics_pkey PRIMARY KEY (id);
+
+ALTER TABLE performance_snapshots ADD CONSTRAINT performance_snapshots_pkey PRIMARY KEY (id);
+
+ALTER TABLE products ADD CONSTRAINT products_pkey PRIMARY KEY (id);
+
+ALTER TABLE products ADD CONSTRAINT products_sku_key UNIQUE (sku);
+
+ALTER TABLE rate_limit_events ADD CONSTRAINT rate_limit_events_pkey PRIMARY KEY (id);
+
+ALTER TABLE report_schedule_runs ADD CONSTRAINT report_schedule_runs_pkey PRIMARY KEY (id);
+
+ALTER TABLE report_schedules ADD CONSTRAINT report_schedules_pkey PRIMARY KEY (id);
+
+ALTER TABLE report_schedules ADD CONSTRAINT report_schedules_name_key UNIQUE (name);
+
+ALTER TABLE sales_order_items ADD CONSTRAINT sales_order_items_pkey PRIMARY KEY (id);
+
+ALTER TABLE sales_orders ADD CONSTRAINT sales_orders_pkey PRIMARY KEY (id);
+
+ALTER TABLE sales_orders ADD CONSTRAINT sales_orders_order_number_key UNIQUE (order_number);
+
+ALTER TABLE stock_movements ADD CONSTRAINT stock_movements_pkey PRIMARY KEY (id);
+
+ALTER TABLE users ADD CONSTRAINT users_pkey PRIMARY KEY (id);
+
+ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);
+
+ALTER TABLE users ADD CONSTRAINT users_username_key UNIQUE (username);
+
+ALTER TABLE customer_addresses ADD CONSTRAINT customer_addresses_customer_id_fkey FOREIGN KEY (customer_id) REFERENCES customers(id) ON DELETE CASCADE;
+
+ALTER TABLE report_schedule_runs ADD CONSTRAINT report_schedule_runs_schedule_id_fkey FOREIGN KEY (schedule_id) REFERENCES report_schedules(id) ON DELETE SET NULL;
+
+ALTER TABLE sales_order_items ADD CONSTRAINT sales_order_items_order_id_fkey FOREIGN KEY (order_id) REFERENCES sales_orders(id) ON DELETE CASCADE;
+
+ALTER TABLE sales_order_items ADD CONSTRAINT sales_order_items_product_id_fkey FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE RESTRICT;
+
+ALTER TABLE sales_orders ADD CONSTRAINT sales_orders_billing_address_id_fkey FOREIGN KEY (billing_address_id) REFERENCES customer_addresses(id) ON DELETE SET NULL;
+
+ALTER TABLE sales_orders ADD CONSTRAINT sales_orders_customer_id_fkey FOREIGN KEY (customer_id) REFERENCES customers(id) ON DELETE RESTRICT;
+
+ALTER TABLE sales_orders ADD CONSTRAINT sales_orders_shipping_address_id_fkey FOREIGN KEY (shipping_address_id) REFERENCES customer_addresses(id) ON DELETE SET NULL;
+
+ALTER TABLE stock_movements ADD CONSTRAINT stock_movements_product_id_fkey FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE;
+
+CREATE INDEX idx_audit_checkpoints_created_at ON public.audit_checkpoints USING btree (created_at);
+
+CREATE INDEX idx_audit_events_action ON public.audit_events USING btree (action);
+
+CREATE INDEX idx_audit_events_occurred_at ON public.audit_events USING btree (occurred_at DESC);
+
+CREATE INDEX idx_audit_events_resource ON public.audit_events USING btree (resource, resource_id);
+
+CREATE UNIQUE INDEX idx_audit_events_sequence ON public.audit_events USING btree (sequence);
+
+CREATE INDEX idx_audit_events_username ON public.audit_events USING btree (username);
+
+CREATE INDEX idx_customer_addresses_customer_id ON public.customer_addresses USING btree (customer_id);
+
+CREATE INDEX idx_customer_addresses_default ON public.customer_addresses USING btree (is_default);
+
+CREATE INDEX idx_customer_addresses_type ON public.customer_addresses USING btree (address_type);
+
+CREATE INDEX idx_customers_company ON public.customers USING btree (company);
+
+CREATE INDEX idx_customers_created_at ON public.customers USING btree (created_at);
+
+CREATE INDEX idx_customers_customer_code ON public.customers USING btree (customer_code);
+
+CREATE INDEX idx_customers_customer_type ON public.customers USING btree (customer_type);
+
+CREATE INDEX idx_customers_email ON public.customers USING btree (email);
+
+CREATE UNIQUE INDEX idx_customers_email_hash ON public.customers USING btree (email_hash);
+
+CREATE INDEX idx_customers_first_name ON public.customers USING btree (first_name);
+
+CREATE INDEX idx_customers_last_name ON public.customers USING btree (last_name);
+
+CREATE INDEX idx_customers_name ON public.customers USING btree (name);
+
+CREATE INDEX idx_customers_phone_hash ON public.customers USING btree (phone_hash);
+
+CREATE INDEX idx_customers_status ON public.customers USING btree (status);
+
+CREATE INDEX idx_customers_type ON public.customers USING btree (customer_type);
+
+CREATE INDEX idx_health_check_results_service ON public.health_check_results USING btree (service, checked_at DESC);
+
+CREATE INDEX idx_monitoring_alerts_resolved ON public.monitoring_alerts USING btree (resolved, raised_at DESC);
+
+CREATE INDEX idx_monitoring_metrics_kind_recorded_at ON public.monitoring_metrics USING btree (kind, recorded_at DESC);
+
+CREATE INDEX idx_performance_metrics_endpoint ON public.performance_metrics USING btree (endpoint, recorded_at DESC);
+
+CREATE INDEX idx_performance_metrics_recorded_at ON public.performance_metrics USING btree (recorded_at);
+
+CREATE INDEX idx_performance_snapshots_kind ON public.performance_snapshots USING btree (kind, recorded_at DESC);
+
+CREATE INDEX idx_products_active ON public.products USING btree (status);
+
+CREATE INDEX idx_products_barcode ON public.products USING btree (barcode);
+
+CREATE INDEX idx_products_category ON public.products USING btree (category);
+
+CREATE INDEX idx_products_created_at ON public.products USING btree (created_at);
+
+CREATE INDEX idx_products_low_stock ON public.products USING btree (quantity, min_stock_level);
+
+CREATE INDEX idx_products_name ON public.products USING btree (name);
+
+CREATE INDEX idx_products_sku ON public.products USING btree (sku);
+
+CREATE INDEX idx_rate_limit_events_identifier ON public.rate_limit_events USING btree (identifier, occurred_at DESC);
+
+CREATE INDEX idx_rate_limit_events_occurred_at ON public.rate_limit_events USING btree (occurred_at);
+
+CREATE INDEX idx_report_schedule_runs_name_started ON public.report_schedule_runs USING btree (schedule_name, started_at DESC);
+
+CREATE INDEX idx_report_schedules_next_run_at ON public.report_schedules USING btree (next_run_at) WHERE enabled;
+
+CREATE INDEX idx_sales_order_items_created_at ON public.sales_order_items USING btree (created_at);
+
+CREATE INDEX idx_sales_order_items_order_id ON public.sales_order_items USING btree (order_id);
+
+CREATE INDEX idx_sales_order_items_product_id ON public.sales_order_items USING btree (product_id);
+
+CREATE INDEX idx_sales_orders_created_at ON public.sales_orders USING btree (created_at);
+
+CREATE INDEX idx_sales_orders_customer_id ON public.sales_orders USING btree (customer_id);
+
+CREATE INDEX idx_sales_orders_order_date ON public.sales_orders USING btree (order_date);
+
+CREATE INDEX idx_sales_orders_order_number ON public.sales_orders USING btree (order_number);
+
+CREATE INDEX idx_sales_orders_payment_status ON public.sales_orders USING btree (payment_status);
+
+CREATE INDEX idx_sales_orders_status ON public.sales_orders USING btree (status);
+
+CREATE INDEX idx_stock_movements_created_at ON public.stock_movements USING btree (created_at);
+
+CREATE INDEX idx_stock_movements_product_id ON public.stock_movements USING btree (product_id);
+
+CREATE INDEX idx_stock_movements_reference ON public.stock_movements USING btree (reference_id);
+
+CREATE INDEX idx_stock_movements_type ON public.stock_movements USING btree (movement_type);
+
+CREATE INDEX idx_users_active ON public.users USING btree (is_active);
+
+CREATE INDEX idx_users_created_at ON public.users USING btree (created_at);
+
+CREATE INDEX idx_users_email ON public.users USING btree (email);
+
+CREATE INDEX idx_users_role ON public.users USING btree (role);
+
+CREATE INDEX idx_users_username ON public.users USING btree (username);
@@ -0,0 +1,38 @@
+-- Migration: restore_uuid_foreign_keys
+-- Version: 022
+-- Description: Restore the product status default and the foreign keys migrations 009 and 010 leave behind when they change column types
+
+-- Safe to run on every database: fresh installs already have this from the
+-- baseline, upgraded ones get back what the type changes dropped
+ALTER TABLE products ALTER COLUMN status SET DEFAULT 'active';
+
+ALTER TABLE customer_addresses DROP CONSTRAINT IF EXISTS customer_addresses_customer_id_fkey;
+ALTER TABLE customer_addresses ADD CONSTRAINT customer_addresses_customer_id_fkey
+    FOREIGN KEY (customer_id) REFERENCES customers(id) ON DELETE CASCADE;
+
+ALTER TABLE sales_orders DROP CONSTRAINT IF EXISTS sales_orders_customer_id_fkey;
+ALTER TABLE sales_orders ADD CONSTRAINT sales_orders_customer_id_fkey
+    FOREIGN KEY (customer_id) REFERENCES customers(id) ON DELETE RESTRICT;
+
+ALTER TABLE sales_orders DROP CONSTRAINT IF EXISTS sales_orders_shipping_address_id_fkey;
+ALTER TABLE sales_orders ADD CONSTRAINT sales_orders_shipping_address_id_fkey
+    FOREIGN KEY (shipping_address_id) REFERENCES customer_addresses(id) ON DELETE SET NULL;
+
+ALTER TABLE sales_orders DROP CONSTRAINT IF EXISTS sales_orders_billing_address_id_fkey;
+ALTER TABLE sales_orders ADD CONSTRAINT sales_orders_billing_address_id_fkey
+    FOREIGN KEY (billing_address_id) REFERENCES customer_addresses(id) ON DELETE SET NULL;
+
+ALTER TABLE sales_order_items DROP CONSTRAINT IF EXISTS sales_order_items_order_id_fkey;
+ALTER TABLE sales_order_items ADD CONSTRAINT sales_order_items_order_id_fkey
+    FOREIGN KEY (order_id) REFERENCES sales_orders(id) ON DELETE CASCADE;
+
+ALTER TABLE sales_order_items DROP CONSTRAINT IF EXISTS sales_order_items_product_id_fkey;
+ALTER TABLE sales_order_items ADD CONSTRAINT sales_order_items_product_id_fkey
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE RESTRICT;
+
+ALTER TABLE stock_movements DROP CONSTRAINT IF EXISTS stock_movements_product_id_fkey;
+ALTER TABLE stock_movements ADD CONSTRAINT stock_movements_product_id_fkey
+    FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE;
+
+-- DOWN
+-- The constraints and default match the baseline schema; nothing to revert
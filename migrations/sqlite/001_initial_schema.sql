@@ -0,0 +1,144 @@
+-- SQLite schema
+-- Version: 001
+-- Description: Offline single-file schema for inventory, customers, sales and config
+--
+-- UUIDs are stored as 16-byte BLOBs, timestamps as RFC 3339 text and money
+-- as decimal text (SQLite has no exact numeric type), so every value
+-- round-trips through the Rust models unchanged.
+
+CREATE TABLE IF NOT EXISTS products (
+    id BLOB PRIMARY KEY,
+    sku TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    name TEXT NOT NULL,
+    description TEXT,
+    category TEXT NOT NULL DEFAULT 'general',
+    price TEXT NOT NULL,
+    cost TEXT NOT NULL DEFAULT '0',
+    quantity INTEGER NOT NULL DEFAULT 0,
+    min_stock_level INTEGER NOT NULL DEFAULT 0,
+    max_stock_level INTEGER,
+    status TEXT NOT NULL DEFAULT 'active',
+    is_taxable INTEGER NOT NULL DEFAULT 1,
+    weight TEXT,
+    dimensions TEXT,
+    barcode TEXT,
+    supplier_id BLOB,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_products_category ON products(category);
+CREATE INDEX IF NOT EXISTS idx_products_status ON products(status);
+CREATE INDEX IF NOT EXISTS idx_products_low_stock ON products(quantity, min_stock_level);
+
+CREATE TABLE IF NOT EXISTS stock_movements (
+    id BLOB PRIMARY KEY,
+    product_id BLOB NOT NULL,
+    movement_type TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    reason TEXT,
+    reference_id BLOB,
+    user_id BLOB,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_stock_movements_product_id ON stock_movements(product_id);
+CREATE INDEX IF NOT EXISTS idx_stock_movements_created_at ON stock_movements(created_at);
+
+CREATE TABLE IF NOT EXISTS customers (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL,
+    email TEXT UNIQUE,
+    phone TEXT,
+    customer_type TEXT NOT NULL DEFAULT 'individual',
+    company TEXT,
+    tax_id TEXT,
+    credit_limit TEXT DEFAULT '0',
+    current_balance TEXT DEFAULT '0',
+    notes TEXT,
+    status TEXT DEFAULT 'active',
+    email_hash TEXT UNIQUE,
+    phone_hash TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_customers_name ON customers(name);
+CREATE INDEX IF NOT EXISTS idx_customers_type ON customers(customer_type);
+CREATE INDEX IF NOT EXISTS idx_customers_status ON customers(status);
+CREATE INDEX IF NOT EXISTS idx_customers_phone_hash ON customers(phone_hash);
+
+CREATE TABLE IF NOT EXISTS customer_addresses (
+    id BLOB PRIMARY KEY,
+    customer_id BLOB NOT NULL REFERENCES customers(id) ON DELETE CASCADE,
+    address_type TEXT NOT NULL DEFAULT 'billing',
+    address_line1 TEXT NOT NULL,
+    address_line2 TEXT,
+    city TEXT,
+    state_province TEXT,
+    postal_code TEXT,
+    country TEXT DEFAULT 'US',
+    is_default INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_customer_addresses_customer_id ON customer_addresses(customer_id);
+
+CREATE TABLE IF NOT EXISTS sales_orders (
+    id BLOB PRIMARY KEY,
+    order_number TEXT NOT NULL UNIQUE,
+    customer_id BLOB NOT NULL REFERENCES customers(id),
+    order_date TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'draft',
+    total_amount TEXT NOT NULL DEFAULT '0',
+    tax_amount TEXT NOT NULL DEFAULT '0',
+    discount_amount TEXT NOT NULL DEFAULT '0',
+    shipping_address TEXT,
+    billing_address TEXT,
+    payment_method TEXT,
+    payment_status TEXT NOT NULL DEFAULT 'pending',
+    notes TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sales_orders_customer_id ON sales_orders(customer_id);
+CREATE INDEX IF NOT EXISTS idx_sales_orders_order_date ON sales_orders(order_date);
+CREATE INDEX IF NOT EXISTS idx_sales_orders_status ON sales_orders(status);
+
+CREATE TABLE IF NOT EXISTS sales_order_items (
+    id BLOB PRIMARY KEY,
+    order_id BLOB NOT NULL REFERENCES sales_orders(id) ON DELETE CASCADE,
+    product_id BLOB NOT NULL REFERENCES products(id),
+    quantity INTEGER NOT NULL,
+    unit_price TEXT NOT NULL,
+    discount TEXT NOT NULL DEFAULT '0',
+    line_total TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sales_order_items_order_id ON sales_order_items(order_id);
+CREATE INDEX IF NOT EXISTS idx_sales_order_items_product_id ON sales_order_items(product_id);
+
+CREATE TABLE IF NOT EXISTS config_items (
+    id BLOB PRIMARY KEY,
+    key TEXT NOT NULL UNIQUE,
+    value TEXT NOT NULL,
+    description TEXT,
+    category TEXT NOT NULL DEFAULT 'general',
+    is_secret INTEGER NOT NULL DEFAULT 0,
+    is_readonly INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_config_category ON config_items(category);
+
+-- DOWN
+DROP TABLE IF EXISTS config_items;
+DROP TABLE IF EXISTS sales_order_items;
+DROP TABLE IF EXISTS sales_orders;
+DROP TABLE IF EXISTS customer_addresses;
+DROP TABLE IF EXISTS customers;
+DROP TABLE IF EXISTS stock_movements;
+DROP TABLE IF EXISTS products;
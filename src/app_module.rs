@@ -2,31 +2,117 @@
 //!
 //! Shaku 기반 의존성 주입 컨테이너를 정의합니다.
 //! 모든 Repository와 Service를 Components로 등록하고 관리합니다.
+//!
+//! - [`AppModule`]: 연결된 데이터베이스(PostgreSQL/SQLite)에 맞는 저장소를 사용
+//! - [`TestAppModule`]: `Mock*Repository` 구현체를 사용하는 테스트용 모듈
+//!
+//! 구조체 서비스(`CustomerService` 등)는 `*ServiceProvider` 인터페이스를 통해
+//! 주입된 저장소로 구성된 인스턴스를 제공합니다.
 
-use shaku::{Component, Interface};
-use sqlx::{Pool, Postgres};
+use shaku::{module, Component, HasComponent, Interface, Module, ModuleBuildContext};
 use std::sync::Arc;
 
-use crate::core::database::connection::DatabaseManager;
+use crate::core::cache::QueryCache;
+use crate::core::config::AppConfig;
+use crate::core::database::connection::{
+    BackendPool, DatabaseConnection, DatabaseManager, ReadPools,
+};
+use crate::core::ops::performance::{
+    MockPerformanceRepository, PerformanceRepository, PostgresPerformanceRepository,
+};
+use crate::core::security::audit::{AuditRepository, MockAuditRepository, PostgresAuditRepository};
+use crate::core::security::monitor::{
+    MetricsRepository, MockMetricsRepository, PostgresMetricsRepository,
+};
+use crate::core::security::pii::PiiCipher;
+use crate::modules::config::repository::ConfigRepositoryTrait;
+use crate::modules::config::{
+    open_config_repository, ConfigRepository, ConfigService, MockConfigRepository,
+    SqliteConfigRepository,
+};
+use crate::modules::customers::{
+    CustomerRepository, CustomerService, MockCustomerRepository, PostgresCustomerRepository,
+    SqliteCustomerRepository,
+};
+use crate::modules::inventory::{
+    InventoryRepository, InventoryService, InventoryServiceImpl, MockInventoryRepository,
+    PostgresInventoryRepository, SqliteInventoryRepository,
+};
+use crate::modules::reports::{
+    MockReportScheduleRepository, MockReportsRepository, PostgresReportScheduleRepository,
+    ReportScheduleRepository, ReportsRepository, ReportsService, SqliteReportsRepository,
+};
+use crate::modules::sales::{
+    MockSalesRepository, PostgresSalesRepository, SalesRepository, SalesService,
+    SqliteSalesRepository,
+};
 use crate::utils::error::ErpResult;
 use crate::utils::validation::ValidationService;
 
-/// DatabasePool을 Component로 래핑
+/// DatabaseConnection을 Component로 래핑
 ///
-/// Shaku는 동기 생성만 지원하므로, 미리 초기화된 Pool을 받습니다.
+/// Shaku는 동기 생성만 지원하므로, 미리 초기화된 연결을 받습니다.
 #[derive(Component)]
 #[shaku(interface = DatabasePoolProvider)]
 pub struct DatabasePoolProviderImpl {
-    pool: Arc<Pool<Postgres>>,
+    connection: Arc<DatabaseConnection>,
+    pii_cipher: Option<Arc<PiiCipher>>,
 }
 
 pub trait DatabasePoolProvider: Interface {
-    fn get_pool(&self) -> Arc<Pool<Postgres>>;
+    fn connection(&self) -> Arc<DatabaseConnection>;
+    /// 고객 PII 암호화 키 (설정에서 비활성화된 경우 `None`)
+    fn pii_cipher(&self) -> Option<Arc<PiiCipher>>;
 }
 
 impl DatabasePoolProvider for DatabasePoolProviderImpl {
-    fn get_pool(&self) -> Arc<Pool<Postgres>> {
-        self.pool.clone()
+    fn connection(&self) -> Arc<DatabaseConnection> {
+        self.connection.clone()
+    }
+
+    fn pii_cipher(&self) -> Option<Arc<PiiCipher>> {
+        self.pii_cipher.clone()
+    }
+}
+
+/// 조회 결과 캐시 Component
+///
+/// 기본값은 비활성화된 캐시이며, [`AppModuleBuilder::build_for`]가
+/// `[cache]` 설정으로 생성한 캐시를 주입합니다.
+#[derive(Component)]
+#[shaku(interface = CacheProvider)]
+pub struct CacheProviderImpl {
+    #[shaku(default)]
+    cache: QueryCache,
+}
+
+pub trait CacheProvider: Interface {
+    fn cache(&self) -> QueryCache;
+}
+
+impl CacheProvider for CacheProviderImpl {
+    fn cache(&self) -> QueryCache {
+        self.cache.clone()
+    }
+}
+
+/// 애플리케이션 설정 Component
+///
+/// 서비스가 읽는 설정 섹션(`sales` 등)을 제공합니다. 테스트 모듈에서는 기본값을 사용합니다.
+#[derive(Component)]
+#[shaku(interface = AppConfigProvider)]
+pub struct AppConfigProviderImpl {
+    #[shaku(default)]
+    config: AppConfig,
+}
+
+pub trait AppConfigProvider: Interface {
+    fn config(&self) -> &AppConfig;
+}
+
+impl AppConfigProvider for AppConfigProviderImpl {
+    fn config(&self) -> &AppConfig {
+        &self.config
     }
 }
 
@@ -45,32 +131,497 @@ impl ValidationServiceProvider for ValidationServiceProviderImpl {
     }
 }
 
-// Application Module 정의는 나중에 각 Repository/Service를
-// Component로 변환한 후 추가할 예정
+/// 연결된 데이터베이스 종류에 맞는 재고 저장소 (목록 조회는 읽기 복제본 사용)
+pub struct InventoryRepositoryComponent;
+
+impl<M: Module + HasComponent<dyn DatabasePoolProvider>> Component<M>
+    for InventoryRepositoryComponent
+{
+    type Interface = dyn InventoryRepository;
+    type Parameters = ();
+
+    fn build(context: &mut ModuleBuildContext<M>, _: ()) -> Box<dyn InventoryRepository> {
+        let database = M::build_component(context);
+        match database.connection().read_pools() {
+            ReadPools::Postgres(reads) => Box::new(
+                PostgresInventoryRepository::new(reads.primary().clone())
+                    .with_read_router(reads.clone()),
+            ),
+            ReadPools::Sqlite(reads) => Box::new(
+                SqliteInventoryRepository::new(reads.primary().clone())
+                    .with_read_router(reads.clone()),
+            ),
+        }
+    }
+}
+
+/// 연결된 데이터베이스 종류에 맞는 고객 저장소 (설정에 따라 PII 암호화 적용,
+/// 목록/검색은 읽기 복제본 사용)
+pub struct CustomerRepositoryComponent;
+
+impl<M: Module + HasComponent<dyn DatabasePoolProvider>> Component<M>
+    for CustomerRepositoryComponent
+{
+    type Interface = dyn CustomerRepository;
+    type Parameters = ();
+
+    fn build(context: &mut ModuleBuildContext<M>, _: ()) -> Box<dyn CustomerRepository> {
+        let database = M::build_component(context);
+        let cipher = database.pii_cipher();
+        match database.connection().read_pools() {
+            ReadPools::Postgres(reads) => Box::new(
+                PostgresCustomerRepository::new(Arc::new(reads.primary().clone()))
+                    .with_read_router(reads.clone())
+                    .with_pii_cipher(cipher),
+            ),
+            ReadPools::Sqlite(reads) => Box::new(
+                SqliteCustomerRepository::new(reads.primary().clone())
+                    .with_read_router(reads.clone())
+                    .with_pii_cipher(cipher),
+            ),
+        }
+    }
+}
+
+/// 연결된 데이터베이스 종류에 맞는 영업 저장소 (목록/검색은 읽기 복제본 사용)
+pub struct SalesRepositoryComponent;
+
+impl<M: Module + HasComponent<dyn DatabasePoolProvider>> Component<M> for SalesRepositoryComponent {
+    type Interface = dyn SalesRepository;
+    type Parameters = ();
+
+    fn build(context: &mut ModuleBuildContext<M>, _: ()) -> Box<dyn SalesRepository> {
+        let database = M::build_component(context);
+        match database.connection().read_pools() {
+            ReadPools::Postgres(reads) => Box::new(
+                PostgresSalesRepository::new(reads.primary().clone())
+                    .with_read_router(reads.clone()),
+            ),
+            ReadPools::Sqlite(reads) => Box::new(
+                SqliteSalesRepository::new(reads.primary().clone()).with_read_router(reads.clone()),
+            ),
+        }
+    }
+}
+
+/// 연결된 데이터베이스 종류에 맞는 설정 저장소
+///
+/// `config_items` 테이블은 [`AppModuleBuilder::build_for`]에서 미리 준비합니다.
+pub struct ConfigRepositoryComponent;
+
+impl<M: Module + HasComponent<dyn DatabasePoolProvider>> Component<M>
+    for ConfigRepositoryComponent
+{
+    type Interface = dyn ConfigRepositoryTrait;
+    type Parameters = ();
+
+    fn build(context: &mut ModuleBuildContext<M>, _: ()) -> Box<dyn ConfigRepositoryTrait> {
+        let database = M::build_component(context);
+        let connection = database.connection();
+        match connection.backend_pool() {
+            BackendPool::Postgres(_) => Box::new(ConfigRepository::new(connection.clone())),
+            BackendPool::Sqlite(pool) => Box::new(SqliteConfigRepository::new(pool.clone())),
+        }
+    }
+}
+
+/// 보고서 저장소
+///
+/// SQLite 연결은 실제 데이터를 집계하고, PostgreSQL은 아직 Mock 데이터를 사용합니다.
+pub struct ReportsRepositoryComponent;
+
+impl<M> Component<M> for ReportsRepositoryComponent
+where
+    M: Module + HasComponent<dyn DatabasePoolProvider> + HasComponent<dyn CustomerRepository>,
+{
+    type Interface = dyn ReportsRepository;
+    type Parameters = ();
+
+    fn build(context: &mut ModuleBuildContext<M>, _: ()) -> Box<dyn ReportsRepository> {
+        let database: Arc<dyn DatabasePoolProvider> = M::build_component(context);
+        match database.connection().read_pools() {
+            ReadPools::Sqlite(reads) => {
+                let customers: Arc<dyn CustomerRepository> = M::build_component(context);
+                Box::new(
+                    SqliteReportsRepository::new(reads.primary().clone(), customers)
+                        .with_read_router(reads.clone()),
+                )
+            }
+            ReadPools::Postgres(_) => Box::new(MockReportsRepository::new()),
+        }
+    }
+}
+
+/// PostgreSQL 전용 저장소 Component
+///
+/// SQLite 스키마에는 해당 테이블이 없어 Mock 저장소를 주입합니다.
+/// 이 저장소를 사용하는 명령어는 [`AppModuleBuilder::build_postgres`]로 모듈을 빌드합니다.
+macro_rules! postgres_component {
+    ($(#[$meta:meta])* $component:ident, $postgres:ty, $mock:ty, $interface:ty) => {
+        $(#[$meta])*
+        pub struct $component;
+
+        impl<M: Module + HasComponent<dyn DatabasePoolProvider>> Component<M> for $component {
+            type Interface = $interface;
+            type Parameters = ();
+
+            fn build(context: &mut ModuleBuildContext<M>, _: ()) -> Box<$interface> {
+                let database: Arc<dyn DatabasePoolProvider> = M::build_component(context);
+                match database.connection().backend_pool() {
+                    BackendPool::Postgres(pool) => Box::new(<$postgres>::new(pool.clone())),
+                    BackendPool::Sqlite(_) => Box::new(<$mock>::new()),
+                }
+            }
+        }
+    };
+}
+
+postgres_component!(
+    /// 감사 로그 저장소
+    AuditRepositoryComponent,
+    PostgresAuditRepository,
+    MockAuditRepository,
+    dyn AuditRepository
+);
+postgres_component!(
+    /// 시스템 메트릭/알림 저장소
+    MetricsRepositoryComponent,
+    PostgresMetricsRepository,
+    MockMetricsRepository,
+    dyn MetricsRepository
+);
+postgres_component!(
+    /// 명령 실행 성능 기록 저장소
+    PerformanceRepositoryComponent,
+    PostgresPerformanceRepository,
+    MockPerformanceRepository,
+    dyn PerformanceRepository
+);
+postgres_component!(
+    /// 보고서 예약 저장소
+    ReportScheduleRepositoryComponent,
+    PostgresReportScheduleRepository,
+    MockReportScheduleRepository,
+    dyn ReportScheduleRepository
+);
+
+impl<M> Component<M> for InventoryServiceImpl
+where
+    M: Module + HasComponent<dyn InventoryRepository> + HasComponent<dyn CacheProvider>,
+{
+    type Interface = dyn InventoryService;
+    type Parameters = ();
+
+    fn build(context: &mut ModuleBuildContext<M>, _: ()) -> Box<dyn InventoryService> {
+        let cache: Arc<dyn CacheProvider> = M::build_component(context);
+        Box::new(InventoryServiceImpl::new(M::build_component(context)).with_cache(cache.cache()))
+    }
+}
+
+/// CustomerService Component
+#[derive(Component)]
+#[shaku(interface = CustomerServiceProvider)]
+pub struct CustomerServiceProviderImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn CustomerRepository>,
+    #[shaku(inject)]
+    cache: Arc<dyn CacheProvider>,
+}
+
+pub trait CustomerServiceProvider: Interface {
+    fn get_service(&self) -> CustomerService;
+}
+
+impl CustomerServiceProvider for CustomerServiceProviderImpl {
+    fn get_service(&self) -> CustomerService {
+        CustomerService::new(self.repository.clone()).with_cache(self.cache.cache())
+    }
+}
+
+/// ConfigService Component
+#[derive(Component)]
+#[shaku(interface = ConfigServiceProvider)]
+pub struct ConfigServiceProviderImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn ConfigRepositoryTrait>,
+    #[shaku(inject)]
+    cache: Arc<dyn CacheProvider>,
+    #[shaku(inject)]
+    settings: Arc<dyn AppConfigProvider>,
+}
+
+pub trait ConfigServiceProvider: Interface {
+    fn get_service(&self) -> ConfigService;
+}
+
+impl ConfigServiceProvider for ConfigServiceProviderImpl {
+    fn get_service(&self) -> ConfigService {
+        ConfigService::new(self.repository.clone())
+            .with_cache(self.cache.cache())
+            .with_default_tax_rate(self.settings.config().sales.default_tax_rate_percent())
+    }
+}
+
+/// ReportsService Component
+#[derive(Component)]
+#[shaku(interface = ReportsServiceProvider)]
+pub struct ReportsServiceProviderImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn ReportsRepository>,
+    #[shaku(inject)]
+    cache: Arc<dyn CacheProvider>,
+}
+
+pub trait ReportsServiceProvider: Interface {
+    fn get_service(&self) -> ReportsService;
+}
+
+impl ReportsServiceProvider for ReportsServiceProviderImpl {
+    fn get_service(&self) -> ReportsService {
+        ReportsService::new(self.repository.clone()).with_cache(self.cache.cache())
+    }
+}
+
+/// SalesService Component
+///
+/// 고객 확인, 재고 조회, 세율 설정까지 연결된 서비스를 제공합니다.
+#[derive(Component)]
+#[shaku(interface = SalesServiceProvider)]
+pub struct SalesServiceProviderImpl {
+    #[shaku(inject)]
+    repository: Arc<dyn SalesRepository>,
+    #[shaku(inject)]
+    customers: Arc<dyn CustomerServiceProvider>,
+    #[shaku(inject)]
+    inventory: Arc<dyn InventoryService>,
+    #[shaku(inject)]
+    config: Arc<dyn ConfigServiceProvider>,
+    #[shaku(inject)]
+    validation: Arc<dyn ValidationServiceProvider>,
+    #[shaku(inject)]
+    cache: Arc<dyn CacheProvider>,
+    #[shaku(inject)]
+    settings: Arc<dyn AppConfigProvider>,
+}
+
+pub trait SalesServiceProvider: Interface {
+    fn get_service(&self) -> SalesService;
+}
+
+impl SalesServiceProvider for SalesServiceProviderImpl {
+    fn get_service(&self) -> SalesService {
+        SalesService::new(self.repository.clone(), self.validation.get_service())
+            .with_config_service(self.config.get_service())
+            .with_customer_service(self.customers.get_service())
+            .with_inventory_service(self.inventory.clone())
+            .with_cache(self.cache.cache())
+            .with_settings(self.settings.config().sales.clone())
+    }
+}
+
+module! {
+    pub AppModule {
+        components = [
+            DatabasePoolProviderImpl,
+            AppConfigProviderImpl,
+            CacheProviderImpl,
+            ValidationServiceProviderImpl,
+            InventoryRepositoryComponent,
+            CustomerRepositoryComponent,
+            SalesRepositoryComponent,
+            ConfigRepositoryComponent,
+            ReportsRepositoryComponent,
+            AuditRepositoryComponent,
+            MetricsRepositoryComponent,
+            PerformanceRepositoryComponent,
+            ReportScheduleRepositoryComponent,
+            InventoryServiceImpl,
+            CustomerServiceProviderImpl,
+            ConfigServiceProviderImpl,
+            ReportsServiceProviderImpl,
+            SalesServiceProviderImpl,
+        ],
+        providers = []
+    }
+}
+
+macro_rules! mock_component {
+    ($mock:ty, $interface:ty) => {
+        impl<M: Module> Component<M> for $mock {
+            type Interface = $interface;
+            type Parameters = ();
+
+            fn build(_: &mut ModuleBuildContext<M>, _: ()) -> Box<$interface> {
+                Box::new(<$mock>::new())
+            }
+        }
+    };
+}
+
+mock_component!(MockInventoryRepository, dyn InventoryRepository);
+mock_component!(MockCustomerRepository, dyn CustomerRepository);
+mock_component!(MockSalesRepository, dyn SalesRepository);
+mock_component!(MockConfigRepository, dyn ConfigRepositoryTrait);
+mock_component!(MockReportsRepository, dyn ReportsRepository);
+mock_component!(MockAuditRepository, dyn AuditRepository);
+mock_component!(MockMetricsRepository, dyn MetricsRepository);
+mock_component!(MockPerformanceRepository, dyn PerformanceRepository);
+mock_component!(MockReportScheduleRepository, dyn ReportScheduleRepository);
+
+module! {
+    pub TestAppModule {
+        components = [
+            AppConfigProviderImpl,
+            CacheProviderImpl,
+            ValidationServiceProviderImpl,
+            MockInventoryRepository,
+            MockCustomerRepository,
+            MockSalesRepository,
+            MockConfigRepository,
+            MockReportsRepository,
+            MockAuditRepository,
+            MockMetricsRepository,
+            MockPerformanceRepository,
+            MockReportScheduleRepository,
+            InventoryServiceImpl,
+            CustomerServiceProviderImpl,
+            ConfigServiceProviderImpl,
+            ReportsServiceProviderImpl,
+            SalesServiceProviderImpl,
+        ],
+        providers = []
+    }
+}
 
 /// AppModule Builder를 위한 헬퍼
 ///
-/// async 초기화가 필요한 DatabasePool을 처리합니다.
+/// async 초기화가 필요한 연결과 설정 테이블을 처리합니다.
 pub struct AppModuleBuilder;
 
 impl AppModuleBuilder {
     /// DatabaseManager에서 연결을 가져와 Module을 빌드합니다.
-    pub async fn build() -> ErpResult<AppModuleContainer> {
+    pub async fn build(config: &AppConfig) -> ErpResult<AppModule> {
         let connection = DatabaseManager::get_connection().await?;
-        let pool = Arc::new(connection.pool().clone());
+        Self::build_for(config, connection).await
+    }
 
-        // 임시: Module은 나중에 정의
-        Ok(AppModuleContainer { pool })
+    /// PostgreSQL 전용 명령어를 위한 Module을 빌드합니다.
+    ///
+    /// SQLite 연결이면 Mock 저장소로 대체하지 않고 오류를 반환합니다.
+    pub async fn build_postgres(config: &AppConfig) -> ErpResult<AppModule> {
+        let connection = DatabaseManager::get_connection().await?;
+        connection.pool()?;
+        Self::build_for(config, connection).await
     }
-}
 
-/// 임시 Container 구조체 (Module 정의 전까지 사용)
-pub struct AppModuleContainer {
-    pool: Arc<Pool<Postgres>>,
+    /// 주어진 연결로 Module을 빌드합니다.
+    pub async fn build_for(
+        config: &AppConfig,
+        connection: Arc<DatabaseConnection>,
+    ) -> ErpResult<AppModule> {
+        let pii_cipher = PiiCipher::from_config(&config.security)?;
+        let cache = QueryCache::from_config(config)?;
+
+        // 설정 테이블 생성은 비동기라 컴포넌트 생성 전에 수행
+        open_config_repository(connection.clone()).await?;
+
+        Ok(AppModule::builder()
+            .with_component_parameters::<DatabasePoolProviderImpl>(
+                DatabasePoolProviderImplParameters {
+                    connection,
+                    pii_cipher,
+                },
+            )
+            .with_component_parameters::<AppConfigProviderImpl>(AppConfigProviderImplParameters {
+                config: config.clone(),
+            })
+            .with_component_parameters::<CacheProviderImpl>(CacheProviderImplParameters { cache })
+            .build())
+    }
+
+    /// Mock 저장소로 구성된 테스트용 Module을 빌드합니다.
+    pub fn build_test() -> TestAppModule {
+        TestAppModule::builder().build()
+    }
 }
 
-impl AppModuleContainer {
-    pub fn get_pool(&self) -> Arc<Pool<Postgres>> {
-        self.pool.clone()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::DatabaseConfig;
+    use crate::modules::customers::{CreateCustomerRequest, CustomerType};
+
+    fn customer_request() -> CreateCustomerRequest {
+        CreateCustomerRequest {
+            first_name: "Minji".to_string(),
+            last_name: "Park".to_string(),
+            company_name: None,
+            email: "minji.park@example.com".to_string(),
+            phone: None,
+            customer_type: CustomerType::Individual,
+            credit_limit: None,
+            tax_id: None,
+            notes: None,
+            addresses: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_test_module_shares_mock_repositories_between_services() {
+        let module = AppModuleBuilder::build_test();
+
+        let customers =
+            HasComponent::<dyn CustomerServiceProvider>::resolve_ref(&module).get_service();
+        let created = customers.create_customer(customer_request()).await.unwrap();
+
+        // 다른 서비스가 같은 Mock 저장소 싱글턴을 보는지 확인
+        let repository: Arc<dyn CustomerRepository> = module.resolve();
+        assert!(repository
+            .get_customer_by_id(created.id)
+            .await
+            .unwrap()
+            .is_some());
+
+        let sales = HasComponent::<dyn SalesServiceProvider>::resolve_ref(&module).get_service();
+        assert!(sales
+            .get_orders_by_customer(created.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_app_module_wires_sqlite_repositories() {
+        let connection = DatabaseConnection::new(DatabaseConfig {
+            url: "sqlite::memory:".to_string(),
+            max_connections: 1,
+            migrate_on_start: true,
+            query_timeout_seconds: 5,
+            idle_timeout_seconds: 60,
+            replica_urls: Vec::new(),
+            enable_query_logging: false,
+        })
+        .await
+        .unwrap();
+        let mut app_config = AppConfig::default();
+        app_config.security.encrypt_sensitive_data = false;
+        let module = AppModuleBuilder::build_for(&app_config, Arc::new(connection))
+            .await
+            .unwrap();
+
+        let config = HasComponent::<dyn ConfigServiceProvider>::resolve_ref(&module).get_service();
+        assert!(config.list_configs(None).await.unwrap().is_empty());
+
+        let inventory: Arc<dyn InventoryService> = module.resolve();
+        let valuation = inventory.get_inventory_valuation().await.unwrap();
+        assert_eq!(valuation.total_items, 0);
+
+        // redis_url이 비어 있으면 프로세스 내 LRU 캐시 사용
+        let cache = HasComponent::<dyn CacheProvider>::resolve_ref(&module).cache();
+        assert_eq!(cache.backend_name(), "memory");
+
+        let test_module = AppModuleBuilder::build_test();
+        let cache = HasComponent::<dyn CacheProvider>::resolve_ref(&test_module).cache();
+        assert!(!cache.is_enabled());
     }
 }
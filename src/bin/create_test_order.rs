@@ -16,7 +16,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize database connection
     DatabaseManager::initialize(config.database.clone()).await?;
     let connection = DatabaseManager::get_connection().await?;
-    let pool = connection.pool();
+    let pool = connection.pool()?;
 
     // Test data
     let order_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440001")?;
@@ -0,0 +1,646 @@
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use serde::Serialize;
+use shaku::HasComponent;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::app_module::{AppModule, AppModuleBuilder, DatabasePoolProvider};
+use crate::cli::parser::{AuditCheckpointCommands, AuditCommands};
+use crate::cli::validator::CliValidator;
+use crate::core::config::AppConfig;
+use crate::core::database::connection::DatabaseBackend;
+use crate::core::security::audit::{
+    current_username, diff_details, AuditAction, AuditConfig, AuditEvent, AuditFilters,
+    AuditRepository, AuditService,
+};
+use crate::core::security::audit_chain::{AuditCheckpoint, AuditCheckpointFile, ChainReport};
+use crate::utils::error::{ErpError, ErpResult};
+
+/// 상세 표시 시 값 최대 길이
+const MAX_DETAIL_VALUE_LEN: usize = 60;
+
+/// 설정 기반 감사 서비스 생성
+pub(crate) fn audit_service(
+    config: &AppConfig,
+    repository: Arc<dyn AuditRepository>,
+) -> AuditService {
+    let audit_config = AuditConfig {
+        enabled: config.security.enable_audit_log,
+        retention_days: config.security.audit_retention_days,
+        checkpoint_interval: config.security.audit_checkpoint_interval,
+        signing_key: audit_signing_key(config),
+        ..AuditConfig::default()
+    };
+    AuditService::new(repository, audit_config)
+}
+
+/// 체크포인트 서명 키 (security.audit_signing_key, 없으면 auth.jwt_secret)
+fn audit_signing_key(config: &AppConfig) -> Option<String> {
+    [
+        config.security.audit_signing_key.as_deref(),
+        Some(config.auth.jwt_secret.as_str()),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|key| !key.is_empty())
+    .map(str::to_string)
+}
+
+/// CLI 명령어 실행 결과를 감사 로그로 남기는 도우미
+///
+/// 명령어를 실행하기 전에 만들어 두며, 감사 저장소를 열지 못하거나 기록에
+/// 실패해도 명령어를 실패시키지 않고 경고로만 남긴다.
+/// 감사 로그 저장소는 PostgreSQL 전용이므로 SQLite에서는 기록하지 않는다.
+pub(crate) struct CommandAudit {
+    service: Option<AuditService>,
+    username: String,
+}
+
+impl CommandAudit {
+    /// 명령어가 이미 빌드한 Module의 감사 저장소를 사용
+    pub(crate) fn from_module(config: &AppConfig, module: &AppModule) -> Self {
+        let database: &dyn DatabasePoolProvider = module.resolve_ref();
+        let service = match database.connection().backend() {
+            DatabaseBackend::Postgres => Some(audit_service(config, module.resolve())),
+            DatabaseBackend::Sqlite => {
+                if config.security.enable_audit_log {
+                    warn!("SQLite 데이터베이스에서는 감사 로그가 기록되지 않습니다");
+                }
+                None
+            }
+        };
+        Self {
+            service,
+            username: current_username(),
+        }
+    }
+
+    /// Module 없이 실행되는 명령어(백업, 데이터 내보내기 등)용
+    pub(crate) async fn new(config: &AppConfig) -> Self {
+        match AppModuleBuilder::build(config).await {
+            Ok(module) => Self::from_module(config, &module),
+            Err(e) => {
+                warn!("감사 로그 저장소를 열 수 없어 기록하지 않습니다: {}", e);
+                Self {
+                    service: None,
+                    username: current_username(),
+                }
+            }
+        }
+    }
+
+    /// 생성(before 없음) 또는 수정 기록
+    pub(crate) async fn modified<T: Serialize>(
+        &self,
+        resource: &str,
+        resource_id: &str,
+        before: Option<&T>,
+        after: &T,
+    ) {
+        let details = diff_details(before, Some(after));
+        let operation = if before.is_some() { "update" } else { "create" };
+        let Some(service) = &self.service else {
+            return;
+        };
+        let result = service
+            .log_data_modification(
+                None,
+                self.username.clone(),
+                resource.to_string(),
+                Some(resource_id.to_string()),
+                with_operation(details, operation),
+            )
+            .await;
+        Self::report(result);
+    }
+
+    /// 삭제 기록 (삭제 전 값 전체와 추가 정보)
+    pub(crate) async fn deleted<T: Serialize>(
+        &self,
+        resource: &str,
+        resource_id: &str,
+        before: &T,
+        extra: &[(&str, String)],
+    ) {
+        let mut details = with_operation(diff_details(Some(before), None), "delete");
+        for (key, value) in extra {
+            details.insert(key.to_string(), value.clone());
+        }
+        let Some(service) = &self.service else {
+            return;
+        };
+        let result = service
+            .log_data_deletion(
+                None,
+                self.username.clone(),
+                resource.to_string(),
+                Some(resource_id.to_string()),
+                details,
+            )
+            .await;
+        Self::report(result);
+    }
+
+    /// 생성/수정/삭제로 분류되지 않는 작업 기록 (복원 등)
+    pub(crate) async fn performed(
+        &self,
+        resource: &str,
+        resource_id: &str,
+        operation: &str,
+        extra: &[(&str, String)],
+    ) {
+        let mut details = HashMap::new();
+        for (key, value) in extra {
+            details.insert(key.to_string(), value.clone());
+        }
+        let Some(service) = &self.service else {
+            return;
+        };
+        let result = service
+            .log_data_modification(
+                None,
+                self.username.clone(),
+                resource.to_string(),
+                Some(resource_id.to_string()),
+                with_operation(details, operation),
+            )
+            .await;
+        Self::report(result);
+    }
+
+    /// 조회 기록
+    pub(crate) async fn accessed(&self, resource: &str, resource_id: Option<&str>) {
+        let Some(service) = &self.service else {
+            return;
+        };
+        let result = service
+            .log_data_access(
+                None,
+                self.username.clone(),
+                resource.to_string(),
+                resource_id.map(str::to_string),
+            )
+            .await;
+        Self::report(result);
+    }
+
+    /// 설정 변경 기록 (비밀 값은 호출 측에서 마스킹해서 전달)
+    pub(crate) async fn config_changed<T: Serialize>(
+        &self,
+        key: &str,
+        before: Option<&T>,
+        after: Option<&T>,
+        extra: &[(&str, String)],
+    ) {
+        let mut details = diff_details(before, after);
+        for (k, value) in extra {
+            details.insert(k.to_string(), value.clone());
+        }
+        let Some(service) = &self.service else {
+            return;
+        };
+        let result = service
+            .log_config_change(None, self.username.clone(), key.to_string(), details)
+            .await;
+        Self::report(result);
+    }
+
+    fn report(result: ErpResult<()>) {
+        if let Err(e) = result {
+            warn!("감사 로그 기록 실패: {}", e);
+        }
+    }
+}
+
+fn with_operation(
+    mut details: HashMap<String, String>,
+    operation: &str,
+) -> HashMap<String, String> {
+    details.insert("operation".to_string(), operation.to_string());
+    details
+}
+
+pub struct AuditHandler;
+
+impl AuditHandler {
+    pub async fn handle(cmd: &AuditCommands, config: &AppConfig) -> ErpResult<()> {
+        if let AuditCommands::Checkpoint(AuditCheckpointCommands::VerifyFile { file }) = cmd {
+            return Self::verify_checkpoint_file(file, config).await;
+        }
+
+        let module = AppModuleBuilder::build_postgres(config).await?;
+        let repository: Arc<dyn AuditRepository> = module.resolve();
+
+        match cmd {
+            AuditCommands::Search {
+                user,
+                resource,
+                resource_id,
+                action,
+                since,
+                limit,
+                details,
+                format,
+            } => {
+                let filters = AuditFilters {
+                    username: user.clone(),
+                    resource: resource.clone(),
+                    resource_id: resource_id.clone(),
+                    action: action
+                        .as_deref()
+                        .map(str::parse::<AuditAction>)
+                        .transpose()?,
+                    start_time: since
+                        .as_deref()
+                        .map(CliValidator::validate_since)
+                        .transpose()?,
+                    limit: Some(u64::from(*limit)),
+                    ..Default::default()
+                };
+                let events = audit_service(config, repository.clone())
+                    .search_events(filters)
+                    .await?;
+
+                match format.to_lowercase().as_str() {
+                    "table" => {
+                        Self::display_events(&events, *details);
+                        Ok(())
+                    }
+                    "json" => {
+                        let json = serde_json::to_string_pretty(&events).map_err(|e| {
+                            ErpError::serialization(format!("감사 이벤트 직렬화 실패: {}", e))
+                        })?;
+                        println!("{}", json);
+                        Ok(())
+                    }
+                    other => Err(ErpError::validation(
+                        "format",
+                        format!("지원하지 않는 출력 형식입니다: {} (table, json)", other),
+                    )),
+                }
+            }
+            AuditCommands::Cleanup { retention_days } => {
+                let retention_days = retention_days.unwrap_or(config.security.audit_retention_days);
+                if retention_days <= 0 {
+                    return Err(ErpError::validation(
+                        "retention_days",
+                        "보존 기간은 1일 이상이어야 합니다",
+                    ));
+                }
+
+                let service = AuditService::new(
+                    repository.clone(),
+                    AuditConfig {
+                        retention_days,
+                        ..AuditConfig::default()
+                    },
+                );
+                let removed = service.cleanup_old_events().await?;
+                println!(
+                    "🧹 보존 기간({}일)이 지난 감사 이벤트 {}건을 삭제했습니다.",
+                    retention_days, removed
+                );
+
+                // 정리 작업 자체도 감사 로그에 남긴다
+                let result = audit_service(config, repository.clone())
+                    .log_data_deletion(
+                        None,
+                        current_username(),
+                        "audit_events".to_string(),
+                        None,
+                        HashMap::from([
+                            ("operation".to_string(), "cleanup".to_string()),
+                            ("retention_days".to_string(), retention_days.to_string()),
+                            ("deleted_count".to_string(), removed.to_string()),
+                        ]),
+                    )
+                    .await;
+                CommandAudit::report(result);
+                Ok(())
+            }
+            AuditCommands::Verify {
+                from,
+                to,
+                checkpoints,
+            } => {
+                let from = from
+                    .as_deref()
+                    .map(CliValidator::validate_since)
+                    .transpose()?;
+                let to = to.as_deref().map(parse_end_of_day).transpose()?;
+                if let (Some(from), Some(to)) = (from, to) {
+                    if from > to {
+                        return Err(ErpError::validation(
+                            "to",
+                            "종료 날짜는 시작 시점 이후여야 합니다",
+                        ));
+                    }
+                }
+
+                let service = audit_service(config, repository.clone());
+                let report = service.verify_chain(from, to).await?;
+                Self::display_chain_report(&report);
+                if let Some(chain_break) = &report.first_break {
+                    return Err(ErpError::business_rule(format!(
+                        "감사 로그 해시 체인이 손상되었습니다: {}",
+                        chain_break
+                    )));
+                }
+
+                if let Some(path) = checkpoints {
+                    let file = AuditCheckpointFile::load(Path::new(path)).await?;
+                    Self::check_checkpoint_signatures(&file, service.signing_key()?)?;
+                    if let Some(chain_break) = service.verify_checkpoints(&file.checkpoints).await?
+                    {
+                        return Err(ErpError::business_rule(format!(
+                            "체크포인트와 감사 로그가 일치하지 않습니다: {}",
+                            chain_break
+                        )));
+                    }
+                    println!(
+                        "✅ 체크포인트 {}개가 현재 감사 로그와 일치합니다.",
+                        file.checkpoints.len()
+                    );
+                }
+                Ok(())
+            }
+            AuditCommands::Checkpoint(cmd) => {
+                let service = audit_service(config, repository.clone());
+                match cmd {
+                    AuditCheckpointCommands::Create => match service.create_checkpoint().await? {
+                        Some(checkpoint) => {
+                            println!(
+                                "🔏 체크포인트를 기록했습니다: #{} ({}…)",
+                                checkpoint.sequence,
+                                &checkpoint.signature[..16]
+                            );
+                            Ok(())
+                        }
+                        None => {
+                            println!("체크포인트를 만들 감사 이벤트가 없습니다.");
+                            Ok(())
+                        }
+                    },
+                    AuditCheckpointCommands::List { since } => {
+                        let since = since
+                            .as_deref()
+                            .map(CliValidator::validate_since)
+                            .transpose()?;
+                        let checkpoints = service.list_checkpoints(since).await?;
+                        Self::display_checkpoints(&checkpoints);
+                        Ok(())
+                    }
+                    AuditCheckpointCommands::Export { output, since } => {
+                        let since = since
+                            .as_deref()
+                            .map(CliValidator::validate_since)
+                            .transpose()?;
+                        let checkpoints = service.list_checkpoints(since).await?;
+                        if checkpoints.is_empty() {
+                            return Err(ErpError::not_found_simple("내보낼 체크포인트가 없습니다"));
+                        }
+                        let count = checkpoints.len();
+                        AuditCheckpointFile::new(checkpoints)
+                            .save(Path::new(output))
+                            .await?;
+                        println!("📤 체크포인트 {}개를 {}에 저장했습니다.", count, output);
+                        Ok(())
+                    }
+                    AuditCheckpointCommands::VerifyFile { .. } => unreachable!(), // 위에서 처리됨
+                }
+            }
+        }
+    }
+
+    /// 데이터베이스 없이 체크포인트 파일의 서명과 연결만 검증
+    async fn verify_checkpoint_file(file: &str, config: &AppConfig) -> ErpResult<()> {
+        let checkpoint_file = AuditCheckpointFile::load(Path::new(file)).await?;
+        let key = audit_signing_key(config)
+            .ok_or_else(|| ErpError::config("감사 체크포인트 서명 키가 설정되지 않았습니다"))?;
+        Self::check_checkpoint_signatures(&checkpoint_file, key.as_bytes())?;
+
+        Self::display_checkpoints(&checkpoint_file.checkpoints);
+        println!(
+            "✅ 체크포인트 {}개의 서명이 모두 유효합니다. (내보낸 시각: {})",
+            checkpoint_file.checkpoints.len(),
+            Self::format_local_time(checkpoint_file.exported_at)
+        );
+        Ok(())
+    }
+
+    fn check_checkpoint_signatures(file: &AuditCheckpointFile, key: &[u8]) -> ErpResult<()> {
+        match file.first_invalid(key) {
+            Some((sequence, reason)) => Err(ErpError::business_rule(format!(
+                "체크포인트 #{} 검증 실패: {}",
+                sequence, reason
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn display_chain_report(report: &ChainReport) {
+        match (report.first_sequence, report.last_sequence) {
+            (Some(first), Some(last)) => {
+                println!(
+                    "🔗 감사 이벤트 {}건 검증 (시퀀스 #{} ~ #{})",
+                    report.checked, first, last
+                );
+            }
+            _ => println!("🔗 검증할 감사 이벤트가 없습니다."),
+        }
+        if report.unsealed > 0 {
+            println!(
+                "⚠️  해시 체인 도입 이전 이벤트 {}건은 검증에서 제외했습니다.",
+                report.unsealed
+            );
+        }
+        match &report.first_break {
+            Some(chain_break) => {
+                println!("❌ 첫 번째 끊어진 지점: {}", chain_break);
+                if let Some(at) = chain_break.timestamp {
+                    println!("   기록 시각: {}", Self::format_local_time(at));
+                }
+            }
+            None => {
+                if let Some(head) = &report.head_hash {
+                    println!("✅ 해시 체인이 온전합니다. (마지막 해시: {})", head);
+                }
+            }
+        }
+    }
+
+    fn display_checkpoints(checkpoints: &[AuditCheckpoint]) {
+        if checkpoints.is_empty() {
+            println!("체크포인트가 없습니다.");
+            return;
+        }
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(vec![
+                "시퀀스",
+                "이벤트 시각",
+                "내용 해시",
+                "서명",
+                "생성 시각",
+            ]);
+
+        for checkpoint in checkpoints {
+            table.add_row(vec![
+                format!("#{}", checkpoint.sequence),
+                Self::format_local_time(checkpoint.event_timestamp),
+                truncate_hash(&checkpoint.content_hash),
+                truncate_hash(&checkpoint.signature),
+                Self::format_local_time(checkpoint.created_at),
+            ]);
+        }
+
+        println!("{}", table);
+        println!("총 {}개", checkpoints.len());
+    }
+
+    fn format_local_time(at: DateTime<Utc>) -> String {
+        at.with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    }
+
+    fn display_events(events: &[AuditEvent], show_details: bool) {
+        if events.is_empty() {
+            println!("조건에 맞는 감사 이벤트가 없습니다.");
+            return;
+        }
+
+        let mut header = vec!["시각", "사용자", "작업", "리소스", "리소스 ID", "결과"];
+        if show_details {
+            header.push("변경 내역");
+        }
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(header);
+
+        for event in events {
+            let outcome = if event.success {
+                "✅ 성공".to_string()
+            } else {
+                format!("❌ {}", event.error_message.as_deref().unwrap_or("실패"))
+            };
+            let mut row = vec![
+                Self::format_local_time(event.timestamp),
+                event.username.clone().unwrap_or_else(|| "-".to_string()),
+                event.action.to_string(),
+                event.resource.clone().unwrap_or_else(|| "-".to_string()),
+                event.resource_id.clone().unwrap_or_else(|| "-".to_string()),
+                outcome,
+            ];
+            if show_details {
+                row.push(format_details(&event.details));
+            }
+            table.add_row(row);
+        }
+
+        println!("{}", table);
+        println!("총 {}건", events.len());
+    }
+}
+
+/// `<필드>.before` / `<필드>.after` 쌍을 "필드: 이전 → 이후" 형식으로 표시
+fn format_details(details: &HashMap<String, String>) -> String {
+    let mut fields: BTreeMap<&str, (Option<&str>, Option<&str>)> = BTreeMap::new();
+    let mut others: BTreeMap<&str, &str> = BTreeMap::new();
+
+    for (key, value) in details {
+        if let Some(field) = key.strip_suffix(".before") {
+            fields.entry(field).or_default().0 = Some(value);
+        } else if let Some(field) = key.strip_suffix(".after") {
+            fields.entry(field).or_default().1 = Some(value);
+        } else {
+            others.insert(key, value);
+        }
+    }
+
+    let mut lines: Vec<String> = others
+        .into_iter()
+        .map(|(key, value)| format!("{}: {}", key, truncate(value)))
+        .collect();
+    for (field, change) in fields {
+        let line = match change {
+            (Some(before), Some(after)) => {
+                format!("{}: {} → {}", field, truncate(before), truncate(after))
+            }
+            (Some(value), None) | (None, Some(value)) => format!("{}: {}", field, truncate(value)),
+            (None, None) => continue,
+        };
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+/// YYYY-MM-DD를 해당 날짜의 마지막 시각(로컬)으로 변환
+fn parse_end_of_day(value: &str) -> ErpResult<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map_err(|_| {
+        ErpError::validation(
+            "to",
+            format!("날짜 형식이 올바르지 않습니다 (YYYY-MM-DD): {}", value),
+        )
+    })?;
+    let end = date
+        .and_hms_micro_opt(23, 59, 59, 999_999)
+        .and_then(|end| Local.from_local_datetime(&end).latest())
+        .ok_or_else(|| {
+            ErpError::validation("to", format!("유효하지 않은 날짜입니다: {}", value))
+        })?;
+    Ok(end.with_timezone(&Utc))
+}
+
+fn truncate_hash(hash: &str) -> String {
+    format!("{}…", &hash[..hash.len().min(16)])
+}
+
+fn truncate(value: &str) -> String {
+    if value.chars().count() > MAX_DETAIL_VALUE_LEN {
+        let truncated: String = value.chars().take(MAX_DETAIL_VALUE_LEN).collect();
+        format!("{}…", truncated)
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_details_pairs_changes() {
+        let details = HashMap::from([
+            ("operation".to_string(), "update".to_string()),
+            ("price.before".to_string(), "10.00".to_string()),
+            ("price.after".to_string(), "12.00".to_string()),
+            ("name.after".to_string(), "Widget".to_string()),
+        ]);
+
+        let formatted = format_details(&details);
+        assert_eq!(
+            formatted,
+            "operation: update\nname: Widget\nprice: 10.00 → 12.00"
+        );
+    }
+
+    #[test]
+    fn test_truncate_long_values() {
+        let long = "가".repeat(MAX_DETAIL_VALUE_LEN + 5);
+        let truncated = truncate(&long);
+        assert_eq!(truncated.chars().count(), MAX_DETAIL_VALUE_LEN + 1);
+        assert!(truncated.ends_with('…'));
+        assert_eq!(truncate("short"), "short");
+    }
+}
@@ -1,13 +1,16 @@
+use crate::app_module::{AppModuleBuilder, ConfigServiceProvider};
+use crate::cli::commands::audit::CommandAudit;
 use crate::cli::parser::ConfigCommands;
-use crate::core::config::AppConfig;
-use crate::core::database::DatabaseConnection;
+use crate::core::config::validation::{check_env, check_file, known_keys};
+use crate::core::config::{AppConfig, ConfigIssue};
+use crate::core::database::connection::DatabaseManager;
 use crate::modules::config::{
-    ConfigFilter, ConfigItem, ConfigRepository, ConfigService, CreateConfigRequest,
+    ConfigFilter, ConfigHistoryEntry, ConfigItem, ConfigService, CreateConfigRequest,
     UpdateConfigRequest,
 };
-use crate::utils::error::ErpResult;
+use crate::utils::error::{ErpError, ErpResult};
 use console::{style, Term};
-use std::sync::Arc;
+use shaku::HasComponent;
 use tabled::{Table, Tabled};
 
 #[derive(Tabled)]
@@ -53,27 +56,66 @@ impl From<ConfigItem> for ConfigItemDisplay {
     }
 }
 
+#[derive(Tabled)]
+struct ConfigHistoryDisplay {
+    #[tabled(rename = "Version")]
+    version: i32,
+    #[tabled(rename = "Change")]
+    change: String,
+    #[tabled(rename = "Old Value")]
+    old_value: String,
+    #[tabled(rename = "New Value")]
+    new_value: String,
+    #[tabled(rename = "Changed By")]
+    changed_by: String,
+    #[tabled(rename = "Changed At")]
+    changed_at: String,
+}
+
+impl From<ConfigHistoryEntry> for ConfigHistoryDisplay {
+    fn from(entry: ConfigHistoryEntry) -> Self {
+        // 비밀 설정 값은 이력에서도 노출하지 않음
+        let entry = entry.masked();
+        let change = match entry.rolled_back_to {
+            Some(version) => format!("{} (to v{})", entry.change, version),
+            None => entry.change.to_string(),
+        };
+
+        Self {
+            version: entry.version,
+            change,
+            old_value: entry.old_value.unwrap_or_else(|| "-".to_string()),
+            new_value: entry.new_value.unwrap_or_else(|| "-".to_string()),
+            changed_by: entry.changed_by,
+            changed_at: entry.changed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
 pub struct ConfigHandler;
 
 impl ConfigHandler {
     pub async fn handle(cmd: &ConfigCommands, config: &AppConfig) -> ErpResult<()> {
-        // 데이터베이스 연결 및 서비스 초기화
-        let db_conn = Arc::new(DatabaseConnection::new(config.database.clone()).await?);
-        let repository = Arc::new(ConfigRepository::new(db_conn.clone()));
-
-        // 테이블 초기화 (존재하지 않는 경우)
-        repository.init_table().await?;
-
-        let service = ConfigService::new(repository);
+        // 공유 연결로 구성된 모듈에서 서비스 해석 (설정 테이블은 빌드 시 준비)
+        let module = AppModuleBuilder::build(config).await?;
+        let service = HasComponent::<dyn ConfigServiceProvider>::resolve_ref(&module).get_service();
+        let audit = CommandAudit::from_module(config, &module);
 
         match cmd {
             ConfigCommands::Get { key } => Self::handle_get(key, &service).await,
-            ConfigCommands::Set { key, value } => Self::handle_set(key, value, &service).await,
+            ConfigCommands::Set { key, value } => {
+                Self::handle_set(key, value, &service, &audit).await
+            }
             ConfigCommands::List { filter, format } => {
                 Self::handle_list(filter, format, &service).await
             }
             ConfigCommands::Path => Self::handle_path().await,
-            ConfigCommands::Reset { force } => Self::handle_reset(*force, &service).await,
+            ConfigCommands::Validate => Self::validate().await,
+            ConfigCommands::History { key } => Self::handle_history(key, &service).await,
+            ConfigCommands::Rollback { key, to } => {
+                Self::handle_rollback(key, *to, &service, &audit).await
+            }
+            ConfigCommands::Reset { force } => Self::handle_reset(*force, &service, &audit).await,
         }
     }
 
@@ -143,12 +185,17 @@ impl ConfigHandler {
         Ok(())
     }
 
-    async fn handle_set(key: &str, value: &str, service: &ConfigService) -> ErpResult<()> {
+    async fn handle_set(
+        key: &str,
+        value: &str,
+        service: &ConfigService,
+        audit: &CommandAudit,
+    ) -> ErpResult<()> {
         let term = Term::stdout();
 
         // 기존 설정이 있는지 확인
         match service.get_config(key).await? {
-            Some(_existing) => {
+            Some(existing) => {
                 // 기존 설정 업데이트
                 let update_request = UpdateConfigRequest {
                     value: Some(value.to_string()),
@@ -158,6 +205,20 @@ impl ConfigHandler {
                 };
 
                 let updated = service.update_config(key, update_request).await?;
+                // 비밀 값은 마스킹되어 diff에 나타나지 않으므로 변경 여부만 기록
+                let secret_changed = if updated.is_secret && existing.value != updated.value {
+                    vec![("value_changed", "true".to_string())]
+                } else {
+                    Vec::new()
+                };
+                audit
+                    .config_changed(
+                        key,
+                        Some(&Self::masked_for_audit(&existing)),
+                        Some(&Self::masked_for_audit(&updated)),
+                        &secret_changed,
+                    )
+                    .await;
 
                 term.write_line(&format!(
                     "✅ {}: {}",
@@ -186,6 +247,9 @@ impl ConfigHandler {
                 );
 
                 let created = service.create_config(create_request).await?;
+                audit
+                    .config_changed(key, None, Some(&Self::masked_for_audit(&created)), &[])
+                    .await;
 
                 term.write_line(&format!(
                     "✅ {}: {}",
@@ -285,6 +349,79 @@ impl ConfigHandler {
         Ok(())
     }
 
+    async fn handle_history(key: &str, service: &ConfigService) -> ErpResult<()> {
+        let term = Term::stdout();
+        let history = service.config_history(key).await?;
+
+        if history.is_empty() {
+            term.write_line(
+                &style(format!("No history recorded for '{}'", key))
+                    .yellow()
+                    .to_string(),
+            )
+            .ok();
+            return Ok(());
+        }
+
+        let display_items: Vec<ConfigHistoryDisplay> = history
+            .into_iter()
+            .map(ConfigHistoryDisplay::from)
+            .collect();
+        term.write_line(&Table::new(&display_items).to_string())
+            .ok();
+        term.write_line(&format!(
+            "{}: erp config rollback {} --to <version>",
+            style("Restore").cyan().bold(),
+            key
+        ))
+        .ok();
+
+        Ok(())
+    }
+
+    async fn handle_rollback(
+        key: &str,
+        version: i32,
+        service: &ConfigService,
+        audit: &CommandAudit,
+    ) -> ErpResult<()> {
+        let term = Term::stdout();
+
+        let before = service.get_config(key).await?;
+        let restored = service.rollback_config(key, version).await?;
+        audit
+            .config_changed(
+                key,
+                before.as_ref().map(Self::masked_for_audit).as_ref(),
+                Some(&Self::masked_for_audit(&restored)),
+                &[
+                    ("operation", "rollback".to_string()),
+                    ("version", version.to_string()),
+                ],
+            )
+            .await;
+
+        term.write_line(&format!(
+            "✅ {}: {} (v{})",
+            style("Rolled back configuration").green().bold(),
+            style(&restored.key).white(),
+            version
+        ))
+        .ok();
+        term.write_line(&format!(
+            "{}: {}",
+            style("Value").cyan(),
+            if restored.is_secret {
+                style(restored.masked_value()).red().to_string()
+            } else {
+                style(restored.value.clone()).green().to_string()
+            }
+        ))
+        .ok();
+
+        Ok(())
+    }
+
     async fn handle_path() -> ErpResult<()> {
         let term = Term::stdout();
 
@@ -353,7 +490,11 @@ impl ConfigHandler {
         Ok(())
     }
 
-    async fn handle_reset(force: bool, service: &ConfigService) -> ErpResult<()> {
+    async fn handle_reset(
+        force: bool,
+        service: &ConfigService,
+        audit: &CommandAudit,
+    ) -> ErpResult<()> {
         let term = Term::stdout();
 
         if !force {
@@ -386,6 +527,17 @@ impl ConfigHandler {
 
         // 초기화 실행
         let deleted_count = service.reset_configs(force).await?;
+        audit
+            .config_changed::<ConfigItem>(
+                "*",
+                None,
+                None,
+                &[
+                    ("operation", "reset".to_string()),
+                    ("deleted_count", deleted_count.to_string()),
+                ],
+            )
+            .await;
 
         if deleted_count > 0 {
             term.write_line(&format!(
@@ -404,4 +556,105 @@ impl ConfigHandler {
 
         Ok(())
     }
+
+    /// 설정 파일, 환경변수, 병합된 값, 설정 저장소를 차례로 검증
+    ///
+    /// 잘못된 설정에서도 문제를 보고해야 하므로 `AppConfig::load()`를 거치지 않는다.
+    pub async fn validate() -> ErpResult<()> {
+        let term = Term::stdout();
+        term.write_line(&format!(
+            "{} ({})",
+            style("Validating configuration").cyan().bold(),
+            AppConfig::environment()
+        ))
+        .ok();
+
+        let mut sections: Vec<(String, Vec<ConfigIssue>)> = AppConfig::config_files()
+            .into_iter()
+            .map(|path| (path.display().to_string(), check_file(&path)))
+            .collect();
+        sections.push((
+            "Environment (ERP_*)".to_string(),
+            check_env(std::env::vars()),
+        ));
+
+        match AppConfig::load_unchecked() {
+            Ok(config) => {
+                sections.push(("Merged values".to_string(), config.check()));
+                sections.push(("Config store".to_string(), Self::check_store(&config).await));
+            }
+            Err(e) => {
+                sections.push((
+                    "Merged values".to_string(),
+                    vec![ConfigIssue::error("merged", "-", e.to_string())],
+                ));
+            }
+        }
+
+        let (mut errors, mut warnings) = (0, 0);
+        for (name, issues) in &sections {
+            term.write_line("").ok();
+            term.write_line(&style(name).yellow().bold().to_string())
+                .ok();
+            if issues.is_empty() {
+                term.write_line(&format!("  {}", style("✅ OK").green()))
+                    .ok();
+            }
+            for issue in issues {
+                let line = if issue.is_error() {
+                    errors += 1;
+                    style(format!("  ❌ {}", issue)).red()
+                } else {
+                    warnings += 1;
+                    style(format!("  ⚠️  {}", issue)).yellow()
+                };
+                term.write_line(&line.to_string()).ok();
+            }
+        }
+
+        term.write_line("").ok();
+        term.write_line(&format!(
+            "{}: {} error(s), {} warning(s)",
+            style("Summary").cyan().bold(),
+            errors,
+            warnings
+        ))
+        .ok();
+
+        if errors > 0 {
+            return Err(ErpError::config(format!(
+                "Configuration has {} error(s)",
+                errors
+            )));
+        }
+        Ok(())
+    }
+
+    /// 데이터베이스에 접속할 수 없으면 저장소 검사는 건너뛰고 경고로 남긴다
+    async fn check_store(config: &AppConfig) -> Vec<ConfigIssue> {
+        let result = async {
+            DatabaseManager::initialize(config.database.clone()).await?;
+            let module = AppModuleBuilder::build(config).await?;
+            HasComponent::<dyn ConfigServiceProvider>::resolve_ref(&module)
+                .get_service()
+                .check_store(&known_keys())
+                .await
+        }
+        .await;
+
+        result.unwrap_or_else(|e| {
+            vec![ConfigIssue::warning(
+                "config store",
+                "-",
+                format!("skipped, database unavailable: {}", e),
+            )]
+        })
+    }
+
+    /// 감사 로그에는 비밀 설정의 원래 값을 남기지 않는다
+    fn masked_for_audit(item: &ConfigItem) -> ConfigItem {
+        let mut masked = item.clone();
+        masked.value = item.masked_value();
+        masked
+    }
 }
@@ -1,30 +1,33 @@
+use chrono::Utc;
 use comfy_table::modifiers::UTF8_ROUND_CORNERS;
 use comfy_table::presets::UTF8_FULL;
 use comfy_table::Table;
 use rust_decimal::Decimal;
-use std::sync::Arc;
+use shaku::HasComponent;
 use uuid::Uuid;
 
+use crate::app_module::{AppModuleBuilder, CustomerServiceProvider};
+use crate::cli::commands::audit::CommandAudit;
+use crate::cli::commands::security::enforce_export_limit;
 use crate::cli::parser::CustomerCommands;
 use crate::cli::validator::CliValidator;
 use crate::core::config::AppConfig;
+use crate::modules::customers::segmentation;
 use crate::modules::customers::{
     AddressType, BalanceOperation, CreateAddressRequest, CreateCustomerRequest, CustomerFilter,
-    CustomerService, CustomerType, PostgresCustomerRepository, UpdateCustomerRequest,
+    CustomerService, CustomerType, UpdateCustomerRequest,
 };
 use crate::utils::error::{ErpError, ErpResult};
+use crate::utils::xlsx::{Workbook, Worksheet};
 
 pub struct CustomerHandler;
 
 impl CustomerHandler {
-    pub async fn handle(cmd: &CustomerCommands, _config: &AppConfig) -> ErpResult<()> {
-        use crate::core::database::DatabaseManager;
-
-        let connection = DatabaseManager::get_connection().await?;
-        let pool = connection.pool().clone();
-
-        let repository = Arc::new(PostgresCustomerRepository::new(Arc::new(pool)));
-        let service = CustomerService::new(repository);
+    pub async fn handle(cmd: &CustomerCommands, config: &AppConfig) -> ErpResult<()> {
+        let module = AppModuleBuilder::build(config).await?;
+        let service =
+            HasComponent::<dyn CustomerServiceProvider>::resolve_ref(&module).get_service();
+        let audit = CommandAudit::from_module(config, &module);
 
         match cmd {
             CustomerCommands::Add {
@@ -39,8 +42,8 @@ impl CustomerHandler {
                 notes,
             } => {
                 Self::handle_add(
-                    &service, name, first_name, last_name, email, phone, address, company, tax_id,
-                    notes,
+                    &service, &audit, name, first_name, last_name, email, phone, address, company,
+                    tax_id, notes,
                 )
                 .await
             }
@@ -48,19 +51,28 @@ impl CustomerHandler {
             CustomerCommands::List {
                 search,
                 customer_type,
+                segment,
                 page,
                 limit,
                 format,
+                output,
                 sort_by,
                 order,
             } => {
+                // 테이블 외 형식은 고객 목록을 내보내므로 대량 내보내기 한도에 포함
+                if matches!(format.as_str(), "json" | "csv" | "xlsx") {
+                    enforce_export_limit(config).await?;
+                }
                 Self::handle_list(
                     &service,
+                    &audit,
                     search,
                     customer_type,
+                    segment,
                     *page,
                     *limit,
                     format,
+                    output,
                     sort_by,
                     order,
                 )
@@ -76,15 +88,18 @@ impl CustomerHandler {
                 company,
                 notes,
             } => {
-                Self::handle_update(&service, id, name, email, phone, address, company, notes).await
+                Self::handle_update(
+                    &service, &audit, id, name, email, phone, address, company, notes,
+                )
+                .await
             }
 
             CustomerCommands::Delete { id, force, cascade } => {
-                Self::handle_delete(&service, id, *force, *cascade).await
+                Self::handle_delete(&service, &audit, id, *force, *cascade).await
             }
 
             CustomerCommands::Search { query, field } => {
-                Self::handle_search(&service, query, field).await
+                Self::handle_search(&service, &audit, query, field).await
             }
         }
     }
@@ -92,6 +107,7 @@ impl CustomerHandler {
     #[allow(clippy::too_many_arguments)]
     async fn handle_add(
         service: &CustomerService,
+        audit: &CommandAudit,
         name: &Option<String>,
         first_name: &Option<String>,
         last_name: &Option<String>,
@@ -199,6 +215,9 @@ impl CustomerHandler {
 
         // Create customer
         let customer = service.create_customer(request).await?;
+        audit
+            .modified("customers", &customer.customer_code, None, &customer)
+            .await;
 
         println!("✅ Customer created successfully!");
         println!("Customer Code: {}", customer.customer_code);
@@ -231,11 +250,14 @@ impl CustomerHandler {
     #[allow(clippy::too_many_arguments)]
     async fn handle_list(
         service: &CustomerService,
+        audit: &CommandAudit,
         search: &Option<String>,
         customer_type: &Option<String>,
+        segment: &Option<String>,
         page: u32,
         limit: u32,
         format: &str,
+        output: &Option<String>,
         sort_by: &str,
         order: &str,
     ) -> ErpResult<()> {
@@ -252,6 +274,9 @@ impl CustomerHandler {
             None => None,
         };
 
+        // Parse RFM segment filter
+        let segment_filter = segmentation::parse_segment(segment)?;
+
         // Create filter
         let filter = CustomerFilter {
             status: None,
@@ -266,12 +291,30 @@ impl CustomerHandler {
             has_outstanding_balance: None,
             credit_limit_min: None,
             credit_limit_max: None,
+            customer_ids: None,
         };
 
         // Get customers
-        let result = service
-            .list_customers(filter, validated_page, validated_limit, sort_by, order)
-            .await?;
+        let result = match segment_filter {
+            Some(segment) => {
+                service
+                    .list_customers_in_segment(
+                        segment,
+                        filter,
+                        validated_page,
+                        validated_limit,
+                        sort_by,
+                        order,
+                    )
+                    .await?
+            }
+            None => {
+                service
+                    .list_customers(filter, validated_page, validated_limit, sort_by, order)
+                    .await?
+            }
+        };
+        audit.accessed("customers", None).await;
 
         if result.customers.is_empty() {
             println!("No customers found.");
@@ -327,6 +370,50 @@ impl CustomerHandler {
                     );
                 }
             }
+            "xlsx" => {
+                // Output as XLSX workbook
+                let mut sheet = Worksheet::new("Customers");
+                sheet.header([
+                    "Code",
+                    "Name",
+                    "Email",
+                    "Phone",
+                    "City",
+                    "Type",
+                    "Status",
+                    "Credit Limit",
+                    "Balance",
+                    "Available",
+                    "Created At",
+                ]);
+                for customer in &result.customers {
+                    sheet.row(vec![
+                        customer.customer_code.as_str().into(),
+                        customer.display_name().into(),
+                        customer.email.as_str().into(),
+                        customer.phone.as_deref().into(),
+                        customer
+                            .addresses
+                            .first()
+                            .map(|addr| addr.city.as_str())
+                            .into(),
+                        customer.customer_type.to_string().into(),
+                        customer.status.to_string().into(),
+                        customer.credit_limit.into(),
+                        customer.current_balance.into(),
+                        customer.available_credit.into(),
+                        customer.created_at.into(),
+                    ]);
+                }
+
+                let path = output.clone().unwrap_or_else(|| {
+                    format!("customers_{}.xlsx", Utc::now().format("%Y%m%d_%H%M%S"))
+                });
+                let mut workbook = Workbook::new();
+                workbook.add_sheet(sheet);
+                workbook.save(&path)?;
+                println!("Exported {} customers to {}", result.customers.len(), path);
+            }
             _ => {
                 // Default table format
                 let mut table = Table::new();
@@ -403,6 +490,7 @@ impl CustomerHandler {
     #[allow(clippy::too_many_arguments)]
     async fn handle_update(
         service: &CustomerService,
+        audit: &CommandAudit,
         id: &str,
         name: &Option<String>,
         email: &Option<String>,
@@ -419,6 +507,7 @@ impl CustomerHandler {
             let customer = service.get_customer_by_code(id).await?;
             customer.id
         };
+        let before = service.get_customer_by_id(customer_id).await?;
 
         // Parse name if provided
         let (first_name, last_name) = if let Some(name_str) = name {
@@ -550,6 +639,14 @@ impl CustomerHandler {
 
         // Show all current addresses
         let final_customer = service.get_customer_by_id(customer_id).await?;
+        audit
+            .modified(
+                "customers",
+                &final_customer.customer_code,
+                Some(&before),
+                &final_customer,
+            )
+            .await;
         if !final_customer.addresses.is_empty() {
             println!("\nCurrent Addresses:");
             for (i, addr) in final_customer.addresses.iter().enumerate() {
@@ -568,6 +665,7 @@ impl CustomerHandler {
 
     async fn handle_delete(
         service: &CustomerService,
+        audit: &CommandAudit,
         id: &str,
         force: bool,
         cascade: bool,
@@ -624,6 +722,14 @@ impl CustomerHandler {
         // Attempt to delete
         if cascade {
             let result = service.delete_customer_cascade(customer_id).await?;
+            audit
+                .deleted(
+                    "customers",
+                    &customer.customer_code,
+                    &customer,
+                    &[("orders_deleted", result.orders_deleted.to_string())],
+                )
+                .await;
             println!("✅ Customer deleted successfully with cascade!");
             println!(
                 "Deleted: {} ({})",
@@ -635,6 +741,9 @@ impl CustomerHandler {
         } else {
             match service.delete_customer(customer_id).await {
                 Ok(_) => {
+                    audit
+                        .deleted("customers", &customer.customer_code, &customer, &[])
+                        .await;
                     println!("✅ Customer deleted successfully!");
                     println!(
                         "Deleted: {} ({})",
@@ -665,6 +774,7 @@ impl CustomerHandler {
 
     async fn handle_search(
         service: &CustomerService,
+        audit: &CommandAudit,
         query: &str,
         _field: &Option<String>,
     ) -> ErpResult<()> {
@@ -674,6 +784,7 @@ impl CustomerHandler {
 
         // Search customers
         let customers = service.search_customers(query.trim(), 20).await?;
+        audit.accessed("customers", None).await;
 
         if customers.is_empty() {
             println!("No customers found for query: '{}'", query.trim());
@@ -0,0 +1,200 @@
+use chrono::Local;
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use std::path::Path;
+
+use crate::cli::commands::audit::CommandAudit;
+use crate::cli::commands::security::enforce_export_limit;
+use crate::cli::parser::DataCommands;
+use crate::core::config::AppConfig;
+use crate::core::database::connection::DatabaseManager;
+use crate::core::ops::data_transfer::{
+    parse_modules, DataExporter, DataImporter, ImportOptions, SnapshotManifest,
+};
+use crate::utils::error::{ErpError, ErpResult};
+
+pub struct DataHandler;
+
+impl DataHandler {
+    pub async fn handle(cmd: &DataCommands, config: &AppConfig) -> ErpResult<()> {
+        match cmd {
+            DataCommands::Export { modules, output } => {
+                Self::handle_export(config, modules, output.as_deref()).await
+            }
+            DataCommands::Import {
+                file,
+                modules,
+                on_conflict,
+                dry_run,
+            } => {
+                let options = ImportOptions {
+                    modules: modules.as_deref().map(parse_modules).transpose()?,
+                    on_conflict: on_conflict.parse()?,
+                    dry_run: *dry_run,
+                };
+                Self::handle_import(config, file, &options).await
+            }
+            DataCommands::Inspect { file } => Self::handle_inspect(file),
+        }
+    }
+
+    async fn handle_export(
+        config: &AppConfig,
+        modules: &str,
+        output: Option<&str>,
+    ) -> ErpResult<()> {
+        let modules = parse_modules(modules)?;
+        let output = output
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("erp_data_{}.tar", Local::now().format("%Y%m%d_%H%M%S")));
+        if Path::new(&output).exists() {
+            return Err(ErpError::validation(
+                "output",
+                format!("{} 파일이 이미 존재합니다", output),
+            ));
+        }
+
+        enforce_export_limit(config).await?;
+
+        println!("📦 데이터 내보내는 중... ({})", modules.join(", "));
+        let connection = DatabaseManager::get_connection().await?;
+        let exporter = DataExporter::new(connection.pool()?.clone());
+        let manifest = exporter.export(&modules, Path::new(&output)).await?;
+
+        Self::display_manifest(&manifest);
+        println!("✅ 내보내기 완료: {}", output);
+
+        let audit = CommandAudit::new(config).await;
+        audit
+            .performed(
+                "data",
+                &output,
+                "export",
+                &[
+                    ("modules", manifest.modules.join(",")),
+                    ("rows", manifest.total_rows().to_string()),
+                ],
+            )
+            .await;
+        Ok(())
+    }
+
+    async fn handle_import(
+        config: &AppConfig,
+        file: &str,
+        options: &ImportOptions,
+    ) -> ErpResult<()> {
+        let manifest = SnapshotManifest::read(Path::new(file))?;
+        println!(
+            "📥 {} 가져오는 중... (생성: {}, 모듈: {}, 충돌 정책: {})",
+            file,
+            manifest
+                .created_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            options
+                .modules
+                .as_ref()
+                .unwrap_or(&manifest.modules)
+                .join(", "),
+            options.on_conflict
+        );
+
+        let connection = DatabaseManager::get_connection().await?;
+        let importer = DataImporter::new(connection.pool()?.clone());
+        let report = importer.import(Path::new(file), options).await?;
+
+        for warning in &report.warnings {
+            println!("⚠️  {}", warning);
+        }
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(vec!["테이블", "행 수", "반영", "건너뜀"]);
+        for result in &report.tables {
+            table.add_row(vec![
+                result.table.clone(),
+                result.rows.to_string(),
+                result.written.to_string(),
+                result.skipped.to_string(),
+            ]);
+        }
+        println!("{}", table);
+
+        let written: u64 = report.tables.iter().map(|r| r.written).sum();
+        if options.dry_run {
+            println!(
+                "ℹ️  --dry-run: 변경 사항을 롤백했습니다. ({}행 반영 예정)",
+                written
+            );
+            return Ok(());
+        }
+        println!("✅ 가져오기 완료: {}행 반영", written);
+
+        let audit = CommandAudit::new(config).await;
+        audit
+            .performed(
+                "data",
+                file,
+                "import",
+                &[
+                    (
+                        "tables",
+                        report
+                            .tables
+                            .iter()
+                            .map(|r| r.table.as_str())
+                            .collect::<Vec<_>>()
+                            .join(","),
+                    ),
+                    ("rows_written", written.to_string()),
+                    ("on_conflict", options.on_conflict.to_string()),
+                ],
+            )
+            .await;
+        Ok(())
+    }
+
+    fn handle_inspect(file: &str) -> ErpResult<()> {
+        let manifest = SnapshotManifest::read(Path::new(file))?;
+        println!("형식: {} v{}", manifest.format, manifest.version);
+        println!(
+            "생성: {} (erp {})",
+            manifest
+                .created_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            manifest.app_version
+        );
+        println!(
+            "스키마 버전: {}",
+            manifest.schema_version.as_deref().unwrap_or("-")
+        );
+        println!("모듈: {}", manifest.modules.join(", "));
+        Self::display_manifest(&manifest);
+        Ok(())
+    }
+
+    fn display_manifest(manifest: &SnapshotManifest) {
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(vec!["모듈", "테이블", "컬럼 수", "행 수"]);
+        for entry in &manifest.tables {
+            table.add_row(vec![
+                entry.module.clone(),
+                entry.name.clone(),
+                entry.columns.len().to_string(),
+                entry.row_count.to_string(),
+            ]);
+        }
+        println!("{}", table);
+        println!(
+            "총 {}개 테이블, {}행",
+            manifest.tables.len(),
+            manifest.total_rows()
+        );
+    }
+}
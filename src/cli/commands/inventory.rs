@@ -1,14 +1,17 @@
+use crate::app_module::AppModuleBuilder;
+use crate::cli::commands::audit::CommandAudit;
 use crate::cli::parser::InventoryCommands;
 use crate::cli::validator::CliValidator;
 use crate::core::config::AppConfig;
-use crate::core::database::connection::DatabaseManager;
 use crate::core::database::models::product::StockStatus;
 use crate::modules::inventory::{
-    CreateInventoryItemRequest, InventoryFilter, InventoryModule, UpdateInventoryItemRequest,
+    CreateInventoryItemRequest, InventoryFilter, InventoryService, UpdateInventoryItemRequest,
 };
 use crate::utils::error::ErpResult;
 use crate::utils::inventory_formatter::InventoryFormatter;
 use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use shaku::HasComponent;
+use std::sync::Arc;
 use tracing::{error, info};
 use uuid::Uuid;
 
@@ -30,6 +33,7 @@ struct ListProductsParams<'a> {
     page: u32,
     limit: u32,
     format: &'a str,
+    output: &'a Option<String>,
     sort_by: &'a str,
     order: &'a str,
 }
@@ -68,6 +72,7 @@ impl InventoryHandler {
                 page,
                 limit,
                 format,
+                output,
                 sort_by,
                 order,
             } => {
@@ -78,6 +83,7 @@ impl InventoryHandler {
                     page: *page,
                     limit: *limit,
                     format,
+                    output,
                     sort_by,
                     order,
                 };
@@ -91,15 +97,39 @@ impl InventoryHandler {
                 cost,
                 category,
                 description,
-            } => Self::handle_update(id, name, quantity, price, cost, category, description).await,
-            InventoryCommands::Remove { id, force } => Self::handle_remove(id, *force).await,
+            } => {
+                Self::handle_update(
+                    id,
+                    name,
+                    quantity,
+                    price,
+                    cost,
+                    category,
+                    description,
+                    config,
+                )
+                .await
+            }
+            InventoryCommands::Remove { id, force } => {
+                Self::handle_remove(id, *force, config).await
+            }
             InventoryCommands::LowStock { threshold, format } => {
-                Self::handle_low_stock(threshold, format).await
+                Self::handle_low_stock(threshold, format, config).await
             }
         }
     }
 
-    async fn handle_add(params: AddProductParams<'_>, _config: &AppConfig) -> ErpResult<()> {
+    /// 애플리케이션 모듈에서 재고 서비스 해석
+    /// 재고 서비스와 같은 Module로 만든 감사 기록 도우미
+    async fn inventory_service(
+        config: &AppConfig,
+    ) -> ErpResult<(Arc<dyn InventoryService>, CommandAudit)> {
+        let module = AppModuleBuilder::build(config).await?;
+        let service = HasComponent::<dyn InventoryService>::resolve(&module);
+        Ok((service, CommandAudit::from_module(config, &module)))
+    }
+
+    async fn handle_add(params: AddProductParams<'_>, config: &AppConfig) -> ErpResult<()> {
         let AddProductParams {
             name,
             quantity,
@@ -134,7 +164,7 @@ impl InventoryHandler {
 
         let validated_min_stock = match min_stock {
             Some(stock) => Some(CliValidator::validate_quantity(*stock)?),
-            None => Some(0), // Default minimum stock
+            None => Some(i32::try_from(config.inventory.low_stock_threshold).unwrap_or(i32::MAX)),
         };
 
         // 요청 객체 생성
@@ -160,15 +190,15 @@ impl InventoryHandler {
         let _user_id = Uuid::new_v4();
 
         // 실제 데이터베이스 연결 사용
-        let db_connection = DatabaseManager::get_connection().await?;
-        let inventory_module = InventoryModule::new_with_postgres(db_connection.pool().clone());
+        let (inventory_service, audit) = Self::inventory_service(config).await?;
         let user_id = Uuid::new_v4(); // TODO: Get from auth context
-        let response = inventory_module
-            .service()
-            .create_product(request, user_id)
-            .await;
+        let response = inventory_service.create_product(request, user_id).await;
         match response {
             Ok(product) => {
+                audit
+                    .modified("products", &product.sku, None, &product)
+                    .await;
+
                 println!("✅ 제품이 성공적으로 추가되었습니다!");
                 println!();
 
@@ -206,7 +236,7 @@ impl InventoryHandler {
         }
     }
 
-    async fn handle_list(params: ListProductsParams<'_>, _config: &AppConfig) -> ErpResult<()> {
+    async fn handle_list(params: ListProductsParams<'_>, config: &AppConfig) -> ErpResult<()> {
         let ListProductsParams {
             low_stock,
             category,
@@ -214,6 +244,7 @@ impl InventoryHandler {
             page,
             limit,
             format,
+            output,
             sort_by,
             order,
         } = params;
@@ -243,11 +274,12 @@ impl InventoryHandler {
         };
 
         // 실제 데이터베이스 연결 사용
-        let db_connection = DatabaseManager::get_connection().await?;
-        let inventory_module = InventoryModule::new_with_postgres(db_connection.pool().clone());
-        let response = inventory_module.service().list_products(_filter).await;
+        let (inventory_service, audit) = Self::inventory_service(config).await?;
+        let response = inventory_service.list_products(_filter).await;
         match response {
             Ok(response) => {
+                audit.accessed("products", None).await;
+
                 if response.items.is_empty() {
                     match format {
                         "json" => println!("{{\"items\": [], \"total\": 0}}"),
@@ -271,6 +303,20 @@ impl InventoryHandler {
                         let yaml_output = InventoryFormatter::to_yaml(&response)?;
                         println!("{}", yaml_output);
                     }
+                    "xlsx" => {
+                        let path = output.clone().unwrap_or_else(|| {
+                            format!(
+                                "inventory_{}.xlsx",
+                                chrono::Utc::now().format("%Y%m%d_%H%M%S")
+                            )
+                        });
+                        InventoryFormatter::to_xlsx(&response).save(&path)?;
+                        println!(
+                            "📄 제품 목록이 저장되었습니다: {} ({}개)",
+                            path,
+                            response.items.len()
+                        );
+                    }
                     _ => {
                         // Default table format
                         println!(
@@ -355,6 +401,7 @@ impl InventoryHandler {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn handle_update(
         id: &str,
         name: &Option<String>,
@@ -363,6 +410,7 @@ impl InventoryHandler {
         cost: &Option<f64>,
         category: &Option<String>,
         description: &Option<String>,
+        config: &AppConfig,
     ) -> ErpResult<()> {
         info!("Updating product: {}", id);
 
@@ -428,15 +476,16 @@ impl InventoryHandler {
         let _user_id = Uuid::new_v4();
 
         // 실제 데이터베이스 연결 사용
-        let db_connection = DatabaseManager::get_connection().await?;
-        let inventory_module = InventoryModule::new_with_postgres(db_connection.pool().clone());
+        let (inventory_service, audit) = Self::inventory_service(config).await?;
+        let before = inventory_service.get_product(id).await?;
         let user_id = Uuid::new_v4(); // TODO: Get from auth context
-        let response = inventory_module
-            .service()
-            .update_product(id, request, user_id)
-            .await;
+        let response = inventory_service.update_product(id, request, user_id).await;
         match response {
             Ok(product) => {
+                audit
+                    .modified("products", &product.sku, Some(&before), &product)
+                    .await;
+
                 println!("✅ 제품이 성공적으로 수정되었습니다!");
                 println!();
 
@@ -479,18 +528,17 @@ impl InventoryHandler {
         }
     }
 
-    async fn handle_remove(id: &str, force: bool) -> ErpResult<()> {
+    async fn handle_remove(id: &str, force: bool, config: &AppConfig) -> ErpResult<()> {
         info!("Removing product: {} (force: {})", id, force);
 
         // 입력 검증
         let validated_id = CliValidator::validate_id_or_sku(id)?;
 
         // 실제 데이터베이스 연결 사용
-        let db_connection = DatabaseManager::get_connection().await?;
-        let inventory_module = InventoryModule::new_with_postgres(db_connection.pool().clone());
+        let (inventory_service, audit) = Self::inventory_service(config).await?;
 
         // 제품 정보 조회
-        let product = match inventory_module.service().get_product(&validated_id).await {
+        let product = match inventory_service.get_product(&validated_id).await {
             Ok(product) => product,
             Err(e) => {
                 error!("Product not found: {}", validated_id);
@@ -518,12 +566,20 @@ impl InventoryHandler {
 
         // 실제 삭제 수행
         let user_id = Uuid::new_v4(); // TODO: Get from auth context
-        match inventory_module
-            .service()
+        match inventory_service
             .delete_product(&validated_id, force, user_id)
             .await
         {
             Ok(()) => {
+                audit
+                    .deleted(
+                        "products",
+                        &product.sku,
+                        &product,
+                        &[("force", force.to_string())],
+                    )
+                    .await;
+
                 if force {
                     println!("✅ 제품이 완전히 삭제되었습니다.");
                 } else {
@@ -538,7 +594,11 @@ impl InventoryHandler {
         }
     }
 
-    async fn handle_low_stock(threshold: &Option<i32>, format: &str) -> ErpResult<()> {
+    async fn handle_low_stock(
+        threshold: &Option<i32>,
+        format: &str,
+        config: &AppConfig,
+    ) -> ErpResult<()> {
         info!("Getting low stock alerts with threshold: {:?}", threshold);
 
         let validated_threshold = match threshold {
@@ -547,15 +607,15 @@ impl InventoryHandler {
         };
 
         // Use actual inventory service instead of stubbed implementation
-        let db_connection = DatabaseManager::get_connection().await?;
-        let inventory_module = InventoryModule::new_with_postgres(db_connection.pool().clone());
-        let alerts_result = inventory_module
-            .service()
+        let (inventory_service, audit) = Self::inventory_service(config).await?;
+        let alerts_result = inventory_service
             .get_low_stock_alerts(validated_threshold)
             .await;
 
         match alerts_result {
             Ok(alerts) => {
+                audit.accessed("low_stock_alerts", None).await;
+
                 if alerts.is_empty() {
                     match format {
                         "json" => println!("[]"),
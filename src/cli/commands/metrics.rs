@@ -0,0 +1,172 @@
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use shaku::HasComponent;
+use sqlx::PgPool;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+use crate::app_module::{
+    AppModule, AppModuleBuilder, CustomerServiceProvider, DatabasePoolProvider,
+    SalesServiceProvider,
+};
+use crate::core::config::AppConfig;
+use crate::core::ops::metrics_exporter::{BusinessMetrics, MetricsExporter, MetricsSource};
+use crate::core::security::audit::{AuditAction, AuditFilters, AuditRepository, AuditSeverity};
+use crate::core::security::monitor::SecurityMetrics;
+use crate::modules::customers::CustomerService;
+use crate::modules::inventory::InventoryModule;
+use crate::modules::sales::{OrderStatus, SalesService};
+use crate::utils::error::{ErpError, ErpResult};
+
+/// 보안 지표를 집계하는 감사 로그 조회 구간
+const SECURITY_WINDOW_HOURS: i64 = 1;
+
+pub struct MetricsHandler;
+
+impl MetricsHandler {
+    pub async fn serve(bind: &str, config: &AppConfig) -> ErpResult<()> {
+        let addr: SocketAddr = bind.parse().map_err(|_| {
+            ErpError::validation("bind", format!("'{}'은(는) 올바른 주소가 아닙니다", bind))
+        })?;
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| ErpError::internal(format!("{}에 바인딩할 수 없습니다: {}", addr, e)))?;
+
+        let module = AppModuleBuilder::build_postgres(config).await?;
+        let source = ErpMetricsSource::new(&module)?;
+        let exporter = Arc::new(MetricsExporter::new(Arc::new(source)));
+
+        println!(
+            "📈 Prometheus 메트릭을 http://{}/metrics 에서 제공합니다. (Ctrl+C로 종료)",
+            listener.local_addr().unwrap_or(addr)
+        );
+
+        tokio::select! {
+            result = exporter.serve(listener) => result,
+            _ = tokio::signal::ctrl_c() => {
+                println!("메트릭 서버를 종료합니다.");
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 감사 로그와 각 업무 모듈에서 지표를 수집
+struct ErpMetricsSource {
+    pool: PgPool,
+    audit: Arc<dyn AuditRepository>,
+    inventory: InventoryModule,
+    sales: SalesService,
+    customers: CustomerService,
+}
+
+impl ErpMetricsSource {
+    fn new(module: &AppModule) -> ErpResult<Self> {
+        let connection = HasComponent::<dyn DatabasePoolProvider>::resolve_ref(module).connection();
+        Ok(Self {
+            pool: connection.pool()?.clone(),
+            audit: module.resolve(),
+            inventory: InventoryModule::from_service(module.resolve()),
+            sales: HasComponent::<dyn SalesServiceProvider>::resolve_ref(module).get_service(),
+            customers: HasComponent::<dyn CustomerServiceProvider>::resolve_ref(module)
+                .get_service(),
+        })
+    }
+
+    async fn count_recent(&self, filters: AuditFilters) -> ErpResult<u32> {
+        let filters = AuditFilters {
+            start_time: Some(Utc::now() - Duration::hours(SECURITY_WINDOW_HOURS)),
+            ..filters
+        };
+        Ok(self.audit.count_events(filters).await? as u32)
+    }
+}
+
+#[async_trait::async_trait]
+impl MetricsSource for ErpMetricsSource {
+    fn active_connections(&self) -> u32 {
+        self.pool.size()
+    }
+
+    async fn security_metrics(&self) -> ErpResult<SecurityMetrics> {
+        Ok(SecurityMetrics {
+            failed_login_attempts: self
+                .count_recent(AuditFilters {
+                    action: Some(AuditAction::LoginFailed),
+                    ..Default::default()
+                })
+                .await?,
+            security_violations: self
+                .count_recent(AuditFilters {
+                    action: Some(AuditAction::SecurityViolation),
+                    ..Default::default()
+                })
+                .await?,
+            suspicious_activities: self
+                .count_recent(AuditFilters {
+                    severity: Some(AuditSeverity::Critical),
+                    ..Default::default()
+                })
+                .await?,
+            ..SecurityMetrics::default()
+        })
+    }
+
+    async fn business_metrics(&self) -> ErpResult<BusinessMetrics> {
+        let inventory = self.inventory.get_statistics().await?;
+        let sales = self.sales.get_sales_statistics(None, None).await?;
+        let debtors = self
+            .customers
+            .get_customers_with_outstanding_balance()
+            .await?;
+
+        let open_orders = sales
+            .orders_by_status
+            .iter()
+            .filter(|(status, _)| is_open(status))
+            .map(|(_, count)| count)
+            .sum();
+        let mut orders_by_status: Vec<(String, i64)> = sales
+            .orders_by_status
+            .iter()
+            .map(|(status, count)| (status.to_string().to_lowercase(), *count))
+            .collect();
+        orders_by_status.sort();
+
+        Ok(BusinessMetrics {
+            orders_by_status,
+            open_orders,
+            low_stock_products: inventory.low_stock_count,
+            out_of_stock_products: inventory.out_of_stock_count,
+            total_stock_value: inventory.total_value,
+            outstanding_receivables: debtors.iter().map(|c| c.current_balance).sum::<Decimal>(),
+            customers_with_outstanding_balance: debtors.len() as i64,
+        })
+    }
+}
+
+/// 접수 후 배송이 끝나지 않은 주문 (초안, 완료, 취소, 반품 제외)
+fn is_open(status: &OrderStatus) -> bool {
+    matches!(
+        status,
+        OrderStatus::Pending
+            | OrderStatus::Confirmed
+            | OrderStatus::Processing
+            | OrderStatus::Shipped
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_order_statuses() {
+        assert!(is_open(&OrderStatus::Pending));
+        assert!(is_open(&OrderStatus::Shipped));
+        assert!(!is_open(&OrderStatus::Draft));
+        assert!(!is_open(&OrderStatus::Delivered));
+        assert!(!is_open(&OrderStatus::Cancelled));
+    }
+}
@@ -1,7 +1,12 @@
 use crate::cli::parser::MigrateCommands;
 use crate::core::config::AppConfig;
-use crate::core::database::connection::DatabaseManager;
-use crate::core::database::migration::{DatabaseMigrator, MigrationRunner, PostgresMigrator};
+use crate::core::database::connection::{sqlite_migrations_dir, DatabaseBackend, DatabaseManager};
+use crate::core::database::migration::{
+    render_baseline, DatabaseMigrator, MigrationFile, MigrationRunner, PlanAction,
+    PlannedMigration, PostgresMigrator, SqliteMigrator,
+};
+use crate::core::database::models::schema::{EXPECTED_ENUMS, EXPECTED_TABLES};
+use crate::core::database::schema_check::verify_postgres_schema;
 use crate::utils::error::{ErpError, ErpResult};
 use chrono::{DateTime, Local};
 use comfy_table::{Cell, Color, Table};
@@ -12,10 +17,16 @@ use tracing::{error, info, warn};
 pub async fn handle_migrate_command(command: MigrateCommands, config: AppConfig) -> ErpResult<()> {
     match command {
         MigrateCommands::Init { force } => init_database(config, force).await,
-        MigrateCommands::Up { target, dir } => run_migrations(config, target, dir).await,
+        MigrateCommands::Up {
+            target,
+            dir,
+            dry_run,
+        } => run_migrations(config, target, dir, dry_run).await,
         MigrateCommands::Down { target, dir } => rollback_migrations(config, target, dir).await,
         MigrateCommands::Status { dir } => show_migration_status(config, dir).await,
         MigrateCommands::Generate { name, dir } => generate_migration(name, dir).await,
+        MigrateCommands::Baseline { dir } => create_baseline(config, dir).await,
+        MigrateCommands::VerifySchema => verify_schema(config).await,
         MigrateCommands::Test => test_database_connection(config).await,
     }
 }
@@ -42,12 +53,18 @@ async fn init_database(config: AppConfig, force: bool) -> ErpResult<()> {
     Ok(())
 }
 
-async fn run_migrations(config: AppConfig, _target: Option<String>, dir: String) -> ErpResult<()> {
+async fn run_migrations(
+    config: AppConfig,
+    _target: Option<String>,
+    dir: String,
+    dry_run: bool,
+) -> ErpResult<()> {
     info!("Running database migrations...");
 
     DatabaseManager::initialize(config.database.clone()).await?;
     let connection = DatabaseManager::get_connection().await?;
     let migrator = create_migrator(&connection).await?;
+    let dir = migrations_dir(&connection, dir);
     let mut runner = MigrationRunner::new(migrator);
 
     // Load migration files from directory
@@ -56,6 +73,15 @@ async fn run_migrations(config: AppConfig, _target: Option<String>, dir: String)
         ErpError::io(format!("Migration loading failed: {}", e))
     })?;
 
+    if dry_run {
+        let plan = runner.plan().await.map_err(|e| {
+            error!("Migration validation failed: {}", e);
+            ErpError::database(format!("Migration plan failed: {}", e))
+        })?;
+        print_migration_plan(&plan);
+        return Ok(());
+    }
+
     // Initialize migration schema if needed
     runner.initialize().await.map_err(|e| {
         error!("Failed to initialize migration schema: {}", e);
@@ -91,6 +117,7 @@ async fn rollback_migrations(
     DatabaseManager::initialize(config.database.clone()).await?;
     let connection = DatabaseManager::get_connection().await?;
     let migrator = create_migrator(&connection).await?;
+    let dir = migrations_dir(&connection, dir);
     let mut runner = MigrationRunner::new(migrator);
 
     // Load migration files
@@ -124,6 +151,7 @@ async fn show_migration_status(config: AppConfig, dir: String) -> ErpResult<()>
     DatabaseManager::initialize(config.database.clone()).await?;
     let connection = DatabaseManager::get_connection().await?;
     let migrator = create_migrator(&connection).await?;
+    let dir = migrations_dir(&connection, dir);
     let mut runner = MigrationRunner::new(migrator);
 
     // Load migration files
@@ -147,16 +175,39 @@ async fn show_migration_status(config: AppConfig, dir: String) -> ErpResult<()>
         Cell::new("Executed At").fg(Color::Blue),
     ]);
 
-    // Show applied migrations
+    // Show applied migrations, flagging files edited after they ran
     for migration in &status.applied {
+        let state = if status.conflicts.contains(&migration.version) {
+            Cell::new("Modified").fg(Color::Red)
+        } else if status.superseded.contains(&migration.version) {
+            Cell::new("Superseded").fg(Color::DarkGrey)
+        } else {
+            Cell::new("Applied").fg(Color::Green)
+        };
         table.add_row(vec![
             Cell::new(&migration.version),
             Cell::new(&migration.name),
-            Cell::new("Applied").fg(Color::Green),
+            state,
             Cell::new(format_datetime(&migration.executed_at)),
         ]);
     }
 
+    // Show history skipped on this database because a baseline replaces it
+    for migration in runner.migrations() {
+        let applied = status
+            .applied
+            .iter()
+            .any(|m| m.version == migration.version);
+        if !applied && status.superseded.contains(&migration.version) {
+            table.add_row(vec![
+                Cell::new(&migration.version),
+                Cell::new(&migration.name),
+                Cell::new("Superseded").fg(Color::DarkGrey),
+                Cell::new("-"),
+            ]);
+        }
+    }
+
     // Show pending migrations
     for migration in &status.pending {
         table.add_row(vec![
@@ -174,12 +225,19 @@ async fn show_migration_status(config: AppConfig, dir: String) -> ErpResult<()>
     println!("\nSummary:");
     println!("  Applied migrations: {}", status.applied_count());
     println!("  Pending migrations: {}", status.pending_count());
+    if !status.superseded.is_empty() {
+        println!("  Superseded by baseline: {}", status.superseded.len());
+    }
 
     if status.has_conflicts() {
-        println!("  ⚠️  Conflicts detected: {}", status.conflicts.len());
+        println!(
+            "  ⚠️  Modified after apply (checksum mismatch): {}",
+            status.conflicts.len()
+        );
         for conflict in &status.conflicts {
             println!("    - {}", conflict);
         }
+        println!("  Restore the original files or add a new migration; `migrate up` refuses to run until then.");
     }
 
     if status.is_up_to_date() {
@@ -191,6 +249,122 @@ async fn show_migration_status(config: AppConfig, dir: String) -> ErpResult<()>
     Ok(())
 }
 
+/// Prints pending migrations and their statements without touching the database
+fn print_migration_plan(plan: &[PlannedMigration<'_>]) {
+    if plan.is_empty() {
+        println!("✅ Database is already up to date - nothing to apply");
+        return;
+    }
+
+    println!("📋 Dry run - {} migration(s) would be applied:", plan.len());
+    for step in plan {
+        let migration = step.migration;
+        if step.action == PlanAction::Record {
+            println!(
+                "\n== {} {} (baseline - recorded only, schema already present) ==",
+                migration.version, migration.name
+            );
+            continue;
+        }
+
+        let mode = if migration.no_transaction {
+            "no transaction"
+        } else {
+            "transaction"
+        };
+        println!(
+            "\n== {} {} ({}) ==",
+            migration.version, migration.name, mode
+        );
+        for statement in migration.up_statements() {
+            println!("{};", statement);
+        }
+    }
+}
+
+/// Squashes the current schema into a baseline migration file.
+///
+/// The database must be fully migrated so the baseline matches the history
+/// it replaces; it is then recorded as applied here.
+async fn create_baseline(config: AppConfig, dir: String) -> ErpResult<()> {
+    info!("Creating baseline migration...");
+
+    DatabaseManager::initialize(config.database.clone()).await?;
+    let connection = DatabaseManager::get_connection().await?;
+    let migrator = create_migrator(&connection).await?;
+    let dir = migrations_dir(&connection, dir);
+    let mut runner = MigrationRunner::new(migrator);
+
+    runner.load_migrations_from_directory(&dir).map_err(|e| {
+        error!("Failed to load migration files: {}", e);
+        ErpError::io(format!("Migration loading failed: {}", e))
+    })?;
+
+    let status = runner.get_migration_status().await.map_err(|e| {
+        error!("Failed to get migration status: {}", e);
+        ErpError::database(format!("Status check failed: {}", e))
+    })?;
+
+    if status.has_conflicts() {
+        return Err(ErpError::validation(
+            "migrations",
+            format!(
+                "Migrations {} were edited after they were applied; resolve that before creating a baseline",
+                status.conflicts.join(", ")
+            ),
+        ));
+    }
+    if status.pending_count() > 0 {
+        return Err(ErpError::validation(
+            "migrations",
+            format!(
+                "Database has {} pending migration(s); run `erp migrate up` before creating a baseline",
+                status.pending_count()
+            ),
+        ));
+    }
+    let Some(latest) = status.applied.iter().map(|m| m.version.clone()).max() else {
+        return Err(ErpError::validation(
+            "migrations",
+            "Database has no applied migrations to squash",
+        ));
+    };
+
+    let statements = runner.dump_schema().await.map_err(|e| {
+        error!("Failed to read database schema: {}", e);
+        ErpError::database(format!("Schema introspection failed: {}", e))
+    })?;
+
+    let version = format!("{:03}", get_next_migration_version(&dir).unwrap_or(1));
+    let content = render_baseline(&version, &latest, &statements);
+    let filepath = Path::new(&dir).join(format!("{}_baseline.sql", version));
+
+    fs::write(&filepath, &content).map_err(|e| {
+        error!("Failed to write baseline file: {}", e);
+        ErpError::io(format!("Baseline file creation failed: {}", e))
+    })?;
+
+    let baseline = MigrationFile::parse(&version, "baseline", &content);
+    runner.record_migration(&baseline).await.map_err(|e| {
+        error!("Failed to record baseline: {}", e);
+        ErpError::database(format!("Baseline recording failed: {}", e))
+    })?;
+
+    println!(
+        "✅ Created baseline {} with {} statement(s): {}",
+        version,
+        statements.len(),
+        filepath.display()
+    );
+    println!(
+        "📝 Fresh databases now apply only the baseline; migrations up to {} are kept as history",
+        latest
+    );
+
+    info!("Baseline migration generated: {}", filepath.display());
+    Ok(())
+}
+
 async fn generate_migration(name: String, dir: String) -> ErpResult<()> {
     info!("Generating new migration: {}", name);
 
@@ -214,6 +388,8 @@ async fn generate_migration(name: String, dir: String) -> ErpResult<()> {
         r#"-- Migration: {}
 -- Version: {}
 -- Description: {}
+-- Add "-- no-transaction" on its own line to run outside a transaction
+-- (required for CREATE INDEX CONCURRENTLY)
 
 -- Add your migration SQL here
 
@@ -237,6 +413,62 @@ async fn generate_migration(name: String, dir: String) -> ErpResult<()> {
     Ok(())
 }
 
+/// Reports drift between the live schema and what the Postgres repositories expect
+async fn verify_schema(config: AppConfig) -> ErpResult<()> {
+    info!("Verifying database schema...");
+
+    DatabaseManager::initialize(config.database.clone()).await?;
+    let connection = DatabaseManager::get_connection().await?;
+    if connection.backend() != DatabaseBackend::Postgres {
+        return Err(ErpError::validation(
+            "database",
+            "verify-schema checks the PostgreSQL schema used by the Postgres repositories",
+        ));
+    }
+
+    let issues = verify_postgres_schema(connection.pool()?)
+        .await
+        .map_err(|e| {
+            error!("Failed to read database schema: {}", e);
+            ErpError::database(format!("Schema introspection failed: {}", e))
+        })?;
+
+    if issues.is_empty() {
+        println!(
+            "✅ Database schema matches the {} tables and {} enum types the repositories expect",
+            EXPECTED_TABLES.len(),
+            EXPECTED_ENUMS.len()
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        Cell::new("Object").fg(Color::Blue),
+        Cell::new("Issue").fg(Color::Blue),
+        Cell::new("Detail").fg(Color::Blue),
+    ]);
+    for issue in &issues {
+        table.add_row(vec![
+            Cell::new(issue.subject()),
+            Cell::new(issue.kind()).fg(Color::Red),
+            Cell::new(issue.to_string()),
+        ]);
+    }
+
+    println!("Schema Drift Report:");
+    println!("{}", table);
+    println!(
+        "\n❌ Found {} schema issue(s); add a migration or update src/core/database/models/schema.rs",
+        issues.len()
+    );
+
+    Err(ErpError::validation(
+        "schema",
+        format!("{} schema issue(s) found", issues.len()),
+    ))
+}
+
 async fn test_database_connection(config: AppConfig) -> ErpResult<()> {
     info!("Testing database connection...");
 
@@ -267,10 +499,26 @@ async fn test_database_connection(config: AppConfig) -> ErpResult<()> {
 async fn create_migrator(
     connection: &std::sync::Arc<crate::core::database::connection::DatabaseConnection>,
 ) -> ErpResult<DatabaseMigrator> {
-    // Currently the system only supports PostgreSQL
-    Ok(DatabaseMigrator::Postgres(PostgresMigrator::new(
-        connection.pool().clone(),
-    )))
+    Ok(match connection.backend() {
+        DatabaseBackend::Postgres => {
+            DatabaseMigrator::Postgres(PostgresMigrator::new(connection.pool()?.clone()))
+        }
+        DatabaseBackend::Sqlite => {
+            DatabaseMigrator::Sqlite(SqliteMigrator::new(connection.sqlite_pool()?.clone()))
+        }
+    })
+}
+
+/// SQLite databases use their own migration set under `<dir>/sqlite`
+fn migrations_dir(
+    connection: &std::sync::Arc<crate::core::database::connection::DatabaseConnection>,
+    dir: String,
+) -> String {
+    match connection.backend() {
+        DatabaseBackend::Postgres => dir,
+        DatabaseBackend::Sqlite if dir.trim_end_matches('/').ends_with("sqlite") => dir,
+        DatabaseBackend::Sqlite => sqlite_migrations_dir(&dir),
+    }
 }
 
 fn get_next_migration_version(dir: &str) -> Option<u32> {
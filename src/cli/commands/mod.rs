@@ -1,12 +1,24 @@
+pub mod audit;
+pub mod backup;
 pub mod config;
 pub mod customers;
+pub mod data;
 pub mod inventory;
+pub mod metrics;
 pub mod migrate;
 pub mod reports;
 pub mod sales;
+pub mod security;
+pub mod system;
 
+pub use audit::AuditHandler;
+pub use backup::BackupHandler;
 pub use config::ConfigHandler;
 pub use customers::CustomerHandler;
+pub use data::DataHandler;
 pub use inventory::InventoryHandler;
+pub use metrics::MetricsHandler;
 pub use reports::ReportsHandler;
 pub use sales::SalesHandler;
+pub use security::SecurityHandler;
+pub use system::SystemHandler;
@@ -1,20 +1,39 @@
 use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use shaku::HasComponent;
 
-use crate::cli::parser::ReportCommands;
+use crate::app_module::{AppModuleBuilder, ReportsServiceProvider};
+use crate::cli::commands::security::enforce_export_limit;
+use crate::cli::parser::{ReportCommands, ReportScheduleCommands};
 use crate::cli::validator::CliValidator;
 use crate::core::config::AppConfig;
+use crate::core::database::connection::DatabaseManager;
+use crate::core::security::pii::PiiCipher;
+use crate::modules::reports::custom::{DateRangeSpec, QueryParam};
 use crate::modules::reports::{
-    create_reports_service, CustomerAnalysisReport, FinancialOverviewReport, InventoryStatusReport,
-    ReportFilters, ReportFormat, ReportPeriod, ReportRequest, ReportType, SalesSummaryReport,
+    create_reports_service, CustomReportResult, CustomReportRunner, CustomReportSpec,
+    CustomerAnalysisReport, FinancialOverviewReport, InventoryStatusReport, ReportFilters,
+    ReportFormat, ReportPeriod, ReportRequest, ReportRun, ReportRunStatus, ReportSchedule,
+    ReportScheduleRepository, ReportScheduler, ReportType, ReportsService, SalesSummaryReport,
 };
-use crate::utils::error::ErpResult;
+use crate::utils::error::{ErpError, ErpResult};
+use chrono::{DateTime, Local, Utc};
+use std::sync::Arc;
 
 pub struct ReportsHandler;
 
 impl ReportsHandler {
-    pub async fn handle(cmd: &ReportCommands, _config: &AppConfig) -> ErpResult<()> {
-        // 보고서 서비스 초기화 (실제 구현에서는 데이터베이스 연결 사용)
-        let _reports_service = create_reports_service(None); // Mock 사용
+    pub async fn handle(cmd: &ReportCommands, config: &AppConfig) -> ErpResult<()> {
+        // 콘솔 외 형식은 파일로 내보내므로 대량 내보내기 한도에 포함
+        if Self::export_format(cmd).is_some_and(|format| {
+            format
+                .parse::<ReportFormat>()
+                .is_ok_and(|format| format != ReportFormat::Console)
+        }) {
+            enforce_export_limit(config).await?;
+        }
+
+        // 보고서 서비스 초기화 (SQLite 파일은 실제 데이터, 그 외에는 Mock 사용)
+        let reports_service = Self::reports_service(config).await?;
 
         match cmd {
             ReportCommands::SalesSummary {
@@ -23,22 +42,53 @@ impl ReportsHandler {
                 to_date,
                 format,
                 output,
-            } => Self::handle_sales_summary(period, from_date, to_date, format, output).await,
+                include_charts,
+            } => {
+                Self::handle_sales_summary(
+                    &reports_service,
+                    period,
+                    from_date,
+                    to_date,
+                    format,
+                    output,
+                    *include_charts,
+                )
+                .await
+            }
             ReportCommands::InventoryStatus {
                 format,
                 output,
                 category,
                 low_stock_only,
                 threshold,
+                include_charts,
             } => {
-                Self::handle_inventory_status(format, output, category, *low_stock_only, *threshold)
-                    .await
+                Self::handle_inventory_status(
+                    &reports_service,
+                    format,
+                    output,
+                    category,
+                    *low_stock_only,
+                    *threshold,
+                    *include_charts,
+                )
+                .await
             }
             ReportCommands::CustomerAnalysis {
                 months,
                 format,
                 output,
-            } => Self::handle_customer_analysis(*months, format, output).await,
+                include_charts,
+            } => {
+                Self::handle_customer_analysis(
+                    &reports_service,
+                    *months,
+                    format,
+                    output,
+                    *include_charts,
+                )
+                .await
+            }
             ReportCommands::FinancialOverview {
                 from_date,
                 to_date,
@@ -48,6 +98,7 @@ impl ReportsHandler {
                 include_charts,
             } => {
                 Self::handle_financial_overview(
+                    &reports_service,
                     from_date,
                     to_date,
                     period,
@@ -57,15 +108,44 @@ impl ReportsHandler {
                 )
                 .await
             }
+            ReportCommands::Custom {
+                spec,
+                from_date,
+                to_date,
+                format,
+                output,
+                include_charts,
+                show_sql,
+            } => {
+                Self::handle_custom(
+                    config,
+                    spec,
+                    from_date,
+                    to_date,
+                    format,
+                    output,
+                    *include_charts,
+                    *show_sql,
+                )
+                .await
+            }
+            ReportCommands::Schedule(schedule_cmd) => {
+                Self::handle_schedule(schedule_cmd, config).await
+            }
+            ReportCommands::RunScheduler { interval, once } => {
+                Self::handle_run_scheduler(config, *interval, *once).await
+            }
         }
     }
 
     async fn handle_sales_summary(
+        reports_service: &ReportsService,
         period: &str,
         from_date: &Option<String>,
         to_date: &Option<String>,
         format: &str,
         output: &Option<String>,
+        include_charts: bool,
     ) -> ErpResult<()> {
         // 입력 검증
         let validated_period = CliValidator::validate_report_period(period)?;
@@ -73,9 +153,6 @@ impl ReportsHandler {
             CliValidator::validate_date_range(from_date, to_date)?;
         let validated_format: ReportFormat = format.parse()?;
 
-        // 보고서 서비스 초기화
-        let reports_service = create_reports_service(None); // Mock 사용
-
         // 기간 설정
         let report_period = if let (Some(from), Some(to)) = (validated_from_date, validated_to_date)
         {
@@ -97,7 +174,7 @@ impl ReportsHandler {
             format: validated_format.clone(),
             output_path: output.clone(),
             filters: ReportFilters::default(),
-            include_charts: false,
+            include_charts,
         };
 
         let report = reports_service.generate_sales_summary(&request).await?;
@@ -124,18 +201,17 @@ impl ReportsHandler {
     }
 
     async fn handle_inventory_status(
+        reports_service: &ReportsService,
         format: &str,
         output: &Option<String>,
         category: &Option<String>,
         low_stock_only: bool,
         threshold: Option<u32>,
+        include_charts: bool,
     ) -> ErpResult<()> {
         // 입력 검증
         let validated_format: ReportFormat = format.parse()?;
 
-        // 보고서 서비스 초기화
-        let reports_service = create_reports_service(None); // Mock 사용
-
         let filters = ReportFilters {
             categories: category.as_ref().map(|c| vec![c.clone()]),
             low_stock_only,
@@ -149,7 +225,7 @@ impl ReportsHandler {
             format: validated_format.clone(),
             output_path: output.clone(),
             filters,
-            include_charts: false,
+            include_charts,
         };
 
         let report = reports_service.generate_inventory_status(&request).await?;
@@ -176,9 +252,11 @@ impl ReportsHandler {
     }
 
     async fn handle_customer_analysis(
+        reports_service: &ReportsService,
         months: u32,
         format: &str,
         output: &Option<String>,
+        include_charts: bool,
     ) -> ErpResult<()> {
         // 입력 검증
         let validated_format: ReportFormat = format.parse()?;
@@ -190,16 +268,13 @@ impl ReportsHandler {
             ));
         }
 
-        // 보고서 서비스 초기화
-        let reports_service = create_reports_service(None); // Mock 사용
-
         let request = ReportRequest {
             report_type: ReportType::CustomerAnalysis { months },
             period: ReportPeriod::Monthly, // 고객 분석은 월 단위
             format: validated_format.clone(),
             output_path: output.clone(),
             filters: ReportFilters::default(),
-            include_charts: false,
+            include_charts,
         };
 
         let report = reports_service
@@ -228,6 +303,7 @@ impl ReportsHandler {
     }
 
     async fn handle_financial_overview(
+        reports_service: &ReportsService,
         from_date: &Option<String>,
         to_date: &Option<String>,
         period: &Option<String>,
@@ -240,9 +316,6 @@ impl ReportsHandler {
             CliValidator::validate_date_range(from_date, to_date)?;
         let validated_format: ReportFormat = format.parse()?;
 
-        // 보고서 서비스 초기화
-        let reports_service = create_reports_service(None); // Mock 사용
-
         // 기간 설정
         let report_period = if let (Some(from), Some(to)) = (validated_from_date, validated_to_date)
         {
@@ -301,6 +374,334 @@ impl ReportsHandler {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_custom(
+        config: &AppConfig,
+        spec_path: &str,
+        from_date: &Option<String>,
+        to_date: &Option<String>,
+        format: &str,
+        output: &Option<String>,
+        include_charts: bool,
+        show_sql: bool,
+    ) -> ErpResult<()> {
+        // 입력 검증
+        let validated_format: ReportFormat = format.parse()?;
+        let (validated_from_date, validated_to_date) =
+            CliValidator::validate_date_range(from_date, to_date)?;
+
+        let mut spec = CustomReportSpec::from_file(spec_path).await?;
+
+        // 명령행 날짜가 지정되면 스펙의 기간을 덮어씀
+        if validated_from_date.is_some() || validated_to_date.is_some() {
+            spec.date_range = Some(DateRangeSpec {
+                from: validated_from_date,
+                to: validated_to_date,
+                period: None,
+            });
+        }
+
+        if show_sql {
+            let query = spec.build_query()?;
+            println!("{}", query.sql);
+            for (index, param) in query.params.iter().enumerate() {
+                match param {
+                    QueryParam::Text(value) => println!("  ${} = {}", index + 1, value),
+                    QueryParam::TextList(values) => {
+                        println!("  ${} = [{}]", index + 1, values.join(", "))
+                    }
+                }
+            }
+            println!();
+        }
+
+        let connection = DatabaseManager::get_connection().await?;
+        // 암호화된 고객 필드를 조회할 때만 PII 키가 필요함
+        let cipher = if spec.uses_encrypted_fields() {
+            PiiCipher::from_config(&config.security)?
+        } else {
+            None
+        };
+        // 사용자 정의 보고서는 읽기 복제본에서 실행 (장애 시 기본 DB)
+        let pool = connection.postgres_reads()?.pool().await;
+        let runner = CustomReportRunner::new(pool).with_pii_cipher(cipher);
+        let result = runner.run(&spec).await?;
+
+        match validated_format {
+            ReportFormat::Console => {
+                Self::display_custom_report_console(&result);
+            }
+            _ => {
+                let reports_service = create_reports_service(None);
+                let path = reports_service
+                    .export_custom_report(
+                        &result,
+                        &validated_format,
+                        output.as_deref(),
+                        include_charts,
+                    )
+                    .await?;
+                println!("보고서가 저장되었습니다: {}", path);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 보고서를 생성하는 명령어의 출력 형식
+    fn export_format(cmd: &ReportCommands) -> Option<&str> {
+        match cmd {
+            ReportCommands::SalesSummary { format, .. }
+            | ReportCommands::InventoryStatus { format, .. }
+            | ReportCommands::CustomerAnalysis { format, .. }
+            | ReportCommands::FinancialOverview { format, .. }
+            | ReportCommands::Custom { format, .. } => Some(format),
+            ReportCommands::Schedule(_) | ReportCommands::RunScheduler { .. } => None,
+        }
+    }
+
+    /// 데이터베이스에 연결되어 있으면 애플리케이션 모듈에서 보고서 서비스를 해석
+    ///
+    /// SQLite 연결은 실제 데이터로 보고서를 생성하고, 연결이 없으면 Mock 데이터를 사용합니다.
+    async fn reports_service(config: &AppConfig) -> ErpResult<ReportsService> {
+        match DatabaseManager::get_connection().await {
+            Ok(connection) => {
+                let module = AppModuleBuilder::build_for(config, connection).await?;
+                Ok(HasComponent::<dyn ReportsServiceProvider>::resolve_ref(&module).get_service())
+            }
+            Err(_) => Ok(create_reports_service(None)),
+        }
+    }
+
+    async fn schedule_repository(
+        config: &AppConfig,
+    ) -> ErpResult<Arc<dyn ReportScheduleRepository>> {
+        let module = AppModuleBuilder::build_postgres(config).await?;
+        Ok(module.resolve())
+    }
+
+    async fn handle_schedule(cmd: &ReportScheduleCommands, config: &AppConfig) -> ErpResult<()> {
+        match cmd {
+            ReportScheduleCommands::Add {
+                name,
+                cron,
+                report,
+                period,
+                format,
+                months,
+                category,
+                low_stock_only,
+                threshold,
+                include_charts,
+            } => {
+                let mut report_type: ReportType = report.parse()?;
+                match (&mut report_type, months) {
+                    (ReportType::CustomerAnalysis { months: target }, Some(months)) => {
+                        if *months == 0 || *months > 120 {
+                            return Err(ErpError::validation(
+                                "months",
+                                "분석 기간은 1-120개월 범위여야 합니다",
+                            ));
+                        }
+                        *target = *months;
+                    }
+                    (_, Some(_)) => {
+                        return Err(ErpError::validation(
+                            "months",
+                            "--months는 customer-analysis 보고서에만 사용할 수 있습니다",
+                        ))
+                    }
+                    _ => {}
+                }
+
+                let request = ReportRequest {
+                    report_type,
+                    period: period.parse()?,
+                    format: format.parse()?,
+                    output_path: None,
+                    filters: ReportFilters {
+                        categories: category.as_ref().map(|c| vec![c.clone()]),
+                        low_stock_only: *low_stock_only,
+                        low_stock_threshold: *threshold,
+                        ..Default::default()
+                    },
+                    include_charts: *include_charts,
+                };
+
+                let schedule = ReportSchedule::new(name.as_str(), cron, request)?;
+                let repository = Self::schedule_repository(config).await?;
+                repository.create_schedule(&schedule).await?;
+
+                println!("✅ 보고서 예약이 추가되었습니다: {}", schedule.name);
+                println!(
+                    "   다음 실행: {}",
+                    Self::format_local_time(schedule.next_run_at)
+                );
+                Ok(())
+            }
+            ReportScheduleCommands::List { format } => {
+                let repository = Self::schedule_repository(config).await?;
+                let schedules = repository.list_schedules().await?;
+
+                match format.to_lowercase().as_str() {
+                    "json" => {
+                        let json = serde_json::to_string_pretty(&schedules).map_err(|e| {
+                            ErpError::serialization(format!("JSON 직렬화 실패: {}", e))
+                        })?;
+                        println!("{}", json);
+                    }
+                    "table" => Self::display_schedules(&schedules),
+                    _ => {
+                        return Err(ErpError::validation(
+                            "format",
+                            "지원되지 않는 형식입니다. 사용 가능한 형식: table, json",
+                        ))
+                    }
+                }
+                Ok(())
+            }
+            ReportScheduleCommands::Remove { name } => {
+                let repository = Self::schedule_repository(config).await?;
+                if repository.delete_schedule(name).await? {
+                    println!("✅ 보고서 예약이 삭제되었습니다: {}", name);
+                    Ok(())
+                } else {
+                    Err(ErpError::not_found("report_schedule", name))
+                }
+            }
+            ReportScheduleCommands::History { name, limit } => {
+                let repository = Self::schedule_repository(config).await?;
+                let runs = repository.list_runs(name.as_deref(), *limit).await?;
+                Self::display_schedule_runs(&runs);
+                Ok(())
+            }
+        }
+    }
+
+    async fn handle_run_scheduler(config: &AppConfig, interval: u64, once: bool) -> ErpResult<()> {
+        if interval == 0 {
+            return Err(ErpError::validation(
+                "interval",
+                "확인 주기는 1초 이상이어야 합니다",
+            ));
+        }
+
+        let module = AppModuleBuilder::build_postgres(config).await?;
+        let scheduler = ReportScheduler::new(
+            module.resolve(),
+            HasComponent::<dyn ReportsServiceProvider>::resolve_ref(&module).get_service(),
+            &config.reports.output_dir,
+        );
+
+        if once {
+            let runs = scheduler.run_due(Utc::now()).await?;
+            if runs.is_empty() {
+                println!("실행할 예약 보고서가 없습니다.");
+            } else {
+                Self::display_schedule_runs(&runs);
+            }
+            if runs.iter().any(|r| r.status == ReportRunStatus::Failed) {
+                return Err(ErpError::internal("일부 예약 보고서 생성에 실패했습니다"));
+            }
+            return Ok(());
+        }
+
+        println!(
+            "보고서 스케줄러를 시작합니다 (확인 주기: {}초, 출력: {}). 종료하려면 Ctrl+C를 누르세요.",
+            interval, config.reports.output_dir
+        );
+        scheduler
+            .run_forever(std::time::Duration::from_secs(interval))
+            .await
+    }
+
+    fn format_local_time(at: DateTime<Utc>) -> String {
+        at.with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    }
+
+    fn display_schedules(schedules: &[ReportSchedule]) {
+        if schedules.is_empty() {
+            println!("등록된 보고서 예약이 없습니다.");
+            return;
+        }
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(vec![
+                "이름",
+                "cron",
+                "보고서",
+                "기간",
+                "형식",
+                "다음 실행",
+                "마지막 실행",
+                "상태",
+            ]);
+
+        for schedule in schedules {
+            table.add_row(vec![
+                schedule.name.clone(),
+                schedule.cron_expression.clone(),
+                schedule.request.report_type.to_string(),
+                schedule.request.period.to_string(),
+                schedule.request.format.to_string(),
+                Self::format_local_time(schedule.next_run_at),
+                schedule
+                    .last_run_at
+                    .map(Self::format_local_time)
+                    .unwrap_or_else(|| "-".to_string()),
+                if schedule.enabled {
+                    "활성".to_string()
+                } else {
+                    "비활성".to_string()
+                },
+            ]);
+        }
+
+        println!("{}", table);
+    }
+
+    fn display_schedule_runs(runs: &[ReportRun]) {
+        if runs.is_empty() {
+            println!("실행 이력이 없습니다.");
+            return;
+        }
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(vec!["시작 시각", "스케줄", "상태", "소요(초)", "결과"]);
+
+        for run in runs {
+            let status = match run.status {
+                ReportRunStatus::Success => "✅ 성공",
+                ReportRunStatus::Failed => "❌ 실패",
+            };
+            let detail = run
+                .output_path
+                .clone()
+                .or_else(|| run.error_message.clone())
+                .unwrap_or_default();
+            table.add_row(vec![
+                Self::format_local_time(run.started_at),
+                run.schedule_name.clone(),
+                status.to_string(),
+                format!(
+                    "{:.1}",
+                    (run.finished_at - run.started_at).num_milliseconds() as f64 / 1000.0
+                ),
+                detail,
+            ]);
+        }
+
+        println!("{}", table);
+    }
+
     // Console display methods
     fn display_sales_summary_console(report: &SalesSummaryReport) {
         println!("\n=== 매출 요약 보고서 ===");
@@ -530,7 +931,13 @@ impl ReportsHandler {
             segments_table
                 .load_preset(UTF8_FULL)
                 .apply_modifier(UTF8_ROUND_CORNERS)
-                .set_header(vec!["세그먼트", "고객 수", "총 매출", "평균 주문 빈도"]);
+                .set_header(vec![
+                    "세그먼트",
+                    "고객 수",
+                    "총 매출",
+                    "평균 주문 빈도",
+                    "이탈 예측",
+                ]);
 
             for segment in &report.customer_segments {
                 segments_table.add_row(vec![
@@ -538,10 +945,69 @@ impl ReportsHandler {
                     segment.customer_count.to_string(),
                     format!("₩{}", segment.total_revenue),
                     segment.average_order_frequency.to_string(),
+                    segment.churn_predicted_count.to_string(),
                 ]);
             }
             println!("{segments_table}");
         }
+
+        // 이탈 위험 고객
+        let at_risk: Vec<_> = report
+            .rfm_scores
+            .iter()
+            .filter(|score| score.churn_predicted)
+            .collect();
+        if !at_risk.is_empty() {
+            println!("\n이탈 위험 고객:");
+            let mut churn_table = Table::new();
+            churn_table
+                .load_preset(UTF8_FULL)
+                .apply_modifier(UTF8_ROUND_CORNERS)
+                .set_header(vec!["고객명", "최근 구매 경과일", "R/F/M", "세그먼트"]);
+
+            for score in at_risk {
+                churn_table.add_row(vec![
+                    score.name.clone(),
+                    score
+                        .recency_days
+                        .map_or("N/A".to_string(), |d| d.to_string()),
+                    format!(
+                        "{}/{}/{}",
+                        score.recency_score, score.frequency_score, score.monetary_score
+                    ),
+                    score.segment.to_string(),
+                ]);
+            }
+            println!("{churn_table}");
+        }
+    }
+
+    fn display_custom_report_console(result: &CustomReportResult) {
+        println!("\n=== {} ===", result.title);
+        println!(
+            "생성 시간: {}",
+            result.generated_at.format("%Y-%m-%d %H:%M:%S")
+        );
+        println!("기간: {}", result.period_label());
+        println!();
+
+        if result.rows.is_empty() {
+            println!("조건에 맞는 데이터가 없습니다.");
+            return;
+        }
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(result.columns.iter().map(|c| c.label.clone()));
+
+        for row in result.rows.iter().chain(result.totals().iter()) {
+            table.add_row(row.iter().map(|cell| cell.to_string()));
+        }
+
+        println!("{}", table);
+        println!("총 {}행", result.rows.len());
     }
 
     fn display_financial_overview_console(report: &FinancialOverviewReport) {
@@ -699,6 +1165,7 @@ mod tests {
             to_date: None,
             format: "console".to_string(),
             output: None,
+            include_charts: false,
         };
 
         let result = ReportsHandler::handle(&command, &config).await;
@@ -714,6 +1181,7 @@ mod tests {
             category: None,
             low_stock_only: true,
             threshold: None,
+            include_charts: false,
         };
 
         let result = ReportsHandler::handle(&command, &config).await;
@@ -727,6 +1195,7 @@ mod tests {
             months: 6,
             format: "json".to_string(),
             output: Some("test_customer_report.json".to_string()),
+            include_charts: false,
         };
 
         let result = ReportsHandler::handle(&command, &config).await;
@@ -742,6 +1211,7 @@ mod tests {
             months: 0,
             format: "console".to_string(),
             output: None,
+            include_charts: false,
         };
 
         let result = ReportsHandler::handle(&command_zero, &config).await;
@@ -752,6 +1222,7 @@ mod tests {
             months: 121,
             format: "console".to_string(),
             output: None,
+            include_charts: false,
         };
 
         let result = ReportsHandler::handle(&command_too_large, &config).await;
@@ -764,8 +1235,10 @@ mod tests {
         let command = ReportCommands::FinancialOverview {
             from_date: Some("2024-01-01".to_string()),
             to_date: Some("2024-01-31".to_string()),
+            period: None,
             format: "csv".to_string(),
             output: Some("financial_overview.csv".to_string()),
+            include_charts: false,
         };
 
         let result = ReportsHandler::handle(&command, &config).await;
@@ -781,6 +1254,7 @@ mod tests {
             to_date: Some("2024-12-31".to_string()),
             format: "html".to_string(),
             output: None,
+            include_charts: false,
         };
 
         let result = ReportsHandler::handle(&command, &config).await;
@@ -796,6 +1270,7 @@ mod tests {
             to_date: None,
             format: "invalid_format".to_string(),
             output: None,
+            include_charts: false,
         };
 
         let result = ReportsHandler::handle(&command, &config).await;
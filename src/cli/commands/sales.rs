@@ -1,19 +1,21 @@
 use chrono::{DateTime, Utc};
 use comfy_table::{Cell, Color, Table};
+use rust_decimal::Decimal;
+use shaku::HasComponent;
 use std::str::FromStr;
+use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::app_module::{
+    AppModule, AppModuleBuilder, CustomerServiceProvider, SalesServiceProvider,
+};
+use crate::cli::commands::audit::CommandAudit;
 use crate::cli::parser::SalesCommands;
 use crate::core::config::AppConfig;
-use crate::core::database::connection::DatabaseManager;
-use crate::modules::config::{ConfigRepository, ConfigService};
-use crate::modules::customers::{CustomerService, PostgresCustomerRepository};
-use crate::modules::inventory::{
-    InventoryService, InventoryServiceImpl, PostgresInventoryRepository,
-};
+use crate::modules::inventory::InventoryService;
 use crate::modules::sales::*;
 use crate::utils::error::{ErpError, ErpResult};
-use crate::utils::validation::ValidationService;
+use crate::utils::xlsx::{CellValue, Workbook, Worksheet};
 
 pub struct SalesHandler;
 
@@ -25,32 +27,16 @@ struct ListOrdersParams<'a> {
     page: u32,
     limit: u32,
     format: &'a str,
+    output: &'a Option<String>,
 }
 
 impl SalesHandler {
-    pub async fn handle(cmd: &SalesCommands, _config: &AppConfig) -> ErpResult<()> {
-        let connection = DatabaseManager::get_connection().await?;
-        let pool = connection.pool().clone();
-
-        let sales_repository = Box::new(PostgresSalesRepository::new(pool.clone()));
-        let validation_service = ValidationService::new();
-
-        let customer_repository = std::sync::Arc::new(PostgresCustomerRepository::new(
-            std::sync::Arc::new(pool.clone()),
-        ));
-        let customer_service = CustomerService::new(customer_repository);
-
-        let inventory_repository =
-            std::sync::Arc::new(PostgresInventoryRepository::new(pool.clone()));
-        let inventory_service = InventoryServiceImpl::new(inventory_repository);
+    pub async fn handle(cmd: &SalesCommands, config: &AppConfig) -> ErpResult<()> {
+        let module = AppModuleBuilder::build(config).await?;
+        let sales_service =
+            HasComponent::<dyn SalesServiceProvider>::resolve_ref(&module).get_service();
+        let audit = CommandAudit::from_module(config, &module);
 
-        let config_repository = std::sync::Arc::new(ConfigRepository::new(connection.clone()));
-        let config_service = ConfigService::new(config_repository);
-
-        let sales_service = SalesService::new(sales_repository, validation_service)
-            .with_config_service(config_service)
-            .with_customer_service(customer_service)
-            .with_inventory_service(Box::new(inventory_service));
         match cmd {
             SalesCommands::CreateOrder {
                 customer_id,
@@ -58,8 +44,16 @@ impl SalesHandler {
                 quantity,
                 notes,
             } => {
-                Self::handle_create_order(&sales_service, customer_id, product_sku, quantity, notes)
-                    .await
+                Self::handle_create_order(
+                    &sales_service,
+                    &audit,
+                    &module,
+                    customer_id,
+                    product_sku,
+                    quantity,
+                    notes,
+                )
+                .await
             }
             SalesCommands::ListOrders {
                 status,
@@ -69,6 +63,7 @@ impl SalesHandler {
                 page,
                 limit,
                 format,
+                output,
             } => {
                 let params = ListOrdersParams {
                     status,
@@ -78,22 +73,28 @@ impl SalesHandler {
                     page: *page,
                     limit: *limit,
                     format,
+                    output,
                 };
-                Self::handle_list_orders(&sales_service, params).await
+                Self::handle_list_orders(&sales_service, &audit, &module, params).await
             }
             SalesCommands::UpdateOrder { id, status, notes } => {
-                Self::handle_update_order(&sales_service, id, status, notes).await
+                Self::handle_update_order(&sales_service, &audit, id, status, notes).await
             }
             SalesCommands::GenerateInvoice {
                 order_id,
                 output,
                 format,
-            } => Self::handle_generate_invoice(&sales_service, order_id, output, format).await,
+            } => {
+                Self::handle_generate_invoice(&sales_service, &audit, order_id, output, format)
+                    .await
+            }
         }
     }
 
     async fn handle_create_order(
         sales_service: &SalesService,
+        audit: &CommandAudit,
+        module: &AppModule,
         customer_id: &str,
         product_sku: &str,
         quantity: &i32,
@@ -104,12 +105,8 @@ impl SalesHandler {
             uuid
         } else {
             // It's likely a customer code, need to look up the UUID
-            let connection = DatabaseManager::get_connection().await?;
-            let pool = connection.pool().clone();
-            let customer_repository = std::sync::Arc::new(PostgresCustomerRepository::new(
-                std::sync::Arc::new(pool.clone()),
-            ));
-            let customer_service = CustomerService::new(customer_repository);
+            let customer_service =
+                HasComponent::<dyn CustomerServiceProvider>::resolve_ref(module).get_service();
 
             // Get customer by code
             let customer = customer_service.get_customer_by_code(customer_id).await?;
@@ -118,11 +115,7 @@ impl SalesHandler {
         };
 
         // First, we need to find the product by SKU to get its ID
-        // Reuse the connection if we already have one, otherwise create new
-        let connection = DatabaseManager::get_connection().await?;
-        let pool = connection.pool().clone();
-        let inventory_repository = std::sync::Arc::new(PostgresInventoryRepository::new(pool));
-        let inventory_service = InventoryServiceImpl::new(inventory_repository);
+        let inventory_service: Arc<dyn InventoryService> = module.resolve();
 
         let product = inventory_service.get_product(product_sku).await?;
 
@@ -145,6 +138,14 @@ impl SalesHandler {
 
         match sales_service.create_order(request).await {
             Ok(order_summary) => {
+                audit
+                    .modified(
+                        "sales_orders",
+                        &order_summary.order.order_number,
+                        None,
+                        &order_summary.order,
+                    )
+                    .await;
                 println!("✅ Order created successfully!");
                 Self::display_order_summary(&order_summary);
                 Ok(())
@@ -158,6 +159,8 @@ impl SalesHandler {
 
     async fn handle_list_orders(
         sales_service: &SalesService,
+        audit: &CommandAudit,
+        module: &AppModule,
         params: ListOrdersParams<'_>,
     ) -> ErpResult<()> {
         // Validate page and limit
@@ -182,12 +185,8 @@ impl SalesHandler {
                 uuid
             } else {
                 // It's likely a customer code, need to look up the UUID
-                let connection = DatabaseManager::get_connection().await?;
-                let pool = connection.pool().clone();
-                let customer_repository = std::sync::Arc::new(PostgresCustomerRepository::new(
-                    std::sync::Arc::new(pool.clone()),
-                ));
-                let customer_service = CustomerService::new(customer_repository);
+                let customer_service =
+                    HasComponent::<dyn CustomerServiceProvider>::resolve_ref(module).get_service();
 
                 // Get customer by code
                 let customer = customer_service.get_customer_by_code(customer_str).await?;
@@ -224,6 +223,8 @@ impl SalesHandler {
                 .await?
         };
 
+        audit.accessed("sales_orders", None).await;
+
         let filtered_orders: Vec<_> = if let Some(status_str) = params.status {
             let status_filter = Self::parse_order_status(status_str)?;
             orders
@@ -242,6 +243,7 @@ impl SalesHandler {
         match params.format.to_lowercase().as_str() {
             "json" => Self::display_orders_json(&filtered_orders),
             "csv" => Self::display_orders_csv(&filtered_orders),
+            "xlsx" => Self::export_orders_xlsx(&filtered_orders, params.output)?,
             _ => {
                 // Default to table format
                 Self::display_orders_table(&filtered_orders);
@@ -254,21 +256,21 @@ impl SalesHandler {
 
     async fn handle_update_order(
         sales_service: &SalesService,
+        audit: &CommandAudit,
         id: &str,
         status: &str,
         notes: &Option<String>,
     ) -> ErpResult<()> {
         // Try to parse as UUID first, if that fails, assume it's an order number
-        let order_id = if let Ok(uuid) = Uuid::from_str(id) {
-            uuid
+        let before = if let Ok(uuid) = Uuid::from_str(id) {
+            sales_service.get_order_by_id(uuid).await?
         } else {
-            // It's likely an order number (e.g., "ORD-000038"), look up the UUID
-            let order_summary = sales_service
-                .get_order_by_number(id)
-                .await?
-                .ok_or_else(|| ErpError::not_found("Order", id))?;
-            order_summary.order.id
-        };
+            // It's likely an order number (e.g., "ORD-000038")
+            sales_service.get_order_by_number(id).await?
+        }
+        .ok_or_else(|| ErpError::not_found("Order", id))?
+        .order;
+        let order_id = before.id;
 
         let new_status = Self::parse_order_status(status)?;
 
@@ -283,6 +285,14 @@ impl SalesHandler {
 
         match sales_service.update_order(order_id, update_request).await {
             Ok(updated_summary) => {
+                audit
+                    .modified(
+                        "sales_orders",
+                        &updated_summary.order.order_number,
+                        Some(&before),
+                        &updated_summary.order,
+                    )
+                    .await;
                 println!("✅ Order updated successfully!");
                 Self::display_order_summary(&updated_summary);
                 Ok(())
@@ -296,6 +306,7 @@ impl SalesHandler {
 
     async fn handle_generate_invoice(
         sales_service: &SalesService,
+        audit: &CommandAudit,
         order_id: &str,
         output: &Option<String>,
         format: &str,
@@ -314,6 +325,9 @@ impl SalesHandler {
 
         match sales_service.generate_invoice(order_uuid).await {
             Ok(invoice) => {
+                audit
+                    .accessed("invoices", Some(&invoice.invoice_number))
+                    .await;
                 println!("✅ Invoice generated successfully!");
                 Self::display_invoice(&invoice);
 
@@ -513,6 +527,59 @@ impl SalesHandler {
         );
     }
 
+    fn export_orders_xlsx(orders: &[SalesOrder], output: &Option<String>) -> ErpResult<()> {
+        let mut sheet = Worksheet::new("Orders");
+        sheet.header([
+            "Order Number",
+            "Customer ID",
+            "Status",
+            "Payment Status",
+            "Tax Amount",
+            "Discount",
+            "Total Amount",
+            "Order Date",
+        ]);
+        for order in orders {
+            sheet.row(vec![
+                order.order_number.as_str().into(),
+                order.customer_id.to_string().into(),
+                order.status.to_string().into(),
+                order.payment_status.to_string().into(),
+                order.tax_amount.into(),
+                order.discount_amount.into(),
+                order.total_amount.into(),
+                order.order_date.into(),
+            ]);
+        }
+        sheet.total_row(vec![
+            "Total".into(),
+            CellValue::Empty,
+            CellValue::Empty,
+            CellValue::Empty,
+            orders.iter().map(|o| o.tax_amount).sum::<Decimal>().into(),
+            orders
+                .iter()
+                .map(|o| o.discount_amount)
+                .sum::<Decimal>()
+                .into(),
+            orders
+                .iter()
+                .map(|o| o.total_amount)
+                .sum::<Decimal>()
+                .into(),
+            CellValue::Empty,
+        ]);
+
+        let path = output
+            .clone()
+            .unwrap_or_else(|| format!("orders_{}.xlsx", Utc::now().format("%Y%m%d_%H%M%S")));
+        let mut workbook = Workbook::new();
+        workbook.add_sheet(sheet);
+        workbook.save(&path)?;
+        println!("📄 Exported {} orders to {}", orders.len(), path);
+        Ok(())
+    }
+
     fn display_orders_csv(orders: &[SalesOrder]) {
         // Print CSV header
         println!("Order Number,Customer ID,Status,Payment Status,Total Amount,Order Date");
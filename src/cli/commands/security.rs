@@ -0,0 +1,289 @@
+use chrono::{DateTime, Local, Utc};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use shaku::HasComponent;
+use std::sync::Arc;
+
+use crate::app_module::AppModuleBuilder;
+use crate::cli::commands::audit::CommandAudit;
+use crate::cli::parser::{SecurityCommands, SecurityLimitCommands};
+use crate::core::config::AppConfig;
+use crate::core::database::connection::DatabaseManager;
+use crate::core::security::audit::current_username;
+use crate::core::security::encryption::EncryptionService;
+use crate::core::security::pii::{encode_key, PiiCipher};
+use crate::core::security::rate_limiter::{
+    export_identifier, PostgresRateLimiter, RateLimitConfig, RateLimiterTrait,
+};
+use crate::modules::customers::{CustomerRepository, PiiRotationBatch};
+use crate::utils::error::{ErpError, ErpResult};
+
+/// 한 번에 처리할 수 있는 최대 행 수
+const MAX_BATCH_SIZE: u32 = 10_000;
+
+/// 내보내기/비밀번호 재설정 제한 구간 (초)
+const HOURLY_WINDOW_SECONDS: u64 = 3600;
+
+/// 식별자에 적용되는 제한
+struct LimitPolicy {
+    label: &'static str,
+    limit: u64,
+    window_seconds: u64,
+}
+
+fn limit_policy(config: &AppConfig, identifier: &str) -> Option<LimitPolicy> {
+    if identifier.starts_with("login:") {
+        Some(LimitPolicy {
+            label: "로그인 실패 잠금",
+            limit: u64::from(config.auth.max_login_attempts.max(1)),
+            window_seconds: u64::from(config.auth.lockout_duration_minutes.max(1)) * 60,
+        })
+    } else if identifier.starts_with("export:") {
+        (config.security.export_limit_per_hour > 0).then(|| LimitPolicy {
+            label: "대량 내보내기",
+            limit: u64::from(config.security.export_limit_per_hour),
+            window_seconds: HOURLY_WINDOW_SECONDS,
+        })
+    } else if identifier.starts_with("password_reset:")
+        || identifier.starts_with("password_reset_source:")
+    {
+        Some(LimitPolicy {
+            label: "비밀번호 재설정",
+            limit: u64::from(config.security.password_reset_limit_per_hour),
+            window_seconds: HOURLY_WINDOW_SECONDS,
+        })
+    } else {
+        None
+    }
+}
+
+fn rate_limiter(pool: sqlx::PgPool) -> PostgresRateLimiter {
+    PostgresRateLimiter::new(pool, RateLimitConfig::default())
+}
+
+fn format_time(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+/// 대량 내보내기 명령어를 사용자별 시간당 security.export_limit_per_hour회로 제한
+///
+/// 제한을 넘지 않으면 이번 실행을 기록한다. 실행 기록은 PostgreSQL에 남으므로
+/// 데이터베이스 연결이 없거나(Mock 데이터) SQLite이면 제한하지 않는다.
+pub(crate) async fn enforce_export_limit(config: &AppConfig) -> ErpResult<()> {
+    let limit = u64::from(config.security.export_limit_per_hour);
+    if limit == 0 {
+        return Ok(());
+    }
+
+    let Ok(connection) = DatabaseManager::get_connection().await else {
+        return Ok(());
+    };
+    let Ok(pool) = connection.pool() else {
+        return Ok(());
+    };
+    let limiter = rate_limiter(pool.clone());
+    let identifier = export_identifier(&current_username());
+    if limiter
+        .acquire(&identifier, limit, HOURLY_WINDOW_SECONDS)
+        .await?
+    {
+        return Ok(());
+    }
+
+    let retry = limiter
+        .blocked_until(&identifier, limit, HOURLY_WINDOW_SECONDS)
+        .await?
+        .map(|at| format!(" {} 이후에 다시 시도하세요.", format_time(at)))
+        .unwrap_or_default();
+    Err(ErpError::validation(
+        "rate_limit",
+        format!("시간당 내보내기 한도({}회)를 초과했습니다.{}", limit, retry),
+    ))
+}
+
+pub struct SecurityHandler;
+
+impl SecurityHandler {
+    pub async fn handle(cmd: &SecurityCommands, config: &AppConfig) -> ErpResult<()> {
+        match cmd {
+            SecurityCommands::RotateKeys { batch_size } => {
+                Self::handle_rotate_keys(config, *batch_size).await
+            }
+            SecurityCommands::GenerateKey => {
+                println!("{}", encode_key(&EncryptionService::generate_key()));
+                Ok(())
+            }
+            SecurityCommands::Limits(SecurityLimitCommands::Show { identifier }) => {
+                Self::handle_limits_show(config, identifier).await
+            }
+            SecurityCommands::Limits(SecurityLimitCommands::Reset { identifier }) => {
+                Self::handle_limits_reset(config, identifier).await
+            }
+        }
+    }
+
+    async fn handle_limits_show(config: &AppConfig, identifier: &str) -> ErpResult<()> {
+        let connection = DatabaseManager::get_connection().await?;
+        let limiter = rate_limiter(connection.pool()?.clone());
+        let usage = limiter.usage(identifier).await?;
+
+        println!("🚦 요청 제한: {}", identifier);
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(vec!["구간", "요청 수"]);
+        for (window, count) in [
+            ("최근 10초", usage.burst),
+            ("최근 1분", usage.minute),
+            ("최근 1시간", usage.hour),
+            ("최근 1일", usage.day),
+        ] {
+            table.add_row(vec![window.to_string(), count.to_string()]);
+        }
+        println!("{}", table);
+        println!(
+            "마지막 요청: {}",
+            usage
+                .last_request
+                .map(format_time)
+                .unwrap_or_else(|| "-".to_string())
+        );
+
+        let Some(policy) = limit_policy(config, identifier) else {
+            println!("ℹ️  이 식별자에 적용되는 명령어별 제한이 없습니다.");
+            return Ok(());
+        };
+        println!(
+            "적용 제한: {} - {}분당 {}회",
+            policy.label,
+            policy.window_seconds / 60,
+            policy.limit
+        );
+        match limiter
+            .blocked_until(identifier, policy.limit, policy.window_seconds)
+            .await?
+        {
+            Some(until) => println!("상태: ⛔ 차단됨 ({}까지)", format_time(until)),
+            None => println!("상태: ✅ 허용"),
+        }
+        Ok(())
+    }
+
+    async fn handle_limits_reset(config: &AppConfig, identifier: &str) -> ErpResult<()> {
+        let connection = DatabaseManager::get_connection().await?;
+        let limiter = rate_limiter(connection.pool()?.clone());
+        let usage = limiter.usage(identifier).await?;
+        limiter.reset_limit(identifier).await?;
+
+        println!(
+            "✅ {}의 요청 기록 {}건을 삭제했습니다.",
+            identifier, usage.day
+        );
+
+        let audit = CommandAudit::new(config).await;
+        audit
+            .performed(
+                "rate_limits",
+                identifier,
+                "reset",
+                &[("events", usage.day.to_string())],
+            )
+            .await;
+        Ok(())
+    }
+
+    async fn handle_rotate_keys(config: &AppConfig, batch_size: u32) -> ErpResult<()> {
+        if batch_size == 0 || batch_size > MAX_BATCH_SIZE {
+            return Err(ErpError::validation(
+                "batch_size",
+                format!("배치 크기는 1-{} 범위여야 합니다", MAX_BATCH_SIZE),
+            ));
+        }
+
+        if PiiCipher::from_config(&config.security)?.is_none() {
+            return Err(ErpError::config(
+                "security.encrypt_sensitive_data가 꺼져 있어 키를 교체할 수 없습니다",
+            ));
+        }
+        if config.security.pii_previous_key.is_none() {
+            println!("ℹ️  이전 키(PII_PREVIOUS_KEY)가 없어 현재 키로만 복호화합니다.");
+        }
+
+        // 컨테이너의 고객 저장소는 같은 설정으로 만든 PII 키를 사용
+        let module = AppModuleBuilder::build_postgres(config).await?;
+        let repository: Arc<dyn CustomerRepository> = module.resolve();
+        let audit = CommandAudit::from_module(config, &module);
+
+        println!("🔑 고객 정보 재암호화 중...");
+        let customers = Self::run_batches("고객", batch_size, |after| {
+            repository.rotate_customer_pii_batch(after, batch_size)
+        })
+        .await?;
+        println!("🔑 고객 주소 재암호화 중...");
+        let addresses = Self::run_batches("주소", batch_size, |after| {
+            repository.rotate_address_pii_batch(after, batch_size)
+        })
+        .await?;
+
+        println!(
+            "✅ 키 교체 완료: 고객 {}건, 주소 {}건 (재암호화 {}개 값, 신규 암호화 {}개 값)",
+            customers.rows,
+            addresses.rows,
+            customers.re_encrypted + addresses.re_encrypted,
+            customers.encrypted + addresses.encrypted
+        );
+        if config.security.pii_previous_key.is_some() {
+            println!("   이제 PII_PREVIOUS_KEY 설정을 제거해도 됩니다.");
+        }
+
+        audit
+            .config_changed::<String>(
+                "security.pii_encryption_key",
+                None,
+                None,
+                &[
+                    ("operation", "rotate_keys".to_string()),
+                    ("customers", customers.rows.to_string()),
+                    ("addresses", addresses.rows.to_string()),
+                    (
+                        "values_encrypted",
+                        (customers.encrypted + addresses.encrypted).to_string(),
+                    ),
+                ],
+            )
+            .await;
+        Ok(())
+    }
+
+    /// 커서가 끝날 때까지 배치를 반복 실행하고 합계를 반환
+    ///
+    /// 배치마다 커밋되므로 중간에 실패해도 다시 실행하면 이어서 처리된다.
+    async fn run_batches<F, Fut>(
+        label: &str,
+        batch_size: u32,
+        mut next_batch: F,
+    ) -> ErpResult<PiiRotationBatch>
+    where
+        F: FnMut(Option<uuid::Uuid>) -> Fut,
+        Fut: std::future::Future<Output = ErpResult<PiiRotationBatch>>,
+    {
+        let mut total = PiiRotationBatch::default();
+        loop {
+            let batch = next_batch(total.last_id).await?;
+            total.rows += batch.rows;
+            total.re_encrypted += batch.re_encrypted;
+            total.encrypted += batch.encrypted;
+            if batch.last_id.is_some() {
+                total.last_id = batch.last_id;
+            }
+            if batch.rows > 0 {
+                println!("   {} {}건 처리", label, total.rows);
+            }
+            if batch.rows < u64::from(batch_size) {
+                return Ok(total);
+            }
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use chrono::Local;
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Table};
+use serde_json::json;
+use shaku::HasComponent;
+use sqlx::Postgres;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::time::{sleep, Duration};
+
+use crate::app_module::{AppModuleBuilder, DatabasePoolProvider};
+use crate::cli::parser::SystemCommands;
+use crate::cli::validator::CliValidator;
+use crate::core::config::AppConfig;
+use crate::core::database::connection::DatabaseManager;
+use crate::core::database::replica::ReadRouter;
+use crate::core::ops::performance::PerformanceRepository;
+use crate::core::ops::profiler::{
+    matches_command, performance_trend, trend_bucket, PerformanceTrend,
+};
+use crate::core::security::monitor::{
+    overall_state, run_health_check, Alert, AlertSeverity, BackupFreshnessHealthCheck,
+    DatabaseHealthCheck, DiskSpaceHealthCheck, HealthCheck, HealthState, HealthStatus,
+    MigrationHealthCheck, MonitoringConfig, MonitoringCycle, MonitoringService, ReplicaHealthCheck,
+};
+use crate::utils::error::{ErpError, ErpResult};
+
+pub struct SystemHandler;
+
+impl SystemHandler {
+    pub async fn handle(cmd: &SystemCommands, config: &AppConfig) -> ErpResult<()> {
+        match cmd {
+            SystemCommands::Health { format } => Self::handle_health(config, format).await,
+            SystemCommands::Monitor { interval, count } => {
+                DatabaseManager::initialize(config.database.clone()).await?;
+                Self::handle_monitor(config, *interval, *count).await
+            }
+            SystemCommands::Perf { command, since } => {
+                DatabaseManager::initialize(config.database.clone()).await?;
+                Self::handle_perf(config, command.as_deref(), since).await
+            }
+        }
+    }
+
+    async fn handle_health(config: &AppConfig, format: &str) -> ErpResult<()> {
+        // 데이터베이스에 접속할 수 없어도 나머지 검사는 실행
+        let pool = match DatabaseManager::initialize(config.database.clone()).await {
+            Ok(()) => DatabaseManager::get_connection()
+                .await
+                .and_then(|connection| connection.postgres_reads().cloned())
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let mut statuses = Vec::new();
+        if let Err(error) = &pool {
+            statuses.push(HealthStatus::down("database", error.clone()));
+            statuses.push(HealthStatus::down(
+                "migrations",
+                "데이터베이스에 접속할 수 없습니다".to_string(),
+            ));
+        }
+        for check in health_checks(config, pool.ok()) {
+            statuses.push(run_health_check(check.as_ref()).await);
+        }
+
+        let overall = overall_state(&statuses);
+        if format == "json" {
+            let report = json!({
+                "status": overall,
+                "checked_at": chrono::Utc::now(),
+                "checks": statuses,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report)
+                    .map_err(|e| ErpError::serialization(e.to_string()))?
+            );
+        } else {
+            Self::display_health(&statuses);
+            println!(
+                "전체 상태: {} {}",
+                state_icon(&overall),
+                state_label(&overall)
+            );
+        }
+
+        // cron/모니터링 도구에서 사용할 수 있도록 상태를 종료 코드로 반환
+        let exit_code = exit_code(&overall);
+        if exit_code != 0 {
+            std::process::exit(exit_code);
+        }
+        Ok(())
+    }
+
+    fn display_health(statuses: &[HealthStatus]) {
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(vec!["검사", "상태", "응답 시간", "상세"]);
+        for status in statuses {
+            table.add_row(vec![
+                status.service.clone(),
+                format!(
+                    "{} {}",
+                    state_icon(&status.status),
+                    state_label(&status.status)
+                ),
+                status
+                    .response_time_ms
+                    .map(|ms| format!("{}ms", ms))
+                    .unwrap_or_else(|| "-".to_string()),
+                format_details(status),
+            ]);
+        }
+        println!("{}", table);
+    }
+
+    async fn handle_monitor(
+        config: &AppConfig,
+        interval: u64,
+        count: Option<u64>,
+    ) -> ErpResult<()> {
+        let module = AppModuleBuilder::build_postgres(config).await?;
+        let reads = HasComponent::<dyn DatabasePoolProvider>::resolve_ref(&module)
+            .connection()
+            .postgres_reads()?
+            .clone();
+        let service = MonitoringService::new(
+            MonitoringConfig {
+                collection_interval_seconds: interval,
+                retention_hours: config.monitoring.retention_hours,
+                disk_warning_threshold: config.monitoring.disk_warning_percent,
+                disk_critical_threshold: config.monitoring.disk_critical_percent,
+                ..MonitoringConfig::default()
+            },
+            module.resolve(),
+        );
+        for check in health_checks(config, Some(reads)) {
+            service.add_health_check(check);
+        }
+
+        let restored = service.load_active_alerts().await?;
+        if restored > 0 {
+            println!(
+                "ℹ️  이전 실행에서 해결되지 않은 알림 {}개를 이어서 추적합니다.",
+                restored
+            );
+        }
+        println!("📈 {}초마다 메트릭을 수집합니다. (Ctrl+C로 종료)", interval);
+
+        let mut cycles = 0;
+        loop {
+            let cycle = service.run_cycle().await?;
+            Self::display_cycle(&cycle);
+
+            cycles += 1;
+            if count.is_some_and(|count| cycles >= count) {
+                return Ok(());
+            }
+
+            tokio::select! {
+                _ = sleep(Duration::from_secs(interval)) => {}
+                _ = tokio::signal::ctrl_c() => {
+                    println!("모니터링을 종료합니다.");
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn display_cycle(cycle: &MonitoringCycle) {
+        let metrics = &cycle.system_metrics;
+        let healthy = cycle
+            .health
+            .iter()
+            .filter(|s| s.status == HealthState::Healthy)
+            .count();
+        println!(
+            "[{}] CPU {:.1}% | 메모리 {:.1}% | 디스크 {:.1}% | 검사 {}/{} 정상 | 활성 알림 {}개",
+            metrics.timestamp.with_timezone(&Local).format("%H:%M:%S"),
+            metrics.cpu_usage,
+            percent(metrics.memory_used, metrics.memory_total),
+            percent(metrics.disk_used, metrics.disk_total),
+            healthy,
+            cycle.health.len(),
+            cycle.active_alerts.len()
+        );
+
+        let mut alerts: Vec<&Alert> = cycle.active_alerts.iter().collect();
+        alerts.sort_by_key(|a| a.timestamp);
+        for alert in alerts {
+            println!(
+                "    {} {} - {} (발생: {})",
+                severity_icon(&alert.severity),
+                alert.title,
+                alert.description,
+                alert
+                    .timestamp
+                    .with_timezone(&Local)
+                    .format("%m-%d %H:%M:%S")
+            );
+        }
+    }
+
+    async fn handle_perf(config: &AppConfig, command: Option<&str>, since: &str) -> ErpResult<()> {
+        let since = CliValidator::validate_since(since)?;
+        let command = command.map(|c| c.split_whitespace().collect::<Vec<_>>().join(" "));
+        let module = AppModuleBuilder::build_postgres(config).await?;
+        let repository: Arc<dyn PerformanceRepository> = module.resolve();
+
+        let mut metrics = repository.get_metrics(None, since, None).await?;
+        if let Some(command) = &command {
+            metrics.retain(|m| matches_command(&m.endpoint, command));
+        }
+        if metrics.is_empty() {
+            println!("기록된 실행이 없습니다. 명령에 --profile을 붙여 실행하면 기록됩니다.");
+            return Ok(());
+        }
+
+        // 하위 명령별로 나눠서 표시
+        let mut endpoints: Vec<String> = metrics.iter().map(|m| m.endpoint.clone()).collect();
+        endpoints.sort();
+        endpoints.dedup();
+        let bucket = trend_bucket(chrono::Utc::now() - since);
+        for endpoint in endpoints {
+            let runs: Vec<_> = metrics
+                .iter()
+                .filter(|m| m.endpoint == endpoint)
+                .cloned()
+                .collect();
+            println!("⏱️  {} ({}회 실행)", endpoint, runs.len());
+            Self::display_trend(&performance_trend(&runs, bucket));
+        }
+        Ok(())
+    }
+
+    fn display_trend(trend: &[PerformanceTrend]) {
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_header(vec![
+                "기간",
+                "실행",
+                "실패",
+                "p50",
+                "p95",
+                "p99",
+                "평균 SQL",
+                "최대 메모리",
+            ]);
+        for row in trend {
+            table.add_row(vec![
+                row.bucket
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string(),
+                row.runs.to_string(),
+                row.failures.to_string(),
+                format!("{:.0}ms", row.p50_ms),
+                format!("{:.0}ms", row.p95_ms),
+                format!("{:.0}ms", row.p99_ms),
+                format!("{:.1}", row.avg_queries),
+                format!("{:.1}MB", row.max_memory_kb as f64 / 1024.0),
+            ]);
+        }
+        println!("{}", table);
+    }
+}
+
+/// 설정에 따라 실행할 상태 검사 목록 (pool이 없으면 데이터베이스 검사 제외)
+/// `reads` carries the primary pool and any configured read replicas
+fn health_checks(
+    config: &AppConfig,
+    reads: Option<ReadRouter<Postgres>>,
+) -> Vec<Box<dyn HealthCheck>> {
+    let monitoring = &config.monitoring;
+    let mut checks: Vec<Box<dyn HealthCheck>> = Vec::new();
+    if let Some(reads) = reads {
+        let pool = reads.primary().clone();
+        checks.push(Box::new(
+            DatabaseHealthCheck::new(pool.clone())
+                .with_slow_threshold_ms(monitoring.database_slow_ms),
+        ));
+        checks.push(Box::new(MigrationHealthCheck::new(
+            pool,
+            &monitoring.migrations_dir,
+        )));
+        if reads.replica_count() > 0 {
+            checks.push(Box::new(ReplicaHealthCheck::new(reads)));
+        }
+    }
+
+    // 백업 디렉터리가 아직 없으면 현재 디렉터리가 있는 디스크를 검사
+    let backup_dir = Path::new(&config.backup.backup_dir);
+    let disk_path = if backup_dir.exists() {
+        backup_dir
+    } else {
+        Path::new(".")
+    };
+    checks.push(Box::new(DiskSpaceHealthCheck::new(
+        disk_path,
+        monitoring.disk_warning_percent,
+        monitoring.disk_critical_percent,
+    )));
+    checks.push(Box::new(BackupFreshnessHealthCheck::new(
+        backup_dir,
+        monitoring.backup_max_age_hours,
+    )));
+    checks
+}
+
+/// Nagios 규약: 0 정상, 1 경고, 2 위험
+fn exit_code(state: &HealthState) -> i32 {
+    match state {
+        HealthState::Healthy => 0,
+        HealthState::Warning => 1,
+        HealthState::Critical | HealthState::Down => 2,
+    }
+}
+
+fn format_details(status: &HealthStatus) -> String {
+    let mut details: Vec<_> = status
+        .details
+        .iter()
+        .filter(|(key, _)| key.as_str() != "response_time_ms")
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+    details.sort();
+    details.join("\n")
+}
+
+fn percent(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        used as f64 / total as f64 * 100.0
+    }
+}
+
+fn state_icon(state: &HealthState) -> &'static str {
+    match state {
+        HealthState::Healthy => "✅",
+        HealthState::Warning => "⚠️",
+        HealthState::Critical | HealthState::Down => "❌",
+    }
+}
+
+fn state_label(state: &HealthState) -> &'static str {
+    match state {
+        HealthState::Healthy => "정상",
+        HealthState::Warning => "경고",
+        HealthState::Critical => "위험",
+        HealthState::Down => "중단",
+    }
+}
+
+fn severity_icon(severity: &AlertSeverity) -> &'static str {
+    match severity {
+        AlertSeverity::Info => "ℹ️",
+        AlertSeverity::Warning => "🟡",
+        AlertSeverity::Error | AlertSeverity::Critical => "🔴",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(exit_code(&HealthState::Healthy), 0);
+        assert_eq!(exit_code(&HealthState::Warning), 1);
+        assert_eq!(exit_code(&HealthState::Critical), 2);
+        assert_eq!(exit_code(&HealthState::Down), 2);
+    }
+
+    #[test]
+    fn test_health_checks_without_database() {
+        let config = AppConfig::default();
+        let names: Vec<String> = health_checks(&config, None)
+            .iter()
+            .map(|c| c.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["disk_space", "backup"]);
+    }
+}
@@ -2,20 +2,72 @@ pub mod commands;
 pub mod parser;
 pub mod validator;
 
-use crate::core::config::AppConfig;
+use crate::core::config::{AppConfig, DatabaseConfig};
 use crate::core::database::connection::DatabaseManager;
+use crate::core::ops::performance::{
+    PerformanceMetrics, PerformanceRepository, PostgresPerformanceRepository,
+};
+use crate::core::ops::profiler::CommandProfiler;
 use crate::utils::error::ErpResult;
+use clap::CommandFactory;
+use parser::{
+    AuditCheckpointCommands, AuditCommands, ConfigCommands, DataCommands, SecurityCommands,
+};
 pub use parser::{Cli, Commands, LogLevel, MigrateCommands};
 
 impl Cli {
-    /// CLI 실행
+    /// 설정을 불러오기 전에 실행해야 하는 명령인지 (`config validate`)
+    pub fn validates_config(&self) -> bool {
+        matches!(
+            self.command,
+            Some(Commands::Config(ConfigCommands::Validate))
+        )
+    }
+
+    /// CLI 실행 (--profile이면 실행 결과를 측정해 저장)
     pub async fn run(&self, config: AppConfig) -> ErpResult<()> {
+        if !self.profile || self.command.is_none() {
+            return self.dispatch(config).await;
+        }
+
+        let database = config.database.clone();
+        let profiler = CommandProfiler::start(command_path());
+        let result = self.dispatch(config).await;
+        let metrics = profiler.finish(result.as_ref().err().map(|e| e.to_string()));
+
+        print_profile(&metrics);
+        // 측정 결과 저장 실패가 명령 결과를 바꾸지 않도록 경고만 출력
+        if let Err(e) = store_profile(database, &metrics).await {
+            eprintln!("⚠️  프로파일 결과를 저장하지 못했습니다: {}", e);
+        }
+        result
+    }
+
+    async fn dispatch(&self, config: AppConfig) -> ErpResult<()> {
         match &self.command {
             Some(command) => match command {
                 // 마이그레이션 명령어는 자체적으로 데이터베이스를 초기화함
                 Commands::Migrate(cmd) => {
                     commands::migrate::handle_migrate_command(cmd.clone(), config).await
                 }
+                // 체크포인트 파일 검증은 오프라인에서도 가능해야 함
+                Commands::Audit(
+                    cmd @ AuditCommands::Checkpoint(AuditCheckpointCommands::VerifyFile { .. }),
+                ) => commands::AuditHandler::handle(cmd, &config).await,
+                // 키 생성은 데이터베이스가 필요 없음
+                Commands::Security(cmd @ SecurityCommands::GenerateKey) => {
+                    commands::SecurityHandler::handle(cmd, &config).await
+                }
+                // 스냅샷 내용 확인은 파일만 읽음
+                Commands::Data(cmd @ DataCommands::Inspect { .. }) => {
+                    commands::DataHandler::handle(cmd, &config).await
+                }
+                // 설정 검증은 데이터베이스 접속 실패도 결과로 보고해야 함
+                Commands::Config(ConfigCommands::Validate) => {
+                    commands::ConfigHandler::validate().await
+                }
+                // 상태 점검은 데이터베이스 접속 실패도 결과로 보고해야 함
+                Commands::System(cmd) => commands::SystemHandler::handle(cmd, &config).await,
                 // 다른 명령어들은 데이터베이스 초기화 필요
                 _ => {
                     // 데이터베이스 초기화
@@ -35,7 +87,18 @@ impl Cli {
                         Commands::Config(cmd) => {
                             commands::ConfigHandler::handle(cmd, &config).await
                         }
-                        Commands::Migrate(_) => unreachable!(), // 이미 위에서 처리됨
+                        Commands::Audit(cmd) => commands::AuditHandler::handle(cmd, &config).await,
+                        Commands::Security(cmd) => {
+                            commands::SecurityHandler::handle(cmd, &config).await
+                        }
+                        Commands::Backup(cmd) => {
+                            commands::BackupHandler::handle(cmd, &config).await
+                        }
+                        Commands::Data(cmd) => commands::DataHandler::handle(cmd, &config).await,
+                        Commands::ServeMetrics { bind } => {
+                            commands::MetricsHandler::serve(bind, &config).await
+                        }
+                        Commands::Migrate(_) | Commands::System(_) => unreachable!(), // 이미 위에서 처리됨
                     }
                 }
             },
@@ -49,3 +112,44 @@ impl Cli {
         }
     }
 }
+
+/// 실행 중인 명령의 하위 명령 경로 (예: "reports financial-overview")
+fn command_path() -> String {
+    let Ok(matches) = Cli::command().try_get_matches_from(std::env::args_os()) else {
+        return "unknown".to_string();
+    };
+
+    let mut path = Vec::new();
+    let mut current = &matches;
+    while let Some((name, sub)) = current.subcommand() {
+        path.push(name.to_string());
+        current = sub;
+    }
+    path.join(" ")
+}
+
+fn print_profile(metrics: &PerformanceMetrics) {
+    println!();
+    println!(
+        "⏱️  {}: {}ms | SQL {}회 ({:.1}ms) | 최대 메모리 {:.1}MB",
+        metrics.endpoint,
+        metrics.response_time_ms,
+        metrics.usage.query_count,
+        metrics.usage.query_time_ms,
+        metrics.usage.peak_memory_kb as f64 / 1024.0
+    );
+}
+
+async fn store_profile(database: DatabaseConfig, metrics: &PerformanceMetrics) -> ErpResult<()> {
+    // 명령이 이미 연결을 만들었으면 그대로 사용
+    let connection = match DatabaseManager::get_connection().await {
+        Ok(connection) => connection,
+        Err(_) => {
+            DatabaseManager::initialize(database).await?;
+            DatabaseManager::get_connection().await?
+        }
+    };
+    PostgresPerformanceRepository::new(connection.pool()?.clone())
+        .store_metrics(std::slice::from_ref(metrics))
+        .await
+}
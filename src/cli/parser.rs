@@ -13,6 +13,10 @@ pub struct Cli {
     #[clap(long, global = true, value_enum)]
     pub log_level: Option<LogLevel>,
 
+    /// 명령 실행 시간, SQL 쿼리 수/시간, 최대 메모리를 기록
+    #[clap(long, global = true)]
+    pub profile: bool,
+
     /// 하위 명령어
     #[clap(subcommand)]
     pub command: Option<Commands>,
@@ -47,6 +51,27 @@ pub enum Commands {
     /// 데이터베이스 마이그레이션 명령어들
     #[clap(subcommand)]
     Migrate(MigrateCommands),
+    /// 감사 로그 명령어들
+    #[clap(subcommand)]
+    Audit(AuditCommands),
+    /// 보안 관리 명령어들
+    #[clap(subcommand)]
+    Security(SecurityCommands),
+    /// 백업 및 복원 명령어들
+    #[clap(subcommand)]
+    Backup(BackupCommands),
+    /// 모듈 단위 데이터 내보내기/가져오기
+    #[clap(subcommand)]
+    Data(DataCommands),
+    /// 시스템 상태 점검 및 모니터링
+    #[clap(subcommand)]
+    System(SystemCommands),
+    /// Prometheus 메트릭 엔드포인트 실행 (GET /metrics)
+    ServeMetrics {
+        /// 수신 주소
+        #[clap(long, default_value = "127.0.0.1:9090")]
+        bind: String,
+    },
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -94,9 +119,12 @@ pub enum InventoryCommands {
         /// 페이지당 아이템 수
         #[clap(long, default_value = "20")]
         limit: u32,
-        /// 출력 형식 (table, json, csv)
+        /// 출력 형식 (table, json, csv, yaml, xlsx)
         #[clap(long, default_value = "table")]
         format: String,
+        /// 출력 파일 경로 (xlsx 형식)
+        #[clap(long)]
+        output: Option<String>,
         /// 정렬 기준 (name, sku, quantity, price, cost, created_at)
         #[clap(long, default_value = "name")]
         sort_by: String,
@@ -185,15 +213,21 @@ pub enum CustomerCommands {
         /// 고객 타입 필터
         #[clap(long)]
         customer_type: Option<String>,
+        /// RFM 세그먼트 필터 (champions, loyal, potential-loyalist, new, need-attention, at-risk, cant-lose, hibernating, lost)
+        #[clap(long)]
+        segment: Option<String>,
         /// 페이지 번호
         #[clap(long, default_value = "1")]
         page: u32,
         /// 페이지당 아이템 수
         #[clap(long, default_value = "20")]
         limit: u32,
-        /// 출력 형식 (table, json, csv)
+        /// 출력 형식 (table, json, csv, xlsx)
         #[clap(long, default_value = "table")]
         format: String,
+        /// 출력 파일 경로 (xlsx 형식)
+        #[clap(long)]
+        output: Option<String>,
         /// 정렬 기준 (name, email, created_at)
         #[clap(long, default_value = "name")]
         sort_by: String,
@@ -282,9 +316,12 @@ pub enum SalesCommands {
         /// 페이지당 아이템 수
         #[clap(long, default_value = "20")]
         limit: u32,
-        /// 출력 형식 (table, json, csv)
+        /// 출력 형식 (table, json, csv, xlsx)
         #[clap(long, default_value = "table")]
         format: String,
+        /// 출력 파일 경로 (xlsx 형식)
+        #[clap(long)]
+        output: Option<String>,
     },
     /// 주문 상태 변경
     UpdateOrder {
@@ -323,16 +360,19 @@ pub enum ReportCommands {
         /// 종료 날짜 (YYYY-MM-DD)
         #[clap(long)]
         to_date: Option<String>,
-        /// 출력 형식 (console, json, csv, html, pdf)
+        /// 출력 형식 (console, json, csv, html, pdf, xlsx)
         #[clap(long, default_value = "console")]
         format: String,
         /// 출력 파일 경로
         #[clap(long)]
         output: Option<String>,
+        /// 차트 포함 여부 (html, pdf)
+        #[clap(long)]
+        include_charts: bool,
     },
     /// 재고 상태 보고서
     InventoryStatus {
-        /// 출력 형식 (console, table, json, csv, html, pdf)
+        /// 출력 형식 (console, table, json, csv, html, pdf, xlsx)
         #[clap(long, default_value = "console")]
         format: String,
         /// 출력 파일 경로
@@ -347,18 +387,24 @@ pub enum ReportCommands {
         /// 저재고 기준 수량
         #[clap(long)]
         threshold: Option<u32>,
+        /// 차트 포함 여부 (html, pdf)
+        #[clap(long)]
+        include_charts: bool,
     },
     /// 고객 분석 보고서
     CustomerAnalysis {
         /// 분석 기간 (months)
         #[clap(long, default_value = "12")]
         months: u32,
-        /// 출력 형식 (console, json, csv, html, pdf)
+        /// 출력 형식 (console, json, csv, html, pdf, xlsx)
         #[clap(long, default_value = "console")]
         format: String,
         /// 출력 파일 경로
         #[clap(long)]
         output: Option<String>,
+        /// 차트 포함 여부 (html, pdf)
+        #[clap(long)]
+        include_charts: bool,
     },
     /// 재무 개요 보고서
     FinancialOverview {
@@ -371,7 +417,7 @@ pub enum ReportCommands {
         /// 기간 (daily, weekly, monthly, quarterly, yearly)
         #[clap(long)]
         period: Option<String>,
-        /// 출력 형식 (table, console, json, csv, html, pdf)
+        /// 출력 형식 (table, console, json, csv, html, pdf, xlsx)
         #[clap(long, default_value = "table")]
         format: String,
         /// 출력 파일 경로
@@ -381,6 +427,98 @@ pub enum ReportCommands {
         #[clap(long)]
         include_charts: bool,
     },
+    /// 사용자 정의 보고서 (YAML 스펙)
+    Custom {
+        /// 보고서 스펙 파일 경로 (YAML)
+        #[clap(long)]
+        spec: String,
+        /// 시작 날짜 (YYYY-MM-DD, 스펙의 기간을 덮어씀)
+        #[clap(long)]
+        from_date: Option<String>,
+        /// 종료 날짜 (YYYY-MM-DD, 스펙의 기간을 덮어씀)
+        #[clap(long)]
+        to_date: Option<String>,
+        /// 출력 형식 (console, json, csv, html, pdf, xlsx)
+        #[clap(long, default_value = "console")]
+        format: String,
+        /// 출력 파일 경로
+        #[clap(long)]
+        output: Option<String>,
+        /// 차트 포함 여부 (html, pdf)
+        #[clap(long)]
+        include_charts: bool,
+        /// 생성된 SQL 출력
+        #[clap(long)]
+        show_sql: bool,
+    },
+    /// 보고서 예약 관리
+    #[clap(subcommand)]
+    Schedule(ReportScheduleCommands),
+    /// 예약된 보고서 실행 (종료할 때까지 반복)
+    RunScheduler {
+        /// 예약 확인 주기 (초)
+        #[clap(long, default_value = "60")]
+        interval: u64,
+        /// 실행 시각이 된 보고서를 한 번만 실행하고 종료
+        #[clap(long)]
+        once: bool,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ReportScheduleCommands {
+    /// 보고서 예약 추가
+    Add {
+        /// 스케줄 이름
+        name: String,
+        /// cron 표현식 (분 시 일 월 요일, 예: "0 8 * * mon")
+        #[clap(long)]
+        cron: String,
+        /// 보고서 종류 (sales-summary, inventory-status, customer-analysis, financial-overview)
+        #[clap(long)]
+        report: String,
+        /// 기간 (daily, weekly, monthly, quarterly, yearly)
+        #[clap(long, default_value = "weekly")]
+        period: String,
+        /// 출력 형식 (json, csv, html, pdf, xlsx)
+        #[clap(long, default_value = "pdf")]
+        format: String,
+        /// 고객 분석 기간 (months)
+        #[clap(long)]
+        months: Option<u32>,
+        /// 특정 카테고리만 포함 (재고 보고서)
+        #[clap(long)]
+        category: Option<String>,
+        /// 저재고만 표시 (재고 보고서)
+        #[clap(long)]
+        low_stock_only: bool,
+        /// 저재고 기준 수량 (재고 보고서)
+        #[clap(long)]
+        threshold: Option<u32>,
+        /// 차트 포함 여부 (html, pdf)
+        #[clap(long)]
+        include_charts: bool,
+    },
+    /// 보고서 예약 목록
+    List {
+        /// 출력 형식 (table, json)
+        #[clap(long, default_value = "table")]
+        format: String,
+    },
+    /// 보고서 예약 삭제
+    Remove {
+        /// 스케줄 이름
+        name: String,
+    },
+    /// 예약 실행 이력
+    History {
+        /// 스케줄 이름
+        #[clap(long)]
+        name: Option<String>,
+        /// 표시할 최대 개수
+        #[clap(long, default_value = "20")]
+        limit: u32,
+    },
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -408,6 +546,21 @@ pub enum ConfigCommands {
     },
     /// 설정 파일 경로 표시
     Path,
+    /// 설정 검증 (설정 파일, ERP_ 환경변수, 설정 저장소의 알 수 없거나 잘못된 키)
+    Validate,
+    /// 설정 변경 이력 (비밀 값은 마스킹)
+    History {
+        /// 설정 키
+        key: String,
+    },
+    /// 설정을 이전 버전의 값으로 되돌림
+    Rollback {
+        /// 설정 키
+        key: String,
+        /// 되돌릴 버전 (config history로 확인)
+        #[clap(long)]
+        to: i32,
+    },
     /// 설정 초기화
     Reset {
         /// 강제 초기화 (확인 없이)
@@ -416,6 +569,222 @@ pub enum ConfigCommands {
     },
 }
 
+#[derive(Debug, clap::Subcommand)]
+pub enum AuditCommands {
+    /// 감사 이벤트 검색
+    Search {
+        /// 사용자 이름
+        #[clap(long)]
+        user: Option<String>,
+        /// 리소스 (products, customers, sales_orders, config 등)
+        #[clap(long)]
+        resource: Option<String>,
+        /// 리소스 ID (SKU, 고객 코드, 주문 번호, 설정 키 등)
+        #[clap(long)]
+        resource_id: Option<String>,
+        /// 작업 (data_modified, data_deleted, config_changed 등)
+        #[clap(long)]
+        action: Option<String>,
+        /// 조회 시작 시점 (예: 30m, 24h, 7d, 2w 또는 YYYY-MM-DD)
+        #[clap(long)]
+        since: Option<String>,
+        /// 최대 조회 건수
+        #[clap(long, default_value = "50")]
+        limit: u32,
+        /// 변경 내역(details) 표시
+        #[clap(long)]
+        details: bool,
+        /// 출력 형식 (table, json)
+        #[clap(long, default_value = "table")]
+        format: String,
+    },
+    /// 보존 기간(security.audit_retention_days)이 지난 이벤트 삭제
+    Cleanup {
+        /// 보존 기간 재정의 (일)
+        #[clap(long)]
+        retention_days: Option<i64>,
+    },
+    /// 해시 체인 무결성 검증 (변조 시 첫 번째 끊어진 지점 보고)
+    Verify {
+        /// 검증 시작 시점 (예: 24h, 7d 또는 YYYY-MM-DD)
+        #[clap(long)]
+        from: Option<String>,
+        /// 검증 종료 날짜 (YYYY-MM-DD, 해당 날짜 포함)
+        #[clap(long)]
+        to: Option<String>,
+        /// 함께 대조할 체크포인트 내보내기 파일
+        #[clap(long)]
+        checkpoints: Option<String>,
+    },
+    /// 서명된 체크포인트 관리
+    #[clap(subcommand)]
+    Checkpoint(AuditCheckpointCommands),
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum AuditCheckpointCommands {
+    /// 현재 체인 끝에 체크포인트 생성
+    Create,
+    /// 체크포인트 목록 조회
+    List {
+        /// 조회 시작 시점 (예: 7d 또는 YYYY-MM-DD)
+        #[clap(long)]
+        since: Option<String>,
+    },
+    /// 오프라인 검증용 파일로 체크포인트 내보내기
+    Export {
+        /// 출력 파일 경로
+        #[clap(long)]
+        output: String,
+        /// 내보낼 체크포인트 생성 시작 시점
+        #[clap(long)]
+        since: Option<String>,
+    },
+    /// 내보낸 체크포인트 파일의 서명 검증 (데이터베이스 불필요)
+    VerifyFile {
+        /// 체크포인트 파일 경로
+        file: String,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SecurityCommands {
+    /// 고객 PII를 현재 키로 재암호화 (평문으로 남은 행도 암호화)
+    RotateKeys {
+        /// 트랜잭션당 처리할 행 수
+        #[clap(long, default_value = "500")]
+        batch_size: u32,
+    },
+    /// 새 PII 암호화 키 생성 (base64)
+    GenerateKey,
+    /// 요청 횟수 제한 및 로그인 잠금 관리
+    #[clap(subcommand)]
+    Limits(SecurityLimitCommands),
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SecurityLimitCommands {
+    /// 식별자의 구간별 요청 수와 차단 상태 표시
+    Show {
+        /// 식별자 (예: login:<사용자>, export:<사용자>, password_reset:<사용자>)
+        identifier: String,
+    },
+    /// 식별자의 기록을 삭제하여 제한/잠금 해제
+    Reset {
+        /// 식별자 (예: login:<사용자>, export:<사용자>, password_reset:<사용자>)
+        identifier: String,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum BackupCommands {
+    /// 전체 백업 생성 (데이터베이스 덤프 + backup.include_paths)
+    Create {
+        /// 압축하지 않음
+        #[clap(long)]
+        no_compress: bool,
+        /// 설정과 무관하게 암호화 (BACKUP_PASSWORD 필요)
+        #[clap(long)]
+        encrypt: bool,
+    },
+    /// 백업 목록
+    List,
+    /// 백업 파일 크기/체크섬 및 아카이브 내용 검증
+    Verify {
+        /// 백업 ID (앞부분만 입력 가능)
+        id: String,
+    },
+    /// 백업을 원격 저장소(backup.remote)에 업로드
+    Upload {
+        /// 백업 ID (앞부분만 입력 가능)
+        id: String,
+    },
+    /// 백업에서 데이터베이스와 파일 복원 (현재 데이터를 덮어씀)
+    Restore {
+        /// 백업 ID (앞부분만 입력 가능)
+        id: String,
+        /// 확인 질문 없이 복원
+        #[clap(long)]
+        yes: bool,
+        /// 파일을 풀어 놓을 디렉터리
+        #[clap(long, default_value = "restore")]
+        target_dir: String,
+        /// 복원 전 검증 생략
+        #[clap(long)]
+        skip_verify: bool,
+    },
+    /// 보존 기간이 지난 백업 삭제 (원격 저장소 포함)
+    Prune {
+        /// 보존 일수 (기본값: backup.retention_days)
+        #[clap(long)]
+        keep_days: Option<i64>,
+        /// 삭제 대상만 표시
+        #[clap(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum DataCommands {
+    /// 모듈 데이터를 스냅샷 아카이브(tar, 테이블별 NDJSON)로 내보내기
+    Export {
+        /// 내보낼 모듈 (쉼표로 구분, all: 전체) - inventory, customers, sales, config, reports
+        #[clap(long, default_value = "all")]
+        modules: String,
+        /// 출력 파일 경로 (기본값: erp_data_<시각>.tar)
+        #[clap(long)]
+        output: Option<String>,
+    },
+    /// 스냅샷 아카이브를 현재 데이터베이스로 가져오기
+    Import {
+        /// 스냅샷 파일 경로
+        file: String,
+        /// 가져올 모듈 (기본값: 스냅샷의 전체 모듈)
+        #[clap(long)]
+        modules: Option<String>,
+        /// 기존 행과 충돌 시 처리 방식
+        #[clap(long, default_value = "fail", value_parser = ["fail", "skip", "overwrite"])]
+        on_conflict: String,
+        /// 실제로 반영하지 않고 결과만 확인
+        #[clap(long)]
+        dry_run: bool,
+    },
+    /// 스냅샷 매니페스트 표시 (데이터베이스 접속 불필요)
+    Inspect {
+        /// 스냅샷 파일 경로
+        file: String,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SystemCommands {
+    /// 데이터베이스, 마이그레이션, 디스크, 백업 상태 점검
+    /// (종료 코드: 0 정상, 1 경고, 2 위험)
+    Health {
+        /// 출력 형식
+        #[clap(long, default_value = "table", value_parser = ["table", "json"])]
+        format: String,
+    },
+    /// 주기적으로 메트릭을 수집/저장하고 활성 알림 표시
+    Monitor {
+        /// 수집 주기 (초)
+        #[clap(long, default_value = "30", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+        /// 지정한 횟수만큼 수집 후 종료 (기본값: Ctrl+C까지 계속)
+        #[clap(long)]
+        count: Option<u64>,
+    },
+    /// --profile로 기록한 명령 실행 시간 추이 (p50/p95/p99)
+    Perf {
+        /// 명령어 (예: sales, "reports financial-overview"; 하위 명령 포함)
+        #[clap(long)]
+        command: Option<String>,
+        /// 조회 시작 시점 (예: 7d, 24h, 2024-01-01)
+        #[clap(long, default_value = "7d")]
+        since: String,
+    },
+}
+
 #[derive(Debug, Clone, clap::Subcommand)]
 pub enum MigrateCommands {
     /// 데이터베이스 초기화 (처음 실행 시)
@@ -429,22 +798,25 @@ pub enum MigrateCommands {
         /// 특정 버전까지만 실행
         #[clap(long)]
         target: Option<String>,
-        /// 마이그레이션 파일 디렉토리
+        /// 마이그레이션 파일 디렉토리 (SQLite는 하위 sqlite 디렉토리 사용)
         #[clap(long, default_value = "migrations")]
         dir: String,
+        /// 적용하지 않고 실행 계획과 SQL만 출력
+        #[clap(long)]
+        dry_run: bool,
     },
     /// 마이그레이션 롤백
     Down {
         /// 롤백할 버전 (지정하지 않으면 1단계만)
         #[clap(long)]
         target: Option<String>,
-        /// 마이그레이션 파일 디렉토리
+        /// 마이그레이션 파일 디렉토리 (SQLite는 하위 sqlite 디렉토리 사용)
         #[clap(long, default_value = "migrations")]
         dir: String,
     },
     /// 마이그레이션 상태 확인
     Status {
-        /// 마이그레이션 파일 디렉토리
+        /// 마이그레이션 파일 디렉토리 (SQLite는 하위 sqlite 디렉토리 사용)
         #[clap(long, default_value = "migrations")]
         dir: String,
     },
@@ -456,6 +828,14 @@ pub enum MigrateCommands {
         #[clap(long, default_value = "migrations")]
         dir: String,
     },
+    /// 현재 스키마를 하나의 베이스라인 마이그레이션으로 압축
+    Baseline {
+        /// 마이그레이션 파일 디렉토리 (SQLite는 하위 sqlite 디렉토리 사용)
+        #[clap(long, default_value = "migrations")]
+        dir: String,
+    },
+    /// 저장소가 기대하는 스키마와 실제 데이터베이스 스키마 비교
+    VerifySchema,
     /// 데이터베이스 연결 테스트
     Test,
 }
@@ -1,6 +1,6 @@
 use crate::utils::error::{ErpError, ErpResult};
 use crate::utils::validation::validate_email;
-use chrono::{NaiveDate, Utc};
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
 use rust_decimal::Decimal;
 use std::str::FromStr;
 
@@ -213,6 +213,46 @@ impl CliValidator {
         })
     }
 
+    /// 상대 기간(30m, 24h, 7d, 2w) 또는 날짜(YYYY-MM-DD, 로컬 자정)를 시작 시점으로 변환
+    pub fn validate_since(since: &str) -> ErpResult<DateTime<Utc>> {
+        let since = since.trim();
+        let invalid = || {
+            ErpError::validation(
+                "since",
+                "기간 형식이 올바르지 않습니다 (예: 30m, 24h, 7d, 2w 또는 YYYY-MM-DD)",
+            )
+        };
+
+        if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+            let midnight = date.and_hms_opt(0, 0, 0).ok_or_else(invalid)?;
+            return Local
+                .from_local_datetime(&midnight)
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc))
+                .ok_or_else(invalid);
+        }
+
+        let unit_start = since
+            .char_indices()
+            .last()
+            .map(|(i, _)| i)
+            .ok_or_else(invalid)?;
+        let (amount, unit) = since.split_at(unit_start);
+        let amount: i64 = amount.parse().map_err(|_| invalid())?;
+        if amount <= 0 {
+            return Err(invalid());
+        }
+        let duration = match unit {
+            "m" => Duration::minutes(amount),
+            "h" => Duration::hours(amount),
+            "d" => Duration::days(amount),
+            "w" => Duration::weeks(amount),
+            _ => return Err(invalid()),
+        };
+
+        Ok(Utc::now() - duration)
+    }
+
     pub fn validate_date_range(
         from_date: &Option<String>,
         to_date: &Option<String>,
@@ -258,13 +298,13 @@ impl CliValidator {
     }
 
     pub fn validate_report_format(format: &str) -> ErpResult<String> {
-        let valid_formats = ["console", "csv", "json", "pdf", "html"];
+        let valid_formats = ["console", "csv", "json", "pdf", "html", "xlsx"];
         let normalized = format.trim().to_lowercase();
 
         if !valid_formats.contains(&normalized.as_str()) {
             return Err(ErpError::validation(
                 "format",
-                "출력 형식은 'console', 'csv', 'json', 'pdf', 'html' 중 하나여야 합니다",
+                "출력 형식은 'console', 'csv', 'json', 'pdf', 'html', 'xlsx' 중 하나여야 합니다",
             ));
         }
 
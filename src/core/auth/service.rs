@@ -1,7 +1,11 @@
 use crate::core::auth::{jwt::JwtService, rbac::RbacService};
+use crate::core::config::AppConfig;
 use crate::core::database::models::{
     CreateUserRequest, UpdateUserRequest, User, UserRole, UserStatus,
 };
+use crate::core::security::rate_limiter::{
+    login_identifier, password_reset_identifier, password_reset_source_identifier, RateLimiterTrait,
+};
 use crate::utils::crypto::HashingService;
 use crate::utils::error::{ErpError, ErpResult};
 use crate::utils::validation::ValidationService;
@@ -49,8 +53,13 @@ pub struct ChangePasswordRequest {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResetPasswordRequest {
+    /// Account whose password is being reset
+    pub username: String,
     pub token: String,
     pub new_password: String,
+    /// Where the request came from (e.g. client address), when known
+    #[serde(default)]
+    pub source: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +78,8 @@ pub struct AuthenticatedUser {
 pub struct AuthConfig {
     pub max_login_attempts: i32,
     pub lockout_duration_minutes: i64,
+    /// Password reset attempts allowed per hour across all users
+    pub password_reset_limit_per_hour: i32,
     pub password_expiry_days: Option<i64>,
     pub require_password_change_on_first_login: bool,
     pub enable_session_timeout: bool,
@@ -80,6 +91,7 @@ impl Default for AuthConfig {
         Self {
             max_login_attempts: 5,
             lockout_duration_minutes: 15,
+            password_reset_limit_per_hour: 5,
             password_expiry_days: Some(90),
             require_password_change_on_first_login: true,
             enable_session_timeout: true,
@@ -88,6 +100,18 @@ impl Default for AuthConfig {
     }
 }
 
+impl AuthConfig {
+    pub fn from_app_config(config: &AppConfig) -> Self {
+        Self {
+            max_login_attempts: config.auth.max_login_attempts as i32,
+            lockout_duration_minutes: i64::from(config.auth.lockout_duration_minutes),
+            password_reset_limit_per_hour: config.security.password_reset_limit_per_hour as i32,
+            password_expiry_days: Some(i64::from(config.security.require_password_change_days)),
+            ..Self::default()
+        }
+    }
+}
+
 #[async_trait::async_trait]
 pub trait UserRepository: Send + Sync + Any {
     async fn find_by_username(&self, username: &str) -> ErpResult<Option<User>>;
@@ -105,10 +129,13 @@ pub struct AuthService {
     rbac_service: RbacService,
     hashing_service: HashingService,
     validation_service: ValidationService,
-    _config: AuthConfig,
+    config: AuthConfig,
+    rate_limiter: Arc<dyn RateLimiterTrait>,
 }
 
 impl AuthService {
+    /// `rate_limiter` enforces login lockout and the password reset limit; use
+    /// `PostgresRateLimiter` so the counters survive across CLI invocations
     pub fn new(
         user_repository: Arc<dyn UserRepository>,
         jwt_service: JwtService,
@@ -116,6 +143,7 @@ impl AuthService {
         hashing_service: HashingService,
         validation_service: ValidationService,
         config: AuthConfig,
+        rate_limiter: Arc<dyn RateLimiterTrait>,
     ) -> Self {
         Self {
             user_repository,
@@ -123,21 +151,51 @@ impl AuthService {
             rbac_service,
             hashing_service,
             validation_service,
-            _config: config,
+            config,
+            rate_limiter,
         }
     }
 
+    /// Records a login attempt, refusing it if the lockout limit is already
+    /// used up. Checking and recording in one `acquire` keeps concurrent
+    /// attempts from all passing the check before any of them is counted;
+    /// a successful login clears the count again.
+    async fn record_login_attempt(&self, identifier: &str) -> ErpResult<()> {
+        let allowed = self
+            .rate_limiter
+            .acquire(
+                identifier,
+                self.config.max_login_attempts.max(1) as u64,
+                (self.config.lockout_duration_minutes.max(1) * 60) as u64,
+            )
+            .await?;
+        if !allowed {
+            warn!("Login rejected for locked out identifier: {}", identifier);
+            return Err(ErpError::Authentication(format!(
+                "Too many failed login attempts; try again in {} minutes",
+                self.config.lockout_duration_minutes
+            )));
+        }
+        Ok(())
+    }
+
     pub async fn login(&self, request: LoginRequest) -> ErpResult<LoginResponse> {
         self.validation_service
             .validate_username(&request.username)?;
         self.validation_service
             .validate_password(&request.password)?;
 
-        let user = self
+        let identifier = login_identifier(&request.username);
+        self.record_login_attempt(&identifier).await?;
+
+        // Unknown usernames count too, so probing for accounts is throttled as well
+        let Some(user) = self
             .user_repository
             .find_by_username(&request.username)
             .await?
-            .ok_or_else(|| ErpError::Authentication("Invalid credentials".to_string()))?;
+        else {
+            return Err(ErpError::Authentication("Invalid credentials".to_string()));
+        };
 
         if !user.can_login() {
             return Err(ErpError::Authentication(
@@ -159,6 +217,7 @@ impl AuthService {
         }
 
         // Successful login
+        self.rate_limiter.reset_limit(&identifier).await?;
         self.user_repository
             .update_login_attempt(user.id, true)
             .await?;
@@ -302,6 +361,26 @@ impl AuthService {
         self.validation_service
             .validate_password(&request.new_password)?;
 
+        // Reset tokens are guessable only by volume, so attempts are limited per
+        // source and per account. The source is checked first so one client
+        // flooding an account runs out of its own budget, not the account's.
+        let limit = self.config.password_reset_limit_per_hour.max(0) as u64;
+        let mut identifiers: Vec<String> = request
+            .source
+            .as_deref()
+            .map(password_reset_source_identifier)
+            .into_iter()
+            .collect();
+        identifiers.push(password_reset_identifier(&request.username));
+        for identifier in &identifiers {
+            if !self.rate_limiter.acquire(identifier, limit, 3600).await? {
+                return Err(ErpError::validation(
+                    "rate_limit",
+                    "too many password reset attempts",
+                ));
+            }
+        }
+
         // For now, this is a placeholder implementation
         warn!("Password reset requested with token: {}", request.token);
 
@@ -592,6 +671,7 @@ mod tests {
     use super::*;
     use crate::core::auth::jwt::{JwtConfig, JwtService};
     use crate::core::database::models::{CreateUserRequest, UserRole};
+    use crate::core::security::rate_limiter::RateLimiter;
     use crate::utils::crypto::{HashedPassword, HashingService};
 
     fn create_test_auth_service() -> AuthService {
@@ -609,6 +689,7 @@ mod tests {
             hashing_service,
             validation_service,
             config,
+            Arc::new(RateLimiter::with_default_config()),
         )
     }
 
@@ -683,6 +764,155 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_login_lockout_after_failed_attempts() {
+        let limiter = Arc::new(RateLimiter::with_default_config());
+        let auth_service = AuthService::new(
+            Arc::new(MockUserRepository::new()),
+            JwtService::new(JwtConfig::default()),
+            RbacService::new(),
+            HashingService::new(),
+            ValidationService::new(),
+            AuthConfig {
+                max_login_attempts: 2,
+                ..AuthConfig::default()
+            },
+            limiter.clone(),
+        );
+
+        let mut user = auth_service
+            .register(CreateUserRequest {
+                username: "lockuser".to_string(),
+                email: "lock@example.com".to_string(),
+                password: "StrongPassword123!".to_string(),
+                first_name: "Lock".to_string(),
+                last_name: "User".to_string(),
+                role: UserRole::Employee,
+            })
+            .await
+            .unwrap();
+        user.status = UserStatus::Active;
+        auth_service.user_repository.update(&user).await.unwrap();
+
+        let login = |password: &str| LoginRequest {
+            username: "lockuser".to_string(),
+            password: password.to_string(),
+            remember_me: None,
+        };
+
+        for _ in 0..2 {
+            assert!(auth_service
+                .login(login("WrongPassword123!"))
+                .await
+                .is_err());
+        }
+
+        // Even the right password is refused while locked out
+        match auth_service.login(login("StrongPassword123!")).await {
+            Err(ErpError::Authentication(message)) => assert!(message.contains("Too many")),
+            other => panic!("Expected lockout, got {:?}", other.map(|r| r.username)),
+        }
+
+        limiter
+            .reset_limit(&login_identifier("lockuser"))
+            .await
+            .unwrap();
+        assert!(auth_service
+            .login(login("StrongPassword123!"))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_failed_logins_respect_lockout() {
+        let auth_service = Arc::new(AuthService::new(
+            Arc::new(MockUserRepository::new()),
+            JwtService::new(JwtConfig::default()),
+            RbacService::new(),
+            HashingService::new(),
+            ValidationService::new(),
+            AuthConfig {
+                max_login_attempts: 3,
+                ..AuthConfig::default()
+            },
+            Arc::new(RateLimiter::with_default_config()),
+        ));
+
+        let attempts: Vec<_> = (0..10)
+            .map(|_| {
+                let auth_service = auth_service.clone();
+                tokio::spawn(async move {
+                    auth_service
+                        .login(LoginRequest {
+                            username: "ghost".to_string(),
+                            password: "WrongPassword123!".to_string(),
+                            remember_me: None,
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        let mut checked = 0;
+        for attempt in attempts {
+            match attempt.await.unwrap() {
+                Err(ErpError::Authentication(message)) if message.contains("Too many") => {}
+                Err(ErpError::Authentication(_)) => checked += 1,
+                other => panic!("Expected authentication error, got {:?}", other.is_ok()),
+            }
+        }
+        // Only the attempts within the limit ever reach the credential check
+        assert_eq!(checked, 3);
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_limit() {
+        let auth_service = AuthService::new(
+            Arc::new(MockUserRepository::new()),
+            JwtService::new(JwtConfig::default()),
+            RbacService::new(),
+            HashingService::new(),
+            ValidationService::new(),
+            AuthConfig {
+                password_reset_limit_per_hour: 1,
+                ..AuthConfig::default()
+            },
+            Arc::new(RateLimiter::with_default_config()),
+        );
+
+        let request = |username: &str, source: Option<&str>| ResetPasswordRequest {
+            username: username.to_string(),
+            token: "token".to_string(),
+            new_password: "StrongPassword123!".to_string(),
+            source: source.map(str::to_string),
+        };
+        assert!(auth_service
+            .reset_password(request("alice", None))
+            .await
+            .is_ok());
+        assert!(auth_service
+            .reset_password(request("alice", None))
+            .await
+            .is_err());
+
+        // Another account keeps its own budget
+        assert!(auth_service
+            .reset_password(request("bob", Some("10.0.0.1")))
+            .await
+            .is_ok());
+
+        // A source that used up its budget is refused without touching the
+        // target account's bucket
+        assert!(auth_service
+            .reset_password(request("carol", Some("10.0.0.1")))
+            .await
+            .is_err());
+        assert!(auth_service
+            .reset_password(request("carol", Some("10.0.0.2")))
+            .await
+            .is_ok());
+    }
+
     #[tokio::test]
     async fn test_token_validation() {
         let auth_service = create_test_auth_service();
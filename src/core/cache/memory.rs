@@ -0,0 +1,112 @@
+//! In-process LRU cache backend.
+
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use lru::LruCache;
+
+use super::CacheBackend;
+use crate::utils::error::ErpResult;
+
+struct Entry {
+    value: String,
+    expires_at: Instant,
+}
+
+/// Least recently used entries are evicted once `capacity` is reached
+pub struct MemoryCache {
+    entries: Mutex<LruCache<String, Entry>>,
+}
+
+impl MemoryCache {
+    /// `None` when `capacity` is zero
+    pub fn new(capacity: usize) -> Option<Self> {
+        let capacity = NonZeroUsize::new(capacity)?;
+        Some(Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, LruCache<String, Entry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryCache {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn get(&self, key: &str) -> ErpResult<Option<String>> {
+        let mut entries = self.entries();
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Ok(Some(entry.value.clone())),
+            Some(_) => {
+                entries.pop(key);
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, key: &str, value: String, ttl: Duration) -> ErpResult<()> {
+        let entry = Entry {
+            value,
+            expires_at: Instant::now() + ttl,
+        };
+        self.entries().put(key.to_string(), entry);
+        Ok(())
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> ErpResult<u64> {
+        let mut entries = self.entries();
+        let keys: Vec<String> = entries
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &keys {
+            entries.pop(key);
+        }
+        Ok(keys.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_cache_evicts_least_recently_used_and_expired_entries() {
+        assert!(MemoryCache::new(0).is_none());
+
+        let cache = MemoryCache::new(2).unwrap();
+        let ttl = Duration::from_secs(60);
+        cache.set("a", "1".into(), ttl).await.unwrap();
+        cache.set("b", "2".into(), ttl).await.unwrap();
+        assert_eq!(cache.get("a").await.unwrap().as_deref(), Some("1"));
+
+        // "b" is now the least recently used entry
+        cache.set("c", "3".into(), ttl).await.unwrap();
+        assert_eq!(cache.get("b").await.unwrap(), None);
+        assert_eq!(cache.len(), 2);
+
+        cache.set("d", "4".into(), Duration::ZERO).await.unwrap();
+        assert_eq!(cache.get("d").await.unwrap(), None);
+        assert_eq!(cache.len(), 1);
+
+        assert_eq!(cache.delete_prefix("c").await.unwrap(), 1);
+        assert!(cache.is_empty());
+    }
+}
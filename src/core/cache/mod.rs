@@ -0,0 +1,281 @@
+//! Query result caching shared by the services.
+//!
+//! [`QueryCache`] stores JSON-serialised query results under a [`CacheScope`]
+//! in a pluggable [`CacheBackend`]: an in-process LRU by default, or Redis
+//! when `cache.redis_url` is set. Writers invalidate a whole scope, and a
+//! failing backend only costs a cache miss, never the query itself.
+
+mod memory;
+mod redis_cache;
+
+pub use memory::MemoryCache;
+pub use redis_cache::RedisCache;
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use crate::core::config::AppConfig;
+use crate::utils::error::ErpResult;
+
+/// Prefix of every key, so the cache can share a Redis database
+const KEY_NAMESPACE: &str = "erp";
+
+/// Storage for cached values
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn get(&self, key: &str) -> ErpResult<Option<String>>;
+
+    async fn set(&self, key: &str, value: String, ttl: Duration) -> ErpResult<()>;
+
+    /// Removes every key starting with `prefix` and returns how many were removed
+    async fn delete_prefix(&self, prefix: &str) -> ErpResult<u64>;
+}
+
+/// Group of cached results invalidated together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheScope {
+    /// Values from the `system_config` table
+    Config,
+    /// Product lookups
+    Products,
+    /// Report summaries, stale after any sales, stock or customer change
+    Reports,
+}
+
+impl CacheScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheScope::Config => "config",
+            CacheScope::Products => "products",
+            CacheScope::Reports => "reports",
+        }
+    }
+
+    fn prefix(&self) -> String {
+        format!("{}:{}:", KEY_NAMESPACE, self.as_str())
+    }
+}
+
+#[derive(Clone)]
+pub struct QueryCache {
+    backend: Option<Arc<dyn CacheBackend>>,
+    ttl: Duration,
+}
+
+impl std::fmt::Debug for QueryCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryCache")
+            .field("backend", &self.backend.as_ref().map(|b| b.name()))
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl QueryCache {
+    /// Cache that stores nothing; every lookup goes to the loader
+    pub fn disabled() -> Self {
+        Self {
+            backend: None,
+            ttl: Duration::ZERO,
+        }
+    }
+
+    pub fn new(backend: Arc<dyn CacheBackend>, ttl: Duration) -> Self {
+        Self {
+            backend: Some(backend),
+            ttl,
+        }
+    }
+
+    pub fn in_memory(capacity: usize, ttl: Duration) -> Self {
+        match MemoryCache::new(capacity) {
+            Some(memory) => Self::new(Arc::new(memory), ttl),
+            None => Self::disabled(),
+        }
+    }
+
+    /// Backend chosen from `[cache]`: Redis when `redis_url` is set, otherwise
+    /// an LRU holding `performance.query_cache_size` entries
+    pub fn from_config(config: &AppConfig) -> ErpResult<Self> {
+        let cache = &config.cache;
+        if !cache.enable_cache || cache.default_ttl_seconds == 0 {
+            debug!("Query cache disabled");
+            return Ok(Self::disabled());
+        }
+
+        let ttl = Duration::from_secs(cache.default_ttl_seconds);
+        let redis_url = cache.redis_url.trim();
+        if redis_url.is_empty() {
+            info!(
+                "Query cache: in-memory LRU ({} entries)",
+                config.performance.query_cache_size
+            );
+            return Ok(Self::in_memory(config.performance.query_cache_size, ttl));
+        }
+
+        let redis = RedisCache::new(redis_url)?;
+        info!("Query cache: Redis at {}", redis.label());
+        Ok(Self::new(Arc::new(redis), ttl))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.backend.is_some()
+    }
+
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.as_ref().map_or("disabled", |b| b.name())
+    }
+
+    fn key(scope: CacheScope, key: &str) -> String {
+        format!("{}{}", scope.prefix(), key)
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, scope: CacheScope, key: &str) -> Option<T> {
+        let backend = self.backend.as_ref()?;
+        let key = Self::key(scope, key);
+        match backend.get(&key).await {
+            Ok(Some(value)) => match serde_json::from_str(&value) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    warn!("Discarding unreadable cache entry {}: {}", key, e);
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Cache lookup for {} failed: {}", key, e);
+                None
+            }
+        }
+    }
+
+    pub async fn set<T: Serialize>(&self, scope: CacheScope, key: &str, value: &T) {
+        let Some(backend) = self.backend.as_ref() else {
+            return;
+        };
+        let key = Self::key(scope, key);
+        let value = match serde_json::to_string(value) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!("Cannot cache {}: {}", key, e);
+                return;
+            }
+        };
+        if let Err(e) = backend.set(&key, value, self.ttl).await {
+            warn!("Cache store for {} failed: {}", key, e);
+        }
+    }
+
+    /// Cached value of `key`, running `load` and caching its result on a miss.
+    /// Errors from `load` are returned as is and never cached.
+    pub async fn get_or_load<T, F, Fut>(
+        &self,
+        scope: CacheScope,
+        key: &str,
+        load: F,
+    ) -> ErpResult<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ErpResult<T>>,
+    {
+        if let Some(value) = self.get(scope, key).await {
+            debug!("Cache hit: {}:{}", scope.as_str(), key);
+            return Ok(value);
+        }
+
+        let value = load().await?;
+        self.set(scope, key, &value).await;
+        Ok(value)
+    }
+
+    /// Drops every cached value of `scope`
+    pub async fn invalidate(&self, scope: CacheScope) {
+        let Some(backend) = self.backend.as_ref() else {
+            return;
+        };
+        match backend.delete_prefix(&scope.prefix()).await {
+            Ok(removed) => debug!("Invalidated {} {} cache entries", removed, scope.as_str()),
+            Err(e) => warn!("Failed to invalidate {} cache: {}", scope.as_str(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn load_counting(calls: &AtomicUsize, value: u32) -> ErpResult<u32> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok(value)
+    }
+
+    #[tokio::test]
+    async fn test_get_or_load_caches_until_scope_is_invalidated() {
+        let cache = QueryCache::in_memory(10, Duration::from_secs(60));
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..3 {
+            let value = cache
+                .get_or_load(CacheScope::Config, "tax_rate", || load_counting(&calls, 7))
+                .await
+                .unwrap();
+            assert_eq!(value, 7);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Other scopes keep their entries
+        cache.set(CacheScope::Reports, "summary", &1u32).await;
+        cache.invalidate(CacheScope::Config).await;
+        assert_eq!(cache.get::<u32>(CacheScope::Config, "tax_rate").await, None);
+        assert_eq!(
+            cache.get::<u32>(CacheScope::Reports, "summary").await,
+            Some(1)
+        );
+
+        cache
+            .get_or_load(CacheScope::Config, "tax_rate", || load_counting(&calls, 8))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Failed loads are not cached
+        let failed: ErpResult<u32> = cache
+            .get_or_load(CacheScope::Products, "SKU-2", || async {
+                Err(crate::utils::error::ErpError::internal("boom"))
+            })
+            .await;
+        assert!(failed.is_err());
+        assert_eq!(cache.get::<u32>(CacheScope::Products, "SKU-2").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_cache_always_loads() {
+        let cache = QueryCache::disabled();
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            cache
+                .get_or_load(CacheScope::Products, "SKU-1", || load_counting(&calls, 1))
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(!cache.is_enabled());
+    }
+}
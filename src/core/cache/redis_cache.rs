@@ -0,0 +1,261 @@
+//! Redis cache backend.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncCommands, Client};
+use tokio::sync::OnceCell;
+use tracing::warn;
+
+use super::CacheBackend;
+use crate::utils::error::{ErpError, ErpResult};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(2);
+/// How long to skip Redis after a failed connection attempt
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(30);
+const SCAN_BATCH: usize = 500;
+
+/// Shared cache in Redis; the connection is opened on first use
+pub struct RedisCache {
+    client: Client,
+    connection: OnceCell<MultiplexedConnection>,
+    retry_after: Mutex<Option<Instant>>,
+}
+
+impl RedisCache {
+    pub fn new(url: &str) -> ErpResult<Self> {
+        let client = Client::open(url)
+            .map_err(|e| ErpError::config(format!("Invalid cache.redis_url: {}", e)))?;
+        Ok(Self {
+            client,
+            connection: OnceCell::new(),
+            retry_after: Mutex::new(None),
+        })
+    }
+
+    /// Server address without credentials, for logs
+    pub fn label(&self) -> String {
+        self.client.get_connection_info().addr.to_string()
+    }
+
+    async fn connection(&self) -> ErpResult<MultiplexedConnection> {
+        if let Some(connection) = self.connection.get() {
+            return Ok(connection.clone());
+        }
+
+        {
+            let retry_after = self.retry_after.lock().unwrap_or_else(|e| e.into_inner());
+            if retry_after.is_some_and(|at| Instant::now() < at) {
+                return Err(ErpError::internal(format!(
+                    "Redis at {} is unavailable",
+                    self.label()
+                )));
+            }
+        }
+
+        let connected = self
+            .connection
+            .get_or_try_init(|| async {
+                self.client
+                    .get_multiplexed_async_connection_with_timeouts(
+                        RESPONSE_TIMEOUT,
+                        CONNECT_TIMEOUT,
+                    )
+                    .await
+            })
+            .await;
+
+        match connected {
+            Ok(connection) => Ok(connection.clone()),
+            Err(e) => {
+                warn!(
+                    "Cannot connect to Redis at {}, caching paused for {}s: {}",
+                    self.label(),
+                    RECONNECT_INTERVAL.as_secs(),
+                    e
+                );
+                *self.retry_after.lock().unwrap_or_else(|e| e.into_inner()) =
+                    Some(Instant::now() + RECONNECT_INTERVAL);
+                Err(redis_error(e))
+            }
+        }
+    }
+}
+
+fn redis_error(error: redis::RedisError) -> ErpError {
+    ErpError::internal(format!("Redis error: {}", error))
+}
+
+#[async_trait]
+impl CacheBackend for RedisCache {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn get(&self, key: &str) -> ErpResult<Option<String>> {
+        let mut connection = self.connection().await?;
+        connection.get(key).await.map_err(redis_error)
+    }
+
+    async fn set(&self, key: &str, value: String, ttl: Duration) -> ErpResult<()> {
+        // Redis rejects a zero expiry, and such an entry would be stale anyway
+        let seconds = ttl.as_secs();
+        if seconds == 0 {
+            return Ok(());
+        }
+        let mut connection = self.connection().await?;
+        connection
+            .set_ex(key, value, seconds)
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn delete_prefix(&self, prefix: &str) -> ErpResult<u64> {
+        let mut connection = self.connection().await?;
+        let pattern = format!("{}*", prefix);
+        let mut cursor: u64 = 0;
+        let mut removed = 0;
+
+        loop {
+            let (next, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(SCAN_BATCH)
+                .query_async(&mut connection)
+                .await
+                .map_err(redis_error)?;
+
+            if !keys.is_empty() {
+                let deleted: u64 = connection.del(&keys).await.map_err(redis_error)?;
+                removed += deleted;
+            }
+
+            if next == 0 {
+                return Ok(removed);
+            }
+            cursor = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{TcpListener, TcpStream};
+
+    type Store = Arc<Mutex<HashMap<String, String>>>;
+
+    /// Minimal RESP server understanding the commands the backend sends
+    async fn start_stand_in() -> (String, Store) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("redis://{}", listener.local_addr().unwrap());
+        let store = Store::default();
+
+        let shared = store.clone();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(serve(socket, shared.clone()));
+            }
+        });
+        (url, store)
+    }
+
+    async fn read_command(reader: &mut BufReader<TcpStream>) -> Option<Vec<String>> {
+        let mut line = String::new();
+        reader.read_line(&mut line).await.ok()?;
+        let count: usize = line.trim().strip_prefix('*')?.parse().ok()?;
+
+        let mut args = Vec::with_capacity(count);
+        for _ in 0..count {
+            line.clear();
+            reader.read_line(&mut line).await.ok()?;
+            let len: usize = line.trim().strip_prefix('$')?.parse().ok()?;
+            let mut data = vec![0; len + 2];
+            reader.read_exact(&mut data).await.ok()?;
+            data.truncate(len);
+            args.push(String::from_utf8(data).ok()?);
+        }
+        Some(args)
+    }
+
+    fn bulk(value: &str) -> String {
+        format!("${}\r\n{}\r\n", value.len(), value)
+    }
+
+    async fn serve(socket: TcpStream, store: Store) {
+        let mut reader = BufReader::new(socket);
+        while let Some(args) = read_command(&mut reader).await {
+            let reply = {
+                let mut store = store.lock().unwrap();
+                match args[0].to_uppercase().as_str() {
+                    "GET" => store
+                        .get(&args[1])
+                        .map_or("$-1\r\n".to_string(), |v| bulk(v)),
+                    "SETEX" => {
+                        store.insert(args[1].clone(), args[3].clone());
+                        "+OK\r\n".to_string()
+                    }
+                    "DEL" => {
+                        let removed = args[1..].iter().filter(|k| store.remove(*k).is_some());
+                        format!(":{}\r\n", removed.count())
+                    }
+                    "SCAN" => {
+                        let prefix = args[3].trim_end_matches('*');
+                        let keys: Vec<String> = store
+                            .keys()
+                            .filter(|k| k.starts_with(prefix))
+                            .map(|k| bulk(k))
+                            .collect();
+                        format!("*2\r\n{}*{}\r\n{}", bulk("0"), keys.len(), keys.concat())
+                    }
+                    _ => "+OK\r\n".to_string(),
+                }
+            };
+            if reader.get_mut().write_all(reply.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redis_cache_round_trip_and_prefix_delete() {
+        let (url, store) = start_stand_in().await;
+        let cache = RedisCache::new(&url).unwrap();
+        let ttl = Duration::from_secs(60);
+
+        cache.set("erp:config:a", "1".into(), ttl).await.unwrap();
+        cache.set("erp:config:b", "2".into(), ttl).await.unwrap();
+        cache.set("erp:reports:c", "3".into(), ttl).await.unwrap();
+        assert_eq!(
+            cache.get("erp:config:a").await.unwrap().as_deref(),
+            Some("1")
+        );
+        assert_eq!(cache.get("erp:config:z").await.unwrap(), None);
+
+        assert_eq!(cache.delete_prefix("erp:config:").await.unwrap(), 2);
+        assert_eq!(store.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_redis_is_skipped_until_retry() {
+        // Bind and drop a listener to get a port nobody listens on
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("redis://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let cache = RedisCache::new(&url).unwrap();
+        assert!(cache.get("erp:config:a").await.is_err());
+        assert!(cache.retry_after.lock().unwrap().is_some());
+        assert!(cache.get("erp:config:a").await.is_err());
+
+        assert!(RedisCache::new("not a url").is_err());
+    }
+}
@@ -1,11 +1,11 @@
-use crate::core::config::AppConfig;
+use crate::core::config::{AppConfig, ENV_PREFIX, ENV_SEPARATOR};
 use crate::utils::error::{ErpError, ErpResult};
 use config::{Config, Environment, File};
 use std::env;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use tokio::sync::RwLock;
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
 
 static CONFIG_INSTANCE: OnceLock<RwLock<Option<AppConfig>>> = OnceLock::new();
 
@@ -55,7 +55,11 @@ impl ConfigLoader {
         config_builder = self.apply_environment_overrides(config_builder)?;
 
         let config = config_builder
-            .add_source(Environment::with_prefix("ERP").separator("_"))
+            .add_source(
+                Environment::with_prefix(ENV_PREFIX)
+                    .prefix_separator("_")
+                    .separator(ENV_SEPARATOR),
+            )
             .build()
             .map_err(|e| ErpError::config(format!("Failed to build configuration: {}", e)))?;
 
@@ -182,35 +186,7 @@ impl ConfigLoader {
     }
 
     fn validate_config(&self, config: &AppConfig) -> ErpResult<()> {
-        if config.database.url.is_empty() {
-            return Err(ErpError::config("Database URL cannot be empty"));
-        }
-
-        if config.auth.jwt_secret.is_empty() {
-            return Err(ErpError::config("JWT secret cannot be empty"));
-        }
-
-        if config.auth.jwt_secret.len() < 32 {
-            warn!("JWT secret is less than 32 characters, consider using a longer secret");
-        }
-
-        if config.database.max_connections == 0 {
-            return Err(ErpError::config(
-                "Database max connections must be greater than 0",
-            ));
-        }
-
-        if config.auth.token_expiry_hours == 0 {
-            return Err(ErpError::config(
-                "Token expiry hours must be greater than 0",
-            ));
-        }
-
-        if config.auth.password_min_length < 8 {
-            warn!("Password minimum length is less than 8, consider increasing for security");
-        }
-
-        Ok(())
+        config.validate()
     }
 }
 
@@ -312,7 +288,10 @@ mod tests {
     }
 
     fn create_test_config() -> AppConfig {
-        use crate::core::config::{AuthConfig, DatabaseConfig, LoggingConfig};
+        use crate::core::config::{
+            AuthConfig, BackupConfig, CacheConfig, DatabaseConfig, LoggingConfig, MonitoringConfig,
+            PerformanceConfig, ReportsConfig, SecurityConfig,
+        };
 
         AppConfig {
             database: DatabaseConfig {
@@ -321,6 +300,8 @@ mod tests {
                 migrate_on_start: true,
                 query_timeout_seconds: 30,
                 idle_timeout_seconds: 600,
+                replica_urls: Vec::new(),
+                enable_query_logging: false,
             },
             logging: LoggingConfig {
                 level: "info".to_string(),
@@ -329,6 +310,7 @@ mod tests {
                 rotate_daily: true,
                 max_file_size: "10MB".to_string(),
                 max_files: 7,
+                console_output: true,
             },
             auth: AuthConfig {
                 jwt_secret: "test_secret_key_that_is_long_enough".to_string(),
@@ -336,7 +318,16 @@ mod tests {
                 password_min_length: 8,
                 max_login_attempts: 5,
                 lockout_duration_minutes: 15,
+                require_strong_password: false,
+                enable_2fa: false,
             },
+            reports: ReportsConfig::default(),
+            security: SecurityConfig::default(),
+            backup: BackupConfig::default(),
+            monitoring: MonitoringConfig::default(),
+            cache: CacheConfig::default(),
+            performance: PerformanceConfig::default(),
+            ..AppConfig::default()
         }
     }
 }
@@ -1,13 +1,17 @@
 // 설정 관리 모듈
 
 pub mod loader;
+pub mod validation;
 
 use crate::utils::error::ErpResult;
 use config::{Config, Environment, File};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::path::{Path, PathBuf};
 
 pub use loader::{get_config, is_config_loaded, reload_config, ConfigLoader};
+pub use validation::{ConfigIssue, IssueSeverity};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DatabaseConfig {
@@ -16,6 +20,11 @@ pub struct DatabaseConfig {
     pub migrate_on_start: bool,
     pub query_timeout_seconds: u64,
     pub idle_timeout_seconds: u64,
+    /// 조회 전용 쿼리(보고서, 목록/검색)를 보낼 읽기 복제본 URL
+    #[serde(default)]
+    pub replica_urls: Vec<String>,
+    #[serde(default)]
+    pub enable_query_logging: bool,
 }
 
 impl Default for DatabaseConfig {
@@ -26,6 +35,8 @@ impl Default for DatabaseConfig {
             migrate_on_start: false,
             query_timeout_seconds: 30,
             idle_timeout_seconds: 300,
+            replica_urls: Vec::new(),
+            enable_query_logging: false,
         }
     }
 }
@@ -38,6 +49,8 @@ pub struct LoggingConfig {
     pub rotate_daily: bool,
     pub max_file_size: String,
     pub max_files: u32,
+    #[serde(default = "default_true")]
+    pub console_output: bool,
 }
 
 impl Default for LoggingConfig {
@@ -49,6 +62,7 @@ impl Default for LoggingConfig {
             rotate_daily: true,
             max_file_size: "10MB".to_string(),
             max_files: 7,
+            console_output: true,
         }
     }
 }
@@ -60,6 +74,10 @@ pub struct AuthConfig {
     pub password_min_length: u32,
     pub max_login_attempts: u32,
     pub lockout_duration_minutes: u32,
+    #[serde(default)]
+    pub require_strong_password: bool,
+    #[serde(default)]
+    pub enable_2fa: bool,
 }
 
 impl Default for AuthConfig {
@@ -70,21 +88,415 @@ impl Default for AuthConfig {
             password_min_length: 8,
             max_login_attempts: 5,
             lockout_duration_minutes: 15,
+            require_strong_password: false,
+            enable_2fa: false,
         }
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ReportsConfig {
+    pub output_dir: String,
+    pub default_format: String,
+    pub max_records_per_report: u32,
+    pub export_formats: Vec<String>,
+    pub compress_reports: bool,
+}
+
+impl Default for ReportsConfig {
+    fn default() -> Self {
+        Self {
+            output_dir: "reports".to_string(),
+            default_format: "json".to_string(),
+            max_records_per_report: 10000,
+            export_formats: vec!["json".to_string(), "csv".to_string(), "xlsx".to_string()],
+            compress_reports: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SecurityConfig {
+    pub enable_audit_log: bool,
+    pub audit_retention_days: i64,
+    pub encrypt_sensitive_data: bool,
+    pub require_password_change_days: u32,
+    /// N개의 감사 이벤트마다 서명된 체크포인트 기록 (0이면 비활성화)
+    pub audit_checkpoint_interval: u64,
+    /// 체크포인트 서명 키 (미설정 시 auth.jwt_secret 사용)
+    pub audit_signing_key: Option<String>,
+    /// 고객 PII 암호화 키 (base64, 32바이트)
+    pub pii_encryption_key: Option<String>,
+    /// 키 교체 중에만 설정하는 이전 PII 암호화 키
+    pub pii_previous_key: Option<String>,
+    /// 사용자별 시간당 대량 내보내기 허용 횟수 (0이면 제한 없음)
+    pub export_limit_per_hour: u32,
+    /// 시간당 비밀번호 재설정 시도 허용 횟수 (전체 사용자 합계)
+    pub password_reset_limit_per_hour: u32,
+    pub enable_ip_whitelist: bool,
+    pub enable_session_monitoring: bool,
+    /// 사용자별 동시 세션 수 (0이면 제한 없음)
+    pub max_concurrent_sessions: u32,
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            enable_audit_log: true,
+            audit_retention_days: 365,
+            encrypt_sensitive_data: true,
+            require_password_change_days: 90,
+            audit_checkpoint_interval: 1000,
+            audit_signing_key: None,
+            pii_encryption_key: None,
+            pii_previous_key: None,
+            export_limit_per_hour: 20,
+            password_reset_limit_per_hour: 5,
+            enable_ip_whitelist: false,
+            enable_session_monitoring: false,
+            max_concurrent_sessions: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    pub backup_dir: String,
+    pub retention_days: i64,
+    pub compression_enabled: bool,
+    pub encryption_enabled: bool,
+    /// 백업 암호화 비밀번호 (BACKUP_PASSWORD 환경변수 권장)
+    pub encryption_password: Option<String>,
+    /// 데이터베이스 외에 함께 보관할 파일/디렉터리
+    pub include_paths: Vec<String>,
+    /// 원격 백업 저장소
+    pub remote: BackupRemoteConfig,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BackupRemoteConfig {
+    /// 백업 생성 시 원격 저장소에 자동 업로드
+    pub enabled: bool,
+    /// local (디렉터리) 또는 s3 (S3 호환 서비스)
+    pub provider: String,
+    pub bucket: String,
+    pub region: Option<String>,
+    /// local: 버킷 상위 디렉터리, s3: 서비스 주소 (예: http://localhost:9000)
+    pub endpoint_url: Option<String>,
+    /// BACKUP_REMOTE_ACCESS_KEY 환경변수 권장
+    pub access_key: Option<String>,
+    /// BACKUP_REMOTE_SECRET_KEY 환경변수 권장
+    pub secret_key: Option<String>,
+}
+
+impl Default for BackupRemoteConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: "local".to_string(),
+            bucket: "erp-backups".to_string(),
+            region: None,
+            endpoint_url: None,
+            access_key: None,
+            secret_key: None,
+        }
+    }
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            backup_dir: "backups".to_string(),
+            retention_days: 30,
+            compression_enabled: true,
+            encryption_enabled: false,
+            encryption_password: None,
+            include_paths: Vec::new(),
+            remote: BackupRemoteConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MonitoringConfig {
+    /// 마이그레이션 상태 검사에 사용할 디렉터리
+    pub migrations_dir: String,
+    /// 최신 백업이 이 시간보다 오래되면 경고, 2배를 넘으면 위험
+    pub backup_max_age_hours: i64,
+    pub disk_warning_percent: f32,
+    pub disk_critical_percent: f32,
+    /// 데이터베이스 응답이 이보다 느리면 경고 (ms)
+    pub database_slow_ms: u64,
+    /// 저장된 메트릭/검사 결과 보존 시간
+    pub retention_hours: i64,
+    pub enable_metrics: bool,
+    pub metrics_port: u16,
+    pub health_check_port: u16,
+    pub enable_tracing: bool,
+    /// 추적할 요청 비율 (0.0 ~ 1.0)
+    pub sample_rate: f64,
+}
+
+impl Default for MonitoringConfig {
+    fn default() -> Self {
+        Self {
+            migrations_dir: "migrations".to_string(),
+            backup_max_age_hours: 26,
+            disk_warning_percent: 80.0,
+            disk_critical_percent: 90.0,
+            database_slow_ms: 500,
+            retention_hours: 168,
+            enable_metrics: false,
+            metrics_port: 9090,
+            health_check_port: 8081,
+            enable_tracing: false,
+            sample_rate: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// false면 조회 결과를 캐시하지 않음
+    pub enable_cache: bool,
+    /// 비어 있으면 프로세스 내 LRU 캐시 사용 (REDIS_URL 환경변수로 지정 가능)
+    pub redis_url: String,
+    /// 캐시 항목 유효 시간 (0이면 캐시 비활성화)
+    pub default_ttl_seconds: u64,
+    pub max_connections: u32,
+    pub cluster_mode: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enable_cache: true,
+            redis_url: String::new(),
+            default_ttl_seconds: 3600,
+            max_connections: 10,
+            cluster_mode: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PerformanceConfig {
+    pub connection_pool_size: u32,
+    /// 프로세스 내 LRU 캐시의 최대 항목 수
+    pub query_cache_size: usize,
+    pub batch_size: usize,
+    pub timeout_seconds: u64,
+    pub enable_connection_pooling: bool,
+    pub enable_prepared_statements: bool,
+    pub enable_compression: bool,
+}
+
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        Self {
+            connection_pool_size: 10,
+            query_cache_size: 1000,
+            batch_size: 100,
+            timeout_seconds: 30,
+            enable_connection_pooling: true,
+            enable_prepared_statements: true,
+            enable_compression: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    pub host: String,
+    /// PORT 환경변수로 변경 가능
+    pub port: u16,
+    pub cors_enabled: bool,
+    pub request_timeout_seconds: u64,
+    /// 요청 본문 최대 크기 (예: 10MB)
+    pub max_request_size: String,
+    pub security_headers: bool,
+    pub rate_limiting: bool,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+            cors_enabled: true,
+            request_timeout_seconds: 30,
+            max_request_size: "10MB".to_string(),
+            security_headers: false,
+            rate_limiting: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct InventoryConfig {
+    /// 최소 재고량을 지정하지 않은 신규 제품에 적용
+    pub low_stock_threshold: u32,
+    pub auto_reorder_enabled: bool,
+    /// ISO 4217 통화 코드
+    pub default_currency: String,
+    pub safety_stock_multiplier: f64,
+}
+
+impl Default for InventoryConfig {
+    fn default() -> Self {
+        Self {
+            low_stock_threshold: 10,
+            auto_reorder_enabled: false,
+            default_currency: "KRW".to_string(),
+            safety_stock_multiplier: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SalesConfig {
+    /// 송장 번호 접두사 (송장 번호: {invoice_prefix}-{주문 번호})
+    pub invoice_prefix: String,
+    /// 주문 번호 접두사 (주문 번호: {order_prefix}-000001)
+    pub order_prefix: String,
+    /// 설정 저장소에 tax_rate가 없을 때 적용할 세율 (0.1 = 10%)
+    pub default_tax_rate: f64,
+    /// 송장 발행일부터 결제 기한까지의 일수
+    pub payment_terms_days: u32,
+    pub accounting_integration: bool,
+}
+
+impl Default for SalesConfig {
+    fn default() -> Self {
+        Self {
+            invoice_prefix: "INV".to_string(),
+            order_prefix: "ORD".to_string(),
+            default_tax_rate: 0.1,
+            payment_terms_days: 30,
+            accounting_integration: false,
+        }
+    }
+}
+
+impl SalesConfig {
+    /// 설정 저장소의 tax_rate와 같은 백분율 단위 기본 세율
+    pub fn default_tax_rate_percent(&self) -> Decimal {
+        Decimal::from_f64_retain(self.default_tax_rate * 100.0)
+            .unwrap_or_else(|| Decimal::from(10))
+            .round_dp(4)
+            .normalize()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    pub enable_notifications: bool,
+    pub email_notifications: bool,
+    /// SLACK_WEBHOOK_URL 환경변수 권장
+    pub slack_webhook: Option<String>,
+    pub critical_error_threshold: u32,
+    pub performance_degradation_threshold: f64,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            enable_notifications: false,
+            email_notifications: false,
+            slack_webhook: None,
+            critical_error_threshold: 10,
+            performance_degradation_threshold: 2.0,
+        }
+    }
+}
+
+/// 개발 환경 전용 설정
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct DevConfig {
+    pub auto_reload: bool,
+    pub debug_queries: bool,
+    pub mock_external_services: bool,
+    pub seed_test_data: bool,
+    pub enable_dev_endpoints: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct AppConfig {
     pub database: DatabaseConfig,
     pub logging: LoggingConfig,
     pub auth: AuthConfig,
+    #[serde(default)]
+    pub reports: ReportsConfig,
+    #[serde(default)]
+    pub security: SecurityConfig,
+    #[serde(default)]
+    pub backup: BackupConfig,
+    #[serde(default)]
+    pub monitoring: MonitoringConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub performance: PerformanceConfig,
+    #[serde(default)]
+    pub api: ApiConfig,
+    #[serde(default)]
+    pub inventory: InventoryConfig,
+    #[serde(default)]
+    pub sales: SalesConfig,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    #[serde(default)]
+    pub dev: DevConfig,
 }
 
+/// `ERP_SALES__ORDER_PREFIX` 형태의 환경변수가 `sales.order_prefix`를 덮어씀
+pub const ENV_PREFIX: &str = "ERP";
+pub const ENV_SEPARATOR: &str = "__";
+/// 설정 키가 아닌 ERP_ 환경변수
+pub const ENV_IGNORED: &[&str] = &["ERP_ENV", "ERP_CONFIG_DIR", "ERP_USER"];
+
+/// 설정 키를 직접 덮어쓰는 환경변수
+pub const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("DATABASE_URL", "database.url"),
+    ("JWT_SECRET", "auth.jwt_secret"),
+    ("AUDIT_SIGNING_KEY", "security.audit_signing_key"),
+    ("PII_ENCRYPTION_KEY", "security.pii_encryption_key"),
+    ("PII_PREVIOUS_KEY", "security.pii_previous_key"),
+    ("REDIS_URL", "cache.redis_url"),
+    ("PORT", "api.port"),
+    ("BACKUP_PASSWORD", "backup.encryption_password"),
+    ("BACKUP_REMOTE_ACCESS_KEY", "backup.remote.access_key"),
+    ("BACKUP_REMOTE_SECRET_KEY", "backup.remote.secret_key"),
+    ("SLACK_WEBHOOK_URL", "alerts.slack_webhook"),
+];
+
 impl AppConfig {
-    /// 설정 로드
+    /// 설정 로드 (잘못된 값이 있으면 모든 오류를 담은 설정 오류 반환)
     pub async fn load() -> ErpResult<Self> {
-        let environment = env::var("ERP_ENV").unwrap_or_else(|_| "development".to_string());
+        let app_config = Self::load_unchecked()?;
+        app_config.validate()?;
+        Ok(app_config)
+    }
+
+    /// 값 검증 없이 설정 로드 (`erp config validate`에서 사용)
+    pub fn load_unchecked() -> ErpResult<Self> {
+        let environment = Self::environment();
 
         let mut builder = Config::builder()
             // 기본 설정 로드
@@ -92,18 +504,35 @@ impl AppConfig {
             // 환경별 설정 로드
             .add_source(File::with_name(&format!("config/{}", environment)).required(false))
             // 환경변수로 오버라이드
-            .add_source(Environment::with_prefix("ERP"));
+            .add_source(
+                Environment::with_prefix(ENV_PREFIX)
+                    .prefix_separator("_")
+                    .separator(ENV_SEPARATOR),
+            );
 
         // 환경변수에서 DATABASE_URL, JWT_SECRET 등을 직접 처리
-        if let Ok(database_url) = env::var("DATABASE_URL") {
-            builder = builder.set_override("database.url", database_url)?;
-        }
-        if let Ok(jwt_secret) = env::var("JWT_SECRET") {
-            builder = builder.set_override("auth.jwt_secret", jwt_secret)?;
+        for (variable, key) in ENV_OVERRIDES {
+            if let Ok(value) = env::var(variable) {
+                builder = builder.set_override(*key, value)?;
+            }
         }
 
         let config = builder.build()?;
         let app_config: AppConfig = config.try_deserialize()?;
         Ok(app_config)
     }
+
+    /// ERP_ENV (기본값: development)
+    pub fn environment() -> String {
+        env::var("ERP_ENV").unwrap_or_else(|_| "development".to_string())
+    }
+
+    /// load()가 읽는 설정 파일 중 존재하는 파일
+    pub fn config_files() -> Vec<PathBuf> {
+        ["default".to_string(), Self::environment()]
+            .iter()
+            .map(|name| Path::new("config").join(format!("{}.toml", name)))
+            .filter(|path| path.exists())
+            .collect()
+    }
 }
@@ -0,0 +1,545 @@
+//! Configuration checks.
+//!
+//! [`AppConfig::validate`] runs at startup and reports every invalid value in
+//! one error. `erp config validate` additionally looks for keys that no
+//! section reads, in the config files and in `ERP_*` environment variables.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+
+use tracing::warn;
+
+use super::{AppConfig, ENV_IGNORED, ENV_PREFIX, ENV_SEPARATOR};
+use crate::utils::error::{ErpError, ErpResult};
+
+const LOG_LEVELS: &[&str] = &["trace", "debug", "info", "warn", "error"];
+const LOG_FORMATS: &[&str] = &["json", "pretty", "full", "compact"];
+const REPORT_FORMATS: &[&str] = &["json", "csv", "xlsx", "pdf"];
+const BACKUP_PROVIDERS: &[&str] = &["local", "s3"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+/// One problem found in a configuration source
+#[derive(Debug, Clone)]
+pub struct ConfigIssue {
+    pub severity: IssueSeverity,
+    /// Where the value came from (file path, environment variable, ...)
+    pub source: String,
+    /// Dotted key, e.g. `sales.default_tax_rate`
+    pub key: String,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    pub fn error(
+        source: impl Into<String>,
+        key: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity: IssueSeverity::Error,
+            source: source.into(),
+            key: key.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(
+        source: impl Into<String>,
+        key: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity: IssueSeverity::Warning,
+            ..Self::error(source, key, message)
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == IssueSeverity::Error
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// Collects value issues for the merged configuration
+struct Checker {
+    issues: Vec<ConfigIssue>,
+}
+
+impl Checker {
+    const SOURCE: &'static str = "merged";
+
+    fn error(&mut self, key: &str, message: impl Into<String>) {
+        self.issues
+            .push(ConfigIssue::error(Self::SOURCE, key, message));
+    }
+
+    fn warning(&mut self, key: &str, message: impl Into<String>) {
+        self.issues
+            .push(ConfigIssue::warning(Self::SOURCE, key, message));
+    }
+
+    fn require(&mut self, ok: bool, key: &str, message: &str) {
+        if !ok {
+            self.error(key, message);
+        }
+    }
+
+    fn one_of(&mut self, key: &str, value: &str, allowed: &[&str]) {
+        if !allowed.contains(&value.to_lowercase().as_str()) {
+            self.error(
+                key,
+                format!("'{}' is not one of: {}", value, allowed.join(", ")),
+            );
+        }
+    }
+
+    fn size(&mut self, key: &str, value: &str) {
+        if parse_size(value).is_none() {
+            self.error(
+                key,
+                format!("'{}' is not a size such as 512KB, 10MB or 1GB", value),
+            );
+        }
+    }
+
+    fn prefix(&mut self, key: &str, value: &str) {
+        let valid = !value.is_empty()
+            && value.len() <= 10
+            && value
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-');
+        if !valid {
+            self.error(
+                key,
+                format!("'{}' must be 1-10 characters of A-Z, 0-9 or '-'", value),
+            );
+        }
+    }
+}
+
+impl AppConfig {
+    /// Value checks on the merged configuration (errors and warnings)
+    pub fn check(&self) -> Vec<ConfigIssue> {
+        let mut c = Checker { issues: Vec::new() };
+
+        unresolved_placeholders(&mut c, self);
+
+        let database = &self.database;
+        c.require(
+            !database.url.trim().is_empty(),
+            "database.url",
+            "Database URL cannot be empty",
+        );
+        c.require(
+            database.max_connections > 0,
+            "database.max_connections",
+            "Database max connections must be greater than 0",
+        );
+        c.require(
+            database.query_timeout_seconds > 0,
+            "database.query_timeout_seconds",
+            "must be greater than 0",
+        );
+        if database
+            .replica_urls
+            .iter()
+            .any(|url| url.trim().is_empty())
+        {
+            c.error("database.replica_urls", "replica URLs cannot be empty");
+        }
+
+        c.one_of("logging.level", &self.logging.level, LOG_LEVELS);
+        c.one_of("logging.format", &self.logging.format, LOG_FORMATS);
+        c.size("logging.max_file_size", &self.logging.max_file_size);
+
+        let auth = &self.auth;
+        if auth.jwt_secret.is_empty() {
+            c.error("auth.jwt_secret", "JWT secret cannot be empty");
+        } else if auth.jwt_secret.len() < 32 {
+            c.warning(
+                "auth.jwt_secret",
+                "JWT secret is less than 32 characters, consider using a longer secret",
+            );
+        }
+        c.require(
+            auth.token_expiry_hours > 0,
+            "auth.token_expiry_hours",
+            "Token expiry hours must be greater than 0",
+        );
+        if auth.password_min_length < 8 {
+            c.warning(
+                "auth.password_min_length",
+                "Password minimum length is less than 8, consider increasing for security",
+            );
+        }
+
+        let redis_url = self.cache.redis_url.trim();
+        if !redis_url.is_empty()
+            && !redis_url.starts_with("redis://")
+            && !redis_url.starts_with("rediss://")
+        {
+            c.error(
+                "cache.redis_url",
+                "must start with redis:// or rediss:// (leave empty for the in-process cache)",
+            );
+        }
+
+        c.require(self.api.port > 0, "api.port", "must be between 1 and 65535");
+        c.size("api.max_request_size", &self.api.max_request_size);
+
+        let reports = &self.reports;
+        for format in &reports.export_formats {
+            c.one_of("reports.export_formats", format, REPORT_FORMATS);
+        }
+        if !reports
+            .export_formats
+            .iter()
+            .any(|format| format.eq_ignore_ascii_case(&reports.default_format))
+        {
+            c.error(
+                "reports.default_format",
+                format!(
+                    "'{}' is not listed in reports.export_formats",
+                    reports.default_format
+                ),
+            );
+        }
+        c.require(
+            reports.max_records_per_report > 0,
+            "reports.max_records_per_report",
+            "must be greater than 0",
+        );
+
+        let inventory = &self.inventory;
+        let currency = &inventory.default_currency;
+        if currency.len() != 3 || !currency.chars().all(|ch| ch.is_ascii_uppercase()) {
+            c.error(
+                "inventory.default_currency",
+                format!("'{}' is not an ISO 4217 code such as KRW or USD", currency),
+            );
+        }
+        c.require(
+            inventory.safety_stock_multiplier >= 1.0,
+            "inventory.safety_stock_multiplier",
+            "must be at least 1.0",
+        );
+
+        let sales = &self.sales;
+        if !(0.0..=1.0).contains(&sales.default_tax_rate) {
+            c.error(
+                "sales.default_tax_rate",
+                format!(
+                    "{} is outside 0.0-1.0 (a fraction, e.g. 0.1 for 10%)",
+                    sales.default_tax_rate
+                ),
+            );
+        }
+        c.prefix("sales.order_prefix", &sales.order_prefix);
+        c.prefix("sales.invoice_prefix", &sales.invoice_prefix);
+
+        c.require(
+            self.security.audit_retention_days > 0,
+            "security.audit_retention_days",
+            "must be greater than 0",
+        );
+
+        c.require(
+            self.backup.retention_days > 0,
+            "backup.retention_days",
+            "must be greater than 0",
+        );
+        c.one_of(
+            "backup.remote.provider",
+            &self.backup.remote.provider,
+            BACKUP_PROVIDERS,
+        );
+
+        let monitoring = &self.monitoring;
+        if !(0.0 < monitoring.disk_warning_percent
+            && monitoring.disk_warning_percent < monitoring.disk_critical_percent
+            && monitoring.disk_critical_percent <= 100.0)
+        {
+            c.error(
+                "monitoring.disk_warning_percent",
+                "must satisfy 0 < disk_warning_percent < disk_critical_percent <= 100",
+            );
+        }
+        c.require(
+            (0.0..=1.0).contains(&monitoring.sample_rate),
+            "monitoring.sample_rate",
+            "must be between 0.0 and 1.0",
+        );
+
+        c.require(
+            self.performance.connection_pool_size > 0,
+            "performance.connection_pool_size",
+            "must be greater than 0",
+        );
+        c.require(
+            self.performance.batch_size > 0,
+            "performance.batch_size",
+            "must be greater than 0",
+        );
+
+        if let Some(webhook) = self.alerts.slack_webhook.as_deref() {
+            c.require(
+                webhook.is_empty() || webhook.starts_with("https://"),
+                "alerts.slack_webhook",
+                "must be an https:// URL",
+            );
+        }
+
+        c.issues
+    }
+
+    /// Fails with every invalid value listed; warnings are only logged
+    pub fn validate(&self) -> ErpResult<()> {
+        let issues = self.check();
+        for issue in issues.iter().filter(|issue| !issue.is_error()) {
+            warn!("Configuration {}", issue);
+        }
+
+        let errors: Vec<String> = issues
+            .iter()
+            .filter(|issue| issue.is_error())
+            .map(|issue| format!("  - {}", issue))
+            .collect();
+        if errors.is_empty() {
+            return Ok(());
+        }
+        Err(ErpError::config(format!(
+            "Invalid configuration ({} error(s)):\n{}",
+            errors.len(),
+            errors.join("\n")
+        )))
+    }
+}
+
+/// `${VAR}` values are not expanded; the variable has to override the key
+fn unresolved_placeholders(c: &mut Checker, config: &AppConfig) {
+    let Ok(value) = serde_json::to_value(config) else {
+        return;
+    };
+    let mut leaves = Vec::new();
+    flatten_json(&value, String::new(), &mut leaves);
+    for (key, value) in leaves {
+        if let Some(text) = value.as_str().filter(|text| text.contains("${")) {
+            c.error(
+                &key,
+                format!(
+                    "'{}' is an unresolved placeholder; set the environment variable instead",
+                    text
+                ),
+            );
+        }
+    }
+}
+
+fn flatten_json(
+    value: &serde_json::Value,
+    path: String,
+    out: &mut Vec<(String, serde_json::Value)>,
+) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                flatten_json(child, join_key(&path, key), out);
+            }
+        }
+        other => out.push((path, other.clone())),
+    }
+}
+
+fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// Every dotted key some section of [`AppConfig`] reads
+pub fn known_keys() -> BTreeSet<String> {
+    let value = serde_json::to_value(AppConfig::default()).unwrap_or_default();
+    let mut leaves = Vec::new();
+    flatten_json(&value, String::new(), &mut leaves);
+    leaves.into_iter().map(|(key, _)| key).collect()
+}
+
+fn is_known(known: &BTreeSet<String>, key: &str) -> bool {
+    // A table where a value is expected is a type error, reported when loading
+    known.contains(key)
+        || known
+            .range(format!("{}.", key)..)
+            .next()
+            .is_some_and(|k| k.starts_with(&format!("{}.", key)))
+}
+
+/// Keys in a TOML config file that no section reads
+pub fn check_file(path: &Path) -> Vec<ConfigIssue> {
+    let source = path.display().to_string();
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            return vec![ConfigIssue::error(
+                source,
+                "-",
+                format!("cannot read file: {}", e),
+            )]
+        }
+    };
+    let table: toml::Table = match content.parse() {
+        Ok(table) => table,
+        Err(e) => {
+            return vec![ConfigIssue::error(
+                source,
+                "-",
+                format!("invalid TOML: {}", e),
+            )]
+        }
+    };
+
+    let mut keys = Vec::new();
+    flatten_toml(&table, String::new(), &mut keys);
+
+    let known = known_keys();
+    keys.into_iter()
+        .filter(|key| !is_known(&known, key))
+        .map(|key| ConfigIssue::warning(&source, key, "unknown key, ignored"))
+        .collect()
+}
+
+fn flatten_toml(table: &toml::Table, path: String, out: &mut Vec<String>) {
+    for (key, value) in table {
+        let key = join_key(&path, key);
+        match value {
+            toml::Value::Table(child) => flatten_toml(child, key, out),
+            _ => out.push(key),
+        }
+    }
+}
+
+/// Config key an `ERP_SECTION__KEY` variable overrides
+pub fn env_key(name: &str) -> Option<String> {
+    let rest = name.strip_prefix(ENV_PREFIX)?.strip_prefix('_')?;
+    if ENV_IGNORED.contains(&name) || rest.is_empty() {
+        return None;
+    }
+    Some(rest.to_lowercase().replace(ENV_SEPARATOR, "."))
+}
+
+/// `ERP_*` variables that do not map to a known key
+pub fn check_env(vars: impl IntoIterator<Item = (String, String)>) -> Vec<ConfigIssue> {
+    let known = known_keys();
+    vars.into_iter()
+        .filter_map(|(name, _)| env_key(&name).map(|key| (name, key)))
+        .filter(|(_, key)| !known.contains(key))
+        .map(|(name, key)| {
+            ConfigIssue::warning(
+                format!("env {}", name),
+                key,
+                format!(
+                    "unknown key, ignored (use {}_SECTION{}KEY)",
+                    ENV_PREFIX, ENV_SEPARATOR
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Bytes in a size such as `10MB`
+fn parse_size(value: &str) -> Option<u64> {
+    let value = value.trim().to_uppercase();
+    let digits = value.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let multiplier = match &value[digits.len()..] {
+        "" | "B" => 1,
+        "KB" => 1024,
+        "MB" => 1024 * 1024,
+        "GB" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    digits.trim().parse::<u64>().ok().map(|n| n * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_keys(config: &AppConfig) -> Vec<String> {
+        config
+            .check()
+            .into_iter()
+            .filter(|issue| issue.is_error())
+            .map(|issue| issue.key)
+            .collect()
+    }
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert!(error_keys(&AppConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_validate_lists_every_invalid_value() {
+        let mut config = AppConfig::default();
+        config.sales.default_tax_rate = 10.0;
+        config.sales.order_prefix = "ord_".to_string();
+        config.logging.level = "verbose".to_string();
+        config.auth.jwt_secret = "${JWT_SECRET}".to_string();
+
+        assert_eq!(
+            error_keys(&config),
+            vec![
+                "auth.jwt_secret",
+                "logging.level",
+                "sales.default_tax_rate",
+                "sales.order_prefix"
+            ]
+        );
+
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("4 error(s)"));
+        assert!(message.contains("sales.default_tax_rate: 10 is outside 0.0-1.0"));
+    }
+
+    #[test]
+    fn test_unknown_file_and_env_keys_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("local.toml");
+        std::fs::write(
+            &path,
+            "[sales]\norder_prefix = \"ORD\"\ndefualt_tax_rate = 0.1\n\n[backup.remote]\nbucket = \"b\"\n\n[unknown]\nkey = 1\n",
+        )
+        .unwrap();
+
+        let keys: Vec<String> = check_file(&path).into_iter().map(|i| i.key).collect();
+        assert_eq!(keys, vec!["sales.defualt_tax_rate", "unknown.key"]);
+
+        let issues = check_env(vec![
+            ("ERP_SALES__ORDER_PREFIX".to_string(), "SO".to_string()),
+            ("ERP_SALES_ORDER_PREFIX".to_string(), "SO".to_string()),
+            ("ERP_ENV".to_string(), "production".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+        ]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].source, "env ERP_SALES_ORDER_PREFIX");
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("10MB"), Some(10 * 1024 * 1024));
+        assert_eq!(parse_size("512kb"), Some(512 * 1024));
+        assert_eq!(parse_size("ten MB"), None);
+    }
+}
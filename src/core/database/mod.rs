@@ -1,7 +1,15 @@
 pub mod connection;
 pub mod migration;
 pub mod models;
+pub mod replica;
+pub mod schema_check;
+pub mod schema_dump;
+pub mod sql_script;
+pub mod sqlite;
+pub mod unit_of_work;
 
 pub use connection::*;
 pub use migration::*;
 pub use models::*;
+pub use replica::{ReadRouter, ReplicaStatus};
+pub use unit_of_work::{MockUnitOfWork, UnitOfWork};
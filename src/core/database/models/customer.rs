@@ -161,6 +161,7 @@ pub struct CustomerFilter {
     pub has_outstanding_balance: Option<bool>,
     pub credit_limit_min: Option<Decimal>,
     pub credit_limit_max: Option<Decimal>,
+    pub customer_ids: Option<Vec<Uuid>>, // Restrict to these customers (e.g. an RFM segment)
 }
 
 impl Customer {
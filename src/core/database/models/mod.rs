@@ -1,6 +1,7 @@
 pub mod customer;
 pub mod order;
 pub mod product;
+pub mod schema;
 pub mod user;
 
 pub use customer::*;
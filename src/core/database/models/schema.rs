@@ -0,0 +1,553 @@
+//! Schema the Postgres repositories expect, checked by `erp migrate verify-schema`.
+//!
+//! Each table lists the columns its repository reads or writes, typed by the
+//! Rust field they decode into. Update this file together with the model or
+//! query that changes.
+
+use ColumnType::*;
+
+/// Rust-side type of a column, matched against the Postgres `udt_name`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Uuid,
+    Text,
+    Integer,
+    BigInt,
+    Double,
+    Numeric,
+    Boolean,
+    Timestamptz,
+    Jsonb,
+    /// A Postgres enum created with `CREATE TYPE ... AS ENUM`
+    Enum(&'static str),
+}
+
+impl ColumnType {
+    /// Whether a column of this Postgres type decodes into the expected Rust type
+    pub fn accepts(&self, udt_name: &str) -> bool {
+        match self {
+            Uuid => udt_name == "uuid",
+            Text => matches!(udt_name, "varchar" | "text" | "bpchar"),
+            Integer => udt_name == "int4",
+            BigInt => udt_name == "int8",
+            Double => udt_name == "float8",
+            Numeric => udt_name == "numeric",
+            Boolean => udt_name == "bool",
+            Timestamptz => udt_name == "timestamptz",
+            Jsonb => matches!(udt_name, "jsonb" | "json"),
+            Enum(name) => udt_name == *name,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Uuid => "uuid",
+            Text => "varchar/text",
+            Integer => "integer",
+            BigInt => "bigint",
+            Double => "double precision",
+            Numeric => "numeric",
+            Boolean => "boolean",
+            Timestamptz => "timestamptz",
+            Jsonb => "jsonb",
+            Enum(name) => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExpectedColumn {
+    pub name: &'static str,
+    pub column_type: ColumnType,
+}
+
+/// An index whose leading columns are `columns`; unique ones must match exactly
+#[derive(Debug, Clone, Copy)]
+pub struct ExpectedIndex {
+    pub columns: &'static [&'static str],
+    pub unique: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExpectedTable {
+    pub name: &'static str,
+    pub repository: &'static str,
+    pub columns: &'static [ExpectedColumn],
+    pub indexes: &'static [ExpectedIndex],
+}
+
+/// Labels are what sqlx sends for `rename_all = "lowercase"`
+#[derive(Debug, Clone, Copy)]
+pub struct ExpectedEnum {
+    pub name: &'static str,
+    pub values: &'static [&'static str],
+}
+
+const fn col(name: &'static str, column_type: ColumnType) -> ExpectedColumn {
+    ExpectedColumn { name, column_type }
+}
+
+const fn index(columns: &'static [&'static str]) -> ExpectedIndex {
+    ExpectedIndex {
+        columns,
+        unique: false,
+    }
+}
+
+const fn unique(columns: &'static [&'static str]) -> ExpectedIndex {
+    ExpectedIndex {
+        columns,
+        unique: true,
+    }
+}
+
+pub const EXPECTED_ENUMS: &[ExpectedEnum] = &[
+    ExpectedEnum {
+        name: "product_status",
+        values: &["active", "inactive", "discontinued", "outofstock"],
+    },
+    ExpectedEnum {
+        name: "stock_movement_type",
+        values: &["in", "out", "adjustment", "transfer", "damaged", "expired"],
+    },
+    ExpectedEnum {
+        name: "order_status",
+        values: &[
+            "draft",
+            "pending",
+            "confirmed",
+            "processing",
+            "shipped",
+            "delivered",
+            "cancelled",
+            "returned",
+        ],
+    },
+    ExpectedEnum {
+        name: "payment_status",
+        values: &[
+            "pending",
+            "paid",
+            "partiallypaid",
+            "overdue",
+            "failed",
+            "refunded",
+        ],
+    },
+    ExpectedEnum {
+        name: "payment_method",
+        values: &[
+            "cash",
+            "creditcard",
+            "debitcard",
+            "banktransfer",
+            "check",
+            "paypal",
+            "crypto",
+        ],
+    },
+];
+
+pub const EXPECTED_TABLES: &[ExpectedTable] = &[
+    ExpectedTable {
+        name: "products",
+        repository: "PostgresInventoryRepository",
+        columns: &[
+            col("id", Uuid),
+            col("sku", Text),
+            col("name", Text),
+            col("description", Text),
+            col("category", Text),
+            col("price", Numeric),
+            col("cost", Numeric),
+            col("quantity", Integer),
+            col("min_stock_level", Integer),
+            col("max_stock_level", Integer),
+            col("status", Enum("product_status")),
+            col("is_taxable", Boolean),
+            col("weight", Numeric),
+            col("dimensions", Text),
+            col("barcode", Text),
+            col("supplier_id", Uuid),
+            col("created_at", Timestamptz),
+            col("updated_at", Timestamptz),
+        ],
+        indexes: &[
+            unique(&["id"]),
+            unique(&["sku"]),
+            index(&["category"]),
+            index(&["status"]),
+        ],
+    },
+    ExpectedTable {
+        name: "stock_movements",
+        repository: "PostgresInventoryRepository",
+        columns: &[
+            col("id", Uuid),
+            col("product_id", Uuid),
+            col("movement_type", Enum("stock_movement_type")),
+            col("quantity", Integer),
+            col("reason", Text),
+            col("reference_id", Uuid),
+            col("user_id", Uuid),
+            col("created_at", Timestamptz),
+        ],
+        indexes: &[unique(&["id"]), index(&["product_id"])],
+    },
+    ExpectedTable {
+        name: "customers",
+        repository: "PostgresCustomerRepository",
+        columns: &[
+            col("id", Uuid),
+            col("name", Text),
+            col("email", Text),
+            col("phone", Text),
+            col("company", Text),
+            col("tax_id", Text),
+            col("customer_type", Text),
+            col("credit_limit", Numeric),
+            col("current_balance", Numeric),
+            col("notes", Text),
+            col("status", Text),
+            col("email_hash", Text),
+            col("phone_hash", Text),
+            col("created_at", Timestamptz),
+            col("updated_at", Timestamptz),
+        ],
+        indexes: &[
+            unique(&["id"]),
+            unique(&["email_hash"]),
+            index(&["phone_hash"]),
+        ],
+    },
+    ExpectedTable {
+        name: "customer_addresses",
+        repository: "PostgresCustomerRepository",
+        columns: &[
+            col("id", Uuid),
+            col("customer_id", Uuid),
+            col("address_type", Text),
+            col("address_line1", Text),
+            col("address_line2", Text),
+            col("city", Text),
+            col("state_province", Text),
+            col("postal_code", Text),
+            col("country", Text),
+            col("is_default", Boolean),
+            col("created_at", Timestamptz),
+        ],
+        indexes: &[unique(&["id"]), index(&["customer_id"])],
+    },
+    ExpectedTable {
+        name: "sales_orders",
+        repository: "PostgresSalesRepository",
+        columns: &[
+            col("id", Uuid),
+            col("order_number", Text),
+            col("customer_id", Uuid),
+            col("order_date", Timestamptz),
+            col("status", Enum("order_status")),
+            col("total_amount", Numeric),
+            col("tax_amount", Numeric),
+            col("discount_amount", Numeric),
+            col("shipping_address", Text),
+            col("billing_address", Text),
+            col("payment_method", Enum("payment_method")),
+            col("payment_status", Enum("payment_status")),
+            col("notes", Text),
+            col("created_at", Timestamptz),
+            col("updated_at", Timestamptz),
+        ],
+        indexes: &[
+            unique(&["id"]),
+            unique(&["order_number"]),
+            index(&["customer_id"]),
+            index(&["status"]),
+        ],
+    },
+    ExpectedTable {
+        name: "sales_order_items",
+        repository: "PostgresSalesRepository",
+        columns: &[
+            col("id", Uuid),
+            col("order_id", Uuid),
+            col("product_id", Uuid),
+            col("quantity", Integer),
+            col("unit_price", Numeric),
+            col("discount", Numeric),
+            col("line_total", Numeric),
+            col("created_at", Timestamptz),
+        ],
+        indexes: &[unique(&["id"]), index(&["order_id"])],
+    },
+    ExpectedTable {
+        name: "report_schedules",
+        repository: "PostgresReportScheduleRepository",
+        columns: &[
+            col("id", Uuid),
+            col("name", Text),
+            col("cron_expression", Text),
+            col("request", Jsonb),
+            col("enabled", Boolean),
+            col("last_run_at", Timestamptz),
+            col("next_run_at", Timestamptz),
+            col("created_at", Timestamptz),
+            col("updated_at", Timestamptz),
+        ],
+        indexes: &[unique(&["id"]), unique(&["name"]), index(&["next_run_at"])],
+    },
+    ExpectedTable {
+        name: "report_schedule_runs",
+        repository: "PostgresReportScheduleRepository",
+        columns: &[
+            col("id", Uuid),
+            col("schedule_id", Uuid),
+            col("schedule_name", Text),
+            col("started_at", Timestamptz),
+            col("finished_at", Timestamptz),
+            col("status", Text),
+            col("output_path", Text),
+            col("error_message", Text),
+        ],
+        indexes: &[unique(&["id"]), index(&["schedule_name", "started_at"])],
+    },
+    ExpectedTable {
+        name: "performance_metrics",
+        repository: "PostgresPerformanceRepository",
+        columns: &[
+            col("id", BigInt),
+            col("recorded_at", Timestamptz),
+            col("endpoint", Text),
+            col("method", Text),
+            col("response_time_ms", BigInt),
+            col("status_code", Integer),
+            col("request_size_bytes", BigInt),
+            col("response_size_bytes", BigInt),
+            col("user_id", Text),
+            col("session_id", Text),
+            col("error_message", Text),
+            col("query_count", BigInt),
+            col("query_time_ms", Double),
+            col("peak_memory_kb", BigInt),
+        ],
+        indexes: &[
+            unique(&["id"]),
+            index(&["endpoint"]),
+            index(&["recorded_at"]),
+        ],
+    },
+    ExpectedTable {
+        name: "performance_snapshots",
+        repository: "PostgresPerformanceRepository",
+        columns: &[
+            col("id", BigInt),
+            col("kind", Text),
+            col("endpoint", Text),
+            col("recorded_at", Timestamptz),
+            col("data", Jsonb),
+        ],
+        indexes: &[unique(&["id"]), index(&["kind", "recorded_at"])],
+    },
+    ExpectedTable {
+        name: "monitoring_metrics",
+        repository: "PostgresMetricsRepository",
+        columns: &[
+            col("id", BigInt),
+            col("kind", Text),
+            col("recorded_at", Timestamptz),
+            col("data", Jsonb),
+        ],
+        indexes: &[unique(&["id"]), index(&["kind", "recorded_at"])],
+    },
+    ExpectedTable {
+        name: "monitoring_alerts",
+        repository: "PostgresMetricsRepository",
+        columns: &[
+            col("id", Uuid),
+            col("raised_at", Timestamptz),
+            col("severity", Text),
+            col("category", Text),
+            col("title", Text),
+            col("description", Text),
+            col("metrics", Jsonb),
+            col("resolved", Boolean),
+            col("resolved_at", Timestamptz),
+        ],
+        indexes: &[unique(&["id"]), index(&["resolved"])],
+    },
+    ExpectedTable {
+        name: "health_check_results",
+        repository: "PostgresMetricsRepository",
+        columns: &[
+            col("id", BigInt),
+            col("service", Text),
+            col("status", Text),
+            col("checked_at", Timestamptz),
+            col("details", Jsonb),
+            col("response_time_ms", BigInt),
+        ],
+        indexes: &[unique(&["id"]), index(&["service"])],
+    },
+    ExpectedTable {
+        name: "audit_events",
+        repository: "PostgresAuditRepository",
+        columns: &[
+            col("id", Uuid),
+            col("occurred_at", Timestamptz),
+            col("user_id", Uuid),
+            col("username", Text),
+            col("action", Text),
+            col("resource", Text),
+            col("resource_id", Text),
+            col("severity", Text),
+            col("details", Jsonb),
+            col("ip_address", Text),
+            col("user_agent", Text),
+            col("success", Boolean),
+            col("error_message", Text),
+            col("sequence", BigInt),
+            col("previous_hash", Text),
+            col("content_hash", Text),
+        ],
+        indexes: &[
+            unique(&["id"]),
+            unique(&["sequence"]),
+            index(&["occurred_at"]),
+        ],
+    },
+    ExpectedTable {
+        name: "audit_checkpoints",
+        repository: "PostgresAuditRepository",
+        columns: &[
+            col("id", Uuid),
+            col("sequence", BigInt),
+            col("event_id", Uuid),
+            col("event_timestamp", Timestamptz),
+            col("content_hash", Text),
+            col("previous_signature", Text),
+            col("signature", Text),
+            col("created_at", Timestamptz),
+        ],
+        indexes: &[unique(&["id"]), unique(&["sequence"])],
+    },
+    ExpectedTable {
+        name: "rate_limit_events",
+        repository: "PostgresRateLimiter",
+        columns: &[
+            col("id", BigInt),
+            col("identifier", Text),
+            col("occurred_at", Timestamptz),
+        ],
+        indexes: &[unique(&["id"]), index(&["identifier", "occurred_at"])],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::database::models::{ProductStatus, StockMovementType};
+    use crate::modules::sales::models::{OrderStatus, PaymentMethod, PaymentStatus};
+
+    /// The label sqlx derives for a unit variant under `rename_all = "lowercase"`
+    fn sqlx_label<T: serde::Serialize>(variant: T) -> String {
+        serde_json::to_value(variant)
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_lowercase()
+    }
+
+    fn expected_values(name: &str) -> Vec<String> {
+        EXPECTED_ENUMS
+            .iter()
+            .find(|e| e.name == name)
+            .unwrap()
+            .values
+            .iter()
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_expected_enum_values_follow_the_models() {
+        assert_eq!(
+            ProductStatus::all()
+                .into_iter()
+                .map(sqlx_label)
+                .collect::<Vec<_>>(),
+            expected_values("product_status")
+        );
+        assert_eq!(
+            [
+                StockMovementType::In,
+                StockMovementType::Out,
+                StockMovementType::Adjustment,
+                StockMovementType::Transfer,
+                StockMovementType::Damaged,
+                StockMovementType::Expired,
+            ]
+            .map(sqlx_label)
+            .to_vec(),
+            expected_values("stock_movement_type")
+        );
+        assert_eq!(
+            [
+                OrderStatus::Draft,
+                OrderStatus::Pending,
+                OrderStatus::Confirmed,
+                OrderStatus::Processing,
+                OrderStatus::Shipped,
+                OrderStatus::Delivered,
+                OrderStatus::Cancelled,
+                OrderStatus::Returned,
+            ]
+            .map(sqlx_label)
+            .to_vec(),
+            expected_values("order_status")
+        );
+        assert_eq!(
+            [
+                PaymentStatus::Pending,
+                PaymentStatus::Paid,
+                PaymentStatus::PartiallyPaid,
+                PaymentStatus::Overdue,
+                PaymentStatus::Failed,
+                PaymentStatus::Refunded,
+            ]
+            .map(sqlx_label)
+            .to_vec(),
+            expected_values("payment_status")
+        );
+        assert_eq!(
+            [
+                PaymentMethod::Cash,
+                PaymentMethod::CreditCard,
+                PaymentMethod::DebitCard,
+                PaymentMethod::BankTransfer,
+                PaymentMethod::Check,
+                PaymentMethod::PayPal,
+                PaymentMethod::Crypto,
+            ]
+            .map(sqlx_label)
+            .to_vec(),
+            expected_values("payment_method")
+        );
+    }
+
+    #[test]
+    fn test_every_enum_column_has_an_expected_enum() {
+        for table in EXPECTED_TABLES {
+            for column in table.columns {
+                if let Enum(name) = column.column_type {
+                    assert!(
+                        EXPECTED_ENUMS.iter().any(|e| e.name == name),
+                        "{}.{} uses undeclared enum {}",
+                        table.name,
+                        column.name,
+                        name
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Routing of read-only queries to `database.replica_urls`.
+//!
+//! A [`ReadRouter`] hands out a healthy replica pool in round-robin order and
+//! falls back to the primary when no replica answers. Replica health is
+//! re-checked at most once per [`HEALTH_RECHECK_INTERVAL`], so a replica that
+//! went down is skipped until it responds again.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use sqlx::{Connection, Database, Pool};
+use tracing::{debug, info, warn};
+
+/// How long a replica health result is trusted before pinging again
+pub const HEALTH_RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ReplicaState {
+    healthy: bool,
+    checked_at: Option<Instant>,
+}
+
+struct Replica<DB: Database> {
+    label: String,
+    pool: Pool<DB>,
+    state: Mutex<ReplicaState>,
+}
+
+impl<DB: Database> Replica<DB> {
+    /// Cached health, or `None` when it is due for another ping
+    fn cached_health(&self) -> Option<bool> {
+        let state = *self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state
+            .checked_at
+            .filter(|checked_at| checked_at.elapsed() < HEALTH_RECHECK_INTERVAL)
+            .map(|_| state.healthy)
+    }
+
+    async fn ping(&self) -> bool {
+        let result = match self.pool.acquire().await {
+            Ok(mut connection) => connection.ping().await,
+            Err(e) => Err(e),
+        };
+
+        let healthy = result.is_ok();
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match (&result, state.healthy) {
+            (Err(e), _) => warn!("Read replica {} is unavailable: {}", self.label, e),
+            (Ok(()), false) if state.checked_at.is_some() => {
+                info!("Read replica {} is available again", self.label)
+            }
+            _ => {}
+        }
+        *state = ReplicaState {
+            healthy,
+            checked_at: Some(Instant::now()),
+        };
+        healthy
+    }
+
+    async fn is_healthy(&self) -> bool {
+        match self.cached_health() {
+            Some(healthy) => healthy,
+            None => self.ping().await,
+        }
+    }
+}
+
+/// Health of one replica as reported by [`ReadRouter::check_replicas`]
+#[derive(Debug, Clone)]
+pub struct ReplicaStatus {
+    pub label: String,
+    pub healthy: bool,
+}
+
+/// Read pool selection for one backend
+pub struct ReadRouter<DB: Database> {
+    primary: Pool<DB>,
+    replicas: Arc<Vec<Replica<DB>>>,
+    next: Arc<AtomicUsize>,
+}
+
+impl<DB: Database> Clone for ReadRouter<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            primary: self.primary.clone(),
+            replicas: Arc::clone(&self.replicas),
+            next: Arc::clone(&self.next),
+        }
+    }
+}
+
+impl<DB: Database> ReadRouter<DB> {
+    /// Router that always reads from the primary
+    pub fn primary_only(primary: Pool<DB>) -> Self {
+        Self::new(primary, Vec::new())
+    }
+
+    /// `replicas` pairs a log label (usually the host) with its pool
+    pub fn new(primary: Pool<DB>, replicas: Vec<(String, Pool<DB>)>) -> Self {
+        let replicas = replicas
+            .into_iter()
+            .map(|(label, pool)| Replica {
+                label,
+                pool,
+                state: Mutex::new(ReplicaState::default()),
+            })
+            .collect();
+
+        Self {
+            primary,
+            replicas: Arc::new(replicas),
+            next: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn primary(&self) -> &Pool<DB> {
+        &self.primary
+    }
+
+    pub fn replica_count(&self) -> usize {
+        self.replicas.len()
+    }
+
+    /// Next healthy replica, or the primary when none is available
+    pub async fn pool(&self) -> Pool<DB> {
+        let count = self.replicas.len();
+        if count == 0 {
+            return self.primary.clone();
+        }
+
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..count {
+            let replica = &self.replicas[(start + offset) % count];
+            if replica.is_healthy().await {
+                return replica.pool.clone();
+            }
+        }
+
+        debug!("No healthy read replica, reading from the primary");
+        self.primary.clone()
+    }
+
+    /// Pings every replica now, refreshing the cached health
+    pub async fn check_replicas(&self) -> Vec<ReplicaStatus> {
+        let mut statuses = Vec::with_capacity(self.replicas.len());
+        for replica in self.replicas.iter() {
+            statuses.push(ReplicaStatus {
+                label: replica.label.clone(),
+                healthy: replica.ping().await,
+            });
+        }
+        statuses
+    }
+
+    pub async fn close(&self) {
+        for replica in self.replicas.iter() {
+            replica.pool.close().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use sqlx::{Sqlite, SqlitePool};
+
+    async fn labelled_pool(label: &str) -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE node (label TEXT NOT NULL)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO node (label) VALUES (?)")
+            .bind(label)
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    async fn label_of(router: &ReadRouter<Sqlite>) -> String {
+        sqlx::query_scalar("SELECT label FROM node")
+            .fetch_one(&router.pool().await)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reads_rotate_over_healthy_replicas_and_fall_back_to_primary() {
+        let primary = labelled_pool("primary").await;
+        let router = ReadRouter::primary_only(primary.clone());
+        assert_eq!(label_of(&router).await, "primary");
+
+        let first = labelled_pool("replica-1").await;
+        let second = labelled_pool("replica-2").await;
+        let router = ReadRouter::new(
+            primary,
+            vec![
+                ("replica-1".to_string(), first.clone()),
+                ("replica-2".to_string(), second.clone()),
+            ],
+        );
+        assert_eq!(label_of(&router).await, "replica-1");
+        assert_eq!(label_of(&router).await, "replica-2");
+
+        // A replica that stops answering is skipped once its health is rechecked
+        first.close().await;
+        let statuses = router.check_replicas().await;
+        assert!(!statuses[0].healthy);
+        assert!(statuses[1].healthy);
+        assert_eq!(label_of(&router).await, "replica-2");
+        assert_eq!(label_of(&router).await, "replica-2");
+
+        second.close().await;
+        router.check_replicas().await;
+        assert_eq!(label_of(&router).await, "primary");
+    }
+}
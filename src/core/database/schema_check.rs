@@ -0,0 +1,415 @@
+//! Compares the live Postgres schema with what the repositories expect.
+//!
+//! The expected side lives in [`models::schema`](super::models::schema);
+//! the actual side is read from `information_schema` and the catalog.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::Result;
+use sqlx::postgres::PgPool;
+
+use super::models::schema::{ExpectedEnum, ExpectedTable, EXPECTED_ENUMS, EXPECTED_TABLES};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaIssue {
+    MissingTable {
+        table: String,
+    },
+    MissingColumn {
+        table: String,
+        column: String,
+        expected: String,
+    },
+    TypeMismatch {
+        table: String,
+        column: String,
+        expected: String,
+        found: String,
+    },
+    MissingIndex {
+        table: String,
+        columns: Vec<String>,
+        unique: bool,
+    },
+    MissingEnum {
+        name: String,
+    },
+    MissingEnumValue {
+        name: String,
+        value: String,
+    },
+}
+
+impl SchemaIssue {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SchemaIssue::MissingTable { .. } => "missing table",
+            SchemaIssue::MissingColumn { .. } => "missing column",
+            SchemaIssue::TypeMismatch { .. } => "type mismatch",
+            SchemaIssue::MissingIndex { .. } => "missing index",
+            SchemaIssue::MissingEnum { .. } => "missing enum",
+            SchemaIssue::MissingEnumValue { .. } => "missing enum value",
+        }
+    }
+
+    /// Table or enum type the issue is about
+    pub fn subject(&self) -> &str {
+        match self {
+            SchemaIssue::MissingTable { table }
+            | SchemaIssue::MissingColumn { table, .. }
+            | SchemaIssue::TypeMismatch { table, .. }
+            | SchemaIssue::MissingIndex { table, .. } => table,
+            SchemaIssue::MissingEnum { name } | SchemaIssue::MissingEnumValue { name, .. } => name,
+        }
+    }
+}
+
+impl fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaIssue::MissingTable { table } => write!(f, "table {} does not exist", table),
+            SchemaIssue::MissingColumn {
+                table,
+                column,
+                expected,
+            } => write!(f, "{}.{} ({}) does not exist", table, column, expected),
+            SchemaIssue::TypeMismatch {
+                table,
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}.{} is {}, expected {}",
+                table, column, found, expected
+            ),
+            SchemaIssue::MissingIndex {
+                table,
+                columns,
+                unique,
+            } => write!(
+                f,
+                "no {}index on {}({})",
+                if *unique { "unique " } else { "" },
+                table,
+                columns.join(", ")
+            ),
+            SchemaIssue::MissingEnum { name } => write!(f, "enum type {} does not exist", name),
+            SchemaIssue::MissingEnumValue { name, value } => {
+                write!(f, "enum {} has no value '{}'", name, value)
+            }
+        }
+    }
+}
+
+/// Index shape as stored in the catalog, key columns in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActualIndex {
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+/// The parts of the live schema the check looks at
+#[derive(Debug, Clone, Default)]
+pub struct ActualSchema {
+    /// table -> column -> `udt_name`
+    pub columns: HashMap<String, HashMap<String, String>>,
+    pub indexes: HashMap<String, Vec<ActualIndex>>,
+    /// enum type -> labels
+    pub enums: HashMap<String, Vec<String>>,
+}
+
+pub async fn verify_postgres_schema(pool: &PgPool) -> Result<Vec<SchemaIssue>> {
+    let actual = read_postgres_schema(pool).await?;
+    Ok(compare_schema(EXPECTED_TABLES, EXPECTED_ENUMS, &actual))
+}
+
+pub async fn read_postgres_schema(pool: &PgPool) -> Result<ActualSchema> {
+    let tables: Vec<String> = EXPECTED_TABLES.iter().map(|t| t.name.to_string()).collect();
+    let mut actual = ActualSchema::default();
+
+    let columns: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT table_name::text, column_name::text, udt_name::text
+         FROM information_schema.columns
+         WHERE table_schema = current_schema() AND table_name = ANY($1)",
+    )
+    .bind(&tables)
+    .fetch_all(pool)
+    .await?;
+    for (table, column, udt_name) in columns {
+        actual
+            .columns
+            .entry(table)
+            .or_default()
+            .insert(column, udt_name);
+    }
+
+    // Expression columns (attnum 0) drop out of the join
+    let indexes: Vec<(String, bool, Vec<String>)> = sqlx::query_as(
+        "SELECT t.relname::text, i.indisunique,
+                array_agg(a.attname::text ORDER BY k.ord)
+         FROM pg_index i
+         JOIN pg_class t ON t.oid = i.indrelid
+         JOIN pg_namespace n ON n.oid = t.relnamespace
+         CROSS JOIN LATERAL unnest(i.indkey) WITH ORDINALITY AS k(attnum, ord)
+         JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = k.attnum
+         WHERE n.nspname = current_schema() AND t.relname = ANY($1)
+         GROUP BY t.relname, i.indexrelid, i.indisunique",
+    )
+    .bind(&tables)
+    .fetch_all(pool)
+    .await?;
+    for (table, unique, columns) in indexes {
+        actual
+            .indexes
+            .entry(table)
+            .or_default()
+            .push(ActualIndex { columns, unique });
+    }
+
+    let labels: Vec<(String, String)> = sqlx::query_as(
+        "SELECT t.typname::text, e.enumlabel::text
+         FROM pg_type t
+         JOIN pg_enum e ON e.enumtypid = t.oid
+         JOIN pg_namespace n ON n.oid = t.typnamespace
+         WHERE n.nspname = current_schema()
+         ORDER BY t.typname, e.enumsortorder",
+    )
+    .fetch_all(pool)
+    .await?;
+    for (name, label) in labels {
+        actual.enums.entry(name).or_default().push(label);
+    }
+
+    Ok(actual)
+}
+
+pub fn compare_schema(
+    tables: &[ExpectedTable],
+    enums: &[ExpectedEnum],
+    actual: &ActualSchema,
+) -> Vec<SchemaIssue> {
+    let mut issues = Vec::new();
+
+    for table in tables {
+        let Some(columns) = actual.columns.get(table.name) else {
+            issues.push(SchemaIssue::MissingTable {
+                table: table.name.to_string(),
+            });
+            continue;
+        };
+
+        for column in table.columns {
+            match columns.get(column.name) {
+                None => issues.push(SchemaIssue::MissingColumn {
+                    table: table.name.to_string(),
+                    column: column.name.to_string(),
+                    expected: column.column_type.label().to_string(),
+                }),
+                Some(udt_name) if !column.column_type.accepts(udt_name) => {
+                    issues.push(SchemaIssue::TypeMismatch {
+                        table: table.name.to_string(),
+                        column: column.name.to_string(),
+                        expected: column.column_type.label().to_string(),
+                        found: udt_name.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        let indexes = actual
+            .indexes
+            .get(table.name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for expected in table.indexes {
+            let covered = indexes.iter().any(|index| {
+                if expected.unique {
+                    index.unique && index.columns == expected.columns
+                } else {
+                    index.columns.len() >= expected.columns.len()
+                        && index
+                            .columns
+                            .iter()
+                            .zip(expected.columns)
+                            .all(|(a, e)| a == e)
+                }
+            });
+            if !covered {
+                issues.push(SchemaIssue::MissingIndex {
+                    table: table.name.to_string(),
+                    columns: expected.columns.iter().map(|c| c.to_string()).collect(),
+                    unique: expected.unique,
+                });
+            }
+        }
+    }
+
+    for expected in enums {
+        let Some(labels) = actual.enums.get(expected.name) else {
+            issues.push(SchemaIssue::MissingEnum {
+                name: expected.name.to_string(),
+            });
+            continue;
+        };
+        for value in expected.values {
+            if !labels.iter().any(|label| label == value) {
+                issues.push(SchemaIssue::MissingEnumValue {
+                    name: expected.name.to_string(),
+                    value: value.to_string(),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::database::migration::ScratchDatabase;
+    use crate::core::database::models::schema::{ColumnType, ExpectedColumn, ExpectedIndex};
+
+    const TABLES: &[ExpectedTable] = &[
+        ExpectedTable {
+            name: "products",
+            repository: "PostgresInventoryRepository",
+            columns: &[
+                ExpectedColumn {
+                    name: "id",
+                    column_type: ColumnType::Uuid,
+                },
+                ExpectedColumn {
+                    name: "sku",
+                    column_type: ColumnType::Text,
+                },
+                ExpectedColumn {
+                    name: "status",
+                    column_type: ColumnType::Enum("product_status"),
+                },
+            ],
+            indexes: &[
+                ExpectedIndex {
+                    columns: &["sku"],
+                    unique: true,
+                },
+                ExpectedIndex {
+                    columns: &["status"],
+                    unique: false,
+                },
+            ],
+        },
+        ExpectedTable {
+            name: "stock_movements",
+            repository: "PostgresInventoryRepository",
+            columns: &[],
+            indexes: &[],
+        },
+    ];
+
+    const ENUMS: &[ExpectedEnum] = &[ExpectedEnum {
+        name: "product_status",
+        values: &["active", "outofstock"],
+    }];
+
+    fn actual_schema() -> ActualSchema {
+        let mut actual = ActualSchema::default();
+        actual.columns.insert(
+            "products".to_string(),
+            HashMap::from([
+                ("id".to_string(), "varchar".to_string()),
+                ("sku".to_string(), "text".to_string()),
+            ]),
+        );
+        actual.indexes.insert(
+            "products".to_string(),
+            vec![
+                ActualIndex {
+                    columns: vec!["sku".to_string()],
+                    unique: false,
+                },
+                ActualIndex {
+                    columns: vec!["status".to_string(), "created_at".to_string()],
+                    unique: false,
+                },
+            ],
+        );
+        actual.enums.insert(
+            "product_status".to_string(),
+            vec!["active".to_string(), "out_of_stock".to_string()],
+        );
+        actual
+    }
+
+    #[test]
+    fn test_compare_reports_each_kind_of_drift() {
+        let issues = compare_schema(TABLES, ENUMS, &actual_schema());
+
+        assert_eq!(
+            issues,
+            vec![
+                SchemaIssue::TypeMismatch {
+                    table: "products".to_string(),
+                    column: "id".to_string(),
+                    expected: "uuid".to_string(),
+                    found: "varchar".to_string(),
+                },
+                SchemaIssue::MissingColumn {
+                    table: "products".to_string(),
+                    column: "status".to_string(),
+                    expected: "product_status".to_string(),
+                },
+                SchemaIssue::MissingIndex {
+                    table: "products".to_string(),
+                    columns: vec!["sku".to_string()],
+                    unique: true,
+                },
+                SchemaIssue::MissingTable {
+                    table: "stock_movements".to_string(),
+                },
+                SchemaIssue::MissingEnumValue {
+                    name: "product_status".to_string(),
+                    value: "outofstock".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "products.id is varchar, expected uuid"
+        );
+    }
+
+    #[test]
+    fn test_matching_schema_has_no_issues() {
+        let mut actual = actual_schema();
+        let products = actual.columns.get_mut("products").unwrap();
+        products.insert("id".to_string(), "uuid".to_string());
+        products.insert("status".to_string(), "product_status".to_string());
+        actual
+            .columns
+            .insert("stock_movements".to_string(), HashMap::new());
+        actual.indexes.get_mut("products").unwrap()[0].unique = true;
+        actual
+            .enums
+            .get_mut("product_status")
+            .unwrap()
+            .push("outofstock".to_string());
+
+        assert!(compare_schema(TABLES, ENUMS, &actual).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_freshly_migrated_database_has_no_issues() {
+        let Some(db) = ScratchDatabase::create().await else {
+            return;
+        };
+        db.migrate_up().await;
+        let issues = verify_postgres_schema(&db.pool).await.unwrap();
+        db.drop().await;
+
+        assert!(issues.is_empty(), "unexpected schema issues: {:?}", issues);
+    }
+}
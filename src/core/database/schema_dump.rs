@@ -0,0 +1,345 @@
+//! Reads the live database schema back as executable DDL.
+//!
+//! Used by `erp migrate baseline` to squash the migration history into a
+//! single file. Statements come out in dependency order (types and
+//! functions, tables and comments, constraints, indexes, views, triggers)
+//! and never include the `schema_migrations` bookkeeping table.
+
+use anyhow::Result;
+use sqlx::{postgres::PgPool, sqlite::SqlitePool};
+
+const MIGRATIONS_TABLE: &str = "schema_migrations";
+
+/// One table column as reported by `pg_attribute`
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct ColumnDef {
+    pub table_name: String,
+    pub name: String,
+    pub data_type: String,
+    pub not_null: bool,
+    pub default_expr: Option<String>,
+    /// `a` (always) or `d` (by default) for identity columns, empty otherwise
+    pub identity: String,
+    /// `s` for stored generated columns, empty otherwise
+    pub generated: String,
+}
+
+pub async fn dump_postgres_schema(pool: &PgPool) -> Result<Vec<String>> {
+    let mut statements = Vec::new();
+
+    let extensions: Vec<String> = sqlx::query_scalar(
+        "SELECT format('CREATE EXTENSION IF NOT EXISTS %I', extname)
+         FROM pg_extension
+         WHERE extname <> 'plpgsql'
+         ORDER BY extname",
+    )
+    .fetch_all(pool)
+    .await?;
+    statements.extend(extensions);
+
+    let enums: Vec<String> = sqlx::query_scalar(
+        "SELECT format('CREATE TYPE %I AS ENUM (%s)', t.typname,
+                string_agg(quote_literal(e.enumlabel), ', ' ORDER BY e.enumsortorder))
+         FROM pg_type t
+         JOIN pg_enum e ON e.enumtypid = t.oid
+         JOIN pg_namespace n ON n.oid = t.typnamespace
+         WHERE n.nspname = current_schema()
+         GROUP BY t.typname
+         ORDER BY t.typname",
+    )
+    .fetch_all(pool)
+    .await?;
+    statements.extend(enums);
+
+    // Identity sequences are recreated by their column definition
+    let sequences: Vec<String> = sqlx::query_scalar(
+        "SELECT format('CREATE SEQUENCE %I AS %s INCREMENT BY %s START WITH %s',
+                c.relname, format_type(s.seqtypid, NULL), s.seqincrement, s.seqstart)
+         FROM pg_sequence s
+         JOIN pg_class c ON c.oid = s.seqrelid
+         JOIN pg_namespace n ON n.oid = c.relnamespace
+         WHERE n.nspname = current_schema()
+           AND NOT EXISTS (
+               SELECT 1 FROM pg_depend d
+               WHERE d.objid = c.oid AND d.deptype IN ('i', 'e')
+           )
+         ORDER BY c.relname",
+    )
+    .fetch_all(pool)
+    .await?;
+    statements.extend(sequences);
+
+    let functions: Vec<String> = sqlx::query_scalar(
+        "SELECT pg_get_functiondef(p.oid)
+         FROM pg_proc p
+         JOIN pg_namespace n ON n.oid = p.pronamespace
+         WHERE n.nspname = current_schema()
+           AND p.prokind IN ('f', 'p')
+           AND NOT EXISTS (
+               SELECT 1 FROM pg_depend d
+               WHERE d.objid = p.oid AND d.deptype = 'e'
+           )
+         ORDER BY p.proname, p.oid",
+    )
+    .fetch_all(pool)
+    .await?;
+    statements.extend(functions.into_iter().map(|f| f.trim().to_string()));
+
+    let columns = sqlx::query_as::<_, ColumnDef>(
+        "SELECT quote_ident(c.relname) AS table_name,
+                quote_ident(a.attname) AS name,
+                format_type(a.atttypid, a.atttypmod) AS data_type,
+                a.attnotnull AS not_null,
+                pg_get_expr(d.adbin, d.adrelid) AS default_expr,
+                a.attidentity::text AS identity,
+                a.attgenerated::text AS generated
+         FROM pg_attribute a
+         JOIN pg_class c ON c.oid = a.attrelid
+         JOIN pg_namespace n ON n.oid = c.relnamespace
+         LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+         WHERE n.nspname = current_schema()
+           AND c.relkind = 'r'
+           AND c.relname <> $1
+           AND a.attnum > 0
+           AND NOT a.attisdropped
+         ORDER BY c.relname, a.attnum",
+    )
+    .bind(MIGRATIONS_TABLE)
+    .fetch_all(pool)
+    .await?;
+    statements.extend(render_create_tables(&columns));
+
+    let sequence_owners: Vec<String> = sqlx::query_scalar(
+        "SELECT format('ALTER SEQUENCE %I OWNED BY %I.%I', s.relname, t.relname, a.attname)
+         FROM pg_depend d
+         JOIN pg_class s ON s.oid = d.objid AND s.relkind = 'S'
+         JOIN pg_class t ON t.oid = d.refobjid
+         JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = d.refobjsubid
+         JOIN pg_namespace n ON n.oid = s.relnamespace
+         WHERE d.deptype = 'a' AND n.nspname = current_schema()
+         ORDER BY s.relname",
+    )
+    .fetch_all(pool)
+    .await?;
+    statements.extend(sequence_owners);
+
+    let comments: Vec<String> = sqlx::query_scalar(
+        "SELECT format('COMMENT ON %s IS %L',
+                CASE WHEN d.objsubid = 0 THEN format('TABLE %I', c.relname)
+                     ELSE format('COLUMN %I.%I', c.relname, a.attname) END,
+                d.description)
+         FROM pg_description d
+         JOIN pg_class c ON c.oid = d.objoid AND d.classoid = 'pg_class'::regclass
+         JOIN pg_namespace n ON n.oid = c.relnamespace
+         LEFT JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum = d.objsubid
+         WHERE n.nspname = current_schema()
+           AND c.relkind = 'r'
+           AND c.relname <> $1
+         ORDER BY c.relname, d.objsubid",
+    )
+    .bind(MIGRATIONS_TABLE)
+    .fetch_all(pool)
+    .await?;
+    statements.extend(comments);
+
+    // Foreign keys last, once every referenced key exists
+    let constraints: Vec<String> = sqlx::query_scalar(
+        "SELECT format('ALTER TABLE %I ADD CONSTRAINT %I %s',
+                c.relname, con.conname, pg_get_constraintdef(con.oid))
+         FROM pg_constraint con
+         JOIN pg_class c ON c.oid = con.conrelid
+         JOIN pg_namespace n ON n.oid = c.relnamespace
+         WHERE n.nspname = current_schema()
+           AND c.relname <> $1
+           AND con.contype IN ('p', 'u', 'c', 'x', 'f')
+         ORDER BY con.contype = 'f', c.relname, con.contype, con.conname",
+    )
+    .bind(MIGRATIONS_TABLE)
+    .fetch_all(pool)
+    .await?;
+    statements.extend(constraints);
+
+    let indexes: Vec<String> = sqlx::query_scalar(
+        "SELECT pg_get_indexdef(i.indexrelid)
+         FROM pg_index i
+         JOIN pg_class t ON t.oid = i.indrelid
+         JOIN pg_class ic ON ic.oid = i.indexrelid
+         JOIN pg_namespace n ON n.oid = t.relnamespace
+         WHERE n.nspname = current_schema()
+           AND t.relname <> $1
+           AND NOT EXISTS (
+               SELECT 1 FROM pg_constraint con
+               WHERE con.conindid = i.indexrelid AND con.contype IN ('p', 'u', 'x')
+           )
+         ORDER BY t.relname, ic.relname",
+    )
+    .bind(MIGRATIONS_TABLE)
+    .fetch_all(pool)
+    .await?;
+    statements.extend(indexes);
+
+    let views: Vec<String> = sqlx::query_scalar(
+        "SELECT format('CREATE %sVIEW %I AS %s',
+                CASE c.relkind WHEN 'm' THEN 'MATERIALIZED ' ELSE '' END,
+                c.relname, pg_get_viewdef(c.oid))
+         FROM pg_class c
+         JOIN pg_namespace n ON n.oid = c.relnamespace
+         WHERE n.nspname = current_schema()
+           AND c.relkind IN ('v', 'm')
+           AND NOT EXISTS (
+               SELECT 1 FROM pg_depend d
+               WHERE d.objid = c.oid AND d.deptype = 'e'
+           )
+         ORDER BY c.oid",
+    )
+    .fetch_all(pool)
+    .await?;
+    statements.extend(
+        views
+            .into_iter()
+            .map(|v| v.trim().trim_end_matches(';').to_string()),
+    );
+
+    let triggers: Vec<String> = sqlx::query_scalar(
+        "SELECT pg_get_triggerdef(t.oid)
+         FROM pg_trigger t
+         JOIN pg_class c ON c.oid = t.tgrelid
+         JOIN pg_namespace n ON n.oid = c.relnamespace
+         WHERE n.nspname = current_schema()
+           AND NOT t.tgisinternal
+         ORDER BY c.relname, t.tgname",
+    )
+    .fetch_all(pool)
+    .await?;
+    statements.extend(triggers);
+
+    Ok(statements)
+}
+
+pub async fn dump_sqlite_schema(pool: &SqlitePool) -> Result<Vec<String>> {
+    // Internal autoindexes have no SQL and are recreated by their constraints
+    let statements: Vec<String> = sqlx::query_scalar(
+        "SELECT sql FROM sqlite_master
+         WHERE sql IS NOT NULL
+           AND name NOT LIKE 'sqlite_%'
+           AND tbl_name <> ?
+         ORDER BY CASE type WHEN 'table' THEN 0 WHEN 'index' THEN 1 WHEN 'view' THEN 2 ELSE 3 END,
+                  rowid",
+    )
+    .bind(MIGRATIONS_TABLE)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(statements)
+}
+
+/// Groups columns (ordered by table) into `CREATE TABLE` statements
+fn render_create_tables(columns: &[ColumnDef]) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+
+    while start < columns.len() {
+        let table = &columns[start].table_name;
+        let end = columns[start..]
+            .iter()
+            .position(|c| &c.table_name != table)
+            .map_or(columns.len(), |offset| start + offset);
+
+        let body = columns[start..end]
+            .iter()
+            .map(|c| format!("    {}", render_column(c)))
+            .collect::<Vec<_>>()
+            .join(",\n");
+        statements.push(format!("CREATE TABLE {} (\n{}\n)", table, body));
+        start = end;
+    }
+
+    statements
+}
+
+fn render_column(column: &ColumnDef) -> String {
+    let mut sql = format!("{} {}", column.name, column.data_type);
+
+    match (column.generated.as_str(), column.identity.as_str()) {
+        ("s", _) => {
+            if let Some(expr) = &column.default_expr {
+                sql.push_str(&format!(" GENERATED ALWAYS AS ({}) STORED", expr));
+            }
+        }
+        (_, "a") => sql.push_str(" GENERATED ALWAYS AS IDENTITY"),
+        (_, "d") => sql.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+        _ => {
+            if let Some(expr) = &column.default_expr {
+                sql.push_str(&format!(" DEFAULT {}", expr));
+            }
+        }
+    }
+
+    if column.not_null {
+        sql.push_str(" NOT NULL");
+    }
+    sql
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(table: &str, name: &str, data_type: &str) -> ColumnDef {
+        ColumnDef {
+            table_name: table.to_string(),
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            not_null: false,
+            default_expr: None,
+            identity: String::new(),
+            generated: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_create_tables_groups_columns_by_table() {
+        let columns = vec![
+            ColumnDef {
+                not_null: true,
+                default_expr: Some("uuid_generate_v4()".to_string()),
+                ..column("customers", "id", "uuid")
+            },
+            column("customers", "email", "character varying(255)"),
+            ColumnDef {
+                not_null: true,
+                identity: "a".to_string(),
+                ..column("events", "id", "bigint")
+            },
+        ];
+
+        assert_eq!(
+            render_create_tables(&columns),
+            vec![
+                "CREATE TABLE customers (\n    id uuid DEFAULT uuid_generate_v4() NOT NULL,\n    email character varying(255)\n)",
+                "CREATE TABLE events (\n    id bigint GENERATED ALWAYS AS IDENTITY NOT NULL\n)",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_dump_skips_migration_bookkeeping() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        for sql in [
+            "CREATE TABLE schema_migrations (version TEXT PRIMARY KEY)",
+            "CREATE TABLE products (id BLOB PRIMARY KEY, sku TEXT NOT NULL UNIQUE)",
+            "CREATE INDEX idx_products_sku ON products(sku)",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+
+        let statements = dump_sqlite_schema(&pool).await.unwrap();
+        assert_eq!(
+            statements,
+            vec![
+                "CREATE TABLE products (id BLOB PRIMARY KEY, sku TEXT NOT NULL UNIQUE)",
+                "CREATE INDEX idx_products_sku ON products(sku)",
+            ]
+        );
+    }
+}
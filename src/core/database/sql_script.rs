@@ -0,0 +1,229 @@
+//! SQL script tokenizer used by the migration runner.
+//!
+//! Splits a script into statements on top-level `;` while skipping over
+//! string literals, quoted identifiers, `--` and `/* */` comments and
+//! PostgreSQL dollar-quoted bodies (`$$ ... $$`, `$fn$ ... $fn$`), so
+//! PL/pgSQL functions and `DO` blocks stay in one piece.
+
+/// Splits `sql` into executable statements without the trailing `;`.
+///
+/// Statements that contain only whitespace and comments are dropped.
+pub fn split_statements(sql: &str) -> Vec<String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_code = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match ch {
+            '-' if next == Some('-') => {
+                i = skip_line_comment(&chars, i);
+                continue;
+            }
+            '/' if next == Some('*') => {
+                i = skip_block_comment(&chars, i);
+                continue;
+            }
+            '\'' => {
+                // E'...' strings treat backslash as an escape character
+                let backslash_escapes = i > 0
+                    && matches!(chars[i - 1], 'e' | 'E')
+                    && (i < 2 || !is_ident_char(chars[i - 2]));
+                i = skip_quoted(&chars, i, '\'', backslash_escapes);
+                has_code = true;
+                continue;
+            }
+            '"' | '`' => {
+                i = skip_quoted(&chars, i, ch, false);
+                has_code = true;
+                continue;
+            }
+            '$' => {
+                if let Some(tag_len) = dollar_tag_len(&chars, i) {
+                    i = skip_dollar_quoted(&chars, i, tag_len);
+                    has_code = true;
+                    continue;
+                }
+            }
+            ';' => {
+                if has_code {
+                    statements.push(
+                        chars[start..i]
+                            .iter()
+                            .collect::<String>()
+                            .trim()
+                            .to_string(),
+                    );
+                }
+                start = i + 1;
+                has_code = false;
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        if !ch.is_whitespace() {
+            has_code = true;
+        }
+        i += 1;
+    }
+
+    if has_code {
+        statements.push(chars[start..].iter().collect::<String>().trim().to_string());
+    }
+
+    statements
+}
+
+fn is_ident_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Returns the index just past the end of the line
+fn skip_line_comment(chars: &[char], start: usize) -> usize {
+    chars[start..]
+        .iter()
+        .position(|&c| c == '\n')
+        .map_or(chars.len(), |offset| start + offset + 1)
+}
+
+/// Block comments nest in PostgreSQL
+fn skip_block_comment(chars: &[char], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < chars.len() {
+        match (chars[i], chars.get(i + 1).copied()) {
+            ('/', Some('*')) => {
+                depth += 1;
+                i += 2;
+            }
+            ('*', Some('/')) => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
+
+/// A doubled quote character is an escaped quote inside the literal
+fn skip_quoted(chars: &[char], start: usize, quote: char, backslash_escapes: bool) -> usize {
+    let mut i = start + 1;
+    while i < chars.len() {
+        let ch = chars[i];
+        if backslash_escapes && ch == '\\' {
+            i += 2;
+        } else if ch == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return i + 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    chars.len()
+}
+
+/// Length of a `$tag$` opener at `start`, if there is one.
+///
+/// Positional parameters like `$1` and identifiers containing `$` are not tags.
+fn dollar_tag_len(chars: &[char], start: usize) -> Option<usize> {
+    if start > 0 && is_ident_char(chars[start - 1]) {
+        return None;
+    }
+
+    let mut i = start + 1;
+    if let Some(&first) = chars.get(i) {
+        if first.is_ascii_digit() {
+            return None;
+        }
+    }
+    while i < chars.len() && is_ident_char(chars[i]) {
+        i += 1;
+    }
+
+    (chars.get(i) == Some(&'$')).then_some(i + 1 - start)
+}
+
+fn skip_dollar_quoted(chars: &[char], start: usize, tag_len: usize) -> usize {
+    let tag = &chars[start..start + tag_len];
+    let mut i = start + tag_len;
+    while i + tag_len <= chars.len() {
+        if &chars[i..i + tag_len] == tag {
+            return i + tag_len;
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splits_plain_statements() {
+        let statements = split_statements("CREATE TABLE a (id INT);\n\nINSERT INTO a VALUES (1);");
+        assert_eq!(
+            statements,
+            vec!["CREATE TABLE a (id INT)", "INSERT INTO a VALUES (1)"]
+        );
+    }
+
+    #[test]
+    fn test_dollar_quoted_function_body_is_one_statement() {
+        let sql = r#"
+CREATE OR REPLACE FUNCTION touch_updated_at() RETURNS trigger AS $$
+BEGIN
+    NEW.updated_at = NOW();
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DO $body$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'mood') THEN
+        CREATE TYPE mood AS ENUM ('happy', 'sad');
+    END IF;
+END
+$body$;
+"#;
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("LANGUAGE plpgsql"));
+        assert!(statements[1].starts_with("DO $body$"));
+        assert!(statements[1].ends_with("$body$"));
+    }
+
+    #[test]
+    fn test_comments_and_quotes_do_not_split() {
+        let sql = "-- don't split here; please\n\
+                   /* nested /* block; */ comment */\n\
+                   INSERT INTO notes (body, \"semi;colon\") VALUES ('it''s; fine', E'a\\'; b');\n\
+                   -- trailing comment only;";
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 1);
+        assert!(statements[0].contains("'it''s; fine'"));
+        assert!(statements[0].contains("E'a\\'; b'"));
+    }
+
+    #[test]
+    fn test_positional_parameters_are_not_dollar_quotes() {
+        let statements = split_statements("SELECT $1; SELECT price$ FROM t; SELECT 2");
+        assert_eq!(
+            statements,
+            vec!["SELECT $1", "SELECT price$ FROM t", "SELECT 2"]
+        );
+    }
+}
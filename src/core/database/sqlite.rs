@@ -0,0 +1,77 @@
+//! Column helpers for the SQLite backend.
+//!
+//! sqlx cannot encode `rust_decimal::Decimal` for SQLite, so money columns
+//! are stored as decimal text and parsed on the way out. Sums are done in
+//! Rust for the same reason: SQLite would aggregate them as floating point.
+
+use rust_decimal::Decimal;
+use sqlx::sqlite::SqliteRow;
+use sqlx::Row;
+use std::str::FromStr;
+
+/// Reads a required decimal text column
+pub fn decimal(row: &SqliteRow, column: &str) -> Result<Decimal, sqlx::Error> {
+    opt_decimal(row, column)?.ok_or_else(|| sqlx::Error::ColumnDecode {
+        index: column.to_string(),
+        source: "unexpected NULL decimal".into(),
+    })
+}
+
+/// Reads a nullable decimal text column
+pub fn opt_decimal(row: &SqliteRow, column: &str) -> Result<Option<Decimal>, sqlx::Error> {
+    let text: Option<String> = row.try_get(column)?;
+    text.map(|value| {
+        Decimal::from_str(value.trim()).map_err(|e| sqlx::Error::ColumnDecode {
+            index: column.to_string(),
+            source: Box::new(e),
+        })
+    })
+    .transpose()
+}
+
+/// Decimal text for binding
+pub fn decimal_text(value: Decimal) -> String {
+    value.to_string()
+}
+
+pub fn opt_decimal_text(value: Option<Decimal>) -> Option<String> {
+    value.map(decimal_text)
+}
+
+#[cfg(test)]
+pub(crate) async fn test_pool() -> sqlx::SqlitePool {
+    use crate::core::config::DatabaseConfig;
+    use crate::core::database::connection::DatabaseConnection;
+
+    let connection = DatabaseConnection::new(DatabaseConfig {
+        url: "sqlite::memory:".to_string(),
+        max_connections: 1,
+        migrate_on_start: true,
+        query_timeout_seconds: 5,
+        idle_timeout_seconds: 60,
+        replica_urls: Vec::new(),
+        enable_query_logging: false,
+    })
+    .await
+    .expect("in-memory SQLite database");
+    connection.sqlite_pool().expect("SQLite pool").clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_decimal_round_trip() {
+        let pool = test_pool().await;
+        let row = sqlx::query("SELECT ? AS price, NULL AS weight")
+            .bind(decimal_text(Decimal::new(129900, 2)))
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(decimal(&row, "price").unwrap(), Decimal::new(1299, 0));
+        assert_eq!(opt_decimal(&row, "weight").unwrap(), None);
+        assert!(decimal(&row, "weight").is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! One transaction shared by repository calls from several modules.
+//!
+//! A service begins a [`UnitOfWork`] from any repository, passes it to the
+//! `*_in` methods of as many repositories as it needs, and finishes it once.
+//! Dropping an unfinished unit of work rolls it back.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Instant;
+
+use sqlx::{PgConnection, PgPool, Postgres, Sqlite, SqliteConnection, SqlitePool, Transaction};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::core::logging::DatabaseLogger;
+use crate::utils::error::{ErpError, ErpResult};
+
+type StagedWrite = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// In-memory stand-in used by the mock repositories.
+///
+/// Writes are staged and only applied on commit, so a rolled back unit of
+/// work leaves the mock stores untouched.
+#[derive(Default)]
+pub struct MockUnitOfWork {
+    staged: Vec<StagedWrite>,
+    fail_after: Option<usize>,
+}
+
+impl MockUnitOfWork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fails the write staged after `writes` successful ones
+    pub fn failing_after(writes: usize) -> Self {
+        Self {
+            staged: Vec::new(),
+            fail_after: Some(writes),
+        }
+    }
+
+    pub fn stage(&mut self, write: impl Future<Output = ()> + Send + 'static) -> ErpResult<()> {
+        if self.fail_after == Some(self.staged.len()) {
+            return Err(ErpError::database(format!(
+                "Injected failure on write {}",
+                self.staged.len() + 1
+            )));
+        }
+        self.staged.push(Box::pin(write));
+        Ok(())
+    }
+
+    pub fn staged(&self) -> usize {
+        self.staged.len()
+    }
+
+    async fn apply(self) {
+        for write in self.staged {
+            write.await;
+        }
+    }
+}
+
+enum Work {
+    Postgres(Box<Transaction<'static, Postgres>>),
+    Sqlite(Transaction<'static, Sqlite>),
+    Mock(MockUnitOfWork),
+}
+
+pub struct UnitOfWork {
+    id: Uuid,
+    started: Instant,
+    work: Work,
+}
+
+impl UnitOfWork {
+    pub async fn begin_postgres(pool: &PgPool) -> ErpResult<Self> {
+        let tx = pool
+            .begin()
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to start transaction: {}", e)))?;
+        Ok(Self::start(Work::Postgres(Box::new(tx))))
+    }
+
+    pub async fn begin_sqlite(pool: &SqlitePool) -> ErpResult<Self> {
+        let tx = pool
+            .begin()
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to start transaction: {}", e)))?;
+        Ok(Self::start(Work::Sqlite(tx)))
+    }
+
+    pub fn mock(mock: MockUnitOfWork) -> Self {
+        Self::start(Work::Mock(mock))
+    }
+
+    fn start(work: Work) -> Self {
+        let id = Uuid::new_v4();
+        DatabaseLogger.log_transaction_start(id);
+        Self {
+            id,
+            started: Instant::now(),
+            work,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Connection of a Postgres unit of work
+    pub fn postgres(&mut self) -> ErpResult<&mut PgConnection> {
+        let (id, actual) = (self.id, self.backend());
+        match &mut self.work {
+            Work::Postgres(tx) => Ok(&mut ***tx),
+            _ => Err(mismatch(id, actual, "Postgres")),
+        }
+    }
+
+    /// Connection of a SQLite unit of work
+    pub fn sqlite(&mut self) -> ErpResult<&mut SqliteConnection> {
+        let (id, actual) = (self.id, self.backend());
+        match &mut self.work {
+            Work::Sqlite(tx) => Ok(&mut **tx),
+            _ => Err(mismatch(id, actual, "SQLite")),
+        }
+    }
+
+    pub fn as_mock(&mut self) -> ErpResult<&mut MockUnitOfWork> {
+        let (id, actual) = (self.id, self.backend());
+        match &mut self.work {
+            Work::Mock(mock) => Ok(mock),
+            _ => Err(mismatch(id, actual, "mock")),
+        }
+    }
+
+    pub fn backend(&self) -> &'static str {
+        match self.work {
+            Work::Postgres(_) => "Postgres",
+            Work::Sqlite(_) => "SQLite",
+            Work::Mock(_) => "mock",
+        }
+    }
+
+    pub async fn commit(self) -> ErpResult<()> {
+        let result = match self.work {
+            Work::Postgres(tx) => (*tx).commit().await,
+            Work::Sqlite(tx) => tx.commit().await,
+            Work::Mock(mock) => {
+                mock.apply().await;
+                Ok(())
+            }
+        }
+        .map_err(|e| ErpError::database(format!("Failed to commit transaction: {}", e)));
+
+        DatabaseLogger.log_transaction_end(
+            self.id,
+            result.is_ok(),
+            self.started.elapsed().as_millis() as u64,
+        );
+        result
+    }
+
+    pub async fn rollback(self) -> ErpResult<()> {
+        let result = match self.work {
+            Work::Postgres(tx) => (*tx).rollback().await,
+            Work::Sqlite(tx) => tx.rollback().await,
+            Work::Mock(_) => Ok(()),
+        }
+        .map_err(|e| ErpError::database(format!("Failed to roll back transaction: {}", e)));
+
+        DatabaseLogger.log_transaction_end(
+            self.id,
+            false,
+            self.started.elapsed().as_millis() as u64,
+        );
+        result
+    }
+
+    /// Commits on `Ok`, rolls back on `Err` and hands the result back
+    pub async fn finish<T>(self, result: ErpResult<T>) -> ErpResult<T> {
+        match result {
+            Ok(value) => {
+                self.commit().await?;
+                Ok(value)
+            }
+            Err(error) => {
+                if let Err(rollback_error) = self.rollback().await {
+                    warn!("Failed to rollback transaction: {}", rollback_error);
+                }
+                Err(error)
+            }
+        }
+    }
+}
+
+fn mismatch(id: Uuid, actual: &str, expected: &str) -> ErpError {
+    ErpError::internal(format!(
+        "Unit of work {} is {}, repository expected {}",
+        id, actual, expected
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn staged_push(uow: &mut UnitOfWork, log: &Arc<Mutex<Vec<u32>>>, value: u32) -> ErpResult<()> {
+        let log = log.clone();
+        uow.as_mock()?.stage(async move {
+            log.lock().unwrap().push(value);
+        })
+    }
+
+    #[tokio::test]
+    async fn test_mock_unit_of_work_applies_writes_only_on_commit() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let mut uow = UnitOfWork::mock(MockUnitOfWork::new());
+        staged_push(&mut uow, &log, 1).unwrap();
+        staged_push(&mut uow, &log, 2).unwrap();
+        assert!(log.lock().unwrap().is_empty());
+        uow.commit().await.unwrap();
+        assert_eq!(*log.lock().unwrap(), vec![1, 2]);
+
+        let mut uow = UnitOfWork::mock(MockUnitOfWork::failing_after(1));
+        staged_push(&mut uow, &log, 3).unwrap();
+        let result = staged_push(&mut uow, &log, 4);
+        assert!(result.is_err());
+        assert!(uow.finish(result).await.is_err());
+        assert_eq!(*log.lock().unwrap(), vec![1, 2]);
+    }
+}
@@ -1,5 +1,11 @@
 use crate::core::config::LoggingConfig;
 use crate::utils::error::{ErpError, ErpResult};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
 use tracing_subscriber::EnvFilter;
 use uuid::Uuid;
 
@@ -47,10 +53,37 @@ impl LoggerBuilder {
     }
 }
 
+/// Queries seen by `DatabaseLogger::log_query` while stats collection is on
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QueryStats {
+    pub count: u64,
+    pub total_time: Duration,
+}
+
+static QUERY_STATS_ENABLED: AtomicBool = AtomicBool::new(false);
+static QUERY_COUNT: AtomicU64 = AtomicU64::new(0);
+static QUERY_TIME_MICROS: AtomicU64 = AtomicU64::new(0);
+
 #[derive(Clone)]
 pub struct DatabaseLogger;
 
 impl DatabaseLogger {
+    /// Resets the process-wide query counters and starts collecting
+    pub fn start_query_stats() {
+        QUERY_COUNT.store(0, Ordering::Relaxed);
+        QUERY_TIME_MICROS.store(0, Ordering::Relaxed);
+        QUERY_STATS_ENABLED.store(true, Ordering::Relaxed);
+    }
+
+    /// Stops collecting and returns what was counted since the start
+    pub fn stop_query_stats() -> QueryStats {
+        QUERY_STATS_ENABLED.store(false, Ordering::Relaxed);
+        QueryStats {
+            count: QUERY_COUNT.load(Ordering::Relaxed),
+            total_time: Duration::from_micros(QUERY_TIME_MICROS.load(Ordering::Relaxed)),
+        }
+    }
+
     pub fn log_transaction_start(&self, tx_id: Uuid) {
         tracing::debug!("Transaction started: {}", tx_id);
     }
@@ -68,8 +101,67 @@ impl DatabaseLogger {
     }
 
     pub fn log_query(&self, query: &str, duration_ms: u64) {
-        tracing::debug!("Query executed: {} ({}ms)", query, duration_ms);
+        self.log_query_duration(query, Duration::from_millis(duration_ms));
     }
+
+    /// Like `log_query`, keeping sub-millisecond precision for the stats
+    pub fn log_query_duration(&self, query: &str, duration: Duration) {
+        tracing::debug!("Query executed: {} ({}ms)", query, duration.as_millis());
+        if QUERY_STATS_ENABLED.load(Ordering::Relaxed) {
+            QUERY_COUNT.fetch_add(1, Ordering::Relaxed);
+            QUERY_TIME_MICROS.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Forwards the statement events sqlx emits under `sqlx::query` to
+/// `DatabaseLogger::log_query_duration`
+///
+/// Queries are issued directly on the pool throughout the codebase, so this
+/// is the one place every executed statement passes through.
+pub struct QueryLogLayer;
+
+impl QueryLogLayer {
+    pub const TARGET: &'static str = "sqlx::query";
+}
+
+impl<S: Subscriber> Layer<S> for QueryLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != Self::TARGET {
+            return;
+        }
+
+        let mut visitor = QueryEventVisitor::default();
+        event.record(&mut visitor);
+        if let Some(elapsed_secs) = visitor.elapsed_secs {
+            DatabaseLogger.log_query_duration(
+                &visitor.summary,
+                Duration::from_secs_f64(elapsed_secs.max(0.0)),
+            );
+        }
+    }
+}
+
+#[derive(Default)]
+struct QueryEventVisitor {
+    summary: String,
+    elapsed_secs: Option<f64>,
+}
+
+impl Visit for QueryEventVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = Some(value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "summary" {
+            self.summary = value.to_string();
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
 }
 
 pub struct Logger;
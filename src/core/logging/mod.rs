@@ -2,4 +2,4 @@
 
 pub mod logger;
 
-pub use logger::{DatabaseLogger, Logger, LoggerBuilder};
+pub use logger::{DatabaseLogger, Logger, LoggerBuilder, QueryLogLayer, QueryStats};
@@ -2,6 +2,7 @@
 // 인증, 데이터베이스, 설정, 로깅 등 시스템의 핵심 기능들
 
 pub mod auth;
+pub mod cache;
 pub mod config;
 pub mod database;
 pub mod logging;
@@ -9,6 +10,7 @@ pub mod ops;
 pub mod security;
 
 pub use auth::{AuthService, LoginRequest};
+pub use cache::{CacheScope, QueryCache};
 pub use config::{AppConfig, AuthConfig, DatabaseConfig, LoggingConfig};
 pub use database::{
     DatabaseConnection, DatabaseManager, PoolInfo, QueryLogger, TransactionManager,
@@ -0,0 +1,958 @@
+//! Logical per-module data snapshots.
+//!
+//! A snapshot is a plain tar archive that describes itself:
+//!
+//! ```text
+//! manifest.json            format/version, schema version, tables and checksums
+//! data/<table>.ndjson      one JSON object per row
+//! ```
+//!
+//! Rows are written with `row_to_json` and read back with
+//! `json_populate_recordset`, so a snapshot is independent of the PostgreSQL
+//! version and can be moved between environments or used to seed test
+//! databases.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool, Row};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::utils::error::{ErpError, ErpResult};
+
+pub const SNAPSHOT_FORMAT: &str = "erp-data-snapshot";
+pub const SNAPSHOT_VERSION: u32 = 1;
+pub const MANIFEST_ENTRY: &str = "manifest.json";
+
+/// Rows fetched per query while exporting
+const EXPORT_PAGE_SIZE: i64 = 1000;
+/// Rows sent per INSERT while importing
+const IMPORT_BATCH_SIZE: usize = 500;
+
+/// Exportable modules and their tables, in foreign-key dependency order.
+/// Users and audit events are deliberately not part of any module.
+pub const MODULES: &[(&str, &[&str])] = &[
+    ("inventory", &["products", "stock_movements"]),
+    ("customers", &["customers", "customer_addresses"]),
+    ("sales", &["sales_orders", "sales_order_items"]),
+    ("config", &["config_items"]),
+    ("reports", &["report_schedules", "report_schedule_runs"]),
+];
+
+pub fn module_names() -> Vec<&'static str> {
+    MODULES.iter().map(|(name, _)| *name).collect()
+}
+
+pub fn module_tables(module: &str) -> ErpResult<&'static [&'static str]> {
+    MODULES
+        .iter()
+        .find(|(name, _)| *name == module)
+        .map(|(_, tables)| *tables)
+        .ok_or_else(|| {
+            ErpError::validation(
+                "modules",
+                format!(
+                    "알 수 없는 모듈입니다: {} (사용 가능: {})",
+                    module,
+                    module_names().join(", ")
+                ),
+            )
+        })
+}
+
+/// Parses `inventory,customers` (or `all`) into module names in dependency order
+pub fn parse_modules(spec: &str) -> ErpResult<Vec<String>> {
+    let requested: Vec<&str> = spec
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect();
+    if requested.is_empty() {
+        return Err(ErpError::validation(
+            "modules",
+            "모듈을 하나 이상 지정하세요",
+        ));
+    }
+    if requested.contains(&"all") {
+        return Ok(module_names().into_iter().map(str::to_string).collect());
+    }
+    for name in &requested {
+        module_tables(name)?;
+    }
+
+    Ok(module_names()
+        .into_iter()
+        .filter(|name| requested.contains(name))
+        .map(str::to_string)
+        .collect())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnInfo {
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableManifest {
+    pub name: String,
+    pub module: String,
+    pub file: String,
+    pub primary_key: String,
+    pub columns: Vec<ColumnInfo>,
+    pub row_count: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub format: String,
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub app_version: String,
+    /// Latest applied migration of the source database, if tracked
+    pub schema_version: Option<String>,
+    pub modules: Vec<String>,
+    pub tables: Vec<TableManifest>,
+}
+
+impl SnapshotManifest {
+    /// Reads and validates the manifest, which must be the first archive entry
+    pub fn read(archive_path: &Path) -> ErpResult<Self> {
+        let mut archive = tar::Archive::new(File::open(archive_path)?);
+        let mut entries = archive.entries()?;
+        let mut entry = entries
+            .next()
+            .transpose()?
+            .ok_or_else(|| ErpError::validation("snapshot", "빈 아카이브입니다"))?;
+        Self::from_entry(&mut entry)
+    }
+
+    fn from_entry<R: Read>(entry: &mut tar::Entry<'_, R>) -> ErpResult<Self> {
+        if entry.path()?.to_string_lossy() != MANIFEST_ENTRY {
+            return Err(ErpError::validation(
+                "snapshot",
+                format!("첫 번째 항목이 {}가 아닙니다", MANIFEST_ENTRY),
+            ));
+        }
+        let manifest: SnapshotManifest = serde_json::from_reader(entry)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    pub fn validate(&self) -> ErpResult<()> {
+        if self.format != SNAPSHOT_FORMAT {
+            return Err(ErpError::validation(
+                "snapshot",
+                format!("지원하지 않는 아카이브 형식입니다: {}", self.format),
+            ));
+        }
+        if self.version == 0 || self.version > SNAPSHOT_VERSION {
+            return Err(ErpError::validation(
+                "snapshot",
+                format!(
+                    "지원하지 않는 스냅샷 버전입니다: {} (지원: {})",
+                    self.version, SNAPSHOT_VERSION
+                ),
+            ));
+        }
+
+        // Table names end up in SQL, so only the known ones are accepted
+        for table in &self.tables {
+            if !self.modules.contains(&table.module)
+                || !module_tables(&table.module)?.contains(&table.name.as_str())
+            {
+                return Err(ErpError::validation(
+                    "snapshot",
+                    format!(
+                        "모듈 {}에 속하지 않는 테이블입니다: {}",
+                        table.module, table.name
+                    ),
+                ));
+            }
+            if table.file != table_file(&table.name) {
+                return Err(ErpError::validation(
+                    "snapshot",
+                    format!("잘못된 데이터 파일 경로입니다: {}", table.file),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn total_rows(&self) -> u64 {
+        self.tables.iter().map(|t| t.row_count).sum()
+    }
+}
+
+/// What to do when an imported row collides with an existing one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Abort the whole import
+    Fail,
+    /// Keep the existing row
+    Skip,
+    /// Replace the existing row with the same primary key
+    Overwrite,
+}
+
+impl std::str::FromStr for ConflictPolicy {
+    type Err = ErpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fail" => Ok(ConflictPolicy::Fail),
+            "skip" => Ok(ConflictPolicy::Skip),
+            "overwrite" => Ok(ConflictPolicy::Overwrite),
+            _ => Err(ErpError::validation(
+                "on_conflict",
+                "사용 가능한 충돌 정책: fail, skip, overwrite",
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for ConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ConflictPolicy::Fail => "fail",
+            ConflictPolicy::Skip => "skip",
+            ConflictPolicy::Overwrite => "overwrite",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// Subset of the snapshot's modules; `None` imports everything
+    pub modules: Option<Vec<String>>,
+    pub on_conflict: ConflictPolicy,
+    /// Run the import and roll it back
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableImportResult {
+    pub table: String,
+    pub rows: u64,
+    /// Rows inserted or overwritten
+    pub written: u64,
+    /// Rows left untouched because of `ConflictPolicy::Skip`
+    pub skipped: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportReport {
+    pub tables: Vec<TableImportResult>,
+    /// Non-fatal differences between the snapshot and the target schema
+    pub warnings: Vec<String>,
+}
+
+pub struct DataExporter {
+    pool: PgPool,
+}
+
+impl DataExporter {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Writes the given modules to a snapshot archive at `output`
+    pub async fn export(&self, modules: &[String], output: &Path) -> ErpResult<SnapshotManifest> {
+        let staging = staging_dir("export");
+        fs::create_dir_all(&staging)?;
+        let result = self.export_with_staging(modules, output, &staging).await;
+        let _ = fs::remove_dir_all(&staging);
+        if result.is_err() {
+            let _ = fs::remove_file(output);
+        }
+        result
+    }
+
+    async fn export_with_staging(
+        &self,
+        modules: &[String],
+        output: &Path,
+        staging: &Path,
+    ) -> ErpResult<SnapshotManifest> {
+        // One snapshot of the whole database so related tables stay consistent
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+            .execute(&mut *tx)
+            .await?;
+
+        let schema_version = schema_version(&mut tx).await?;
+        let mut tables = Vec::new();
+        for module in modules {
+            for table in module_tables(module)? {
+                let columns = table_columns(&mut tx, table).await?;
+                if columns.is_empty() {
+                    return Err(ErpError::not_found("table", *table));
+                }
+                let primary_key = primary_key(&mut tx, table).await?;
+                let pk_type = columns
+                    .iter()
+                    .find(|c| c.info.name == primary_key)
+                    .map(|c| c.info.data_type.clone())
+                    .unwrap_or_default();
+
+                let staged = staging.join(format!("{}.ndjson", table));
+                let (row_count, sha256) =
+                    export_table(&mut tx, table, &primary_key, &pk_type, &staged).await?;
+                debug!("Exported {} rows from {}", row_count, table);
+
+                tables.push(TableManifest {
+                    name: table.to_string(),
+                    module: module.clone(),
+                    file: table_file(table),
+                    primary_key,
+                    columns: columns.into_iter().map(|c| c.info).collect(),
+                    row_count,
+                    sha256,
+                });
+            }
+        }
+        tx.rollback().await?;
+
+        let manifest = SnapshotManifest {
+            format: SNAPSHOT_FORMAT.to_string(),
+            version: SNAPSHOT_VERSION,
+            created_at: Utc::now(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version,
+            modules: modules.to_vec(),
+            tables,
+        };
+
+        if let Some(dir) = output.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let mut archive = tar::Builder::new(BufWriter::new(File::create(output)?));
+        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(manifest_json.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(manifest.created_at.timestamp().max(0) as u64);
+        header.set_cksum();
+        archive.append_data(&mut header, MANIFEST_ENTRY, manifest_json.as_slice())?;
+        for table in &manifest.tables {
+            archive.append_path_with_name(
+                staging.join(format!("{}.ndjson", table.name)),
+                &table.file,
+            )?;
+        }
+        archive.into_inner()?.flush()?;
+
+        info!(
+            "Exported {} rows from {} tables to {}",
+            manifest.total_rows(),
+            manifest.tables.len(),
+            output.display()
+        );
+        Ok(manifest)
+    }
+}
+
+pub struct DataImporter {
+    pool: PgPool,
+}
+
+impl DataImporter {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Imports a snapshot in a single transaction. Checksums are verified
+    /// before anything is written.
+    pub async fn import(
+        &self,
+        archive_path: &Path,
+        options: &ImportOptions,
+    ) -> ErpResult<ImportReport> {
+        let staging = staging_dir("import");
+        fs::create_dir_all(&staging)?;
+        let result = self
+            .import_with_staging(archive_path, options, &staging)
+            .await;
+        let _ = fs::remove_dir_all(&staging);
+        result
+    }
+
+    async fn import_with_staging(
+        &self,
+        archive_path: &Path,
+        options: &ImportOptions,
+        staging: &Path,
+    ) -> ErpResult<ImportReport> {
+        let (manifest, selected) = extract_snapshot(archive_path, options, staging)?;
+
+        let mut tx = self.pool.begin().await?;
+        let mut warnings = Vec::new();
+        if let (Some(source), Some(target)) =
+            (&manifest.schema_version, schema_version(&mut tx).await?)
+        {
+            if *source != target {
+                warnings.push(format!(
+                    "스키마 버전이 다릅니다 (스냅샷 {}, 대상 {})",
+                    source, target
+                ));
+            }
+        }
+
+        // Check every table before writing any rows
+        let mut plans = Vec::new();
+        for table in &selected {
+            let target_columns = table_columns(&mut tx, &table.name).await?;
+            plans.push(plan_table(table, &target_columns, &mut warnings)?);
+        }
+
+        let mut results = Vec::new();
+        for (table, plan) in selected.iter().zip(&plans) {
+            let sql = insert_sql(&table.name, &table.primary_key, plan, options.on_conflict);
+            let reader =
+                BufReader::new(File::open(staging.join(format!("{}.ndjson", table.name)))?);
+            let mut result = TableImportResult {
+                table: table.name.clone(),
+                ..Default::default()
+            };
+
+            let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+            for (index, line) in reader.lines().enumerate() {
+                let line = line?;
+                if serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&line)
+                    .is_err()
+                {
+                    return Err(ErpError::validation(
+                        "snapshot",
+                        format!("{} {}번째 행이 JSON 객체가 아닙니다", table.file, index + 1),
+                    ));
+                }
+                batch.push(line);
+                if batch.len() == IMPORT_BATCH_SIZE {
+                    write_batch(&mut tx, &sql, &mut batch, &table.name, &mut result).await?;
+                }
+            }
+            if !batch.is_empty() {
+                write_batch(&mut tx, &sql, &mut batch, &table.name, &mut result).await?;
+            }
+            debug!(
+                "Imported {}: {} written, {} skipped",
+                table.name, result.written, result.skipped
+            );
+            results.push(result);
+        }
+
+        if options.dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+            info!(
+                "Imported {} rows into {} tables from {}",
+                results.iter().map(|r| r.written).sum::<u64>(),
+                results.len(),
+                archive_path.display()
+            );
+        }
+
+        Ok(ImportReport {
+            tables: results,
+            warnings,
+        })
+    }
+}
+
+/// Reads the manifest, picks the tables to import and extracts their data
+/// files into `staging`, verifying row counts and checksums on the way.
+fn extract_snapshot(
+    archive_path: &Path,
+    options: &ImportOptions,
+    staging: &Path,
+) -> ErpResult<(SnapshotManifest, Vec<TableManifest>)> {
+    let mut archive = tar::Archive::new(File::open(archive_path)?);
+    let mut entries = archive.entries()?;
+    let mut first = entries
+        .next()
+        .transpose()?
+        .ok_or_else(|| ErpError::validation("snapshot", "빈 아카이브입니다"))?;
+    let manifest = SnapshotManifest::from_entry(&mut first)?;
+    drop(first);
+
+    let selected: Vec<TableManifest> = match &options.modules {
+        Some(modules) => {
+            if let Some(missing) = modules.iter().find(|m| !manifest.modules.contains(m)) {
+                return Err(ErpError::validation(
+                    "modules",
+                    format!("스냅샷에 {} 모듈이 없습니다", missing),
+                ));
+            }
+            manifest
+                .tables
+                .iter()
+                .filter(|t| modules.contains(&t.module))
+                .cloned()
+                .collect()
+        }
+        None => manifest.tables.clone(),
+    };
+
+    let mut seen = HashSet::new();
+    for entry in entries {
+        let mut entry = entry?;
+        // Archives repacked with standard tools may carry directory entries
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let Some(table) = manifest.tables.iter().find(|t| t.file == path) else {
+            return Err(ErpError::validation(
+                "snapshot",
+                format!("매니페스트에 없는 항목입니다: {}", path),
+            ));
+        };
+        if !selected.iter().any(|t| t.name == table.name) {
+            continue;
+        }
+
+        let mut hasher = Sha256::new();
+        let mut rows = 0u64;
+        let mut output = BufWriter::new(File::create(
+            staging.join(format!("{}.ndjson", table.name)),
+        )?);
+        let mut reader = BufReader::new(&mut entry);
+        let mut line = Vec::new();
+        while reader.read_until(b'\n', &mut line)? > 0 {
+            hasher.update(&line);
+            if line.iter().any(|b| !b.is_ascii_whitespace()) {
+                rows += 1;
+            }
+            output.write_all(&line)?;
+            line.clear();
+        }
+        output.flush()?;
+
+        let checksum = format!("{:x}", hasher.finalize());
+        if checksum != table.sha256 || rows != table.row_count {
+            return Err(ErpError::validation(
+                "snapshot",
+                format!(
+                    "{} 데이터가 매니페스트와 일치하지 않습니다 (손상된 아카이브)",
+                    table.file
+                ),
+            ));
+        }
+        seen.insert(table.name.clone());
+    }
+
+    if let Some(missing) = selected.iter().find(|t| !seen.contains(&t.name)) {
+        return Err(ErpError::validation(
+            "snapshot",
+            format!("아카이브에 {} 데이터가 없습니다", missing.file),
+        ));
+    }
+    Ok((manifest, selected))
+}
+
+/// Columns to copy for one table
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TablePlan {
+    columns: Vec<String>,
+}
+
+/// Matches snapshot columns against the target table. Columns the target
+/// lacks are an error; columns the snapshot lacks get their defaults.
+fn plan_table(
+    table: &TableManifest,
+    target: &[TargetColumn],
+    warnings: &mut Vec<String>,
+) -> ErpResult<TablePlan> {
+    if target.is_empty() {
+        return Err(ErpError::not_found("table", &table.name));
+    }
+
+    let missing: Vec<&str> = table
+        .columns
+        .iter()
+        .filter(|c| !target.iter().any(|t| t.info.name == c.name))
+        .map(|c| c.name.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(ErpError::validation(
+            "snapshot",
+            format!(
+                "{} 테이블에 없는 컬럼이 있습니다: {} (대상 데이터베이스 마이그레이션 확인)",
+                table.name,
+                missing.join(", ")
+            ),
+        ));
+    }
+
+    let mut columns = Vec::new();
+    for column in &table.columns {
+        let target_column = target.iter().find(|t| t.info.name == column.name);
+        match target_column {
+            Some(t) if t.generated => continue,
+            Some(t) if t.info.data_type != column.data_type => warnings.push(format!(
+                "{}.{} 타입이 다릅니다 ({} → {})",
+                table.name, column.name, column.data_type, t.info.data_type
+            )),
+            _ => {}
+        }
+        columns.push(column.name.clone());
+    }
+    for t in target.iter().filter(|t| !t.generated) {
+        if !columns.contains(&t.info.name) && !t.info.nullable && !t.has_default {
+            return Err(ErpError::validation(
+                "snapshot",
+                format!(
+                    "스냅샷에 필수 컬럼 {}.{} 값이 없습니다",
+                    table.name, t.info.name
+                ),
+            ));
+        }
+    }
+
+    Ok(TablePlan { columns })
+}
+
+fn insert_sql(table: &str, primary_key: &str, plan: &TablePlan, policy: ConflictPolicy) -> String {
+    let columns = plan
+        .columns
+        .iter()
+        .map(|c| quote_ident(c))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let conflict = match policy {
+        ConflictPolicy::Fail => String::new(),
+        ConflictPolicy::Skip => " ON CONFLICT DO NOTHING".to_string(),
+        ConflictPolicy::Overwrite => {
+            let updates: Vec<String> = plan
+                .columns
+                .iter()
+                .filter(|c| *c != primary_key)
+                .map(|c| format!("{0} = EXCLUDED.{0}", quote_ident(c)))
+                .collect();
+            if updates.is_empty() {
+                " ON CONFLICT DO NOTHING".to_string()
+            } else {
+                format!(
+                    " ON CONFLICT ({}) DO UPDATE SET {}",
+                    quote_ident(primary_key),
+                    updates.join(", ")
+                )
+            }
+        }
+    };
+
+    format!(
+        "INSERT INTO {table} ({columns}) SELECT {columns} FROM json_populate_recordset(NULL::{table}, $1::json){conflict}",
+        table = quote_ident(table),
+        columns = columns,
+        conflict = conflict
+    )
+}
+
+async fn write_batch(
+    conn: &mut PgConnection,
+    sql: &str,
+    batch: &mut Vec<String>,
+    table: &str,
+    result: &mut TableImportResult,
+) -> ErpResult<()> {
+    let rows = batch.len() as u64;
+    let payload = format!("[{}]", batch.join(","));
+    let written = sqlx::query(sql)
+        .bind(payload)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db) if db.is_unique_violation() => {
+                ErpError::conflict(format!(
+                "{} 테이블에 이미 같은 행이 있습니다 (--on-conflict skip 또는 overwrite 사용): {}",
+                table,
+                db.message()
+            ))
+            }
+            other => ErpError::Database(other),
+        })?
+        .rows_affected();
+
+    result.rows += rows;
+    result.written += written;
+    result.skipped += rows.saturating_sub(written);
+    batch.clear();
+    Ok(())
+}
+
+async fn export_table(
+    conn: &mut PgConnection,
+    table: &str,
+    primary_key: &str,
+    pk_type: &str,
+    output: &Path,
+) -> ErpResult<(u64, String)> {
+    // Keyset pagination keeps every page cheap regardless of table size
+    let sql = format!(
+        "SELECT {pk}::text AS cursor, row_to_json(t)::text AS data FROM {table} t \
+         WHERE $1::text IS NULL OR {pk} > CAST($1 AS {pk_type}) ORDER BY {pk} LIMIT $2",
+        pk = quote_ident(primary_key),
+        table = quote_ident(table),
+        pk_type = quote_ident(pk_type),
+    );
+
+    let mut writer = BufWriter::new(File::create(output)?);
+    let mut hasher = Sha256::new();
+    let mut cursor: Option<String> = None;
+    let mut rows = 0u64;
+    loop {
+        let page = sqlx::query(&sql)
+            .bind(&cursor)
+            .bind(EXPORT_PAGE_SIZE)
+            .fetch_all(&mut *conn)
+            .await?;
+        for row in &page {
+            let data: String = row.try_get("data")?;
+            let line = format!("{}\n", data);
+            hasher.update(line.as_bytes());
+            writer.write_all(line.as_bytes())?;
+        }
+        rows += page.len() as u64;
+        if (page.len() as i64) < EXPORT_PAGE_SIZE {
+            break;
+        }
+        cursor = page.last().map(|row| row.try_get("cursor")).transpose()?;
+    }
+    writer.flush()?;
+
+    Ok((rows, format!("{:x}", hasher.finalize())))
+}
+
+#[derive(Debug, Clone)]
+struct TargetColumn {
+    info: ColumnInfo,
+    has_default: bool,
+    generated: bool,
+}
+
+async fn table_columns(conn: &mut PgConnection, table: &str) -> ErpResult<Vec<TargetColumn>> {
+    let rows = sqlx::query(
+        "SELECT column_name::text AS name, udt_name::text AS data_type, \
+                is_nullable = 'YES' AS nullable, column_default IS NOT NULL AS has_default, \
+                (is_generated = 'ALWAYS' OR is_identity = 'YES') AS generated \
+         FROM information_schema.columns \
+         WHERE table_schema = current_schema() AND table_name = $1 \
+         ORDER BY ordinal_position",
+    )
+    .bind(table)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(TargetColumn {
+                info: ColumnInfo {
+                    name: row.try_get("name")?,
+                    data_type: row.try_get("data_type")?,
+                    nullable: row.try_get("nullable")?,
+                },
+                has_default: row.try_get("has_default")?,
+                generated: row.try_get("generated")?,
+            })
+        })
+        .collect()
+}
+
+async fn primary_key(conn: &mut PgConnection, table: &str) -> ErpResult<String> {
+    let columns: Vec<String> = sqlx::query_scalar(
+        "SELECT a.attname::text \
+         FROM pg_index i \
+         JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey) \
+         WHERE i.indrelid = to_regclass($1) AND i.indisprimary",
+    )
+    .bind(table)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    match columns.as_slice() {
+        [column] => Ok(column.clone()),
+        _ => Err(ErpError::validation(
+            "snapshot",
+            format!("{} 테이블은 단일 컬럼 기본 키가 필요합니다", table),
+        )),
+    }
+}
+
+/// Latest applied migration, or `None` when migrations are not tracked
+async fn schema_version(conn: &mut PgConnection) -> ErpResult<Option<String>> {
+    let tracked: bool = sqlx::query_scalar("SELECT to_regclass('schema_migrations') IS NOT NULL")
+        .fetch_one(&mut *conn)
+        .await?;
+    if !tracked {
+        return Ok(None);
+    }
+
+    let version =
+        sqlx::query_scalar("SELECT version FROM schema_migrations ORDER BY version DESC LIMIT 1")
+            .fetch_optional(&mut *conn)
+            .await?;
+    if version.is_none() {
+        warn!("schema_migrations is empty");
+    }
+    Ok(version)
+}
+
+fn table_file(table: &str) -> String {
+    format!("data/{}.ndjson", table)
+}
+
+fn staging_dir(purpose: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("erp_{}_{}", purpose, Uuid::new_v4()))
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str, nullable: bool) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            nullable,
+        }
+    }
+
+    fn target(info: ColumnInfo, has_default: bool) -> TargetColumn {
+        TargetColumn {
+            info,
+            has_default,
+            generated: false,
+        }
+    }
+
+    fn manifest() -> SnapshotManifest {
+        SnapshotManifest {
+            format: SNAPSHOT_FORMAT.to_string(),
+            version: SNAPSHOT_VERSION,
+            created_at: Utc::now(),
+            app_version: "0.1.0".to_string(),
+            schema_version: Some("016".to_string()),
+            modules: vec!["inventory".to_string()],
+            tables: vec![TableManifest {
+                name: "products".to_string(),
+                module: "inventory".to_string(),
+                file: table_file("products"),
+                primary_key: "id".to_string(),
+                columns: vec![
+                    column("id", "uuid", false),
+                    column("name", "varchar", false),
+                ],
+                row_count: 0,
+                sha256: String::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_parse_modules_uses_dependency_order() {
+        assert_eq!(
+            parse_modules("sales, inventory,customers,sales").unwrap(),
+            vec!["inventory", "customers", "sales"]
+        );
+        assert_eq!(parse_modules("all").unwrap().len(), MODULES.len());
+        assert!(parse_modules("inventory,users").is_err());
+        assert!(parse_modules(" , ").is_err());
+    }
+
+    #[test]
+    fn test_manifest_validation_rejects_unknown_tables() {
+        assert!(manifest().validate().is_ok());
+
+        let mut newer = manifest();
+        newer.version = SNAPSHOT_VERSION + 1;
+        assert!(newer.validate().is_err());
+
+        let mut foreign = manifest();
+        foreign.tables[0].name = "users".to_string();
+        foreign.tables[0].file = table_file("users");
+        assert!(foreign.validate().is_err());
+
+        let mut traversal = manifest();
+        traversal.tables[0].file = "../products.ndjson".to_string();
+        assert!(traversal.validate().is_err());
+    }
+
+    #[test]
+    fn test_plan_table_checks_target_columns() {
+        let table = &manifest().tables[0];
+        let mut warnings = Vec::new();
+
+        // Extra target columns are fine when they have defaults or are nullable
+        let plan = plan_table(
+            table,
+            &[
+                target(column("id", "uuid", false), false),
+                target(column("name", "text", false), false),
+                target(column("created_at", "timestamptz", false), true),
+                target(column("notes", "text", true), false),
+            ],
+            &mut warnings,
+        )
+        .unwrap();
+        assert_eq!(plan.columns, vec!["id", "name"]);
+        assert_eq!(warnings.len(), 1);
+
+        // The target lacks a snapshot column
+        assert!(plan_table(
+            table,
+            &[target(column("id", "uuid", false), false)],
+            &mut warnings
+        )
+        .is_err());
+
+        // The target requires a column the snapshot does not have
+        assert!(plan_table(
+            table,
+            &[
+                target(column("id", "uuid", false), false),
+                target(column("name", "varchar", false), false),
+                target(column("sku", "varchar", false), false),
+            ],
+            &mut warnings
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_insert_sql_per_conflict_policy() {
+        let plan = TablePlan {
+            columns: vec!["id".to_string(), "name".to_string()],
+        };
+
+        let fail = insert_sql("products", "id", &plan, ConflictPolicy::Fail);
+        assert_eq!(
+            fail,
+            "INSERT INTO \"products\" (\"id\", \"name\") SELECT \"id\", \"name\" \
+             FROM json_populate_recordset(NULL::\"products\", $1::json)"
+        );
+        assert!(insert_sql("products", "id", &plan, ConflictPolicy::Skip)
+            .ends_with("ON CONFLICT DO NOTHING"));
+        assert!(
+            insert_sql("products", "id", &plan, ConflictPolicy::Overwrite)
+                .ends_with("ON CONFLICT (\"id\") DO UPDATE SET \"name\" = EXCLUDED.\"name\"")
+        );
+        assert_eq!(
+            "OVERWRITE".parse::<ConflictPolicy>().unwrap(),
+            ConflictPolicy::Overwrite
+        );
+        assert!("merge".parse::<ConflictPolicy>().is_err());
+    }
+}
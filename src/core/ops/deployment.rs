@@ -930,7 +930,7 @@ impl DeploymentRepository for MockDeploymentRepository {
     ) -> ErpResult<Vec<DeploymentRecord>> {
         let deployments = self.deployments.lock().unwrap();
         let mut sorted_deployments = deployments.clone();
-        sorted_deployments.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        sorted_deployments.sort_by_key(|x| std::cmp::Reverse(x.timestamp));
 
         if let Some(limit) = limit {
             sorted_deployments.truncate(limit);
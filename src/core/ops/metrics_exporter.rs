@@ -0,0 +1,661 @@
+//! Prometheus exporter for the ERP process.
+//!
+//! `MetricsExporter` serves `GET /metrics` in the text exposition format
+//! (version 0.0.4). Every scrape combines:
+//!
+//! - `ApplicationMetrics` for the exporter process itself,
+//! - `SecurityMetrics` and `BusinessMetrics` supplied by a `MetricsSource`,
+//! - the `AggregatedMetrics` of the exporter's own HTTP requests, tracked by
+//!   a `PerformanceMonitor` that is never flushed so its totals only grow.
+//!
+//! A failing source does not fail the scrape: its families are left out and
+//! `erp_collector_up{collector="..."}` drops to 0.
+
+use chrono::Utc;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use sysinfo::{ProcessExt, System, SystemExt};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::{timeout, Duration};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use super::performance::{
+    AggregatedMetrics, MockPerformanceRepository, PerformanceConfig, PerformanceMonitor,
+};
+use crate::core::security::monitor::{ApplicationMetrics, SecurityMetrics};
+use crate::utils::error::{ErpError, ErpResult};
+
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Business gauges derived from the ERP modules
+#[derive(Debug, Clone, Default)]
+pub struct BusinessMetrics {
+    pub orders_by_status: Vec<(String, i64)>,
+    pub open_orders: i64,
+    pub low_stock_products: i64,
+    pub out_of_stock_products: i64,
+    pub total_stock_value: Decimal,
+    pub outstanding_receivables: Decimal,
+    pub customers_with_outstanding_balance: i64,
+}
+
+/// Supplies the metrics the exporter cannot observe in-process
+#[async_trait::async_trait]
+pub trait MetricsSource: Send + Sync {
+    /// Connections currently open in the database pool
+    fn active_connections(&self) -> u32;
+    async fn security_metrics(&self) -> ErpResult<SecurityMetrics>;
+    async fn business_metrics(&self) -> ErpResult<BusinessMetrics>;
+}
+
+/// Everything exposed by a single scrape
+#[derive(Debug, Clone)]
+pub struct MetricsSnapshot {
+    pub application: ApplicationMetrics,
+    pub security: Option<SecurityMetrics>,
+    pub business: Option<BusinessMetrics>,
+    pub endpoints: Vec<AggregatedMetrics>,
+}
+
+impl MetricsSnapshot {
+    pub fn encode(&self) -> String {
+        let mut encoder = PrometheusEncoder::new();
+        let app = &self.application;
+
+        encoder
+            .gauge(
+                "erp_active_connections",
+                "Open database connections held by the exporter",
+                app.active_connections as f64,
+            )
+            .counter(
+                "erp_requests_total",
+                "Requests handled by the exporter",
+                app.total_requests as f64,
+            )
+            .counter(
+                "erp_requests_failed_total",
+                "Requests answered with a 4xx or 5xx status",
+                app.failed_requests as f64,
+            )
+            .gauge(
+                "erp_request_duration_avg_milliseconds",
+                "Average request duration",
+                app.avg_response_time_ms,
+            )
+            .gauge(
+                "erp_request_error_rate_percent",
+                "Share of failed requests",
+                app.error_rate,
+            )
+            .gauge(
+                "erp_memory_usage_megabytes",
+                "Resident memory of the exporter process",
+                app.memory_usage_mb as f64,
+            )
+            .gauge(
+                "erp_virtual_memory_megabytes",
+                "Virtual memory of the exporter process",
+                app.heap_size_mb as f64,
+            )
+            .gauge(
+                "erp_threads",
+                "Threads of the exporter process",
+                app.thread_count as f64,
+            );
+
+        if !self.endpoints.is_empty() {
+            encoder.family(
+                "erp_http_requests_total",
+                "counter",
+                "HTTP requests by endpoint",
+            );
+            for endpoint in &self.endpoints {
+                encoder.sample(
+                    "erp_http_requests_total",
+                    &endpoint_labels(endpoint),
+                    endpoint.total_requests as f64,
+                );
+            }
+            encoder.family(
+                "erp_http_requests_failed_total",
+                "counter",
+                "Failed HTTP requests by endpoint",
+            );
+            for endpoint in &self.endpoints {
+                encoder.sample(
+                    "erp_http_requests_failed_total",
+                    &endpoint_labels(endpoint),
+                    endpoint.failed_requests as f64,
+                );
+            }
+            encoder.family(
+                "erp_http_request_duration_milliseconds",
+                "summary",
+                "HTTP request duration by endpoint",
+            );
+            for endpoint in &self.endpoints {
+                let labels = endpoint_labels(endpoint);
+                for (quantile, value) in [
+                    ("0.5", endpoint.p50_response_time_ms),
+                    ("0.95", endpoint.p95_response_time_ms),
+                    ("0.99", endpoint.p99_response_time_ms),
+                ] {
+                    let mut quantile_labels = labels.clone();
+                    quantile_labels.push(("quantile", quantile.to_string()));
+                    encoder.sample(
+                        "erp_http_request_duration_milliseconds",
+                        &quantile_labels,
+                        value,
+                    );
+                }
+                encoder
+                    .sample(
+                        "erp_http_request_duration_milliseconds_sum",
+                        &labels,
+                        endpoint.avg_response_time_ms * endpoint.total_requests as f64,
+                    )
+                    .sample(
+                        "erp_http_request_duration_milliseconds_count",
+                        &labels,
+                        endpoint.total_requests as f64,
+                    );
+            }
+        }
+
+        if let Some(security) = &self.security {
+            encoder
+                .gauge(
+                    "erp_security_failed_login_attempts",
+                    "Failed logins in the last hour",
+                    security.failed_login_attempts as f64,
+                )
+                .gauge(
+                    "erp_security_blocked_ips",
+                    "IP addresses currently blocked",
+                    security.blocked_ips as f64,
+                )
+                .gauge(
+                    "erp_security_rate_limit_violations",
+                    "Rate limit violations in the last hour",
+                    security.rate_limit_violations as f64,
+                )
+                .gauge(
+                    "erp_security_violations",
+                    "Security violations in the last hour",
+                    security.security_violations as f64,
+                )
+                .gauge(
+                    "erp_security_active_sessions",
+                    "Active user sessions",
+                    security.active_sessions as f64,
+                )
+                .gauge(
+                    "erp_security_suspicious_activities",
+                    "Critical audit events in the last hour",
+                    security.suspicious_activities as f64,
+                );
+        }
+
+        if let Some(business) = &self.business {
+            encoder.family("erp_sales_orders", "gauge", "Sales orders by status");
+            for (status, count) in &business.orders_by_status {
+                encoder.sample(
+                    "erp_sales_orders",
+                    &[("status", status.clone())],
+                    *count as f64,
+                );
+            }
+            encoder
+                .gauge(
+                    "erp_sales_open_orders",
+                    "Orders that are pending, confirmed, processing or shipped",
+                    business.open_orders as f64,
+                )
+                .gauge(
+                    "erp_inventory_low_stock_products",
+                    "Products at or below their minimum stock level",
+                    business.low_stock_products as f64,
+                )
+                .gauge(
+                    "erp_inventory_out_of_stock_products",
+                    "Products with no stock left",
+                    business.out_of_stock_products as f64,
+                )
+                .gauge(
+                    "erp_inventory_stock_value",
+                    "Total value of stock on hand at selling price",
+                    decimal_to_f64(business.total_stock_value),
+                )
+                .gauge(
+                    "erp_receivables_outstanding",
+                    "Sum of positive customer balances",
+                    decimal_to_f64(business.outstanding_receivables),
+                )
+                .gauge(
+                    "erp_customers_with_outstanding_balance",
+                    "Customers owing a positive balance",
+                    business.customers_with_outstanding_balance as f64,
+                );
+        }
+
+        encoder.family(
+            "erp_collector_up",
+            "gauge",
+            "Whether the last collection from a source succeeded",
+        );
+        for (collector, up) in [
+            ("security", self.security.is_some()),
+            ("business", self.business.is_some()),
+        ] {
+            encoder.sample(
+                "erp_collector_up",
+                &[("collector", collector.to_string())],
+                if up { 1.0 } else { 0.0 },
+            );
+        }
+
+        encoder.finish()
+    }
+}
+
+fn endpoint_labels(endpoint: &AggregatedMetrics) -> Vec<(&'static str, String)> {
+    vec![
+        ("endpoint", endpoint.endpoint.clone()),
+        ("method", endpoint.method.clone()),
+    ]
+}
+
+fn decimal_to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Writes metric families in the Prometheus text exposition format
+#[derive(Debug, Default)]
+pub struct PrometheusEncoder {
+    output: String,
+    declared: HashSet<String>,
+}
+
+impl PrometheusEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emits the HELP and TYPE lines; samples of the family must follow
+    pub fn family(&mut self, name: &str, kind: &str, help: &str) -> &mut Self {
+        if self.declared.insert(name.to_string()) {
+            let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+            let _ = writeln!(self.output, "# HELP {} {}", name, help);
+            let _ = writeln!(self.output, "# TYPE {} {}", name, kind);
+        }
+        self
+    }
+
+    pub fn sample(&mut self, name: &str, labels: &[(&str, String)], value: f64) -> &mut Self {
+        self.output.push_str(name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+                .collect();
+            let _ = write!(self.output, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(self.output, " {}", format_value(value));
+        self
+    }
+
+    pub fn gauge(&mut self, name: &str, help: &str, value: f64) -> &mut Self {
+        self.family(name, "gauge", help).sample(name, &[], value)
+    }
+
+    pub fn counter(&mut self, name: &str, help: &str, value: f64) -> &mut Self {
+        self.family(name, "counter", help).sample(name, &[], value)
+    }
+
+    pub fn finish(self) -> String {
+        self.output
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// HTTP endpoint exposing `MetricsSnapshot`s
+pub struct MetricsExporter {
+    source: Arc<dyn MetricsSource>,
+    performance: PerformanceMonitor,
+    system: Mutex<System>,
+}
+
+impl MetricsExporter {
+    pub fn new(source: Arc<dyn MetricsSource>) -> Self {
+        // Raw samples are not kept and aggregates are read in place, so the
+        // repository is never written to.
+        let performance = PerformanceMonitor::new(
+            PerformanceConfig {
+                sample_rate: 0.0,
+                ..PerformanceConfig::default()
+            },
+            Arc::new(MockPerformanceRepository::new()),
+        );
+
+        Self {
+            source,
+            performance,
+            system: Mutex::new(System::new()),
+        }
+    }
+
+    /// Accepts connections until the listener fails
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> ErpResult<()> {
+        loop {
+            let (stream, peer) = listener
+                .accept()
+                .await
+                .map_err(|e| ErpError::internal(format!("Failed to accept connection: {}", e)))?;
+            let exporter = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = exporter.handle_connection(stream).await {
+                    debug!("Metrics connection from {} failed: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    pub async fn snapshot(&self) -> MetricsSnapshot {
+        let endpoints = self.performance.current_aggregates().unwrap_or_else(|e| {
+            warn!("Failed to read request aggregates: {}", e);
+            Vec::new()
+        });
+        let security = self
+            .source
+            .security_metrics()
+            .await
+            .map_err(|e| warn!("Failed to collect security metrics: {}", e))
+            .ok();
+        let business = self
+            .source
+            .business_metrics()
+            .await
+            .map_err(|e| warn!("Failed to collect business metrics: {}", e))
+            .ok();
+
+        MetricsSnapshot {
+            application: self.application_metrics(&endpoints),
+            security,
+            business,
+            endpoints,
+        }
+    }
+
+    fn application_metrics(&self, endpoints: &[AggregatedMetrics]) -> ApplicationMetrics {
+        let total_requests: u64 = endpoints.iter().map(|e| e.total_requests).sum();
+        let failed_requests: u64 = endpoints.iter().map(|e| e.failed_requests).sum();
+        let total_time: f64 = endpoints
+            .iter()
+            .map(|e| e.avg_response_time_ms * e.total_requests as f64)
+            .sum();
+
+        let mut metrics = ApplicationMetrics {
+            timestamp: Utc::now(),
+            active_connections: self.source.active_connections(),
+            total_requests,
+            failed_requests,
+            ..ApplicationMetrics::default()
+        };
+        if total_requests > 0 {
+            metrics.avg_response_time_ms = total_time / total_requests as f64;
+            metrics.error_rate = failed_requests as f64 / total_requests as f64 * 100.0;
+        }
+
+        if let Ok(pid) = sysinfo::get_current_pid() {
+            let mut system = self.system.lock().unwrap();
+            if system.refresh_process(pid) {
+                if let Some(process) = system.process(pid) {
+                    metrics.memory_usage_mb = process.memory() / 1024 / 1024;
+                    metrics.heap_size_mb = process.virtual_memory() / 1024 / 1024;
+                }
+            }
+        }
+        if let Ok(tasks) = std::fs::read_dir("/proc/self/task") {
+            metrics.thread_count = tasks.count() as u32;
+        }
+
+        metrics
+    }
+
+    async fn handle_connection(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let request = match timeout(REQUEST_TIMEOUT, read_request_head(&mut stream)).await {
+            Ok(request) => request?,
+            Err(_) => return Ok(()),
+        };
+        let mut parts = request.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let target = parts.next().unwrap_or_default();
+        let path = target.split('?').next().unwrap_or_default();
+
+        // Unknown paths share one label value to keep cardinality bounded
+        let endpoint = match path {
+            "/" | "/metrics" => path.to_string(),
+            _ => "other".to_string(),
+        };
+        let request_id = Uuid::new_v4().to_string();
+        let _ = self
+            .performance
+            .start_request(request_id.clone(), endpoint, method.clone(), None, None)
+            .await;
+
+        let (status, reason, content_type, body) = match (method.as_str(), path) {
+            ("GET" | "HEAD", "/metrics") => {
+                (200, "OK", CONTENT_TYPE, self.snapshot().await.encode())
+            }
+            ("GET" | "HEAD", "/") => (
+                200,
+                "OK",
+                "text/plain; charset=utf-8",
+                "ERP metrics exporter - scrape /metrics\n".to_string(),
+            ),
+            (_, "/" | "/metrics") => (
+                405,
+                "Method Not Allowed",
+                "text/plain; charset=utf-8",
+                "Method not allowed\n".to_string(),
+            ),
+            _ => (
+                404,
+                "Not Found",
+                "text/plain; charset=utf-8",
+                "Not found\n".to_string(),
+            ),
+        };
+
+        let mut response = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            status,
+            reason,
+            content_type,
+            body.len()
+        );
+        if method != "HEAD" {
+            response.push_str(&body);
+        }
+        let result = stream.write_all(response.as_bytes()).await;
+
+        let _ = self
+            .performance
+            .end_request(
+                request_id,
+                status,
+                request.len() as u64,
+                response.len() as u64,
+                None,
+            )
+            .await;
+
+        result?;
+        stream.shutdown().await
+    }
+}
+
+/// Reads the request line and headers; bodies are never expected
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "request header too large",
+            ));
+        }
+    }
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StubSource {
+        business_available: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl MetricsSource for StubSource {
+        fn active_connections(&self) -> u32 {
+            3
+        }
+
+        async fn security_metrics(&self) -> ErpResult<SecurityMetrics> {
+            Ok(SecurityMetrics {
+                failed_login_attempts: 4,
+                ..SecurityMetrics::default()
+            })
+        }
+
+        async fn business_metrics(&self) -> ErpResult<BusinessMetrics> {
+            if !self.business_available {
+                return Err(ErpError::internal("database unavailable"));
+            }
+            Ok(BusinessMetrics {
+                orders_by_status: vec![("pending".to_string(), 2), ("delivered".to_string(), 5)],
+                open_orders: 2,
+                low_stock_products: 1,
+                total_stock_value: Decimal::new(123450, 2),
+                outstanding_receivables: Decimal::new(5000, 0),
+                customers_with_outstanding_balance: 1,
+                ..BusinessMetrics::default()
+            })
+        }
+    }
+
+    async fn get(addr: &str, path: &str) -> reqwest::Response {
+        reqwest::get(format!("http://{}{}", addr, path))
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_encoder_declares_families_once_and_escapes_labels() {
+        let mut encoder = PrometheusEncoder::new();
+        encoder
+            .family("erp_test", "gauge", "Help with \\ and\nnewline")
+            .sample(
+                "erp_test",
+                &[("name", "a \"quoted\"\nvalue".to_string())],
+                1.5,
+            )
+            .family("erp_test", "gauge", "ignored")
+            .sample("erp_test", &[], f64::NAN)
+            .counter("erp_test_total", "Counter", 2.0);
+
+        assert_eq!(
+            encoder.finish(),
+            "# HELP erp_test Help with \\\\ and\\nnewline\n\
+             # TYPE erp_test gauge\n\
+             erp_test{name=\"a \\\"quoted\\\"\\nvalue\"} 1.5\n\
+             erp_test NaN\n\
+             # HELP erp_test_total Counter\n\
+             # TYPE erp_test_total counter\n\
+             erp_test_total 2\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_source_is_reported_and_omitted() {
+        let exporter = MetricsExporter::new(Arc::new(StubSource {
+            business_available: false,
+        }));
+        let text = exporter.snapshot().await.encode();
+
+        assert!(text.contains("erp_collector_up{collector=\"security\"} 1\n"));
+        assert!(text.contains("erp_collector_up{collector=\"business\"} 0\n"));
+        assert!(text.contains("erp_security_failed_login_attempts 4\n"));
+        assert!(!text.contains("erp_sales_open_orders"));
+    }
+
+    #[tokio::test]
+    async fn test_serves_metrics_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let exporter = Arc::new(MetricsExporter::new(Arc::new(StubSource {
+            business_available: true,
+        })));
+        tokio::spawn(exporter.serve(listener));
+
+        assert_eq!(get(&addr, "/nope").await.status(), 404);
+
+        let response = get(&addr, "/metrics").await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers()["content-type"].to_str().unwrap(),
+            CONTENT_TYPE
+        );
+        let text = response.text().await.unwrap();
+        assert!(text.contains("erp_active_connections 3\n"));
+        assert!(text.contains("erp_sales_orders{status=\"pending\"} 2\n"));
+        assert!(text.contains("erp_sales_open_orders 2\n"));
+        assert!(text.contains("erp_inventory_stock_value 1234.5\n"));
+        assert!(text.contains("erp_receivables_outstanding 5000\n"));
+        // Requests finished before this scrape are counted
+        assert!(text.contains("erp_requests_total 1\n"));
+        assert!(text.contains("erp_http_requests_total{endpoint=\"other\",method=\"GET\"} 1\n"));
+
+        let text = get(&addr, "/metrics").await.text().await.unwrap();
+        assert!(text.contains("erp_requests_total 2\n"));
+        assert!(text.contains("erp_requests_failed_total 1\n"));
+        assert!(text.contains(
+            "erp_http_request_duration_milliseconds_count{endpoint=\"/metrics\",method=\"GET\"} 1\n"
+        ));
+    }
+}
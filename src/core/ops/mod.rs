@@ -1,3 +1,7 @@
 pub mod backup;
+pub mod data_transfer;
 pub mod deployment;
+pub mod metrics_exporter;
 pub mod performance;
+pub mod profiler;
+pub mod remote_storage;
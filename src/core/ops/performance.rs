@@ -1,6 +1,8 @@
-use crate::utils::error::ErpResult;
+use crate::utils::error::{ErpError, ErpResult};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use shaku::Interface;
+use sqlx::{PgPool, Row};
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
@@ -19,6 +21,16 @@ pub struct PerformanceMetrics {
     pub user_id: Option<String>,
     pub session_id: Option<String>,
     pub error_message: Option<String>,
+    #[serde(default)]
+    pub usage: ResourceUsage,
+}
+
+/// Resources a request consumed, when they were measured
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    pub query_count: u64,
+    pub query_time_ms: f64,
+    pub peak_memory_kb: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -113,7 +125,7 @@ impl Default for AlertThresholds {
 }
 
 #[async_trait::async_trait]
-pub trait PerformanceRepository: Send + Sync {
+pub trait PerformanceRepository: Interface {
     async fn store_metrics(&self, metrics: &[PerformanceMetrics]) -> ErpResult<()>;
     async fn store_aggregated_metrics(&self, metrics: &AggregatedMetrics) -> ErpResult<()>;
     async fn store_system_performance(&self, performance: &SystemPerformance) -> ErpResult<()>;
@@ -241,6 +253,7 @@ impl PerformanceMonitor {
                     user_id: tracker.user_id.clone(),
                     session_id: tracker.session_id.clone(),
                     error_message,
+                    usage: ResourceUsage::default(),
                 };
 
                 self.record_metrics(metrics).await?;
@@ -446,6 +459,23 @@ impl PerformanceMonitor {
         Ok(())
     }
 
+    /// Aggregates accumulated since the last flush, without draining them
+    pub fn current_aggregates(&self) -> ErpResult<Vec<AggregatedMetrics>> {
+        let accumulators: Vec<MetricsAccumulator> = {
+            let cache = self.aggregated_cache.lock().unwrap();
+            cache.values().cloned().collect()
+        };
+
+        let mut aggregates = accumulators
+            .into_iter()
+            .filter(|acc| acc.total_requests > 0)
+            .map(|acc| self.create_aggregated_metrics(acc))
+            .collect::<ErpResult<Vec<_>>>()?;
+        aggregates.sort_by(|a, b| (&a.endpoint, &a.method).cmp(&(&b.endpoint, &b.method)));
+
+        Ok(aggregates)
+    }
+
     pub async fn cleanup_old_metrics(&self) -> ErpResult<u64> {
         let cutoff = Utc::now() - Duration::hours(self.config.retention_hours);
         let removed = self.repository.cleanup_old_metrics(cutoff).await?;
@@ -579,12 +609,7 @@ impl PerformanceMonitor {
     }
 
     fn percentile(&self, sorted_values: &[u64], percentile: f64) -> f64 {
-        if sorted_values.is_empty() {
-            return 0.0;
-        }
-
-        let index = (percentile * (sorted_values.len() - 1) as f64).round() as usize;
-        sorted_values[index.min(sorted_values.len() - 1)] as f64
+        nearest_rank(sorted_values, percentile)
     }
 
     fn should_sample(&self) -> bool {
@@ -631,6 +656,16 @@ impl PerformanceMonitor {
     }
 }
 
+/// Nearest-rank percentile of already sorted values
+pub fn nearest_rank(sorted_values: &[u64], percentile: f64) -> f64 {
+    if sorted_values.is_empty() {
+        return 0.0;
+    }
+
+    let index = (percentile * (sorted_values.len() - 1) as f64).round() as usize;
+    sorted_values[index.min(sorted_values.len() - 1)] as f64
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EndpointPerformanceSummary {
     pub endpoint: String,
@@ -664,6 +699,185 @@ pub struct EndpointTrend {
     pub total_requests: u64,
 }
 
+/// Stores request metrics in `performance_metrics`; aggregates and system
+/// snapshots are kept as JSON in `performance_snapshots`
+pub struct PostgresPerformanceRepository {
+    pool: PgPool,
+}
+
+impl PostgresPerformanceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn store_snapshot<T: Serialize>(
+        &self,
+        kind: &str,
+        endpoint: Option<&str>,
+        recorded_at: DateTime<Utc>,
+        snapshot: &T,
+    ) -> ErpResult<()> {
+        let data =
+            serde_json::to_string(snapshot).map_err(|e| ErpError::serialization(e.to_string()))?;
+        sqlx::query(
+            "INSERT INTO performance_snapshots (kind, endpoint, recorded_at, data) \
+             VALUES ($1, $2, $3, $4::jsonb)",
+        )
+        .bind(kind)
+        .bind(endpoint)
+        .bind(recorded_at)
+        .bind(data)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_snapshots<T: serde::de::DeserializeOwned>(
+        &self,
+        kind: &str,
+        endpoint: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> ErpResult<Vec<T>> {
+        let rows: Vec<String> = sqlx::query_scalar(
+            "SELECT data::text FROM performance_snapshots \
+             WHERE kind = $1 AND ($2::text IS NULL OR endpoint = $2) AND recorded_at > $3 \
+             ORDER BY recorded_at",
+        )
+        .bind(kind)
+        .bind(endpoint)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|data| {
+                serde_json::from_str(data).map_err(|e| ErpError::serialization(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl PerformanceRepository for PostgresPerformanceRepository {
+    async fn store_metrics(&self, metrics: &[PerformanceMetrics]) -> ErpResult<()> {
+        let mut tx = self.pool.begin().await?;
+        for metric in metrics {
+            sqlx::query(
+                "INSERT INTO performance_metrics \
+                 (recorded_at, endpoint, method, response_time_ms, status_code, \
+                  request_size_bytes, response_size_bytes, user_id, session_id, error_message, \
+                  query_count, query_time_ms, peak_memory_kb) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+            )
+            .bind(metric.timestamp)
+            .bind(&metric.endpoint)
+            .bind(&metric.method)
+            .bind(metric.response_time_ms as i64)
+            .bind(i32::from(metric.status_code))
+            .bind(metric.request_size_bytes as i64)
+            .bind(metric.response_size_bytes as i64)
+            .bind(&metric.user_id)
+            .bind(&metric.session_id)
+            .bind(&metric.error_message)
+            .bind(metric.usage.query_count as i64)
+            .bind(metric.usage.query_time_ms)
+            .bind(metric.usage.peak_memory_kb as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn store_aggregated_metrics(&self, metrics: &AggregatedMetrics) -> ErpResult<()> {
+        self.store_snapshot(
+            "aggregated",
+            Some(&metrics.endpoint),
+            metrics.timestamp,
+            metrics,
+        )
+        .await
+    }
+
+    async fn store_system_performance(&self, performance: &SystemPerformance) -> ErpResult<()> {
+        self.store_snapshot("system", None, performance.timestamp, performance)
+            .await
+    }
+
+    async fn get_metrics(
+        &self,
+        endpoint: Option<&str>,
+        since: DateTime<Utc>,
+        limit: Option<usize>,
+    ) -> ErpResult<Vec<PerformanceMetrics>> {
+        let rows = sqlx::query(
+            "SELECT recorded_at, endpoint, method, response_time_ms, status_code, \
+             request_size_bytes, response_size_bytes, user_id, session_id, error_message, \
+             query_count, query_time_ms, peak_memory_kb \
+             FROM performance_metrics \
+             WHERE ($1::text IS NULL OR endpoint = $1) AND recorded_at > $2 \
+             ORDER BY recorded_at \
+             LIMIT $3",
+        )
+        .bind(endpoint)
+        .bind(since)
+        .bind(limit.map(|limit| limit as i64))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(PerformanceMetrics {
+                    timestamp: row.try_get("recorded_at")?,
+                    endpoint: row.try_get("endpoint")?,
+                    method: row.try_get("method")?,
+                    response_time_ms: row.try_get::<i64, _>("response_time_ms")? as u64,
+                    status_code: row.try_get::<i32, _>("status_code")? as u16,
+                    request_size_bytes: row.try_get::<i64, _>("request_size_bytes")? as u64,
+                    response_size_bytes: row.try_get::<i64, _>("response_size_bytes")? as u64,
+                    user_id: row.try_get("user_id")?,
+                    session_id: row.try_get("session_id")?,
+                    error_message: row.try_get("error_message")?,
+                    usage: ResourceUsage {
+                        query_count: row.try_get::<i64, _>("query_count")? as u64,
+                        query_time_ms: row.try_get("query_time_ms")?,
+                        peak_memory_kb: row.try_get::<i64, _>("peak_memory_kb")? as u64,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    async fn get_aggregated_metrics(
+        &self,
+        endpoint: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> ErpResult<Vec<AggregatedMetrics>> {
+        self.get_snapshots("aggregated", endpoint, since).await
+    }
+
+    async fn get_system_performance(
+        &self,
+        since: DateTime<Utc>,
+    ) -> ErpResult<Vec<SystemPerformance>> {
+        self.get_snapshots("system", None, since).await
+    }
+
+    async fn cleanup_old_metrics(&self, before: DateTime<Utc>) -> ErpResult<u64> {
+        let metrics = sqlx::query("DELETE FROM performance_metrics WHERE recorded_at <= $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        let snapshots = sqlx::query("DELETE FROM performance_snapshots WHERE recorded_at <= $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+        Ok(metrics + snapshots)
+    }
+}
+
 // Mock implementation for testing
 #[derive(Debug)]
 pub struct MockPerformanceRepository {
@@ -882,6 +1096,7 @@ mod tests {
                 user_id: Some("user1".to_string()),
                 session_id: Some("session1".to_string()),
                 error_message: None,
+                usage: ResourceUsage::default(),
             },
             PerformanceMetrics {
                 timestamp: Utc::now(),
@@ -894,6 +1109,7 @@ mod tests {
                 user_id: Some("user2".to_string()),
                 session_id: Some("session2".to_string()),
                 error_message: Some("Internal error".to_string()),
+                usage: ResourceUsage::default(),
             },
         ];
 
@@ -950,6 +1166,39 @@ mod tests {
         assert_eq!(monitor.percentile(&[], 0.5), 0.0);
     }
 
+    #[tokio::test]
+    async fn test_current_aggregates_do_not_drain() {
+        let config = PerformanceConfig::default();
+        let repository = Arc::new(MockPerformanceRepository::new());
+        let monitor = PerformanceMonitor::new(config, repository);
+
+        for (i, status) in [200, 200, 500].into_iter().enumerate() {
+            let request_id = format!("req-{}", i);
+            monitor
+                .start_request(
+                    request_id.clone(),
+                    "/metrics".to_string(),
+                    "GET".to_string(),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+            monitor
+                .end_request(request_id, status, 0, 100, None)
+                .await
+                .unwrap();
+        }
+
+        let aggregates = monitor.current_aggregates().unwrap();
+        assert_eq!(aggregates.len(), 1);
+        assert_eq!(aggregates[0].total_requests, 3);
+        assert_eq!(aggregates[0].failed_requests, 1);
+
+        // Reading again still sees the same totals
+        assert_eq!(monitor.current_aggregates().unwrap()[0].total_requests, 3);
+    }
+
     #[tokio::test]
     async fn test_metrics_cleanup() {
         let config = PerformanceConfig::default();
@@ -968,6 +1217,7 @@ mod tests {
             user_id: None,
             session_id: None,
             error_message: None,
+            usage: ResourceUsage::default(),
         }];
 
         repository.store_metrics(&old_metrics).await.unwrap();
@@ -0,0 +1,283 @@
+//! Per-command profiling behind the global `--profile` flag.
+//!
+//! `CommandProfiler` wraps a single CLI invocation and turns it into a
+//! `PerformanceMetrics` row: the command path is stored as the endpoint,
+//! `cli` as the method and 200/500 as the status. SQL statements are counted
+//! through `QueryLogLayer`, which forwards sqlx's statement events to
+//! `DatabaseLogger`, and peak memory is the process high-water mark.
+//!
+//! `performance_trend` summarises stored runs into per-bucket percentiles for
+//! `erp system perf`.
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use std::collections::BTreeMap;
+use std::sync::Once;
+use std::time::Instant;
+use sysinfo::{ProcessExt, System, SystemExt};
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::{Layer, SubscriberExt};
+
+use super::performance::{nearest_rank, PerformanceMetrics, ResourceUsage};
+use crate::core::logging::{DatabaseLogger, QueryLogLayer};
+
+pub const PROFILE_METHOD: &str = "cli";
+
+static INSTALL_QUERY_LAYER: Once = Once::new();
+
+/// Installs `QueryLogLayer` as the global subscriber so sqlx statement
+/// events reach `DatabaseLogger`
+///
+/// Does nothing if another global subscriber is already set.
+pub fn install_query_layer() {
+    INSTALL_QUERY_LAYER.call_once(|| {
+        let subscriber =
+            tracing_subscriber::registry().with(QueryLogLayer.with_filter(filter_fn(|metadata| {
+                metadata.target() == QueryLogLayer::TARGET
+            })));
+        let _ = tracing::subscriber::set_global_default(subscriber);
+    });
+}
+
+/// Measures one command run
+pub struct CommandProfiler {
+    command: String,
+    started_at: DateTime<Utc>,
+    start: Instant,
+}
+
+impl CommandProfiler {
+    pub fn start(command: impl Into<String>) -> Self {
+        install_query_layer();
+        DatabaseLogger::start_query_stats();
+        Self {
+            command: command.into(),
+            started_at: Utc::now(),
+            start: Instant::now(),
+        }
+    }
+
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Stops the measurement; `error` is the command's failure, if any
+    pub fn finish(self, error: Option<String>) -> PerformanceMetrics {
+        let elapsed = self.start.elapsed();
+        let queries = DatabaseLogger::stop_query_stats();
+
+        PerformanceMetrics {
+            timestamp: self.started_at,
+            endpoint: self.command,
+            method: PROFILE_METHOD.to_string(),
+            response_time_ms: elapsed.as_millis() as u64,
+            status_code: if error.is_some() { 500 } else { 200 },
+            request_size_bytes: 0,
+            response_size_bytes: 0,
+            user_id: None,
+            session_id: None,
+            error_message: error,
+            usage: ResourceUsage {
+                query_count: queries.count,
+                query_time_ms: queries.total_time.as_secs_f64() * 1000.0,
+                peak_memory_kb: peak_memory_kb(),
+            },
+        }
+    }
+}
+
+/// Peak resident memory of this process in KiB
+///
+/// Reads `VmHWM` where procfs is available; elsewhere the current resident
+/// size is the best available approximation.
+pub fn peak_memory_kb() -> u64 {
+    if let Some(kb) = std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| parse_vm_hwm(&status))
+    {
+        return kb;
+    }
+
+    let Ok(pid) = sysinfo::get_current_pid() else {
+        return 0;
+    };
+    let mut system = System::new();
+    if !system.refresh_process(pid) {
+        return 0;
+    }
+    system
+        .process(pid)
+        .map(|process| process.memory() / 1024)
+        .unwrap_or(0)
+}
+
+fn parse_vm_hwm(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+/// Timing summary of the runs that started within one bucket
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerformanceTrend {
+    pub bucket: DateTime<Utc>,
+    pub runs: usize,
+    pub failures: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub avg_queries: f64,
+    pub max_memory_kb: u64,
+}
+
+/// Bucket size for a trend over `range`: hourly up to two days, daily beyond
+pub fn trend_bucket(range: Duration) -> Duration {
+    if range <= Duration::days(2) {
+        Duration::hours(1)
+    } else {
+        Duration::days(1)
+    }
+}
+
+/// Groups runs into `bucket`-sized windows (UTC) in chronological order
+pub fn performance_trend(
+    metrics: &[PerformanceMetrics],
+    bucket: Duration,
+) -> Vec<PerformanceTrend> {
+    let mut buckets: BTreeMap<DateTime<Utc>, Vec<&PerformanceMetrics>> = BTreeMap::new();
+    for metric in metrics {
+        let start = metric
+            .timestamp
+            .duration_trunc(bucket)
+            .unwrap_or(metric.timestamp);
+        buckets.entry(start).or_default().push(metric);
+    }
+
+    buckets
+        .into_iter()
+        .map(|(bucket, runs)| {
+            let mut times: Vec<u64> = runs.iter().map(|m| m.response_time_ms).collect();
+            times.sort_unstable();
+            let total_queries: u64 = runs.iter().map(|m| m.usage.query_count).sum();
+
+            PerformanceTrend {
+                bucket,
+                runs: runs.len(),
+                failures: runs.iter().filter(|m| m.status_code >= 400).count(),
+                p50_ms: nearest_rank(&times, 0.50),
+                p95_ms: nearest_rank(&times, 0.95),
+                p99_ms: nearest_rank(&times, 0.99),
+                avg_queries: total_queries as f64 / runs.len() as f64,
+                max_memory_kb: runs
+                    .iter()
+                    .map(|m| m.usage.peak_memory_kb)
+                    .max()
+                    .unwrap_or(0),
+            }
+        })
+        .collect()
+}
+
+/// Whether a stored endpoint belongs to `command` or one of its subcommands
+pub fn matches_command(endpoint: &str, command: &str) -> bool {
+    endpoint == command
+        || endpoint
+            .strip_prefix(command)
+            .is_some_and(|rest| rest.starts_with(' '))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn run(at: DateTime<Utc>, ms: u64, queries: u64, memory_kb: u64) -> PerformanceMetrics {
+        PerformanceMetrics {
+            timestamp: at,
+            endpoint: "reports financial-overview".to_string(),
+            method: PROFILE_METHOD.to_string(),
+            response_time_ms: ms,
+            status_code: 200,
+            request_size_bytes: 0,
+            response_size_bytes: 0,
+            user_id: None,
+            session_id: None,
+            error_message: None,
+            usage: ResourceUsage {
+                query_count: queries,
+                query_time_ms: 0.0,
+                peak_memory_kb: memory_kb,
+            },
+        }
+    }
+
+    #[test]
+    fn test_performance_trend_buckets_by_day() {
+        let day1 = Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap();
+        let day2 = Utc.with_ymd_and_hms(2024, 3, 2, 23, 59, 0).unwrap();
+        let mut metrics: Vec<_> = (1..=100).map(|ms| run(day1, ms, 4, 1000)).collect();
+        metrics.push(run(day2, 500, 10, 3000));
+        let mut failed = run(day2, 700, 2, 2000);
+        failed.status_code = 500;
+        metrics.push(failed);
+
+        let trend = performance_trend(&metrics, Duration::days(1));
+        assert_eq!(trend.len(), 2);
+
+        assert_eq!(
+            trend[0].bucket,
+            Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(trend[0].runs, 100);
+        assert_eq!(trend[0].failures, 0);
+        assert_eq!(trend[0].p50_ms, 51.0);
+        assert_eq!(trend[0].p95_ms, 95.0);
+        assert_eq!(trend[0].p99_ms, 99.0);
+        assert_eq!(trend[0].avg_queries, 4.0);
+
+        assert_eq!(trend[1].runs, 2);
+        assert_eq!(trend[1].failures, 1);
+        assert_eq!(trend[1].p99_ms, 700.0);
+        assert_eq!(trend[1].avg_queries, 6.0);
+        assert_eq!(trend[1].max_memory_kb, 3000);
+    }
+
+    #[test]
+    fn test_trend_bucket() {
+        assert_eq!(trend_bucket(Duration::hours(12)), Duration::hours(1));
+        assert_eq!(trend_bucket(Duration::days(7)), Duration::days(1));
+    }
+
+    #[test]
+    fn test_matches_command() {
+        assert!(matches_command("sales", "sales"));
+        assert!(matches_command("sales create-order", "sales"));
+        assert!(!matches_command("sales-report", "sales"));
+        assert!(!matches_command("reports sales", "sales"));
+    }
+
+    #[test]
+    fn test_parse_vm_hwm() {
+        let status = "Name:\terp\nVmPeak:\t  123456 kB\nVmHWM:\t   45678 kB\nVmRSS:\t 40000 kB\n";
+        assert_eq!(parse_vm_hwm(status), Some(45678));
+        assert_eq!(parse_vm_hwm("Name:\terp\n"), None);
+    }
+
+    #[test]
+    fn test_profiler_counts_logged_queries() {
+        let profiler = CommandProfiler::start("sales list");
+        DatabaseLogger.log_query("SELECT 1", 3);
+        DatabaseLogger.log_query("SELECT 2", 2);
+        let metrics = profiler.finish(None);
+
+        assert_eq!(metrics.endpoint, "sales list");
+        assert_eq!(metrics.method, PROFILE_METHOD);
+        assert_eq!(metrics.status_code, 200);
+        assert!(metrics.usage.query_count >= 2);
+        assert!(metrics.usage.query_time_ms >= 5.0);
+
+        let failed = CommandProfiler::start("sales list").finish(Some("boom".to_string()));
+        assert_eq!(failed.status_code, 500);
+        assert_eq!(failed.error_message.as_deref(), Some("boom"));
+    }
+}
@@ -1,7 +1,15 @@
-use crate::utils::error::ErpResult;
+use super::audit_chain::{
+    AuditCheckpoint, ChainBreak, ChainBreakReason, ChainReport, ChainVerifier,
+};
+use crate::utils::error::{ErpError, ErpResult};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shaku::Interface;
+use sqlx::PgPool;
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
@@ -24,6 +32,72 @@ pub enum AuditAction {
     SecurityViolation,
 }
 
+impl AuditAction {
+    pub const ALL: [AuditAction; 15] = [
+        AuditAction::Login,
+        AuditAction::Logout,
+        AuditAction::LoginFailed,
+        AuditAction::UserCreated,
+        AuditAction::UserUpdated,
+        AuditAction::UserDeactivated,
+        AuditAction::PasswordChanged,
+        AuditAction::PermissionGranted,
+        AuditAction::PermissionRevoked,
+        AuditAction::DataAccessed,
+        AuditAction::DataModified,
+        AuditAction::DataDeleted,
+        AuditAction::ConfigChanged,
+        AuditAction::SystemError,
+        AuditAction::SecurityViolation,
+    ];
+
+    /// Stable snake_case name used for storage and search
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Login => "login",
+            AuditAction::Logout => "logout",
+            AuditAction::LoginFailed => "login_failed",
+            AuditAction::UserCreated => "user_created",
+            AuditAction::UserUpdated => "user_updated",
+            AuditAction::UserDeactivated => "user_deactivated",
+            AuditAction::PasswordChanged => "password_changed",
+            AuditAction::PermissionGranted => "permission_granted",
+            AuditAction::PermissionRevoked => "permission_revoked",
+            AuditAction::DataAccessed => "data_accessed",
+            AuditAction::DataModified => "data_modified",
+            AuditAction::DataDeleted => "data_deleted",
+            AuditAction::ConfigChanged => "config_changed",
+            AuditAction::SystemError => "system_error",
+            AuditAction::SecurityViolation => "security_violation",
+        }
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for AuditAction {
+    type Err = ErpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase().replace('-', "_");
+        Self::ALL
+            .iter()
+            .find(|action| action.as_str() == normalized)
+            .cloned()
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|a| a.as_str()).collect();
+                ErpError::validation(
+                    "action",
+                    format!("알 수 없는 감사 작업입니다: {} ({})", s, names.join(", ")),
+                )
+            })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AuditSeverity {
     Low,
@@ -32,6 +106,40 @@ pub enum AuditSeverity {
     Critical,
 }
 
+impl AuditSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditSeverity::Low => "low",
+            AuditSeverity::Medium => "medium",
+            AuditSeverity::High => "high",
+            AuditSeverity::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for AuditSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for AuditSeverity {
+    type Err = ErpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "low" => Ok(AuditSeverity::Low),
+            "medium" => Ok(AuditSeverity::Medium),
+            "high" => Ok(AuditSeverity::High),
+            "critical" => Ok(AuditSeverity::Critical),
+            _ => Err(ErpError::validation(
+                "severity",
+                format!("알 수 없는 심각도입니다: {}", s),
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEvent {
     pub id: Uuid,
@@ -47,6 +155,13 @@ pub struct AuditEvent {
     pub user_agent: Option<String>,
     pub success: bool,
     pub error_message: Option<String>,
+    /// Position in the hash chain, assigned by the repository
+    #[serde(default)]
+    pub sequence: Option<i64>,
+    #[serde(default)]
+    pub previous_hash: Option<String>,
+    #[serde(default)]
+    pub content_hash: Option<String>,
 }
 
 impl AuditEvent {
@@ -65,6 +180,9 @@ impl AuditEvent {
             user_agent: None,
             success: true,
             error_message: None,
+            sequence: None,
+            previous_hash: None,
+            content_hash: None,
         }
     }
 
@@ -74,6 +192,13 @@ impl AuditEvent {
         self
     }
 
+    /// Records the acting user when no user ID is known (e.g. the CLI operator)
+    pub fn with_username(mut self, user_id: Option<Uuid>, username: String) -> Self {
+        self.user_id = user_id;
+        self.username = Some(username);
+        self
+    }
+
     pub fn with_resource(mut self, resource: String, resource_id: Option<String>) -> Self {
         self.resource = Some(resource);
         self.resource_id = resource_id;
@@ -107,20 +232,110 @@ impl AuditEvent {
     }
 }
 
+/// Fields that change on every write and only add noise to a diff
+const DIFF_IGNORED_FIELDS: &[&str] = &["created_at", "updated_at"];
+
+/// Flattens a before/after pair into `<field>.before` / `<field>.after` entries.
+///
+/// Only changed top-level fields are kept. A creation (no `before`) records the
+/// non-null `after` values, a deletion (no `after`) records the `before` values.
+pub fn diff_details<T: Serialize>(
+    before: Option<&T>,
+    after: Option<&T>,
+) -> HashMap<String, String> {
+    let to_fields = |value: Option<&T>| -> serde_json::Map<String, serde_json::Value> {
+        match value.map(serde_json::to_value) {
+            Some(Ok(serde_json::Value::Object(map))) => map,
+            Some(Ok(other)) => {
+                let mut map = serde_json::Map::new();
+                map.insert("value".to_string(), other);
+                map
+            }
+            _ => serde_json::Map::new(),
+        }
+    };
+    let render = |value: &serde_json::Value| match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+
+    let before_fields = to_fields(before);
+    let after_fields = to_fields(after);
+    let mut details = HashMap::new();
+
+    let mut keys: Vec<&String> = before_fields.keys().chain(after_fields.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    for key in keys {
+        if DIFF_IGNORED_FIELDS.contains(&key.as_str()) {
+            continue;
+        }
+        let old = before_fields.get(key).filter(|v| !v.is_null());
+        let new = after_fields.get(key).filter(|v| !v.is_null());
+        if old == new {
+            continue;
+        }
+        if before.is_some() {
+            let value = old.map(render).unwrap_or_else(|| "null".to_string());
+            details.insert(format!("{}.before", key), value);
+        }
+        if after.is_some() {
+            let value = new.map(render).unwrap_or_else(|| "null".to_string());
+            details.insert(format!("{}.after", key), value);
+        }
+    }
+
+    details
+}
+
+/// Name of the operator running the CLI (`ERP_USER`, then `USER`/`USERNAME`)
+pub fn current_username() -> String {
+    ["ERP_USER", "USER", "USERNAME"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|name| name.trim().to_string())
+        .find(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
 #[async_trait::async_trait]
-pub trait AuditRepository: Send + Sync {
-    async fn store_event(&self, event: &AuditEvent) -> ErpResult<()>;
+pub trait AuditRepository: Interface {
+    /// Seals the event onto the hash chain, stores it and returns the stored copy
+    async fn store_event(&self, event: &AuditEvent) -> ErpResult<AuditEvent>;
     async fn get_events(&self, filters: AuditFilters) -> ErpResult<Vec<AuditEvent>>;
     async fn count_events(&self, filters: AuditFilters) -> ErpResult<u64>;
     async fn cleanup_old_events(&self, retention_days: i64) -> ErpResult<u64>;
+
+    /// First and last chain sequence of events within the time range
+    async fn chain_bounds(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> ErpResult<Option<(i64, i64)>>;
+    /// Events in chain order with `start <= sequence <= end`
+    async fn get_chain_page(&self, start: i64, end: i64, limit: u32) -> ErpResult<Vec<AuditEvent>>;
+    async fn get_event_by_sequence(&self, sequence: i64) -> ErpResult<Option<AuditEvent>>;
+    /// The event stored right before `sequence`, if it still exists
+    async fn get_predecessor(&self, sequence: i64) -> ErpResult<Option<AuditEvent>>;
+    async fn get_chain_head(&self) -> ErpResult<Option<AuditEvent>>;
+
+    async fn store_checkpoint(&self, checkpoint: &AuditCheckpoint) -> ErpResult<()>;
+    async fn list_checkpoints(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> ErpResult<Vec<AuditCheckpoint>>;
+    async fn latest_checkpoint(&self) -> ErpResult<Option<AuditCheckpoint>>;
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct AuditFilters {
     pub user_id: Option<Uuid>,
+    pub username: Option<String>,
     pub action: Option<AuditAction>,
     pub severity: Option<AuditSeverity>,
     pub resource: Option<String>,
+    pub resource_id: Option<String>,
     pub success: Option<bool>,
     pub start_time: Option<DateTime<Utc>>,
     pub end_time: Option<DateTime<Utc>>,
@@ -129,7 +344,7 @@ pub struct AuditFilters {
 }
 
 pub struct AuditService {
-    repository: Box<dyn AuditRepository>,
+    repository: Arc<dyn AuditRepository>,
     config: AuditConfig,
 }
 
@@ -141,6 +356,10 @@ pub struct AuditConfig {
     pub log_to_file: bool,
     pub log_failed_only: bool,
     pub sensitive_actions: Vec<AuditAction>,
+    /// A signed checkpoint is written every N chained events (0 disables)
+    pub checkpoint_interval: u64,
+    /// HMAC key for checkpoints; checkpoints are disabled without one
+    pub signing_key: Option<String>,
 }
 
 impl Default for AuditConfig {
@@ -160,12 +379,17 @@ impl Default for AuditConfig {
                 AuditAction::SecurityViolation,
                 AuditAction::SystemError,
             ],
+            checkpoint_interval: 1000,
+            signing_key: None,
         }
     }
 }
 
+/// Number of events fetched per page while verifying the chain
+const CHAIN_PAGE_SIZE: u32 = 1000;
+
 impl AuditService {
-    pub fn new(repository: Box<dyn AuditRepository>, config: AuditConfig) -> Self {
+    pub fn new(repository: Arc<dyn AuditRepository>, config: AuditConfig) -> Self {
         Self { repository, config }
     }
 
@@ -191,9 +415,17 @@ impl AuditService {
         self.log_to_tracing(&event);
 
         // Store in repository
-        if let Err(e) = self.repository.store_event(&event).await {
-            error!("Failed to store audit event: {}", e);
-            return Err(e);
+        let stored = match self.repository.store_event(&event).await {
+            Ok(stored) => stored,
+            Err(e) => {
+                error!("Failed to store audit event: {}", e);
+                return Err(e);
+            }
+        };
+
+        // Periodic checkpoint; a failure here must not lose the event itself
+        if let Err(e) = self.checkpoint_if_due(&stored).await {
+            warn!("Failed to write audit checkpoint: {}", e);
         }
 
         info!(
@@ -240,13 +472,13 @@ impl AuditService {
 
     pub async fn log_data_access(
         &self,
-        user_id: Uuid,
+        user_id: Option<Uuid>,
         username: String,
         resource: String,
         resource_id: Option<String>,
     ) -> ErpResult<()> {
         let event = AuditEvent::new(AuditAction::DataAccessed, AuditSeverity::Low)
-            .with_user(user_id, username)
+            .with_username(user_id, username)
             .with_resource(resource, resource_id);
 
         self.log_event(event).await
@@ -254,20 +486,51 @@ impl AuditService {
 
     pub async fn log_data_modification(
         &self,
-        user_id: Uuid,
+        user_id: Option<Uuid>,
         username: String,
         resource: String,
         resource_id: Option<String>,
         details: HashMap<String, String>,
     ) -> ErpResult<()> {
         let event = AuditEvent::new(AuditAction::DataModified, AuditSeverity::Medium)
-            .with_user(user_id, username)
+            .with_username(user_id, username)
             .with_resource(resource, resource_id)
             .with_details(details);
 
         self.log_event(event).await
     }
 
+    pub async fn log_data_deletion(
+        &self,
+        user_id: Option<Uuid>,
+        username: String,
+        resource: String,
+        resource_id: Option<String>,
+        details: HashMap<String, String>,
+    ) -> ErpResult<()> {
+        let event = AuditEvent::new(AuditAction::DataDeleted, AuditSeverity::High)
+            .with_username(user_id, username)
+            .with_resource(resource, resource_id)
+            .with_details(details);
+
+        self.log_event(event).await
+    }
+
+    pub async fn log_config_change(
+        &self,
+        user_id: Option<Uuid>,
+        username: String,
+        key: String,
+        details: HashMap<String, String>,
+    ) -> ErpResult<()> {
+        let event = AuditEvent::new(AuditAction::ConfigChanged, AuditSeverity::High)
+            .with_username(user_id, username)
+            .with_resource("config".to_string(), Some(key))
+            .with_details(details);
+
+        self.log_event(event).await
+    }
+
     pub async fn log_security_violation(
         &self,
         user_id: Option<Uuid>,
@@ -347,6 +610,152 @@ impl AuditService {
         self.repository.get_events(filters).await
     }
 
+    pub async fn search_events(&self, filters: AuditFilters) -> ErpResult<Vec<AuditEvent>> {
+        self.repository.get_events(filters).await
+    }
+
+    pub fn retention_days(&self) -> i64 {
+        self.config.retention_days
+    }
+
+    /// Walks the hash chain for events within the time range and stops at the first break
+    pub async fn verify_chain(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> ErpResult<ChainReport> {
+        let Some((start, end)) = self.repository.chain_bounds(from, to).await? else {
+            return Ok(ChainReport::default());
+        };
+
+        let predecessor = self.repository.get_predecessor(start).await?;
+        let mut verifier = ChainVerifier::new(predecessor.as_ref());
+        let mut next = start;
+
+        'pages: while next <= end {
+            let page = self
+                .repository
+                .get_chain_page(next, end, CHAIN_PAGE_SIZE)
+                .await?;
+            let Some(last) = page.last().and_then(|e| e.sequence) else {
+                break;
+            };
+            for event in &page {
+                if !verifier.check(event) {
+                    break 'pages;
+                }
+            }
+            next = last + 1;
+        }
+
+        Ok(verifier.finish())
+    }
+
+    /// Compares checkpoints against the stored chain. Checkpoints older than the
+    /// retained events (pruned by retention) are skipped.
+    pub async fn verify_checkpoints(
+        &self,
+        checkpoints: &[AuditCheckpoint],
+    ) -> ErpResult<Option<ChainBreak>> {
+        let oldest = self
+            .repository
+            .chain_bounds(None, None)
+            .await?
+            .map(|(first, _)| first);
+
+        for checkpoint in checkpoints {
+            let reason = match self
+                .repository
+                .get_event_by_sequence(checkpoint.sequence)
+                .await?
+            {
+                Some(event)
+                    if event.id == checkpoint.event_id
+                        && event.content_hash.as_deref()
+                            == Some(checkpoint.content_hash.as_str()) =>
+                {
+                    continue
+                }
+                Some(_) => ChainBreakReason::CheckpointMismatch {
+                    checkpoint_sequence: checkpoint.sequence,
+                },
+                None if oldest.is_some_and(|first| checkpoint.sequence < first) => continue,
+                None => ChainBreakReason::CheckpointEventMissing {
+                    checkpoint_sequence: checkpoint.sequence,
+                },
+            };
+            return Ok(Some(ChainBreak {
+                sequence: checkpoint.sequence,
+                event_id: Some(checkpoint.event_id),
+                timestamp: Some(checkpoint.event_timestamp),
+                reason,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    /// Signs the current chain head. Returns the latest checkpoint unchanged when
+    /// nothing was appended since.
+    pub async fn create_checkpoint(&self) -> ErpResult<Option<AuditCheckpoint>> {
+        let key = self.signing_key()?;
+        let Some(head) = self.repository.get_chain_head().await? else {
+            return Ok(None);
+        };
+        let latest = self.repository.latest_checkpoint().await?;
+        if let Some(latest) = latest
+            .as_ref()
+            .filter(|c| Some(c.sequence) == head.sequence)
+        {
+            return Ok(Some(latest.clone()));
+        }
+
+        let checkpoint = AuditCheckpoint::sign(&head, latest.as_ref(), key)?;
+        self.repository.store_checkpoint(&checkpoint).await?;
+        info!(
+            "Audit checkpoint written at sequence {}",
+            checkpoint.sequence
+        );
+        Ok(Some(checkpoint))
+    }
+
+    pub async fn list_checkpoints(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> ErpResult<Vec<AuditCheckpoint>> {
+        self.repository.list_checkpoints(since).await
+    }
+
+    pub fn signing_key(&self) -> ErpResult<&[u8]> {
+        self.config
+            .signing_key
+            .as_deref()
+            .filter(|key| !key.is_empty())
+            .map(str::as_bytes)
+            .ok_or_else(|| ErpError::config("감사 체크포인트 서명 키가 설정되지 않았습니다"))
+    }
+
+    async fn checkpoint_if_due(&self, stored: &AuditEvent) -> ErpResult<()> {
+        let interval = self.config.checkpoint_interval as i64;
+        let (Some(sequence), true) = (stored.sequence, interval > 0) else {
+            return Ok(());
+        };
+        if self.signing_key().is_err() {
+            return Ok(());
+        }
+
+        let last_checkpointed = self
+            .repository
+            .latest_checkpoint()
+            .await?
+            .map(|c| c.sequence)
+            .unwrap_or(0);
+        if sequence - last_checkpointed >= interval {
+            self.create_checkpoint().await?;
+        }
+        Ok(())
+    }
+
     pub async fn cleanup_old_events(&self) -> ErpResult<u64> {
         self.repository
             .cleanup_old_events(self.config.retention_days)
@@ -371,10 +780,381 @@ impl AuditService {
     }
 }
 
+pub struct PostgresAuditRepository {
+    pool: PgPool,
+}
+
+impl PostgresAuditRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+const AUDIT_EVENT_COLUMNS: &str = "id, occurred_at, user_id, username, action, resource, \
+     resource_id, severity, details::text AS details, ip_address, user_agent, success, \
+     error_message, sequence, previous_hash, content_hash";
+
+const AUDIT_CHECKPOINT_COLUMNS: &str = "id, sequence, event_id, event_timestamp, content_hash, \
+     previous_signature, created_at, signature";
+
+/// Advisory lock key serializing appends to the hash chain
+const AUDIT_CHAIN_LOCK_ID: i64 = 0x4155_4449_545f_4348;
+
+const AUDIT_FILTER_CLAUSE: &str = "($1::uuid IS NULL OR user_id = $1) \
+     AND ($2::text IS NULL OR username = $2) \
+     AND ($3::text IS NULL OR action = $3) \
+     AND ($4::text IS NULL OR severity = $4) \
+     AND ($5::text IS NULL OR resource = $5) \
+     AND ($6::text IS NULL OR resource_id = $6) \
+     AND ($7::boolean IS NULL OR success = $7) \
+     AND ($8::timestamptz IS NULL OR occurred_at >= $8) \
+     AND ($9::timestamptz IS NULL OR occurred_at <= $9)";
+
+#[derive(sqlx::FromRow)]
+struct AuditEventRow {
+    id: Uuid,
+    occurred_at: DateTime<Utc>,
+    user_id: Option<Uuid>,
+    username: Option<String>,
+    action: String,
+    resource: Option<String>,
+    resource_id: Option<String>,
+    severity: String,
+    details: String,
+    ip_address: Option<String>,
+    user_agent: Option<String>,
+    success: bool,
+    error_message: Option<String>,
+    sequence: i64,
+    previous_hash: Option<String>,
+    content_hash: Option<String>,
+}
+
+impl TryFrom<AuditEventRow> for AuditEvent {
+    type Error = ErpError;
+
+    fn try_from(row: AuditEventRow) -> Result<Self, Self::Error> {
+        let details = serde_json::from_str(&row.details).map_err(|e| {
+            ErpError::serialization(format!("Invalid details for audit event {}: {}", row.id, e))
+        })?;
+        Ok(Self {
+            id: row.id,
+            timestamp: row.occurred_at,
+            user_id: row.user_id,
+            username: row.username,
+            action: row.action.parse()?,
+            resource: row.resource,
+            resource_id: row.resource_id,
+            severity: row.severity.parse()?,
+            details,
+            ip_address: row.ip_address,
+            user_agent: row.user_agent,
+            success: row.success,
+            error_message: row.error_message,
+            sequence: Some(row.sequence),
+            previous_hash: row.previous_hash,
+            content_hash: row.content_hash,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct AuditCheckpointRow {
+    id: Uuid,
+    sequence: i64,
+    event_id: Uuid,
+    event_timestamp: DateTime<Utc>,
+    content_hash: String,
+    previous_signature: Option<String>,
+    created_at: DateTime<Utc>,
+    signature: String,
+}
+
+impl From<AuditCheckpointRow> for AuditCheckpoint {
+    fn from(row: AuditCheckpointRow) -> Self {
+        Self {
+            id: row.id,
+            sequence: row.sequence,
+            event_id: row.event_id,
+            event_timestamp: row.event_timestamp,
+            content_hash: row.content_hash,
+            previous_signature: row.previous_signature,
+            created_at: row.created_at,
+            signature: row.signature,
+        }
+    }
+}
+
+impl PostgresAuditRepository {
+    async fn fetch_optional_event(
+        &self,
+        condition: &str,
+        sequence: Option<i64>,
+    ) -> ErpResult<Option<AuditEvent>> {
+        let sql = format!(
+            "SELECT {} FROM audit_events {}",
+            AUDIT_EVENT_COLUMNS, condition
+        );
+        let mut query = sqlx::query_as::<_, AuditEventRow>(&sql);
+        if let Some(sequence) = sequence {
+            query = query.bind(sequence);
+        }
+        let row = query
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to query audit chain: {}", e)))?;
+
+        row.map(AuditEvent::try_from).transpose()
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditRepository for PostgresAuditRepository {
+    async fn store_event(&self, event: &AuditEvent) -> ErpResult<AuditEvent> {
+        let db_error =
+            |e: sqlx::Error| ErpError::database(format!("Failed to store audit event: {}", e));
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        // Serialize appends so every event links to the true chain head
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(AUDIT_CHAIN_LOCK_ID)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        let previous_hash: Option<String> = sqlx::query_scalar(
+            "SELECT content_hash FROM audit_events ORDER BY sequence DESC LIMIT 1",
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .flatten();
+
+        let mut sealed = event.clone();
+        sealed.seal(previous_hash);
+
+        let details = serde_json::to_string(&sealed.details).map_err(|e| {
+            ErpError::serialization(format!("Failed to serialize audit details: {}", e))
+        })?;
+
+        let sequence: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO audit_events
+                (id, occurred_at, user_id, username, action, resource, resource_id, severity,
+                 details, ip_address, user_agent, success, error_message, previous_hash,
+                 content_hash)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9::jsonb, $10, $11, $12, $13, $14, $15)
+            RETURNING sequence
+            "#,
+        )
+        .bind(sealed.id)
+        .bind(sealed.timestamp)
+        .bind(sealed.user_id)
+        .bind(&sealed.username)
+        .bind(sealed.action.as_str())
+        .bind(&sealed.resource)
+        .bind(&sealed.resource_id)
+        .bind(sealed.severity.as_str())
+        .bind(details)
+        .bind(&sealed.ip_address)
+        .bind(&sealed.user_agent)
+        .bind(sealed.success)
+        .bind(&sealed.error_message)
+        .bind(&sealed.previous_hash)
+        .bind(&sealed.content_hash)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        sealed.sequence = Some(sequence);
+        Ok(sealed)
+    }
+
+    async fn get_events(&self, filters: AuditFilters) -> ErpResult<Vec<AuditEvent>> {
+        let sql = format!(
+            "SELECT {} FROM audit_events WHERE {} \
+             ORDER BY occurred_at DESC, id LIMIT $10 OFFSET $11",
+            AUDIT_EVENT_COLUMNS, AUDIT_FILTER_CLAUSE
+        );
+
+        let rows = sqlx::query_as::<_, AuditEventRow>(&sql)
+            .bind(filters.user_id)
+            .bind(&filters.username)
+            .bind(filters.action.as_ref().map(|a| a.as_str()))
+            .bind(filters.severity.as_ref().map(|s| s.as_str()))
+            .bind(&filters.resource)
+            .bind(&filters.resource_id)
+            .bind(filters.success)
+            .bind(filters.start_time)
+            .bind(filters.end_time)
+            .bind(filters.limit.map(|l| l as i64))
+            .bind(filters.offset.map(|o| o as i64))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to query audit events: {}", e)))?;
+
+        rows.into_iter().map(AuditEvent::try_from).collect()
+    }
+
+    async fn count_events(&self, filters: AuditFilters) -> ErpResult<u64> {
+        let sql = format!(
+            "SELECT COUNT(*) FROM audit_events WHERE {}",
+            AUDIT_FILTER_CLAUSE
+        );
+
+        let count: i64 = sqlx::query_scalar(&sql)
+            .bind(filters.user_id)
+            .bind(&filters.username)
+            .bind(filters.action.as_ref().map(|a| a.as_str()))
+            .bind(filters.severity.as_ref().map(|s| s.as_str()))
+            .bind(&filters.resource)
+            .bind(&filters.resource_id)
+            .bind(filters.success)
+            .bind(filters.start_time)
+            .bind(filters.end_time)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to count audit events: {}", e)))?;
+
+        Ok(count as u64)
+    }
+
+    async fn cleanup_old_events(&self, retention_days: i64) -> ErpResult<u64> {
+        let cutoff_date = Utc::now() - chrono::Duration::days(retention_days);
+        let result = sqlx::query("DELETE FROM audit_events WHERE occurred_at < $1")
+            .bind(cutoff_date)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to clean up audit events: {}", e)))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn chain_bounds(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> ErpResult<Option<(i64, i64)>> {
+        let (first, last): (Option<i64>, Option<i64>) = sqlx::query_as(
+            r#"
+            SELECT MIN(sequence), MAX(sequence) FROM audit_events
+            WHERE ($1::timestamptz IS NULL OR occurred_at >= $1)
+              AND ($2::timestamptz IS NULL OR occurred_at <= $2)
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to query audit chain bounds: {}", e)))?;
+
+        Ok(first.zip(last))
+    }
+
+    async fn get_chain_page(&self, start: i64, end: i64, limit: u32) -> ErpResult<Vec<AuditEvent>> {
+        let sql = format!(
+            "SELECT {} FROM audit_events WHERE sequence BETWEEN $1 AND $2 \
+             ORDER BY sequence LIMIT $3",
+            AUDIT_EVENT_COLUMNS
+        );
+        let rows = sqlx::query_as::<_, AuditEventRow>(&sql)
+            .bind(start)
+            .bind(end)
+            .bind(i64::from(limit))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to query audit chain: {}", e)))?;
+
+        rows.into_iter().map(AuditEvent::try_from).collect()
+    }
+
+    async fn get_event_by_sequence(&self, sequence: i64) -> ErpResult<Option<AuditEvent>> {
+        self.fetch_optional_event("WHERE sequence = $1", Some(sequence))
+            .await
+    }
+
+    async fn get_predecessor(&self, sequence: i64) -> ErpResult<Option<AuditEvent>> {
+        self.fetch_optional_event(
+            "WHERE sequence < $1 ORDER BY sequence DESC LIMIT 1",
+            Some(sequence),
+        )
+        .await
+    }
+
+    async fn get_chain_head(&self) -> ErpResult<Option<AuditEvent>> {
+        self.fetch_optional_event("ORDER BY sequence DESC LIMIT 1", None)
+            .await
+    }
+
+    async fn store_checkpoint(&self, checkpoint: &AuditCheckpoint) -> ErpResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_checkpoints
+                (id, sequence, event_id, event_timestamp, content_hash, previous_signature,
+                 created_at, signature)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(checkpoint.id)
+        .bind(checkpoint.sequence)
+        .bind(checkpoint.event_id)
+        .bind(checkpoint.event_timestamp)
+        .bind(&checkpoint.content_hash)
+        .bind(&checkpoint.previous_signature)
+        .bind(checkpoint.created_at)
+        .bind(&checkpoint.signature)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => ErpError::conflict(format!(
+                "Checkpoint already exists for sequence {}",
+                checkpoint.sequence
+            )),
+            e => ErpError::database(format!("Failed to store audit checkpoint: {}", e)),
+        })?;
+
+        Ok(())
+    }
+
+    async fn list_checkpoints(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> ErpResult<Vec<AuditCheckpoint>> {
+        let sql = format!(
+            "SELECT {} FROM audit_checkpoints \
+             WHERE ($1::timestamptz IS NULL OR created_at >= $1) ORDER BY sequence",
+            AUDIT_CHECKPOINT_COLUMNS
+        );
+        let rows = sqlx::query_as::<_, AuditCheckpointRow>(&sql)
+            .bind(since)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to list audit checkpoints: {}", e)))?;
+
+        Ok(rows.into_iter().map(AuditCheckpoint::from).collect())
+    }
+
+    async fn latest_checkpoint(&self) -> ErpResult<Option<AuditCheckpoint>> {
+        let sql = format!(
+            "SELECT {} FROM audit_checkpoints ORDER BY sequence DESC LIMIT 1",
+            AUDIT_CHECKPOINT_COLUMNS
+        );
+        let row = sqlx::query_as::<_, AuditCheckpointRow>(&sql)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get audit checkpoint: {}", e)))?;
+
+        Ok(row.map(AuditCheckpoint::from))
+    }
+}
+
 // Mock implementation for testing
 #[derive(Debug, Clone)]
 pub struct MockAuditRepository {
     events: std::sync::Arc<std::sync::Mutex<Vec<AuditEvent>>>,
+    checkpoints: std::sync::Arc<std::sync::Mutex<Vec<AuditCheckpoint>>>,
 }
 
 impl Default for MockAuditRepository {
@@ -387,6 +1167,7 @@ impl MockAuditRepository {
     pub fn new() -> Self {
         Self {
             events: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
+            checkpoints: std::sync::Arc::new(std::sync::Mutex::new(Vec::new())),
         }
     }
 
@@ -397,10 +1178,18 @@ impl MockAuditRepository {
 
 #[async_trait::async_trait]
 impl AuditRepository for MockAuditRepository {
-    async fn store_event(&self, event: &AuditEvent) -> ErpResult<()> {
+    async fn store_event(&self, event: &AuditEvent) -> ErpResult<AuditEvent> {
         let mut events = self.events.lock().unwrap();
-        events.push(event.clone());
-        Ok(())
+        let (previous_hash, sequence) = match events.last() {
+            Some(last) => (last.content_hash.clone(), last.sequence.unwrap_or(0) + 1),
+            None => (None, 1),
+        };
+
+        let mut sealed = event.clone();
+        sealed.seal(previous_hash);
+        sealed.sequence = Some(sequence);
+        events.push(sealed.clone());
+        Ok(sealed)
     }
 
     async fn get_events(&self, filters: AuditFilters) -> ErpResult<Vec<AuditEvent>> {
@@ -423,17 +1212,38 @@ impl AuditRepository for MockAuditRepository {
                         return false;
                     }
                 }
+                if filters.username.is_some() && e.username != filters.username {
+                    return false;
+                }
+                if filters.resource.is_some() && e.resource != filters.resource {
+                    return false;
+                }
+                if filters.resource_id.is_some() && e.resource_id != filters.resource_id {
+                    return false;
+                }
                 if let Some(success) = filters.success {
                     if e.success != success {
                         return false;
                     }
                 }
+                if filters.start_time.is_some_and(|start| e.timestamp < start) {
+                    return false;
+                }
+                if filters.end_time.is_some_and(|end| e.timestamp > end) {
+                    return false;
+                }
                 true
             })
             .cloned()
             .collect();
 
-        // Apply limit
+        // Newest first, matching the Postgres repository
+        filtered.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+
+        // Apply offset and limit
+        if let Some(offset) = filters.offset {
+            filtered.drain(..(offset as usize).min(filtered.len()));
+        }
         if let Some(limit) = filters.limit {
             filtered.truncate(limit as usize);
         }
@@ -454,6 +1264,87 @@ impl AuditRepository for MockAuditRepository {
         let removed_count = original_count - events.len();
         Ok(removed_count as u64)
     }
+
+    async fn chain_bounds(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> ErpResult<Option<(i64, i64)>> {
+        let events = self.events.lock().unwrap();
+        let sequences: Vec<i64> = events
+            .iter()
+            .filter(|e| from.is_none_or(|from| e.timestamp >= from))
+            .filter(|e| to.is_none_or(|to| e.timestamp <= to))
+            .filter_map(|e| e.sequence)
+            .collect();
+        Ok(sequences
+            .iter()
+            .min()
+            .copied()
+            .zip(sequences.iter().max().copied()))
+    }
+
+    async fn get_chain_page(&self, start: i64, end: i64, limit: u32) -> ErpResult<Vec<AuditEvent>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .iter()
+            .filter(|e| e.sequence.is_some_and(|seq| seq >= start && seq <= end))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_event_by_sequence(&self, sequence: i64) -> ErpResult<Option<AuditEvent>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .iter()
+            .find(|e| e.sequence == Some(sequence))
+            .cloned())
+    }
+
+    async fn get_predecessor(&self, sequence: i64) -> ErpResult<Option<AuditEvent>> {
+        let events = self.events.lock().unwrap();
+        Ok(events
+            .iter()
+            .rev()
+            .find(|e| e.sequence.is_some_and(|seq| seq < sequence))
+            .cloned())
+    }
+
+    async fn get_chain_head(&self) -> ErpResult<Option<AuditEvent>> {
+        Ok(self.events.lock().unwrap().last().cloned())
+    }
+
+    async fn store_checkpoint(&self, checkpoint: &AuditCheckpoint) -> ErpResult<()> {
+        let mut checkpoints = self.checkpoints.lock().unwrap();
+        if checkpoints
+            .iter()
+            .any(|c| c.sequence == checkpoint.sequence)
+        {
+            return Err(ErpError::conflict(format!(
+                "Checkpoint already exists for sequence {}",
+                checkpoint.sequence
+            )));
+        }
+        checkpoints.push(checkpoint.clone());
+        Ok(())
+    }
+
+    async fn list_checkpoints(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> ErpResult<Vec<AuditCheckpoint>> {
+        let checkpoints = self.checkpoints.lock().unwrap();
+        Ok(checkpoints
+            .iter()
+            .filter(|c| since.is_none_or(|since| c.created_at >= since))
+            .cloned()
+            .collect())
+    }
+
+    async fn latest_checkpoint(&self) -> ErpResult<Option<AuditCheckpoint>> {
+        Ok(self.checkpoints.lock().unwrap().last().cloned())
+    }
 }
 
 #[cfg(test)]
@@ -474,7 +1365,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_audit_service_log_event() {
-        let repository = Box::new(MockAuditRepository::new());
+        let repository = Arc::new(MockAuditRepository::new());
         let config = AuditConfig::default();
         let service = AuditService::new(repository, config);
 
@@ -523,7 +1414,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_security_violation_logging() {
-        let repository = Box::new(MockAuditRepository::new());
+        let repository = Arc::new(MockAuditRepository::new());
         let config = AuditConfig::default();
         let service = AuditService::new(repository, config);
 
@@ -565,4 +1456,196 @@ mod tests {
         let remaining_events = repository.get_all_events();
         assert_eq!(remaining_events.len(), 1);
     }
+
+    #[test]
+    fn test_audit_action_round_trip() {
+        for action in AuditAction::ALL.iter() {
+            assert_eq!(&action.as_str().parse::<AuditAction>().unwrap(), action);
+        }
+        assert_eq!(
+            "data-modified".parse::<AuditAction>().unwrap(),
+            AuditAction::DataModified
+        );
+        assert!("unknown".parse::<AuditAction>().is_err());
+        assert_eq!(
+            "Critical".parse::<AuditSeverity>().unwrap(),
+            AuditSeverity::Critical
+        );
+    }
+
+    #[derive(Serialize)]
+    struct Product {
+        name: String,
+        price: f64,
+        description: Option<String>,
+        updated_at: String,
+    }
+
+    #[test]
+    fn test_diff_details() {
+        let before = Product {
+            name: "Widget".to_string(),
+            price: 10.0,
+            description: None,
+            updated_at: "2024-01-01".to_string(),
+        };
+        let after = Product {
+            name: "Widget".to_string(),
+            price: 12.5,
+            description: Some("New".to_string()),
+            updated_at: "2024-01-02".to_string(),
+        };
+
+        let changes = diff_details(Some(&before), Some(&after));
+        assert_eq!(changes.len(), 4);
+        assert_eq!(changes["price.before"], "10.0");
+        assert_eq!(changes["price.after"], "12.5");
+        assert_eq!(changes["description.before"], "null");
+        assert_eq!(changes["description.after"], "New");
+
+        let created = diff_details(None, Some(&before));
+        assert_eq!(created.len(), 2);
+        assert_eq!(created["name.after"], "Widget");
+        assert!(!created.keys().any(|k| k.ends_with(".before")));
+
+        let deleted = diff_details(Some(&after), None);
+        assert_eq!(deleted["description.before"], "New");
+        assert!(!deleted.keys().any(|k| k.ends_with(".after")));
+    }
+
+    #[tokio::test]
+    async fn test_mock_repository_resource_and_time_filters() {
+        let repository = MockAuditRepository::new();
+
+        let mut old_event = AuditEvent::new(AuditAction::DataModified, AuditSeverity::Medium)
+            .with_username(None, "alice".to_string())
+            .with_resource("customers".to_string(), Some("CUST-1".to_string()));
+        old_event.timestamp = Utc::now() - chrono::Duration::days(10);
+        let recent_event = AuditEvent::new(AuditAction::DataModified, AuditSeverity::Medium)
+            .with_username(None, "alice".to_string())
+            .with_resource("customers".to_string(), Some("CUST-2".to_string()));
+        let other_event = AuditEvent::new(AuditAction::DataDeleted, AuditSeverity::High)
+            .with_username(None, "bob".to_string())
+            .with_resource("products".to_string(), None);
+
+        for event in [&old_event, &recent_event, &other_event] {
+            repository.store_event(event).await.unwrap();
+        }
+
+        let filters = AuditFilters {
+            username: Some("alice".to_string()),
+            resource: Some("customers".to_string()),
+            ..Default::default()
+        };
+        let events = repository.get_events(filters.clone()).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].id, recent_event.id); // newest first
+
+        let since = AuditFilters {
+            start_time: Some(Utc::now() - chrono::Duration::days(1)),
+            ..filters
+        };
+        assert_eq!(repository.count_events(since).await.unwrap(), 1);
+    }
+
+    fn chained_service(repository: &MockAuditRepository, interval: u64) -> AuditService {
+        let config = AuditConfig {
+            checkpoint_interval: interval,
+            signing_key: Some("test-signing-key".to_string()),
+            ..AuditConfig::default()
+        };
+        AuditService::new(Arc::new(repository.clone()), config)
+    }
+
+    #[tokio::test]
+    async fn test_verify_chain_reports_first_tampered_event() {
+        let repository = MockAuditRepository::new();
+        let service = chained_service(&repository, 0);
+        for i in 0..5 {
+            service
+                .log_data_access(
+                    None,
+                    "alice".to_string(),
+                    "customers".to_string(),
+                    Some(i.to_string()),
+                )
+                .await
+                .unwrap();
+        }
+
+        let report = service.verify_chain(None, None).await.unwrap();
+        assert!(report.is_intact());
+        assert_eq!(report.checked, 5);
+        assert_eq!(
+            (report.first_sequence, report.last_sequence),
+            (Some(1), Some(5))
+        );
+
+        // Rewrite an event in place, as a direct UPDATE on the table would
+        repository.events.lock().unwrap()[2].username = Some("mallory".to_string());
+
+        let report = service.verify_chain(None, None).await.unwrap();
+        let chain_break = report.first_break.expect("tampering must be detected");
+        assert_eq!(chain_break.sequence, 3);
+        assert!(matches!(
+            chain_break.reason,
+            ChainBreakReason::ContentMismatch { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoints_written_periodically_and_verified() {
+        let repository = MockAuditRepository::new();
+        let service = chained_service(&repository, 3);
+        for _ in 0..7 {
+            service
+                .log_data_access(None, "alice".to_string(), "products".to_string(), None)
+                .await
+                .unwrap();
+        }
+
+        let checkpoints = service.list_checkpoints(None).await.unwrap();
+        let sequences: Vec<i64> = checkpoints.iter().map(|c| c.sequence).collect();
+        assert_eq!(sequences, vec![3, 6]);
+        assert_eq!(
+            checkpoints[1].previous_signature.as_deref(),
+            Some(checkpoints[0].signature.as_str())
+        );
+        assert!(service
+            .verify_checkpoints(&checkpoints)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Creating again without new events returns the head checkpoint only once
+        let head = service.create_checkpoint().await.unwrap().unwrap();
+        assert_eq!(head.sequence, 7);
+        let again = service.create_checkpoint().await.unwrap().unwrap();
+        assert_eq!(again.id, head.id);
+
+        // Deleting a checkpointed event and re-chaining the rest is caught by the checkpoint
+        {
+            let mut events = repository.events.lock().unwrap();
+            events.remove(5);
+            let mut previous = events[4].content_hash.clone();
+            for (index, event) in events.iter_mut().enumerate().skip(5) {
+                event.seal(previous.clone());
+                event.sequence = Some(index as i64 + 1);
+                previous = event.content_hash.clone();
+            }
+        }
+        assert!(service.verify_chain(None, None).await.unwrap().is_intact());
+        let chain_break = service
+            .verify_checkpoints(&checkpoints)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(chain_break.sequence, 6);
+        assert!(matches!(
+            chain_break.reason,
+            ChainBreakReason::CheckpointMismatch {
+                checkpoint_sequence: 6
+            }
+        ));
+    }
 }
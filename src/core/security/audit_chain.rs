@@ -0,0 +1,531 @@
+//! Tamper-evident hash chain for audit events.
+//!
+//! Every stored event carries the content hash of the event stored right before it
+//! (`previous_hash`) and a SHA-256 hash over its own canonical content including that
+//! link (`content_hash`). Editing or deleting a row breaks the chain at that point.
+//! Signed checkpoints pin the chain head with an HMAC so that a rewrite of the whole
+//! chain can still be detected from an exported checkpoint file.
+
+use super::audit::AuditEvent;
+use crate::utils::crypto::{hash_string, hmac_sha256, secure_compare};
+use crate::utils::error::{ErpError, ErpResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Identifier written into exported checkpoint files
+pub const CHECKPOINT_FILE_FORMAT: &str = "erp-audit-checkpoints/v1";
+pub const CHECKPOINT_ALGORITHM: &str = "HMAC-SHA256";
+
+/// Timestamps are hashed at microsecond precision, which is what the database keeps
+fn canonical_time(at: &DateTime<Utc>) -> String {
+    at.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string()
+}
+
+fn truncate_to_micros(at: DateTime<Utc>) -> DateTime<Utc> {
+    DateTime::from_timestamp_micros(at.timestamp_micros()).unwrap_or(at)
+}
+
+#[derive(Serialize)]
+struct CanonicalEvent<'a> {
+    id: &'a Uuid,
+    timestamp: String,
+    user_id: &'a Option<Uuid>,
+    username: &'a Option<String>,
+    action: &'a str,
+    resource: &'a Option<String>,
+    resource_id: &'a Option<String>,
+    severity: &'a str,
+    details: BTreeMap<&'a str, &'a str>,
+    ip_address: &'a Option<String>,
+    user_agent: &'a Option<String>,
+    success: bool,
+    error_message: &'a Option<String>,
+    previous_hash: &'a Option<String>,
+}
+
+impl AuditEvent {
+    /// SHA-256 over the canonical JSON form of the event, including `previous_hash`
+    pub fn compute_hash(&self) -> String {
+        let canonical = CanonicalEvent {
+            id: &self.id,
+            timestamp: canonical_time(&self.timestamp),
+            user_id: &self.user_id,
+            username: &self.username,
+            action: self.action.as_str(),
+            resource: &self.resource,
+            resource_id: &self.resource_id,
+            severity: self.severity.as_str(),
+            details: self
+                .details
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect(),
+            ip_address: &self.ip_address,
+            user_agent: &self.user_agent,
+            success: self.success,
+            error_message: &self.error_message,
+            previous_hash: &self.previous_hash,
+        };
+        // Serializing plain strings, options and a BTreeMap cannot fail
+        let json = serde_json::to_string(&canonical).unwrap_or_default();
+        hash_string(&json)
+    }
+
+    /// Links the event to the current chain head and stamps its content hash
+    pub fn seal(&mut self, previous_hash: Option<String>) {
+        self.timestamp = truncate_to_micros(self.timestamp);
+        self.previous_hash = previous_hash;
+        self.content_hash = Some(self.compute_hash());
+    }
+
+    pub fn is_sealed(&self) -> bool {
+        self.content_hash.is_some()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChainBreakReason {
+    /// Event stored after the chain started but without a hash
+    MissingHash,
+    /// Stored hash does not match the event content
+    ContentMismatch { stored: String, computed: String },
+    /// `previous_hash` does not point at the preceding event
+    BrokenLink {
+        expected: Option<String>,
+        found: Option<String>,
+    },
+    /// Event pinned by a checkpoint is missing
+    CheckpointEventMissing { checkpoint_sequence: i64 },
+    /// Event pinned by a checkpoint differs from the checkpoint
+    CheckpointMismatch { checkpoint_sequence: i64 },
+}
+
+impl fmt::Display for ChainBreakReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn short(hash: &Option<String>) -> String {
+            hash.as_deref()
+                .map(|h| h.chars().take(16).collect())
+                .unwrap_or_else(|| "(없음)".to_string())
+        }
+
+        match self {
+            ChainBreakReason::MissingHash => write!(f, "해시가 없는 이벤트"),
+            ChainBreakReason::ContentMismatch { stored, computed } => write!(
+                f,
+                "내용 해시 불일치 (저장: {}…, 계산: {}…)",
+                &stored[..stored.len().min(16)],
+                &computed[..computed.len().min(16)]
+            ),
+            ChainBreakReason::BrokenLink { expected, found } => write!(
+                f,
+                "이전 해시 연결 끊김 (기대: {}, 실제: {})",
+                short(expected),
+                short(found)
+            ),
+            ChainBreakReason::CheckpointEventMissing {
+                checkpoint_sequence,
+            } => write!(
+                f,
+                "체크포인트(#{})가 가리키는 이벤트가 없음",
+                checkpoint_sequence
+            ),
+            ChainBreakReason::CheckpointMismatch {
+                checkpoint_sequence,
+            } => write!(
+                f,
+                "체크포인트(#{})와 이벤트 해시 불일치",
+                checkpoint_sequence
+            ),
+        }
+    }
+}
+
+/// First broken link found while walking the chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainBreak {
+    pub sequence: i64,
+    pub event_id: Option<Uuid>,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub reason: ChainBreakReason,
+}
+
+impl ChainBreak {
+    fn at(event: &AuditEvent, reason: ChainBreakReason) -> Self {
+        Self {
+            sequence: event.sequence.unwrap_or_default(),
+            event_id: Some(event.id),
+            timestamp: Some(event.timestamp),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for ChainBreak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}: {}", self.sequence, self.reason)?;
+        if let Some(id) = self.event_id {
+            write!(f, " (이벤트 {})", id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of walking a range of the chain
+#[derive(Debug, Clone, Default)]
+pub struct ChainReport {
+    pub checked: u64,
+    /// Events stored before hashing was introduced
+    pub unsealed: u64,
+    pub first_sequence: Option<i64>,
+    pub last_sequence: Option<i64>,
+    pub head_hash: Option<String>,
+    pub first_break: Option<ChainBreak>,
+}
+
+impl ChainReport {
+    pub fn is_intact(&self) -> bool {
+        self.first_break.is_none()
+    }
+}
+
+/// Incremental verifier fed with events in chain (sequence) order
+#[derive(Debug)]
+pub struct ChainVerifier {
+    /// `None` while the expected link is unknown (first event after pruning or range start)
+    expected_previous: Option<Option<String>>,
+    chain_started: bool,
+    report: ChainReport,
+}
+
+impl ChainVerifier {
+    /// `predecessor` is the event right before the verified range, if it still exists
+    pub fn new(predecessor: Option<&AuditEvent>) -> Self {
+        Self {
+            expected_previous: predecessor.map(|p| p.content_hash.clone()),
+            chain_started: predecessor.is_some_and(|p| p.is_sealed()),
+            report: ChainReport::default(),
+        }
+    }
+
+    /// Checks the next event; returns `false` once a break has been recorded
+    pub fn check(&mut self, event: &AuditEvent) -> bool {
+        if self.report.first_break.is_some() {
+            return false;
+        }
+
+        self.report
+            .first_sequence
+            .get_or_insert(event.sequence.unwrap_or_default());
+        self.report.last_sequence = event.sequence;
+        self.report.checked += 1;
+
+        let Some(stored) = event.content_hash.as_ref() else {
+            if self.chain_started {
+                return self.fail(event, ChainBreakReason::MissingHash);
+            }
+            // Legacy rows written before the chain existed
+            self.report.unsealed += 1;
+            self.expected_previous = Some(None);
+            return true;
+        };
+
+        let computed = event.compute_hash();
+        if !secure_compare(stored, &computed) {
+            return self.fail(
+                event,
+                ChainBreakReason::ContentMismatch {
+                    stored: stored.clone(),
+                    computed,
+                },
+            );
+        }
+
+        if let Some(expected) = &self.expected_previous {
+            if *expected != event.previous_hash {
+                return self.fail(
+                    event,
+                    ChainBreakReason::BrokenLink {
+                        expected: expected.clone(),
+                        found: event.previous_hash.clone(),
+                    },
+                );
+            }
+        }
+
+        self.chain_started = true;
+        self.expected_previous = Some(Some(stored.clone()));
+        self.report.head_hash = Some(stored.clone());
+        true
+    }
+
+    fn fail(&mut self, event: &AuditEvent, reason: ChainBreakReason) -> bool {
+        self.report.first_break = Some(ChainBreak::at(event, reason));
+        false
+    }
+
+    pub fn finish(self) -> ChainReport {
+        self.report
+    }
+}
+
+/// Signed snapshot of the chain head
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditCheckpoint {
+    pub id: Uuid,
+    pub sequence: i64,
+    pub event_id: Uuid,
+    pub event_timestamp: DateTime<Utc>,
+    pub content_hash: String,
+    pub previous_signature: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub signature: String,
+}
+
+impl AuditCheckpoint {
+    /// Pins `head` and links to the previous checkpoint's signature
+    pub fn sign(
+        head: &AuditEvent,
+        previous: Option<&AuditCheckpoint>,
+        key: &[u8],
+    ) -> ErpResult<Self> {
+        let sequence = head
+            .sequence
+            .ok_or_else(|| ErpError::internal("Checkpoint head has no sequence"))?;
+        let content_hash = head
+            .content_hash
+            .clone()
+            .ok_or_else(|| ErpError::internal("Checkpoint head is not sealed"))?;
+
+        let mut checkpoint = Self {
+            id: Uuid::new_v4(),
+            sequence,
+            event_id: head.id,
+            event_timestamp: head.timestamp,
+            content_hash,
+            previous_signature: previous.map(|p| p.signature.clone()),
+            created_at: truncate_to_micros(Utc::now()),
+            signature: String::new(),
+        };
+        checkpoint.signature = hmac_sha256(key, checkpoint.signing_payload().as_bytes());
+        Ok(checkpoint)
+    }
+
+    /// Documented payload so checkpoints can be verified with any HMAC-SHA256 tool:
+    /// `v1|sequence|event_id|event_timestamp|content_hash|previous_signature|created_at`
+    pub fn signing_payload(&self) -> String {
+        format!(
+            "v1|{}|{}|{}|{}|{}|{}",
+            self.sequence,
+            self.event_id,
+            canonical_time(&self.event_timestamp),
+            self.content_hash,
+            self.previous_signature.as_deref().unwrap_or(""),
+            canonical_time(&self.created_at)
+        )
+    }
+
+    pub fn verify_signature(&self, key: &[u8]) -> bool {
+        secure_compare(
+            &hmac_sha256(key, self.signing_payload().as_bytes()),
+            &self.signature,
+        )
+    }
+}
+
+/// Exported checkpoint file for offline verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditCheckpointFile {
+    pub format: String,
+    pub algorithm: String,
+    pub exported_at: DateTime<Utc>,
+    pub checkpoints: Vec<AuditCheckpoint>,
+}
+
+impl AuditCheckpointFile {
+    pub fn new(checkpoints: Vec<AuditCheckpoint>) -> Self {
+        Self {
+            format: CHECKPOINT_FILE_FORMAT.to_string(),
+            algorithm: CHECKPOINT_ALGORITHM.to_string(),
+            exported_at: Utc::now(),
+            checkpoints,
+        }
+    }
+
+    pub async fn save(&self, path: &Path) -> ErpResult<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            ErpError::serialization(format!("Failed to serialize checkpoints: {}", e))
+        })?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, json).await?;
+        Ok(())
+    }
+
+    pub async fn load(path: &Path) -> ErpResult<Self> {
+        let content = tokio::fs::read_to_string(path).await?;
+        let file: Self = serde_json::from_str(&content).map_err(|e| {
+            ErpError::serialization(format!("Invalid checkpoint file {}: {}", path.display(), e))
+        })?;
+        if file.format != CHECKPOINT_FILE_FORMAT {
+            return Err(ErpError::validation(
+                "format",
+                format!("지원하지 않는 체크포인트 파일 형식입니다: {}", file.format),
+            ));
+        }
+        Ok(file)
+    }
+
+    /// Checks every signature and the signature links between consecutive checkpoints.
+    /// Returns the sequence of the first invalid checkpoint, if any.
+    pub fn first_invalid(&self, key: &[u8]) -> Option<(i64, &'static str)> {
+        let mut previous: Option<&AuditCheckpoint> = None;
+        for checkpoint in &self.checkpoints {
+            if !checkpoint.verify_signature(key) {
+                return Some((checkpoint.sequence, "서명 불일치"));
+            }
+            if let Some(prev) = previous {
+                if checkpoint.sequence < prev.sequence {
+                    return Some((checkpoint.sequence, "순서가 잘못됨"));
+                }
+                if checkpoint.previous_signature.as_deref() != Some(prev.signature.as_str()) {
+                    return Some((checkpoint.sequence, "이전 체크포인트 연결 끊김"));
+                }
+            }
+            previous = Some(checkpoint);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::security::audit::{AuditAction, AuditSeverity};
+
+    fn sealed_chain(count: usize) -> Vec<AuditEvent> {
+        let mut events: Vec<AuditEvent> = Vec::new();
+        for i in 0..count {
+            let mut event = AuditEvent::new(AuditAction::DataModified, AuditSeverity::Medium)
+                .with_username(None, "tester".to_string())
+                .add_detail("price.after", format!("{}", i * 10));
+            let previous = events.last().and_then(|e| e.content_hash.clone());
+            event.seal(previous);
+            event.sequence = Some(i as i64 + 1);
+            events.push(event);
+        }
+        events
+    }
+
+    fn verify(predecessor: Option<&AuditEvent>, events: &[AuditEvent]) -> ChainReport {
+        let mut verifier = ChainVerifier::new(predecessor);
+        for event in events {
+            if !verifier.check(event) {
+                break;
+            }
+        }
+        verifier.finish()
+    }
+
+    #[test]
+    fn test_intact_chain_verifies() {
+        let events = sealed_chain(5);
+        let report = verify(None, &events);
+        assert!(report.is_intact());
+        assert_eq!(report.checked, 5);
+        assert_eq!(report.head_hash, events[4].content_hash);
+
+        // Verifying from the middle checks the link to the preceding event
+        let report = verify(Some(&events[1]), &events[2..]);
+        assert!(report.is_intact());
+        assert_eq!(report.first_sequence, Some(3));
+    }
+
+    #[test]
+    fn test_edited_event_breaks_chain() {
+        let mut events = sealed_chain(4);
+        events[2]
+            .details
+            .insert("price.after".to_string(), "0".to_string());
+
+        let report = verify(None, &events);
+        let broken = report.first_break.unwrap();
+        assert_eq!(broken.sequence, 3);
+        assert!(matches!(
+            broken.reason,
+            ChainBreakReason::ContentMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_deleted_event_breaks_link() {
+        let mut events = sealed_chain(4);
+        events.remove(1);
+
+        let report = verify(None, &events);
+        let broken = report.first_break.unwrap();
+        assert_eq!(broken.sequence, 3);
+        assert!(matches!(broken.reason, ChainBreakReason::BrokenLink { .. }));
+    }
+
+    #[test]
+    fn test_legacy_events_before_chain_are_skipped() {
+        let mut legacy = AuditEvent::new(AuditAction::Login, AuditSeverity::Low);
+        legacy.sequence = Some(1);
+        let mut first = AuditEvent::new(AuditAction::Logout, AuditSeverity::Low);
+        first.seal(None);
+        first.sequence = Some(2);
+        let mut unsealed_after = AuditEvent::new(AuditAction::Login, AuditSeverity::Low);
+        unsealed_after.sequence = Some(3);
+
+        let report = verify(None, &[legacy.clone(), first.clone()]);
+        assert!(report.is_intact());
+        assert_eq!(report.unsealed, 1);
+
+        let report = verify(None, &[legacy, first, unsealed_after]);
+        assert_eq!(
+            report.first_break.unwrap().reason,
+            ChainBreakReason::MissingHash
+        );
+    }
+
+    #[test]
+    fn test_seal_survives_microsecond_round_trip() {
+        let mut event = AuditEvent::new(AuditAction::ConfigChanged, AuditSeverity::High);
+        event.seal(Some("abc".to_string()));
+        let mut reloaded = event.clone();
+        reloaded.timestamp = truncate_to_micros(reloaded.timestamp);
+        assert_eq!(reloaded.compute_hash(), event.content_hash.unwrap());
+    }
+
+    #[test]
+    fn test_checkpoint_signatures() {
+        let events = sealed_chain(3);
+        let key = b"checkpoint-key";
+        let first = AuditCheckpoint::sign(&events[1], None, key).unwrap();
+        let second = AuditCheckpoint::sign(&events[2], Some(&first), key).unwrap();
+
+        assert!(first.verify_signature(key));
+        assert!(!first.verify_signature(b"other-key"));
+
+        let file = AuditCheckpointFile::new(vec![first.clone(), second.clone()]);
+        assert_eq!(file.first_invalid(key), None);
+
+        let mut tampered = second.clone();
+        tampered.content_hash = "0".repeat(64);
+        let file = AuditCheckpointFile::new(vec![first.clone(), tampered]);
+        assert_eq!(file.first_invalid(key), Some((3, "서명 불일치")));
+
+        // Dropping a checkpoint in between breaks the signature link
+        let third = AuditCheckpoint::sign(&events[2], Some(&second), key).unwrap();
+        let file = AuditCheckpointFile::new(vec![first, third]);
+        assert_eq!(
+            file.first_invalid(key),
+            Some((3, "이전 체크포인트 연결 끊김"))
+        );
+    }
+}
@@ -435,6 +435,7 @@ mod tests {
         let validation_service = ValidationService::new();
         let auth_config = AuthConfig::default();
 
+        let rate_limiter: Arc<dyn RateLimiterTrait> = Arc::new(MockRateLimiter::new());
         let auth_service = Arc::new(AuthService::new(
             user_repo,
             jwt_service,
@@ -442,9 +443,8 @@ mod tests {
             hashing_service,
             validation_service,
             auth_config,
+            rate_limiter.clone(),
         ));
-
-        let rate_limiter: Arc<dyn RateLimiterTrait> = Arc::new(MockRateLimiter::new());
         let config = SecurityConfig::default();
 
         SecurityMiddleware::new(auth_service, rate_limiter, config)
@@ -549,6 +549,7 @@ mod tests {
         let validation_service = ValidationService::new();
         let auth_config = AuthConfig::default();
 
+        let rate_limiter: Arc<dyn RateLimiterTrait> = Arc::new(MockRateLimiter::new());
         let auth_service = Arc::new(AuthService::new(
             user_repo,
             jwt_service,
@@ -556,10 +557,9 @@ mod tests {
             hashing_service,
             validation_service,
             auth_config,
+            rate_limiter.clone(),
         ));
 
-        let rate_limiter: Arc<dyn RateLimiterTrait> = Arc::new(MockRateLimiter::new());
-
         let rule = SecurityRule {
             name: "test_rule".to_string(),
             enabled: true,
@@ -1,5 +1,7 @@
 pub mod audit;
+pub mod audit_chain;
 pub mod encryption;
 pub mod middleware;
 pub mod monitor;
+pub mod pii;
 pub mod rate_limiter;
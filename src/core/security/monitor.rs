@@ -1,7 +1,13 @@
+use crate::core::database::migration::{DatabaseMigrator, MigrationRunner, PostgresMigrator};
+use crate::core::database::replica::ReadRouter;
+use crate::core::ops::backup::{BackupRepository, BackupStatus, ManifestBackupRepository};
 use crate::utils::error::{ErpError, ErpResult};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use shaku::Interface;
+use sqlx::{PgPool, Postgres};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use sysinfo::{CpuExt, DiskExt, NetworkExt, NetworksExt, System, SystemExt};
 use tokio::time::{sleep, Duration as TokioDuration};
@@ -134,7 +140,7 @@ impl Default for MonitoringConfig {
 }
 
 #[async_trait::async_trait]
-pub trait MetricsRepository: Send + Sync {
+pub trait MetricsRepository: Interface {
     async fn store_system_metrics(&self, metrics: &SystemMetrics) -> ErpResult<()>;
     async fn store_application_metrics(&self, metrics: &ApplicationMetrics) -> ErpResult<()>;
     async fn store_security_metrics(&self, metrics: &SecurityMetrics) -> ErpResult<()>;
@@ -155,7 +161,7 @@ pub trait MetricsRepository: Send + Sync {
 
 pub struct MonitoringService {
     config: MonitoringConfig,
-    repository: Box<dyn MetricsRepository>,
+    repository: Arc<dyn MetricsRepository>,
     system: Arc<Mutex<System>>,
     application_metrics: Arc<Mutex<ApplicationMetrics>>,
     security_metrics: Arc<Mutex<SecurityMetrics>>,
@@ -170,7 +176,7 @@ pub trait HealthCheck: Send + Sync {
 }
 
 impl MonitoringService {
-    pub fn new(config: MonitoringConfig, repository: Box<dyn MetricsRepository>) -> Self {
+    pub fn new(config: MonitoringConfig, repository: Arc<dyn MetricsRepository>) -> Self {
         let mut system = System::new_all();
         system.refresh_all();
 
@@ -205,27 +211,44 @@ impl MonitoringService {
         let interval = TokioDuration::from_secs(self.config.collection_interval_seconds);
 
         loop {
-            if let Err(e) = self.collect_and_store_metrics().await {
+            if let Err(e) = self.run_cycle().await {
                 error!("Failed to collect metrics: {}", e);
             }
 
-            if let Err(e) = self.run_health_checks().await {
-                error!("Failed to run health checks: {}", e);
-            }
+            sleep(interval).await;
+        }
+    }
 
-            if let Err(e) = self.check_alerts().await {
-                error!("Failed to check alerts: {}", e);
-            }
+    /// Runs one collection cycle: stores metrics, runs the health checks,
+    /// raises or resolves alerts and removes expired data
+    pub async fn run_cycle(&self) -> ErpResult<MonitoringCycle> {
+        let system_metrics = self.collect_and_store_metrics().await?;
+        let health = self.run_health_checks().await?;
+        self.check_alerts(&system_metrics, &health).await?;
 
-            if let Err(e) = self.cleanup_old_data().await {
-                warn!("Failed to cleanup old data: {}", e);
-            }
+        if let Err(e) = self.cleanup_old_data().await {
+            warn!("Failed to cleanup old data: {}", e);
+        }
 
-            sleep(interval).await;
+        Ok(MonitoringCycle {
+            system_metrics,
+            health,
+            active_alerts: self.get_active_alerts().await?,
+        })
+    }
+
+    /// Restores unresolved alerts from the repository so that a restarted
+    /// monitor resolves them once the condition clears
+    pub async fn load_active_alerts(&self) -> ErpResult<usize> {
+        let alerts = self.repository.get_alerts(Some(false)).await?;
+        let mut active_alerts = self.active_alerts.lock().unwrap();
+        for alert in alerts {
+            active_alerts.insert(alert_key(&alert.category, &alert.title), alert);
         }
+        Ok(active_alerts.len())
     }
 
-    async fn collect_and_store_metrics(&self) -> ErpResult<()> {
+    async fn collect_and_store_metrics(&self) -> ErpResult<SystemMetrics> {
         // Collect system metrics
         let system_metrics = self.collect_system_metrics()?;
         self.repository
@@ -249,7 +272,7 @@ impl MonitoringService {
         self.repository.store_security_metrics(&sec_metrics).await?;
 
         debug!("Metrics collected and stored successfully");
-        Ok(())
+        Ok(system_metrics)
     }
 
     fn collect_system_metrics(&self) -> ErpResult<SystemMetrics> {
@@ -297,8 +320,11 @@ impl MonitoringService {
         })
     }
 
+    /// Runs every registered check and stores the results. A check that
+    /// fails to run is reported as `Down`.
     #[allow(clippy::await_holding_lock)]
-    async fn run_health_checks(&self) -> ErpResult<()> {
+    pub async fn run_health_checks(&self) -> ErpResult<Vec<HealthStatus>> {
+        let mut statuses = Vec::new();
         let checks = {
             let health_checks = self.health_checks.lock().unwrap();
             health_checks
@@ -310,37 +336,25 @@ impl MonitoringService {
         for check_name in checks {
             let health_checks = self.health_checks.lock().unwrap();
             if let Some(check) = health_checks.iter().find(|c| c.name() == check_name) {
-                match check.check().await {
-                    Ok(status) => {
-                        self.repository.store_health_status(&status).await?;
-                        debug!("Health check {} completed: {:?}", check_name, status.status);
-                    }
-                    Err(e) => {
-                        warn!("Health check {} failed: {}", check_name, e);
-                        let status = HealthStatus {
-                            service: check_name.clone(),
-                            status: HealthState::Critical,
-                            timestamp: Utc::now(),
-                            details: [("error".to_string(), e.to_string())]
-                                .iter()
-                                .cloned()
-                                .collect(),
-                            response_time_ms: None,
-                        };
-                        self.repository.store_health_status(&status).await?;
-                    }
-                }
+                let status = run_health_check(check.as_ref()).await;
+                self.repository.store_health_status(&status).await?;
+                statuses.push(status);
             }
         }
 
-        Ok(())
+        Ok(statuses)
     }
 
-    async fn check_alerts(&self) -> ErpResult<()> {
-        let system_metrics = self.collect_system_metrics()?;
+    async fn check_alerts(
+        &self,
+        system_metrics: &SystemMetrics,
+        health: &[HealthStatus],
+    ) -> ErpResult<()> {
+        let mut firing = HashSet::new();
 
         // Check CPU alerts
         if system_metrics.cpu_usage > self.config.cpu_critical_threshold {
+            firing.insert(alert_key(&AlertCategory::System, "High CPU Usage"));
             self.create_alert(
                 AlertSeverity::Critical,
                 AlertCategory::System,
@@ -359,6 +373,7 @@ impl MonitoringService {
             )
             .await?;
         } else if system_metrics.cpu_usage > self.config.cpu_warning_threshold {
+            firing.insert(alert_key(&AlertCategory::System, "Elevated CPU Usage"));
             self.create_alert(
                 AlertSeverity::Warning,
                 AlertCategory::System,
@@ -382,6 +397,7 @@ impl MonitoringService {
         let memory_usage_percent =
             (system_metrics.memory_used as f32 / system_metrics.memory_total as f32) * 100.0;
         if memory_usage_percent > self.config.memory_critical_threshold {
+            firing.insert(alert_key(&AlertCategory::System, "High Memory Usage"));
             self.create_alert(
                 AlertSeverity::Critical,
                 AlertCategory::System,
@@ -405,6 +421,7 @@ impl MonitoringService {
         let disk_usage_percent =
             (system_metrics.disk_used as f32 / system_metrics.disk_total as f32) * 100.0;
         if disk_usage_percent > self.config.disk_critical_threshold {
+            firing.insert(alert_key(&AlertCategory::System, "High Disk Usage"));
             self.create_alert(
                 AlertSeverity::Critical,
                 AlertCategory::System,
@@ -424,6 +441,49 @@ impl MonitoringService {
             .await?;
         }
 
+        // Failing health checks
+        for status in health {
+            let severity = match status.status {
+                HealthState::Healthy => continue,
+                HealthState::Warning => AlertSeverity::Warning,
+                HealthState::Critical | HealthState::Down => AlertSeverity::Critical,
+            };
+            let title = format!("Health check failed: {}", status.service);
+            firing.insert(alert_key(&AlertCategory::Application, &title));
+            let mut details: Vec<_> = status.details.iter().collect();
+            details.sort();
+            self.create_alert(
+                severity,
+                AlertCategory::Application,
+                title,
+                format!(
+                    "{} is {:?}: {}",
+                    status.service,
+                    status.status,
+                    details
+                        .iter()
+                        .map(|(k, v)| format!("{}={}", k, v))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                status.details.clone(),
+            )
+            .await?;
+        }
+
+        // Conditions that cleared since the last cycle
+        let cleared: Vec<Uuid> = {
+            let active_alerts = self.active_alerts.lock().unwrap();
+            active_alerts
+                .iter()
+                .filter(|(key, _)| !firing.contains(*key))
+                .map(|(_, alert)| alert.id)
+                .collect()
+        };
+        for alert_id in cleared {
+            self.resolve_alert(alert_id).await?;
+        }
+
         Ok(())
     }
 
@@ -436,7 +496,7 @@ impl MonitoringService {
         description: String,
         metrics: HashMap<String, String>,
     ) -> ErpResult<()> {
-        let alert_key = format!("{:?}-{}", category, title);
+        let alert_key = alert_key(&category, &title);
 
         let mut active_alerts = self.active_alerts.lock().unwrap();
 
@@ -585,6 +645,62 @@ impl MonitoringService {
     }
 }
 
+/// Runs a single check, reporting a check that fails to run as `Down`
+pub async fn run_health_check(check: &dyn HealthCheck) -> HealthStatus {
+    match check.check().await {
+        Ok(status) => {
+            debug!(
+                "Health check {} completed: {:?}",
+                check.name(),
+                status.status
+            );
+            status
+        }
+        Err(e) => {
+            warn!("Health check {} failed: {}", check.name(), e);
+            HealthStatus::down(check.name(), e.to_string())
+        }
+    }
+}
+
+/// The worst state among `statuses`; `Healthy` when there are none
+pub fn overall_state(statuses: &[HealthStatus]) -> HealthState {
+    statuses
+        .iter()
+        .map(|s| s.status.clone())
+        .max_by_key(|state| match state {
+            HealthState::Healthy => 0,
+            HealthState::Warning => 1,
+            HealthState::Critical => 2,
+            HealthState::Down => 3,
+        })
+        .unwrap_or(HealthState::Healthy)
+}
+
+impl HealthStatus {
+    pub fn down(service: &str, error: String) -> Self {
+        Self {
+            service: service.to_string(),
+            status: HealthState::Down,
+            timestamp: Utc::now(),
+            details: [("error".to_string(), error)].into_iter().collect(),
+            response_time_ms: None,
+        }
+    }
+}
+
+/// Result of one `MonitoringService::run_cycle`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitoringCycle {
+    pub system_metrics: SystemMetrics,
+    pub health: Vec<HealthStatus>,
+    pub active_alerts: Vec<Alert>,
+}
+
+fn alert_key(category: &AlertCategory, title: &str) -> String {
+    format!("{:?}-{}", category, title)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemHealthSummary {
     pub timestamp: DateTime<Utc>,
@@ -629,23 +745,26 @@ impl Default for SecurityMetrics {
     }
 }
 
-// Example health checks
+/// Checks that the database answers a query within `slow_threshold_ms`
 pub struct DatabaseHealthCheck {
     name: String,
-}
-
-impl Default for DatabaseHealthCheck {
-    fn default() -> Self {
-        Self::new()
-    }
+    pool: PgPool,
+    slow_threshold_ms: u64,
 }
 
 impl DatabaseHealthCheck {
-    pub fn new() -> Self {
+    pub fn new(pool: PgPool) -> Self {
         Self {
             name: "database".to_string(),
+            pool,
+            slow_threshold_ms: 500,
         }
     }
+
+    pub fn with_slow_threshold_ms(mut self, slow_threshold_ms: u64) -> Self {
+        self.slow_threshold_ms = slow_threshold_ms;
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -653,32 +772,558 @@ impl HealthCheck for DatabaseHealthCheck {
     async fn check(&self) -> ErpResult<HealthStatus> {
         let start_time = std::time::Instant::now();
 
-        // Simulate database health check
-        tokio::time::sleep(TokioDuration::from_millis(10)).await;
+        let query = sqlx::query_scalar::<_, String>("SHOW server_version").fetch_one(&self.pool);
+        let result = tokio::time::timeout(TokioDuration::from_secs(5), query).await;
+        let response_time_ms = start_time.elapsed().as_millis() as u64;
 
+        let mut details = HashMap::new();
+        let status = match result {
+            Ok(Ok(version)) => {
+                details.insert("server_version".to_string(), version);
+                details.insert("pool_size".to_string(), self.pool.size().to_string());
+                details.insert(
+                    "idle_connections".to_string(),
+                    self.pool.num_idle().to_string(),
+                );
+                if response_time_ms > self.slow_threshold_ms {
+                    HealthState::Warning
+                } else {
+                    HealthState::Healthy
+                }
+            }
+            Ok(Err(e)) => {
+                details.insert("error".to_string(), e.to_string());
+                HealthState::Down
+            }
+            Err(_) => {
+                details.insert("error".to_string(), "query timed out after 5s".to_string());
+                HealthState::Down
+            }
+        };
+        details.insert("response_time_ms".to_string(), response_time_ms.to_string());
+
+        Ok(HealthStatus {
+            service: self.name.clone(),
+            status,
+            timestamp: Utc::now(),
+            details,
+            response_time_ms: Some(response_time_ms),
+        })
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Pings the read replicas: a replica that is down is a warning, since reads
+/// fall back to the primary
+pub struct ReplicaHealthCheck {
+    reads: ReadRouter<Postgres>,
+}
+
+impl ReplicaHealthCheck {
+    pub fn new(reads: ReadRouter<Postgres>) -> Self {
+        Self { reads }
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthCheck for ReplicaHealthCheck {
+    async fn check(&self) -> ErpResult<HealthStatus> {
+        let start_time = std::time::Instant::now();
+        let replicas = self.reads.check_replicas().await;
         let response_time_ms = start_time.elapsed().as_millis() as u64;
 
-        // Simulate random health status
-        let status = if response_time_ms > 100 {
+        let down = replicas.iter().filter(|replica| !replica.healthy).count();
+        let mut details: HashMap<String, String> = replicas
+            .into_iter()
+            .map(|replica| {
+                let state = if replica.healthy { "up" } else { "down" };
+                (replica.label, state.to_string())
+            })
+            .collect();
+        if down > 0 {
+            details.insert(
+                "error".to_string(),
+                format!("{} replica(s) down, reads fall back to the primary", down),
+            );
+        }
+
+        Ok(HealthStatus {
+            service: self.name().to_string(),
+            status: if down > 0 {
+                HealthState::Warning
+            } else {
+                HealthState::Healthy
+            },
+            timestamp: Utc::now(),
+            details,
+            response_time_ms: Some(response_time_ms),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "read_replicas"
+    }
+}
+
+/// Compares applied migrations with the files in `migrations_dir`:
+/// pending migrations are a warning, checksum conflicts are critical
+pub struct MigrationHealthCheck {
+    pool: PgPool,
+    migrations_dir: PathBuf,
+}
+
+impl MigrationHealthCheck {
+    pub fn new(pool: PgPool, migrations_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            pool,
+            migrations_dir: migrations_dir.into(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthCheck for MigrationHealthCheck {
+    async fn check(&self) -> ErpResult<HealthStatus> {
+        let mut runner = MigrationRunner::new(DatabaseMigrator::Postgres(PostgresMigrator::new(
+            self.pool.clone(),
+        )));
+        runner
+            .load_migrations_from_directory(&self.migrations_dir.to_string_lossy())
+            .map_err(|e| ErpError::io(format!("Migration loading failed: {}", e)))?;
+        let migration_status = runner
+            .get_migration_status()
+            .await
+            .map_err(|e| ErpError::database(format!("Status check failed: {}", e)))?;
+
+        let mut details = HashMap::new();
+        details.insert(
+            "applied".to_string(),
+            migration_status.applied_count().to_string(),
+        );
+        details.insert(
+            "pending".to_string(),
+            migration_status.pending_count().to_string(),
+        );
+        if let Some(latest) = migration_status.applied.iter().map(|m| &m.version).max() {
+            details.insert("latest".to_string(), latest.clone());
+        }
+        if migration_status.has_conflicts() {
+            details.insert(
+                "checksum_conflicts".to_string(),
+                migration_status.conflicts.join(","),
+            );
+        }
+
+        let status = if migration_status.has_conflicts() {
+            HealthState::Critical
+        } else if migration_status.pending_count() > 0 {
             HealthState::Warning
         } else {
             HealthState::Healthy
         };
 
         Ok(HealthStatus {
-            service: self.name.clone(),
+            service: "migrations".to_string(),
             status,
             timestamp: Utc::now(),
-            details: [("response_time_ms".to_string(), response_time_ms.to_string())]
-                .iter()
-                .cloned()
-                .collect(),
-            response_time_ms: Some(response_time_ms),
+            details,
+            response_time_ms: None,
         })
     }
 
     fn name(&self) -> &str {
-        &self.name
+        "migrations"
+    }
+}
+
+/// Usage of the file system that holds `path`
+pub struct DiskSpaceHealthCheck {
+    path: PathBuf,
+    warning_percent: f32,
+    critical_percent: f32,
+}
+
+impl DiskSpaceHealthCheck {
+    pub fn new(path: impl Into<PathBuf>, warning_percent: f32, critical_percent: f32) -> Self {
+        Self {
+            path: path.into(),
+            warning_percent,
+            critical_percent,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthCheck for DiskSpaceHealthCheck {
+    async fn check(&self) -> ErpResult<HealthStatus> {
+        let path = self.path.canonicalize()?;
+        let mut system = System::new();
+        system.refresh_disks_list();
+        system.refresh_disks();
+
+        // The disk with the longest mount point containing the path
+        let disk = system
+            .disks()
+            .iter()
+            .filter(|disk| path.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .ok_or_else(|| ErpError::not_found("disk", path.display().to_string()))?;
+
+        let total = disk.total_space();
+        let available = disk.available_space();
+        let used_percent = if total == 0 {
+            0.0
+        } else {
+            (total - available) as f32 / total as f32 * 100.0
+        };
+
+        let details = [
+            ("path".to_string(), path.display().to_string()),
+            (
+                "mount_point".to_string(),
+                disk.mount_point().display().to_string(),
+            ),
+            ("used_percent".to_string(), format!("{:.1}", used_percent)),
+            ("available_bytes".to_string(), available.to_string()),
+            ("total_bytes".to_string(), total.to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        Ok(HealthStatus {
+            service: "disk_space".to_string(),
+            status: disk_state(used_percent, self.warning_percent, self.critical_percent),
+            timestamp: Utc::now(),
+            details,
+            response_time_ms: None,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "disk_space"
+    }
+}
+
+fn disk_state(used_percent: f32, warning_percent: f32, critical_percent: f32) -> HealthState {
+    if used_percent >= critical_percent {
+        HealthState::Critical
+    } else if used_percent >= warning_percent {
+        HealthState::Warning
+    } else {
+        HealthState::Healthy
+    }
+}
+
+/// Age of the newest successful backup recorded in the backup manifest.
+/// Older than `max_age_hours` is a warning, twice that or no backup at all
+/// is critical.
+pub struct BackupFreshnessHealthCheck {
+    backup_dir: PathBuf,
+    max_age_hours: i64,
+}
+
+impl BackupFreshnessHealthCheck {
+    pub fn new(backup_dir: impl Into<PathBuf>, max_age_hours: i64) -> Self {
+        Self {
+            backup_dir: backup_dir.into(),
+            max_age_hours,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl HealthCheck for BackupFreshnessHealthCheck {
+    async fn check(&self) -> ErpResult<HealthStatus> {
+        let repository = ManifestBackupRepository::new(&self.backup_dir);
+        let latest = repository
+            .list_backups(None)
+            .await?
+            .into_iter()
+            .filter(|b| matches!(b.status, BackupStatus::Completed | BackupStatus::Verified))
+            .max_by_key(|b| b.timestamp);
+
+        let mut details = HashMap::new();
+        details.insert(
+            "backup_dir".to_string(),
+            self.backup_dir.display().to_string(),
+        );
+        details.insert("max_age_hours".to_string(), self.max_age_hours.to_string());
+
+        let status = match &latest {
+            Some(backup) => {
+                let age = Utc::now() - backup.timestamp;
+                details.insert("latest_backup".to_string(), backup.id.to_string());
+                details.insert("age_hours".to_string(), age.num_hours().to_string());
+                backup_state(age, self.max_age_hours)
+            }
+            None => {
+                details.insert("error".to_string(), "no completed backup".to_string());
+                HealthState::Critical
+            }
+        };
+
+        Ok(HealthStatus {
+            service: "backup".to_string(),
+            status,
+            timestamp: Utc::now(),
+            details,
+            response_time_ms: None,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "backup"
+    }
+}
+
+fn backup_state(age: Duration, max_age_hours: i64) -> HealthState {
+    if age > Duration::hours(max_age_hours * 2) {
+        HealthState::Critical
+    } else if age > Duration::hours(max_age_hours) {
+        HealthState::Warning
+    } else {
+        HealthState::Healthy
+    }
+}
+
+/// Stores metrics, health check results and alerts in PostgreSQL
+pub struct PostgresMetricsRepository {
+    pool: PgPool,
+}
+
+impl PostgresMetricsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn store_metrics<T: Serialize>(
+        &self,
+        kind: &str,
+        recorded_at: DateTime<Utc>,
+        metrics: &T,
+    ) -> ErpResult<()> {
+        let data =
+            serde_json::to_string(metrics).map_err(|e| ErpError::serialization(e.to_string()))?;
+        sqlx::query(
+            "INSERT INTO monitoring_metrics (kind, recorded_at, data) VALUES ($1, $2, $3::jsonb)",
+        )
+        .bind(kind)
+        .bind(recorded_at)
+        .bind(data)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_metrics<T: serde::de::DeserializeOwned>(
+        &self,
+        kind: &str,
+        since: DateTime<Utc>,
+    ) -> ErpResult<Vec<T>> {
+        let rows: Vec<String> = sqlx::query_scalar(
+            "SELECT data::text FROM monitoring_metrics \
+             WHERE kind = $1 AND recorded_at > $2 ORDER BY recorded_at",
+        )
+        .bind(kind)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|data| {
+                serde_json::from_str(data).map_err(|e| ErpError::serialization(e.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Unit enum variant name, e.g. `Critical`
+fn variant_name<T: Serialize>(value: &T) -> ErpResult<String> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => Ok(name),
+        _ => Err(ErpError::serialization("Expected a unit enum variant")),
+    }
+}
+
+fn from_variant_name<T: serde::de::DeserializeOwned>(name: String) -> ErpResult<T> {
+    serde_json::from_value(serde_json::Value::String(name))
+        .map_err(|e| ErpError::serialization(e.to_string()))
+}
+
+fn json_map(data: &str) -> ErpResult<HashMap<String, String>> {
+    serde_json::from_str(data).map_err(|e| ErpError::serialization(e.to_string()))
+}
+
+#[derive(sqlx::FromRow)]
+struct HealthStatusRow {
+    service: String,
+    status: String,
+    checked_at: DateTime<Utc>,
+    details: String,
+    response_time_ms: Option<i64>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AlertRow {
+    id: Uuid,
+    raised_at: DateTime<Utc>,
+    severity: String,
+    category: String,
+    title: String,
+    description: String,
+    metrics: String,
+    resolved: bool,
+    resolved_at: Option<DateTime<Utc>>,
+}
+
+#[async_trait::async_trait]
+impl MetricsRepository for PostgresMetricsRepository {
+    async fn store_system_metrics(&self, metrics: &SystemMetrics) -> ErpResult<()> {
+        self.store_metrics("system", metrics.timestamp, metrics)
+            .await
+    }
+
+    async fn store_application_metrics(&self, metrics: &ApplicationMetrics) -> ErpResult<()> {
+        self.store_metrics("application", metrics.timestamp, metrics)
+            .await
+    }
+
+    async fn store_security_metrics(&self, metrics: &SecurityMetrics) -> ErpResult<()> {
+        self.store_metrics("security", metrics.timestamp, metrics)
+            .await
+    }
+
+    async fn store_health_status(&self, status: &HealthStatus) -> ErpResult<()> {
+        let details = serde_json::to_string(&status.details)
+            .map_err(|e| ErpError::serialization(e.to_string()))?;
+        sqlx::query(
+            "INSERT INTO health_check_results \
+             (service, status, checked_at, details, response_time_ms) \
+             VALUES ($1, $2, $3, $4::jsonb, $5)",
+        )
+        .bind(&status.service)
+        .bind(variant_name(&status.status)?)
+        .bind(status.timestamp)
+        .bind(details)
+        .bind(status.response_time_ms.map(|ms| ms as i64))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Inserts a new alert or records its resolution
+    async fn store_alert(&self, alert: &Alert) -> ErpResult<()> {
+        let metrics = serde_json::to_string(&alert.metrics)
+            .map_err(|e| ErpError::serialization(e.to_string()))?;
+        sqlx::query(
+            "INSERT INTO monitoring_alerts \
+             (id, raised_at, severity, category, title, description, metrics, resolved, \
+              resolved_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7::jsonb, $8, $9) \
+             ON CONFLICT (id) DO UPDATE SET resolved = EXCLUDED.resolved, \
+              resolved_at = EXCLUDED.resolved_at",
+        )
+        .bind(alert.id)
+        .bind(alert.timestamp)
+        .bind(variant_name(&alert.severity)?)
+        .bind(variant_name(&alert.category)?)
+        .bind(&alert.title)
+        .bind(&alert.description)
+        .bind(metrics)
+        .bind(alert.resolved)
+        .bind(alert.resolved_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_system_metrics(&self, since: DateTime<Utc>) -> ErpResult<Vec<SystemMetrics>> {
+        self.get_metrics("system", since).await
+    }
+
+    async fn get_application_metrics(
+        &self,
+        since: DateTime<Utc>,
+    ) -> ErpResult<Vec<ApplicationMetrics>> {
+        self.get_metrics("application", since).await
+    }
+
+    async fn get_security_metrics(&self, since: DateTime<Utc>) -> ErpResult<Vec<SecurityMetrics>> {
+        self.get_metrics("security", since).await
+    }
+
+    /// Latest result of each service
+    async fn get_health_status(&self, service: Option<&str>) -> ErpResult<Vec<HealthStatus>> {
+        let rows = sqlx::query_as::<_, HealthStatusRow>(
+            "SELECT DISTINCT ON (service) service, status, checked_at, details::text AS details, \
+             response_time_ms FROM health_check_results \
+             WHERE ($1::text IS NULL OR service = $1) \
+             ORDER BY service, checked_at DESC",
+        )
+        .bind(service)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(HealthStatus {
+                    service: row.service,
+                    status: from_variant_name(row.status)?,
+                    timestamp: row.checked_at,
+                    details: json_map(&row.details)?,
+                    response_time_ms: row.response_time_ms.map(|ms| ms as u64),
+                })
+            })
+            .collect()
+    }
+
+    async fn get_alerts(&self, resolved: Option<bool>) -> ErpResult<Vec<Alert>> {
+        let rows = sqlx::query_as::<_, AlertRow>(
+            "SELECT id, raised_at, severity, category, title, description, \
+             metrics::text AS metrics, resolved, resolved_at FROM monitoring_alerts \
+             WHERE ($1::boolean IS NULL OR resolved = $1) ORDER BY raised_at DESC",
+        )
+        .bind(resolved)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(Alert {
+                    id: row.id,
+                    timestamp: row.raised_at,
+                    severity: from_variant_name(row.severity)?,
+                    category: from_variant_name(row.category)?,
+                    title: row.title,
+                    description: row.description,
+                    metrics: json_map(&row.metrics)?,
+                    resolved: row.resolved,
+                    resolved_at: row.resolved_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Removes metrics, health results and resolved alerts older than `before`
+    async fn cleanup_old_metrics(&self, before: DateTime<Utc>) -> ErpResult<u64> {
+        let mut tx = self.pool.begin().await?;
+        let mut removed = 0;
+        for sql in [
+            "DELETE FROM monitoring_metrics WHERE recorded_at < $1",
+            "DELETE FROM health_check_results WHERE checked_at < $1",
+            "DELETE FROM monitoring_alerts WHERE resolved AND resolved_at < $1",
+        ] {
+            removed += sqlx::query(sql)
+                .bind(before)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+        tx.commit().await?;
+        Ok(removed)
     }
 }
 
@@ -738,7 +1383,10 @@ impl MetricsRepository for MockMetricsRepository {
 
     async fn store_alert(&self, alert: &Alert) -> ErpResult<()> {
         let mut alerts = self.alerts.lock().unwrap();
-        alerts.push(alert.clone());
+        match alerts.iter_mut().find(|a| a.id == alert.id) {
+            Some(existing) => *existing = alert.clone(),
+            None => alerts.push(alert.clone()),
+        }
         Ok(())
     }
 
@@ -923,18 +1571,164 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_database_health_check() {
-        let health_check = DatabaseHealthCheck::new();
+    async fn test_database_health_check_reports_unreachable_database() {
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(std::time::Duration::from_secs(1))
+            .connect_lazy("postgres://erp@127.0.0.1:1/erp")
+            .unwrap();
+        let health_check = DatabaseHealthCheck::new(pool);
         let status = health_check.check().await.unwrap();
 
         assert_eq!(status.service, "database");
-        assert!(matches!(
-            status.status,
-            HealthState::Healthy | HealthState::Warning
-        ));
+        assert_eq!(status.status, HealthState::Down);
+        assert!(status.details.contains_key("error"));
         assert!(status.response_time_ms.is_some());
     }
 
+    #[test]
+    fn test_overall_state() {
+        let status = |state: HealthState| HealthStatus {
+            status: state,
+            ..HealthStatus::down("x", String::new())
+        };
+        assert_eq!(overall_state(&[]), HealthState::Healthy);
+        assert_eq!(
+            overall_state(&[status(HealthState::Healthy), status(HealthState::Warning)]),
+            HealthState::Warning
+        );
+        assert_eq!(
+            overall_state(&[status(HealthState::Down), status(HealthState::Critical)]),
+            HealthState::Down
+        );
+    }
+
+    #[test]
+    fn test_threshold_states() {
+        assert_eq!(disk_state(50.0, 80.0, 90.0), HealthState::Healthy);
+        assert_eq!(disk_state(85.0, 80.0, 90.0), HealthState::Warning);
+        assert_eq!(disk_state(90.0, 80.0, 90.0), HealthState::Critical);
+
+        assert_eq!(backup_state(Duration::hours(2), 26), HealthState::Healthy);
+        assert_eq!(backup_state(Duration::hours(30), 26), HealthState::Warning);
+        assert_eq!(backup_state(Duration::hours(60), 26), HealthState::Critical);
+    }
+
+    #[tokio::test]
+    async fn test_backup_freshness_health_check() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let check = BackupFreshnessHealthCheck::new(temp_dir.path(), 26);
+        assert_eq!(check.check().await.unwrap().status, HealthState::Critical);
+
+        let repository = ManifestBackupRepository::new(temp_dir.path());
+        let mut backup = crate::core::ops::backup::BackupMetadata {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now() - Duration::hours(30),
+            backup_type: crate::core::ops::backup::BackupType::Full,
+            size_bytes: 0,
+            compressed: true,
+            encrypted: false,
+            file_path: temp_dir.path().join("backup.tar.gz"),
+            remote_path: None,
+            checksum: String::new(),
+            databases: Vec::new(),
+            file_count: 0,
+            status: BackupStatus::Completed,
+            error_message: None,
+            duration_seconds: None,
+        };
+        repository.store_metadata(&backup).await.unwrap();
+        let status = check.check().await.unwrap();
+        assert_eq!(status.status, HealthState::Warning);
+        assert_eq!(status.details["age_hours"], "30");
+
+        // Failed backups do not count
+        backup.id = Uuid::new_v4();
+        backup.timestamp = Utc::now();
+        backup.status = BackupStatus::Failed;
+        repository.store_metadata(&backup).await.unwrap();
+        assert_eq!(check.check().await.unwrap().status, HealthState::Warning);
+    }
+
+    struct ToggleHealthCheck {
+        healthy: Arc<Mutex<bool>>,
+    }
+
+    #[async_trait::async_trait]
+    impl HealthCheck for ToggleHealthCheck {
+        async fn check(&self) -> ErpResult<HealthStatus> {
+            if !*self.healthy.lock().unwrap() {
+                return Err(ErpError::internal("connection refused"));
+            }
+            Ok(HealthStatus {
+                service: "toggle".to_string(),
+                status: HealthState::Healthy,
+                timestamp: Utc::now(),
+                details: HashMap::new(),
+                response_time_ms: None,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "toggle"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_cycle_raises_and_resolves_health_alerts() {
+        let repository = MockMetricsRepository::new();
+        let config = MonitoringConfig {
+            cpu_warning_threshold: f32::MAX,
+            cpu_critical_threshold: f32::MAX,
+            memory_critical_threshold: f32::MAX,
+            disk_critical_threshold: f32::MAX,
+            ..MonitoringConfig::default()
+        };
+        let service = MonitoringService::new(config, Arc::new(repository.clone()));
+        let healthy = Arc::new(Mutex::new(false));
+        service.add_health_check(Box::new(ToggleHealthCheck {
+            healthy: healthy.clone(),
+        }));
+
+        let cycle = service.run_cycle().await.unwrap();
+        assert_eq!(cycle.health[0].status, HealthState::Down);
+        assert_eq!(cycle.active_alerts.len(), 1);
+        assert_eq!(cycle.active_alerts[0].severity, AlertSeverity::Critical);
+
+        // The same condition does not raise a second alert
+        let cycle = service.run_cycle().await.unwrap();
+        assert_eq!(cycle.active_alerts.len(), 1);
+        assert_eq!(repository.get_alerts(None).await.unwrap().len(), 1);
+        assert_eq!(
+            repository
+                .get_system_metrics(Utc::now() - Duration::minutes(1))
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // A restarted monitor picks the unresolved alert up and resolves it
+        let restarted = MonitoringService::new(
+            MonitoringConfig {
+                cpu_warning_threshold: f32::MAX,
+                cpu_critical_threshold: f32::MAX,
+                memory_critical_threshold: f32::MAX,
+                disk_critical_threshold: f32::MAX,
+                ..MonitoringConfig::default()
+            },
+            Arc::new(repository.clone()),
+        );
+        restarted.add_health_check(Box::new(ToggleHealthCheck {
+            healthy: healthy.clone(),
+        }));
+        assert_eq!(restarted.load_active_alerts().await.unwrap(), 1);
+
+        *healthy.lock().unwrap() = true;
+        let cycle = restarted.run_cycle().await.unwrap();
+        assert!(cycle.active_alerts.is_empty());
+        assert_eq!(repository.get_alerts(Some(true)).await.unwrap().len(), 1);
+    }
+
     #[test]
     fn test_monitoring_config() {
         let config = MonitoringConfig::default();
@@ -948,10 +1742,10 @@ mod tests {
     #[tokio::test]
     async fn test_monitoring_service_creation() {
         let config = MonitoringConfig::default();
-        let repository = Box::new(MockMetricsRepository::new());
+        let repository = Arc::new(MockMetricsRepository::new());
         let service = MonitoringService::new(config, repository);
 
-        let health_check = Box::new(DatabaseHealthCheck::new());
+        let health_check = Box::new(DiskSpaceHealthCheck::new(".", 80.0, 90.0));
         service.add_health_check(health_check);
 
         // Test metrics update
@@ -0,0 +1,320 @@
+//! Transparent field-level encryption for personally identifiable information.
+//!
+//! Encrypted columns hold `enc:v1:<nonce>:<ciphertext>` (both base64). Values
+//! without the prefix are legacy plaintext and are returned unchanged, so rows
+//! written before encryption was enabled keep working until `erp security
+//! rotate-keys` encrypts them.
+//!
+//! Lookups use blind indexes: an HMAC of the normalized value that can be
+//! compared for equality without revealing the value itself.
+
+use base64::{engine::general_purpose, Engine as _};
+use std::sync::Arc;
+
+use super::encryption::{EncryptedData, KeyRotationManager};
+use crate::core::config::SecurityConfig;
+use crate::utils::crypto::hmac_sha256;
+use crate::utils::error::{ErpError, ErpResult};
+
+/// Prefix marking an encrypted column value
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+const ALGORITHM: &str = "AES-256-GCM";
+/// Domain separation for deriving the blind index key from an encryption key
+const BLIND_INDEX_CONTEXT: &[u8] = b"erp-pii-blind-index/v1";
+
+/// Which normalization a blind index applies before hashing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PiiKind {
+    /// Case-insensitive, surrounding whitespace ignored
+    Email,
+    /// Only digits (and a leading `+`) are significant
+    Phone,
+}
+
+impl PiiKind {
+    fn normalize(self, value: &str) -> String {
+        let value = value.trim();
+        match self {
+            PiiKind::Email => value.to_lowercase(),
+            PiiKind::Phone => value
+                .char_indices()
+                .filter(|(i, c)| c.is_ascii_digit() || (*i == 0 && *c == '+'))
+                .map(|(_, c)| c)
+                .collect(),
+        }
+    }
+}
+
+/// Outcome of re-encrypting a single stored value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rotated {
+    /// Re-encrypted under the current key
+    ReEncrypted(String),
+    /// Legacy plaintext encrypted for the first time
+    Encrypted(String),
+}
+
+impl Rotated {
+    pub fn into_value(self) -> String {
+        match self {
+            Rotated::ReEncrypted(value) | Rotated::Encrypted(value) => value,
+        }
+    }
+}
+
+pub struct PiiCipher {
+    keys: KeyRotationManager,
+    index_keys: Vec<Vec<u8>>,
+}
+
+impl std::fmt::Debug for PiiCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PiiCipher")
+            .field("rotation_id", &self.keys.get_rotation_id())
+            .field("has_previous_key", &(self.index_keys.len() > 1))
+            .finish()
+    }
+}
+
+impl PiiCipher {
+    /// `previous_key` is only needed while a key rotation is in progress.
+    pub fn new(current_key: Vec<u8>, previous_key: Option<Vec<u8>>) -> ErpResult<Self> {
+        let mut index_keys = vec![derive_index_key(&current_key)];
+        let keys = match previous_key {
+            Some(previous) => {
+                index_keys.push(derive_index_key(&previous));
+                let mut keys = KeyRotationManager::new(previous)?;
+                keys.rotate_key(current_key)?;
+                keys
+            }
+            None => KeyRotationManager::new(current_key)?,
+        };
+
+        Ok(Self { keys, index_keys })
+    }
+
+    /// Builds the cipher from `[security]` settings. Returns `None` when
+    /// `encrypt_sensitive_data` is off.
+    pub fn from_config(config: &SecurityConfig) -> ErpResult<Option<Arc<Self>>> {
+        if !config.encrypt_sensitive_data {
+            return Ok(None);
+        }
+
+        let current = config
+            .pii_encryption_key
+            .as_deref()
+            .filter(|key| !key.trim().is_empty())
+            .ok_or_else(|| {
+                ErpError::config(
+                    "security.encrypt_sensitive_data가 켜져 있지만 PII 암호화 키가 없습니다 \
+                     (PII_ENCRYPTION_KEY 설정 또는 'erp security generate-key'로 생성)",
+                )
+            })?;
+        let previous = config
+            .pii_previous_key
+            .as_deref()
+            .filter(|key| !key.trim().is_empty())
+            .map(|key| decode_key("pii_previous_key", key))
+            .transpose()?;
+
+        Ok(Some(Arc::new(Self::new(
+            decode_key("pii_encryption_key", current)?,
+            previous,
+        )?)))
+    }
+
+    /// Encrypts a value for storage. Empty values are stored as-is.
+    pub fn encrypt(&self, value: &str) -> ErpResult<String> {
+        if value.is_empty() {
+            return Ok(String::new());
+        }
+        Ok(format_stored(&self.keys.encrypt(value)?))
+    }
+
+    pub fn encrypt_opt(&self, value: Option<&str>) -> ErpResult<Option<String>> {
+        value.map(|value| self.encrypt(value)).transpose()
+    }
+
+    /// Decrypts a stored value; legacy plaintext is returned unchanged.
+    pub fn decrypt(&self, stored: &str) -> ErpResult<String> {
+        match parse_stored(stored)? {
+            Some(data) => self.keys.decrypt(&data),
+            None => Ok(stored.to_string()),
+        }
+    }
+
+    pub fn decrypt_opt(&self, stored: Option<String>) -> ErpResult<Option<String>> {
+        stored.map(|value| self.decrypt(&value)).transpose()
+    }
+
+    /// Blind index under the current key, stored next to the ciphertext
+    pub fn blind_index(&self, kind: PiiKind, value: &str) -> Option<String> {
+        let normalized = kind.normalize(value);
+        if normalized.is_empty() {
+            return None;
+        }
+        Some(hmac_sha256(&self.index_keys[0], normalized.as_bytes()))
+    }
+
+    /// Blind indexes to match during lookups: current key first, then the
+    /// previous key so rows not yet rotated are still found.
+    pub fn lookup_indexes(&self, kind: PiiKind, value: &str) -> Vec<String> {
+        let normalized = kind.normalize(value);
+        if normalized.is_empty() {
+            return Vec::new();
+        }
+        self.index_keys
+            .iter()
+            .map(|key| hmac_sha256(key, normalized.as_bytes()))
+            .collect()
+    }
+
+    /// Re-encrypts a stored value under the current key via
+    /// [`KeyRotationManager::re_encrypt`]; plaintext is encrypted.
+    pub fn rotate(&self, stored: &str) -> ErpResult<Rotated> {
+        if stored.is_empty() {
+            return Ok(Rotated::ReEncrypted(String::new()));
+        }
+        match parse_stored(stored)? {
+            Some(data) => Ok(Rotated::ReEncrypted(format_stored(
+                &self.keys.re_encrypt(&data)?,
+            ))),
+            None => Ok(Rotated::Encrypted(self.encrypt(stored)?)),
+        }
+    }
+}
+
+/// Whether a column value is in encrypted form
+pub fn is_encrypted(stored: &str) -> bool {
+    stored.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Base64 encoded 32-byte key, as produced by `erp security generate-key`
+pub fn decode_key(field: &str, encoded: &str) -> ErpResult<Vec<u8>> {
+    let key = general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|_| ErpError::validation(field, "base64로 인코딩된 키여야 합니다"))?;
+    if key.len() != 32 {
+        return Err(ErpError::validation(field, "키는 32바이트여야 합니다"));
+    }
+    Ok(key)
+}
+
+pub fn encode_key(key: &[u8]) -> String {
+    general_purpose::STANDARD.encode(key)
+}
+
+fn derive_index_key(encryption_key: &[u8]) -> Vec<u8> {
+    hex::decode(hmac_sha256(encryption_key, BLIND_INDEX_CONTEXT))
+        .expect("hmac_sha256 returns valid hex")
+}
+
+fn format_stored(data: &EncryptedData) -> String {
+    format!("{}{}:{}", ENCRYPTED_PREFIX, data.nonce, data.ciphertext)
+}
+
+fn parse_stored(stored: &str) -> ErpResult<Option<EncryptedData>> {
+    let Some(body) = stored.strip_prefix(ENCRYPTED_PREFIX) else {
+        return Ok(None);
+    };
+    let (nonce, ciphertext) = body
+        .split_once(':')
+        .ok_or_else(|| ErpError::validation("ciphertext", "암호화된 값의 형식이 잘못되었습니다"))?;
+    Ok(Some(EncryptedData::new(
+        ciphertext.to_string(),
+        nonce.to_string(),
+        ALGORITHM.to_string(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::security::encryption::EncryptionService;
+
+    fn cipher() -> PiiCipher {
+        PiiCipher::new(EncryptionService::generate_key(), None).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_and_legacy_plaintext() {
+        let cipher = cipher();
+
+        let stored = cipher.encrypt("jane@example.com").unwrap();
+        assert!(is_encrypted(&stored));
+        assert!(!stored.contains("jane"));
+        assert_eq!(cipher.decrypt(&stored).unwrap(), "jane@example.com");
+
+        // Same plaintext encrypts differently each time
+        assert_ne!(stored, cipher.encrypt("jane@example.com").unwrap());
+
+        assert_eq!(
+            cipher.decrypt("legacy@example.com").unwrap(),
+            "legacy@example.com"
+        );
+        assert_eq!(cipher.encrypt("").unwrap(), "");
+    }
+
+    #[test]
+    fn test_blind_index_normalization() {
+        let cipher = cipher();
+
+        assert_eq!(
+            cipher.blind_index(PiiKind::Email, " Jane@Example.com "),
+            cipher.blind_index(PiiKind::Email, "jane@example.com")
+        );
+        assert_eq!(
+            cipher.blind_index(PiiKind::Phone, "+1 (555) 010-2000"),
+            cipher.blind_index(PiiKind::Phone, "+15550102000")
+        );
+        assert_ne!(
+            cipher.blind_index(PiiKind::Phone, "+15550102000"),
+            cipher.blind_index(PiiKind::Phone, "15550102000")
+        );
+        assert_eq!(cipher.blind_index(PiiKind::Phone, "n/a"), None);
+
+        let other = PiiCipher::new(EncryptionService::generate_key(), None).unwrap();
+        assert_ne!(
+            cipher.blind_index(PiiKind::Email, "jane@example.com"),
+            other.blind_index(PiiKind::Email, "jane@example.com")
+        );
+    }
+
+    #[test]
+    fn test_rotation_re_encrypts_under_new_key() {
+        let old_key = EncryptionService::generate_key();
+        let new_key = EncryptionService::generate_key();
+        let old = PiiCipher::new(old_key.clone(), None).unwrap();
+        let stored = old.encrypt("010-1234-5678").unwrap();
+        let old_index = old.blind_index(PiiKind::Phone, "010-1234-5678").unwrap();
+
+        let rotating = PiiCipher::new(new_key.clone(), Some(old_key)).unwrap();
+        // Rows not yet rotated still decrypt and match lookups
+        assert_eq!(rotating.decrypt(&stored).unwrap(), "010-1234-5678");
+        assert!(rotating
+            .lookup_indexes(PiiKind::Phone, "01012345678")
+            .contains(&old_index));
+
+        let rotated = match rotating.rotate(&stored).unwrap() {
+            Rotated::ReEncrypted(value) => value,
+            other => panic!("unexpected {:?}", other),
+        };
+        assert!(matches!(
+            rotating.rotate("plain").unwrap(),
+            Rotated::Encrypted(_)
+        ));
+
+        let new_only = PiiCipher::new(new_key, None).unwrap();
+        assert_eq!(new_only.decrypt(&rotated).unwrap(), "010-1234-5678");
+        assert!(new_only.decrypt(&stored).is_err());
+    }
+
+    #[test]
+    fn test_decode_key_validation() {
+        let key = EncryptionService::generate_key();
+        assert_eq!(decode_key("key", &encode_key(&key)).unwrap(), key);
+        assert!(decode_key("key", "not base64!").is_err());
+        assert!(decode_key("key", &encode_key(&[0u8; 16])).is_err());
+    }
+}
@@ -1,11 +1,33 @@
-use crate::utils::error::ErpResult;
+use crate::utils::error::{ErpError, ErpResult};
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool, Row};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tracing::{debug, warn};
 
+/// Identifier under which password resets for the account `username` are recorded
+pub fn password_reset_identifier(username: &str) -> String {
+    format!("password_reset:{}", username.trim().to_lowercase())
+}
+
+/// Identifier under which password resets requested from `source` (e.g. a
+/// client address) are recorded, whichever account they target
+pub fn password_reset_source_identifier(source: &str) -> String {
+    format!("password_reset_source:{}", source.trim())
+}
+
+/// Identifier under which failed logins for `username` are recorded
+pub fn login_identifier(username: &str) -> String {
+    format!("login:{}", username.trim().to_lowercase())
+}
+
+/// Identifier under which bulk exports run by `username` are recorded
+pub fn export_identifier(username: &str) -> String {
+    format!("export:{}", username)
+}
+
 #[async_trait::async_trait]
 pub trait RateLimiterTrait: Send + Sync {
     async fn allow_request(&self, identifier: &str) -> ErpResult<bool>;
@@ -15,6 +37,10 @@ pub trait RateLimiterTrait: Send + Sync {
         limit: u64,
         window_seconds: u64,
     ) -> ErpResult<bool>;
+    /// Records a request without checking any limit (e.g. a failed login)
+    async fn record_request(&self, identifier: &str) -> ErpResult<()>;
+    /// Records a request only if fewer than `limit` were recorded within the window
+    async fn acquire(&self, identifier: &str, limit: u64, window_seconds: u64) -> ErpResult<bool>;
     async fn reset_limit(&self, identifier: &str) -> ErpResult<()>;
     async fn get_remaining_requests(&self, identifier: &str) -> ErpResult<RemainingRequests>;
 }
@@ -210,6 +236,44 @@ impl RateLimiter {
         Ok(true)
     }
 
+    pub async fn record_request(&self, identifier: &str) -> ErpResult<()> {
+        let mut windows = self.windows.lock().await;
+        windows
+            .entry(identifier.to_string())
+            .or_insert_with(RequestWindow::new)
+            .add_request(Utc::now());
+        Ok(())
+    }
+
+    pub async fn acquire(
+        &self,
+        identifier: &str,
+        limit: u64,
+        window_seconds: u64,
+    ) -> ErpResult<bool> {
+        if !self.config.enabled {
+            return Ok(true);
+        }
+
+        let now = Utc::now();
+        let mut windows = self.windows.lock().await;
+        let window = windows
+            .entry(identifier.to_string())
+            .or_insert_with(RequestWindow::new);
+
+        let cutoff = now - Duration::seconds(window_seconds as i64);
+        if window.count_requests_in_window(cutoff) >= limit as usize {
+            warn!(
+                "Rate limit exceeded for {}: {} requests in {} seconds",
+                identifier, limit, window_seconds
+            );
+            return Ok(false);
+        }
+
+        window.add_request(now);
+        Ok(true)
+    }
+
     pub async fn reset_limit(&self, identifier: &str) -> ErpResult<()> {
         let mut windows = self.windows.lock().await;
         windows.remove(identifier);
@@ -344,6 +408,14 @@ impl RateLimiterTrait for RateLimiter {
             .await
     }
 
+    async fn record_request(&self, identifier: &str) -> ErpResult<()> {
+        self.record_request(identifier).await
+    }
+
+    async fn acquire(&self, identifier: &str, limit: u64, window_seconds: u64) -> ErpResult<bool> {
+        self.acquire(identifier, limit, window_seconds).await
+    }
+
     async fn reset_limit(&self, identifier: &str) -> ErpResult<()> {
         self.reset_limit(identifier).await
     }
@@ -371,6 +443,235 @@ pub struct RateLimitStatistics {
     pub config: RateLimitConfig,
 }
 
+/// Requests recorded for one identifier in each of the configured windows
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowCounts {
+    /// Requests in the last 10 seconds
+    pub burst: u64,
+    pub minute: u64,
+    pub hour: u64,
+    pub day: u64,
+    pub last_request: Option<DateTime<Utc>>,
+}
+
+impl WindowCounts {
+    /// Name of the first window that has no room left
+    pub fn exceeded(&self, config: &RateLimitConfig) -> Option<&'static str> {
+        [
+            ("burst", self.burst, config.burst_size),
+            ("minute", self.minute, config.requests_per_minute),
+            ("hour", self.hour, config.requests_per_hour),
+            ("day", self.day, config.requests_per_day),
+        ]
+        .into_iter()
+        .find(|(_, used, limit)| used >= limit)
+        .map(|(window, _, _)| window)
+    }
+
+    fn remaining(&self, config: &RateLimitConfig, now: DateTime<Utc>) -> RemainingRequests {
+        RemainingRequests {
+            per_minute: config.requests_per_minute.saturating_sub(self.minute),
+            per_hour: config.requests_per_hour.saturating_sub(self.hour),
+            per_day: config.requests_per_day.saturating_sub(self.day),
+            reset_time_minute: now + Duration::minutes(1),
+            reset_time_hour: now + Duration::hours(1),
+            reset_time_day: now + Duration::days(1),
+        }
+    }
+}
+
+/// Rate limiter whose request log lives in the `rate_limit_events` table
+///
+/// Each CLI invocation is a new process, so in-memory counters never see more
+/// than one request. Here every request is a row, windows are measured against
+/// the database clock, and a transaction-scoped advisory lock per identifier
+/// makes check-and-record atomic across processes. Rows older than a day are
+/// pruned on insert, so windows longer than a day are effectively capped.
+pub struct PostgresRateLimiter {
+    pool: PgPool,
+    config: RateLimitConfig,
+}
+
+impl PostgresRateLimiter {
+    pub fn new(pool: PgPool, config: RateLimitConfig) -> Self {
+        Self { pool, config }
+    }
+
+    /// Requests recorded for `identifier` in each window
+    pub async fn usage(&self, identifier: &str) -> ErpResult<WindowCounts> {
+        let mut conn = self.pool.acquire().await?;
+        Self::window_counts(&mut conn, identifier).await
+    }
+
+    /// When the next request under `limit` per `window_seconds` will be
+    /// allowed, or `None` if it would be allowed now
+    pub async fn blocked_until(
+        &self,
+        identifier: &str,
+        limit: u64,
+        window_seconds: u64,
+    ) -> ErpResult<Option<DateTime<Utc>>> {
+        // The window frees up when the limit-th most recent request expires
+        let until: Option<DateTime<Utc>> = sqlx::query_scalar(
+            "SELECT occurred_at + make_interval(secs => $2) FROM rate_limit_events \
+             WHERE identifier = $1 AND occurred_at > NOW() - make_interval(secs => $2) \
+             ORDER BY occurred_at DESC OFFSET $3 LIMIT 1",
+        )
+        .bind(identifier)
+        .bind(window_seconds as f64)
+        .bind(limit.saturating_sub(1) as i64)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(until)
+    }
+
+    async fn lock(conn: &mut PgConnection, identifier: &str) -> ErpResult<()> {
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(identifier)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn window_counts(conn: &mut PgConnection, identifier: &str) -> ErpResult<WindowCounts> {
+        let row = sqlx::query(
+            "SELECT \
+             COUNT(*) FILTER (WHERE occurred_at > NOW() - INTERVAL '10 seconds') AS burst, \
+             COUNT(*) FILTER (WHERE occurred_at > NOW() - INTERVAL '1 minute') AS minute, \
+             COUNT(*) FILTER (WHERE occurred_at > NOW() - INTERVAL '1 hour') AS hour, \
+             COUNT(*) FILTER (WHERE occurred_at > NOW() - INTERVAL '1 day') AS day, \
+             MAX(occurred_at) AS last_request \
+             FROM rate_limit_events WHERE identifier = $1",
+        )
+        .bind(identifier)
+        .fetch_one(conn)
+        .await?;
+
+        Ok(WindowCounts {
+            burst: row.try_get::<i64, _>("burst")? as u64,
+            minute: row.try_get::<i64, _>("minute")? as u64,
+            hour: row.try_get::<i64, _>("hour")? as u64,
+            day: row.try_get::<i64, _>("day")? as u64,
+            last_request: row.try_get("last_request")?,
+        })
+    }
+
+    async fn count_since(
+        conn: &mut PgConnection,
+        identifier: &str,
+        window_seconds: u64,
+    ) -> ErpResult<u64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM rate_limit_events \
+             WHERE identifier = $1 AND occurred_at > NOW() - make_interval(secs => $2)",
+        )
+        .bind(identifier)
+        .bind(window_seconds as f64)
+        .fetch_one(conn)
+        .await?;
+        Ok(count as u64)
+    }
+
+    async fn insert(conn: &mut PgConnection, identifier: &str) -> ErpResult<()> {
+        sqlx::query("INSERT INTO rate_limit_events (identifier, occurred_at) VALUES ($1, NOW())")
+            .bind(identifier)
+            .execute(&mut *conn)
+            .await?;
+        let removed = sqlx::query(
+            "DELETE FROM rate_limit_events WHERE occurred_at <= NOW() - INTERVAL '1 day'",
+        )
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+        if removed > 0 {
+            debug!("Cleaned up {} old rate limit events", removed);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl RateLimiterTrait for PostgresRateLimiter {
+    async fn allow_request(&self, identifier: &str) -> ErpResult<bool> {
+        if !self.config.enabled {
+            return Ok(true);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        Self::lock(&mut tx, identifier).await?;
+        let counts = Self::window_counts(&mut tx, identifier).await?;
+        if let Some(window) = counts.exceeded(&self.config) {
+            warn!("{} limit exceeded for {}", window, identifier);
+            return Ok(false);
+        }
+        Self::insert(&mut tx, identifier).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn check_rate_limit(
+        &self,
+        identifier: &str,
+        limit: u64,
+        window_seconds: u64,
+    ) -> ErpResult<bool> {
+        if !self.config.enabled {
+            return Ok(true);
+        }
+
+        let mut conn = self.pool.acquire().await?;
+        let count = Self::count_since(&mut conn, identifier, window_seconds).await?;
+        if count >= limit {
+            warn!(
+                "Custom rate limit exceeded for {}: {} requests in {} seconds",
+                identifier, count, window_seconds
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    async fn record_request(&self, identifier: &str) -> ErpResult<()> {
+        let mut conn = self.pool.acquire().await?;
+        Self::insert(&mut conn, identifier).await
+    }
+
+    async fn acquire(&self, identifier: &str, limit: u64, window_seconds: u64) -> ErpResult<bool> {
+        if !self.config.enabled {
+            return Ok(true);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        Self::lock(&mut tx, identifier).await?;
+        if Self::count_since(&mut tx, identifier, window_seconds).await? >= limit {
+            warn!(
+                "Rate limit exceeded for {}: {} requests in {} seconds",
+                identifier, limit, window_seconds
+            );
+            return Ok(false);
+        }
+        Self::insert(&mut tx, identifier).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn reset_limit(&self, identifier: &str) -> ErpResult<()> {
+        let removed = sqlx::query("DELETE FROM rate_limit_events WHERE identifier = $1")
+            .bind(identifier)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to reset rate limit: {}", e)))?
+            .rows_affected();
+        debug!("Rate limit reset for {} ({} events)", identifier, removed);
+        Ok(())
+    }
+
+    async fn get_remaining_requests(&self, identifier: &str) -> ErpResult<RemainingRequests> {
+        let counts = self.usage(identifier).await?;
+        Ok(counts.remaining(&self.config, Utc::now()))
+    }
+}
+
 // Mock implementation for testing
 pub struct MockRateLimiter {
     allow_requests: bool,
@@ -406,6 +707,19 @@ impl MockRateLimiter {
         Ok(self.allow_requests)
     }
 
+    pub async fn record_request(&self, _identifier: &str) -> ErpResult<()> {
+        Ok(())
+    }
+
+    pub async fn acquire(
+        &self,
+        _identifier: &str,
+        _limit: u64,
+        _window_seconds: u64,
+    ) -> ErpResult<bool> {
+        Ok(self.allow_requests)
+    }
+
     pub async fn reset_limit(&self, _identifier: &str) -> ErpResult<()> {
         Ok(())
     }
@@ -438,6 +752,14 @@ impl RateLimiterTrait for MockRateLimiter {
             .await
     }
 
+    async fn record_request(&self, identifier: &str) -> ErpResult<()> {
+        self.record_request(identifier).await
+    }
+
+    async fn acquire(&self, identifier: &str, limit: u64, window_seconds: u64) -> ErpResult<bool> {
+        self.acquire(identifier, limit, window_seconds).await
+    }
+
     async fn reset_limit(&self, identifier: &str) -> ErpResult<()> {
         self.reset_limit(identifier).await
     }
@@ -690,6 +1012,66 @@ mod tests {
         assert!(!allowed);
     }
 
+    #[tokio::test]
+    async fn test_acquire_and_record() {
+        let limiter = RateLimiter::with_default_config();
+
+        // Failures recorded without a check still count towards the window
+        limiter.record_request("login:alice").await.unwrap();
+        limiter.record_request("login:alice").await.unwrap();
+        assert!(!limiter
+            .check_rate_limit("login:alice", 2, 60)
+            .await
+            .unwrap());
+
+        assert!(limiter.acquire("export:bob", 2, 3600).await.unwrap());
+        assert!(limiter.acquire("export:bob", 2, 3600).await.unwrap());
+        assert!(!limiter.acquire("export:bob", 2, 3600).await.unwrap());
+
+        limiter.reset_limit("export:bob").await.unwrap();
+        assert!(limiter.acquire("export:bob", 2, 3600).await.unwrap());
+    }
+
+    #[test]
+    fn test_window_counts_exceeded() {
+        let config = RateLimitConfig {
+            requests_per_minute: 5,
+            requests_per_hour: 10,
+            requests_per_day: 20,
+            burst_size: 3,
+            ..RateLimitConfig::default()
+        };
+
+        let counts = WindowCounts {
+            burst: 1,
+            minute: 4,
+            hour: 9,
+            day: 19,
+            last_request: None,
+        };
+        assert_eq!(counts.exceeded(&config), None);
+
+        let counts = WindowCounts {
+            burst: 1,
+            minute: 2,
+            hour: 10,
+            day: 19,
+            last_request: None,
+        };
+        assert_eq!(counts.exceeded(&config), Some("hour"));
+
+        let remaining = counts.remaining(&config, Utc::now());
+        assert_eq!(remaining.per_minute, 3);
+        assert_eq!(remaining.per_hour, 0);
+        assert_eq!(remaining.per_day, 1);
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(login_identifier(" Alice "), "login:alice");
+        assert_eq!(export_identifier("bob"), "export:bob");
+    }
+
     #[test]
     fn test_rate_limit_strategies() {
         let strict = create_rate_limiter(RateLimitStrategy::Strict);
@@ -9,6 +9,15 @@ async fn main() {
     // CLI 파싱
     let cli = Cli::parse();
 
+    // 설정 검증은 잘못된 설정에서도 문제를 보고해야 하므로 로드 전에 실행
+    if cli.validates_config() {
+        if let Err(e) = erp_cli::cli::commands::ConfigHandler::validate().await {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // 실제 설정 로드
     let config = match erp_cli::core::config::AppConfig::load().await {
         Ok(config) => config,
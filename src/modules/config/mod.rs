@@ -6,5 +6,7 @@ pub mod repository;
 pub mod service;
 
 pub use models::*;
-pub use repository::ConfigRepository;
+pub use repository::{
+    open_config_repository, ConfigRepository, MockConfigRepository, SqliteConfigRepository,
+};
 pub use service::ConfigService;
@@ -58,14 +58,109 @@ impl ConfigItem {
 
     /// 마스킹된 값 반환 (비밀 설정인 경우)
     pub fn masked_value(&self) -> String {
-        if self.is_secret {
+        Self::mask(&self.value, self.is_secret)
+    }
+
+    /// 비밀 설정 값을 표시용으로 가림
+    pub fn mask(value: &str, is_secret: bool) -> String {
+        if is_secret {
             "*".repeat(8)
         } else {
-            self.value.clone()
+            value.to_string()
         }
     }
 }
 
+/// 설정 변경 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigChangeKind {
+    Create,
+    Update,
+    Delete,
+    Rollback,
+}
+
+impl ConfigChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigChangeKind::Create => "create",
+            ConfigChangeKind::Update => "update",
+            ConfigChangeKind::Delete => "delete",
+            ConfigChangeKind::Rollback => "rollback",
+        }
+    }
+}
+
+impl std::str::FromStr for ConfigChangeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "create" => Ok(ConfigChangeKind::Create),
+            "update" => Ok(ConfigChangeKind::Update),
+            "delete" => Ok(ConfigChangeKind::Delete),
+            "rollback" => Ok(ConfigChangeKind::Rollback),
+            other => Err(format!("Unknown config change kind: {}", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigChangeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 설정 변경 이력 (키별로 1부터 증가하는 버전)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigHistoryEntry {
+    pub id: Uuid,
+    pub key: String,
+    pub version: i32,
+    pub change: ConfigChangeKind,
+    /// 변경 전 값 (생성 시 없음)
+    pub old_value: Option<String>,
+    /// 변경 후 값 (삭제 시 없음)
+    pub new_value: Option<String>,
+    pub category: String,
+    /// 변경 전후 중 한 번이라도 비밀 설정이었는지
+    pub is_secret: bool,
+    /// 롤백이 복원한 버전
+    pub rolled_back_to: Option<i32>,
+    pub changed_by: String,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl ConfigHistoryEntry {
+    /// 표시용 사본 (비밀 설정 값은 마스킹)
+    pub fn masked(&self) -> Self {
+        let mask = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|value| ConfigItem::mask(value, self.is_secret))
+        };
+        Self {
+            old_value: mask(&self.old_value),
+            new_value: mask(&self.new_value),
+            ..self.clone()
+        }
+    }
+}
+
+/// 변경 이력 기록 요청 (버전과 시각은 저장소가 부여)
+#[derive(Debug, Clone)]
+pub struct CreateConfigHistoryRequest {
+    pub key: String,
+    pub change: ConfigChangeKind,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub category: String,
+    pub is_secret: bool,
+    pub rolled_back_to: Option<i32>,
+    pub changed_by: String,
+}
+
 /// 설정 생성을 위한 데이터 구조
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateConfigRequest {
@@ -249,6 +344,36 @@ mod tests {
         assert!(!config.is_readonly);
     }
 
+    #[test]
+    fn test_history_entry_masks_secret_values() {
+        let entry = ConfigHistoryEntry {
+            id: Uuid::new_v4(),
+            key: "smtp_password".to_string(),
+            version: 2,
+            change: ConfigChangeKind::Update,
+            old_value: Some("first".to_string()),
+            new_value: Some("second".to_string()),
+            category: "email".to_string(),
+            is_secret: true,
+            rolled_back_to: None,
+            changed_by: "kim".to_string(),
+            changed_at: Utc::now(),
+        };
+
+        let masked = entry.masked();
+        assert_eq!(masked.old_value.as_deref(), Some("********"));
+        assert_eq!(masked.new_value.as_deref(), Some("********"));
+        assert_eq!(
+            ConfigHistoryEntry {
+                is_secret: false,
+                ..entry.clone()
+            }
+            .masked()
+            .new_value,
+            entry.new_value
+        );
+    }
+
     #[test]
     fn test_config_item_masked_value() {
         let secret_config = ConfigItem::new(
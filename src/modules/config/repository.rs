@@ -1,26 +1,82 @@
 // Config Module Repository - Data Access Layer
 
+use crate::core::database::DatabaseBackend;
 use crate::core::database::DatabaseConnection;
+use crate::core::database::{MockUnitOfWork, UnitOfWork};
 use crate::modules::config::models::*;
 use crate::utils::error::{ErpError, ErpResult};
 use async_trait::async_trait;
-use sqlx::{postgres::PgRow, Row};
-use std::sync::Arc;
+use shaku::Interface;
+use sqlx::{postgres::PgRow, sqlite::SqliteRow, Postgres, Row, Sqlite, SqlitePool};
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 /// Config Repository Trait
 #[async_trait]
-pub trait ConfigRepositoryTrait: Send + Sync {
-    async fn create(&self, config: &CreateConfigRequest) -> ErpResult<ConfigItem>;
+pub trait ConfigRepositoryTrait: Interface {
+    /// 같은 백엔드의 다른 저장소와 함께 쓰는 작업 단위 시작
+    async fn begin(&self) -> ErpResult<UnitOfWork>;
+    async fn create_in(
+        &self,
+        uow: &mut UnitOfWork,
+        config: &CreateConfigRequest,
+    ) -> ErpResult<ConfigItem>;
+    async fn update_in(
+        &self,
+        uow: &mut UnitOfWork,
+        id: &Uuid,
+        update_data: &UpdateConfigRequest,
+    ) -> ErpResult<ConfigItem>;
+    async fn delete_in(&self, uow: &mut UnitOfWork, id: &Uuid) -> ErpResult<()>;
+    /// 변경 이력 추가 (키별 다음 버전을 부여)
+    async fn add_history_in(
+        &self,
+        uow: &mut UnitOfWork,
+        change: &CreateConfigHistoryRequest,
+    ) -> ErpResult<ConfigHistoryEntry>;
+
+    async fn create(&self, config: &CreateConfigRequest) -> ErpResult<ConfigItem> {
+        let mut uow = self.begin().await?;
+        let result = self.create_in(&mut uow, config).await;
+        uow.finish(result).await
+    }
     async fn get_by_key(&self, key: &str) -> ErpResult<Option<ConfigItem>>;
     async fn get_by_id(&self, id: &Uuid) -> ErpResult<Option<ConfigItem>>;
     async fn get_by_filter(&self, filter: &ConfigFilter) -> ErpResult<Vec<ConfigItem>>;
     async fn get_all(&self) -> ErpResult<Vec<ConfigItem>>;
-    async fn update(&self, id: &Uuid, update_data: &UpdateConfigRequest) -> ErpResult<ConfigItem>;
-    async fn delete(&self, id: &Uuid) -> ErpResult<()>;
+    async fn update(&self, id: &Uuid, update_data: &UpdateConfigRequest) -> ErpResult<ConfigItem> {
+        let mut uow = self.begin().await?;
+        let result = self.update_in(&mut uow, id, update_data).await;
+        uow.finish(result).await
+    }
+    async fn delete(&self, id: &Uuid) -> ErpResult<()> {
+        let mut uow = self.begin().await?;
+        let result = self.delete_in(&mut uow, id).await;
+        uow.finish(result).await
+    }
     async fn delete_by_key(&self, key: &str) -> ErpResult<()>;
     async fn key_exists(&self, key: &str) -> ErpResult<bool>;
     async fn get_categories(&self) -> ErpResult<Vec<String>>;
+    async fn add_history(
+        &self,
+        change: &CreateConfigHistoryRequest,
+    ) -> ErpResult<ConfigHistoryEntry> {
+        let mut uow = self.begin().await?;
+        let result = self.add_history_in(&mut uow, change).await;
+        uow.finish(result).await
+    }
+    /// 키의 변경 이력 (버전 오름차순)
+    async fn get_history(&self, key: &str) -> ErpResult<Vec<ConfigHistoryEntry>>;
+    async fn get_history_version(
+        &self,
+        key: &str,
+        version: i32,
+    ) -> ErpResult<Option<ConfigHistoryEntry>>;
+}
+
+/// 이력 행의 변경 종류 해석
+fn parse_change_kind(value: &str) -> ErpResult<ConfigChangeKind> {
+    value.parse().map_err(ErpError::database)
 }
 
 /// Config Repository Implementation
@@ -35,7 +91,7 @@ impl ConfigRepository {
 
     /// 데이터베이스 테이블 초기화
     pub async fn init_table(&self) -> ErpResult<()> {
-        let pool = self.db.pool();
+        let pool = self.db.pool()?;
 
         // PostgreSQL 호환 CREATE TABLE 문
         let create_table_query = r#"
@@ -70,9 +126,52 @@ impl ConfigRepository {
             .await
             .map_err(|e| ErpError::database(format!("Failed to create category index: {}", e)))?;
 
+        // 변경 이력은 설정이 삭제된 뒤에도 남도록 config_items를 참조하지 않음
+        let create_history_query = r#"
+            CREATE TABLE IF NOT EXISTS config_history (
+                id UUID PRIMARY KEY,
+                key VARCHAR(255) NOT NULL,
+                version INTEGER NOT NULL,
+                change_type VARCHAR(20) NOT NULL,
+                old_value TEXT,
+                new_value TEXT,
+                category VARCHAR(100) NOT NULL,
+                is_secret BOOLEAN NOT NULL DEFAULT FALSE,
+                rolled_back_to INTEGER,
+                changed_by VARCHAR(255) NOT NULL,
+                changed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+                UNIQUE (key, version)
+            )
+        "#;
+
+        sqlx::query(create_history_query)
+            .execute(pool)
+            .await
+            .map_err(|e| {
+                ErpError::database(format!("Failed to create config_history table: {}", e))
+            })?;
+
         Ok(())
     }
 
+    /// SQL 쿼리에서 ConfigHistoryEntry 생성
+    fn row_to_history_entry(row: &PgRow) -> ErpResult<ConfigHistoryEntry> {
+        let change: String = row.try_get("change_type").map_err(ErpError::Database)?;
+        Ok(ConfigHistoryEntry {
+            id: row.try_get("id").map_err(ErpError::Database)?,
+            key: row.try_get("key").map_err(ErpError::Database)?,
+            version: row.try_get("version").map_err(ErpError::Database)?,
+            change: parse_change_kind(&change)?,
+            old_value: row.try_get("old_value").map_err(ErpError::Database)?,
+            new_value: row.try_get("new_value").map_err(ErpError::Database)?,
+            category: row.try_get("category").map_err(ErpError::Database)?,
+            is_secret: row.try_get("is_secret").map_err(ErpError::Database)?,
+            rolled_back_to: row.try_get("rolled_back_to").map_err(ErpError::Database)?,
+            changed_by: row.try_get("changed_by").map_err(ErpError::Database)?,
+            changed_at: row.try_get("changed_at").map_err(ErpError::Database)?,
+        })
+    }
+
     /// SQL 쿼리에서 ConfigItem 생성
     fn row_to_config_item(&self, row: &PgRow) -> ErpResult<ConfigItem> {
         let id: Uuid = row
@@ -109,8 +208,15 @@ impl ConfigRepository {
 
 #[async_trait]
 impl ConfigRepositoryTrait for ConfigRepository {
-    async fn create(&self, config: &CreateConfigRequest) -> ErpResult<ConfigItem> {
-        let pool = self.db.pool();
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        UnitOfWork::begin_postgres(self.db.pool()?).await
+    }
+
+    async fn create_in(
+        &self,
+        uow: &mut UnitOfWork,
+        config: &CreateConfigRequest,
+    ) -> ErpResult<ConfigItem> {
         let config_item = ConfigItem::new(
             config.key.clone(),
             config.value.clone(),
@@ -135,7 +241,7 @@ impl ConfigRepositoryTrait for ConfigRepository {
             .bind(config_item.is_readonly)
             .bind(config_item.created_at)
             .bind(config_item.updated_at)
-            .execute(pool)
+            .execute(uow.postgres()?)
             .await
             .map_err(|e| ErpError::database(format!("Failed to create config item: {}", e)))?;
 
@@ -143,7 +249,7 @@ impl ConfigRepositoryTrait for ConfigRepository {
     }
 
     async fn get_by_key(&self, key: &str) -> ErpResult<Option<ConfigItem>> {
-        let pool = self.db.pool();
+        let pool = self.db.pool()?;
 
         let query = "SELECT * FROM config_items WHERE key = $1";
 
@@ -158,7 +264,7 @@ impl ConfigRepositoryTrait for ConfigRepository {
     }
 
     async fn get_by_id(&self, id: &Uuid) -> ErpResult<Option<ConfigItem>> {
-        let pool = self.db.pool();
+        let pool = self.db.pool()?;
 
         let query = "SELECT * FROM config_items WHERE id = $1";
 
@@ -173,7 +279,7 @@ impl ConfigRepositoryTrait for ConfigRepository {
     }
 
     async fn get_by_filter(&self, filter: &ConfigFilter) -> ErpResult<Vec<ConfigItem>> {
-        let pool = self.db.pool();
+        let pool = self.db.pool()?;
         let mut query_str = "SELECT * FROM config_items WHERE 1=1".to_string();
         let mut bind_params: Vec<String> = Vec::new();
 
@@ -216,7 +322,7 @@ impl ConfigRepositoryTrait for ConfigRepository {
     }
 
     async fn get_all(&self) -> ErpResult<Vec<ConfigItem>> {
-        let pool = self.db.pool();
+        let pool = self.db.pool()?;
 
         let query = "SELECT * FROM config_items ORDER BY category, key";
         let rows = sqlx::query(query)
@@ -232,90 +338,86 @@ impl ConfigRepositoryTrait for ConfigRepository {
         Ok(configs)
     }
 
-    async fn update(&self, id: &Uuid, update_data: &UpdateConfigRequest) -> ErpResult<ConfigItem> {
-        let pool = self.db.pool();
-
-        // 현재 설정 조회
-        let current = self
-            .get_by_id(id)
-            .await?
+    async fn update_in(
+        &self,
+        uow: &mut UnitOfWork,
+        id: &Uuid,
+        update_data: &UpdateConfigRequest,
+    ) -> ErpResult<ConfigItem> {
+        // 현재 설정 조회 (같은 작업 단위 안에서 잠금)
+        let row = sqlx::query("SELECT * FROM config_items WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(uow.postgres()?)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get config by id: {}", e)))?
             .ok_or_else(|| ErpError::not_found("ConfigItem", id.to_string()))?;
+        let current = self.row_to_config_item(&row)?;
 
         // 읽기 전용 설정 확인
         if current.is_readonly {
             return Err(ErpError::forbidden("Cannot update readonly configuration"));
         }
 
-        let mut query_parts = Vec::new();
-        let mut bind_params: Vec<String> = Vec::new();
+        let has_changes = update_data.value.is_some()
+            || update_data.description.is_some()
+            || update_data.category.is_some()
+            || update_data.is_secret.is_some();
+        if !has_changes {
+            return Ok(current); // 변경사항이 없으면 현재 값 반환
+        }
 
+        let mut builder = sqlx::QueryBuilder::<Postgres>::new("UPDATE config_items SET ");
+        let mut fields = builder.separated(", ");
         if let Some(value) = &update_data.value {
-            query_parts.push("value = ?");
-            bind_params.push(value.clone());
+            fields.push("value = ");
+            fields.push_bind_unseparated(value.clone());
         }
-
         if let Some(description) = &update_data.description {
-            query_parts.push("description = ?");
-            bind_params.push(description.clone());
+            fields.push("description = ");
+            fields.push_bind_unseparated(description.clone());
         }
-
         if let Some(category) = &update_data.category {
-            query_parts.push("category = ?");
-            bind_params.push(category.clone());
+            fields.push("category = ");
+            fields.push_bind_unseparated(category.clone());
         }
-
         if let Some(is_secret) = update_data.is_secret {
-            query_parts.push("is_secret = ?");
-            bind_params.push(is_secret.to_string());
+            fields.push("is_secret = ");
+            fields.push_bind_unseparated(is_secret);
         }
-
-        if query_parts.is_empty() {
-            return Ok(current); // 변경사항이 없으면 현재 값 반환
-        }
-
-        query_parts.push("updated_at = ?");
-        bind_params.push(chrono::Utc::now().to_rfc3339());
-
-        let query_str = format!(
-            "UPDATE config_items SET {} WHERE id = ?",
-            query_parts.join(", ")
-        );
-
-        let mut query = sqlx::query(&query_str);
-        for param in bind_params {
-            query = query.bind(param);
-        }
-        query = query.bind(id.to_string());
-
-        query
-            .execute(pool)
+        fields.push("updated_at = ");
+        fields.push_bind_unseparated(chrono::Utc::now());
+        builder.push(" WHERE id = ");
+        builder.push_bind(*id);
+        builder.push(" RETURNING *");
+
+        let row = builder
+            .build()
+            .fetch_one(uow.postgres()?)
             .await
             .map_err(|e| ErpError::database(format!("Failed to update config: {}", e)))?;
 
-        // 업데이트된 설정 조회 후 반환
-        self.get_by_id(id)
-            .await?
-            .ok_or_else(|| ErpError::internal("Updated config not found"))
+        self.row_to_config_item(&row)
     }
 
-    async fn delete(&self, id: &Uuid) -> ErpResult<()> {
-        let pool = self.db.pool();
-
+    async fn delete_in(&self, uow: &mut UnitOfWork, id: &Uuid) -> ErpResult<()> {
         // 설정이 존재하는지 먼저 확인
-        let config = self
-            .get_by_id(id)
-            .await?
-            .ok_or_else(|| ErpError::not_found("ConfigItem", id.to_string()))?;
+        let is_readonly: bool =
+            sqlx::query_scalar("SELECT is_readonly FROM config_items WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_optional(uow.postgres()?)
+                .await
+                .map_err(|e| ErpError::database(format!("Failed to get config by id: {}", e)))?
+                .ok_or_else(|| ErpError::not_found("ConfigItem", id.to_string()))?;
 
         // 읽기 전용 설정은 삭제 불가
-        if config.is_readonly {
+        if is_readonly {
             return Err(ErpError::forbidden("Cannot delete readonly configuration"));
         }
 
         let query = "DELETE FROM config_items WHERE id = $1";
         sqlx::query(query)
             .bind(id)
-            .execute(pool)
+            .execute(uow.postgres()?)
             .await
             .map_err(|e| ErpError::database(format!("Failed to delete config: {}", e)))?;
 
@@ -323,7 +425,7 @@ impl ConfigRepositoryTrait for ConfigRepository {
     }
 
     async fn delete_by_key(&self, key: &str) -> ErpResult<()> {
-        let pool = self.db.pool();
+        let pool = self.db.pool()?;
 
         // 설정이 존재하는지 먼저 확인
         let config = self
@@ -347,7 +449,7 @@ impl ConfigRepositoryTrait for ConfigRepository {
     }
 
     async fn key_exists(&self, key: &str) -> ErpResult<bool> {
-        let pool = self.db.pool();
+        let pool = self.db.pool()?;
 
         let query = "SELECT COUNT(*) as count FROM config_items WHERE key = $1";
         let row = sqlx::query(query)
@@ -364,7 +466,7 @@ impl ConfigRepositoryTrait for ConfigRepository {
     }
 
     async fn get_categories(&self) -> ErpResult<Vec<String>> {
-        let pool = self.db.pool();
+        let pool = self.db.pool()?;
 
         let query = "SELECT DISTINCT category FROM config_items ORDER BY category";
         let rows = sqlx::query(query)
@@ -382,6 +484,746 @@ impl ConfigRepositoryTrait for ConfigRepository {
 
         Ok(categories)
     }
+
+    async fn add_history_in(
+        &self,
+        uow: &mut UnitOfWork,
+        change: &CreateConfigHistoryRequest,
+    ) -> ErpResult<ConfigHistoryEntry> {
+        // 같은 키의 동시 기록은 UNIQUE (key, version) 제약으로 막힘
+        let query = r#"
+            INSERT INTO config_history (id, key, version, change_type, old_value, new_value, category, is_secret, rolled_back_to, changed_by, changed_at)
+            SELECT $1, $2, COALESCE(MAX(version), 0) + 1, $3, $4, $5, $6, $7, $8, $9, $10
+            FROM config_history WHERE key = $2
+            RETURNING *
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(Uuid::new_v4())
+            .bind(&change.key)
+            .bind(change.change.as_str())
+            .bind(&change.old_value)
+            .bind(&change.new_value)
+            .bind(&change.category)
+            .bind(change.is_secret)
+            .bind(change.rolled_back_to)
+            .bind(&change.changed_by)
+            .bind(chrono::Utc::now())
+            .fetch_one(uow.postgres()?)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to record config history: {}", e)))?;
+
+        Self::row_to_history_entry(&row)
+    }
+
+    async fn get_history(&self, key: &str) -> ErpResult<Vec<ConfigHistoryEntry>> {
+        let pool = self.db.pool()?;
+
+        let rows = sqlx::query("SELECT * FROM config_history WHERE key = $1 ORDER BY version")
+            .bind(key)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get config history: {}", e)))?;
+
+        rows.iter().map(Self::row_to_history_entry).collect()
+    }
+
+    async fn get_history_version(
+        &self,
+        key: &str,
+        version: i32,
+    ) -> ErpResult<Option<ConfigHistoryEntry>> {
+        let pool = self.db.pool()?;
+
+        let row = sqlx::query("SELECT * FROM config_history WHERE key = $1 AND version = $2")
+            .bind(key)
+            .bind(version)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get config history: {}", e)))?;
+
+        row.as_ref().map(Self::row_to_history_entry).transpose()
+    }
+}
+
+/// SQLite 설정 저장소
+pub struct SqliteConfigRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteConfigRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// 데이터베이스 테이블 초기화
+    pub async fn init_table(&self) -> ErpResult<()> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS config_items (
+                id BLOB PRIMARY KEY,
+                key TEXT NOT NULL UNIQUE,
+                value TEXT NOT NULL,
+                description TEXT,
+                category TEXT NOT NULL DEFAULT 'general',
+                is_secret INTEGER NOT NULL DEFAULT 0,
+                is_readonly INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to create config_items table: {}", e)))?;
+
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_config_category ON config_items(category)")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to create category index: {}", e)))?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS config_history (
+                id BLOB PRIMARY KEY,
+                key TEXT NOT NULL,
+                version INTEGER NOT NULL,
+                change_type TEXT NOT NULL,
+                old_value TEXT,
+                new_value TEXT,
+                category TEXT NOT NULL,
+                is_secret INTEGER NOT NULL DEFAULT 0,
+                rolled_back_to INTEGER,
+                changed_by TEXT NOT NULL,
+                changed_at TEXT NOT NULL,
+                UNIQUE (key, version)
+            )
+        "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to create config_history table: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn row_to_history_entry(row: &SqliteRow) -> ErpResult<ConfigHistoryEntry> {
+        let change: String = row.try_get("change_type").map_err(ErpError::Database)?;
+        Ok(ConfigHistoryEntry {
+            id: row.try_get("id").map_err(ErpError::Database)?,
+            key: row.try_get("key").map_err(ErpError::Database)?,
+            version: row.try_get("version").map_err(ErpError::Database)?,
+            change: parse_change_kind(&change)?,
+            old_value: row.try_get("old_value").map_err(ErpError::Database)?,
+            new_value: row.try_get("new_value").map_err(ErpError::Database)?,
+            category: row.try_get("category").map_err(ErpError::Database)?,
+            is_secret: row.try_get("is_secret").map_err(ErpError::Database)?,
+            rolled_back_to: row.try_get("rolled_back_to").map_err(ErpError::Database)?,
+            changed_by: row.try_get("changed_by").map_err(ErpError::Database)?,
+            changed_at: row.try_get("changed_at").map_err(ErpError::Database)?,
+        })
+    }
+
+    async fn fetch_items(
+        &self,
+        builder: &mut sqlx::QueryBuilder<'_, Sqlite>,
+    ) -> ErpResult<Vec<ConfigItem>> {
+        builder
+            .build_query_as::<ConfigItem>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get configs: {}", e)))
+    }
+}
+
+#[async_trait]
+impl ConfigRepositoryTrait for SqliteConfigRepository {
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        UnitOfWork::begin_sqlite(&self.pool).await
+    }
+
+    async fn create_in(
+        &self,
+        uow: &mut UnitOfWork,
+        config: &CreateConfigRequest,
+    ) -> ErpResult<ConfigItem> {
+        let config_item = ConfigItem::new(
+            config.key.clone(),
+            config.value.clone(),
+            config.category.clone(),
+            config.description.clone(),
+            config.is_secret,
+            config.is_readonly,
+        );
+
+        sqlx::query(
+            "INSERT INTO config_items (id, key, value, description, category, is_secret, is_readonly, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(config_item.id)
+        .bind(&config_item.key)
+        .bind(&config_item.value)
+        .bind(&config_item.description)
+        .bind(&config_item.category)
+        .bind(config_item.is_secret)
+        .bind(config_item.is_readonly)
+        .bind(config_item.created_at)
+        .bind(config_item.updated_at)
+        .execute(uow.sqlite()?)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to create config item: {}", e)))?;
+
+        Ok(config_item)
+    }
+
+    async fn get_by_key(&self, key: &str) -> ErpResult<Option<ConfigItem>> {
+        sqlx::query_as::<_, ConfigItem>("SELECT * FROM config_items WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get config by key: {}", e)))
+    }
+
+    async fn get_by_id(&self, id: &Uuid) -> ErpResult<Option<ConfigItem>> {
+        sqlx::query_as::<_, ConfigItem>("SELECT * FROM config_items WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get config by id: {}", e)))
+    }
+
+    async fn get_by_filter(&self, filter: &ConfigFilter) -> ErpResult<Vec<ConfigItem>> {
+        let mut builder = sqlx::QueryBuilder::new("SELECT * FROM config_items WHERE 1=1");
+
+        if let Some(category) = &filter.category {
+            builder.push(" AND category = ");
+            builder.push_bind(category.clone());
+        }
+
+        if let Some(pattern) = &filter.key_pattern {
+            builder.push(" AND key LIKE ");
+            builder.push_bind(format!("%{}%", pattern));
+        }
+
+        if !filter.include_secrets {
+            builder.push(" AND is_secret = 0");
+        }
+
+        if filter.readonly_only {
+            builder.push(" AND is_readonly = 1");
+        }
+
+        builder.push(" ORDER BY category, key");
+        self.fetch_items(&mut builder).await
+    }
+
+    async fn get_all(&self) -> ErpResult<Vec<ConfigItem>> {
+        let mut builder =
+            sqlx::QueryBuilder::new("SELECT * FROM config_items ORDER BY category, key");
+        self.fetch_items(&mut builder).await
+    }
+
+    async fn update_in(
+        &self,
+        uow: &mut UnitOfWork,
+        id: &Uuid,
+        update_data: &UpdateConfigRequest,
+    ) -> ErpResult<ConfigItem> {
+        let current = sqlx::query_as::<_, ConfigItem>("SELECT * FROM config_items WHERE id = ?")
+            .bind(id)
+            .fetch_optional(uow.sqlite()?)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get config by id: {}", e)))?
+            .ok_or_else(|| ErpError::not_found("ConfigItem", id.to_string()))?;
+
+        if current.is_readonly {
+            return Err(ErpError::forbidden("Cannot update readonly configuration"));
+        }
+
+        let has_changes = update_data.value.is_some()
+            || update_data.description.is_some()
+            || update_data.category.is_some()
+            || update_data.is_secret.is_some();
+        if !has_changes {
+            return Ok(current);
+        }
+
+        let mut builder = sqlx::QueryBuilder::<Sqlite>::new("UPDATE config_items SET ");
+        let mut fields = builder.separated(", ");
+        if let Some(value) = &update_data.value {
+            fields.push("value = ");
+            fields.push_bind_unseparated(value.clone());
+        }
+        if let Some(description) = &update_data.description {
+            fields.push("description = ");
+            fields.push_bind_unseparated(description.clone());
+        }
+        if let Some(category) = &update_data.category {
+            fields.push("category = ");
+            fields.push_bind_unseparated(category.clone());
+        }
+        if let Some(is_secret) = update_data.is_secret {
+            fields.push("is_secret = ");
+            fields.push_bind_unseparated(is_secret);
+        }
+        fields.push("updated_at = ");
+        fields.push_bind_unseparated(chrono::Utc::now());
+        builder.push(" WHERE id = ");
+        builder.push_bind(*id);
+        builder.push(" RETURNING *");
+
+        builder
+            .build_query_as::<ConfigItem>()
+            .fetch_one(uow.sqlite()?)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to update config: {}", e)))
+    }
+
+    async fn delete_in(&self, uow: &mut UnitOfWork, id: &Uuid) -> ErpResult<()> {
+        let is_readonly: bool =
+            sqlx::query_scalar("SELECT is_readonly FROM config_items WHERE id = ?")
+                .bind(id)
+                .fetch_optional(uow.sqlite()?)
+                .await
+                .map_err(|e| ErpError::database(format!("Failed to get config by id: {}", e)))?
+                .ok_or_else(|| ErpError::not_found("ConfigItem", id.to_string()))?;
+
+        if is_readonly {
+            return Err(ErpError::forbidden("Cannot delete readonly configuration"));
+        }
+
+        sqlx::query("DELETE FROM config_items WHERE id = ?")
+            .bind(id)
+            .execute(uow.sqlite()?)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to delete config: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn delete_by_key(&self, key: &str) -> ErpResult<()> {
+        let config = self
+            .get_by_key(key)
+            .await?
+            .ok_or_else(|| ErpError::not_found("ConfigItem", key.to_string()))?;
+
+        if config.is_readonly {
+            return Err(ErpError::forbidden("Cannot delete readonly configuration"));
+        }
+
+        sqlx::query("DELETE FROM config_items WHERE key = ?")
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to delete config by key: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn key_exists(&self, key: &str) -> ErpResult<bool> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM config_items WHERE key = ?")
+            .bind(key)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to check key existence: {}", e)))?;
+
+        Ok(count > 0)
+    }
+
+    async fn get_categories(&self) -> ErpResult<Vec<String>> {
+        sqlx::query_scalar("SELECT DISTINCT category FROM config_items ORDER BY category")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get categories: {}", e)))
+    }
+
+    async fn add_history_in(
+        &self,
+        uow: &mut UnitOfWork,
+        change: &CreateConfigHistoryRequest,
+    ) -> ErpResult<ConfigHistoryEntry> {
+        let row = sqlx::query(
+            "INSERT INTO config_history (id, key, version, change_type, old_value, new_value, category, is_secret, rolled_back_to, changed_by, changed_at) \
+             SELECT ?1, ?2, COALESCE(MAX(version), 0) + 1, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10 \
+             FROM config_history WHERE key = ?2 \
+             RETURNING *",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&change.key)
+        .bind(change.change.as_str())
+        .bind(&change.old_value)
+        .bind(&change.new_value)
+        .bind(&change.category)
+        .bind(change.is_secret)
+        .bind(change.rolled_back_to)
+        .bind(&change.changed_by)
+        .bind(chrono::Utc::now())
+        .fetch_one(uow.sqlite()?)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to record config history: {}", e)))?;
+
+        Self::row_to_history_entry(&row)
+    }
+
+    async fn get_history(&self, key: &str) -> ErpResult<Vec<ConfigHistoryEntry>> {
+        let rows = sqlx::query("SELECT * FROM config_history WHERE key = ? ORDER BY version")
+            .bind(key)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get config history: {}", e)))?;
+
+        rows.iter().map(Self::row_to_history_entry).collect()
+    }
+
+    async fn get_history_version(
+        &self,
+        key: &str,
+        version: i32,
+    ) -> ErpResult<Option<ConfigHistoryEntry>> {
+        let row = sqlx::query("SELECT * FROM config_history WHERE key = ? AND version = ?")
+            .bind(key)
+            .bind(version)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get config history: {}", e)))?;
+
+        row.as_ref().map(Self::row_to_history_entry).transpose()
+    }
+}
+
+/// 테스트용 메모리 설정 저장소 (쓰기는 작업 단위 커밋 시 반영)
+#[derive(Default)]
+pub struct MockConfigRepository {
+    items: Arc<Mutex<std::collections::HashMap<Uuid, ConfigItem>>>,
+    history: Arc<Mutex<Vec<ConfigHistoryEntry>>>,
+    fail_unit_of_work_after: Option<usize>,
+}
+
+impl MockConfigRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 이 저장소가 시작한 작업 단위는 `writes`번 쓴 뒤 실패
+    pub fn failing_unit_of_work_after(mut self, writes: usize) -> Self {
+        self.fail_unit_of_work_after = Some(writes);
+        self
+    }
+
+    fn sorted(mut items: Vec<ConfigItem>) -> Vec<ConfigItem> {
+        items.sort_by(|a, b| (&a.category, &a.key).cmp(&(&b.category, &b.key)));
+        items
+    }
 }
 
-// Tests moved to separate test module for compilation
+#[async_trait]
+impl ConfigRepositoryTrait for MockConfigRepository {
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        let mock = match self.fail_unit_of_work_after {
+            Some(writes) => MockUnitOfWork::failing_after(writes),
+            None => MockUnitOfWork::new(),
+        };
+        Ok(UnitOfWork::mock(mock))
+    }
+
+    async fn create_in(
+        &self,
+        uow: &mut UnitOfWork,
+        config: &CreateConfigRequest,
+    ) -> ErpResult<ConfigItem> {
+        if self.key_exists(&config.key).await? {
+            return Err(ErpError::conflict(format!(
+                "Config key already exists: {}",
+                config.key
+            )));
+        }
+
+        let config_item = ConfigItem::new(
+            config.key.clone(),
+            config.value.clone(),
+            config.category.clone(),
+            config.description.clone(),
+            config.is_secret,
+            config.is_readonly,
+        );
+        let items = self.items.clone();
+        let staged = config_item.clone();
+        uow.as_mock()?.stage(async move {
+            items.lock().unwrap().insert(staged.id, staged);
+        })?;
+        Ok(config_item)
+    }
+
+    async fn get_by_key(&self, key: &str) -> ErpResult<Option<ConfigItem>> {
+        let items = self.items.lock().unwrap();
+        Ok(items.values().find(|item| item.key == key).cloned())
+    }
+
+    async fn get_by_id(&self, id: &Uuid) -> ErpResult<Option<ConfigItem>> {
+        let items = self.items.lock().unwrap();
+        Ok(items.get(id).cloned())
+    }
+
+    async fn get_by_filter(&self, filter: &ConfigFilter) -> ErpResult<Vec<ConfigItem>> {
+        let items = self.items.lock().unwrap();
+        Ok(Self::sorted(
+            items
+                .values()
+                .filter(|item| {
+                    filter
+                        .category
+                        .as_ref()
+                        .is_none_or(|category| &item.category == category)
+                })
+                .filter(|item| {
+                    filter
+                        .key_pattern
+                        .as_ref()
+                        .is_none_or(|pattern| item.key.contains(pattern.as_str()))
+                })
+                .filter(|item| filter.include_secrets || !item.is_secret)
+                .filter(|item| !filter.readonly_only || item.is_readonly)
+                .cloned()
+                .collect(),
+        ))
+    }
+
+    async fn get_all(&self) -> ErpResult<Vec<ConfigItem>> {
+        let items = self.items.lock().unwrap();
+        Ok(Self::sorted(items.values().cloned().collect()))
+    }
+
+    async fn update_in(
+        &self,
+        uow: &mut UnitOfWork,
+        id: &Uuid,
+        update_data: &UpdateConfigRequest,
+    ) -> ErpResult<ConfigItem> {
+        let mut item = self
+            .get_by_id(id)
+            .await?
+            .ok_or_else(|| ErpError::not_found("ConfigItem", id.to_string()))?;
+
+        if item.is_readonly {
+            return Err(ErpError::forbidden("Cannot update readonly configuration"));
+        }
+
+        if let Some(value) = &update_data.value {
+            item.value = value.clone();
+        }
+        if let Some(description) = &update_data.description {
+            item.description = Some(description.clone());
+        }
+        if let Some(category) = &update_data.category {
+            item.category = category.clone();
+        }
+        if let Some(is_secret) = update_data.is_secret {
+            item.is_secret = is_secret;
+        }
+        item.updated_at = chrono::Utc::now();
+
+        let items = self.items.clone();
+        let staged = item.clone();
+        uow.as_mock()?.stage(async move {
+            items.lock().unwrap().insert(staged.id, staged);
+        })?;
+        Ok(item)
+    }
+
+    async fn delete_in(&self, uow: &mut UnitOfWork, id: &Uuid) -> ErpResult<()> {
+        let item = self
+            .get_by_id(id)
+            .await?
+            .ok_or_else(|| ErpError::not_found("ConfigItem", id.to_string()))?;
+
+        if item.is_readonly {
+            return Err(ErpError::forbidden("Cannot delete readonly configuration"));
+        }
+
+        let items = self.items.clone();
+        let id = *id;
+        uow.as_mock()?.stage(async move {
+            items.lock().unwrap().remove(&id);
+        })
+    }
+
+    async fn delete_by_key(&self, key: &str) -> ErpResult<()> {
+        let config = self
+            .get_by_key(key)
+            .await?
+            .ok_or_else(|| ErpError::not_found("ConfigItem", key.to_string()))?;
+        self.delete(&config.id).await
+    }
+
+    async fn key_exists(&self, key: &str) -> ErpResult<bool> {
+        Ok(self.get_by_key(key).await?.is_some())
+    }
+
+    async fn get_categories(&self) -> ErpResult<Vec<String>> {
+        let items = self.items.lock().unwrap();
+        let categories: std::collections::BTreeSet<String> =
+            items.values().map(|item| item.category.clone()).collect();
+        Ok(categories.into_iter().collect())
+    }
+
+    async fn add_history_in(
+        &self,
+        uow: &mut UnitOfWork,
+        change: &CreateConfigHistoryRequest,
+    ) -> ErpResult<ConfigHistoryEntry> {
+        let version = self
+            .history
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry.key == change.key)
+            .map(|entry| entry.version)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let entry = ConfigHistoryEntry {
+            id: Uuid::new_v4(),
+            key: change.key.clone(),
+            version,
+            change: change.change,
+            old_value: change.old_value.clone(),
+            new_value: change.new_value.clone(),
+            category: change.category.clone(),
+            is_secret: change.is_secret,
+            rolled_back_to: change.rolled_back_to,
+            changed_by: change.changed_by.clone(),
+            changed_at: chrono::Utc::now(),
+        };
+        let history = self.history.clone();
+        let staged = entry.clone();
+        uow.as_mock()?.stage(async move {
+            history.lock().unwrap().push(staged);
+        })?;
+        Ok(entry)
+    }
+
+    async fn get_history(&self, key: &str) -> ErpResult<Vec<ConfigHistoryEntry>> {
+        let history = self.history.lock().unwrap();
+        Ok(history
+            .iter()
+            .filter(|entry| entry.key == key)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_history_version(
+        &self,
+        key: &str,
+        version: i32,
+    ) -> ErpResult<Option<ConfigHistoryEntry>> {
+        let history = self.history.lock().unwrap();
+        Ok(history
+            .iter()
+            .find(|entry| entry.key == key && entry.version == version)
+            .cloned())
+    }
+}
+
+/// 연결된 데이터베이스 종류에 맞는 설정 저장소를 열고 테이블을 준비
+pub async fn open_config_repository(
+    db: Arc<DatabaseConnection>,
+) -> ErpResult<Arc<dyn ConfigRepositoryTrait>> {
+    match db.backend() {
+        DatabaseBackend::Postgres => {
+            let repository = ConfigRepository::new(db);
+            repository.init_table().await?;
+            Ok(Arc::new(repository))
+        }
+        DatabaseBackend::Sqlite => {
+            let repository = SqliteConfigRepository::new(db.sqlite_pool()?.clone());
+            repository.init_table().await?;
+            Ok(Arc::new(repository))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::database::sqlite::test_pool;
+
+    #[tokio::test]
+    async fn test_sqlite_config_round_trip() {
+        let repository = SqliteConfigRepository::new(test_pool().await);
+        repository.init_table().await.unwrap();
+
+        let created = repository
+            .create(&CreateConfigRequest::new(
+                "sales.tax_rate".to_string(),
+                "10".to_string(),
+                "sales".to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let updated = repository
+            .update(
+                &created.id,
+                &UpdateConfigRequest {
+                    value: Some("8".to_string()),
+                    description: None,
+                    category: None,
+                    is_secret: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.value, "8");
+        assert!(repository.key_exists("sales.tax_rate").await.unwrap());
+        assert_eq!(repository.get_categories().await.unwrap(), vec!["sales"]);
+
+        repository.delete_by_key("sales.tax_rate").await.unwrap();
+        assert!(repository
+            .get_by_key("sales.tax_rate")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_history_versions_per_key() {
+        let repository = SqliteConfigRepository::new(test_pool().await);
+        repository.init_table().await.unwrap();
+
+        let change = |key: &str, value: &str| CreateConfigHistoryRequest {
+            key: key.to_string(),
+            change: ConfigChangeKind::Create,
+            old_value: None,
+            new_value: Some(value.to_string()),
+            category: "system".to_string(),
+            is_secret: false,
+            rolled_back_to: None,
+            changed_by: "kim".to_string(),
+        };
+        repository.add_history(&change("a", "1")).await.unwrap();
+        repository.add_history(&change("b", "1")).await.unwrap();
+        let second = repository.add_history(&change("a", "2")).await.unwrap();
+        assert_eq!(second.version, 2);
+
+        let history = repository.get_history("a").await.unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .map(|entry| entry.version)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        let first = repository
+            .get_history_version("a", 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.new_value.as_deref(), Some("1"));
+        assert_eq!(first.change, ConfigChangeKind::Create);
+        assert!(repository
+            .get_history_version("b", 2)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
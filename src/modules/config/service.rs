@@ -1,11 +1,15 @@
 // Config Module Service - Business Logic Layer
 
+use crate::core::cache::{CacheScope, QueryCache};
+use crate::core::config::ConfigIssue;
+use crate::core::database::UnitOfWork;
+use crate::core::security::audit::current_username;
 use crate::modules::config::models::*;
 use crate::modules::config::repository::ConfigRepositoryTrait;
 use crate::utils::error::{ErpError, ErpResult};
 use crate::utils::validation;
 use rust_decimal::Decimal;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use tracing::{info, warn};
 use uuid::Uuid;
@@ -13,11 +17,40 @@ use uuid::Uuid;
 /// Config Service - Business Logic for Configuration Management
 pub struct ConfigService {
     repository: Arc<dyn ConfigRepositoryTrait>,
+    cache: QueryCache,
+    default_tax_rate: Decimal,
+    actor: String,
 }
 
+/// 애플리케이션이 설정 저장소에서 읽는 키
+const STORE_KEYS: &[&str] = &["tax_rate"];
+
 impl ConfigService {
     pub fn new(repository: Arc<dyn ConfigRepositoryTrait>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            cache: QueryCache::disabled(),
+            default_tax_rate: Decimal::from(10),
+            actor: current_username(),
+        }
+    }
+
+    /// 변경 이력에 남길 사용자 (기본값: ERP_USER 또는 OS 사용자)
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = actor.into();
+        self
+    }
+
+    /// tax_rate가 저장되지 않았을 때 사용할 세율 (백분율, 기본값 10%)
+    pub fn with_default_tax_rate(mut self, rate: Decimal) -> Self {
+        self.default_tax_rate = rate;
+        self
+    }
+
+    /// 설정값 조회 결과를 캐시 (변경 시 config 범위 전체 무효화)
+    pub fn with_cache(mut self, cache: QueryCache) -> Self {
+        self.cache = cache;
+        self
     }
 
     /// 새로운 설정 생성
@@ -34,7 +67,7 @@ impl ConfigService {
         }
 
         // 설정 생성
-        let config = self.repository.create(&request).await?;
+        let config = self.create_with_history(&request).await?;
 
         info!(
             "Created configuration: key={}, category={}",
@@ -61,11 +94,26 @@ impl ConfigService {
         self.repository.get_by_id(id).await
     }
 
-    /// 설정값만 조회 (비즈니스 로직에서 사용)
+    /// 설정값만 조회 (비즈니스 로직에서 사용, 비밀 설정은 캐시하지 않음)
     pub async fn get_config_value(&self, key: &str) -> ErpResult<Option<String>> {
+        let cache_key = format!("value:{}", key.trim());
+        if let Some(value) = self.cache.get(CacheScope::Config, &cache_key).await {
+            return Ok(value);
+        }
+
         match self.get_config(key).await? {
-            Some(config) => Ok(Some(config.value)),
-            None => Ok(None),
+            Some(config) if config.is_secret => Ok(Some(config.value)),
+            Some(config) => {
+                let value = Some(config.value);
+                self.cache.set(CacheScope::Config, &cache_key, &value).await;
+                Ok(value)
+            }
+            None => {
+                self.cache
+                    .set(CacheScope::Config, &cache_key, &None::<String>)
+                    .await;
+                Ok(None)
+            }
         }
     }
 
@@ -116,8 +164,29 @@ impl ConfigService {
         // 입력 검증
         self.validate_update_request(&request)?;
 
-        // 업데이트 실행
-        let updated = self.repository.update(&existing.id, &request).await?;
+        // 업데이트 실행 (값과 이력은 한 트랜잭션)
+        let mut uow = self.repository.begin().await?;
+        let result = async {
+            let updated = self
+                .repository
+                .update_in(&mut uow, &existing.id, &request)
+                .await?;
+            // 설명·카테고리만 바뀐 경우는 값 이력에 남기지 않음
+            if existing.value != updated.value || existing.is_secret != updated.is_secret {
+                self.record_change(
+                    &mut uow,
+                    ConfigChangeKind::Update,
+                    Some(&existing),
+                    Some(&updated),
+                    None,
+                )
+                .await?;
+            }
+            Ok(updated)
+        }
+        .await;
+        let updated = uow.finish(result).await?;
+        self.cache.invalidate(CacheScope::Config).await;
 
         info!("Updated configuration: key={}, changes={:?}", key, request);
 
@@ -144,8 +213,22 @@ impl ConfigService {
             return Err(ErpError::forbidden("Cannot delete readonly configuration"));
         }
 
-        // 삭제 실행
-        self.repository.delete_by_key(key).await?;
+        // 삭제 실행 (삭제와 이력은 한 트랜잭션)
+        let mut uow = self.repository.begin().await?;
+        let result = async {
+            self.repository.delete_in(&mut uow, &config.id).await?;
+            self.record_change(
+                &mut uow,
+                ConfigChangeKind::Delete,
+                Some(&config),
+                None,
+                None,
+            )
+            .await
+        }
+        .await;
+        uow.finish(result).await?;
+        self.cache.invalidate(CacheScope::Config).await;
 
         warn!("Deleted configuration: key={}", key);
 
@@ -163,21 +246,165 @@ impl ConfigService {
 
         // 모든 설정 조회
         let configs = self.repository.get_all().await?;
-        let mut deleted_count = 0u32;
 
-        // 읽기 전용이 아닌 설정만 삭제
-        for config in configs {
-            if !config.is_readonly {
-                self.repository.delete(&config.id).await?;
+        // 읽기 전용이 아닌 설정만 삭제 (전부 삭제되거나 하나도 삭제되지 않음)
+        let mut uow = self.repository.begin().await?;
+        let result = async {
+            let mut deleted_count = 0u32;
+            for config in configs.iter().filter(|config| !config.is_readonly) {
+                self.repository.delete_in(&mut uow, &config.id).await?;
+                self.record_change(&mut uow, ConfigChangeKind::Delete, Some(config), None, None)
+                    .await?;
                 deleted_count += 1;
             }
+            Ok(deleted_count)
         }
+        .await;
+        let deleted_count = uow.finish(result).await?;
+        self.cache.invalidate(CacheScope::Config).await;
 
         warn!("Reset configurations: deleted {} items", deleted_count);
 
         Ok(deleted_count)
     }
 
+    /// 키의 변경 이력 (버전 오름차순, 비밀 값은 원본 그대로이므로 표시 전 `masked()` 사용)
+    pub async fn config_history(&self, key: &str) -> ErpResult<Vec<ConfigHistoryEntry>> {
+        if key.trim().is_empty() {
+            return Err(ErpError::validation(
+                "key",
+                "Configuration key cannot be empty",
+            ));
+        }
+
+        self.repository.get_history(key.trim()).await
+    }
+
+    /// 설정을 특정 버전의 값으로 되돌림 (삭제된 설정은 다시 생성)
+    ///
+    /// 롤백 자체도 새 버전으로 기록되므로 롤백을 다시 되돌릴 수 있다.
+    pub async fn rollback_config(&self, key: &str, version: i32) -> ErpResult<ConfigItem> {
+        let key = key.trim();
+        let target = self
+            .repository
+            .get_history_version(key, version)
+            .await?
+            .ok_or_else(|| ErpError::not_found("ConfigHistory", format!("{} v{}", key, version)))?;
+        let value = target.new_value.clone().ok_or_else(|| {
+            ErpError::business_rule(format!(
+                "Version {} of '{}' deleted the configuration; there is no value to restore",
+                version, key
+            ))
+        })?;
+
+        let existing = self.repository.get_by_key(key).await?;
+        if let Some(current) = &existing {
+            if current.value == value && current.is_secret == target.is_secret {
+                return Err(ErpError::business_rule(format!(
+                    "'{}' already has the value of version {}",
+                    key, version
+                )));
+            }
+        }
+
+        // 값·비밀 여부 복원과 롤백 이력은 한 트랜잭션
+        let mut uow = self.repository.begin().await?;
+        let result = async {
+            let restored = match &existing {
+                Some(current) => {
+                    let request = UpdateConfigRequest {
+                        value: Some(value),
+                        description: None,
+                        category: None,
+                        is_secret: Some(target.is_secret),
+                    };
+                    self.repository
+                        .update_in(&mut uow, &current.id, &request)
+                        .await?
+                }
+                None => {
+                    let request = CreateConfigRequest {
+                        key: key.to_string(),
+                        value,
+                        description: None,
+                        category: target.category.clone(),
+                        is_secret: target.is_secret,
+                        is_readonly: false,
+                    };
+                    self.repository.create_in(&mut uow, &request).await?
+                }
+            };
+            self.record_change(
+                &mut uow,
+                ConfigChangeKind::Rollback,
+                existing.as_ref(),
+                Some(&restored),
+                Some(version),
+            )
+            .await?;
+            Ok(restored)
+        }
+        .await;
+        let restored = uow.finish(result).await?;
+        self.cache.invalidate(CacheScope::Config).await;
+
+        warn!(
+            "Rolled back configuration: key={}, version={}",
+            key, version
+        );
+
+        Ok(restored)
+    }
+
+    /// 설정 생성과 생성 이력을 한 트랜잭션으로 기록
+    async fn create_with_history(&self, request: &CreateConfigRequest) -> ErpResult<ConfigItem> {
+        let mut uow = self.repository.begin().await?;
+        let result = async {
+            let config = self.repository.create_in(&mut uow, request).await?;
+            self.record_change(
+                &mut uow,
+                ConfigChangeKind::Create,
+                None,
+                Some(&config),
+                None,
+            )
+            .await?;
+            Ok(config)
+        }
+        .await;
+        let config = uow.finish(result).await?;
+        self.cache.invalidate(CacheScope::Config).await;
+        Ok(config)
+    }
+
+    /// 변경 이력 기록 (before/after 중 하나는 반드시 있음, 값 변경과 같은 작업 단위)
+    async fn record_change(
+        &self,
+        uow: &mut UnitOfWork,
+        change: ConfigChangeKind,
+        before: Option<&ConfigItem>,
+        after: Option<&ConfigItem>,
+        rolled_back_to: Option<i32>,
+    ) -> ErpResult<()> {
+        let Some(item) = after.or(before) else {
+            return Ok(());
+        };
+
+        let request = CreateConfigHistoryRequest {
+            key: item.key.clone(),
+            change,
+            old_value: before.map(|item| item.value.clone()),
+            new_value: after.map(|item| item.value.clone()),
+            category: item.category.clone(),
+            is_secret: before.is_some_and(|item| item.is_secret)
+                || after.is_some_and(|item| item.is_secret),
+            rolled_back_to,
+            changed_by: self.actor.clone(),
+        };
+        self.repository.add_history_in(uow, &request).await?;
+        Ok(())
+    }
+
     /// 카테고리별 설정 조회
     pub async fn get_configs_by_category(&self, category: &str) -> ErpResult<Vec<ConfigItem>> {
         let filter = ConfigFilter::new().with_category(category.to_string());
@@ -315,7 +542,7 @@ impl ConfigService {
             self.update_config(&request.key, update_request).await?;
         } else {
             // 새 설정 생성
-            self.repository.create(&request).await?;
+            self.create_with_history(&request).await?;
         }
 
         Ok(true) // 가져오기 성공
@@ -380,13 +607,10 @@ impl ConfigService {
         Ok(())
     }
 
-    /// 세금률 조회 (기본값: 10.0% - 한국 부가세)
+    /// 세금률 조회 (미설정 시 sales.default_tax_rate)
     pub async fn get_tax_rate(&self) -> ErpResult<Decimal> {
-        const DEFAULT_TAX_RATE: &str = "10.0";
-
-        match self.get_config("tax_rate").await? {
-            Some(config) => config
-                .value
+        match self.get_config_value("tax_rate").await? {
+            Some(value) => value
                 .parse::<f64>()
                 .map_err(|_| ErpError::validation("tax_rate", "Invalid tax rate format"))
                 .and_then(|rate| {
@@ -396,19 +620,57 @@ impl ConfigService {
                             "Tax rate must be between 0.0 and 100.0",
                         ))
                     } else {
-                        Ok(Decimal::from_f64_retain(rate)
-                            .unwrap_or(Decimal::from_f64_retain(10.0).unwrap()))
+                        Ok(Decimal::from_f64_retain(rate).unwrap_or(self.default_tax_rate))
                     }
                 }),
             None => {
                 // 기본값 설정
                 info!(
                     "Tax rate not configured, using default: {}%",
-                    DEFAULT_TAX_RATE
+                    self.default_tax_rate
                 );
-                Ok(Decimal::from_f64_retain(10.0).unwrap())
+                Ok(self.default_tax_rate)
+            }
+        }
+    }
+
+    /// 저장된 설정 검사 (`erp config validate`)
+    ///
+    /// `file_keys`는 설정 파일이 읽는 키 목록으로, 같은 키가 저장소에 있으면
+    /// 적용되지 않는다는 경고를 남긴다.
+    pub async fn check_store(&self, file_keys: &BTreeSet<String>) -> ErpResult<Vec<ConfigIssue>> {
+        const SOURCE: &str = "config store";
+        let mut issues = Vec::new();
+
+        for item in self.repository.get_all().await? {
+            if item.key == "tax_rate" {
+                let valid = item
+                    .value
+                    .parse::<f64>()
+                    .is_ok_and(|rate| (0.0..=100.0).contains(&rate));
+                if !valid {
+                    issues.push(ConfigIssue::error(
+                        SOURCE,
+                        &item.key,
+                        format!("'{}' is not a percentage between 0 and 100", item.value),
+                    ));
+                }
+            } else if file_keys.contains(&item.key) {
+                issues.push(ConfigIssue::warning(
+                    SOURCE,
+                    &item.key,
+                    "file setting stored in the database is not applied; set it in config/*.toml or the environment",
+                ));
+            } else if !STORE_KEYS.contains(&item.key.as_str()) {
+                issues.push(ConfigIssue::warning(
+                    SOURCE,
+                    &item.key,
+                    "unknown key, not read by the application",
+                ));
             }
         }
+
+        Ok(issues)
     }
 
     /// 세금률 설정
@@ -467,3 +729,210 @@ pub struct ImportResult {
 }
 
 // Tests moved to separate test module for compilation
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::config::repository::MockConfigRepository;
+    use std::time::Duration;
+
+    fn value_update(value: &str) -> UpdateConfigRequest {
+        UpdateConfigRequest {
+            value: Some(value.to_string()),
+            description: None,
+            category: None,
+            is_secret: None,
+        }
+    }
+
+    /// 서비스를 거치지 않고 저장소 값을 직접 변경
+    async fn update_behind_cache(repository: &MockConfigRepository, key: &str, value: &str) {
+        let item = repository.get_by_key(key).await.unwrap().unwrap();
+        repository
+            .update(&item.id, &value_update(value))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cached_values_are_invalidated_by_service_writes() {
+        let repository = Arc::new(MockConfigRepository::new());
+        let service = ConfigService::new(repository.clone())
+            .with_cache(QueryCache::in_memory(10, Duration::from_secs(60)));
+
+        service.set_tax_rate(8.0).await.unwrap();
+        assert_eq!(service.get_tax_rate().await.unwrap().to_string(), "8");
+
+        update_behind_cache(&repository, "tax_rate", "5").await;
+        assert_eq!(service.get_tax_rate().await.unwrap().to_string(), "8");
+
+        service
+            .update_config("tax_rate", value_update("12"))
+            .await
+            .unwrap();
+        assert_eq!(service.get_tax_rate().await.unwrap().to_string(), "12");
+    }
+
+    #[tokio::test]
+    async fn test_secret_values_are_not_cached() {
+        let repository = Arc::new(MockConfigRepository::new());
+        let service = ConfigService::new(repository.clone())
+            .with_cache(QueryCache::in_memory(10, Duration::from_secs(60)));
+
+        service
+            .create_config(CreateConfigRequest {
+                key: "smtp_password".to_string(),
+                value: "first".to_string(),
+                description: None,
+                category: "email".to_string(),
+                is_secret: true,
+                is_readonly: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            service.get_config_value("smtp_password").await.unwrap(),
+            Some("first".to_string())
+        );
+
+        update_behind_cache(&repository, "smtp_password", "second").await;
+        assert_eq!(
+            service.get_config_value("smtp_password").await.unwrap(),
+            Some("second".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_changes_are_versioned_and_rolled_back() {
+        let repository = Arc::new(MockConfigRepository::new());
+        let service = ConfigService::new(repository).with_actor("kim");
+
+        service.set_tax_rate(10.0).await.unwrap();
+        service.set_tax_rate(8.0).await.unwrap();
+        service.delete_config("tax_rate").await.unwrap();
+
+        // 삭제된 설정은 버전 2의 값으로 다시 생성
+        let restored = service.rollback_config("tax_rate", 2).await.unwrap();
+        assert_eq!(restored.value, "8");
+        let restored = service.rollback_config("tax_rate", 1).await.unwrap();
+        assert_eq!(restored.value, "10");
+
+        let history = service.config_history("tax_rate").await.unwrap();
+        let changes: Vec<(i32, ConfigChangeKind, Option<&str>, Option<&str>)> = history
+            .iter()
+            .map(|entry| {
+                (
+                    entry.version,
+                    entry.change,
+                    entry.old_value.as_deref(),
+                    entry.new_value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (1, ConfigChangeKind::Create, None, Some("10")),
+                (2, ConfigChangeKind::Update, Some("10"), Some("8")),
+                (3, ConfigChangeKind::Delete, Some("8"), None),
+                (4, ConfigChangeKind::Rollback, None, Some("8")),
+                (5, ConfigChangeKind::Rollback, Some("8"), Some("10")),
+            ]
+        );
+        assert_eq!(history[4].rolled_back_to, Some(1));
+        assert!(history.iter().all(|entry| entry.changed_by == "kim"));
+
+        // 삭제 버전이나 현재와 같은 값으로는 되돌릴 수 없음
+        assert!(service.rollback_config("tax_rate", 3).await.is_err());
+        assert!(service.rollback_config("tax_rate", 1).await.is_err());
+        assert!(service.rollback_config("tax_rate", 9).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_failed_history_write_leaves_value_unchanged() {
+        // 값 변경은 스테이징되고 이력 기록에서 실패
+        let repository = Arc::new(MockConfigRepository::new().failing_unit_of_work_after(1));
+        let service = ConfigService::new(repository.clone());
+        let request = CreateConfigRequest::new(
+            "theme".to_string(),
+            "light".to_string(),
+            "system".to_string(),
+        );
+
+        assert!(service.create_config(request.clone()).await.is_err());
+        assert!(repository.get_by_key("theme").await.unwrap().is_none());
+
+        repository.create(&request).await.unwrap();
+        assert!(service
+            .update_config("theme", value_update("dark"))
+            .await
+            .is_err());
+        let item = repository.get_by_key("theme").await.unwrap().unwrap();
+        assert_eq!(item.value, "light");
+        assert!(repository.get_history("theme").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rollback_restores_secret_flag() {
+        let repository = Arc::new(MockConfigRepository::new());
+        let service = ConfigService::new(repository);
+
+        service
+            .create_config(CreateConfigRequest {
+                key: "smtp_password".to_string(),
+                value: "hunter2".to_string(),
+                description: None,
+                category: "email".to_string(),
+                is_secret: true,
+                is_readonly: false,
+            })
+            .await
+            .unwrap();
+        service
+            .update_config(
+                "smtp_password",
+                UpdateConfigRequest {
+                    value: Some("public".to_string()),
+                    description: None,
+                    category: None,
+                    is_secret: Some(false),
+                },
+            )
+            .await
+            .unwrap();
+
+        let restored = service.rollback_config("smtp_password", 1).await.unwrap();
+        assert_eq!(restored.value, "hunter2");
+        assert!(restored.is_secret);
+    }
+
+    #[tokio::test]
+    async fn test_check_store_reports_invalid_and_unread_keys() {
+        let repository = Arc::new(MockConfigRepository::new());
+        let service =
+            ConfigService::new(repository.clone()).with_default_tax_rate(Decimal::from(7));
+        assert_eq!(service.get_tax_rate().await.unwrap(), Decimal::from(7));
+
+        for (key, value) in [
+            ("tax_rate", "250"),
+            ("sales.order_prefix", "SO"),
+            ("theme", "dark"),
+        ] {
+            let request =
+                CreateConfigRequest::new(key.to_string(), value.to_string(), "system".to_string());
+            repository.create(&request).await.unwrap();
+        }
+
+        let file_keys = BTreeSet::from(["sales.order_prefix".to_string()]);
+        let issues = service.check_store(&file_keys).await.unwrap();
+        let found: Vec<(&str, bool)> = issues
+            .iter()
+            .map(|issue| (issue.key.as_str(), issue.is_error()))
+            .collect();
+
+        assert_eq!(issues.len(), 3);
+        assert!(found.contains(&("tax_rate", true)));
+        assert!(found.contains(&("sales.order_prefix", false)));
+        assert!(found.contains(&("theme", false)));
+    }
+}
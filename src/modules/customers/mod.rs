@@ -1,12 +1,18 @@
 //! Customer management module
 //!
 //! This module provides comprehensive customer relationship management functionality
-//! including customer creation, updating, search, credit management, and address handling.
+//! including customer creation, updating, search, credit management, address handling,
+//! and RFM-based segmentation with churn prediction.
 
 pub mod models;
 pub mod repository;
+pub mod segmentation;
 pub mod service;
 
 pub use models::*;
-pub use repository::{CustomerRepository, MockCustomerRepository, PostgresCustomerRepository};
+pub use repository::{
+    CustomerRepository, MockCustomerRepository, PiiRotationBatch, PostgresCustomerRepository,
+    SqliteCustomerRepository,
+};
+pub use segmentation::{CustomerOrderMetrics, CustomerRfmScore, RfmSegment, RfmSegmentSummary};
 pub use service::{BalanceOperation, CreditCheckResult, CustomerService, CustomerStatistics};
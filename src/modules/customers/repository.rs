@@ -1,9 +1,14 @@
 use async_trait::async_trait;
-use sqlx::{Pool, Postgres};
+use shaku::Interface;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Pool, Postgres, Row, Sqlite, SqlitePool};
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::core::database::models::customer::*;
+use crate::core::database::{sqlite, MockUnitOfWork, ReadRouter, UnitOfWork};
+use crate::core::security::pii::{is_encrypted, PiiCipher, PiiKind, Rotated};
+use crate::modules::customers::segmentation::CustomerOrderMetrics;
 use crate::utils::error::{ErpError, ErpResult};
 
 // Helper function to build ORDER BY clause
@@ -26,7 +31,18 @@ fn build_sort_clause(sort_by: &str, sort_order: &str) -> (String, String) {
 }
 
 #[async_trait]
-pub trait CustomerRepository: Send + Sync {
+pub trait CustomerRepository: Interface {
+    /// Starts a unit of work other repositories on the same backend can join
+    async fn begin(&self) -> ErpResult<UnitOfWork>;
+    async fn delete_customer_in(&self, uow: &mut UnitOfWork, id: Uuid) -> ErpResult<()>;
+    async fn delete_customer_order_in(&self, uow: &mut UnitOfWork, order_id: Uuid)
+        -> ErpResult<()>;
+    async fn delete_customer_address_in(
+        &self,
+        uow: &mut UnitOfWork,
+        address_id: Uuid,
+    ) -> ErpResult<()>;
+
     async fn create_customer(&self, customer: &Customer) -> ErpResult<()>;
     async fn create_customer_address(&self, address: &CustomerAddress) -> ErpResult<()>;
     async fn get_customer_by_id(&self, id: Uuid) -> ErpResult<Option<Customer>>;
@@ -47,7 +63,11 @@ pub trait CustomerRepository: Send + Sync {
         id: Uuid,
         new_balance: rust_decimal::Decimal,
     ) -> ErpResult<()>;
-    async fn delete_customer(&self, id: Uuid) -> ErpResult<()>;
+    async fn delete_customer(&self, id: Uuid) -> ErpResult<()> {
+        let mut uow = self.begin().await?;
+        let result = self.delete_customer_in(&mut uow, id).await;
+        uow.finish(result).await
+    }
     async fn search_customers(&self, query: &str, limit: u32) -> ErpResult<Vec<Customer>>;
     async fn get_customers_with_outstanding_balance(&self) -> ErpResult<Vec<Customer>>;
     async fn get_customers_by_type(&self, customer_type: &CustomerType)
@@ -55,22 +75,323 @@ pub trait CustomerRepository: Send + Sync {
     async fn count_customers(&self) -> ErpResult<i64>;
     async fn count_customers_by_status(&self, status: &CustomerStatus) -> ErpResult<i64>;
     async fn get_customer_orders(&self, customer_id: Uuid) -> ErpResult<Vec<Uuid>>;
-    async fn delete_customer_order(&self, order_id: Uuid) -> ErpResult<()>;
-    async fn delete_customer_address(&self, address_id: Uuid) -> ErpResult<()>;
+    async fn delete_customer_order(&self, order_id: Uuid) -> ErpResult<()> {
+        let mut uow = self.begin().await?;
+        let result = self.delete_customer_order_in(&mut uow, order_id).await;
+        uow.finish(result).await
+    }
+    async fn delete_customer_address(&self, address_id: Uuid) -> ErpResult<()> {
+        let mut uow = self.begin().await?;
+        let result = self.delete_customer_address_in(&mut uow, address_id).await;
+        uow.finish(result).await
+    }
+    async fn get_customer_order_metrics(&self) -> ErpResult<Vec<CustomerOrderMetrics>>;
+
+    /// Re-encrypts one batch of customers (ordered by id, after `after`) under the
+    /// current key and recomputes their blind indexes
+    async fn rotate_customer_pii_batch(
+        &self,
+        _after: Option<Uuid>,
+        _limit: u32,
+    ) -> ErpResult<PiiRotationBatch> {
+        Err(ErpError::unsupported(
+            "PII key rotation requires a PostgreSQL database",
+        ))
+    }
+
+    /// Re-encrypts one batch of customer addresses under the current key
+    async fn rotate_address_pii_batch(
+        &self,
+        _after: Option<Uuid>,
+        _limit: u32,
+    ) -> ErpResult<PiiRotationBatch> {
+        Err(ErpError::unsupported(
+            "PII key rotation requires a PostgreSQL database",
+        ))
+    }
 }
 
 pub struct PostgresCustomerRepository {
     pool: Arc<Pool<Postgres>>,
+    reads: ReadRouter<Postgres>,
+    pii: PiiCodec,
+}
+
+/// Progress of one re-encryption batch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PiiRotationBatch {
+    pub rows: u64,
+    /// Values re-encrypted under the current key
+    pub re_encrypted: u64,
+    /// Legacy plaintext values encrypted for the first time
+    pub encrypted: u64,
+    /// Keyset cursor for the next batch
+    pub last_id: Option<Uuid>,
+}
+
+impl PiiRotationBatch {
+    fn count(&mut self, rotated: Rotated) -> String {
+        match rotated {
+            Rotated::ReEncrypted(value) => {
+                if !value.is_empty() {
+                    self.re_encrypted += 1;
+                }
+                value
+            }
+            Rotated::Encrypted(value) => {
+                self.encrypted += 1;
+                value
+            }
+        }
+    }
+
+    fn rotate_opt(
+        &mut self,
+        cipher: &PiiCipher,
+        value: Option<String>,
+    ) -> ErpResult<Option<String>> {
+        value
+            .map(|value| Ok(self.count(cipher.rotate(&value)?)))
+            .transpose()
+    }
+}
+
+/// Customer columns read by the Postgres lookups; searches leave out `status`
+#[derive(sqlx::FromRow)]
+struct PgCustomerRow {
+    id: Uuid,
+    name: String,
+    email: Option<String>,
+    phone: Option<String>,
+    company: Option<String>,
+    tax_id: Option<String>,
+    customer_type: String,
+    credit_limit: Option<rust_decimal::Decimal>,
+    current_balance: Option<rust_decimal::Decimal>,
+    notes: Option<String>,
+    #[sqlx(default)]
+    status: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct PgAddressRow {
+    id: Uuid,
+    address_type: String,
+    address_line1: String,
+    city: Option<String>,
+    state_province: Option<String>,
+    postal_code: Option<String>,
+    country: Option<String>,
+    is_default: bool,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Encrypted customer columns re-sealed by key rotation
+#[derive(sqlx::FromRow)]
+struct PgCustomerPiiRow {
+    id: Uuid,
+    email: Option<String>,
+    phone: Option<String>,
+    tax_id: Option<String>,
+}
+
+/// Encrypted address columns re-sealed by key rotation
+#[derive(sqlx::FromRow)]
+struct PgAddressPiiRow {
+    id: Uuid,
+    address_line1: String,
+    address_line2: Option<String>,
+    postal_code: Option<String>,
+}
+
+/// Seals and opens the PII columns shared by every customer repository
+#[derive(Clone, Default)]
+struct PiiCodec {
+    cipher: Option<Arc<PiiCipher>>,
+}
+
+impl PiiCodec {
+    fn seal(&self, value: Option<&str>) -> ErpResult<Option<String>> {
+        match &self.cipher {
+            Some(cipher) => cipher.encrypt_opt(value),
+            None => Ok(value.map(str::to_string)),
+        }
+    }
+
+    fn open(&self, stored: Option<String>) -> ErpResult<Option<String>> {
+        match &self.cipher {
+            Some(cipher) => cipher.decrypt_opt(stored),
+            None if stored.as_deref().is_some_and(is_encrypted) => Err(ErpError::config(
+                "Customer data is encrypted but no PII encryption key is configured",
+            )),
+            None => Ok(stored),
+        }
+    }
+
+    fn blind_index(&self, kind: PiiKind, value: Option<&str>) -> Option<String> {
+        let cipher = self.cipher.as_ref()?;
+        cipher.blind_index(kind, value?)
+    }
+
+    fn lookup_indexes(&self, kind: PiiKind, value: &str) -> Vec<String> {
+        self.cipher
+            .as_ref()
+            .map(|cipher| cipher.lookup_indexes(kind, value))
+            .unwrap_or_default()
+    }
+
+    fn open_customer(&self, mut customer: Customer) -> ErpResult<Customer> {
+        customer.email = self.open(Some(customer.email))?.unwrap_or_default();
+        customer.phone = self.open(customer.phone)?;
+        customer.tax_id = self.open(customer.tax_id)?;
+        Ok(customer)
+    }
 }
 
 impl PostgresCustomerRepository {
     pub fn new(pool: Arc<Pool<Postgres>>) -> Self {
-        Self { pool }
+        Self {
+            reads: ReadRouter::primary_only((*pool).clone()),
+            pool,
+            pii: PiiCodec::default(),
+        }
+    }
+
+    /// Sends customer listings and searches to read replicas
+    pub fn with_read_router(mut self, reads: ReadRouter<Postgres>) -> Self {
+        self.reads = reads;
+        self
+    }
+
+    /// Encrypts email, phone, tax ID and street/postal address columns at rest
+    pub fn with_pii_cipher(mut self, cipher: Option<Arc<PiiCipher>>) -> Self {
+        self.pii.cipher = cipher;
+        self
     }
 }
 
 #[async_trait]
 impl CustomerRepository for PostgresCustomerRepository {
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        UnitOfWork::begin_postgres(&self.pool).await
+    }
+
+    async fn rotate_customer_pii_batch(
+        &self,
+        after: Option<Uuid>,
+        limit: u32,
+    ) -> ErpResult<PiiRotationBatch> {
+        let cipher = self
+            .pii
+            .cipher
+            .as_ref()
+            .ok_or_else(|| ErpError::config("PII encryption is not enabled"))?;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to start transaction: {}", e)))?;
+
+        let rows = sqlx::query_as::<_, PgCustomerPiiRow>(
+            "SELECT id, email, phone, tax_id FROM customers
+             WHERE ($1::uuid IS NULL OR id > $1)
+             ORDER BY id LIMIT $2 FOR UPDATE",
+        )
+        .bind(after)
+        .bind(limit as i64)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to fetch customers: {}", e)))?;
+
+        let mut batch = PiiRotationBatch::default();
+        for row in rows {
+            let email_plain = cipher.decrypt_opt(row.email.clone())?;
+            let phone_plain = cipher.decrypt_opt(row.phone.clone())?;
+            let email = batch.rotate_opt(cipher, row.email)?;
+            let phone = batch.rotate_opt(cipher, row.phone)?;
+            let tax_id = batch.rotate_opt(cipher, row.tax_id)?;
+
+            sqlx::query(
+                "UPDATE customers SET email = $2, phone = $3, tax_id = $4, email_hash = $5, phone_hash = $6
+                 WHERE id = $1",
+            )
+            .bind(row.id)
+            .bind(email)
+            .bind(phone)
+            .bind(tax_id)
+            .bind(email_plain.and_then(|v| cipher.blind_index(PiiKind::Email, &v)))
+            .bind(phone_plain.and_then(|v| cipher.blind_index(PiiKind::Phone, &v)))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to re-encrypt customer: {}", e)))?;
+
+            batch.rows += 1;
+            batch.last_id = Some(row.id);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to commit transaction: {}", e)))?;
+        Ok(batch)
+    }
+
+    async fn rotate_address_pii_batch(
+        &self,
+        after: Option<Uuid>,
+        limit: u32,
+    ) -> ErpResult<PiiRotationBatch> {
+        let cipher = self
+            .pii
+            .cipher
+            .as_ref()
+            .ok_or_else(|| ErpError::config("PII encryption is not enabled"))?;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to start transaction: {}", e)))?;
+
+        let rows = sqlx::query_as::<_, PgAddressPiiRow>(
+            "SELECT id, address_line1, address_line2, postal_code FROM customer_addresses
+             WHERE ($1::uuid IS NULL OR id > $1)
+             ORDER BY id LIMIT $2 FOR UPDATE",
+        )
+        .bind(after)
+        .bind(limit as i64)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to fetch customer addresses: {}", e)))?;
+
+        let mut batch = PiiRotationBatch::default();
+        for row in rows {
+            let address_line1 = batch.count(cipher.rotate(&row.address_line1)?);
+            let address_line2 = batch.rotate_opt(cipher, row.address_line2)?;
+            let postal_code = batch.rotate_opt(cipher, row.postal_code)?;
+
+            sqlx::query(
+                "UPDATE customer_addresses SET address_line1 = $2, address_line2 = $3, postal_code = $4
+                 WHERE id = $1",
+            )
+            .bind(row.id)
+            .bind(address_line1)
+            .bind(address_line2)
+            .bind(postal_code)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to re-encrypt address: {}", e)))?;
+
+            batch.rows += 1;
+            batch.last_id = Some(row.id);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to commit transaction: {}", e)))?;
+        Ok(batch)
+    }
+
     async fn create_customer(&self, customer: &Customer) -> ErpResult<()> {
         let full_name = format!("{} {}", customer.first_name, customer.last_name)
             .trim()
@@ -82,22 +403,24 @@ impl CustomerRepository for PostgresCustomerRepository {
             CustomerType::Retail => "retail",
         };
 
-        sqlx::query!(
+        sqlx::query(
             r#"
-            INSERT INTO customers (id, name, email, phone, company, tax_id, customer_type, credit_limit, current_balance, notes)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            INSERT INTO customers (id, name, email, phone, company, tax_id, customer_type, credit_limit, current_balance, notes, email_hash, phone_hash)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             "#,
-            customer.id,
-            full_name,
-            customer.email,
-            customer.phone,
-            customer.company_name,
-            customer.tax_id,
-            customer_type_str,
-            customer.credit_limit,
-            customer.current_balance,
-            customer.notes
         )
+        .bind(customer.id)
+        .bind(full_name)
+        .bind(self.pii.seal(Some(&customer.email))?)
+        .bind(self.pii.seal(customer.phone.as_deref())?)
+        .bind(&customer.company_name)
+        .bind(self.pii.seal(customer.tax_id.as_deref())?)
+        .bind(customer_type_str)
+        .bind(customer.credit_limit)
+        .bind(customer.current_balance)
+        .bind(&customer.notes)
+        .bind(self.pii.blind_index(PiiKind::Email, Some(&customer.email)))
+        .bind(self.pii.blind_index(PiiKind::Phone, customer.phone.as_deref()))
         .execute(&*self.pool)
         .await
         .map_err(|e| ErpError::database(format!("Failed to create customer: {}", e)))?;
@@ -112,21 +435,21 @@ impl CustomerRepository for PostgresCustomerRepository {
             AddressType::Both => "both",
         };
 
-        sqlx::query!(
+        sqlx::query(
             r#"
             INSERT INTO customer_addresses (id, customer_id, address_type, address_line1, city, state_province, postal_code, country, is_default)
             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             "#,
-            address.id,
-            address.customer_id,
-            address_type_str,
-            address.street_address,
-            address.city,
-            address.state_province,
-            address.postal_code,
-            address.country,
-            address.is_default
         )
+        .bind(address.id)
+        .bind(address.customer_id)
+        .bind(address_type_str)
+        .bind(self.pii.seal(Some(&address.street_address))?)
+        .bind(&address.city)
+        .bind(&address.state_province)
+        .bind(self.pii.seal(Some(&address.postal_code))?)
+        .bind(&address.country)
+        .bind(address.is_default)
         .execute(&*self.pool)
         .await
         .map_err(|e| ErpError::database(format!("Failed to create customer address: {}", e)))?;
@@ -135,11 +458,11 @@ impl CustomerRepository for PostgresCustomerRepository {
     }
 
     async fn get_customer_by_id(&self, id: Uuid) -> ErpResult<Option<Customer>> {
-        let row = sqlx::query!(
+        let row = sqlx::query_as::<_, PgCustomerRow>(
             "SELECT id, name, email, phone, company, tax_id, customer_type, credit_limit, current_balance, notes, status, created_at, updated_at
              FROM customers WHERE id = $1",
-            id
         )
+        .bind(id)
         .fetch_optional(&*self.pool)
         .await
         .map_err(|e| ErpError::database(format!("Failed to get customer by id: {}", e)))?;
@@ -182,7 +505,7 @@ impl CustomerRepository for PostgresCustomerRepository {
                 updated_at: row.updated_at,
             };
 
-            Ok(Some(customer))
+            Ok(Some(self.pii.open_customer(customer)?))
         } else {
             Ok(None)
         }
@@ -196,11 +519,11 @@ impl CustomerRepository for PostgresCustomerRepository {
 
         let partial_id = &customer_code[5..13]; // Extract the 8-char UUID prefix
 
-        let row = sqlx::query!(
+        let row = sqlx::query_as::<_, PgCustomerRow>(
             "SELECT id, name, email, phone, company, tax_id, customer_type, credit_limit, current_balance, notes, status, created_at, updated_at
              FROM customers WHERE SUBSTRING(id::text, 1, 8) = $1",
-            partial_id
         )
+        .bind(partial_id)
         .fetch_optional(&*self.pool)
         .await
         .map_err(|e| ErpError::database(format!("Failed to get customer by code: {}", e)))?;
@@ -243,18 +566,19 @@ impl CustomerRepository for PostgresCustomerRepository {
                 updated_at: row.updated_at,
             };
 
-            Ok(Some(customer))
+            Ok(Some(self.pii.open_customer(customer)?))
         } else {
             Ok(None)
         }
     }
 
     async fn get_customer_by_email(&self, email: &str) -> ErpResult<Option<Customer>> {
-        let row = sqlx::query!(
+        let row = sqlx::query_as::<_, PgCustomerRow>(
             "SELECT id, name, email, phone, company, tax_id, customer_type, credit_limit, current_balance, notes, status, created_at, updated_at
-             FROM customers WHERE email = $1",
-            email
+             FROM customers WHERE email_hash = ANY($1) OR (email_hash IS NULL AND email = $2)",
         )
+        .bind(self.pii.lookup_indexes(PiiKind::Email, email))
+        .bind(email)
         .fetch_optional(&*self.pool)
         .await
         .map_err(|e| ErpError::database(format!("Failed to get customer by email: {}", e)))?;
@@ -297,18 +621,18 @@ impl CustomerRepository for PostgresCustomerRepository {
                 updated_at: row.updated_at,
             };
 
-            Ok(Some(customer))
+            Ok(Some(self.pii.open_customer(customer)?))
         } else {
             Ok(None)
         }
     }
 
     async fn get_customer_addresses(&self, customer_id: Uuid) -> ErpResult<Vec<CustomerAddress>> {
-        let rows = sqlx::query!(
+        let rows = sqlx::query_as::<_, PgAddressRow>(
             "SELECT id, customer_id, address_type, address_line1, address_line2, city, state_province, postal_code, country, is_default, created_at
              FROM customer_addresses WHERE customer_id = $1",
-            customer_id
         )
+        .bind(customer_id)
         .fetch_all(&*self.pool)
         .await
         .map_err(|e| ErpError::database(format!("Failed to get customer addresses: {}", e)))?;
@@ -326,10 +650,10 @@ impl CustomerRepository for PostgresCustomerRepository {
                 id: row.id,
                 customer_id,
                 address_type,
-                street_address: row.address_line1,
+                street_address: self.pii.open(Some(row.address_line1))?.unwrap_or_default(),
                 city: row.city.unwrap_or_default(),
                 state_province: row.state_province.unwrap_or_default(),
-                postal_code: row.postal_code.unwrap_or_default(),
+                postal_code: self.pii.open(row.postal_code)?.unwrap_or_default(),
                 country: row.country.unwrap_or_default(),
                 is_default: row.is_default,
                 created_at: row.created_at,
@@ -350,6 +674,7 @@ impl CustomerRepository for PostgresCustomerRepository {
         sort_by: &str,
         sort_order: &str,
     ) -> ErpResult<CustomerListResponse> {
+        let pool = self.reads.pool().await;
         let offset = (page - 1) * per_page;
         let (sort_field, sort_order) = build_sort_clause(sort_by, sort_order);
 
@@ -361,11 +686,20 @@ impl CustomerRepository for PostgresCustomerRepository {
         if let Some(search) = &filter.search {
             param_count += 1;
             let search_pattern = format!("%{}%", search);
-            where_conditions.push(format!(
-                "(name ILIKE ${} OR email ILIKE ${} OR company ILIKE ${})",
-                param_count, param_count, param_count
-            ));
+            // Encrypted emails only match exactly, through their blind index
+            let mut condition = format!(
+                "name ILIKE ${0} OR company ILIKE ${0} OR (email_hash IS NULL AND email ILIKE ${0})",
+                param_count
+            );
             bind_values.push(search_pattern);
+
+            let email_indexes = self.pii.lookup_indexes(PiiKind::Email, search);
+            if !email_indexes.is_empty() {
+                param_count += 1;
+                condition.push_str(&format!(" OR email_hash = ANY(${}::text[])", param_count));
+                bind_values.push(format!("{{{}}}", email_indexes.join(",")));
+            }
+            where_conditions.push(format!("({})", condition));
         }
 
         if let Some(customer_type) = &filter.customer_type {
@@ -392,6 +726,17 @@ impl CustomerRepository for PostgresCustomerRepository {
             bind_values.push(status_str.to_string());
         }
 
+        if let Some(customer_ids) = &filter.customer_ids {
+            param_count += 1;
+            let id_list = customer_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            where_conditions.push(format!("id = ANY(${}::uuid[])", param_count));
+            bind_values.push(format!("{{{}}}", id_list));
+        }
+
         let where_clause = if where_conditions.is_empty() {
             String::new()
         } else {
@@ -405,7 +750,7 @@ impl CustomerRepository for PostgresCustomerRepository {
             count_query_builder = count_query_builder.bind(value);
         }
         let total: i64 = count_query_builder
-            .fetch_one(&*self.pool)
+            .fetch_one(&pool)
             .await
             .map_err(|e| ErpError::database(format!("Failed to count customers: {}", e)))?;
 
@@ -446,7 +791,7 @@ impl CustomerRepository for PostgresCustomerRepository {
         let rows = query_builder
             .bind(per_page as i64)
             .bind(offset as i64)
-            .fetch_all(&*self.pool)
+            .fetch_all(&pool)
             .await
             .map_err(|e| ErpError::database(format!("Failed to fetch customers: {}", e)))?;
 
@@ -498,6 +843,8 @@ impl CustomerRepository for PostgresCustomerRepository {
                 updated_at,
             };
 
+            let customer = self.pii.open_customer(customer)?;
+
             // Load addresses for each customer
             let addresses = self.get_customer_addresses(customer.id).await?;
             customers.push(customer.to_response(addresses));
@@ -528,26 +875,28 @@ impl CustomerRepository for PostgresCustomerRepository {
             CustomerStatus::Blacklisted => "blacklisted",
         };
 
-        sqlx::query!(
+        sqlx::query(
             r#"
             UPDATE customers
             SET name = $2, email = $3, phone = $4, company = $5,
                 tax_id = $6, customer_type = $7, credit_limit = $8, current_balance = $9, notes = $10,
-                status = $11, updated_at = CURRENT_TIMESTAMP
+                status = $11, email_hash = $12, phone_hash = $13, updated_at = CURRENT_TIMESTAMP
             WHERE id = $1
             "#,
-            id,
-            full_name,
-            customer.email,
-            customer.phone,
-            customer.company_name,
-            customer.tax_id,
-            customer_type_str,
-            customer.credit_limit,
-            customer.current_balance,
-            customer.notes,
-            status_str
         )
+        .bind(id)
+        .bind(full_name)
+        .bind(self.pii.seal(Some(&customer.email))?)
+        .bind(self.pii.seal(customer.phone.as_deref())?)
+        .bind(&customer.company_name)
+        .bind(self.pii.seal(customer.tax_id.as_deref())?)
+        .bind(customer_type_str)
+        .bind(customer.credit_limit)
+        .bind(customer.current_balance)
+        .bind(&customer.notes)
+        .bind(status_str)
+        .bind(self.pii.blind_index(PiiKind::Email, Some(&customer.email)))
+        .bind(self.pii.blind_index(PiiKind::Phone, customer.phone.as_deref()))
         .execute(&*self.pool)
         .await
         .map_err(|e| ErpError::database(format!("Failed to update customer: {}", e)))?;
@@ -560,15 +909,15 @@ impl CustomerRepository for PostgresCustomerRepository {
         id: Uuid,
         new_balance: rust_decimal::Decimal,
     ) -> ErpResult<()> {
-        sqlx::query!(
+        sqlx::query(
             r#"
             UPDATE customers
             SET current_balance = $2, updated_at = CURRENT_TIMESTAMP
             WHERE id = $1
             "#,
-            id,
-            new_balance
         )
+        .bind(id)
+        .bind(new_balance)
         .execute(&*self.pool)
         .await
         .map_err(|e| ErpError::database(format!("Failed to update customer balance: {}", e)))?;
@@ -576,56 +925,49 @@ impl CustomerRepository for PostgresCustomerRepository {
         Ok(())
     }
 
-    async fn delete_customer(&self, id: Uuid) -> ErpResult<()> {
-        // Start a transaction to ensure atomicity
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(|e| ErpError::database(format!("Failed to start transaction: {}", e)))?;
-
+    async fn delete_customer_in(&self, uow: &mut UnitOfWork, id: Uuid) -> ErpResult<()> {
         // First, delete all customer addresses
-        sqlx::query!("DELETE FROM customer_addresses WHERE customer_id = $1", id)
-            .execute(&mut *tx)
+        sqlx::query("DELETE FROM customer_addresses WHERE customer_id = $1")
+            .bind(id)
+            .execute(uow.postgres()?)
             .await
             .map_err(|e| {
                 ErpError::database(format!("Failed to delete customer addresses: {}", e))
             })?;
 
         // Then delete the customer
-        let result = sqlx::query!("DELETE FROM customers WHERE id = $1", id)
-            .execute(&mut *tx)
+        let result = sqlx::query("DELETE FROM customers WHERE id = $1")
+            .bind(id)
+            .execute(uow.postgres()?)
             .await
             .map_err(|e| ErpError::database(format!("Failed to delete customer: {}", e)))?;
 
-        // Check if customer was actually deleted
+        // The caller's unit of work rolls back the address delete
         if result.rows_affected() == 0 {
-            tx.rollback().await.map_err(|e| {
-                ErpError::database(format!("Failed to rollback transaction: {}", e))
-            })?;
             return Err(ErpError::not_found_simple("Customer not found"));
         }
 
-        // Commit the transaction
-        tx.commit()
-            .await
-            .map_err(|e| ErpError::database(format!("Failed to commit transaction: {}", e)))?;
-
         Ok(())
     }
 
     async fn search_customers(&self, query: &str, limit: u32) -> ErpResult<Vec<Customer>> {
+        let pool = self.reads.pool().await;
         let search_pattern = format!("%{}%", query);
-        let rows = sqlx::query!(
+        let rows = sqlx::query_as::<_, PgCustomerRow>(
             "SELECT id, name, email, phone, company, tax_id, customer_type, credit_limit, current_balance, notes, created_at, updated_at
              FROM customers
-             WHERE name ILIKE $1 OR email ILIKE $1 OR company ILIKE $1 OR phone ILIKE $1
+             WHERE name ILIKE $1 OR company ILIKE $1
+                OR (email_hash IS NULL AND email ILIKE $1)
+                OR (phone_hash IS NULL AND phone NOT LIKE 'enc:v1:%' AND phone ILIKE $1)
+                OR email_hash = ANY($3) OR phone_hash = ANY($4)
              ORDER BY created_at DESC
              LIMIT $2",
-            search_pattern,
-            limit as i64
         )
-        .fetch_all(&*self.pool)
+        .bind(search_pattern)
+        .bind(limit as i64)
+        .bind(self.pii.lookup_indexes(PiiKind::Email, query))
+        .bind(self.pii.lookup_indexes(PiiKind::Phone, query))
+        .fetch_all(&pool)
         .await
         .map_err(|e| ErpError::database(format!("Failed to search customers: {}", e)))?;
 
@@ -660,16 +1002,71 @@ impl CustomerRepository for PostgresCustomerRepository {
                 updated_at: row.updated_at,
             };
 
-            customers.push(customer);
+            customers.push(self.pii.open_customer(customer)?);
         }
 
         Ok(customers)
     }
 
     async fn get_customers_with_outstanding_balance(&self) -> ErpResult<Vec<Customer>> {
-        Err(ErpError::internal(
-            "Database operations not yet implemented - use MockCustomerRepository for testing",
-        ))
+        let rows = sqlx::query_as::<_, PgCustomerRow>(
+            "SELECT id, name, email, phone, company, tax_id, customer_type, credit_limit, current_balance, notes, status, created_at, updated_at
+             FROM customers
+             WHERE current_balance > 0
+             ORDER BY current_balance DESC",
+        )
+        .fetch_all(&*self.pool)
+        .await
+        .map_err(|e| {
+            ErpError::database(format!(
+                "Failed to get customers with outstanding balance: {}",
+                e
+            ))
+        })?;
+
+        let mut customers = Vec::new();
+        for row in rows {
+            let customer_type = match row.customer_type.as_str() {
+                "business" => CustomerType::Business,
+                "wholesale" => CustomerType::Wholesale,
+                "retail" => CustomerType::Retail,
+                _ => CustomerType::Individual,
+            };
+
+            let status = match row.status.as_deref() {
+                Some("active") => CustomerStatus::Active,
+                Some("inactive") => CustomerStatus::Inactive,
+                Some("suspended") => CustomerStatus::Suspended,
+                Some("blacklisted") => CustomerStatus::Blacklisted,
+                _ => CustomerStatus::Active,
+            };
+
+            let name_parts: Vec<&str> = row.name.splitn(2, ' ').collect();
+            let first_name = name_parts.first().unwrap_or(&"").to_string();
+            let last_name = name_parts.get(1).unwrap_or(&"").to_string();
+
+            let customer = Customer {
+                id: row.id,
+                customer_code: format!("CUST-{}", &row.id.to_string()[..8]),
+                first_name,
+                last_name,
+                company_name: row.company,
+                email: row.email.unwrap_or_default(),
+                phone: row.phone,
+                customer_type,
+                status,
+                credit_limit: row.credit_limit.unwrap_or_default(),
+                current_balance: row.current_balance.unwrap_or_default(),
+                tax_id: row.tax_id,
+                notes: row.notes,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            };
+
+            customers.push(self.pii.open_customer(customer)?);
+        }
+
+        Ok(customers)
     }
 
     async fn get_customers_by_type(
@@ -694,74 +1091,769 @@ impl CustomerRepository for PostgresCustomerRepository {
     }
 
     async fn get_customer_orders(&self, customer_id: Uuid) -> ErpResult<Vec<Uuid>> {
-        let rows = sqlx::query!(
+        let order_ids = sqlx::query_scalar::<_, Uuid>(
             r#"
             SELECT id
             FROM sales_orders
             WHERE customer_id = $1
             "#,
-            customer_id
         )
+        .bind(customer_id)
         .fetch_all(&*self.pool)
         .await
         .map_err(|e| ErpError::database(format!("Failed to get customer orders: {}", e)))?;
 
-        Ok(rows.into_iter().map(|row| row.id).collect())
+        Ok(order_ids)
     }
 
-    async fn delete_customer_order(&self, order_id: Uuid) -> ErpResult<()> {
-        let mut tx = self
-            .pool
-            .begin()
+    async fn delete_customer_order_in(
+        &self,
+        uow: &mut UnitOfWork,
+        order_id: Uuid,
+    ) -> ErpResult<()> {
+        // First, delete all order items
+        sqlx::query("DELETE FROM sales_order_items WHERE order_id = $1")
+            .bind(order_id)
+            .execute(uow.postgres()?)
             .await
-            .map_err(|e| ErpError::database(format!("Failed to start transaction: {}", e)))?;
+            .map_err(|e| ErpError::database(format!("Failed to delete order items: {}", e)))?;
 
-        // First, delete all order items
-        sqlx::query!(
-            "DELETE FROM sales_order_items WHERE order_id = $1",
-            order_id
+        // Then delete the order
+        sqlx::query("DELETE FROM sales_orders WHERE id = $1")
+            .bind(order_id)
+            .execute(uow.postgres()?)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to delete order: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn delete_customer_address_in(
+        &self,
+        uow: &mut UnitOfWork,
+        address_id: Uuid,
+    ) -> ErpResult<()> {
+        sqlx::query("DELETE FROM customer_addresses WHERE id = $1")
+            .bind(address_id)
+            .execute(uow.postgres()?)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to delete customer address: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn get_customer_order_metrics(&self) -> ErpResult<Vec<CustomerOrderMetrics>> {
+        // Cancelled and returned orders do not count towards RFM
+        let rows = sqlx::query_as::<
+            _,
+            (
+                Uuid,
+                String,
+                Option<String>,
+                Option<chrono::NaiveDate>,
+                Option<chrono::NaiveDate>,
+                i64,
+                rust_decimal::Decimal,
+            ),
+        >(
+            r#"
+            SELECT c.id, c.name, c.email,
+                   MIN(so.order_date)::date AS first_order_date,
+                   MAX(so.order_date)::date AS last_order_date,
+                   COUNT(so.id) AS order_count,
+                   COALESCE(SUM(so.total_amount), 0) AS total_spent
+            FROM customers c
+            LEFT JOIN sales_orders so
+                   ON so.customer_id = c.id
+                  AND so.status NOT IN ('cancelled', 'returned', 'draft')
+            GROUP BY c.id, c.name, c.email
+            "#,
         )
-        .execute(&mut *tx)
+        .fetch_all(&*self.pool)
         .await
-        .map_err(|e| ErpError::database(format!("Failed to delete order items: {}", e)))?;
+        .map_err(|e| ErpError::database(format!("Failed to get customer order metrics: {}", e)))?;
+
+        rows.into_iter()
+            .map(
+                |(
+                    customer_id,
+                    name,
+                    email,
+                    first_order_date,
+                    last_order_date,
+                    order_count,
+                    total_spent,
+                )| {
+                    Ok(CustomerOrderMetrics {
+                        customer_id,
+                        name,
+                        email: self.pii.open(email)?,
+                        first_order_date,
+                        last_order_date,
+                        order_count: order_count as u32,
+                        total_spent,
+                    })
+                },
+            )
+            .collect()
+    }
+}
 
-        // Then delete the order
-        sqlx::query!("DELETE FROM sales_orders WHERE id = $1", order_id)
-            .execute(&mut *tx)
+pub struct SqliteCustomerRepository {
+    pool: SqlitePool,
+    reads: ReadRouter<Sqlite>,
+    pii: PiiCodec,
+}
+
+const SQLITE_CUSTOMER_COLUMNS: &str = "id, name, email, phone, company, tax_id, customer_type, \
+     credit_limit, current_balance, notes, status, created_at, updated_at";
+
+fn customer_type_str(customer_type: &CustomerType) -> &'static str {
+    match customer_type {
+        CustomerType::Individual => "individual",
+        CustomerType::Business => "business",
+        CustomerType::Wholesale => "wholesale",
+        CustomerType::Retail => "retail",
+    }
+}
+
+fn customer_status_str(status: &CustomerStatus) -> &'static str {
+    match status {
+        CustomerStatus::Active => "active",
+        CustomerStatus::Inactive => "inactive",
+        CustomerStatus::Suspended => "suspended",
+        CustomerStatus::Blacklisted => "blacklisted",
+    }
+}
+
+impl SqliteCustomerRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            reads: ReadRouter::primary_only(pool.clone()),
+            pool,
+            pii: PiiCodec::default(),
+        }
+    }
+
+    /// Sends customer listings and searches to read replicas
+    pub fn with_read_router(mut self, reads: ReadRouter<Sqlite>) -> Self {
+        self.reads = reads;
+        self
+    }
+
+    /// Encrypts email, phone, tax ID and street/postal address columns at rest
+    pub fn with_pii_cipher(mut self, cipher: Option<Arc<PiiCipher>>) -> Self {
+        self.pii.cipher = cipher;
+        self
+    }
+
+    fn customer_from_row(&self, row: &SqliteRow) -> ErpResult<Customer> {
+        let parse = || -> Result<Customer, sqlx::Error> {
+            let id: Uuid = row.try_get("id")?;
+            let name: String = row.try_get("name")?;
+            let customer_type: String = row.try_get("customer_type")?;
+            let status: Option<String> = row.try_get("status")?;
+
+            let name_parts: Vec<&str> = name.splitn(2, ' ').collect();
+
+            Ok(Customer {
+                id,
+                customer_code: format!("CUST-{}", &id.to_string()[..8]),
+                first_name: name_parts.first().unwrap_or(&"").to_string(),
+                last_name: name_parts.get(1).unwrap_or(&"").to_string(),
+                company_name: row.try_get("company")?,
+                email: row
+                    .try_get::<Option<String>, _>("email")?
+                    .unwrap_or_default(),
+                phone: row.try_get("phone")?,
+                customer_type: match customer_type.as_str() {
+                    "business" => CustomerType::Business,
+                    "wholesale" => CustomerType::Wholesale,
+                    "retail" => CustomerType::Retail,
+                    _ => CustomerType::Individual,
+                },
+                status: match status.as_deref() {
+                    Some("inactive") => CustomerStatus::Inactive,
+                    Some("suspended") => CustomerStatus::Suspended,
+                    Some("blacklisted") => CustomerStatus::Blacklisted,
+                    _ => CustomerStatus::Active,
+                },
+                credit_limit: sqlite::opt_decimal(row, "credit_limit")?.unwrap_or_default(),
+                current_balance: sqlite::opt_decimal(row, "current_balance")?.unwrap_or_default(),
+                tax_id: row.try_get("tax_id")?,
+                notes: row.try_get("notes")?,
+                created_at: row.try_get("created_at")?,
+                updated_at: row.try_get("updated_at")?,
+            })
+        };
+
+        let customer =
+            parse().map_err(|e| ErpError::database(format!("Failed to parse customer: {}", e)))?;
+        self.pii.open_customer(customer)
+    }
+
+    async fn fetch_customers<'q>(
+        &self,
+        query: sqlx::query::Query<'q, Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    ) -> ErpResult<Vec<Customer>> {
+        self.fetch_customers_from(&self.pool, query).await
+    }
+
+    async fn fetch_customers_from<'q>(
+        &self,
+        pool: &SqlitePool,
+        query: sqlx::query::Query<'q, Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    ) -> ErpResult<Vec<Customer>> {
+        let rows = query
+            .fetch_all(pool)
             .await
-            .map_err(|e| ErpError::database(format!("Failed to delete order: {}", e)))?;
+            .map_err(|e| ErpError::database(format!("Failed to fetch customers: {}", e)))?;
 
-        tx.commit()
+        rows.iter().map(|row| self.customer_from_row(row)).collect()
+    }
+
+    async fn fetch_customer<'q>(
+        &self,
+        query: sqlx::query::Query<'q, Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    ) -> ErpResult<Option<Customer>> {
+        Ok(self.fetch_customers(query).await?.into_iter().next())
+    }
+
+    fn push_in<'q, T>(builder: &mut sqlx::QueryBuilder<'q, Sqlite>, values: Vec<T>)
+    where
+        T: 'q + Send + sqlx::Encode<'q, Sqlite> + sqlx::Type<Sqlite>,
+    {
+        builder.push("(");
+        let mut separated = builder.separated(", ");
+        for value in values {
+            separated.push_bind(value);
+        }
+        separated.push_unseparated(")");
+    }
+
+    fn push_filters<'q>(
+        &self,
+        builder: &mut sqlx::QueryBuilder<'q, Sqlite>,
+        filter: &CustomerFilter,
+    ) {
+        builder.push(" WHERE 1 = 1");
+
+        if let Some(search) = &filter.search {
+            let pattern = format!("%{}%", search);
+            // Encrypted emails only match exactly, through their blind index
+            builder.push(" AND (name LIKE ");
+            builder.push_bind(pattern.clone());
+            builder.push(" OR company LIKE ");
+            builder.push_bind(pattern.clone());
+            builder.push(" OR (email_hash IS NULL AND email LIKE ");
+            builder.push_bind(pattern);
+            builder.push(")");
+
+            let email_indexes = self.pii.lookup_indexes(PiiKind::Email, search);
+            if !email_indexes.is_empty() {
+                builder.push(" OR email_hash IN ");
+                Self::push_in(builder, email_indexes);
+            }
+            builder.push(")");
+        }
+
+        if let Some(customer_type) = &filter.customer_type {
+            builder.push(" AND customer_type = ");
+            builder.push_bind(customer_type_str(customer_type));
+        }
+
+        if let Some(status) = &filter.status {
+            builder.push(" AND status = ");
+            builder.push_bind(customer_status_str(status));
+        }
+
+        if let Some(customer_ids) = &filter.customer_ids {
+            if customer_ids.is_empty() {
+                builder.push(" AND 0");
+            } else {
+                builder.push(" AND id IN ");
+                Self::push_in(builder, customer_ids.clone());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl CustomerRepository for SqliteCustomerRepository {
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        UnitOfWork::begin_sqlite(&self.pool).await
+    }
+
+    async fn create_customer(&self, customer: &Customer) -> ErpResult<()> {
+        let full_name = format!("{} {}", customer.first_name, customer.last_name)
+            .trim()
+            .to_string();
+
+        sqlx::query(
+            "INSERT INTO customers (id, name, email, phone, company, tax_id, customer_type, \
+             credit_limit, current_balance, notes, status, email_hash, phone_hash, created_at, updated_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(customer.id)
+        .bind(full_name)
+        .bind(self.pii.seal(Some(&customer.email))?)
+        .bind(self.pii.seal(customer.phone.as_deref())?)
+        .bind(&customer.company_name)
+        .bind(self.pii.seal(customer.tax_id.as_deref())?)
+        .bind(customer_type_str(&customer.customer_type))
+        .bind(sqlite::decimal_text(customer.credit_limit))
+        .bind(sqlite::decimal_text(customer.current_balance))
+        .bind(&customer.notes)
+        .bind(customer_status_str(&customer.status))
+        .bind(self.pii.blind_index(PiiKind::Email, Some(&customer.email)))
+        .bind(self.pii.blind_index(PiiKind::Phone, customer.phone.as_deref()))
+        .bind(customer.created_at)
+        .bind(customer.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to create customer: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn create_customer_address(&self, address: &CustomerAddress) -> ErpResult<()> {
+        let address_type_str = match address.address_type {
+            AddressType::Billing => "billing",
+            AddressType::Shipping => "shipping",
+            AddressType::Both => "both",
+        };
+
+        sqlx::query(
+            "INSERT INTO customer_addresses (id, customer_id, address_type, address_line1, city, \
+             state_province, postal_code, country, is_default, created_at) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(address.id)
+        .bind(address.customer_id)
+        .bind(address_type_str)
+        .bind(self.pii.seal(Some(&address.street_address))?)
+        .bind(&address.city)
+        .bind(&address.state_province)
+        .bind(self.pii.seal(Some(&address.postal_code))?)
+        .bind(&address.country)
+        .bind(address.is_default)
+        .bind(address.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to create customer address: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn get_customer_by_id(&self, id: Uuid) -> ErpResult<Option<Customer>> {
+        let query = format!(
+            "SELECT {} FROM customers WHERE id = ?",
+            SQLITE_CUSTOMER_COLUMNS
+        );
+        self.fetch_customer(sqlx::query(&query).bind(id)).await
+    }
+
+    async fn get_customer_by_code(&self, customer_code: &str) -> ErpResult<Option<Customer>> {
+        // Extract UUID from customer code format "CUST-{first 8 chars of UUID}"
+        if !customer_code.starts_with("CUST-") || customer_code.len() < 13 {
+            return Ok(None);
+        }
+        let partial_id = customer_code[5..13].to_lowercase();
+
+        // The id is a 16-byte BLOB; its first four bytes are the code's eight hex digits
+        let query = format!(
+            "SELECT {} FROM customers WHERE lower(hex(substr(id, 1, 4))) = ?",
+            SQLITE_CUSTOMER_COLUMNS
+        );
+        self.fetch_customer(sqlx::query(&query).bind(partial_id))
             .await
-            .map_err(|e| ErpError::database(format!("Failed to commit transaction: {}", e)))?;
+    }
+
+    async fn get_customer_by_email(&self, email: &str) -> ErpResult<Option<Customer>> {
+        let mut builder = sqlx::QueryBuilder::new(format!(
+            "SELECT {} FROM customers WHERE (email_hash IS NULL AND email = ",
+            SQLITE_CUSTOMER_COLUMNS
+        ));
+        builder.push_bind(email.to_string());
+        builder.push(")");
+
+        let indexes = self.pii.lookup_indexes(PiiKind::Email, email);
+        if !indexes.is_empty() {
+            builder.push(" OR email_hash IN ");
+            Self::push_in(&mut builder, indexes);
+        }
+        builder.push(" LIMIT 1");
+
+        self.fetch_customer(builder.build()).await
+    }
+
+    async fn get_customer_addresses(&self, customer_id: Uuid) -> ErpResult<Vec<CustomerAddress>> {
+        let rows = sqlx::query(
+            "SELECT id, address_type, address_line1, city, state_province, postal_code, country, \
+             is_default, created_at FROM customer_addresses WHERE customer_id = ?",
+        )
+        .bind(customer_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to get customer addresses: {}", e)))?;
+
+        let mut addresses = Vec::new();
+        for row in rows {
+            let parse = || -> Result<_, sqlx::Error> {
+                let address_type: String = row.try_get("address_type")?;
+                Ok((
+                    row.try_get::<Uuid, _>("id")?,
+                    match address_type.as_str() {
+                        "shipping" => AddressType::Shipping,
+                        "both" => AddressType::Both,
+                        _ => AddressType::Billing,
+                    },
+                    row.try_get::<String, _>("address_line1")?,
+                    row.try_get::<Option<String>, _>("city")?,
+                    row.try_get::<Option<String>, _>("state_province")?,
+                    row.try_get::<Option<String>, _>("postal_code")?,
+                    row.try_get::<Option<String>, _>("country")?,
+                    row.try_get::<bool, _>("is_default")?,
+                    row.try_get::<chrono::DateTime<chrono::Utc>, _>("created_at")?,
+                ))
+            };
+            let (
+                id,
+                address_type,
+                address_line1,
+                city,
+                state_province,
+                postal_code,
+                country,
+                is_default,
+                created_at,
+            ) = parse()
+                .map_err(|e| ErpError::database(format!("Failed to parse address: {}", e)))?;
+
+            addresses.push(CustomerAddress {
+                id,
+                customer_id,
+                address_type,
+                street_address: self.pii.open(Some(address_line1))?.unwrap_or_default(),
+                city: city.unwrap_or_default(),
+                state_province: state_province.unwrap_or_default(),
+                postal_code: self.pii.open(postal_code)?.unwrap_or_default(),
+                country: country.unwrap_or_default(),
+                is_default,
+                created_at,
+                updated_at: created_at,
+            });
+        }
+
+        Ok(addresses)
+    }
+
+    async fn list_customers(
+        &self,
+        filter: &CustomerFilter,
+        page: u32,
+        per_page: u32,
+        sort_by: &str,
+        sort_order: &str,
+    ) -> ErpResult<CustomerListResponse> {
+        let offset = (page - 1) * per_page;
+        let (sort_field, sort_order) = build_sort_clause(sort_by, sort_order);
+
+        let pool = self.reads.pool().await;
+        let mut count_builder = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM customers");
+        self.push_filters(&mut count_builder, filter);
+        let total: i64 = count_builder
+            .build_query_scalar()
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to count customers: {}", e)))?;
+
+        let mut builder =
+            sqlx::QueryBuilder::new(format!("SELECT {} FROM customers", SQLITE_CUSTOMER_COLUMNS));
+        self.push_filters(&mut builder, filter);
+        builder.push(format!(" ORDER BY {} {} LIMIT ", sort_field, sort_order));
+        builder.push_bind(per_page as i64);
+        builder.push(" OFFSET ");
+        builder.push_bind(offset as i64);
+
+        let mut customers = Vec::new();
+        for customer in self.fetch_customers_from(&pool, builder.build()).await? {
+            let addresses = self.get_customer_addresses(customer.id).await?;
+            customers.push(customer.to_response(addresses));
+        }
+
+        Ok(CustomerListResponse {
+            customers,
+            total,
+            page,
+            per_page,
+        })
+    }
+
+    async fn update_customer(&self, id: Uuid, customer: &Customer) -> ErpResult<()> {
+        let full_name = format!("{} {}", customer.first_name, customer.last_name)
+            .trim()
+            .to_string();
+
+        sqlx::query(
+            "UPDATE customers SET name = ?, email = ?, phone = ?, company = ?, tax_id = ?, \
+             customer_type = ?, credit_limit = ?, current_balance = ?, notes = ?, status = ?, \
+             email_hash = ?, phone_hash = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(full_name)
+        .bind(self.pii.seal(Some(&customer.email))?)
+        .bind(self.pii.seal(customer.phone.as_deref())?)
+        .bind(&customer.company_name)
+        .bind(self.pii.seal(customer.tax_id.as_deref())?)
+        .bind(customer_type_str(&customer.customer_type))
+        .bind(sqlite::decimal_text(customer.credit_limit))
+        .bind(sqlite::decimal_text(customer.current_balance))
+        .bind(&customer.notes)
+        .bind(customer_status_str(&customer.status))
+        .bind(self.pii.blind_index(PiiKind::Email, Some(&customer.email)))
+        .bind(
+            self.pii
+                .blind_index(PiiKind::Phone, customer.phone.as_deref()),
+        )
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to update customer: {}", e)))?;
 
         Ok(())
     }
 
-    async fn delete_customer_address(&self, address_id: Uuid) -> ErpResult<()> {
-        sqlx::query!("DELETE FROM customer_addresses WHERE id = $1", address_id)
-            .execute(&*self.pool)
+    async fn update_customer_balance(
+        &self,
+        id: Uuid,
+        new_balance: rust_decimal::Decimal,
+    ) -> ErpResult<()> {
+        sqlx::query("UPDATE customers SET current_balance = ?, updated_at = ? WHERE id = ?")
+            .bind(sqlite::decimal_text(new_balance))
+            .bind(chrono::Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to update customer balance: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn delete_customer_in(&self, uow: &mut UnitOfWork, id: Uuid) -> ErpResult<()> {
+        // Addresses go with the customer through ON DELETE CASCADE
+        let result = sqlx::query("DELETE FROM customers WHERE id = ?")
+            .bind(id)
+            .execute(uow.sqlite()?)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to delete customer: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(ErpError::not_found_simple("Customer not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn search_customers(&self, query: &str, limit: u32) -> ErpResult<Vec<Customer>> {
+        let pool = self.reads.pool().await;
+        let pattern = format!("%{}%", query);
+        let mut builder = sqlx::QueryBuilder::new(format!(
+            "SELECT {} FROM customers WHERE name LIKE ",
+            SQLITE_CUSTOMER_COLUMNS
+        ));
+        builder.push_bind(pattern.clone());
+        builder.push(" OR company LIKE ");
+        builder.push_bind(pattern.clone());
+        builder.push(" OR (email_hash IS NULL AND email LIKE ");
+        builder.push_bind(pattern.clone());
+        builder.push(") OR (phone_hash IS NULL AND phone NOT LIKE 'enc:v1:%' AND phone LIKE ");
+        builder.push_bind(pattern);
+        builder.push(")");
+
+        let email_indexes = self.pii.lookup_indexes(PiiKind::Email, query);
+        if !email_indexes.is_empty() {
+            builder.push(" OR email_hash IN ");
+            Self::push_in(&mut builder, email_indexes);
+        }
+        let phone_indexes = self.pii.lookup_indexes(PiiKind::Phone, query);
+        if !phone_indexes.is_empty() {
+            builder.push(" OR phone_hash IN ");
+            Self::push_in(&mut builder, phone_indexes);
+        }
+
+        builder.push(" ORDER BY created_at DESC LIMIT ");
+        builder.push_bind(limit as i64);
+
+        self.fetch_customers_from(&pool, builder.build()).await
+    }
+
+    async fn get_customers_with_outstanding_balance(&self) -> ErpResult<Vec<Customer>> {
+        let query = format!(
+            "SELECT {} FROM customers WHERE CAST(current_balance AS REAL) > 0 \
+             ORDER BY CAST(current_balance AS REAL) DESC",
+            SQLITE_CUSTOMER_COLUMNS
+        );
+        self.fetch_customers(sqlx::query(&query)).await
+    }
+
+    async fn get_customers_by_type(
+        &self,
+        customer_type: &CustomerType,
+    ) -> ErpResult<Vec<Customer>> {
+        let query = format!(
+            "SELECT {} FROM customers WHERE customer_type = ? ORDER BY name",
+            SQLITE_CUSTOMER_COLUMNS
+        );
+        self.fetch_customers(sqlx::query(&query).bind(customer_type_str(customer_type)))
+            .await
+    }
+
+    async fn count_customers(&self) -> ErpResult<i64> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM customers")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to count customers: {}", e)))
+    }
+
+    async fn count_customers_by_status(&self, status: &CustomerStatus) -> ErpResult<i64> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM customers WHERE COALESCE(status, 'active') = ?")
+            .bind(customer_status_str(status))
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to count customers: {}", e)))
+    }
+
+    async fn get_customer_orders(&self, customer_id: Uuid) -> ErpResult<Vec<Uuid>> {
+        sqlx::query_scalar("SELECT id FROM sales_orders WHERE customer_id = ?")
+            .bind(customer_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to get customer orders: {}", e)))
+    }
+
+    async fn delete_customer_order_in(
+        &self,
+        uow: &mut UnitOfWork,
+        order_id: Uuid,
+    ) -> ErpResult<()> {
+        // Order items go with the order through ON DELETE CASCADE
+        sqlx::query("DELETE FROM sales_orders WHERE id = ?")
+            .bind(order_id)
+            .execute(uow.sqlite()?)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to delete order: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn delete_customer_address_in(
+        &self,
+        uow: &mut UnitOfWork,
+        address_id: Uuid,
+    ) -> ErpResult<()> {
+        sqlx::query("DELETE FROM customer_addresses WHERE id = ?")
+            .bind(address_id)
+            .execute(uow.sqlite()?)
             .await
             .map_err(|e| ErpError::database(format!("Failed to delete customer address: {}", e)))?;
 
         Ok(())
     }
+
+    async fn get_customer_order_metrics(&self) -> ErpResult<Vec<CustomerOrderMetrics>> {
+        // Cancelled and returned orders do not count towards RFM; totals are
+        // summed in Rust because the amounts are decimal text
+        let rows = sqlx::query(
+            "SELECT c.id, c.name, c.email, so.order_date, so.total_amount \
+             FROM customers c \
+             LEFT JOIN sales_orders so \
+                    ON so.customer_id = c.id \
+                   AND so.status NOT IN ('cancelled', 'returned', 'draft') \
+             ORDER BY c.id",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to get customer order metrics: {}", e)))?;
+
+        let mut metrics: Vec<CustomerOrderMetrics> = Vec::new();
+        for row in rows {
+            let parse = || -> Result<_, sqlx::Error> {
+                Ok((
+                    row.try_get::<Uuid, _>("id")?,
+                    row.try_get::<String, _>("name")?,
+                    row.try_get::<Option<String>, _>("email")?,
+                    row.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>("order_date")?,
+                    sqlite::opt_decimal(&row, "total_amount")?,
+                ))
+            };
+            let (customer_id, name, email, order_date, total_amount) = parse().map_err(|e| {
+                ErpError::database(format!("Failed to parse customer order metrics: {}", e))
+            })?;
+
+            if metrics.last().map(|m| m.customer_id) != Some(customer_id) {
+                metrics.push(CustomerOrderMetrics {
+                    customer_id,
+                    name,
+                    email: self.pii.open(email)?,
+                    first_order_date: None,
+                    last_order_date: None,
+                    order_count: 0,
+                    total_spent: rust_decimal::Decimal::ZERO,
+                });
+            }
+
+            if let (Some(entry), Some(order_date)) = (metrics.last_mut(), order_date) {
+                let date = order_date.date_naive();
+                entry.first_order_date = Some(entry.first_order_date.map_or(date, |d| d.min(date)));
+                entry.last_order_date = Some(entry.last_order_date.map_or(date, |d| d.max(date)));
+                entry.order_count += 1;
+                entry.total_spent += total_amount.unwrap_or_default();
+            }
+        }
+
+        Ok(metrics)
+    }
 }
 
 // Mock repository for testing
 pub struct MockCustomerRepository {
-    customers: std::sync::Mutex<std::collections::HashMap<Uuid, Customer>>,
-    addresses: std::sync::Mutex<std::collections::HashMap<Uuid, Vec<CustomerAddress>>>,
+    customers: Arc<std::sync::Mutex<std::collections::HashMap<Uuid, Customer>>>,
+    addresses: Arc<std::sync::Mutex<std::collections::HashMap<Uuid, Vec<CustomerAddress>>>>,
+    order_metrics: std::sync::Mutex<std::collections::HashMap<Uuid, CustomerOrderMetrics>>,
+    orders: Arc<std::sync::Mutex<std::collections::HashMap<Uuid, Uuid>>>,
+    fail_unit_of_work_after: Option<usize>,
 }
 
 impl MockCustomerRepository {
     pub fn new() -> Self {
         Self {
-            customers: std::sync::Mutex::new(std::collections::HashMap::new()),
-            addresses: std::sync::Mutex::new(std::collections::HashMap::new()),
+            customers: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            addresses: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            order_metrics: std::sync::Mutex::new(std::collections::HashMap::new()),
+            orders: Arc::new(std::sync::Mutex::new(std::collections::HashMap::new())),
+            fail_unit_of_work_after: None,
         }
     }
 
+    /// Seed an order returned by `get_customer_orders`
+    pub fn add_order(&self, customer_id: Uuid, order_id: Uuid) {
+        let mut orders = self.orders.lock().unwrap();
+        orders.insert(order_id, customer_id);
+    }
+
+    /// Units of work begun by this repository fail after `writes` writes
+    pub fn failing_unit_of_work_after(mut self, writes: usize) -> Self {
+        self.fail_unit_of_work_after = Some(writes);
+        self
+    }
+
+    /// Seed order aggregates used by `get_customer_order_metrics`
+    pub fn set_order_metrics(&self, metrics: CustomerOrderMetrics) {
+        let mut store = self.order_metrics.lock().unwrap();
+        store.insert(metrics.customer_id, metrics);
+    }
+
     pub fn with_customers(customers: Vec<Customer>) -> Self {
         let repo = Self::new();
         {
@@ -782,6 +1874,14 @@ impl Default for MockCustomerRepository {
 
 #[async_trait]
 impl CustomerRepository for MockCustomerRepository {
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        let mock = match self.fail_unit_of_work_after {
+            Some(writes) => MockUnitOfWork::failing_after(writes),
+            None => MockUnitOfWork::new(),
+        };
+        Ok(UnitOfWork::mock(mock))
+    }
+
     async fn create_customer(&self, customer: &Customer) -> ErpResult<()> {
         let mut customers = self.customers.lock().unwrap();
 
@@ -855,6 +1955,9 @@ impl CustomerRepository for MockCustomerRepository {
         if let Some(customer_type) = &filter.customer_type {
             filtered_customers.retain(|c| &c.customer_type == customer_type);
         }
+        if let Some(customer_ids) = &filter.customer_ids {
+            filtered_customers.retain(|c| customer_ids.contains(&c.id));
+        }
         if let Some(search) = &filter.search {
             let search_lower = search.to_lowercase();
             filtered_customers.retain(|c| {
@@ -902,9 +2005,9 @@ impl CustomerRepository for MockCustomerRepository {
             }
             "created_at" => {
                 if sort_order == "ASC" {
-                    filtered_customers.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+                    filtered_customers.sort_by_key(|x| x.created_at);
                 } else {
-                    filtered_customers.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+                    filtered_customers.sort_by_key(|x| std::cmp::Reverse(x.created_at));
                 }
             }
             _ => {
@@ -969,16 +2072,17 @@ impl CustomerRepository for MockCustomerRepository {
         }
     }
 
-    async fn delete_customer(&self, id: Uuid) -> ErpResult<()> {
-        let mut customers = self.customers.lock().unwrap();
-        let mut addresses = self.addresses.lock().unwrap();
-
-        if customers.remove(&id).is_some() {
-            addresses.remove(&id);
-            Ok(())
-        } else {
-            Err(ErpError::not_found_simple("Customer not found"))
+    async fn delete_customer_in(&self, uow: &mut UnitOfWork, id: Uuid) -> ErpResult<()> {
+        if !self.customers.lock().unwrap().contains_key(&id) {
+            return Err(ErpError::not_found_simple("Customer not found"));
         }
+
+        let customers = self.customers.clone();
+        let addresses = self.addresses.clone();
+        uow.as_mock()?.stage(async move {
+            customers.lock().unwrap().remove(&id);
+            addresses.lock().unwrap().remove(&id);
+        })
     }
 
     async fn search_customers(&self, query: &str, limit: u32) -> ErpResult<Vec<Customer>> {
@@ -999,7 +2103,7 @@ impl CustomerRepository for MockCustomerRepository {
             .cloned()
             .collect();
 
-        matching_customers.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        matching_customers.sort_by_key(|x| std::cmp::Reverse(x.created_at));
         matching_customers.truncate(limit as usize);
 
         Ok(matching_customers)
@@ -1013,7 +2117,7 @@ impl CustomerRepository for MockCustomerRepository {
             .cloned()
             .collect();
 
-        outstanding_customers.sort_by(|a, b| b.current_balance.cmp(&a.current_balance));
+        outstanding_customers.sort_by_key(|x| std::cmp::Reverse(x.current_balance));
         Ok(outstanding_customers)
     }
 
@@ -1028,7 +2132,7 @@ impl CustomerRepository for MockCustomerRepository {
             .cloned()
             .collect();
 
-        type_customers.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        type_customers.sort_by_key(|x| std::cmp::Reverse(x.created_at));
         Ok(type_customers)
     }
 
@@ -1042,21 +2146,60 @@ impl CustomerRepository for MockCustomerRepository {
         Ok(customers.values().filter(|c| &c.status == status).count() as i64)
     }
 
-    async fn get_customer_orders(&self, _customer_id: Uuid) -> ErpResult<Vec<Uuid>> {
-        // Mock implementation - returns empty list
-        Ok(Vec::new())
+    async fn get_customer_orders(&self, customer_id: Uuid) -> ErpResult<Vec<Uuid>> {
+        let orders = self.orders.lock().unwrap();
+        Ok(orders
+            .iter()
+            .filter(|(_, owner)| **owner == customer_id)
+            .map(|(order_id, _)| *order_id)
+            .collect())
     }
 
-    async fn delete_customer_order(&self, _order_id: Uuid) -> ErpResult<()> {
-        // Mock implementation - does nothing
-        Ok(())
+    async fn delete_customer_order_in(
+        &self,
+        uow: &mut UnitOfWork,
+        order_id: Uuid,
+    ) -> ErpResult<()> {
+        let orders = self.orders.clone();
+        uow.as_mock()?.stage(async move {
+            orders.lock().unwrap().remove(&order_id);
+        })
     }
 
-    async fn delete_customer_address(&self, address_id: Uuid) -> ErpResult<()> {
-        let mut addresses = self.addresses.lock().unwrap();
-        for customer_addresses in addresses.values_mut() {
-            customer_addresses.retain(|addr| addr.id != address_id);
-        }
-        Ok(())
+    async fn delete_customer_address_in(
+        &self,
+        uow: &mut UnitOfWork,
+        address_id: Uuid,
+    ) -> ErpResult<()> {
+        let addresses = self.addresses.clone();
+        uow.as_mock()?.stage(async move {
+            let mut addresses = addresses.lock().unwrap();
+            for customer_addresses in addresses.values_mut() {
+                customer_addresses.retain(|addr| addr.id != address_id);
+            }
+        })
+    }
+
+    async fn get_customer_order_metrics(&self) -> ErpResult<Vec<CustomerOrderMetrics>> {
+        let customers = self.customers.lock().unwrap();
+        let order_metrics = self.order_metrics.lock().unwrap();
+
+        Ok(customers
+            .values()
+            .map(|c| {
+                order_metrics
+                    .get(&c.id)
+                    .cloned()
+                    .unwrap_or_else(|| CustomerOrderMetrics {
+                        customer_id: c.id,
+                        name: c.full_name(),
+                        email: Some(c.email.clone()),
+                        first_order_date: None,
+                        last_order_date: None,
+                        order_count: 0,
+                        total_spent: rust_decimal::Decimal::ZERO,
+                    })
+            })
+            .collect())
     }
 }
@@ -0,0 +1,375 @@
+//! RFM (recency, frequency, monetary) customer segmentation
+//!
+//! Customers are scored 1-5 on each RFM dimension by quintile rank against the
+//! rest of the customer base, mapped to a named segment, and flagged when they
+//! look likely to churn.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::utils::error::{ErpError, ErpResult};
+
+/// Raw per-customer order aggregates used as RFM input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomerOrderMetrics {
+    pub customer_id: Uuid,
+    pub name: String,
+    pub email: Option<String>,
+    pub first_order_date: Option<NaiveDate>,
+    pub last_order_date: Option<NaiveDate>,
+    pub order_count: u32,
+    pub total_spent: Decimal,
+}
+
+/// Named RFM segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RfmSegment {
+    Champions,
+    Loyal,
+    PotentialLoyalist,
+    New,
+    NeedAttention,
+    AtRisk,
+    CantLose,
+    Hibernating,
+    Lost,
+}
+
+/// RFM scores, segment and churn prediction for a single customer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomerRfmScore {
+    pub customer_id: Uuid,
+    pub name: String,
+    pub email: Option<String>,
+    pub recency_days: Option<i64>,
+    pub frequency: u32,
+    pub monetary: Decimal,
+    pub recency_score: u8,
+    pub frequency_score: u8,
+    pub monetary_score: u8,
+    pub segment: RfmSegment,
+    pub churn_predicted: bool,
+}
+
+impl RfmSegment {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Champions,
+            Self::Loyal,
+            Self::PotentialLoyalist,
+            Self::New,
+            Self::NeedAttention,
+            Self::AtRisk,
+            Self::CantLose,
+            Self::Hibernating,
+            Self::Lost,
+        ]
+    }
+
+    /// Map a set of 1-5 scores to a segment
+    pub fn from_scores(recency: u8, frequency: u8, monetary: u8) -> Self {
+        match (recency, frequency, monetary) {
+            (r, f, m) if r >= 4 && f >= 4 && m >= 4 => Self::Champions,
+            (r, f, _) if r >= 3 && f >= 4 => Self::Loyal,
+            (r, 1, _) if r >= 4 => Self::New,
+            (r, _, _) if r >= 4 => Self::PotentialLoyalist,
+            (3, _, _) => Self::NeedAttention,
+            (_, f, m) if f >= 4 && m >= 4 => Self::CantLose,
+            (_, f, _) if f >= 3 => Self::AtRisk,
+            (2, _, _) => Self::Hibernating,
+            _ => Self::Lost,
+        }
+    }
+
+    /// Whether customers in this segment are considered likely to churn
+    pub fn is_churn_risk(&self) -> bool {
+        matches!(
+            self,
+            Self::AtRisk | Self::CantLose | Self::Hibernating | Self::Lost
+        )
+    }
+}
+
+impl std::fmt::Display for RfmSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Champions => write!(f, "champions"),
+            Self::Loyal => write!(f, "loyal"),
+            Self::PotentialLoyalist => write!(f, "potential-loyalist"),
+            Self::New => write!(f, "new"),
+            Self::NeedAttention => write!(f, "need-attention"),
+            Self::AtRisk => write!(f, "at-risk"),
+            Self::CantLose => write!(f, "cant-lose"),
+            Self::Hibernating => write!(f, "hibernating"),
+            Self::Lost => write!(f, "lost"),
+        }
+    }
+}
+
+impl std::str::FromStr for RfmSegment {
+    type Err = ErpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_lowercase().replace('_', "-");
+        Self::all()
+            .into_iter()
+            .find(|segment| segment.to_string() == normalized)
+            .ok_or_else(|| {
+                ErpError::validation(
+                    "segment",
+                    format!(
+                        "Unknown segment '{}'. Available segments: {}",
+                        s,
+                        Self::all()
+                            .iter()
+                            .map(|segment| segment.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                )
+            })
+    }
+}
+
+/// Score every customer against the rest of the given population.
+///
+/// Customers without any orders receive the lowest score on every dimension.
+pub fn score_customers(
+    metrics: &[CustomerOrderMetrics],
+    as_of: NaiveDate,
+) -> Vec<CustomerRfmScore> {
+    let recency_days: Vec<Option<i64>> = metrics
+        .iter()
+        .map(|m| m.last_order_date.map(|d| (as_of - d).num_days().max(0)))
+        .collect();
+
+    // 최근 구매일수는 작을수록 좋으므로 부호를 반전해 순위를 매김
+    let recency_values: Vec<Option<Decimal>> = recency_days
+        .iter()
+        .map(|d| d.map(|days| Decimal::from(-days)))
+        .collect();
+    let frequency_values: Vec<Option<Decimal>> = metrics
+        .iter()
+        .map(|m| (m.order_count > 0).then(|| Decimal::from(m.order_count)))
+        .collect();
+    let monetary_values: Vec<Option<Decimal>> = metrics
+        .iter()
+        .map(|m| (m.order_count > 0).then_some(m.total_spent))
+        .collect();
+
+    let recency_scores = quintile_scores(&recency_values);
+    let frequency_scores = quintile_scores(&frequency_values);
+    let monetary_scores = quintile_scores(&monetary_values);
+
+    metrics
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let segment =
+                RfmSegment::from_scores(recency_scores[i], frequency_scores[i], monetary_scores[i]);
+            let churn_predicted = segment.is_churn_risk() || is_overdue(m, recency_days[i]);
+
+            CustomerRfmScore {
+                customer_id: m.customer_id,
+                name: m.name.clone(),
+                email: m.email.clone(),
+                recency_days: recency_days[i],
+                frequency: m.order_count,
+                monetary: m.total_spent,
+                recency_score: recency_scores[i],
+                frequency_score: frequency_scores[i],
+                monetary_score: monetary_scores[i],
+                segment,
+                churn_predicted,
+            }
+        })
+        .collect()
+}
+
+/// Assign 1-5 quintile scores; higher values score higher, `None` always scores 1.
+fn quintile_scores(values: &[Option<Decimal>]) -> Vec<u8> {
+    let mut ranked: Vec<Decimal> = values.iter().flatten().copied().collect();
+    ranked.sort();
+
+    let population = ranked.len();
+    values
+        .iter()
+        .map(|value| match value {
+            Some(v) if population > 0 => {
+                // 동점은 같은 점수를 받도록 첫 번째 위치 기준으로 순위 계산
+                let rank = ranked.partition_point(|x| x < v);
+                ((rank + 1) * 5).div_ceil(population) as u8
+            }
+            _ => 1,
+        })
+        .collect()
+}
+
+/// A repeat customer is overdue when the time since their last order exceeds
+/// twice their average interval between orders.
+fn is_overdue(metrics: &CustomerOrderMetrics, recency_days: Option<i64>) -> bool {
+    let (Some(first), Some(last), Some(recency)) = (
+        metrics.first_order_date,
+        metrics.last_order_date,
+        recency_days,
+    ) else {
+        return false;
+    };
+
+    if metrics.order_count < 2 {
+        return false;
+    }
+
+    let average_interval = (last - first).num_days() / (metrics.order_count as i64 - 1);
+    average_interval > 0 && recency > average_interval * 2
+}
+
+/// Aggregate scores into one summary row per segment
+pub fn summarize_segments(scores: &[CustomerRfmScore]) -> Vec<RfmSegmentSummary> {
+    RfmSegment::all()
+        .into_iter()
+        .filter_map(|segment| {
+            let members: Vec<&CustomerRfmScore> =
+                scores.iter().filter(|s| s.segment == segment).collect();
+            if members.is_empty() {
+                return None;
+            }
+
+            let customer_count = members.len() as u32;
+            let total_revenue: Decimal = members.iter().map(|s| s.monetary).sum();
+            let total_orders: u32 = members.iter().map(|s| s.frequency).sum();
+
+            Some(RfmSegmentSummary {
+                segment,
+                customer_count,
+                total_revenue,
+                average_order_frequency: Decimal::from(total_orders)
+                    / Decimal::from(customer_count),
+                churn_predicted_count: members.iter().filter(|s| s.churn_predicted).count() as u32,
+            })
+        })
+        .collect()
+}
+
+/// Per-segment aggregate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RfmSegmentSummary {
+    pub segment: RfmSegment,
+    pub customer_count: u32,
+    pub total_revenue: Decimal,
+    pub average_order_frequency: Decimal,
+    pub churn_predicted_count: u32,
+}
+
+/// Parse an optional CLI segment argument
+pub fn parse_segment(segment: &Option<String>) -> ErpResult<Option<RfmSegment>> {
+    segment.as_deref().map(str::parse).transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(days_ago: Option<i64>, orders: u32, spent: i64) -> CustomerOrderMetrics {
+        let as_of = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        CustomerOrderMetrics {
+            customer_id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            email: None,
+            first_order_date: days_ago
+                .map(|d| as_of - chrono::Duration::days(d + 30 * orders as i64)),
+            last_order_date: days_ago.map(|d| as_of - chrono::Duration::days(d)),
+            order_count: orders,
+            total_spent: Decimal::from(spent),
+        }
+    }
+
+    #[test]
+    fn test_segment_from_scores() {
+        assert_eq!(RfmSegment::from_scores(5, 5, 5), RfmSegment::Champions);
+        assert_eq!(RfmSegment::from_scores(3, 4, 2), RfmSegment::Loyal);
+        assert_eq!(RfmSegment::from_scores(5, 1, 1), RfmSegment::New);
+        assert_eq!(RfmSegment::from_scores(1, 5, 5), RfmSegment::CantLose);
+        assert_eq!(RfmSegment::from_scores(2, 3, 2), RfmSegment::AtRisk);
+        assert_eq!(RfmSegment::from_scores(2, 1, 1), RfmSegment::Hibernating);
+        assert_eq!(RfmSegment::from_scores(1, 1, 1), RfmSegment::Lost);
+    }
+
+    #[test]
+    fn test_segment_parsing() {
+        assert_eq!("at-risk".parse::<RfmSegment>().unwrap(), RfmSegment::AtRisk);
+        assert_eq!(
+            "CANT_LOSE".parse::<RfmSegment>().unwrap(),
+            RfmSegment::CantLose
+        );
+        assert!("unknown".parse::<RfmSegment>().is_err());
+        for segment in RfmSegment::all() {
+            assert_eq!(segment.to_string().parse::<RfmSegment>().unwrap(), segment);
+        }
+    }
+
+    #[test]
+    fn test_score_customers_ranks_population() {
+        let as_of = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let population = vec![
+            metrics(Some(2), 20, 10_000),
+            metrics(Some(200), 15, 8_000),
+            metrics(Some(10), 1, 50),
+            metrics(Some(400), 1, 20),
+            metrics(None, 0, 0),
+        ];
+
+        let scores = score_customers(&population, as_of);
+
+        assert_eq!(scores[0].segment, RfmSegment::Champions);
+        assert!(!scores[0].churn_predicted);
+        assert_eq!(scores[0].recency_days, Some(2));
+
+        // 충성 고객이라도 평소 주문 간격의 두 배 이상 주문이 없으면 이탈 예측
+        assert_eq!(scores[1].segment, RfmSegment::Loyal);
+        assert!(scores[1].churn_predicted);
+
+        assert_eq!(scores[2].segment, RfmSegment::PotentialLoyalist);
+        assert!(!scores[2].churn_predicted);
+
+        assert_eq!(scores[3].segment, RfmSegment::Hibernating);
+        assert!(scores[3].churn_predicted);
+        assert_eq!(scores[4].recency_score, 1);
+        assert_eq!(scores[4].segment, RfmSegment::Lost);
+        assert!(scores[4].churn_predicted);
+    }
+
+    #[test]
+    fn test_overdue_repeat_customer_predicted_to_churn() {
+        let as_of = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let mut overdue = metrics(Some(90), 4, 1_000);
+        overdue.first_order_date = Some(as_of - chrono::Duration::days(180));
+
+        assert!(is_overdue(&overdue, Some(90)));
+        assert!(!is_overdue(&metrics(Some(90), 1, 100), Some(90)));
+    }
+
+    #[test]
+    fn test_summarize_segments() {
+        let as_of = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let scores = score_customers(
+            &[
+                metrics(None, 0, 0),
+                metrics(None, 0, 0),
+                metrics(Some(1), 3, 300),
+            ],
+            as_of,
+        );
+
+        let summary = summarize_segments(&scores);
+        let lost = summary
+            .iter()
+            .find(|s| s.segment == RfmSegment::Lost)
+            .unwrap();
+        assert_eq!(lost.customer_count, 2);
+        assert_eq!(lost.churn_predicted_count, 2);
+    }
+}
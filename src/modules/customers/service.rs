@@ -3,18 +3,34 @@ use std::sync::Arc;
 use tracing::{info, warn};
 use uuid::Uuid;
 
+use crate::core::cache::{CacheScope, QueryCache};
 use crate::modules::customers::models::*;
 use crate::modules::customers::repository::CustomerRepository;
+use crate::modules::customers::segmentation::{self, CustomerRfmScore, RfmSegment};
 use crate::utils::error::{ErpError, ErpResult};
 use crate::utils::validation::{validate_email, validate_phone};
 
 pub struct CustomerService {
     repository: Arc<dyn CustomerRepository>,
+    cache: QueryCache,
 }
 
 impl CustomerService {
     pub fn new(repository: Arc<dyn CustomerRepository>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            cache: QueryCache::disabled(),
+        }
+    }
+
+    /// Cache whose report summaries are invalidated by customer changes
+    pub fn with_cache(mut self, cache: QueryCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    async fn invalidate_reports(&self) {
+        self.cache.invalidate(CacheScope::Reports).await;
     }
 
     pub async fn create_customer(
@@ -49,6 +65,7 @@ impl CustomerService {
             self.repository.create_customer_address(&address).await?;
             customer_addresses.push(address);
         }
+        self.invalidate_reports().await;
 
         info!(
             "Customer created successfully: {} ({})",
@@ -148,6 +165,7 @@ impl CustomerService {
 
         // Save updated customer
         self.repository.update_customer(id, &customer).await?;
+        self.invalidate_reports().await;
 
         let addresses = self.repository.get_customer_addresses(customer.id).await?;
 
@@ -185,6 +203,7 @@ impl CustomerService {
         // For now, we'll allow deletion
 
         self.repository.delete_customer(id).await?;
+        self.invalidate_reports().await;
 
         info!(
             "Customer deleted successfully: {} ({})",
@@ -207,20 +226,31 @@ impl CustomerService {
         let orders = self.repository.get_customer_orders(id).await?;
         let order_count = orders.len();
 
-        // Delete all orders and their items
-        for order_id in orders {
-            self.repository.delete_customer_order(order_id).await?;
-            info!("Deleted order {} for customer {}", order_id, id);
-        }
-
-        // Delete customer addresses (if any)
         let addresses = self.repository.get_customer_addresses(id).await?;
-        for address in &addresses {
-            self.repository.delete_customer_address(address.id).await?;
+
+        // Orders, addresses and the customer go in one unit of work so a
+        // failure part way leaves nothing half deleted
+        let mut uow = self.repository.begin().await?;
+        let result = async {
+            for order_id in &orders {
+                self.repository
+                    .delete_customer_order_in(&mut uow, *order_id)
+                    .await?;
+            }
+            for address in &addresses {
+                self.repository
+                    .delete_customer_address_in(&mut uow, address.id)
+                    .await?;
+            }
+            self.repository.delete_customer_in(&mut uow, id).await
         }
+        .await;
+        uow.finish(result).await?;
+        self.invalidate_reports().await;
 
-        // Now delete the customer
-        self.repository.delete_customer(id).await?;
+        for order_id in &orders {
+            info!("Deleted order {} for customer {}", order_id, id);
+        }
 
         info!(
             "Customer deleted successfully with cascade: {} ({}), {} orders deleted",
@@ -428,6 +458,43 @@ impl CustomerService {
         })
     }
 
+    /// Score every customer by recency, frequency and monetary value of their orders
+    pub async fn calculate_rfm_scores(&self) -> ErpResult<Vec<CustomerRfmScore>> {
+        let metrics = self.repository.get_customer_order_metrics().await?;
+        let as_of = chrono::Utc::now().date_naive();
+
+        Ok(segmentation::score_customers(&metrics, as_of))
+    }
+
+    pub async fn list_customers_in_segment(
+        &self,
+        segment: RfmSegment,
+        mut filter: CustomerFilter,
+        page: u32,
+        per_page: u32,
+        sort_by: &str,
+        sort_order: &str,
+    ) -> ErpResult<CustomerListResponse> {
+        let segment_ids: Vec<Uuid> = self
+            .calculate_rfm_scores()
+            .await?
+            .into_iter()
+            .filter(|score| score.segment == segment)
+            .map(|score| score.customer_id)
+            .collect();
+
+        filter.customer_ids = Some(match filter.customer_ids.take() {
+            Some(ids) => ids
+                .into_iter()
+                .filter(|id| segment_ids.contains(id))
+                .collect(),
+            None => segment_ids,
+        });
+
+        self.list_customers(filter, page, per_page, sort_by, sort_order)
+            .await
+    }
+
     pub async fn activate_customer(&self, id: Uuid) -> ErpResult<CustomerResponse> {
         let mut customer = self
             .repository
@@ -441,6 +508,7 @@ impl CustomerService {
 
         customer.status = CustomerStatus::Active;
         self.repository.update_customer(id, &customer).await?;
+        self.invalidate_reports().await;
 
         let addresses = self.repository.get_customer_addresses(customer.id).await?;
 
@@ -473,6 +541,7 @@ impl CustomerService {
         }
 
         self.repository.update_customer(id, &customer).await?;
+        self.invalidate_reports().await;
 
         let addresses = self.repository.get_customer_addresses(customer.id).await?;
 
@@ -927,4 +996,104 @@ mod tests {
             .unwrap();
         assert_eq!(result.current_balance, Decimal::from(2000));
     }
+
+    #[tokio::test]
+    async fn test_list_customers_in_segment() {
+        let repository = Arc::new(MockCustomerRepository::new());
+        let service = CustomerService::new(repository.clone());
+
+        let loyal = service
+            .create_customer(create_test_customer_request())
+            .await
+            .unwrap();
+        let mut request = create_test_customer_request();
+        request.first_name = "Jane".to_string();
+        request.last_name = "Smith".to_string();
+        request.email = "jane.smith@example.com".to_string();
+        let dormant = service.create_customer(request).await.unwrap();
+
+        let today = chrono::Utc::now().date_naive();
+        repository.set_order_metrics(crate::modules::customers::CustomerOrderMetrics {
+            customer_id: loyal.id,
+            name: loyal.full_name(),
+            email: Some(loyal.email.clone()),
+            first_order_date: Some(today - chrono::Duration::days(60)),
+            last_order_date: Some(today),
+            order_count: 6,
+            total_spent: Decimal::from(1200),
+        });
+
+        let scores = service.calculate_rfm_scores().await.unwrap();
+        assert_eq!(scores.len(), 2);
+
+        let filter = CustomerFilter {
+            status: None,
+            customer_type: None,
+            search: None,
+            city: None,
+            state_province: None,
+            country: None,
+            has_outstanding_balance: None,
+            credit_limit_min: None,
+            credit_limit_max: None,
+            customer_ids: None,
+        };
+
+        let lost = service
+            .list_customers_in_segment(RfmSegment::Lost, filter.clone(), 1, 20, "name", "asc")
+            .await
+            .unwrap();
+        assert_eq!(lost.total, 1);
+        assert_eq!(lost.customers[0].id, dormant.id);
+
+        let champions = service
+            .list_customers_in_segment(RfmSegment::Champions, filter, 1, 20, "name", "asc")
+            .await
+            .unwrap();
+        assert_eq!(champions.total, 1);
+        assert_eq!(champions.customers[0].id, loyal.id);
+    }
+
+    #[tokio::test]
+    async fn test_delete_customer_cascade_is_all_or_nothing() {
+        let repository = Arc::new(MockCustomerRepository::new());
+        let service = CustomerService::new(repository.clone());
+        let customer = service
+            .create_customer(create_test_customer_request())
+            .await
+            .unwrap();
+        repository.add_order(customer.id, Uuid::new_v4());
+        repository.add_order(customer.id, Uuid::new_v4());
+
+        let result = service.delete_customer_cascade(customer.id).await.unwrap();
+        assert_eq!(result.orders_deleted, 2);
+        assert!(repository
+            .get_customer_orders(customer.id)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(service.get_customer_by_id(customer.id).await.is_err());
+
+        // Fail on the address delete, after both orders were staged
+        let repository = Arc::new(MockCustomerRepository::new().failing_unit_of_work_after(2));
+        let service = CustomerService::new(repository.clone());
+        let customer = service
+            .create_customer(create_test_customer_request())
+            .await
+            .unwrap();
+        repository.add_order(customer.id, Uuid::new_v4());
+        repository.add_order(customer.id, Uuid::new_v4());
+
+        assert!(service.delete_customer_cascade(customer.id).await.is_err());
+        assert_eq!(
+            repository
+                .get_customer_orders(customer.id)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        let kept = service.get_customer_by_id(customer.id).await.unwrap();
+        assert_eq!(kept.addresses.len(), 1);
+    }
 }
@@ -18,7 +18,7 @@
 //!
 //! ## 사용 예시
 //!
-//! ```rust
+//! ```rust,ignore
 //! use crate::modules::inventory::{
 //!     service::{InventoryService, InventoryServiceImpl},
 //!     repository::PostgresInventoryRepository,
@@ -53,11 +53,14 @@ pub use models::{
     StockAdjustmentRequest, StockMovementResponse, UpdateInventoryItemRequest,
 };
 
-pub use repository::{InventoryRepository, MockInventoryRepository, PostgresInventoryRepository};
+pub use repository::{
+    InventoryRepository, MockInventoryRepository, PostgresInventoryRepository,
+    SqliteInventoryRepository,
+};
 
 pub use service::{InventoryService, InventoryServiceImpl};
 
-use crate::core::database::connection::DatabasePool;
+use crate::core::database::connection::{DatabaseBackend, DatabaseConnection, DatabasePool};
 use crate::utils::error::ErpResult;
 use std::sync::Arc;
 
@@ -77,6 +80,25 @@ impl InventoryModule {
         Self { service }
     }
 
+    /// SQLite 파일 데이터베이스를 사용하여 새로운 재고 관리 모듈을 생성합니다.
+    pub fn new_with_sqlite(pool: sqlx::SqlitePool) -> Self {
+        let repository = Arc::new(SqliteInventoryRepository::new(pool));
+        let service = Arc::new(InventoryServiceImpl::new(repository));
+
+        Self { service }
+    }
+
+    /// 연결된 데이터베이스 종류에 맞는 재고 관리 모듈을 생성합니다.
+    pub fn for_connection(connection: &DatabaseConnection) -> ErpResult<Self> {
+        let service = Arc::new(InventoryServiceImpl::new(repository_for(connection)?));
+        Ok(Self { service })
+    }
+
+    /// DI 컨테이너에서 해석한 재고 서비스로 모듈을 생성합니다.
+    pub fn from_service(service: Arc<dyn InventoryService>) -> Self {
+        Self { service }
+    }
+
     /// 테스트용 모의 구현체를 사용하여 새로운 재고 관리 모듈을 생성합니다.
     pub fn new_with_mock() -> Self {
         let repository = Arc::new(MockInventoryRepository::new());
@@ -129,6 +151,18 @@ impl InventoryModule {
     }
 }
 
+/// 연결된 데이터베이스 종류에 맞는 재고 저장소를 생성합니다.
+pub fn repository_for(connection: &DatabaseConnection) -> ErpResult<Arc<dyn InventoryRepository>> {
+    Ok(match connection.backend() {
+        DatabaseBackend::Postgres => {
+            Arc::new(PostgresInventoryRepository::new(connection.pool()?.clone()))
+        }
+        DatabaseBackend::Sqlite => Arc::new(SqliteInventoryRepository::new(
+            connection.sqlite_pool()?.clone(),
+        )),
+    })
+}
+
 /// 재고 관리 모듈 통계 정보
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct InventoryModuleStats {
@@ -2,6 +2,9 @@ use crate::core::database::connection::DatabasePool;
 use crate::core::database::models::product::{
     CreateProductRequest, Product, ProductStatus, StockMovement, UpdateProductRequest,
 };
+use crate::core::database::replica::ReadRouter;
+use crate::core::database::sqlite;
+use crate::core::database::{MockUnitOfWork, UnitOfWork};
 use crate::modules::inventory::models::{
     CategoryValuation, InventoryFilter, InventoryItem, InventoryValuation, LowStockAlert,
     StockAdjustmentRequest, StockMovementResponse,
@@ -10,7 +13,9 @@ use crate::utils::error::{ErpError, ErpResult};
 use async_trait::async_trait;
 use chrono::Utc;
 use rust_decimal::Decimal;
-use sqlx::Row;
+use shaku::Interface;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Postgres, Row, Sqlite, SqlitePool};
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -41,8 +46,29 @@ fn build_sort_clause(sort_by: &Option<String>, sort_order: &Option<String>) -> (
     (sort_field.to_string(), sort_order)
 }
 
+/// Rejects adjustments that would take stock below zero
+fn check_stock_available(product: &Product, quantity_change: i32) -> ErpResult<()> {
+    if product.quantity + quantity_change < 0 {
+        return Err(ErpError::validation_simple(format!(
+            "Insufficient stock. Current: {}, Requested: {}",
+            product.quantity,
+            quantity_change.abs()
+        )));
+    }
+    Ok(())
+}
+
 #[async_trait]
-pub trait InventoryRepository: Send + Sync {
+pub trait InventoryRepository: Interface {
+    /// Starts a unit of work other repositories on the same backend can join
+    async fn begin(&self) -> ErpResult<UnitOfWork>;
+    async fn adjust_stock_in(
+        &self,
+        uow: &mut UnitOfWork,
+        request: StockAdjustmentRequest,
+        user_id: Uuid,
+    ) -> ErpResult<StockMovement>;
+
     async fn create_product(&self, request: CreateProductRequest) -> ErpResult<Product>;
     async fn get_product_by_id(&self, id: Uuid) -> ErpResult<Option<Product>>;
     async fn get_product_by_sku(&self, sku: &str) -> ErpResult<Option<Product>>;
@@ -54,7 +80,11 @@ pub trait InventoryRepository: Send + Sync {
         &self,
         request: StockAdjustmentRequest,
         user_id: Uuid,
-    ) -> ErpResult<StockMovement>;
+    ) -> ErpResult<StockMovement> {
+        let mut uow = self.begin().await?;
+        let result = self.adjust_stock_in(&mut uow, request, user_id).await;
+        uow.finish(result).await
+    }
     async fn get_stock_movements(
         &self,
         product_id: Option<Uuid>,
@@ -69,16 +99,30 @@ pub trait InventoryRepository: Send + Sync {
 
 pub struct PostgresInventoryRepository {
     pool: DatabasePool,
+    reads: ReadRouter<Postgres>,
 }
 
 impl PostgresInventoryRepository {
     pub fn new(pool: DatabasePool) -> Self {
-        Self { pool }
+        Self {
+            reads: ReadRouter::primary_only(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Sends product listings to read replicas
+    pub fn with_read_router(mut self, reads: ReadRouter<Postgres>) -> Self {
+        self.reads = reads;
+        self
     }
 }
 
 #[async_trait]
 impl InventoryRepository for PostgresInventoryRepository {
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        UnitOfWork::begin_postgres(&self.pool).await
+    }
+
     async fn create_product(&self, request: CreateProductRequest) -> ErpResult<Product> {
         // Check if SKU already exists
         if self.sku_exists(&request.sku, None).await? {
@@ -162,6 +206,8 @@ impl InventoryRepository for PostgresInventoryRepository {
         &self,
         filter: &InventoryFilter,
     ) -> ErpResult<(Vec<InventoryItem>, i64)> {
+        let pool = self.reads.pool().await;
+
         // Build COUNT query using QueryBuilder
         let mut count_builder = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM products");
 
@@ -270,7 +316,7 @@ impl InventoryRepository for PostgresInventoryRepository {
         // Execute count query
         let count_result = count_builder
             .build()
-            .fetch_one(&self.pool)
+            .fetch_one(&pool)
             .await
             .map_err(|e| ErpError::internal(format!("Failed to count products: {}", e)))?;
 
@@ -400,7 +446,7 @@ impl InventoryRepository for PostgresInventoryRepository {
         // Execute main query
         let products = query_builder
             .build_query_as::<Product>()
-            .fetch_all(&self.pool)
+            .fetch_all(&pool)
             .await
             .map_err(|e| ErpError::internal(format!("Failed to fetch products: {}", e)))?;
 
@@ -489,29 +535,26 @@ impl InventoryRepository for PostgresInventoryRepository {
         Ok(())
     }
 
-    async fn adjust_stock(
+    async fn adjust_stock_in(
         &self,
+        uow: &mut UnitOfWork,
         request: StockAdjustmentRequest,
         user_id: Uuid,
     ) -> ErpResult<StockMovement> {
-        let mut tx = self
-            .pool
-            .begin()
-            .await
-            .map_err(|e| ErpError::internal(format!("Failed to start transaction: {}", e)))?;
-
-        // Get current product
-        let mut product = self
-            .get_product_by_id(request.product_id)
-            .await?
-            .ok_or_else(|| {
-                ErpError::not_found_simple(format!(
-                    "Product with ID {} not found",
-                    request.product_id
-                ))
-            })?;
-
-        let _previous_quantity = product.quantity;
+        // Lock the product row so concurrent adjustments see each other
+        let mut product =
+            sqlx::query_as::<_, Product>("SELECT * FROM products WHERE id = $1 FOR UPDATE")
+                .bind(request.product_id)
+                .fetch_optional(uow.postgres()?)
+                .await
+                .map_err(|e| ErpError::internal(format!("Failed to get product by ID: {}", e)))?
+                .ok_or_else(|| {
+                    ErpError::not_found_simple(format!(
+                        "Product with ID {} not found",
+                        request.product_id
+                    ))
+                })?;
+        check_stock_available(&product, request.quantity_change)?;
 
         // Create stock movement with user_id
         let mut movement = product.adjust_quantity(request.quantity_change, request.reason.clone());
@@ -527,7 +570,7 @@ impl InventoryRepository for PostgresInventoryRepository {
             .bind(&product.status)
             .bind(product.updated_at)
             .bind(product.id)
-            .execute(&mut *tx)
+            .execute(uow.postgres()?)
             .await
             .map_err(|e| ErpError::internal(format!("Failed to update product quantity: {}", e)))?;
 
@@ -547,14 +590,10 @@ impl InventoryRepository for PostgresInventoryRepository {
             .bind(movement.reference_id)
             .bind(movement.user_id)
             .bind(movement.created_at)
-            .execute(&mut *tx)
+            .execute(uow.postgres()?)
             .await
             .map_err(|e| ErpError::internal(format!("Failed to create stock movement: {}", e)))?;
 
-        tx.commit()
-            .await
-            .map_err(|e| ErpError::internal(format!("Failed to commit transaction: {}", e)))?;
-
         Ok(movement)
     }
 
@@ -688,12 +727,12 @@ impl InventoryRepository for PostgresInventoryRepository {
         let summary_query = r#"
             SELECT
                 COUNT(*) as total_items,
-                SUM(quantity) as total_quantity,
-                SUM(cost * quantity) as total_cost_value,
-                SUM(price * quantity) as total_sell_value,
-                SUM(CASE WHEN quantity <= min_stock_level THEN 1 ELSE 0 END) as low_stock_items,
-                SUM(CASE WHEN quantity <= 0 THEN 1 ELSE 0 END) as out_of_stock_items,
-                SUM(CASE WHEN max_stock_level IS NOT NULL AND quantity >= max_stock_level THEN 1 ELSE 0 END) as overstocked_items
+                COALESCE(SUM(quantity), 0) as total_quantity,
+                COALESCE(SUM(cost * quantity), 0) as total_cost_value,
+                COALESCE(SUM(price * quantity), 0) as total_sell_value,
+                COALESCE(SUM(CASE WHEN quantity <= min_stock_level THEN 1 ELSE 0 END), 0) as low_stock_items,
+                COALESCE(SUM(CASE WHEN quantity <= 0 THEN 1 ELSE 0 END), 0) as out_of_stock_items,
+                COALESCE(SUM(CASE WHEN max_stock_level IS NOT NULL AND quantity >= max_stock_level THEN 1 ELSE 0 END), 0) as overstocked_items
             FROM products
             WHERE status != 'discontinued'
         "#;
@@ -839,6 +878,557 @@ impl InventoryRepository for PostgresInventoryRepository {
     }
 }
 
+pub struct SqliteInventoryRepository {
+    pool: SqlitePool,
+    reads: ReadRouter<Sqlite>,
+}
+
+impl SqliteInventoryRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            reads: ReadRouter::primary_only(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Sends product listings to read replicas
+    pub fn with_read_router(mut self, reads: ReadRouter<Sqlite>) -> Self {
+        self.reads = reads;
+        self
+    }
+
+    fn product_from_row(row: &SqliteRow) -> Result<Product, sqlx::Error> {
+        Ok(Product {
+            id: row.try_get("id")?,
+            sku: row.try_get("sku")?,
+            name: row.try_get("name")?,
+            description: row.try_get("description")?,
+            category: row.try_get("category")?,
+            price: sqlite::decimal(row, "price")?,
+            cost: sqlite::decimal(row, "cost")?,
+            quantity: row.try_get("quantity")?,
+            min_stock_level: row.try_get("min_stock_level")?,
+            max_stock_level: row.try_get("max_stock_level")?,
+            status: row.try_get("status")?,
+            is_taxable: row.try_get("is_taxable")?,
+            weight: sqlite::opt_decimal(row, "weight")?,
+            dimensions: row.try_get("dimensions")?,
+            barcode: row.try_get("barcode")?,
+            supplier_id: row.try_get("supplier_id")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    async fn fetch_products<'q>(
+        pool: &SqlitePool,
+        query: sqlx::query::Query<'q, Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    ) -> ErpResult<Vec<Product>> {
+        let rows = query
+            .fetch_all(pool)
+            .await
+            .map_err(|e| ErpError::internal(format!("Failed to fetch products: {}", e)))?;
+
+        rows.iter()
+            .map(Self::product_from_row)
+            .collect::<Result<_, _>>()
+            .map_err(|e| ErpError::internal(format!("Failed to parse product: {}", e)))
+    }
+
+    fn push_filters(builder: &mut sqlx::QueryBuilder<'_, Sqlite>, filter: &InventoryFilter) {
+        builder.push(" WHERE 1 = 1");
+
+        // Always exclude discontinued products unless explicitly requested
+        match &filter.status {
+            Some(status) => {
+                builder.push(" AND status = ");
+                builder.push_bind(status.clone());
+            }
+            None => {
+                builder.push(" AND status != ");
+                builder.push_bind(ProductStatus::Discontinued);
+            }
+        }
+
+        if let Some(category) = &filter.category {
+            builder.push(" AND category = ");
+            builder.push_bind(category.clone());
+        }
+
+        // LIKE is case-insensitive for ASCII in SQLite
+        if let Some(sku) = &filter.sku {
+            builder.push(" AND sku LIKE ");
+            builder.push_bind(format!("%{}%", sku));
+        }
+
+        if let Some(name) = &filter.name {
+            builder.push(" AND name LIKE ");
+            builder.push_bind(format!("%{}%", name));
+        }
+
+        if let Some(search_query) = &filter.search_query {
+            let pattern = format!("%{}%", search_query);
+            builder.push(" AND (name LIKE ");
+            builder.push_bind(pattern.clone());
+            builder.push(" OR sku LIKE ");
+            builder.push_bind(pattern.clone());
+            builder.push(" OR category LIKE ");
+            builder.push_bind(pattern);
+            builder.push(")");
+        }
+
+        if let Some(true) = filter.low_stock_only {
+            builder.push(" AND quantity <= min_stock_level");
+        }
+
+        if let Some(min_quantity) = filter.min_quantity {
+            builder.push(" AND quantity >= ");
+            builder.push_bind(min_quantity);
+        }
+
+        if let Some(max_quantity) = filter.max_quantity {
+            builder.push(" AND quantity <= ");
+            builder.push_bind(max_quantity);
+        }
+    }
+}
+
+#[async_trait]
+impl InventoryRepository for SqliteInventoryRepository {
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        UnitOfWork::begin_sqlite(&self.pool).await
+    }
+
+    async fn create_product(&self, request: CreateProductRequest) -> ErpResult<Product> {
+        if self.sku_exists(&request.sku, None).await? {
+            return Err(ErpError::conflict(format!(
+                "SKU '{}' already exists",
+                request.sku
+            )));
+        }
+
+        let product = Product::new(request);
+
+        let query = r#"
+            INSERT INTO products (
+                id, sku, name, description, category, price, cost, quantity,
+                min_stock_level, max_stock_level, status, is_taxable, weight,
+                dimensions, barcode, supplier_id, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(query)
+            .bind(product.id)
+            .bind(&product.sku)
+            .bind(&product.name)
+            .bind(&product.description)
+            .bind(&product.category)
+            .bind(sqlite::decimal_text(product.price))
+            .bind(sqlite::decimal_text(product.cost))
+            .bind(product.quantity)
+            .bind(product.min_stock_level)
+            .bind(product.max_stock_level)
+            .bind(&product.status)
+            .bind(product.is_taxable)
+            .bind(sqlite::opt_decimal_text(product.weight))
+            .bind(&product.dimensions)
+            .bind(&product.barcode)
+            .bind(product.supplier_id)
+            .bind(product.created_at)
+            .bind(product.updated_at)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ErpError::internal(format!("Failed to create product: {}", e)))?;
+
+        Ok(product)
+    }
+
+    async fn get_product_by_id(&self, id: Uuid) -> ErpResult<Option<Product>> {
+        let products = Self::fetch_products(
+            &self.pool,
+            sqlx::query("SELECT * FROM products WHERE id = ?").bind(id),
+        )
+        .await?;
+        Ok(products.into_iter().next())
+    }
+
+    async fn get_product_by_sku(&self, sku: &str) -> ErpResult<Option<Product>> {
+        let products = Self::fetch_products(
+            &self.pool,
+            sqlx::query("SELECT * FROM products WHERE sku = ? COLLATE NOCASE").bind(sku),
+        )
+        .await?;
+        Ok(products.into_iter().next())
+    }
+
+    async fn list_products(
+        &self,
+        filter: &InventoryFilter,
+    ) -> ErpResult<(Vec<InventoryItem>, i64)> {
+        let pool = self.reads.pool().await;
+
+        let mut count_builder = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM products");
+        Self::push_filters(&mut count_builder, filter);
+
+        let total: i64 = count_builder
+            .build_query_scalar()
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| ErpError::internal(format!("Failed to count products: {}", e)))?;
+
+        let mut query_builder = sqlx::QueryBuilder::new("SELECT * FROM products");
+        Self::push_filters(&mut query_builder, filter);
+
+        // Money is stored as text, so it has to be compared numerically
+        let (sort_field, sort_order) = build_sort_clause(&filter.sort_by, &filter.sort_order);
+        let sort_expr = match sort_field.as_str() {
+            "price" | "cost" => format!("CAST({} AS REAL)", sort_field),
+            _ => sort_field,
+        };
+        query_builder.push(format!(" ORDER BY {} {}", sort_expr, sort_order));
+
+        let page = filter.page.unwrap_or(1);
+        let limit = filter.limit.unwrap_or(20).min(100);
+        let offset = (page - 1) * limit;
+
+        query_builder.push(" LIMIT ");
+        query_builder.push_bind(limit as i64);
+        query_builder.push(" OFFSET ");
+        query_builder.push_bind(offset as i64);
+
+        let products = Self::fetch_products(&pool, query_builder.build()).await?;
+
+        let inventory_items = products
+            .into_iter()
+            .map(InventoryItem::from_product)
+            .collect();
+
+        Ok((inventory_items, total))
+    }
+
+    async fn update_product(&self, id: Uuid, request: UpdateProductRequest) -> ErpResult<Product> {
+        let mut product = self.get_product_by_id(id).await?.ok_or_else(|| {
+            ErpError::not_found_simple(format!("Product with ID {} not found", id))
+        })?;
+
+        product.update(request);
+
+        let query = r#"
+            UPDATE products SET
+                name = ?, description = ?, category = ?, price = ?, cost = ?,
+                quantity = ?, min_stock_level = ?, max_stock_level = ?, status = ?, is_taxable = ?,
+                weight = ?, dimensions = ?, barcode = ?, supplier_id = ?,
+                updated_at = ?
+            WHERE id = ?
+        "#;
+
+        sqlx::query(query)
+            .bind(&product.name)
+            .bind(&product.description)
+            .bind(&product.category)
+            .bind(sqlite::decimal_text(product.price))
+            .bind(sqlite::decimal_text(product.cost))
+            .bind(product.quantity)
+            .bind(product.min_stock_level)
+            .bind(product.max_stock_level)
+            .bind(&product.status)
+            .bind(product.is_taxable)
+            .bind(sqlite::opt_decimal_text(product.weight))
+            .bind(&product.dimensions)
+            .bind(&product.barcode)
+            .bind(product.supplier_id)
+            .bind(product.updated_at)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ErpError::internal(format!("Failed to update product: {}", e)))?;
+
+        Ok(product)
+    }
+
+    async fn delete_product(&self, id: Uuid, force: bool) -> ErpResult<()> {
+        if self.get_product_by_id(id).await?.is_none() {
+            return Err(ErpError::not_found_simple(format!(
+                "Product with ID {} not found",
+                id
+            )));
+        }
+
+        let query = if force {
+            sqlx::query("DELETE FROM products WHERE id = ?").bind(id)
+        } else {
+            sqlx::query("UPDATE products SET status = ?, updated_at = ? WHERE id = ?")
+                .bind(ProductStatus::Discontinued)
+                .bind(Utc::now())
+                .bind(id)
+        };
+
+        query
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ErpError::internal(format!("Failed to delete product: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn adjust_stock_in(
+        &self,
+        uow: &mut UnitOfWork,
+        request: StockAdjustmentRequest,
+        user_id: Uuid,
+    ) -> ErpResult<StockMovement> {
+        let row = sqlx::query("SELECT * FROM products WHERE id = ?")
+            .bind(request.product_id)
+            .fetch_optional(uow.sqlite()?)
+            .await
+            .map_err(|e| ErpError::internal(format!("Failed to get product by ID: {}", e)))?
+            .ok_or_else(|| {
+                ErpError::not_found_simple(format!(
+                    "Product with ID {} not found",
+                    request.product_id
+                ))
+            })?;
+        let mut product = Self::product_from_row(&row)
+            .map_err(|e| ErpError::internal(format!("Failed to parse product: {}", e)))?;
+        check_stock_available(&product, request.quantity_change)?;
+
+        let mut movement = product.adjust_quantity(request.quantity_change, request.reason.clone());
+        movement.user_id = user_id;
+        movement.reference_id = request.reference_id;
+
+        sqlx::query("UPDATE products SET quantity = ?, status = ?, updated_at = ? WHERE id = ?")
+            .bind(product.quantity)
+            .bind(&product.status)
+            .bind(product.updated_at)
+            .bind(product.id)
+            .execute(uow.sqlite()?)
+            .await
+            .map_err(|e| ErpError::internal(format!("Failed to update product quantity: {}", e)))?;
+
+        let movement_query = r#"
+            INSERT INTO stock_movements (
+                id, product_id, movement_type, quantity, reason, reference_id, user_id, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        sqlx::query(movement_query)
+            .bind(movement.id)
+            .bind(movement.product_id)
+            .bind(&movement.movement_type)
+            .bind(movement.quantity)
+            .bind(&movement.reason)
+            .bind(movement.reference_id)
+            .bind(movement.user_id)
+            .bind(movement.created_at)
+            .execute(uow.sqlite()?)
+            .await
+            .map_err(|e| ErpError::internal(format!("Failed to create stock movement: {}", e)))?;
+
+        Ok(movement)
+    }
+
+    async fn get_stock_movements(
+        &self,
+        product_id: Option<Uuid>,
+        limit: Option<i32>,
+    ) -> ErpResult<Vec<StockMovementResponse>> {
+        let limit = limit.unwrap_or(50).min(200);
+
+        let query = r#"
+            SELECT
+                sm.id, sm.product_id, sm.movement_type, sm.quantity, sm.reason,
+                sm.reference_id, sm.user_id, sm.created_at,
+                p.name as product_name, p.sku as product_sku
+            FROM stock_movements sm
+            JOIN products p ON sm.product_id = p.id
+            WHERE ? IS NULL OR sm.product_id = ?
+            ORDER BY sm.created_at DESC LIMIT ?
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(product_id)
+            .bind(product_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ErpError::internal(format!("Failed to fetch stock movements: {}", e)))?;
+
+        let movements: Result<Vec<StockMovementResponse>, _> = rows
+            .iter()
+            .map(|row| {
+                Ok(StockMovementResponse {
+                    id: row.try_get("id")?,
+                    product_id: row.try_get("product_id")?,
+                    product_name: row.try_get("product_name")?,
+                    product_sku: row.try_get("product_sku")?,
+                    movement_type: row.try_get("movement_type")?,
+                    quantity: row.try_get("quantity")?,
+                    reason: row
+                        .try_get::<Option<String>, _>("reason")?
+                        .unwrap_or_default(),
+                    reference_id: row.try_get("reference_id")?,
+                    user_id: row.try_get("user_id")?,
+                    notes: None,
+                    previous_quantity: 0,
+                    new_quantity: 0,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect();
+
+        movements.map_err(|e: sqlx::Error| {
+            ErpError::internal(format!("Failed to parse stock movements: {}", e))
+        })
+    }
+
+    async fn get_low_stock_alerts(&self, threshold: Option<i32>) -> ErpResult<Vec<LowStockAlert>> {
+        let query = r#"
+            SELECT * FROM products
+            WHERE status = 'active'
+            AND quantity <= COALESCE(?, min_stock_level)
+            ORDER BY (min_stock_level - quantity) DESC
+        "#;
+
+        let products = Self::fetch_products(&self.pool, sqlx::query(query).bind(threshold)).await?;
+
+        Ok(products
+            .into_iter()
+            .map(|product| LowStockAlert {
+                product_id: product.id,
+                shortfall: threshold.unwrap_or(product.min_stock_level) - product.quantity,
+                sku: product.sku,
+                name: product.name,
+                current_quantity: product.quantity,
+                min_stock_level: product.min_stock_level,
+                category: product.category,
+                status: product.status,
+                last_restock_date: None,
+                supplier_id: product.supplier_id,
+            })
+            .collect())
+    }
+
+    async fn get_inventory_valuation(&self) -> ErpResult<InventoryValuation> {
+        let products = Self::fetch_products(
+            &self.pool,
+            sqlx::query("SELECT * FROM products WHERE status != 'discontinued'"),
+        )
+        .await?;
+
+        let mut valuation = InventoryValuation {
+            total_items: products.len() as i64,
+            total_quantity: 0,
+            total_cost_value: Decimal::ZERO,
+            total_sell_value: Decimal::ZERO,
+            total_margin: Decimal::ZERO,
+            margin_percentage: Decimal::ZERO,
+            by_category: Vec::new(),
+            low_stock_items: 0,
+            out_of_stock_items: 0,
+            overstocked_items: 0,
+        };
+        let mut categories: HashMap<String, CategoryValuation> = HashMap::new();
+
+        for product in &products {
+            let quantity = Decimal::from(product.quantity);
+            let cost_value = product.cost * quantity;
+            let sell_value = product.price * quantity;
+
+            valuation.total_quantity += product.quantity as i64;
+            valuation.total_cost_value += cost_value;
+            valuation.total_sell_value += sell_value;
+            if product.quantity <= product.min_stock_level {
+                valuation.low_stock_items += 1;
+            }
+            if product.quantity <= 0 {
+                valuation.out_of_stock_items += 1;
+            }
+            if product
+                .max_stock_level
+                .is_some_and(|max| product.quantity >= max)
+            {
+                valuation.overstocked_items += 1;
+            }
+
+            let category = categories
+                .entry(product.category.clone())
+                .or_insert_with(|| CategoryValuation {
+                    category: product.category.clone(),
+                    item_count: 0,
+                    quantity: 0,
+                    cost_value: Decimal::ZERO,
+                    sell_value: Decimal::ZERO,
+                    margin: Decimal::ZERO,
+                    margin_percentage: Decimal::ZERO,
+                });
+            category.item_count += 1;
+            category.quantity += product.quantity as i64;
+            category.cost_value += cost_value;
+            category.sell_value += sell_value;
+        }
+
+        let margin_percentage = |margin: Decimal, cost: Decimal| {
+            if cost != Decimal::ZERO {
+                (margin / cost) * Decimal::from(100)
+            } else {
+                Decimal::ZERO
+            }
+        };
+
+        valuation.total_margin = valuation.total_sell_value - valuation.total_cost_value;
+        valuation.margin_percentage =
+            margin_percentage(valuation.total_margin, valuation.total_cost_value);
+
+        let mut by_category: Vec<CategoryValuation> = categories
+            .into_values()
+            .map(|mut category| {
+                category.margin = category.sell_value - category.cost_value;
+                category.margin_percentage =
+                    margin_percentage(category.margin, category.cost_value);
+                category
+            })
+            .collect();
+        by_category.sort_by_key(|entry| std::cmp::Reverse(entry.sell_value));
+        valuation.by_category = by_category;
+
+        Ok(valuation)
+    }
+
+    async fn sku_exists(&self, sku: &str, exclude_id: Option<Uuid>) -> ErpResult<bool> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM products WHERE sku = ? COLLATE NOCASE AND (? IS NULL OR id != ?)",
+        )
+        .bind(sku)
+        .bind(exclude_id)
+        .bind(exclude_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| ErpError::internal(format!("Failed to check SKU existence: {}", e)))?;
+
+        Ok(count > 0)
+    }
+
+    async fn get_inventory_by_category(&self) -> ErpResult<HashMap<String, i64>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT category, COUNT(*) FROM products WHERE status != 'discontinued' GROUP BY category",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| ErpError::internal(format!("Failed to get inventory by category: {}", e)))?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    async fn get_products_by_status(&self, status: ProductStatus) -> ErpResult<Vec<Product>> {
+        Self::fetch_products(
+            &self.pool,
+            sqlx::query("SELECT * FROM products WHERE status = ? ORDER BY created_at DESC")
+                .bind(status),
+        )
+        .await
+    }
+}
+
 // Mock implementation for testing
 use std::sync::LazyLock;
 
@@ -907,6 +1497,10 @@ impl MockInventoryRepository {
 
 #[async_trait]
 impl InventoryRepository for MockInventoryRepository {
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        Ok(UnitOfWork::mock(MockUnitOfWork::new()))
+    }
+
     async fn create_product(&self, request: CreateProductRequest) -> ErpResult<Product> {
         let product = Product::new(request);
         let mut products = self.products.lock().unwrap();
@@ -1073,29 +1667,39 @@ impl InventoryRepository for MockInventoryRepository {
         }
     }
 
-    async fn adjust_stock(
+    async fn adjust_stock_in(
         &self,
+        uow: &mut UnitOfWork,
         request: StockAdjustmentRequest,
         user_id: Uuid,
     ) -> ErpResult<StockMovement> {
-        let mut products = self.products.lock().unwrap();
-        if let Some(mut product) = products.get(&request.product_id).cloned() {
-            let mut movement = product.adjust_quantity(request.quantity_change, request.reason);
-            movement.user_id = user_id;
-            movement.reference_id = request.reference_id;
+        let product = self
+            .products
+            .lock()
+            .unwrap()
+            .get(&request.product_id)
+            .cloned();
+        let Some(mut product) = product else {
+            return Err(ErpError::not_found_simple(format!(
+                "Product with ID {} not found",
+                request.product_id
+            )));
+        };
+        check_stock_available(&product, request.quantity_change)?;
 
-            products.insert(request.product_id, product);
+        let mut movement = product.adjust_quantity(request.quantity_change, request.reason);
+        movement.user_id = user_id;
+        movement.reference_id = request.reference_id;
 
-            let mut movements = self.stock_movements.lock().unwrap();
-            movements.push(movement.clone());
+        let products = self.products.clone();
+        let movements = self.stock_movements.clone();
+        let staged = movement.clone();
+        uow.as_mock()?.stage(async move {
+            products.lock().unwrap().insert(product.id, product);
+            movements.lock().unwrap().push(staged);
+        })?;
 
-            Ok(movement)
-        } else {
-            Err(ErpError::not_found_simple(format!(
-                "Product with ID {} not found",
-                request.product_id
-            )))
-        }
+        Ok(movement)
     }
 
     async fn get_stock_movements(
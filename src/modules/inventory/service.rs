@@ -1,4 +1,6 @@
+use crate::core::cache::{CacheScope, QueryCache};
 use crate::core::database::models::product::{Product, ProductStatus, StockMovement, StockStatus};
+use crate::core::database::UnitOfWork;
 use crate::modules::inventory::models::{
     CreateInventoryItemRequest, InventoryFilter, InventoryItem, InventoryItemResponse,
     InventoryListResponse, InventoryValuation, LowStockAlert, StockAdjustmentRequest,
@@ -9,12 +11,13 @@ use crate::utils::error::{ErpError, ErpResult};
 use crate::utils::validation::ValidationService;
 use async_trait::async_trait;
 use rust_decimal::Decimal;
+use shaku::Interface;
 use std::sync::Arc;
 use tracing::{info, warn};
 use uuid::Uuid;
 
 #[async_trait]
-pub trait InventoryService: Send + Sync {
+pub trait InventoryService: Interface {
     async fn create_product(
         &self,
         request: CreateInventoryItemRequest,
@@ -36,6 +39,16 @@ pub trait InventoryService: Send + Sync {
         reason: String,
         user_id: Uuid,
     ) -> ErpResult<StockMovementResponse>;
+    /// Stock adjustment that commits or rolls back with `uow`.
+    /// Product caches are left to the caller once `uow` is finished.
+    async fn adjust_stock_in(
+        &self,
+        uow: &mut UnitOfWork,
+        product_id: Uuid,
+        quantity_change: i32,
+        reason: String,
+        user_id: Uuid,
+    ) -> ErpResult<StockMovement>;
     async fn get_stock_movements(
         &self,
         product_id: Option<Uuid>,
@@ -67,6 +80,7 @@ pub trait InventoryService: Send + Sync {
 pub struct InventoryServiceImpl {
     repository: Arc<dyn InventoryRepository>,
     validation_service: ValidationService,
+    cache: QueryCache,
 }
 
 impl InventoryServiceImpl {
@@ -74,9 +88,22 @@ impl InventoryServiceImpl {
         Self {
             repository,
             validation_service: ValidationService::new(),
+            cache: QueryCache::disabled(),
         }
     }
 
+    /// Caches SKU lookups in `get_product`; stock changes invalidate them
+    /// together with the report summaries
+    pub fn with_cache(mut self, cache: QueryCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    async fn invalidate_cached_products(&self) {
+        self.cache.invalidate(CacheScope::Products).await;
+        self.cache.invalidate(CacheScope::Reports).await;
+    }
+
     async fn get_product_by_id_or_sku(&self, id_or_sku: &str) -> ErpResult<Product> {
         // Try to parse as UUID first
         if let Ok(id) = Uuid::parse_str(id_or_sku) {
@@ -205,6 +232,7 @@ impl InventoryService for InventoryServiceImpl {
 
         // Create product
         let product = self.repository.create_product(create_request).await?;
+        self.invalidate_cached_products().await;
 
         info!(
             "Product created successfully: {} ({})",
@@ -217,7 +245,15 @@ impl InventoryService for InventoryServiceImpl {
     }
 
     async fn get_product(&self, id_or_sku: &str) -> ErpResult<InventoryItemResponse> {
-        let product = self.get_product_by_id_or_sku(id_or_sku).await?;
+        let product = if Uuid::parse_str(id_or_sku).is_ok() {
+            self.get_product_by_id_or_sku(id_or_sku).await?
+        } else {
+            self.cache
+                .get_or_load(CacheScope::Products, &format!("sku:{}", id_or_sku), || {
+                    self.get_product_by_id_or_sku(id_or_sku)
+                })
+                .await?
+        };
         let inventory_item = InventoryItem::from_product(product);
         Ok(inventory_item.to_response())
     }
@@ -307,6 +343,7 @@ impl InventoryService for InventoryServiceImpl {
             .repository
             .update_product(product.id, update_request)
             .await?;
+        self.invalidate_cached_products().await;
 
         info!(
             "Product updated successfully: {} ({})",
@@ -333,6 +370,7 @@ impl InventoryService for InventoryServiceImpl {
         }
 
         self.repository.delete_product(product.id, force).await?;
+        self.invalidate_cached_products().await;
 
         info!(
             "Product deleted successfully: {} ({})",
@@ -377,6 +415,7 @@ impl InventoryService for InventoryServiceImpl {
             .repository
             .adjust_stock(adjustment_request, user_id)
             .await?;
+        self.invalidate_cached_products().await;
 
         // Get updated product for response
         let updated_product = self
@@ -405,6 +444,38 @@ impl InventoryService for InventoryServiceImpl {
         Ok(response)
     }
 
+    async fn adjust_stock_in(
+        &self,
+        uow: &mut UnitOfWork,
+        product_id: Uuid,
+        quantity_change: i32,
+        reason: String,
+        user_id: Uuid,
+    ) -> ErpResult<StockMovement> {
+        // The product is read inside `uow`, which checks the stock level
+        if quantity_change == 0 {
+            return Err(ErpError::validation_simple(
+                "Quantity change cannot be zero".to_string(),
+            ));
+        }
+        if reason.trim().is_empty() {
+            return Err(ErpError::validation_simple(
+                "Reason is required for stock adjustments".to_string(),
+            ));
+        }
+
+        let adjustment_request = StockAdjustmentRequest {
+            product_id,
+            quantity_change,
+            reason,
+            reference_id: None,
+            notes: None,
+        };
+        self.repository
+            .adjust_stock_in(uow, adjustment_request, user_id)
+            .await
+    }
+
     async fn get_stock_movements(
         &self,
         product_id: Option<Uuid>,
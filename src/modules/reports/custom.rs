@@ -0,0 +1,1492 @@
+//! 사용자 정의 보고서
+//!
+//! YAML 스펙으로 기본 데이터셋, 차원, 측정값(sum/avg/count), 필터, 기간을 선언하면
+//! 데이터셋별로 허용된 필드 카탈로그만을 사용해 SQL을 조립합니다. 스펙의 문자열은 SQL
+//! 식별자로 사용되지 않으며, 필터 값과 기간은 모두 바인드 파라미터로 전달됩니다.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::models::ReportPeriod;
+use super::templates::{BarChart, ReportDocument, ReportSection, ReportTable};
+use crate::core::security::pii::{is_encrypted, PiiCipher};
+use crate::utils::error::{ErpError, ErpResult};
+use crate::utils::xlsx::CellValue;
+
+/// 사용자 정의 보고서에서 조회할 수 있는 최대 행 수
+pub const MAX_CUSTOM_REPORT_ROWS: u32 = 10_000;
+
+/// 차트에 표시할 최대 항목 수
+const MAX_CHART_POINTS: usize = 20;
+
+/// 종료 날짜만 지정된 기간의 시작 날짜
+const EARLIEST_DATE: NaiveDate = match NaiveDate::from_ymd_opt(1900, 1, 1) {
+    Some(date) => date,
+    None => panic!("invalid date"),
+};
+
+/// 보고서의 기본 데이터셋
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomDataset {
+    Orders,
+    OrderItems,
+    Products,
+    Customers,
+    StockMovements,
+}
+
+/// 필드 값 타입
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    Text,
+    Integer,
+    Decimal,
+    Date,
+    Bool,
+}
+
+/// 데이터셋에서 사용할 수 있는 필드 정의
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldDef {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: FieldKind,
+    /// 암호화되어 저장되는 PII 필드 (조회 시 복호화, 필터/정렬 불가)
+    pub encrypted: bool,
+    expr: &'static str,
+}
+
+impl FieldDef {
+    const fn new(
+        name: &'static str,
+        label: &'static str,
+        expr: &'static str,
+        kind: FieldKind,
+    ) -> Self {
+        Self {
+            name,
+            label,
+            kind,
+            encrypted: false,
+            expr,
+        }
+    }
+
+    const fn encrypted(mut self) -> Self {
+        self.encrypted = true;
+        self
+    }
+}
+
+// 주의: products.id는 문자열, 다른 테이블의 product_id는 UUID이므로 텍스트로 비교합니다.
+const ORDERS_FROM: &str = "sales_orders so LEFT JOIN customers c ON c.id = so.customer_id";
+const ORDER_ITEMS_FROM: &str = "sales_order_items soi \
+     JOIN sales_orders so ON so.id = soi.order_id \
+     LEFT JOIN products p ON p.id::text = soi.product_id::text \
+     LEFT JOIN customers c ON c.id = so.customer_id";
+const PRODUCTS_FROM: &str = "products p";
+const CUSTOMERS_FROM: &str = "customers c";
+const STOCK_MOVEMENTS_FROM: &str =
+    "stock_movements sm LEFT JOIN products p ON p.id::text = sm.product_id::text";
+
+const ORDER_FIELDS: &[FieldDef] = &[
+    FieldDef::new(
+        "order_number",
+        "주문번호",
+        "so.order_number",
+        FieldKind::Text,
+    ),
+    FieldDef::new("status", "주문 상태", "so.status::text", FieldKind::Text),
+    FieldDef::new(
+        "payment_status",
+        "결제 상태",
+        "so.payment_status::text",
+        FieldKind::Text,
+    ),
+    FieldDef::new(
+        "payment_method",
+        "결제 수단",
+        "so.payment_method::text",
+        FieldKind::Text,
+    ),
+    FieldDef::new("customer_name", "고객명", "c.name", FieldKind::Text),
+    FieldDef::new(
+        "customer_type",
+        "고객 유형",
+        "c.customer_type::text",
+        FieldKind::Text,
+    ),
+    FieldDef::new(
+        "order_date",
+        "주문일",
+        "so.order_date::date",
+        FieldKind::Date,
+    ),
+    FieldDef::new(
+        "order_month",
+        "주문월",
+        "to_char(so.order_date, 'YYYY-MM')",
+        FieldKind::Text,
+    ),
+    FieldDef::new(
+        "order_year",
+        "주문연도",
+        "EXTRACT(YEAR FROM so.order_date)::int8",
+        FieldKind::Integer,
+    ),
+    FieldDef::new("subtotal", "소계", "so.subtotal", FieldKind::Decimal),
+    FieldDef::new("tax_amount", "세금", "so.tax_amount", FieldKind::Decimal),
+    FieldDef::new(
+        "discount_amount",
+        "할인",
+        "so.discount_amount",
+        FieldKind::Decimal,
+    ),
+    FieldDef::new(
+        "shipping_amount",
+        "배송비",
+        "so.shipping_amount",
+        FieldKind::Decimal,
+    ),
+    FieldDef::new(
+        "total_amount",
+        "총액",
+        "so.total_amount",
+        FieldKind::Decimal,
+    ),
+];
+
+const ORDER_ITEM_FIELDS: &[FieldDef] = &[
+    FieldDef::new(
+        "order_number",
+        "주문번호",
+        "so.order_number",
+        FieldKind::Text,
+    ),
+    FieldDef::new(
+        "order_status",
+        "주문 상태",
+        "so.status::text",
+        FieldKind::Text,
+    ),
+    FieldDef::new(
+        "order_date",
+        "주문일",
+        "so.order_date::date",
+        FieldKind::Date,
+    ),
+    FieldDef::new(
+        "order_month",
+        "주문월",
+        "to_char(so.order_date, 'YYYY-MM')",
+        FieldKind::Text,
+    ),
+    FieldDef::new("customer_name", "고객명", "c.name", FieldKind::Text),
+    FieldDef::new("product_sku", "SKU", "p.sku", FieldKind::Text),
+    FieldDef::new("product_name", "제품명", "p.name", FieldKind::Text),
+    FieldDef::new("category", "카테고리", "p.category", FieldKind::Text),
+    FieldDef::new("quantity", "수량", "soi.quantity::int8", FieldKind::Integer),
+    FieldDef::new("unit_price", "단가", "soi.unit_price", FieldKind::Decimal),
+    FieldDef::new(
+        "discount_amount",
+        "할인",
+        "soi.discount_amount",
+        FieldKind::Decimal,
+    ),
+    FieldDef::new("total_price", "금액", "soi.total_price", FieldKind::Decimal),
+];
+
+const PRODUCT_FIELDS: &[FieldDef] = &[
+    FieldDef::new("sku", "SKU", "p.sku", FieldKind::Text),
+    FieldDef::new("name", "제품명", "p.name", FieldKind::Text),
+    FieldDef::new("category", "카테고리", "p.category", FieldKind::Text),
+    FieldDef::new("status", "상태", "p.status::text", FieldKind::Text),
+    FieldDef::new("is_taxable", "과세 여부", "p.is_taxable", FieldKind::Bool),
+    FieldDef::new("price", "판매가", "p.price", FieldKind::Decimal),
+    FieldDef::new("cost", "원가", "p.cost", FieldKind::Decimal),
+    FieldDef::new(
+        "quantity",
+        "재고 수량",
+        "p.quantity::int8",
+        FieldKind::Integer,
+    ),
+    FieldDef::new(
+        "min_stock_level",
+        "최소 재고",
+        "p.min_stock_level::int8",
+        FieldKind::Integer,
+    ),
+    FieldDef::new(
+        "stock_value",
+        "재고 가치",
+        "(p.cost * p.quantity)",
+        FieldKind::Decimal,
+    ),
+    FieldDef::new(
+        "created_date",
+        "등록일",
+        "p.created_at::date",
+        FieldKind::Date,
+    ),
+];
+
+const CUSTOMER_FIELDS: &[FieldDef] = &[
+    FieldDef::new(
+        "customer_code",
+        "고객 코드",
+        "c.customer_code",
+        FieldKind::Text,
+    ),
+    FieldDef::new("name", "고객명", "c.name", FieldKind::Text),
+    FieldDef::new("email", "이메일", "c.email", FieldKind::Text).encrypted(),
+    FieldDef::new(
+        "customer_type",
+        "고객 유형",
+        "c.customer_type::text",
+        FieldKind::Text,
+    ),
+    FieldDef::new("status", "상태", "c.status::text", FieldKind::Text),
+    FieldDef::new(
+        "credit_limit",
+        "신용 한도",
+        "c.credit_limit",
+        FieldKind::Decimal,
+    ),
+    FieldDef::new(
+        "current_balance",
+        "현재 잔액",
+        "c.current_balance",
+        FieldKind::Decimal,
+    ),
+    FieldDef::new(
+        "created_date",
+        "등록일",
+        "c.created_at::date",
+        FieldKind::Date,
+    ),
+    FieldDef::new(
+        "created_month",
+        "등록월",
+        "to_char(c.created_at, 'YYYY-MM')",
+        FieldKind::Text,
+    ),
+];
+
+const STOCK_MOVEMENT_FIELDS: &[FieldDef] = &[
+    FieldDef::new(
+        "movement_type",
+        "이동 유형",
+        "sm.movement_type::text",
+        FieldKind::Text,
+    ),
+    FieldDef::new("reason", "사유", "sm.reason", FieldKind::Text),
+    FieldDef::new("product_sku", "SKU", "p.sku", FieldKind::Text),
+    FieldDef::new("product_name", "제품명", "p.name", FieldKind::Text),
+    FieldDef::new("category", "카테고리", "p.category", FieldKind::Text),
+    FieldDef::new("quantity", "수량", "sm.quantity::int8", FieldKind::Integer),
+    FieldDef::new(
+        "movement_date",
+        "이동일",
+        "sm.created_at::date",
+        FieldKind::Date,
+    ),
+    FieldDef::new(
+        "movement_month",
+        "이동월",
+        "to_char(sm.created_at, 'YYYY-MM')",
+        FieldKind::Text,
+    ),
+];
+
+impl CustomDataset {
+    /// 데이터셋 표시 이름
+    pub fn label(&self) -> &'static str {
+        match self {
+            CustomDataset::Orders => "주문",
+            CustomDataset::OrderItems => "주문 항목",
+            CustomDataset::Products => "제품",
+            CustomDataset::Customers => "고객",
+            CustomDataset::StockMovements => "재고 이동",
+        }
+    }
+
+    /// 사용할 수 있는 필드 목록
+    pub fn fields(&self) -> &'static [FieldDef] {
+        match self {
+            CustomDataset::Orders => ORDER_FIELDS,
+            CustomDataset::OrderItems => ORDER_ITEM_FIELDS,
+            CustomDataset::Products => PRODUCT_FIELDS,
+            CustomDataset::Customers => CUSTOMER_FIELDS,
+            CustomDataset::StockMovements => STOCK_MOVEMENT_FIELDS,
+        }
+    }
+
+    /// 이름으로 필드 조회
+    pub fn field(&self, name: &str) -> Option<&'static FieldDef> {
+        self.fields().iter().find(|f| f.name == name)
+    }
+
+    fn source_tables(&self) -> &'static str {
+        match self {
+            CustomDataset::Orders => ORDERS_FROM,
+            CustomDataset::OrderItems => ORDER_ITEMS_FROM,
+            CustomDataset::Products => PRODUCTS_FROM,
+            CustomDataset::Customers => CUSTOMERS_FROM,
+            CustomDataset::StockMovements => STOCK_MOVEMENTS_FROM,
+        }
+    }
+
+    /// 기간 조건에 사용할 날짜 컬럼
+    fn date_expr(&self) -> &'static str {
+        match self {
+            CustomDataset::Orders | CustomDataset::OrderItems => "so.order_date::date",
+            CustomDataset::Products => "p.created_at::date",
+            CustomDataset::Customers => "c.created_at::date",
+            CustomDataset::StockMovements => "sm.created_at::date",
+        }
+    }
+}
+
+impl fmt::Display for CustomDataset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CustomDataset::Orders => "orders",
+            CustomDataset::OrderItems => "order_items",
+            CustomDataset::Products => "products",
+            CustomDataset::Customers => "customers",
+            CustomDataset::StockMovements => "stock_movements",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FieldKind {
+    fn pg_type(&self) -> &'static str {
+        match self {
+            FieldKind::Text => "text",
+            FieldKind::Integer => "int8",
+            FieldKind::Decimal => "numeric",
+            FieldKind::Date => "date",
+            FieldKind::Bool => "boolean",
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, FieldKind::Integer | FieldKind::Decimal)
+    }
+
+    /// 필터 값이 필드 타입에 맞는지 미리 검증해 데이터베이스 캐스팅 오류를 방지
+    fn check_value(&self, field: &str, value: &str) -> ErpResult<()> {
+        let valid = match self {
+            FieldKind::Text => true,
+            FieldKind::Integer => value.parse::<i64>().is_ok(),
+            FieldKind::Decimal => value.parse::<Decimal>().is_ok(),
+            FieldKind::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+            FieldKind::Bool => value.parse::<bool>().is_ok(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(ErpError::validation(
+                field,
+                format!(
+                    "필터 값 '{}'이(가) 필드 타입({})과 맞지 않습니다",
+                    value,
+                    self.pg_type()
+                ),
+            ))
+        }
+    }
+}
+
+/// 집계 함수
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    Sum,
+    Avg,
+    Count,
+}
+
+impl fmt::Display for Aggregation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Aggregation::Sum => write!(f, "sum"),
+            Aggregation::Avg => write!(f, "avg"),
+            Aggregation::Count => write!(f, "count"),
+        }
+    }
+}
+
+/// 필터 연산자
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOp {
+    #[default]
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    In,
+    Contains,
+}
+
+/// 측정값 정의
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeasureSpec {
+    pub function: Aggregation,
+    /// 집계 대상 필드 (count는 생략 가능)
+    #[serde(default)]
+    pub field: Option<String>,
+    /// 출력 컬럼 이름
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl MeasureSpec {
+    /// 정렬 등에서 참조하는 측정값 키 (예: sum_total_price, count)
+    pub fn key(&self) -> String {
+        match &self.field {
+            Some(field) => format!("{}_{}", self.function, field),
+            None => self.function.to_string(),
+        }
+    }
+}
+
+/// 필터 정의
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterSpec {
+    pub field: String,
+    #[serde(default)]
+    pub op: FilterOp,
+    pub value: serde_json::Value,
+}
+
+/// 기간 정의 (from/to 또는 period 중 하나)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DateRangeSpec {
+    #[serde(default)]
+    pub from: Option<NaiveDate>,
+    #[serde(default)]
+    pub to: Option<NaiveDate>,
+    /// daily, weekly, monthly, quarterly, yearly
+    #[serde(default)]
+    pub period: Option<String>,
+}
+
+/// 정렬 정의
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderBySpec {
+    /// 차원 이름 또는 측정값 키/라벨
+    pub column: String,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// 사용자 정의 보고서 스펙
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomReportSpec {
+    pub name: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    pub dataset: CustomDataset,
+    #[serde(default)]
+    pub dimensions: Vec<String>,
+    #[serde(default)]
+    pub measures: Vec<MeasureSpec>,
+    #[serde(default)]
+    pub filters: Vec<FilterSpec>,
+    #[serde(default)]
+    pub date_range: Option<DateRangeSpec>,
+    #[serde(default)]
+    pub order_by: Vec<OrderBySpec>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+/// 결과 컬럼의 역할
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnRole {
+    Dimension,
+    Measure(Aggregation),
+}
+
+/// 결과 컬럼 정보
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomColumn {
+    pub key: String,
+    pub label: String,
+    pub kind: FieldKind,
+    pub role: ColumnRole,
+}
+
+/// 바인드 파라미터
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryParam {
+    Text(String),
+    TextList(Vec<String>),
+}
+
+/// 조립된 쿼리
+#[derive(Debug, Clone)]
+pub struct CustomQuery {
+    pub sql: String,
+    pub params: Vec<QueryParam>,
+    pub columns: Vec<CustomColumn>,
+    pub date_range: Option<(NaiveDate, NaiveDate)>,
+}
+
+impl CustomReportSpec {
+    /// YAML 문자열에서 스펙 파싱
+    pub fn from_yaml(content: &str) -> ErpResult<Self> {
+        serde_yaml::from_str(content)
+            .map_err(|e| ErpError::serialization(format!("보고서 스펙 파싱 실패: {}", e)))
+    }
+
+    /// 파일에서 스펙 로드
+    pub async fn from_file(path: impl AsRef<Path>) -> ErpResult<Self> {
+        let path = path.as_ref();
+        let content = tokio::fs::read_to_string(path).await.map_err(|e| {
+            ErpError::io(format!(
+                "보고서 스펙 파일 읽기 실패 ({}): {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_yaml(&content)
+    }
+
+    /// 보고서 제목 (없으면 이름 사용)
+    pub fn display_title(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.name)
+    }
+
+    /// 스펙의 기간을 실제 날짜 범위로 변환
+    pub fn resolved_date_range(&self) -> ErpResult<Option<(NaiveDate, NaiveDate)>> {
+        let Some(range) = &self.date_range else {
+            return Ok(None);
+        };
+
+        let resolved = match (&range.period, range.from, range.to) {
+            (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+                return Err(ErpError::validation(
+                    "date_range",
+                    "period와 from/to는 함께 사용할 수 없습니다",
+                ))
+            }
+            (Some(period), None, None) => period.parse::<ReportPeriod>()?.to_date_range(),
+            (None, Some(from), Some(to)) => (from, to),
+            (None, Some(from), None) => (from, Utc::now().date_naive()),
+            (None, None, Some(to)) => (EARLIEST_DATE, to),
+            (None, None, None) => return Ok(None),
+        };
+
+        if resolved.0 > resolved.1 {
+            return Err(ErpError::validation(
+                "date_range",
+                "시작 날짜는 종료 날짜보다 이전이어야 합니다",
+            ));
+        }
+
+        Ok(Some(resolved))
+    }
+
+    /// 스펙을 검증하고 파라미터화된 SQL로 변환
+    pub fn build_query(&self) -> ErpResult<CustomQuery> {
+        if self.name.trim().is_empty() {
+            return Err(ErpError::validation("name", "보고서 이름은 필수입니다"));
+        }
+        if self.dimensions.is_empty() && self.measures.is_empty() {
+            return Err(ErpError::validation(
+                "dimensions",
+                "차원 또는 측정값을 하나 이상 지정해야 합니다",
+            ));
+        }
+
+        let limit = self.limit.unwrap_or(MAX_CUSTOM_REPORT_ROWS);
+        if limit == 0 || limit > MAX_CUSTOM_REPORT_ROWS {
+            return Err(ErpError::validation(
+                "limit",
+                format!("limit은 1-{} 범위여야 합니다", MAX_CUSTOM_REPORT_ROWS),
+            ));
+        }
+
+        let mut columns = Vec::new();
+        let mut select = Vec::new();
+
+        let mut seen = HashSet::new();
+        for name in &self.dimensions {
+            let field = self.lookup_field("dimensions", name)?;
+            if !seen.insert(name.as_str()) {
+                return Err(ErpError::validation(
+                    "dimensions",
+                    format!("중복된 차원입니다: {}", name),
+                ));
+            }
+            select.push(format!("{} AS c{}", field.expr, columns.len()));
+            columns.push(CustomColumn {
+                key: field.name.to_string(),
+                label: field.label.to_string(),
+                kind: field.kind,
+                role: ColumnRole::Dimension,
+            });
+        }
+
+        let mut measure_keys = HashSet::new();
+        for measure in &self.measures {
+            let (expr, kind, default_label) = self.measure_expr(measure)?;
+            let key = measure.key();
+            if !measure_keys.insert(key.clone()) {
+                return Err(ErpError::validation(
+                    "measures",
+                    format!("중복된 측정값입니다: {}", key),
+                ));
+            }
+            select.push(format!("{} AS c{}", expr, columns.len()));
+            columns.push(CustomColumn {
+                key,
+                label: measure.label.clone().unwrap_or(default_label),
+                kind,
+                role: ColumnRole::Measure(measure.function),
+            });
+        }
+
+        let mut params = Vec::new();
+        let mut conditions = Vec::new();
+        for filter in &self.filters {
+            conditions.push(self.filter_condition(filter, &mut params)?);
+        }
+
+        let date_range = self.resolved_date_range()?;
+        if let Some((from, to)) = date_range {
+            params.push(QueryParam::Text(from.to_string()));
+            params.push(QueryParam::Text(to.to_string()));
+            conditions.push(format!(
+                "{} BETWEEN ${}::date AND ${}::date",
+                self.dataset.date_expr(),
+                params.len() - 1,
+                params.len()
+            ));
+        }
+
+        let mut sql = format!(
+            "SELECT {} FROM {}",
+            select.join(", "),
+            self.dataset.source_tables()
+        );
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+
+        let dimension_ordinals: Vec<String> =
+            (1..=self.dimensions.len()).map(|i| i.to_string()).collect();
+        if !self.measures.is_empty() && !self.dimensions.is_empty() {
+            sql.push_str(" GROUP BY ");
+            sql.push_str(&dimension_ordinals.join(", "));
+        }
+
+        if !self.order_by.is_empty() {
+            let mut order = Vec::new();
+            for spec in &self.order_by {
+                let index = columns
+                    .iter()
+                    .position(|c| c.key == spec.column || c.label == spec.column)
+                    .ok_or_else(|| {
+                        ErpError::validation(
+                            "order_by",
+                            format!("정렬 컬럼이 결과에 없습니다: {}", spec.column),
+                        )
+                    })?;
+                if self.is_encrypted_dimension(&columns[index]) {
+                    return Err(ErpError::validation(
+                        "order_by",
+                        format!("암호화된 필드로는 정렬할 수 없습니다: {}", spec.column),
+                    ));
+                }
+                let direction = if spec.descending { "DESC" } else { "ASC" };
+                order.push(format!("c{} {} NULLS LAST", index, direction));
+            }
+            sql.push_str(" ORDER BY ");
+            sql.push_str(&order.join(", "));
+        } else if !dimension_ordinals.is_empty() {
+            sql.push_str(" ORDER BY ");
+            sql.push_str(&dimension_ordinals.join(", "));
+        }
+
+        sql.push_str(&format!(" LIMIT {}", limit));
+
+        Ok(CustomQuery {
+            sql,
+            params,
+            columns,
+            date_range,
+        })
+    }
+
+    /// 암호화된 필드를 차원으로 조회하는지 여부
+    pub fn uses_encrypted_fields(&self) -> bool {
+        self.dimensions
+            .iter()
+            .filter_map(|name| self.dataset.field(name))
+            .any(|field| field.encrypted)
+    }
+
+    /// 복호화가 필요한 차원 컬럼인지 확인
+    pub fn is_encrypted_dimension(&self, column: &CustomColumn) -> bool {
+        column.role == ColumnRole::Dimension
+            && self
+                .dataset
+                .field(&column.key)
+                .is_some_and(|field| field.encrypted)
+    }
+
+    fn lookup_field(&self, context: &str, name: &str) -> ErpResult<&'static FieldDef> {
+        self.dataset.field(name).ok_or_else(|| {
+            let available: Vec<&str> = self.dataset.fields().iter().map(|f| f.name).collect();
+            ErpError::validation(
+                context,
+                format!(
+                    "데이터셋 '{}'에 없는 필드입니다: {} (사용 가능: {})",
+                    self.dataset,
+                    name,
+                    available.join(", ")
+                ),
+            )
+        })
+    }
+
+    fn measure_expr(&self, measure: &MeasureSpec) -> ErpResult<(String, FieldKind, String)> {
+        let field = match &measure.field {
+            Some(name) => Some(self.lookup_field("measures", name)?),
+            None => None,
+        };
+
+        match (measure.function, field) {
+            (Aggregation::Count, None) => Ok((
+                "COUNT(*)::int8".to_string(),
+                FieldKind::Integer,
+                "건수".to_string(),
+            )),
+            (Aggregation::Count, Some(field)) => Ok((
+                format!("COUNT({})::int8", field.expr),
+                FieldKind::Integer,
+                format!("{} 건수", field.label),
+            )),
+            (Aggregation::Sum | Aggregation::Avg, None) => Err(ErpError::validation(
+                "measures",
+                format!("{} 측정값에는 field가 필요합니다", measure.function),
+            )),
+            (function, Some(field)) if !field.kind.is_numeric() => Err(ErpError::validation(
+                "measures",
+                format!(
+                    "{}는 숫자 필드에만 사용할 수 있습니다: {}",
+                    function, field.name
+                ),
+            )),
+            (Aggregation::Sum, Some(field)) => {
+                // 정수 합계는 정수로 유지하고, 그 외에는 numeric으로 반환
+                let (cast, kind) = match field.kind {
+                    FieldKind::Integer => ("int8", FieldKind::Integer),
+                    _ => ("numeric", FieldKind::Decimal),
+                };
+                Ok((
+                    format!("COALESCE(SUM({}), 0)::{}", field.expr, cast),
+                    kind,
+                    format!("{} 합계", field.label),
+                ))
+            }
+            (Aggregation::Avg, Some(field)) => Ok((
+                format!("ROUND(AVG({})::numeric, 2)", field.expr),
+                FieldKind::Decimal,
+                format!("{} 평균", field.label),
+            )),
+        }
+    }
+
+    fn filter_condition(
+        &self,
+        filter: &FilterSpec,
+        params: &mut Vec<QueryParam>,
+    ) -> ErpResult<String> {
+        let field = self.lookup_field("filters", &filter.field)?;
+        if field.encrypted {
+            return Err(ErpError::validation(
+                &filter.field,
+                "암호화된 필드는 필터에 사용할 수 없습니다",
+            ));
+        }
+
+        match filter.op {
+            FilterOp::In => {
+                let values = match &filter.value {
+                    serde_json::Value::Array(items) if !items.is_empty() => items
+                        .iter()
+                        .map(|v| scalar_to_string(&filter.field, v))
+                        .collect::<ErpResult<Vec<_>>>()?,
+                    _ => {
+                        return Err(ErpError::validation(
+                            &filter.field,
+                            "in 필터에는 비어 있지 않은 배열 값이 필요합니다",
+                        ))
+                    }
+                };
+                for value in &values {
+                    field.kind.check_value(&filter.field, value)?;
+                }
+                params.push(QueryParam::TextList(values));
+                Ok(format!(
+                    "{} = ANY(${}::text[]::{}[])",
+                    field.expr,
+                    params.len(),
+                    field.kind.pg_type()
+                ))
+            }
+            FilterOp::Contains => {
+                if field.kind != FieldKind::Text {
+                    return Err(ErpError::validation(
+                        &filter.field,
+                        "contains 필터는 문자열 필드에만 사용할 수 있습니다",
+                    ));
+                }
+                params.push(QueryParam::Text(scalar_to_string(
+                    &filter.field,
+                    &filter.value,
+                )?));
+                Ok(format!(
+                    "strpos(lower({}), lower(${}::text)) > 0",
+                    field.expr,
+                    params.len()
+                ))
+            }
+            op => {
+                if field.kind == FieldKind::Bool && !matches!(op, FilterOp::Eq | FilterOp::Ne) {
+                    return Err(ErpError::validation(
+                        &filter.field,
+                        "불리언 필드에는 eq/ne 필터만 사용할 수 있습니다",
+                    ));
+                }
+                let operator = match op {
+                    FilterOp::Eq => "=",
+                    FilterOp::Ne => "<>",
+                    FilterOp::Gt => ">",
+                    FilterOp::Gte => ">=",
+                    FilterOp::Lt => "<",
+                    FilterOp::Lte => "<=",
+                    FilterOp::In | FilterOp::Contains => unreachable!("위에서 처리됨"),
+                };
+                let value = scalar_to_string(&filter.field, &filter.value)?;
+                field.kind.check_value(&filter.field, &value)?;
+                params.push(QueryParam::Text(value));
+                Ok(format!(
+                    "{} {} ${}::{}",
+                    field.expr,
+                    operator,
+                    params.len(),
+                    field.kind.pg_type()
+                ))
+            }
+        }
+    }
+}
+
+fn scalar_to_string(field: &str, value: &serde_json::Value) -> ErpResult<String> {
+    match value {
+        serde_json::Value::String(s) => Ok(s.clone()),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        serde_json::Value::Bool(b) => Ok(b.to_string()),
+        _ => Err(ErpError::validation(
+            field,
+            "필터 값은 문자열, 숫자 또는 불리언이어야 합니다",
+        )),
+    }
+}
+
+/// 사용자 정의 보고서 실행 결과
+#[derive(Debug, Clone)]
+pub struct CustomReportResult {
+    pub name: String,
+    pub title: String,
+    pub dataset: CustomDataset,
+    pub generated_at: DateTime<Utc>,
+    pub date_range: Option<(NaiveDate, NaiveDate)>,
+    pub columns: Vec<CustomColumn>,
+    pub rows: Vec<Vec<CellValue>>,
+}
+
+impl CustomReportResult {
+    /// 측정값 합계 행 (sum/count만 합산, avg는 비워 둠)
+    pub fn totals(&self) -> Option<Vec<CellValue>> {
+        let has_dimension = self.columns.iter().any(|c| c.role == ColumnRole::Dimension);
+        let has_measure = self
+            .columns
+            .iter()
+            .any(|c| matches!(c.role, ColumnRole::Measure(_)));
+        if !has_dimension || !has_measure || self.rows.len() < 2 {
+            return None;
+        }
+
+        let mut totals = Vec::with_capacity(self.columns.len());
+        for (index, column) in self.columns.iter().enumerate() {
+            let cell = match column.role {
+                ColumnRole::Dimension if index == 0 => CellValue::from("합계"),
+                ColumnRole::Dimension | ColumnRole::Measure(Aggregation::Avg) => CellValue::Empty,
+                ColumnRole::Measure(_) => {
+                    let values = self.rows.iter().map(|row| &row[index]);
+                    match column.kind {
+                        FieldKind::Integer => CellValue::Integer(
+                            values
+                                .filter_map(|v| match v {
+                                    CellValue::Integer(i) => Some(*i),
+                                    _ => None,
+                                })
+                                .sum(),
+                        ),
+                        _ => CellValue::Decimal(
+                            values
+                                .filter_map(|v| match v {
+                                    CellValue::Decimal(d) => Some(*d),
+                                    _ => None,
+                                })
+                                .sum(),
+                        ),
+                    }
+                }
+            };
+            totals.push(cell);
+        }
+        Some(totals)
+    }
+
+    /// 기간 표시 문자열
+    pub fn period_label(&self) -> String {
+        match self.date_range {
+            Some((from, to)) if from == EARLIEST_DATE => format!("~ {}", to),
+            Some((from, to)) => format!("{} ~ {}", from, to),
+            None => "전체".to_string(),
+        }
+    }
+
+    /// 공통 템플릿 문서로 변환 (HTML/PDF/XLSX)
+    pub fn to_document(&self) -> ReportDocument {
+        let headers: Vec<&str> = self.columns.iter().map(|c| c.label.as_str()).collect();
+        let numeric: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.kind.is_numeric())
+            .map(|(i, _)| i)
+            .collect();
+
+        let mut table = ReportTable::new(&headers)
+            .numeric(&numeric)
+            .rows(self.rows.iter().cloned());
+        if let Some(totals) = self.totals() {
+            table = table.totals(totals);
+        }
+
+        let mut section = ReportSection::new("결과")
+            .item("데이터셋", self.dataset.label())
+            .item("기간", self.period_label())
+            .item("행 수", self.rows.len())
+            .table(table);
+
+        // 첫 번째 차원과 첫 번째 측정값으로 막대 차트 구성
+        let measure_index = self
+            .columns
+            .iter()
+            .position(|c| matches!(c.role, ColumnRole::Measure(_)));
+        if let (Some(ColumnRole::Dimension), Some(measure_index)) =
+            (self.columns.first().map(|c| c.role), measure_index)
+        {
+            let points: Vec<(String, f64)> = self
+                .rows
+                .iter()
+                .take(MAX_CHART_POINTS)
+                .filter_map(|row| {
+                    cell_to_f64(&row[measure_index]).map(|value| (row[0].to_string(), value))
+                })
+                .collect();
+            if !points.is_empty() {
+                section = section.chart(BarChart::new(
+                    self.columns[measure_index].label.clone(),
+                    points,
+                ));
+            }
+        }
+
+        ReportDocument::new(self.title.clone(), self.generated_at)
+            .with_subtitle(format!(
+                "{} · {} · {}",
+                self.name,
+                self.dataset.label(),
+                self.period_label()
+            ))
+            .section(section)
+    }
+
+    /// CSV 변환
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        let header: Vec<String> = self.columns.iter().map(|c| csv_field(&c.label)).collect();
+        csv.push_str(&header.join(","));
+        csv.push('\n');
+
+        for row in self.rows.iter().chain(self.totals().iter()) {
+            let line: Vec<String> = row
+                .iter()
+                .map(|cell| csv_field(&cell.to_string()))
+                .collect();
+            csv.push_str(&line.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// JSON 변환 (행은 컬럼 키를 속성으로 갖는 객체)
+    pub fn to_json(&self) -> ErpResult<String> {
+        let rows: Vec<serde_json::Map<String, serde_json::Value>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                self.columns
+                    .iter()
+                    .zip(row)
+                    .map(|(column, cell)| (column.key.clone(), cell_to_json(cell)))
+                    .collect()
+            })
+            .collect();
+
+        let columns: Vec<serde_json::Value> = self
+            .columns
+            .iter()
+            .map(|c| serde_json::json!({ "key": c.key, "label": c.label, "type": c.kind }))
+            .collect();
+
+        let document = serde_json::json!({
+            "name": self.name,
+            "title": self.title,
+            "dataset": self.dataset,
+            "generated_at": self.generated_at,
+            "date_range": self.date_range.map(|(from, to)| serde_json::json!({ "from": from, "to": to })),
+            "columns": columns,
+            "rows": rows,
+        });
+
+        serde_json::to_string_pretty(&document)
+            .map_err(|e| ErpError::serialization(format!("JSON 직렬화 실패: {}", e)))
+    }
+}
+
+fn cell_to_f64(cell: &CellValue) -> Option<f64> {
+    match cell {
+        CellValue::Integer(i) => Some(*i as f64),
+        CellValue::Decimal(d) => d.to_f64(),
+        CellValue::Number(n) => Some(*n),
+        _ => None,
+    }
+}
+
+fn cell_to_json(cell: &CellValue) -> serde_json::Value {
+    match cell {
+        CellValue::Empty => serde_json::Value::Null,
+        CellValue::Integer(i) => serde_json::Value::from(*i),
+        CellValue::Number(n) => serde_json::Value::from(*n),
+        CellValue::Bool(b) => serde_json::Value::from(*b),
+        // Decimal은 정밀도 보존을 위해 문자열로 기록
+        other => serde_json::Value::from(other.to_string()),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// PostgreSQL 풀에서 사용자 정의 보고서를 실행
+pub struct CustomReportRunner {
+    pool: PgPool,
+    pii: Option<Arc<PiiCipher>>,
+}
+
+impl CustomReportRunner {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, pii: None }
+    }
+
+    /// 암호화된 고객 PII 컬럼 복호화에 사용할 암호화 설정
+    pub fn with_pii_cipher(mut self, cipher: Option<Arc<PiiCipher>>) -> Self {
+        self.pii = cipher;
+        self
+    }
+
+    /// 스펙 실행
+    pub async fn run(&self, spec: &CustomReportSpec) -> ErpResult<CustomReportResult> {
+        let query = spec.build_query()?;
+
+        let mut statement = sqlx::query(&query.sql);
+        for param in &query.params {
+            statement = match param {
+                QueryParam::Text(value) => statement.bind(value.clone()),
+                QueryParam::TextList(values) => statement.bind(values.clone()),
+            };
+        }
+
+        let records = statement.fetch_all(&self.pool).await.map_err(|e| {
+            ErpError::database(format!(
+                "사용자 정의 보고서 조회 실패 ({}): {}",
+                spec.name, e
+            ))
+        })?;
+
+        let mut rows = Vec::with_capacity(records.len());
+        for record in &records {
+            let mut row = Vec::with_capacity(query.columns.len());
+            for (index, column) in query.columns.iter().enumerate() {
+                let cell = match column.kind {
+                    FieldKind::Text if spec.is_encrypted_dimension(column) => {
+                        let stored = record.try_get::<Option<String>, _>(index).map_err(|e| {
+                            ErpError::database(format!("컬럼 '{}' 값 변환 실패: {}", column.key, e))
+                        })?;
+                        row.push(CellValue::from(self.decrypt(stored)?));
+                        continue;
+                    }
+                    FieldKind::Text => record
+                        .try_get::<Option<String>, _>(index)
+                        .map(CellValue::from),
+                    FieldKind::Integer => {
+                        record.try_get::<Option<i64>, _>(index).map(CellValue::from)
+                    }
+                    FieldKind::Decimal => record
+                        .try_get::<Option<Decimal>, _>(index)
+                        .map(CellValue::from),
+                    FieldKind::Date => record
+                        .try_get::<Option<NaiveDate>, _>(index)
+                        .map(CellValue::from),
+                    FieldKind::Bool => record
+                        .try_get::<Option<bool>, _>(index)
+                        .map(CellValue::from),
+                }
+                .map_err(|e| {
+                    ErpError::database(format!("컬럼 '{}' 값 변환 실패: {}", column.key, e))
+                })?;
+                row.push(cell);
+            }
+            rows.push(row);
+        }
+
+        Ok(CustomReportResult {
+            name: spec.name.clone(),
+            title: spec.display_title().to_string(),
+            dataset: spec.dataset,
+            generated_at: Utc::now(),
+            date_range: query.date_range,
+            columns: query.columns,
+            rows,
+        })
+    }
+
+    fn decrypt(&self, stored: Option<String>) -> ErpResult<Option<String>> {
+        match &self.pii {
+            Some(cipher) => cipher.decrypt_opt(stored),
+            None if stored.as_deref().is_some_and(is_encrypted) => Err(ErpError::config(
+                "암호화된 고객 정보가 있지만 PII 암호화 키가 설정되지 않았습니다",
+            )),
+            None => Ok(stored),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_SPEC: &str = r#"
+name: category_revenue
+title: 카테고리별 월 매출
+dataset: order_items
+dimensions: [order_month, category]
+measures:
+  - function: sum
+    field: total_price
+    label: 매출
+  - function: count
+filters:
+  - field: order_status
+    op: in
+    value: [delivered, shipped]
+  - field: quantity
+    op: gte
+    value: 2
+date_range:
+  from: 2024-01-01
+  to: 2024-12-31
+order_by:
+  - column: 매출
+    descending: true
+limit: 50
+"#;
+
+    fn sample_result() -> CustomReportResult {
+        CustomReportResult {
+            name: "by_category".to_string(),
+            title: "카테고리별 매출".to_string(),
+            dataset: CustomDataset::OrderItems,
+            generated_at: Utc::now(),
+            date_range: None,
+            columns: vec![
+                CustomColumn {
+                    key: "category".to_string(),
+                    label: "카테고리".to_string(),
+                    kind: FieldKind::Text,
+                    role: ColumnRole::Dimension,
+                },
+                CustomColumn {
+                    key: "sum_total_price".to_string(),
+                    label: "매출".to_string(),
+                    kind: FieldKind::Decimal,
+                    role: ColumnRole::Measure(Aggregation::Sum),
+                },
+                CustomColumn {
+                    key: "avg_unit_price".to_string(),
+                    label: "평균 단가".to_string(),
+                    kind: FieldKind::Decimal,
+                    role: ColumnRole::Measure(Aggregation::Avg),
+                },
+                CustomColumn {
+                    key: "count".to_string(),
+                    label: "건수".to_string(),
+                    kind: FieldKind::Integer,
+                    role: ColumnRole::Measure(Aggregation::Count),
+                },
+            ],
+            rows: vec![
+                vec![
+                    "전자, 기기".into(),
+                    Decimal::new(150050, 2).into(),
+                    Decimal::new(5000, 2).into(),
+                    3i64.into(),
+                ],
+                vec![
+                    "가구".into(),
+                    Decimal::new(49950, 2).into(),
+                    Decimal::new(2500, 2).into(),
+                    2i64.into(),
+                ],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_parse_and_build_grouped_query() {
+        let spec = CustomReportSpec::from_yaml(SAMPLE_SPEC).unwrap();
+        assert_eq!(spec.dataset, CustomDataset::OrderItems);
+        assert_eq!(spec.display_title(), "카테고리별 월 매출");
+
+        let query = spec.build_query().unwrap();
+        assert!(query.sql.starts_with(
+            "SELECT to_char(so.order_date, 'YYYY-MM') AS c0, p.category AS c1, \
+             COALESCE(SUM(soi.total_price), 0)::numeric AS c2, COUNT(*)::int8 AS c3 FROM"
+        ));
+        assert!(query
+            .sql
+            .contains("WHERE so.status::text = ANY($1::text[]::text[]) AND soi.quantity::int8 >= $2::int8 AND so.order_date::date BETWEEN $3::date AND $4::date"));
+        assert!(query
+            .sql
+            .ends_with("GROUP BY 1, 2 ORDER BY c2 DESC NULLS LAST LIMIT 50"));
+        assert_eq!(
+            query.params,
+            vec![
+                QueryParam::TextList(vec!["delivered".to_string(), "shipped".to_string()]),
+                QueryParam::Text("2".to_string()),
+                QueryParam::Text("2024-01-01".to_string()),
+                QueryParam::Text("2024-12-31".to_string()),
+            ]
+        );
+        assert_eq!(query.columns.len(), 4);
+        assert_eq!(query.columns[2].label, "매출");
+        assert_eq!(query.columns[3].label, "건수");
+    }
+
+    #[test]
+    fn test_filter_values_are_never_inlined() {
+        let spec = CustomReportSpec::from_yaml(
+            r#"
+name: injection
+dataset: customers
+dimensions: [name]
+filters:
+  - field: name
+    op: contains
+    value: "'; DROP TABLE customers; --"
+"#,
+        )
+        .unwrap();
+
+        let query = spec.build_query().unwrap();
+        assert!(!query.sql.contains("DROP TABLE"));
+        assert!(query
+            .sql
+            .contains("strpos(lower(c.name), lower($1::text)) > 0"));
+        assert!(query.sql.ends_with("ORDER BY 1 LIMIT 10000"));
+    }
+
+    #[test]
+    fn test_spec_validation_errors() {
+        let base = CustomReportSpec {
+            name: "test".to_string(),
+            title: None,
+            dataset: CustomDataset::Products,
+            dimensions: vec!["category".to_string()],
+            measures: vec![],
+            filters: vec![],
+            date_range: None,
+            order_by: vec![],
+            limit: None,
+        };
+        assert!(base.build_query().is_ok());
+
+        // 카탈로그에 없는 필드는 식별자로 사용될 수 없음
+        let mut spec = base.clone();
+        spec.dimensions = vec!["category; DROP TABLE products".to_string()];
+        assert!(spec.build_query().is_err());
+
+        // 문자열 필드 합계 불가
+        let mut spec = base.clone();
+        spec.measures = vec![MeasureSpec {
+            function: Aggregation::Sum,
+            field: Some("name".to_string()),
+            label: None,
+        }];
+        assert!(spec.build_query().is_err());
+
+        // 타입이 맞지 않는 필터 값
+        let mut spec = base.clone();
+        spec.filters = vec![FilterSpec {
+            field: "quantity".to_string(),
+            op: FilterOp::Lt,
+            value: serde_json::json!("ten"),
+        }];
+        assert!(spec.build_query().is_err());
+
+        // 숫자 필드에 contains 불가
+        let mut spec = base.clone();
+        spec.filters = vec![FilterSpec {
+            field: "price".to_string(),
+            op: FilterOp::Contains,
+            value: serde_json::json!("1"),
+        }];
+        assert!(spec.build_query().is_err());
+
+        // 최대 행 수 초과
+        let mut spec = base.clone();
+        spec.limit = Some(MAX_CUSTOM_REPORT_ROWS + 1);
+        assert!(spec.build_query().is_err());
+
+        // period와 from/to 동시 사용 불가
+        let mut spec = base.clone();
+        spec.date_range = Some(DateRangeSpec {
+            from: NaiveDate::from_ymd_opt(2024, 1, 1),
+            to: None,
+            period: Some("monthly".to_string()),
+        });
+        assert!(spec.build_query().is_err());
+
+        // 결과에 없는 정렬 컬럼
+        let mut spec = base;
+        spec.order_by = vec![OrderBySpec {
+            column: "price".to_string(),
+            descending: false,
+        }];
+        assert!(spec.build_query().is_err());
+    }
+
+    #[test]
+    fn test_encrypted_fields_cannot_be_filtered_or_sorted() {
+        let spec = CustomReportSpec::from_yaml(
+            r#"
+name: contacts
+dataset: customers
+dimensions: [name, email]
+"#,
+        )
+        .unwrap();
+        assert!(spec.uses_encrypted_fields());
+        let query = spec.build_query().unwrap();
+        assert!(!spec.is_encrypted_dimension(&query.columns[0]));
+        assert!(spec.is_encrypted_dimension(&query.columns[1]));
+
+        let mut filtered = spec.clone();
+        filtered.filters = vec![FilterSpec {
+            field: "email".to_string(),
+            op: FilterOp::Contains,
+            value: serde_json::json!("@example.com"),
+        }];
+        assert!(filtered.build_query().is_err());
+
+        let mut sorted = spec.clone();
+        sorted.order_by = vec![OrderBySpec {
+            column: "email".to_string(),
+            descending: false,
+        }];
+        assert!(sorted.build_query().is_err());
+
+        // 측정값에는 사용할 수 있음 (암호문도 NULL 여부는 동일)
+        let mut counted = spec;
+        counted.dimensions = vec!["customer_type".to_string()];
+        counted.measures = vec![MeasureSpec {
+            function: Aggregation::Count,
+            field: Some("email".to_string()),
+            label: None,
+        }];
+        assert!(!counted.uses_encrypted_fields());
+        assert!(counted.build_query().is_ok());
+    }
+
+    #[test]
+    fn test_totals_and_csv() {
+        let result = sample_result();
+        let totals = result.totals().unwrap();
+        assert_eq!(totals[0], CellValue::from("합계"));
+        assert_eq!(totals[1], CellValue::Decimal(Decimal::new(200000, 2)));
+        assert_eq!(totals[2], CellValue::Empty);
+        assert_eq!(totals[3], CellValue::Integer(5));
+
+        let csv = result.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "카테고리,매출,평균 단가,건수");
+        assert_eq!(lines[1], "\"전자, 기기\",1500.50,50.00,3");
+        assert_eq!(lines[3], "합계,2000.00,,5");
+    }
+
+    #[test]
+    fn test_json_and_document() {
+        let result = sample_result();
+        let json: serde_json::Value = serde_json::from_str(&result.to_json().unwrap()).unwrap();
+        assert_eq!(json["dataset"], "order_items");
+        assert_eq!(json["rows"][0]["sum_total_price"], "1500.50");
+        assert_eq!(json["rows"][1]["count"], 2);
+
+        let document = result.to_document();
+        let section = &document.sections[0];
+        let table = section.table.as_ref().unwrap();
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.numeric_columns, vec![1, 2, 3]);
+        assert!(table.totals.is_some());
+        assert_eq!(section.chart.as_ref().unwrap().points.len(), 2);
+    }
+}
@@ -1,12 +1,21 @@
+pub mod custom;
 pub mod models;
 pub mod repository;
+pub mod schedule;
 pub mod service;
+pub mod templates;
 
+pub use custom::{CustomReportResult, CustomReportRunner, CustomReportSpec};
 pub use models::*;
-pub use repository::{MockReportsRepository, ReportsRepository};
+pub use repository::{MockReportsRepository, ReportsRepository, SqliteReportsRepository};
+pub use schedule::{
+    MockReportScheduleRepository, PostgresReportScheduleRepository, ReportRun, ReportRunStatus,
+    ReportSchedule, ReportScheduleRepository, ReportScheduler,
+};
 pub use service::ReportsService;
 
-use sqlx::PgPool;
+use crate::modules::customers::CustomerRepository;
+use sqlx::{PgPool, SqlitePool};
 use std::sync::Arc;
 
 /// 보고서 모듈 초기화 및 종속성 설정
@@ -25,6 +34,14 @@ pub fn create_reports_service(pool: Option<PgPool>) -> ReportsService {
     }
 }
 
+/// SQLite 데이터베이스 기반 보고서 서비스
+pub fn create_sqlite_reports_service(
+    pool: SqlitePool,
+    customers: Arc<dyn CustomerRepository>,
+) -> ReportsService {
+    ReportsService::new(Arc::new(SqliteReportsRepository::new(pool, customers)))
+}
+
 /// 보고서 모듈의 초기 설정 및 검증
 pub async fn initialize_reports_module(
     pool: Option<&PgPool>,
@@ -112,6 +129,7 @@ mod tests {
         assert_eq!("json".parse::<ReportFormat>().unwrap(), ReportFormat::Json);
         assert_eq!("csv".parse::<ReportFormat>().unwrap(), ReportFormat::Csv);
         assert_eq!("html".parse::<ReportFormat>().unwrap(), ReportFormat::Html);
+        assert_eq!("xlsx".parse::<ReportFormat>().unwrap(), ReportFormat::Xlsx);
         assert!("invalid".parse::<ReportFormat>().is_err());
     }
 
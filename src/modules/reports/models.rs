@@ -2,6 +2,8 @@ use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
+use crate::modules::customers::segmentation::CustomerRfmScore;
+
 /// 보고서 생성을 위한 기간 정의
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ReportPeriod {
@@ -21,6 +23,7 @@ pub enum ReportFormat {
     Csv,
     Html,
     Pdf,
+    Xlsx,
 }
 
 impl std::fmt::Display for ReportFormat {
@@ -31,6 +34,7 @@ impl std::fmt::Display for ReportFormat {
             ReportFormat::Csv => write!(f, "csv"),
             ReportFormat::Html => write!(f, "html"),
             ReportFormat::Pdf => write!(f, "pdf"),
+            ReportFormat::Xlsx => write!(f, "xlsx"),
         }
     }
 }
@@ -140,6 +144,9 @@ pub struct CustomerAnalysisReport {
     pub customer_segments: Vec<CustomerSegment>,
     pub geographic_distribution: Vec<GeographicDistribution>,
     pub customer_lifecycle: CustomerLifecycleMetrics,
+    /// 고객별 RFM 점수 및 이탈 예측
+    #[serde(default)]
+    pub rfm_scores: Vec<CustomerRfmScore>,
 }
 
 /// 상위 고객 정보
@@ -161,6 +168,8 @@ pub struct CustomerSegment {
     pub customer_count: u32,
     pub total_revenue: Decimal,
     pub average_order_frequency: Decimal,
+    #[serde(default)]
+    pub churn_predicted_count: u32,
 }
 
 /// 지리적 분포 정보
@@ -270,7 +279,7 @@ pub struct FinancialRatios {
 }
 
 /// 보고서 생성 요청
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportRequest {
     pub report_type: ReportType,
     pub period: ReportPeriod,
@@ -281,7 +290,7 @@ pub struct ReportRequest {
 }
 
 /// 보고서 타입
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ReportType {
     SalesSummary,
     InventoryStatus,
@@ -290,7 +299,7 @@ pub enum ReportType {
 }
 
 /// 보고서 필터
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ReportFilters {
     pub customer_ids: Option<Vec<uuid::Uuid>>,
     pub product_ids: Option<Vec<uuid::Uuid>>,
@@ -302,6 +311,62 @@ pub struct ReportFilters {
     pub include_inactive: bool,
 }
 
+impl std::fmt::Display for ReportPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportPeriod::Daily => write!(f, "daily"),
+            ReportPeriod::Weekly => write!(f, "weekly"),
+            ReportPeriod::Monthly => write!(f, "monthly"),
+            ReportPeriod::Quarterly => write!(f, "quarterly"),
+            ReportPeriod::Yearly => write!(f, "yearly"),
+            ReportPeriod::Custom { from, to } => write!(f, "{} ~ {}", from, to),
+        }
+    }
+}
+
+impl std::fmt::Display for ReportType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportType::SalesSummary => write!(f, "sales-summary"),
+            ReportType::InventoryStatus => write!(f, "inventory-status"),
+            ReportType::CustomerAnalysis { .. } => write!(f, "customer-analysis"),
+            ReportType::FinancialOverview => write!(f, "financial-overview"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportType {
+    type Err = crate::utils::error::ErpError;
+
+    /// 고객 분석의 기간은 기본 12개월로 설정됩니다
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "sales-summary" => Ok(ReportType::SalesSummary),
+            "inventory-status" => Ok(ReportType::InventoryStatus),
+            "customer-analysis" => Ok(ReportType::CustomerAnalysis { months: 12 }),
+            "financial-overview" => Ok(ReportType::FinancialOverview),
+            _ => Err(crate::utils::error::ErpError::validation(
+                "report",
+                "지원되지 않는 보고서입니다. 사용 가능한 보고서: sales-summary, inventory-status, customer-analysis, financial-overview",
+            )),
+        }
+    }
+}
+
+impl ReportFormat {
+    /// 파일 확장자
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ReportFormat::Json => "json",
+            ReportFormat::Csv => "csv",
+            ReportFormat::Html => "html",
+            ReportFormat::Pdf => "pdf",
+            ReportFormat::Xlsx => "xlsx",
+            ReportFormat::Console => "txt",
+        }
+    }
+}
+
 impl ReportPeriod {
     /// 기간을 시작일과 종료일로 변환
     pub fn to_date_range(&self) -> (NaiveDate, NaiveDate) {
@@ -339,9 +404,10 @@ impl std::str::FromStr for ReportFormat {
             "csv" => Ok(ReportFormat::Csv),
             "html" => Ok(ReportFormat::Html),
             "pdf" => Ok(ReportFormat::Pdf),
+            "xlsx" | "excel" => Ok(ReportFormat::Xlsx),
             _ => Err(crate::utils::error::ErpError::validation(
                 "format",
-                "지원되지 않는 보고서 형식입니다. 사용 가능한 형식: console, json, csv, html, pdf",
+                "지원되지 않는 보고서 형식입니다. 사용 가능한 형식: console, json, csv, html, pdf, xlsx",
             )),
         }
     }
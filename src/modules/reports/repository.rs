@@ -1,12 +1,21 @@
 use async_trait::async_trait;
-use chrono::{NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
+use shaku::Interface;
+use sqlx::{Row, Sqlite, SqlitePool};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
 
 use super::models::*;
+use crate::core::database::models::product::StockMovementType;
+use crate::core::database::{sqlite, ReadRouter};
+use crate::modules::customers::segmentation::CustomerOrderMetrics;
+use crate::modules::customers::CustomerRepository;
 use crate::utils::error::ErpResult;
 
 #[async_trait]
-pub trait ReportsRepository: Send + Sync {
+pub trait ReportsRepository: Interface {
     // 매출 요약 관련
     async fn get_sales_summary(
         &self,
@@ -65,6 +74,9 @@ pub trait ReportsRepository: Send + Sync {
         months: u32,
     ) -> ErpResult<CustomerLifecycleMetrics>;
 
+    /// RFM 점수 계산을 위한 고객별 주문 집계
+    async fn get_customer_order_metrics(&self) -> ErpResult<Vec<CustomerOrderMetrics>>;
+
     // 재무 개요 관련
     async fn get_financial_overview(
         &self,
@@ -464,6 +476,7 @@ impl ReportsRepository for PostgresReportsRepository {
             customer_segments,
             geographic_distribution,
             customer_lifecycle,
+            rfm_scores: Vec::new(),
         })
     }
 
@@ -541,6 +554,7 @@ impl ReportsRepository for PostgresReportsRepository {
                 customer_count: row.customer_count.unwrap_or(0) as u32,
                 total_revenue: row.total_revenue.unwrap_or(Decimal::ZERO),
                 average_order_frequency: row.avg_frequency.unwrap_or(0.0).into(),
+                churn_predicted_count: 0,
             });
         }
 
@@ -627,6 +641,35 @@ impl ReportsRepository for PostgresReportsRepository {
         })
     }
 
+    async fn get_customer_order_metrics(&self) -> ErpResult<Vec<CustomerOrderMetrics>> {
+        let rows = sqlx::query!(
+            "SELECT c.id, c.name, c.email,
+                    MIN(so.order_date)::date as first_order_date,
+                    MAX(so.order_date)::date as last_order_date,
+                    COUNT(so.id) as order_count,
+                    COALESCE(SUM(so.total_amount), 0) as total_spent
+             FROM customers c
+             LEFT JOIN sales_orders so ON c.id = so.customer_id
+                  AND so.status NOT IN ('cancelled', 'returned', 'draft')
+             GROUP BY c.id, c.name, c.email"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| CustomerOrderMetrics {
+                customer_id: row.id,
+                name: row.name,
+                email: row.email,
+                first_order_date: row.first_order_date,
+                last_order_date: row.last_order_date,
+                order_count: row.order_count.unwrap_or(0) as u32,
+                total_spent: row.total_spent.unwrap_or(Decimal::ZERO),
+            })
+            .collect())
+    }
+
     async fn get_financial_overview(
         &self,
         start_date: NaiveDate,
@@ -846,6 +889,881 @@ impl ReportsRepository for PostgresReportsRepository {
 }
 */
 
+/// SQLite 보고서 저장소
+///
+/// 금액이 decimal 텍스트로 저장되므로 집계는 Rust에서 수행한다. 고객 이름과
+/// 이메일은 PII 암호화를 처리하는 고객 저장소를 통해 조회한다. 모든 조회는
+/// 읽기 복제본이 설정되어 있으면 복제본으로 보낸다.
+pub struct SqliteReportsRepository {
+    reads: ReadRouter<Sqlite>,
+    customers: Arc<dyn CustomerRepository>,
+}
+
+struct OrderFacts {
+    id: Uuid,
+    customer_id: Uuid,
+    order_date: DateTime<Utc>,
+    status: String,
+    payment_status: String,
+    payment_method: Option<String>,
+    total_amount: Decimal,
+}
+
+struct ItemFacts {
+    order_id: Uuid,
+    product_id: Uuid,
+    quantity: i32,
+    unit_price: Decimal,
+}
+
+struct ProductFacts {
+    id: Uuid,
+    name: String,
+    sku: String,
+    category: String,
+    price: Decimal,
+    quantity: i32,
+    min_stock_level: i32,
+    max_stock_level: Option<i32>,
+    status: String,
+    updated_at: DateTime<Utc>,
+}
+
+fn in_range(at: DateTime<Utc>, start_date: NaiveDate, end_date: NaiveDate) -> bool {
+    let date = at.date_naive();
+    date >= start_date && date <= end_date
+}
+
+fn percentage(part: Decimal, total: Decimal) -> Decimal {
+    if total > Decimal::ZERO {
+        (part * Decimal::from(100)) / total
+    } else {
+        Decimal::ZERO
+    }
+}
+
+fn months_ago(months: u32) -> NaiveDate {
+    Utc::now().date_naive() - chrono::Duration::days((months * 30) as i64)
+}
+
+impl SqliteReportsRepository {
+    pub fn new(pool: SqlitePool, customers: Arc<dyn CustomerRepository>) -> Self {
+        Self {
+            reads: ReadRouter::primary_only(pool),
+            customers,
+        }
+    }
+
+    /// 보고서 조회를 읽기 복제본으로 라우팅
+    pub fn with_read_router(mut self, reads: ReadRouter<Sqlite>) -> Self {
+        self.reads = reads;
+        self
+    }
+
+    async fn load_orders(&self) -> ErpResult<Vec<OrderFacts>> {
+        let rows = sqlx::query(
+            "SELECT id, customer_id, order_date, status, payment_status, payment_method, total_amount \
+             FROM sales_orders ORDER BY order_date",
+        )
+        .fetch_all(&self.reads.pool().await)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(OrderFacts {
+                    id: row.try_get("id")?,
+                    customer_id: row.try_get("customer_id")?,
+                    order_date: row.try_get("order_date")?,
+                    status: row.try_get("status")?,
+                    payment_status: row.try_get("payment_status")?,
+                    payment_method: row.try_get("payment_method")?,
+                    total_amount: sqlite::decimal(row, "total_amount")?,
+                })
+            })
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(Into::into)
+    }
+
+    async fn load_orders_between(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> ErpResult<Vec<OrderFacts>> {
+        let mut orders = self.load_orders().await?;
+        orders.retain(|order| in_range(order.order_date, start_date, end_date));
+        Ok(orders)
+    }
+
+    async fn load_items(&self) -> ErpResult<Vec<ItemFacts>> {
+        let rows =
+            sqlx::query("SELECT order_id, product_id, quantity, unit_price FROM sales_order_items")
+                .fetch_all(&self.reads.pool().await)
+                .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ItemFacts {
+                    order_id: row.try_get("order_id")?,
+                    product_id: row.try_get("product_id")?,
+                    quantity: row.try_get("quantity")?,
+                    unit_price: sqlite::decimal(row, "unit_price")?,
+                })
+            })
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(Into::into)
+    }
+
+    /// 단종 제품을 제외한 제품 목록
+    async fn load_products(&self) -> ErpResult<Vec<ProductFacts>> {
+        let rows = sqlx::query(
+            "SELECT id, name, sku, category, price, quantity, min_stock_level, max_stock_level, \
+                    status, updated_at \
+             FROM products WHERE status != 'discontinued' ORDER BY name",
+        )
+        .fetch_all(&self.reads.pool().await)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ProductFacts {
+                    id: row.try_get("id")?,
+                    name: row.try_get("name")?,
+                    sku: row.try_get("sku")?,
+                    category: row.try_get("category")?,
+                    price: sqlite::decimal(row, "price")?,
+                    quantity: row.try_get("quantity")?,
+                    min_stock_level: row.try_get("min_stock_level")?,
+                    max_stock_level: row.try_get("max_stock_level")?,
+                    status: row.try_get("status")?,
+                    updated_at: row.try_get("updated_at")?,
+                })
+            })
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(Into::into)
+    }
+
+    async fn customer_created_dates(&self) -> ErpResult<Vec<(Uuid, String, NaiveDate)>> {
+        let rows = sqlx::query("SELECT id, customer_type, created_at FROM customers")
+            .fetch_all(&self.reads.pool().await)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok((
+                    row.try_get("id")?,
+                    row.try_get("customer_type")?,
+                    row.try_get::<DateTime<Utc>, _>("created_at")?.date_naive(),
+                ))
+            })
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(Into::into)
+    }
+
+    /// 기간 내 매출 (배송 완료 주문 기준)
+    fn recognized_revenue(orders: &[OrderFacts]) -> Decimal {
+        orders
+            .iter()
+            .filter(|order| order.status == "delivered")
+            .map(|order| order.total_amount)
+            .sum()
+    }
+}
+
+#[async_trait]
+impl ReportsRepository for SqliteReportsRepository {
+    async fn get_sales_summary(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> ErpResult<SalesSummaryReport> {
+        let orders = self.load_orders_between(start_date, end_date).await?;
+        let order_ids: HashSet<Uuid> = orders.iter().map(|order| order.id).collect();
+        let total_items_sold: i64 = self
+            .load_items()
+            .await?
+            .iter()
+            .filter(|item| order_ids.contains(&item.order_id))
+            .map(|item| item.quantity as i64)
+            .sum();
+
+        let total_orders = orders.len() as u32;
+        let total_revenue = Self::recognized_revenue(&orders);
+        let average_order_value = if total_orders > 0 {
+            total_revenue / Decimal::from(total_orders)
+        } else {
+            Decimal::ZERO
+        };
+
+        Ok(SalesSummaryReport {
+            period: ReportPeriod::Custom {
+                from: start_date,
+                to: end_date,
+            },
+            generated_at: Utc::now(),
+            total_orders,
+            total_revenue,
+            total_items_sold: total_items_sold as u32,
+            average_order_value,
+            top_selling_products: self
+                .get_top_selling_products(start_date, end_date, 10)
+                .await?,
+            sales_by_status: self.get_sales_by_status(start_date, end_date).await?,
+            daily_sales: self.get_daily_sales(start_date, end_date).await?,
+        })
+    }
+
+    async fn get_top_selling_products(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        limit: u32,
+    ) -> ErpResult<Vec<TopSellingProduct>> {
+        let order_ids: HashSet<Uuid> = self
+            .load_orders_between(start_date, end_date)
+            .await?
+            .iter()
+            .map(|order| order.id)
+            .collect();
+
+        let mut sold: HashMap<Uuid, (u32, Decimal)> = HashMap::new();
+        for item in self.load_items().await? {
+            if order_ids.contains(&item.order_id) {
+                let entry = sold.entry(item.product_id).or_default();
+                entry.0 += item.quantity.max(0) as u32;
+                entry.1 += item.unit_price * Decimal::from(item.quantity);
+            }
+        }
+
+        let rows = sqlx::query("SELECT id, name, sku FROM products")
+            .fetch_all(&self.reads.pool().await)
+            .await?;
+        let mut products = Vec::new();
+        for row in rows {
+            let product_id: Uuid = row.try_get("id")?;
+            if let Some((quantity_sold, total_revenue)) = sold.get(&product_id) {
+                products.push(TopSellingProduct {
+                    product_id,
+                    name: row.try_get("name")?,
+                    sku: row.try_get("sku")?,
+                    quantity_sold: *quantity_sold,
+                    total_revenue: *total_revenue,
+                });
+            }
+        }
+
+        products.sort_by_key(|entry| std::cmp::Reverse(entry.quantity_sold));
+        products.truncate(limit as usize);
+        Ok(products)
+    }
+
+    async fn get_sales_by_status(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> ErpResult<Vec<SalesByStatus>> {
+        let mut sales_by_status: Vec<SalesByStatus> = Vec::new();
+        for order in self.load_orders_between(start_date, end_date).await? {
+            match sales_by_status
+                .iter_mut()
+                .find(|entry| entry.status == order.status)
+            {
+                Some(entry) => {
+                    entry.order_count += 1;
+                    entry.total_amount += order.total_amount;
+                }
+                None => sales_by_status.push(SalesByStatus {
+                    status: order.status,
+                    order_count: 1,
+                    total_amount: order.total_amount,
+                }),
+            }
+        }
+
+        sales_by_status.sort_by_key(|entry| std::cmp::Reverse(entry.order_count));
+        Ok(sales_by_status)
+    }
+
+    async fn get_daily_sales(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> ErpResult<Vec<DailySales>> {
+        let mut daily: BTreeMap<NaiveDate, (u32, Decimal)> = BTreeMap::new();
+        for order in self.load_orders_between(start_date, end_date).await? {
+            let entry = daily.entry(order.order_date.date_naive()).or_default();
+            entry.0 += 1;
+            entry.1 += order.total_amount;
+        }
+
+        Ok(daily
+            .into_iter()
+            .map(|(date, (order_count, total_amount))| DailySales {
+                date,
+                order_count,
+                total_amount,
+            })
+            .collect())
+    }
+
+    async fn get_inventory_status(&self) -> ErpResult<InventoryStatusReport> {
+        let products = self.load_products().await?;
+        let total_stock_value = products
+            .iter()
+            .map(|product| Decimal::from(product.quantity) * product.price)
+            .sum();
+
+        // 최근 30일간의 재고 이동
+        let end_date = Utc::now().date_naive();
+        let start_date = end_date - chrono::Duration::days(30);
+
+        Ok(InventoryStatusReport {
+            generated_at: Utc::now(),
+            total_products: products.len() as u32,
+            total_stock_value,
+            low_stock_items: self.get_low_stock_items(1.0).await?,
+            out_of_stock_items: self.get_out_of_stock_items().await?,
+            inventory_by_category: self.get_inventory_by_category().await?,
+            stock_movements: self.get_stock_movements(start_date, end_date).await?,
+        })
+    }
+
+    async fn get_low_stock_items(&self, threshold_multiplier: f64) -> ErpResult<Vec<LowStockItem>> {
+        let mut products: Vec<ProductFacts> = self
+            .load_products()
+            .await?
+            .into_iter()
+            .filter(|product| {
+                product.quantity > 0
+                    && product.quantity as f64
+                        <= product.min_stock_level as f64 * threshold_multiplier
+            })
+            .collect();
+
+        // 재주문 기준 대비 재고 비율이 낮은 순
+        products.sort_by(|a, b| {
+            let ratio = |p: &ProductFacts| p.quantity as f64 / p.min_stock_level.max(1) as f64;
+            ratio(a).total_cmp(&ratio(b))
+        });
+
+        Ok(products
+            .into_iter()
+            .map(|product| {
+                let target = product
+                    .max_stock_level
+                    .unwrap_or(product.min_stock_level * 2);
+                LowStockItem {
+                    product_id: product.id,
+                    name: product.name,
+                    sku: product.sku,
+                    current_stock: product.quantity as u32,
+                    reorder_level: product.min_stock_level.max(0) as u32,
+                    suggested_reorder_quantity: (target - product.quantity).max(0) as u32,
+                    stock_value: Decimal::from(product.quantity) * product.price,
+                }
+            })
+            .collect())
+    }
+
+    async fn get_out_of_stock_items(&self) -> ErpResult<Vec<OutOfStockItem>> {
+        let products = self.load_products().await?;
+        let open_orders: HashSet<Uuid> = self
+            .load_orders()
+            .await?
+            .into_iter()
+            .filter(|order| matches!(order.status.as_str(), "draft" | "pending" | "confirmed"))
+            .map(|order| order.id)
+            .collect();
+
+        let mut pending: HashMap<Uuid, i64> = HashMap::new();
+        for item in self.load_items().await? {
+            if open_orders.contains(&item.order_id) {
+                *pending.entry(item.product_id).or_default() += item.quantity as i64;
+            }
+        }
+
+        Ok(products
+            .into_iter()
+            .filter(|product| product.quantity <= 0 || product.status == "outofstock")
+            .map(|product| OutOfStockItem {
+                pending_orders: pending.get(&product.id).copied().unwrap_or(0) as u32,
+                product_id: product.id,
+                name: product.name,
+                sku: product.sku,
+                last_stock_date: Some(product.updated_at.date_naive()),
+            })
+            .collect())
+    }
+
+    async fn get_inventory_by_category(&self) -> ErpResult<Vec<InventoryByCategory>> {
+        let mut categories: Vec<InventoryByCategory> = Vec::new();
+        for product in self.load_products().await? {
+            let value = Decimal::from(product.quantity) * product.price;
+            match categories
+                .iter_mut()
+                .find(|entry| entry.category == product.category)
+            {
+                Some(entry) => {
+                    entry.product_count += 1;
+                    entry.total_stock += product.quantity.max(0) as u32;
+                    entry.total_value += value;
+                }
+                None => categories.push(InventoryByCategory {
+                    category: product.category,
+                    product_count: 1,
+                    total_stock: product.quantity.max(0) as u32,
+                    total_value: value,
+                    average_stock_per_product: Decimal::ZERO,
+                }),
+            }
+        }
+
+        for entry in &mut categories {
+            entry.average_stock_per_product =
+                Decimal::from(entry.total_stock) / Decimal::from(entry.product_count);
+        }
+        categories.sort_by_key(|entry| std::cmp::Reverse(entry.total_value));
+        Ok(categories)
+    }
+
+    async fn get_stock_movements(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> ErpResult<Vec<StockMovement>> {
+        let prices: HashMap<Uuid, Decimal> = self
+            .load_products()
+            .await?
+            .into_iter()
+            .map(|product| (product.id, product.price))
+            .collect();
+
+        let rows = sqlx::query(
+            "SELECT product_id, movement_type, quantity, created_at FROM stock_movements",
+        )
+        .fetch_all(&self.reads.pool().await)
+        .await?;
+
+        let mut movements: BTreeMap<(NaiveDate, String), (i32, Decimal)> = BTreeMap::new();
+        for row in rows {
+            let created_at: DateTime<Utc> = row.try_get("created_at")?;
+            if !in_range(created_at, start_date, end_date) {
+                continue;
+            }
+
+            let product_id: Uuid = row.try_get("product_id")?;
+            let movement_type: StockMovementType = row.try_get("movement_type")?;
+            let mut quantity: i32 = row.try_get("quantity")?;
+            if movement_type == StockMovementType::Out {
+                quantity = -quantity;
+            }
+
+            let price = prices.get(&product_id).copied().unwrap_or_default();
+            let entry = movements
+                .entry((created_at.date_naive(), format!("{:?}", movement_type)))
+                .or_default();
+            entry.0 += quantity;
+            entry.1 += Decimal::from(quantity) * price;
+        }
+
+        Ok(movements
+            .into_iter()
+            .map(
+                |((date, movement_type), (total_quantity, total_value))| StockMovement {
+                    date,
+                    movement_type,
+                    total_quantity,
+                    total_value,
+                },
+            )
+            .collect())
+    }
+
+    async fn get_customer_analysis(&self, months: u32) -> ErpResult<CustomerAnalysisReport> {
+        let start_date = months_ago(months);
+        let customers = self.customer_created_dates().await?;
+        let active_customers: HashSet<Uuid> = self
+            .load_orders()
+            .await?
+            .iter()
+            .filter(|order| order.order_date.date_naive() >= start_date)
+            .map(|order| order.customer_id)
+            .collect();
+
+        Ok(CustomerAnalysisReport {
+            analysis_period_months: months,
+            generated_at: Utc::now(),
+            total_customers: customers.len() as u32,
+            active_customers: active_customers.len() as u32,
+            new_customers: customers
+                .iter()
+                .filter(|(_, _, created)| *created >= start_date)
+                .count() as u32,
+            top_customers: self.get_top_customers(months, 10).await?,
+            customer_segments: self.get_customer_segments(months).await?,
+            geographic_distribution: self.get_geographic_distribution(months).await?,
+            customer_lifecycle: self.get_customer_lifecycle_metrics(months).await?,
+            rfm_scores: Vec::new(),
+        })
+    }
+
+    async fn get_top_customers(&self, months: u32, limit: u32) -> ErpResult<Vec<TopCustomer>> {
+        let start_date = months_ago(months);
+
+        let mut totals: HashMap<Uuid, (u32, Decimal, NaiveDate)> = HashMap::new();
+        for order in self.load_orders().await? {
+            let date = order.order_date.date_naive();
+            if date < start_date {
+                continue;
+            }
+            let entry = totals
+                .entry(order.customer_id)
+                .or_insert((0, Decimal::ZERO, date));
+            entry.0 += 1;
+            entry.1 += order.total_amount;
+            entry.2 = entry.2.max(date);
+        }
+
+        let mut top_customers: Vec<TopCustomer> = self
+            .customers
+            .get_customer_order_metrics()
+            .await?
+            .into_iter()
+            .filter_map(|customer| {
+                let (total_orders, total_spent, last_order) = totals.get(&customer.customer_id)?;
+                Some(TopCustomer {
+                    customer_id: customer.customer_id,
+                    name: customer.name,
+                    email: customer.email,
+                    total_orders: *total_orders,
+                    total_spent: *total_spent,
+                    average_order_value: *total_spent / Decimal::from(*total_orders),
+                    last_order_date: Some(*last_order),
+                })
+            })
+            .collect();
+
+        top_customers.sort_by_key(|entry| std::cmp::Reverse(entry.total_spent));
+        top_customers.truncate(limit as usize);
+        Ok(top_customers)
+    }
+
+    async fn get_customer_segments(&self, months: u32) -> ErpResult<Vec<CustomerSegment>> {
+        // 간단한 세그멘테이션: 고객 유형별
+        let start_date = months_ago(months);
+
+        let mut orders_by_customer: HashMap<Uuid, (u32, Decimal)> = HashMap::new();
+        for order in self.load_orders().await? {
+            if order.order_date.date_naive() >= start_date {
+                let entry = orders_by_customer.entry(order.customer_id).or_default();
+                entry.0 += 1;
+                entry.1 += order.total_amount;
+            }
+        }
+
+        // (고객 수, 매출, 주문 고객 수, 주문 수)
+        let mut segments: BTreeMap<String, (u32, Decimal, u32, u32)> = BTreeMap::new();
+        for (customer_id, customer_type, _) in self.customer_created_dates().await? {
+            let entry = segments.entry(customer_type).or_default();
+            entry.0 += 1;
+            if let Some((order_count, revenue)) = orders_by_customer.get(&customer_id) {
+                entry.1 += *revenue;
+                entry.2 += 1;
+                entry.3 += order_count;
+            }
+        }
+
+        Ok(segments
+            .into_iter()
+            .map(
+                |(segment_name, (customer_count, total_revenue, ordering, orders))| {
+                    CustomerSegment {
+                        segment_name,
+                        customer_count,
+                        total_revenue,
+                        average_order_frequency: if ordering > 0 {
+                            Decimal::from(orders) / Decimal::from(ordering)
+                        } else {
+                            Decimal::ZERO
+                        },
+                        churn_predicted_count: 0,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    async fn get_geographic_distribution(
+        &self,
+        months: u32,
+    ) -> ErpResult<Vec<GeographicDistribution>> {
+        let start_date = months_ago(months);
+
+        let mut revenue_by_customer: HashMap<Uuid, Decimal> = HashMap::new();
+        for order in self.load_orders().await? {
+            if order.order_date.date_naive() >= start_date {
+                *revenue_by_customer.entry(order.customer_id).or_default() += order.total_amount;
+            }
+        }
+
+        let rows = sqlx::query(
+            "SELECT c.id, ca.country, ca.state_province, ca.city \
+             FROM customers c \
+             LEFT JOIN customer_addresses ca \
+                    ON c.id = ca.customer_id AND ca.address_type = 'billing'",
+        )
+        .fetch_all(&self.reads.pool().await)
+        .await?;
+
+        let mut distribution: Vec<GeographicDistribution> = Vec::new();
+        let mut counted: HashSet<(Uuid, String, Option<String>, Option<String>)> = HashSet::new();
+        for row in rows {
+            let customer_id: Uuid = row.try_get("id")?;
+            let country = row
+                .try_get::<Option<String>, _>("country")?
+                .unwrap_or_else(|| "Unknown".to_string());
+            let state_province: Option<String> = row.try_get("state_province")?;
+            let city: Option<String> = row.try_get("city")?;
+
+            if !counted.insert((
+                customer_id,
+                country.clone(),
+                state_province.clone(),
+                city.clone(),
+            )) {
+                continue;
+            }
+
+            let revenue = revenue_by_customer
+                .get(&customer_id)
+                .copied()
+                .unwrap_or_default();
+            match distribution.iter_mut().find(|entry| {
+                entry.country == country
+                    && entry.state_province == state_province
+                    && entry.city == city
+            }) {
+                Some(entry) => {
+                    entry.customer_count += 1;
+                    entry.total_revenue += revenue;
+                }
+                None => distribution.push(GeographicDistribution {
+                    country,
+                    state_province,
+                    city,
+                    customer_count: 1,
+                    total_revenue: revenue,
+                }),
+            }
+        }
+
+        distribution.sort_by_key(|entry| std::cmp::Reverse(entry.total_revenue));
+        Ok(distribution)
+    }
+
+    async fn get_customer_lifecycle_metrics(
+        &self,
+        months: u32,
+    ) -> ErpResult<CustomerLifecycleMetrics> {
+        let start_date = months_ago(months);
+
+        let new_count = self
+            .customer_created_dates()
+            .await?
+            .iter()
+            .filter(|(_, _, created)| *created >= start_date)
+            .count() as u32;
+
+        let orders = self.load_orders().await?;
+        let earlier: HashSet<Uuid> = orders
+            .iter()
+            .filter(|order| order.order_date.date_naive() < start_date)
+            .map(|order| order.customer_id)
+            .collect();
+        let returning: HashSet<Uuid> = orders
+            .iter()
+            .filter(|order| order.order_date.date_naive() >= start_date)
+            .map(|order| order.customer_id)
+            .filter(|customer_id| earlier.contains(customer_id))
+            .collect();
+        let returning_count = returning.len() as u32;
+
+        let total_active = new_count + returning_count;
+        let churn_rate = if total_active > 0 {
+            Decimal::from(100)
+                - percentage(Decimal::from(returning_count), Decimal::from(total_active))
+        } else {
+            Decimal::ZERO
+        };
+
+        Ok(CustomerLifecycleMetrics {
+            new_customers: new_count,
+            returning_customers: returning_count,
+            churn_rate,
+            customer_lifetime_value: Decimal::new(50000, 2), // 가상 값 - 실제로는 복잡한 계산 필요
+            average_customer_lifespan_days: 365,             // 가상 값
+        })
+    }
+
+    async fn get_customer_order_metrics(&self) -> ErpResult<Vec<CustomerOrderMetrics>> {
+        self.customers.get_customer_order_metrics().await
+    }
+
+    async fn get_financial_overview(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> ErpResult<FinancialOverviewReport> {
+        let revenue_summary = self.get_revenue_summary(start_date, end_date).await?;
+        let expense_summary = self.get_expense_summary(start_date, end_date).await?;
+        let payment_analytics = self.get_payment_analytics(start_date, end_date).await?;
+        let total_revenue = revenue_summary.total_revenue;
+
+        let gross_profit = total_revenue - expense_summary.cost_of_goods_sold;
+        let net_profit = total_revenue - expense_summary.total_expenses;
+        let operating_profit = total_revenue - expense_summary.operating_expenses;
+        let profit_analysis = ProfitAnalysis {
+            gross_profit,
+            net_profit,
+            operating_profit,
+            gross_margin: percentage(gross_profit, total_revenue),
+            net_margin: percentage(net_profit, total_revenue),
+            operating_margin: percentage(operating_profit, total_revenue),
+        };
+
+        let cash_flow = CashFlowAnalysis {
+            cash_inflow: payment_analytics.payments_received,
+            cash_outflow: expense_summary.total_expenses,
+            net_cash_flow: payment_analytics.payments_received - expense_summary.total_expenses,
+            operating_cash_flow: operating_profit,
+            investing_cash_flow: Decimal::ZERO, // 투자 활동으로 인한 현금흐름 - 별도 추적 필요
+            financing_cash_flow: Decimal::ZERO, // 재무 활동으로 인한 현금흐름 - 별도 추적 필요
+        };
+
+        let financial_ratios = FinancialRatios {
+            current_ratio: Decimal::new(150, 2),       // 1.5 - 가상 값
+            quick_ratio: Decimal::new(125, 2),         // 1.25 - 가상 값
+            debt_to_equity_ratio: Decimal::new(50, 2), // 0.5 - 가상 값
+            return_on_investment: percentage(net_profit, total_revenue),
+            inventory_turnover: Decimal::new(6, 0), // 6 - 가상 값
+            receivables_turnover: Decimal::new(12, 0), // 12 - 가상 값
+        };
+
+        Ok(FinancialOverviewReport {
+            period: ReportPeriod::Custom {
+                from: start_date,
+                to: end_date,
+            },
+            generated_at: Utc::now(),
+            revenue_summary,
+            expense_summary,
+            profit_analysis,
+            cash_flow,
+            payment_analytics,
+            financial_ratios,
+        })
+    }
+
+    async fn get_revenue_summary(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> ErpResult<RevenueSummary> {
+        let orders = self.load_orders_between(start_date, end_date).await?;
+        let total_revenue = Self::recognized_revenue(&orders);
+
+        // 간단화된 수익 분류 - 실제로는 더 복잡한 분류가 필요
+        Ok(RevenueSummary {
+            total_revenue,
+            product_revenue: total_revenue * Decimal::new(80, 2), // 80%
+            service_revenue: total_revenue * Decimal::new(20, 2), // 20%
+            recurring_revenue: total_revenue * Decimal::new(30, 2), // 30%
+            one_time_revenue: total_revenue * Decimal::new(70, 2), // 70%
+            revenue_growth_rate: Decimal::new(5, 0),              // 5% 성장률 가정
+        })
+    }
+
+    async fn get_expense_summary(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> ErpResult<ExpenseSummary> {
+        // 비용 테이블이 없으므로 주문 금액 기준으로 추정
+        let total_revenue: Decimal = self
+            .load_orders_between(start_date, end_date)
+            .await?
+            .iter()
+            .map(|order| order.total_amount)
+            .sum();
+
+        let cost_of_goods_sold = total_revenue * Decimal::new(60, 2); // 60%
+        let operating_expenses = total_revenue * Decimal::new(25, 2); // 25%
+        let marketing_expenses = total_revenue * Decimal::new(10, 2); // 10%
+        let administrative_expenses = total_revenue * Decimal::new(5, 2); // 5%
+
+        Ok(ExpenseSummary {
+            total_expenses: cost_of_goods_sold
+                + operating_expenses
+                + marketing_expenses
+                + administrative_expenses,
+            cost_of_goods_sold,
+            operating_expenses,
+            marketing_expenses,
+            administrative_expenses,
+        })
+    }
+
+    async fn get_payment_analytics(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> ErpResult<PaymentAnalytics> {
+        let orders = self.load_orders_between(start_date, end_date).await?;
+        let sum_where = |statuses: &[&str]| -> Decimal {
+            orders
+                .iter()
+                .filter(|order| statuses.contains(&order.payment_status.as_str()))
+                .map(|order| order.total_amount)
+                .sum()
+        };
+
+        let payments_received = sum_where(&["paid"]);
+
+        let mut payment_methods_breakdown: Vec<PaymentMethodBreakdown> = Vec::new();
+        for order in orders.iter().filter(|order| order.payment_status == "paid") {
+            let method = order
+                .payment_method
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string());
+            match payment_methods_breakdown
+                .iter_mut()
+                .find(|entry| entry.payment_method == method)
+            {
+                Some(entry) => {
+                    entry.transaction_count += 1;
+                    entry.total_amount += order.total_amount;
+                }
+                None => payment_methods_breakdown.push(PaymentMethodBreakdown {
+                    payment_method: method,
+                    transaction_count: 1,
+                    total_amount: order.total_amount,
+                    percentage_of_total: Decimal::ZERO,
+                }),
+            }
+        }
+        for entry in &mut payment_methods_breakdown {
+            entry.percentage_of_total = percentage(entry.total_amount, payments_received);
+        }
+
+        Ok(PaymentAnalytics {
+            payments_received,
+            outstanding_receivables: sum_where(&["pending", "partiallypaid"]),
+            overdue_payments: sum_where(&["overdue"]),
+            average_payment_terms_days: 30, // 가정값
+            payment_methods_breakdown,
+        })
+    }
+}
+
 // Mock 구현체
 pub struct MockReportsRepository;
 
@@ -992,6 +1910,7 @@ impl ReportsRepository for MockReportsRepository {
                 customer_lifetime_value: Decimal::new(50000, 2),
                 average_customer_lifespan_days: 365,
             },
+            rfm_scores: vec![],
         })
     }
 
@@ -1023,6 +1942,30 @@ impl ReportsRepository for MockReportsRepository {
         })
     }
 
+    async fn get_customer_order_metrics(&self) -> ErpResult<Vec<CustomerOrderMetrics>> {
+        let today = Utc::now().date_naive();
+        Ok(vec![
+            CustomerOrderMetrics {
+                customer_id: uuid::Uuid::new_v4(),
+                name: "Mock Champion".to_string(),
+                email: Some("champion@example.com".to_string()),
+                first_order_date: Some(today - chrono::Duration::days(180)),
+                last_order_date: Some(today - chrono::Duration::days(3)),
+                order_count: 12,
+                total_spent: Decimal::new(1200000, 2),
+            },
+            CustomerOrderMetrics {
+                customer_id: uuid::Uuid::new_v4(),
+                name: "Mock Dormant".to_string(),
+                email: Some("dormant@example.com".to_string()),
+                first_order_date: Some(today - chrono::Duration::days(400)),
+                last_order_date: Some(today - chrono::Duration::days(300)),
+                order_count: 2,
+                total_spent: Decimal::new(30000, 2),
+            },
+        ])
+    }
+
     async fn get_financial_overview(
         &self,
         _start_date: NaiveDate,
@@ -1123,3 +2066,122 @@ impl ReportsRepository for MockReportsRepository {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::database::models::product::CreateProductRequest;
+    use crate::modules::customers::SqliteCustomerRepository;
+    use crate::modules::customers::{CreateCustomerRequest, Customer, CustomerType};
+    use crate::modules::inventory::{InventoryRepository, SqliteInventoryRepository};
+    use crate::modules::sales::{
+        OrderStatus, PaymentMethod, PaymentStatus, SalesOrder, SalesOrderItem, SalesRepository,
+        SqliteSalesRepository,
+    };
+
+    #[tokio::test]
+    async fn test_sqlite_reports_from_seeded_file() {
+        let pool = sqlite::test_pool().await;
+        let inventory = SqliteInventoryRepository::new(pool.clone());
+        let customers = Arc::new(SqliteCustomerRepository::new(pool.clone()));
+        let sales = SqliteSalesRepository::new(pool.clone());
+
+        let product = inventory
+            .create_product(CreateProductRequest {
+                sku: "lap-001".to_string(),
+                name: "Laptop".to_string(),
+                description: None,
+                category: "electronics".to_string(),
+                price: Decimal::new(120050, 2),
+                cost: Decimal::new(90000, 2),
+                quantity: 3,
+                min_stock_level: 5,
+                max_stock_level: Some(20),
+                is_taxable: true,
+                weight: None,
+                dimensions: None,
+                barcode: None,
+                supplier_id: None,
+            })
+            .await
+            .unwrap();
+
+        let customer = Customer::new(CreateCustomerRequest {
+            first_name: "Minji".to_string(),
+            last_name: "Kim".to_string(),
+            company_name: None,
+            email: "minji@example.com".to_string(),
+            phone: None,
+            customer_type: CustomerType::Business,
+            credit_limit: None,
+            tax_id: None,
+            notes: None,
+            addresses: Vec::new(),
+        });
+        customers.create_customer(&customer).await.unwrap();
+
+        let now = Utc::now();
+        let order = SalesOrder {
+            id: Uuid::new_v4(),
+            order_number: sales.get_next_order_number("ORD").await.unwrap(),
+            customer_id: customer.id,
+            order_date: now,
+            status: OrderStatus::Delivered,
+            total_amount: Decimal::new(240100, 2),
+            tax_amount: Decimal::ZERO,
+            discount_amount: Decimal::ZERO,
+            shipping_address: None,
+            billing_address: None,
+            payment_method: Some(PaymentMethod::CreditCard),
+            payment_status: PaymentStatus::Paid,
+            notes: None,
+            created_at: now,
+            updated_at: now,
+        };
+        sales.create_order(&order).await.unwrap();
+        sales
+            .create_order_items(&[SalesOrderItem {
+                id: Uuid::new_v4(),
+                order_id: order.id,
+                product_id: product.id,
+                quantity: 2,
+                unit_price: product.price,
+                discount: Decimal::ZERO,
+                line_total: Decimal::new(240100, 2),
+                created_at: now,
+            }])
+            .await
+            .unwrap();
+        assert_eq!(order.order_number, "ORD-000001");
+        assert_eq!(
+            sales.get_next_order_number("ORD").await.unwrap(),
+            "ORD-000002"
+        );
+
+        let reports = SqliteReportsRepository::new(pool, customers);
+        let today = now.date_naive();
+
+        let summary = reports.get_sales_summary(today, today).await.unwrap();
+        assert_eq!(summary.total_orders, 1);
+        assert_eq!(summary.total_items_sold, 2);
+        assert_eq!(summary.total_revenue, Decimal::new(240100, 2));
+        assert_eq!(summary.top_selling_products[0].sku, "LAP-001");
+
+        let inventory_status = reports.get_inventory_status().await.unwrap();
+        assert_eq!(inventory_status.total_stock_value, Decimal::new(360150, 2));
+        assert_eq!(
+            inventory_status.low_stock_items[0].suggested_reorder_quantity,
+            17
+        );
+
+        let top_customers = reports.get_top_customers(1, 10).await.unwrap();
+        assert_eq!(top_customers[0].email.as_deref(), Some("minji@example.com"));
+
+        let payments = reports.get_payment_analytics(today, today).await.unwrap();
+        assert_eq!(payments.payments_received, Decimal::new(240100, 2));
+        assert_eq!(
+            payments.payment_methods_breakdown[0].payment_method,
+            "creditcard"
+        );
+    }
+}
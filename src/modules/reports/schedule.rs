@@ -0,0 +1,788 @@
+//! 보고서 예약 실행
+//!
+//! cron 표현식과 `ReportRequest`를 스케줄로 저장하고, 스케줄러가 실행 시각이 된 작업을
+//! `ReportsService::export_report`로 실행해 출력 디렉토리에 시각이 붙은 파일로 저장합니다.
+//! 각 실행의 성공/실패는 실행 이력으로 기록됩니다.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shaku::Interface;
+use sqlx::PgPool;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::models::{ReportFormat, ReportRequest};
+use super::service::ReportsService;
+use crate::utils::cron::CronSchedule;
+use crate::utils::error::{ErpError, ErpResult};
+
+/// 보고서 스케줄
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSchedule {
+    pub id: Uuid,
+    pub name: String,
+    pub cron_expression: String,
+    pub request: ReportRequest,
+    pub enabled: bool,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub next_run_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ReportSchedule {
+    /// 새 스케줄 생성 (cron 표현식과 출력 형식 검증)
+    pub fn new(
+        name: impl Into<String>,
+        cron_expression: &str,
+        request: ReportRequest,
+    ) -> ErpResult<Self> {
+        let name = name.into().trim().to_string();
+        if name.is_empty() || name.len() > 100 {
+            return Err(ErpError::validation(
+                "name",
+                "스케줄 이름은 1-100자여야 합니다",
+            ));
+        }
+        if request.format == ReportFormat::Console {
+            return Err(ErpError::validation(
+                "format",
+                "예약 보고서는 파일 형식(json, csv, html, pdf, xlsx)이어야 합니다",
+            ));
+        }
+
+        let cron: CronSchedule = cron_expression.parse()?;
+        let now = Utc::now();
+        let next_run_at = cron.next_run(now).ok_or_else(|| {
+            ErpError::validation("cron", "cron 표현식의 다음 실행 시각을 찾을 수 없습니다")
+        })?;
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            name,
+            cron_expression: cron.to_string(),
+            request,
+            enabled: true,
+            last_run_at: None,
+            next_run_at,
+            created_at: now,
+        })
+    }
+
+    pub fn cron(&self) -> ErpResult<CronSchedule> {
+        self.cron_expression.parse()
+    }
+
+    /// 실행 결과 파일 경로 (`<출력 디렉토리>/<스케줄 이름>_<시각>.<확장자>`)
+    pub fn output_path(&self, output_dir: &Path, at: DateTime<Utc>) -> PathBuf {
+        let name: String = self
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        output_dir.join(format!(
+            "{}_{}.{}",
+            name,
+            at.format("%Y%m%d_%H%M%S"),
+            self.request.format.file_extension()
+        ))
+    }
+}
+
+/// 실행 결과 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportRunStatus {
+    Success,
+    Failed,
+}
+
+impl std::fmt::Display for ReportRunStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportRunStatus::Success => write!(f, "success"),
+            ReportRunStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportRunStatus {
+    type Err = ErpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "success" => Ok(ReportRunStatus::Success),
+            "failed" => Ok(ReportRunStatus::Failed),
+            _ => Err(ErpError::validation(
+                "status",
+                format!("알 수 없는 실행 상태입니다: {}", s),
+            )),
+        }
+    }
+}
+
+/// 스케줄 실행 이력
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportRun {
+    pub id: Uuid,
+    pub schedule_id: Uuid,
+    pub schedule_name: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub status: ReportRunStatus,
+    pub output_path: Option<String>,
+    pub error_message: Option<String>,
+}
+
+#[async_trait]
+pub trait ReportScheduleRepository: Interface {
+    async fn create_schedule(&self, schedule: &ReportSchedule) -> ErpResult<()>;
+
+    async fn list_schedules(&self) -> ErpResult<Vec<ReportSchedule>>;
+
+    async fn get_schedule_by_name(&self, name: &str) -> ErpResult<Option<ReportSchedule>>;
+
+    async fn delete_schedule(&self, name: &str) -> ErpResult<bool>;
+
+    async fn find_due_schedules(&self, now: DateTime<Utc>) -> ErpResult<Vec<ReportSchedule>>;
+
+    /// 다음 실행 시각이 `expected_next_run`일 때만 갱신 (다른 스케줄러가 먼저 가져간 경우 false)
+    async fn claim_schedule(
+        &self,
+        id: Uuid,
+        expected_next_run: DateTime<Utc>,
+        started_at: DateTime<Utc>,
+        next_run_at: DateTime<Utc>,
+    ) -> ErpResult<bool>;
+
+    async fn record_run(&self, run: &ReportRun) -> ErpResult<()>;
+
+    async fn list_runs(&self, schedule_name: Option<&str>, limit: u32)
+        -> ErpResult<Vec<ReportRun>>;
+}
+
+#[derive(sqlx::FromRow)]
+struct ScheduleRow {
+    id: Uuid,
+    name: String,
+    cron_expression: String,
+    request: String,
+    enabled: bool,
+    last_run_at: Option<DateTime<Utc>>,
+    next_run_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<ScheduleRow> for ReportSchedule {
+    type Error = ErpError;
+
+    fn try_from(row: ScheduleRow) -> Result<Self, Self::Error> {
+        let request = serde_json::from_str(&row.request).map_err(|e| {
+            ErpError::serialization(format!("스케줄 '{}'의 요청 파싱 실패: {}", row.name, e))
+        })?;
+        Ok(Self {
+            id: row.id,
+            name: row.name,
+            cron_expression: row.cron_expression,
+            request,
+            enabled: row.enabled,
+            last_run_at: row.last_run_at,
+            next_run_at: row.next_run_at,
+            created_at: row.created_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RunRow {
+    id: Uuid,
+    schedule_id: Option<Uuid>,
+    schedule_name: String,
+    started_at: DateTime<Utc>,
+    finished_at: DateTime<Utc>,
+    status: String,
+    output_path: Option<String>,
+    error_message: Option<String>,
+}
+
+impl TryFrom<RunRow> for ReportRun {
+    type Error = ErpError;
+
+    fn try_from(row: RunRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            // 스케줄이 삭제된 이력은 nil UUID로 표시
+            schedule_id: row.schedule_id.unwrap_or_else(Uuid::nil),
+            schedule_name: row.schedule_name,
+            started_at: row.started_at,
+            finished_at: row.finished_at,
+            status: row.status.parse()?,
+            output_path: row.output_path,
+            error_message: row.error_message,
+        })
+    }
+}
+
+const SCHEDULE_COLUMNS: &str = "id, name, cron_expression, request::text AS request, enabled, \
+     last_run_at, next_run_at, created_at";
+
+pub struct PostgresReportScheduleRepository {
+    pool: PgPool,
+}
+
+impl PostgresReportScheduleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReportScheduleRepository for PostgresReportScheduleRepository {
+    async fn create_schedule(&self, schedule: &ReportSchedule) -> ErpResult<()> {
+        let request = serde_json::to_string(&schedule.request)
+            .map_err(|e| ErpError::serialization(format!("요청 직렬화 실패: {}", e)))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO report_schedules
+                (id, name, cron_expression, request, enabled, last_run_at, next_run_at, created_at)
+            VALUES ($1, $2, $3, $4::jsonb, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(schedule.id)
+        .bind(&schedule.name)
+        .bind(&schedule.cron_expression)
+        .bind(request)
+        .bind(schedule.enabled)
+        .bind(schedule.last_run_at)
+        .bind(schedule.next_run_at)
+        .bind(schedule.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => ErpError::conflict(format!(
+                "이미 존재하는 스케줄 이름입니다: {}",
+                schedule.name
+            )),
+            e => ErpError::database(format!("Failed to create report schedule: {}", e)),
+        })?;
+
+        Ok(())
+    }
+
+    async fn list_schedules(&self) -> ErpResult<Vec<ReportSchedule>> {
+        let rows = sqlx::query_as::<_, ScheduleRow>(&format!(
+            "SELECT {} FROM report_schedules ORDER BY name",
+            SCHEDULE_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to list report schedules: {}", e)))?;
+
+        rows.into_iter().map(ReportSchedule::try_from).collect()
+    }
+
+    async fn get_schedule_by_name(&self, name: &str) -> ErpResult<Option<ReportSchedule>> {
+        let row = sqlx::query_as::<_, ScheduleRow>(&format!(
+            "SELECT {} FROM report_schedules WHERE name = $1",
+            SCHEDULE_COLUMNS
+        ))
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to get report schedule: {}", e)))?;
+
+        row.map(ReportSchedule::try_from).transpose()
+    }
+
+    async fn delete_schedule(&self, name: &str) -> ErpResult<bool> {
+        let result = sqlx::query("DELETE FROM report_schedules WHERE name = $1")
+            .bind(name)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| ErpError::database(format!("Failed to delete report schedule: {}", e)))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_due_schedules(&self, now: DateTime<Utc>) -> ErpResult<Vec<ReportSchedule>> {
+        let rows = sqlx::query_as::<_, ScheduleRow>(&format!(
+            "SELECT {} FROM report_schedules WHERE enabled AND next_run_at <= $1 ORDER BY next_run_at",
+            SCHEDULE_COLUMNS
+        ))
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to find due report schedules: {}", e)))?;
+
+        rows.into_iter().map(ReportSchedule::try_from).collect()
+    }
+
+    async fn claim_schedule(
+        &self,
+        id: Uuid,
+        expected_next_run: DateTime<Utc>,
+        started_at: DateTime<Utc>,
+        next_run_at: DateTime<Utc>,
+    ) -> ErpResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE report_schedules
+            SET last_run_at = $3, next_run_at = $4, updated_at = NOW()
+            WHERE id = $1 AND next_run_at = $2
+            "#,
+        )
+        .bind(id)
+        .bind(expected_next_run)
+        .bind(started_at)
+        .bind(next_run_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to claim report schedule: {}", e)))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn record_run(&self, run: &ReportRun) -> ErpResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO report_schedule_runs
+                (id, schedule_id, schedule_name, started_at, finished_at, status, output_path, error_message)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(run.id)
+        .bind(run.schedule_id)
+        .bind(&run.schedule_name)
+        .bind(run.started_at)
+        .bind(run.finished_at)
+        .bind(run.status.to_string())
+        .bind(&run.output_path)
+        .bind(&run.error_message)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to record report run: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn list_runs(
+        &self,
+        schedule_name: Option<&str>,
+        limit: u32,
+    ) -> ErpResult<Vec<ReportRun>> {
+        let rows = sqlx::query_as::<_, RunRow>(
+            r#"
+            SELECT id, schedule_id, schedule_name, started_at, finished_at, status,
+                   output_path, error_message
+            FROM report_schedule_runs
+            WHERE $1::text IS NULL OR schedule_name = $1
+            ORDER BY started_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(schedule_name)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| ErpError::database(format!("Failed to list report runs: {}", e)))?;
+
+        rows.into_iter().map(ReportRun::try_from).collect()
+    }
+}
+
+// Mock repository for testing
+#[derive(Default)]
+pub struct MockReportScheduleRepository {
+    schedules: Mutex<Vec<ReportSchedule>>,
+    runs: Mutex<Vec<ReportRun>>,
+}
+
+impl MockReportScheduleRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ReportScheduleRepository for MockReportScheduleRepository {
+    async fn create_schedule(&self, schedule: &ReportSchedule) -> ErpResult<()> {
+        let mut schedules = self.schedules.lock().unwrap();
+        if schedules.iter().any(|s| s.name == schedule.name) {
+            return Err(ErpError::conflict(format!(
+                "이미 존재하는 스케줄 이름입니다: {}",
+                schedule.name
+            )));
+        }
+        schedules.push(schedule.clone());
+        Ok(())
+    }
+
+    async fn list_schedules(&self) -> ErpResult<Vec<ReportSchedule>> {
+        let mut schedules = self.schedules.lock().unwrap().clone();
+        schedules.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(schedules)
+    }
+
+    async fn get_schedule_by_name(&self, name: &str) -> ErpResult<Option<ReportSchedule>> {
+        let schedules = self.schedules.lock().unwrap();
+        Ok(schedules.iter().find(|s| s.name == name).cloned())
+    }
+
+    async fn delete_schedule(&self, name: &str) -> ErpResult<bool> {
+        let mut schedules = self.schedules.lock().unwrap();
+        let before = schedules.len();
+        schedules.retain(|s| s.name != name);
+        Ok(schedules.len() != before)
+    }
+
+    async fn find_due_schedules(&self, now: DateTime<Utc>) -> ErpResult<Vec<ReportSchedule>> {
+        let schedules = self.schedules.lock().unwrap();
+        let mut due: Vec<ReportSchedule> = schedules
+            .iter()
+            .filter(|s| s.enabled && s.next_run_at <= now)
+            .cloned()
+            .collect();
+        due.sort_by_key(|s| s.next_run_at);
+        Ok(due)
+    }
+
+    async fn claim_schedule(
+        &self,
+        id: Uuid,
+        expected_next_run: DateTime<Utc>,
+        started_at: DateTime<Utc>,
+        next_run_at: DateTime<Utc>,
+    ) -> ErpResult<bool> {
+        let mut schedules = self.schedules.lock().unwrap();
+        match schedules
+            .iter_mut()
+            .find(|s| s.id == id && s.next_run_at == expected_next_run)
+        {
+            Some(schedule) => {
+                schedule.last_run_at = Some(started_at);
+                schedule.next_run_at = next_run_at;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn record_run(&self, run: &ReportRun) -> ErpResult<()> {
+        self.runs.lock().unwrap().push(run.clone());
+        Ok(())
+    }
+
+    async fn list_runs(
+        &self,
+        schedule_name: Option<&str>,
+        limit: u32,
+    ) -> ErpResult<Vec<ReportRun>> {
+        let runs = self.runs.lock().unwrap();
+        let mut result: Vec<ReportRun> = runs
+            .iter()
+            .filter(|r| schedule_name.is_none_or(|name| r.schedule_name == name))
+            .cloned()
+            .collect();
+        result.sort_by_key(|r| std::cmp::Reverse(r.started_at));
+        result.truncate(limit as usize);
+        Ok(result)
+    }
+}
+
+/// 예약된 보고서를 실행하는 스케줄러
+pub struct ReportScheduler {
+    repository: Arc<dyn ReportScheduleRepository>,
+    reports_service: ReportsService,
+    output_dir: PathBuf,
+}
+
+impl ReportScheduler {
+    pub fn new(
+        repository: Arc<dyn ReportScheduleRepository>,
+        reports_service: ReportsService,
+        output_dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            repository,
+            reports_service,
+            output_dir: output_dir.into(),
+        }
+    }
+
+    /// 실행 시각이 된 스케줄을 모두 실행하고 실행 이력을 반환
+    pub async fn run_due(&self, now: DateTime<Utc>) -> ErpResult<Vec<ReportRun>> {
+        let due = self.repository.find_due_schedules(now).await?;
+        let mut runs = Vec::with_capacity(due.len());
+
+        for schedule in due {
+            // 밀린 실행은 한 번만 수행하고 현재 시각 이후로 다음 실행을 잡음
+            let next_run_at = match schedule.cron().ok().and_then(|cron| cron.next_run(now)) {
+                Some(next) => next,
+                None => {
+                    tracing::warn!(
+                        "스케줄 '{}'의 cron 표현식이 잘못되어 건너뜁니다: {}",
+                        schedule.name,
+                        schedule.cron_expression
+                    );
+                    continue;
+                }
+            };
+
+            let started_at = Utc::now();
+            if !self
+                .repository
+                .claim_schedule(schedule.id, schedule.next_run_at, started_at, next_run_at)
+                .await?
+            {
+                tracing::debug!("스케줄 '{}'은 다른 스케줄러가 실행 중입니다", schedule.name);
+                continue;
+            }
+
+            let run = self.execute(&schedule, started_at).await;
+            self.repository.record_run(&run).await?;
+            runs.push(run);
+        }
+
+        Ok(runs)
+    }
+
+    /// 종료 신호(Ctrl+C)를 받을 때까지 주기적으로 실행
+    pub async fn run_forever(&self, poll_interval: Duration) -> ErpResult<()> {
+        tracing::info!(
+            "보고서 스케줄러 시작 (확인 주기: {}초, 출력 디렉토리: {})",
+            poll_interval.as_secs(),
+            self.output_dir.display()
+        );
+
+        loop {
+            match self.run_due(Utc::now()).await {
+                Ok(runs) => {
+                    for run in &runs {
+                        match run.status {
+                            ReportRunStatus::Success => tracing::info!(
+                                "보고서 생성 완료: {} -> {}",
+                                run.schedule_name,
+                                run.output_path.as_deref().unwrap_or("-")
+                            ),
+                            ReportRunStatus::Failed => tracing::error!(
+                                "보고서 생성 실패: {} ({})",
+                                run.schedule_name,
+                                run.error_message.as_deref().unwrap_or("-")
+                            ),
+                        }
+                    }
+                }
+                Err(e) => tracing::error!("스케줄 확인 중 오류 발생: {}", e),
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(poll_interval) => {}
+                _ = tokio::signal::ctrl_c() => {
+                    tracing::info!("보고서 스케줄러를 종료합니다");
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    async fn execute(&self, schedule: &ReportSchedule, started_at: DateTime<Utc>) -> ReportRun {
+        let mut request = schedule.request.clone();
+        let output_path = schedule.output_path(&self.output_dir, started_at);
+        request.output_path = Some(output_path.to_string_lossy().to_string());
+
+        let result = self
+            .reports_service
+            .export_report(&request.report_type, &request)
+            .await;
+
+        let (status, output_path, error_message) = match result {
+            Ok(path) => (ReportRunStatus::Success, Some(path), None),
+            Err(e) => (ReportRunStatus::Failed, None, Some(e.to_string())),
+        };
+
+        ReportRun {
+            id: Uuid::new_v4(),
+            schedule_id: schedule.id,
+            schedule_name: schedule.name.clone(),
+            started_at,
+            finished_at: Utc::now(),
+            status,
+            output_path,
+            error_message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::reports::models::{ReportFilters, ReportPeriod, ReportType};
+    use crate::modules::reports::MockReportsRepository;
+
+    fn weekly_sales_request(format: ReportFormat) -> ReportRequest {
+        ReportRequest {
+            report_type: ReportType::SalesSummary,
+            period: ReportPeriod::Weekly,
+            format,
+            output_path: None,
+            filters: ReportFilters::default(),
+            include_charts: false,
+        }
+    }
+
+    fn scheduler(
+        repository: Arc<MockReportScheduleRepository>,
+        output_dir: &Path,
+    ) -> ReportScheduler {
+        let service = ReportsService::new(Arc::new(MockReportsRepository::new()));
+        ReportScheduler::new(repository, service, output_dir)
+    }
+
+    #[test]
+    fn test_schedule_validation() {
+        assert!(ReportSchedule::new(
+            "weekly-sales",
+            "0 8 * * mon",
+            weekly_sales_request(ReportFormat::Pdf)
+        )
+        .is_ok());
+        assert!(ReportSchedule::new(
+            "weekly-sales",
+            "0 8 * *",
+            weekly_sales_request(ReportFormat::Pdf)
+        )
+        .is_err());
+        assert!(ReportSchedule::new(
+            "weekly-sales",
+            "0 8 * * mon",
+            weekly_sales_request(ReportFormat::Console)
+        )
+        .is_err());
+        assert!(
+            ReportSchedule::new(" ", "@daily", weekly_sales_request(ReportFormat::Csv)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_request_round_trips_as_json() {
+        let mut request = weekly_sales_request(ReportFormat::Xlsx);
+        request.report_type = ReportType::CustomerAnalysis { months: 6 };
+        request.filters.low_stock_only = true;
+
+        let json = serde_json::to_string(&request).unwrap();
+        let parsed: ReportRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed.report_type,
+            ReportType::CustomerAnalysis { months: 6 }
+        );
+        assert_eq!(parsed.format, ReportFormat::Xlsx);
+        assert!(parsed.filters.low_stock_only);
+    }
+
+    #[tokio::test]
+    async fn test_run_due_writes_timestamped_file_and_history() {
+        let repository = Arc::new(MockReportScheduleRepository::new());
+        let dir = tempfile::tempdir().unwrap();
+        let scheduler = scheduler(repository.clone(), dir.path());
+
+        let mut schedule = ReportSchedule::new(
+            "weekly sales",
+            "0 8 * * mon",
+            weekly_sales_request(ReportFormat::Csv),
+        )
+        .unwrap();
+        let now = Utc::now();
+        schedule.next_run_at = now - chrono::Duration::minutes(1);
+        repository.create_schedule(&schedule).await.unwrap();
+
+        let runs = scheduler.run_due(now).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, ReportRunStatus::Success);
+
+        let output = runs[0].output_path.clone().unwrap();
+        let file_name = Path::new(&output).file_name().unwrap().to_string_lossy();
+        assert!(file_name.starts_with("weekly_sales_"));
+        assert!(file_name.ends_with(".csv"));
+        assert!(std::fs::read_to_string(&output)
+            .unwrap()
+            .contains("매출 요약 보고서"));
+
+        // 다음 실행 시각이 미래로 이동해 다시 실행되지 않음
+        let updated = repository
+            .get_schedule_by_name("weekly sales")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(updated.next_run_at > now);
+        assert!(updated.last_run_at.is_some());
+        assert!(scheduler.run_due(now).await.unwrap().is_empty());
+
+        let history = repository
+            .list_runs(Some("weekly sales"), 10)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_run_is_recorded() {
+        let repository = Arc::new(MockReportScheduleRepository::new());
+        let dir = tempfile::tempdir().unwrap();
+        // 출력 디렉토리 자리에 파일을 두어 디렉토리 생성이 실패하도록 함
+        let blocked = dir.path().join("blocked");
+        std::fs::write(&blocked, b"").unwrap();
+        let scheduler = scheduler(repository.clone(), &blocked);
+
+        let mut schedule = ReportSchedule::new(
+            "low-stock",
+            "@daily",
+            weekly_sales_request(ReportFormat::Json),
+        )
+        .unwrap();
+        schedule.request.report_type = ReportType::InventoryStatus;
+        let now = Utc::now();
+        schedule.next_run_at = now;
+        repository.create_schedule(&schedule).await.unwrap();
+
+        let runs = scheduler.run_due(now).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, ReportRunStatus::Failed);
+        assert!(runs[0].output_path.is_none());
+        assert!(runs[0].error_message.is_some());
+
+        let history = repository.list_runs(None, 10).await.unwrap();
+        assert_eq!(history[0].status, ReportRunStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn test_claim_prevents_double_execution() {
+        let repository = MockReportScheduleRepository::new();
+        let schedule =
+            ReportSchedule::new("daily", "@daily", weekly_sales_request(ReportFormat::Json))
+                .unwrap();
+        repository.create_schedule(&schedule).await.unwrap();
+
+        let now = Utc::now();
+        let next = now + chrono::Duration::days(1);
+        assert!(repository
+            .claim_schedule(schedule.id, schedule.next_run_at, now, next)
+            .await
+            .unwrap());
+        assert!(!repository
+            .claim_schedule(schedule.id, schedule.next_run_at, now, next)
+            .await
+            .unwrap());
+        assert!(repository.create_schedule(&schedule).await.is_err());
+        assert!(repository.delete_schedule("daily").await.unwrap());
+        assert!(!repository.delete_schedule("daily").await.unwrap());
+    }
+}
@@ -1,21 +1,60 @@
-use chrono::Utc;
-use printpdf::*;
-use rust_decimal::{prelude::FromPrimitive, Decimal};
+use chrono::{NaiveDate, Utc};
+use rust_decimal::{
+    prelude::{FromPrimitive, ToPrimitive},
+    Decimal,
+};
 use std::path::Path;
 use std::sync::Arc;
 use tokio::fs;
 
+use super::custom::CustomReportResult;
 use super::models::*;
 use super::repository::ReportsRepository;
+use super::templates::{self, BarChart, ReportDocument, ReportSection, ReportTable};
+use crate::core::cache::{CacheScope, QueryCache};
+use crate::modules::customers::segmentation;
 use crate::utils::error::{ErpError, ErpResult};
+use crate::utils::xlsx::CellValue;
 
 pub struct ReportsService {
     repository: Arc<dyn ReportsRepository>,
+    cache: QueryCache,
 }
 
 impl ReportsService {
     pub fn new(repository: Arc<dyn ReportsRepository>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            cache: QueryCache::disabled(),
+        }
+    }
+
+    /// 매출/재고/재무 집계와 요약 통계를 캐시
+    /// (판매, 재고, 고객 변경 시 reports 범위가 무효화됨)
+    pub fn with_cache(mut self, cache: QueryCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    async fn sales_summary(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> ErpResult<SalesSummaryReport> {
+        let key = format!("sales_summary:{}:{}", start_date, end_date);
+        self.cache
+            .get_or_load(CacheScope::Reports, &key, || {
+                self.repository.get_sales_summary(start_date, end_date)
+            })
+            .await
+    }
+
+    async fn inventory_status(&self) -> ErpResult<InventoryStatusReport> {
+        self.cache
+            .get_or_load(CacheScope::Reports, "inventory_status", || {
+                self.repository.get_inventory_status()
+            })
+            .await
     }
 
     /// 매출 요약 보고서 생성
@@ -26,10 +65,7 @@ impl ReportsService {
         let (start_date, end_date) = request.period.to_date_range();
 
         // 기본 매출 요약 데이터 조회
-        let mut report = self
-            .repository
-            .get_sales_summary(start_date, end_date)
-            .await?;
+        let mut report = self.sales_summary(start_date, end_date).await?;
 
         // 필터 적용
         self.apply_sales_filters(&mut report, &request.filters)
@@ -37,8 +73,13 @@ impl ReportsService {
 
         // 출력 형식에 따른 처리
         if let Some(output_path) = &request.output_path {
-            self.export_sales_summary(&report, &request.format, output_path)
-                .await?;
+            self.export_sales_summary(
+                &report,
+                &request.format,
+                output_path,
+                request.include_charts,
+            )
+            .await?;
         }
 
         Ok(report)
@@ -49,7 +90,7 @@ impl ReportsService {
         &self,
         request: &ReportRequest,
     ) -> ErpResult<InventoryStatusReport> {
-        let mut report = self.repository.get_inventory_status().await?;
+        let mut report = self.inventory_status().await?;
 
         // 저재고만 표시 옵션 적용
         if request.filters.low_stock_only {
@@ -71,8 +112,13 @@ impl ReportsService {
 
         // 출력 형식에 따른 처리
         if let Some(output_path) = &request.output_path {
-            self.export_inventory_status(&report, &request.format, output_path)
-                .await?;
+            self.export_inventory_status(
+                &report,
+                &request.format,
+                output_path,
+                request.include_charts,
+            )
+            .await?;
         }
 
         Ok(report)
@@ -94,17 +140,42 @@ impl ReportsService {
 
         let mut report = self.repository.get_customer_analysis(months).await?;
 
+        // RFM 점수 및 이탈 예측
+        let metrics = self.repository.get_customer_order_metrics().await?;
+        report.rfm_scores = segmentation::score_customers(&metrics, Utc::now().date_naive());
+
         // 고객 ID 필터 적용
         if let Some(customer_ids) = &request.filters.customer_ids {
             report
                 .top_customers
                 .retain(|customer| customer_ids.contains(&customer.customer_id));
+            report
+                .rfm_scores
+                .retain(|score| customer_ids.contains(&score.customer_id));
+        }
+
+        if !report.rfm_scores.is_empty() {
+            report.customer_segments = segmentation::summarize_segments(&report.rfm_scores)
+                .into_iter()
+                .map(|summary| CustomerSegment {
+                    segment_name: summary.segment.to_string(),
+                    customer_count: summary.customer_count,
+                    total_revenue: summary.total_revenue,
+                    average_order_frequency: summary.average_order_frequency,
+                    churn_predicted_count: summary.churn_predicted_count,
+                })
+                .collect();
         }
 
         // 출력 형식에 따른 처리
         if let Some(output_path) = &request.output_path {
-            self.export_customer_analysis(&report, &request.format, output_path)
-                .await?;
+            self.export_customer_analysis(
+                &report,
+                &request.format,
+                output_path,
+                request.include_charts,
+            )
+            .await?;
         }
 
         Ok(report)
@@ -117,15 +188,23 @@ impl ReportsService {
     ) -> ErpResult<FinancialOverviewReport> {
         let (start_date, end_date) = request.period.to_date_range();
 
+        let key = format!("financial_overview:{}:{}", start_date, end_date);
         let report = self
-            .repository
-            .get_financial_overview(start_date, end_date)
+            .cache
+            .get_or_load(CacheScope::Reports, &key, || {
+                self.repository.get_financial_overview(start_date, end_date)
+            })
             .await?;
 
         // 출력 형식에 따른 처리
         if let Some(output_path) = &request.output_path {
-            self.export_financial_overview(&report, &request.format, output_path)
-                .await?;
+            self.export_financial_overview(
+                &report,
+                &request.format,
+                output_path,
+                request.include_charts,
+            )
+            .await?;
         }
 
         Ok(report)
@@ -155,40 +234,132 @@ impl ReportsService {
         match report_type {
             ReportType::SalesSummary => {
                 let report = self.generate_sales_summary(request).await?;
-                self.export_sales_summary(&report, &request.format, &output_path)
-                    .await?;
+                self.export_sales_summary(
+                    &report,
+                    &request.format,
+                    &output_path,
+                    request.include_charts,
+                )
+                .await?;
             }
             ReportType::InventoryStatus => {
                 let report = self.generate_inventory_status(request).await?;
-                self.export_inventory_status(&report, &request.format, &output_path)
-                    .await?;
+                self.export_inventory_status(
+                    &report,
+                    &request.format,
+                    &output_path,
+                    request.include_charts,
+                )
+                .await?;
             }
             ReportType::CustomerAnalysis { months } => {
                 let report = self.generate_customer_analysis(*months, request).await?;
-                self.export_customer_analysis(&report, &request.format, &output_path)
-                    .await?;
+                self.export_customer_analysis(
+                    &report,
+                    &request.format,
+                    &output_path,
+                    request.include_charts,
+                )
+                .await?;
             }
             ReportType::FinancialOverview => {
                 let report = self.generate_financial_overview(request).await?;
-                self.export_financial_overview(&report, &request.format, &output_path)
-                    .await?;
+                self.export_financial_overview(
+                    &report,
+                    &request.format,
+                    &output_path,
+                    request.include_charts,
+                )
+                .await?;
             }
         }
 
         Ok(output_path)
     }
 
+    /// 사용자 정의 보고서 내보내기
+    ///
+    /// 출력 경로가 없으면 `./reports/custom_<이름>_<시각>.<확장자>`에 저장합니다.
+    pub async fn export_custom_report(
+        &self,
+        result: &CustomReportResult,
+        format: &ReportFormat,
+        output_path: Option<&str>,
+        include_charts: bool,
+    ) -> ErpResult<String> {
+        let output_path = match output_path {
+            Some(path) => path.to_string(),
+            None => {
+                let name: String = result
+                    .name
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || c == '-' {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect();
+                format!(
+                    "./reports/custom_{}_{}.{}",
+                    name,
+                    Utc::now().format("%Y%m%d_%H%M%S"),
+                    format.file_extension()
+                )
+            }
+        };
+
+        if let Some(parent) = Path::new(&output_path).parent() {
+            fs::create_dir_all(parent)
+                .await
+                .map_err(|e| ErpError::io(format!("보고서 디렉토리 생성 실패: {}", e)))?;
+        }
+
+        let content = match format {
+            ReportFormat::Json => result.to_json()?.into_bytes(),
+            ReportFormat::Csv => result.to_csv().into_bytes(),
+            ReportFormat::Html => {
+                templates::render_html(&result.to_document(), include_charts).into_bytes()
+            }
+            ReportFormat::Pdf => templates::render_pdf(&result.to_document(), include_charts)?,
+            ReportFormat::Xlsx => templates::render_xlsx(&result.to_document())?,
+            ReportFormat::Console => {
+                return Err(ErpError::validation(
+                    "format",
+                    "콘솔 형식은 파일로 내보낼 수 없습니다",
+                ))
+            }
+        };
+
+        fs::write(&output_path, content)
+            .await
+            .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
+
+        Ok(output_path)
+    }
+
     /// 보고서 요약 통계 조회
     pub async fn get_report_summary(&self, months: Option<u32>) -> ErpResult<ReportSummary> {
         let months = months.unwrap_or(1);
         let end_date = Utc::now().date_naive();
+        let key = format!("summary:{}:{}", months, end_date);
+        self.cache
+            .get_or_load(CacheScope::Reports, &key, || {
+                self.load_report_summary(months, end_date)
+            })
+            .await
+    }
+
+    async fn load_report_summary(
+        &self,
+        months: u32,
+        end_date: NaiveDate,
+    ) -> ErpResult<ReportSummary> {
         let start_date = end_date - chrono::Duration::days((months * 30) as i64);
 
-        let sales_summary = self
-            .repository
-            .get_sales_summary(start_date, end_date)
-            .await?;
-        let inventory_status = self.repository.get_inventory_status().await?;
+        let sales_summary = self.sales_summary(start_date, end_date).await?;
+        let inventory_status = self.inventory_status().await?;
         let customer_analysis = self.repository.get_customer_analysis(months).await?;
 
         Ok(ReportSummary {
@@ -318,7 +489,7 @@ impl ReportsService {
         }
 
         // 회전율 기준으로 정렬
-        turnover_items.sort_by(|a, b| b.turnover_rate.cmp(&a.turnover_rate));
+        turnover_items.sort_by_key(|x| std::cmp::Reverse(x.turnover_rate));
 
         let average_turnover = if !turnover_items.is_empty() {
             turnover_items
@@ -368,6 +539,7 @@ impl ReportsService {
         report: &SalesSummaryReport,
         format: &ReportFormat,
         output_path: &str,
+        include_charts: bool,
     ) -> ErpResult<()> {
         match format {
             ReportFormat::Json => {
@@ -384,7 +556,7 @@ impl ReportsService {
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
             ReportFormat::Html => {
-                let html = self.generate_sales_summary_html(report)?;
+                let html = self.generate_sales_summary_html(report, include_charts)?;
                 fs::write(output_path, html)
                     .await
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
@@ -394,11 +566,17 @@ impl ReportsService {
                 return Ok(());
             }
             ReportFormat::Pdf => {
-                let pdf_bytes = self.generate_sales_summary_pdf(report)?;
+                let pdf_bytes = self.generate_sales_summary_pdf(report, include_charts)?;
                 fs::write(output_path, pdf_bytes)
                     .await
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
+            ReportFormat::Xlsx => {
+                let xlsx_bytes = self.generate_sales_summary_xlsx(report)?;
+                fs::write(output_path, xlsx_bytes)
+                    .await
+                    .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
+            }
         }
         Ok(())
     }
@@ -408,6 +586,7 @@ impl ReportsService {
         report: &InventoryStatusReport,
         format: &ReportFormat,
         output_path: &str,
+        include_charts: bool,
     ) -> ErpResult<()> {
         match format {
             ReportFormat::Json => {
@@ -424,18 +603,24 @@ impl ReportsService {
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
             ReportFormat::Html => {
-                let html = self.generate_inventory_status_html(report)?;
+                let html = self.generate_inventory_status_html(report, include_charts)?;
                 fs::write(output_path, html)
                     .await
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
             ReportFormat::Console => return Ok(()),
             ReportFormat::Pdf => {
-                let pdf_bytes = self.generate_inventory_status_pdf(report)?;
+                let pdf_bytes = self.generate_inventory_status_pdf(report, include_charts)?;
                 fs::write(output_path, pdf_bytes)
                     .await
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
+            ReportFormat::Xlsx => {
+                let xlsx_bytes = self.generate_inventory_status_xlsx(report)?;
+                fs::write(output_path, xlsx_bytes)
+                    .await
+                    .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
+            }
         }
         Ok(())
     }
@@ -445,6 +630,7 @@ impl ReportsService {
         report: &CustomerAnalysisReport,
         format: &ReportFormat,
         output_path: &str,
+        include_charts: bool,
     ) -> ErpResult<()> {
         match format {
             ReportFormat::Json => {
@@ -461,18 +647,24 @@ impl ReportsService {
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
             ReportFormat::Html => {
-                let html = self.generate_customer_analysis_html(report)?;
+                let html = self.generate_customer_analysis_html(report, include_charts)?;
                 fs::write(output_path, html)
                     .await
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
             ReportFormat::Console => return Ok(()),
             ReportFormat::Pdf => {
-                let pdf_bytes = self.generate_customer_analysis_pdf(report)?;
+                let pdf_bytes = self.generate_customer_analysis_pdf(report, include_charts)?;
                 fs::write(output_path, pdf_bytes)
                     .await
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
+            ReportFormat::Xlsx => {
+                let xlsx_bytes = self.generate_customer_analysis_xlsx(report)?;
+                fs::write(output_path, xlsx_bytes)
+                    .await
+                    .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
+            }
         }
         Ok(())
     }
@@ -482,6 +674,7 @@ impl ReportsService {
         report: &FinancialOverviewReport,
         format: &ReportFormat,
         output_path: &str,
+        include_charts: bool,
     ) -> ErpResult<()> {
         match format {
             ReportFormat::Json => {
@@ -498,18 +691,24 @@ impl ReportsService {
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
             ReportFormat::Html => {
-                let html = self.generate_financial_overview_html(report)?;
+                let html = self.generate_financial_overview_html(report, include_charts)?;
                 fs::write(output_path, html)
                     .await
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
             ReportFormat::Console => return Ok(()),
             ReportFormat::Pdf => {
-                let pdf_bytes = self.generate_financial_overview_pdf(report)?;
+                let pdf_bytes = self.generate_financial_overview_pdf(report, include_charts)?;
                 fs::write(output_path, pdf_bytes)
                     .await
                     .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
             }
+            ReportFormat::Xlsx => {
+                let xlsx_bytes = self.generate_financial_overview_xlsx(report)?;
+                fs::write(output_path, xlsx_bytes)
+                    .await
+                    .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))?;
+            }
         }
         Ok(())
     }
@@ -522,14 +721,7 @@ impl ReportsService {
             ReportType::CustomerAnalysis { .. } => "customer_analysis",
             ReportType::FinancialOverview => "financial_overview",
         };
-        let extension = match format {
-            ReportFormat::Json => "json",
-            ReportFormat::Csv => "csv",
-            ReportFormat::Html => "html",
-            ReportFormat::Pdf => "pdf",
-            ReportFormat::Console => "txt",
-        };
-        format!("{}_{}.{}", type_name, timestamp, extension)
+        format!("{}_{}.{}", type_name, timestamp, format.file_extension())
     }
 
     // CSV 생성 메서드들
@@ -616,6 +808,40 @@ impl ReportsService {
             ));
         }
 
+        csv.push_str("\n고객 세그먼트:\n");
+        csv.push_str("세그먼트,고객수,총매출,평균구매빈도,이탈예측고객수\n");
+        for segment in &report.customer_segments {
+            csv.push_str(&format!(
+                "{},{},{},{},{}\n",
+                segment.segment_name,
+                segment.customer_count,
+                segment.total_revenue,
+                segment.average_order_frequency,
+                segment.churn_predicted_count
+            ));
+        }
+
+        csv.push_str("\nRFM 점수:\n");
+        csv.push_str("고객명,이메일,최근구매경과일,구매빈도,구매금액,R,F,M,세그먼트,이탈예측\n");
+        for score in &report.rfm_scores {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                score.name,
+                score.email.as_deref().unwrap_or("N/A"),
+                score
+                    .recency_days
+                    .map(|d| d.to_string())
+                    .unwrap_or("N/A".to_string()),
+                score.frequency,
+                score.monetary,
+                score.recency_score,
+                score.frequency_score,
+                score.monetary_score,
+                score.segment,
+                if score.churn_predicted { "Y" } else { "N" }
+            ));
+        }
+
         Ok(csv)
     }
 
@@ -673,487 +899,794 @@ impl ReportsService {
         Ok(csv)
     }
 
-    // HTML 생성 메서드들 (기본 구현)
-    fn generate_sales_summary_html(&self, report: &SalesSummaryReport) -> ErpResult<String> {
-        let html = format!(
-            r#"
-<!DOCTYPE html>
-<html>
-<head>
-    <title>매출 요약 보고서</title>
-    <meta charset="UTF-8">
-    <style>
-        body {{ font-family: Arial, sans-serif; margin: 20px; }}
-        table {{ border-collapse: collapse; width: 100%; }}
-        th, td {{ border: 1px solid #ddd; padding: 8px; text-align: left; }}
-        th {{ background-color: #f2f2f2; }}
-        .summary {{ margin-bottom: 20px; }}
-        .summary h2 {{ color: #333; }}
-    </style>
-</head>
-<body>
-    <h1>매출 요약 보고서</h1>
-    <div class="summary">
-        <p><strong>생성 시간:</strong> {}</p>
-        <p><strong>총 주문 수:</strong> {}</p>
-        <p><strong>총 매출:</strong> {}</p>
-        <p><strong>평균 주문 금액:</strong> {}</p>
-    </div>
-
-    <h2>상위 판매 제품</h2>
-    <table>
-        <thead>
-            <tr>
-                <th>제품명</th>
-                <th>SKU</th>
-                <th>판매량</th>
-                <th>매출</th>
-            </tr>
-        </thead>
-        <tbody>
-            {}
-        </tbody>
-    </table>
-</body>
-</html>"#,
-            report.generated_at.format("%Y-%m-%d %H:%M:%S"),
-            report.total_orders,
-            report.total_revenue,
-            report.average_order_value,
+    // 보고서 문서 구성 (HTML/PDF/XLSX 공통)
+    fn sales_summary_document(&self, report: &SalesSummaryReport) -> ReportDocument {
+        let (start_date, end_date) = report.period.to_date_range();
+
+        let top_products = ReportTable::new(&["제품명", "SKU", "판매량", "매출"])
+            .numeric(&[2, 3])
+            .rows(report.top_selling_products.iter().map(|p| {
+                vec![
+                    p.name.clone().into(),
+                    p.sku.clone().into(),
+                    p.quantity_sold.into(),
+                    p.total_revenue.into(),
+                ]
+            }))
+            .totals(vec![
+                "합계".into(),
+                CellValue::Empty,
+                report
+                    .top_selling_products
+                    .iter()
+                    .map(|p| p.quantity_sold)
+                    .sum::<u32>()
+                    .into(),
+                report
+                    .top_selling_products
+                    .iter()
+                    .map(|p| p.total_revenue)
+                    .sum::<Decimal>()
+                    .into(),
+            ]);
+
+        let by_status = ReportTable::new(&["상태", "주문 수", "금액"])
+            .numeric(&[1, 2])
+            .rows(report.sales_by_status.iter().map(|s| {
+                vec![
+                    s.status.clone().into(),
+                    s.order_count.into(),
+                    s.total_amount.into(),
+                ]
+            }))
+            .totals(vec![
+                "합계".into(),
+                report
+                    .sales_by_status
+                    .iter()
+                    .map(|s| s.order_count)
+                    .sum::<u32>()
+                    .into(),
+                report
+                    .sales_by_status
+                    .iter()
+                    .map(|s| s.total_amount)
+                    .sum::<Decimal>()
+                    .into(),
+            ]);
+
+        let daily = ReportTable::new(&["날짜", "주문 수", "매출"])
+            .numeric(&[1, 2])
+            .rows(
+                report
+                    .daily_sales
+                    .iter()
+                    .map(|d| vec![d.date.into(), d.order_count.into(), d.total_amount.into()]),
+            )
+            .totals(vec![
+                "합계".into(),
+                report
+                    .daily_sales
+                    .iter()
+                    .map(|d| d.order_count)
+                    .sum::<u32>()
+                    .into(),
+                report
+                    .daily_sales
+                    .iter()
+                    .map(|d| d.total_amount)
+                    .sum::<Decimal>()
+                    .into(),
+            ]);
+
+        ReportDocument::new("매출 요약 보고서", report.generated_at)
+            .with_subtitle(format!("기간: {} ~ {}", start_date, end_date))
+            .section(
+                ReportSection::new("요약")
+                    .item("총 주문 수", report.total_orders)
+                    .item("총 매출", report.total_revenue)
+                    .item("총 판매 수량", report.total_items_sold)
+                    .item("평균 주문 금액", report.average_order_value),
+            )
+            .section(
+                ReportSection::new("상위 판매 제품")
+                    .table(top_products)
+                    .chart(BarChart::new(
+                        "제품별 매출",
+                        report
+                            .top_selling_products
+                            .iter()
+                            .map(|p| (p.name.clone(), decimal_to_f64(p.total_revenue))),
+                    )),
+            )
+            .section(
+                ReportSection::new("주문 상태별 매출")
+                    .table(by_status)
+                    .chart(BarChart::new(
+                        "상태별 주문 수",
+                        report
+                            .sales_by_status
+                            .iter()
+                            .map(|s| (s.status.clone(), s.order_count as f64)),
+                    )),
+            )
+            .section(
+                ReportSection::new("일별 매출")
+                    .table(daily)
+                    .chart(BarChart::new(
+                        "일별 매출",
+                        report
+                            .daily_sales
+                            .iter()
+                            .map(|d| (d.date.to_string(), decimal_to_f64(d.total_amount))),
+                    )),
+            )
+    }
+
+    fn inventory_status_document(&self, report: &InventoryStatusReport) -> ReportDocument {
+        let categories = ReportTable::new(&[
+            "카테고리",
+            "제품 수",
+            "총 재고",
+            "재고 가치",
+            "제품당 평균 재고",
+        ])
+        .numeric(&[1, 2, 3, 4])
+        .rows(report.inventory_by_category.iter().map(|c| {
+            vec![
+                c.category.clone().into(),
+                c.product_count.into(),
+                c.total_stock.into(),
+                c.total_value.into(),
+                c.average_stock_per_product.round_dp(2).into(),
+            ]
+        }))
+        .totals(vec![
+            "합계".into(),
             report
-                .top_selling_products
+                .inventory_by_category
                 .iter()
-                .map(|p| format!(
-                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
-                    p.name, p.sku, p.quantity_sold, p.total_revenue
-                ))
-                .collect::<Vec<_>>()
-                .join("")
-        );
-        Ok(html)
+                .map(|c| c.product_count)
+                .sum::<u32>()
+                .into(),
+            report
+                .inventory_by_category
+                .iter()
+                .map(|c| c.total_stock)
+                .sum::<u32>()
+                .into(),
+            report
+                .inventory_by_category
+                .iter()
+                .map(|c| c.total_value)
+                .sum::<Decimal>()
+                .into(),
+            CellValue::Empty,
+        ]);
+
+        let low_stock = ReportTable::new(&[
+            "제품명",
+            "SKU",
+            "현재 재고",
+            "재주문 레벨",
+            "권장 주문량",
+            "재고 가치",
+        ])
+        .numeric(&[2, 3, 4, 5])
+        .rows(report.low_stock_items.iter().map(|item| {
+            vec![
+                item.name.clone().into(),
+                item.sku.clone().into(),
+                item.current_stock.into(),
+                item.reorder_level.into(),
+                item.suggested_reorder_quantity.into(),
+                item.stock_value.into(),
+            ]
+        }))
+        .totals(vec![
+            "합계".into(),
+            CellValue::Empty,
+            report
+                .low_stock_items
+                .iter()
+                .map(|item| item.current_stock)
+                .sum::<u32>()
+                .into(),
+            CellValue::Empty,
+            report
+                .low_stock_items
+                .iter()
+                .map(|item| item.suggested_reorder_quantity)
+                .sum::<u32>()
+                .into(),
+            report
+                .low_stock_items
+                .iter()
+                .map(|item| item.stock_value)
+                .sum::<Decimal>()
+                .into(),
+        ]);
+
+        let out_of_stock = ReportTable::new(&["제품명", "SKU", "최종 재고일", "대기 주문"])
+            .numeric(&[3])
+            .rows(report.out_of_stock_items.iter().map(|item| {
+                vec![
+                    item.name.clone().into(),
+                    item.sku.clone().into(),
+                    item.last_stock_date.into(),
+                    item.pending_orders.into(),
+                ]
+            }))
+            .totals(vec![
+                "합계".into(),
+                CellValue::Empty,
+                CellValue::Empty,
+                report
+                    .out_of_stock_items
+                    .iter()
+                    .map(|item| item.pending_orders)
+                    .sum::<u32>()
+                    .into(),
+            ]);
+
+        let movements = ReportTable::new(&["날짜", "유형", "수량", "금액"])
+            .numeric(&[2, 3])
+            .rows(report.stock_movements.iter().map(|m| {
+                vec![
+                    m.date.into(),
+                    m.movement_type.clone().into(),
+                    m.total_quantity.into(),
+                    m.total_value.into(),
+                ]
+            }))
+            .totals(vec![
+                "합계".into(),
+                CellValue::Empty,
+                report
+                    .stock_movements
+                    .iter()
+                    .map(|m| m.total_quantity)
+                    .sum::<i32>()
+                    .into(),
+                report
+                    .stock_movements
+                    .iter()
+                    .map(|m| m.total_value)
+                    .sum::<Decimal>()
+                    .into(),
+            ]);
+
+        ReportDocument::new("재고 상태 보고서", report.generated_at)
+            .section(
+                ReportSection::new("요약")
+                    .item("총 제품 수", report.total_products)
+                    .item("총 재고 가치", report.total_stock_value)
+                    .item("저재고 아이템", report.low_stock_items.len())
+                    .item("품절 아이템", report.out_of_stock_items.len()),
+            )
+            .section(
+                ReportSection::new("카테고리별 재고")
+                    .table(categories)
+                    .chart(BarChart::new(
+                        "카테고리별 재고 가치",
+                        report
+                            .inventory_by_category
+                            .iter()
+                            .map(|c| (c.category.clone(), decimal_to_f64(c.total_value))),
+                    )),
+            )
+            .section(
+                ReportSection::new("저재고 아이템")
+                    .table(low_stock)
+                    .chart(BarChart::new(
+                        "저재고 아이템 현재 재고",
+                        report
+                            .low_stock_items
+                            .iter()
+                            .map(|item| (item.name.clone(), item.current_stock as f64)),
+                    )),
+            )
+            .section(ReportSection::new("품절 아이템").table(out_of_stock))
+            .section(
+                ReportSection::new("재고 이동")
+                    .table(movements)
+                    .chart(BarChart::new(
+                        "재고 이동 수량",
+                        report.stock_movements.iter().map(|m| {
+                            (
+                                format!("{} {}", m.date, m.movement_type),
+                                m.total_quantity as f64,
+                            )
+                        }),
+                    )),
+            )
     }
 
-    fn generate_inventory_status_html(&self, _report: &InventoryStatusReport) -> ErpResult<String> {
-        // 기본 HTML 구현
-        Ok(
-            "<html><body><h1>재고 상태 보고서</h1><p>HTML 형식 구현 중...</p></body></html>"
-                .to_string(),
-        )
+    fn customer_analysis_document(&self, report: &CustomerAnalysisReport) -> ReportDocument {
+        let top_customers = ReportTable::new(&[
+            "고객명",
+            "이메일",
+            "주문 수",
+            "총 구매액",
+            "평균 주문 금액",
+            "최종 주문일",
+        ])
+        .numeric(&[2, 3, 4])
+        .rows(report.top_customers.iter().map(|c| {
+            vec![
+                c.name.clone().into(),
+                c.email.clone().into(),
+                c.total_orders.into(),
+                c.total_spent.into(),
+                c.average_order_value.into(),
+                c.last_order_date.into(),
+            ]
+        }))
+        .totals(vec![
+            "합계".into(),
+            CellValue::Empty,
+            report
+                .top_customers
+                .iter()
+                .map(|c| c.total_orders)
+                .sum::<u32>()
+                .into(),
+            report
+                .top_customers
+                .iter()
+                .map(|c| c.total_spent)
+                .sum::<Decimal>()
+                .into(),
+            CellValue::Empty,
+            CellValue::Empty,
+        ]);
+
+        let segments = ReportTable::new(&[
+            "세그먼트",
+            "고객 수",
+            "총 매출",
+            "평균 구매 빈도",
+            "이탈 예측",
+        ])
+        .numeric(&[1, 2, 3, 4])
+        .rows(report.customer_segments.iter().map(|s| {
+            vec![
+                s.segment_name.clone().into(),
+                s.customer_count.into(),
+                s.total_revenue.into(),
+                s.average_order_frequency.round_dp(2).into(),
+                s.churn_predicted_count.into(),
+            ]
+        }))
+        .totals(vec![
+            "합계".into(),
+            report
+                .customer_segments
+                .iter()
+                .map(|s| s.customer_count)
+                .sum::<u32>()
+                .into(),
+            report
+                .customer_segments
+                .iter()
+                .map(|s| s.total_revenue)
+                .sum::<Decimal>()
+                .into(),
+            CellValue::Empty,
+            report
+                .customer_segments
+                .iter()
+                .map(|s| s.churn_predicted_count)
+                .sum::<u32>()
+                .into(),
+        ]);
+
+        let rfm = ReportTable::new(&[
+            "고객명",
+            "최근 구매 경과일",
+            "구매 빈도",
+            "구매 금액",
+            "R/F/M",
+            "세그먼트",
+            "이탈 예측",
+        ])
+        .numeric(&[1, 2, 3])
+        .rows(report.rfm_scores.iter().map(|s| {
+            vec![
+                s.name.clone().into(),
+                s.recency_days.into(),
+                s.frequency.into(),
+                s.monetary.into(),
+                format!(
+                    "{}/{}/{}",
+                    s.recency_score, s.frequency_score, s.monetary_score
+                )
+                .into(),
+                s.segment.to_string().into(),
+                s.churn_predicted.into(),
+            ]
+        }));
+
+        let geography = ReportTable::new(&["국가", "시/도", "도시", "고객 수", "총 매출"])
+            .numeric(&[3, 4])
+            .rows(report.geographic_distribution.iter().map(|g| {
+                vec![
+                    g.country.clone().into(),
+                    g.state_province.clone().into(),
+                    g.city.clone().into(),
+                    g.customer_count.into(),
+                    g.total_revenue.into(),
+                ]
+            }))
+            .totals(vec![
+                "합계".into(),
+                CellValue::Empty,
+                CellValue::Empty,
+                report
+                    .geographic_distribution
+                    .iter()
+                    .map(|g| g.customer_count)
+                    .sum::<u32>()
+                    .into(),
+                report
+                    .geographic_distribution
+                    .iter()
+                    .map(|g| g.total_revenue)
+                    .sum::<Decimal>()
+                    .into(),
+            ]);
+
+        let lifecycle = &report.customer_lifecycle;
+
+        ReportDocument::new("고객 분석 보고서", report.generated_at)
+            .with_subtitle(format!("분석 기간: {}개월", report.analysis_period_months))
+            .section(
+                ReportSection::new("요약")
+                    .item("총 고객 수", report.total_customers)
+                    .item("활성 고객 수", report.active_customers)
+                    .item("신규 고객 수", report.new_customers),
+            )
+            .section(
+                ReportSection::new("고객 생애주기")
+                    .item("신규 고객", lifecycle.new_customers)
+                    .item("재구매 고객", lifecycle.returning_customers)
+                    .item("이탈률(%)", lifecycle.churn_rate)
+                    .item("고객 생애 가치", lifecycle.customer_lifetime_value)
+                    .item(
+                        "평균 고객 유지 기간(일)",
+                        lifecycle.average_customer_lifespan_days,
+                    ),
+            )
+            .section(
+                ReportSection::new("상위 고객")
+                    .table(top_customers)
+                    .chart(BarChart::new(
+                        "고객별 구매액",
+                        report
+                            .top_customers
+                            .iter()
+                            .map(|c| (c.name.clone(), decimal_to_f64(c.total_spent))),
+                    )),
+            )
+            .section(
+                ReportSection::new("고객 세그먼트")
+                    .table(segments)
+                    .chart(BarChart::new(
+                        "세그먼트별 고객 수",
+                        report
+                            .customer_segments
+                            .iter()
+                            .map(|s| (s.segment_name.clone(), s.customer_count as f64)),
+                    )),
+            )
+            .section(ReportSection::new("RFM 점수").table(rfm))
+            .section(
+                ReportSection::new("지역별 분포")
+                    .table(geography)
+                    .chart(BarChart::new(
+                        "지역별 고객 수",
+                        report.geographic_distribution.iter().map(|g| {
+                            (
+                                g.city.clone().unwrap_or_else(|| g.country.clone()),
+                                g.customer_count as f64,
+                            )
+                        }),
+                    )),
+            )
+    }
+
+    fn financial_overview_document(&self, report: &FinancialOverviewReport) -> ReportDocument {
+        let (start_date, end_date) = report.period.to_date_range();
+        let revenue = &report.revenue_summary;
+        let expenses = &report.expense_summary;
+        let profit = &report.profit_analysis;
+        let cash_flow = &report.cash_flow;
+        let payments = &report.payment_analytics;
+        let ratios = &report.financial_ratios;
+
+        let revenue_table = ReportTable::new(&["항목", "금액"])
+            .numeric(&[1])
+            .row(vec!["제품 매출".into(), revenue.product_revenue.into()])
+            .row(vec!["서비스 매출".into(), revenue.service_revenue.into()])
+            .row(vec!["반복 매출".into(), revenue.recurring_revenue.into()])
+            .row(vec!["일회성 매출".into(), revenue.one_time_revenue.into()])
+            .totals(vec!["총 매출".into(), revenue.total_revenue.into()]);
+
+        let expense_table = ReportTable::new(&["항목", "금액"])
+            .numeric(&[1])
+            .row(vec!["매출원가".into(), expenses.cost_of_goods_sold.into()])
+            .row(vec!["운영비용".into(), expenses.operating_expenses.into()])
+            .row(vec![
+                "마케팅비용".into(),
+                expenses.marketing_expenses.into(),
+            ])
+            .row(vec![
+                "관리비용".into(),
+                expenses.administrative_expenses.into(),
+            ])
+            .totals(vec!["총 비용".into(), expenses.total_expenses.into()]);
+
+        let profit_table = ReportTable::new(&["항목", "금액", "이익률(%)"])
+            .numeric(&[1, 2])
+            .row(vec![
+                "총 이익".into(),
+                profit.gross_profit.into(),
+                profit.gross_margin.into(),
+            ])
+            .row(vec![
+                "영업 이익".into(),
+                profit.operating_profit.into(),
+                profit.operating_margin.into(),
+            ])
+            .row(vec![
+                "순 이익".into(),
+                profit.net_profit.into(),
+                profit.net_margin.into(),
+            ]);
+
+        let cash_flow_table = ReportTable::new(&["항목", "금액"])
+            .numeric(&[1])
+            .row(vec!["현금 유입".into(), cash_flow.cash_inflow.into()])
+            .row(vec!["현금 유출".into(), cash_flow.cash_outflow.into()])
+            .row(vec![
+                "영업 현금 흐름".into(),
+                cash_flow.operating_cash_flow.into(),
+            ])
+            .row(vec![
+                "투자 현금 흐름".into(),
+                cash_flow.investing_cash_flow.into(),
+            ])
+            .row(vec![
+                "재무 현금 흐름".into(),
+                cash_flow.financing_cash_flow.into(),
+            ])
+            .totals(vec!["순 현금 흐름".into(), cash_flow.net_cash_flow.into()]);
+
+        let payment_methods = ReportTable::new(&["결제 방법", "거래 수", "금액", "비율(%)"])
+            .numeric(&[1, 2, 3])
+            .rows(payments.payment_methods_breakdown.iter().map(|m| {
+                vec![
+                    m.payment_method.clone().into(),
+                    m.transaction_count.into(),
+                    m.total_amount.into(),
+                    m.percentage_of_total.into(),
+                ]
+            }))
+            .totals(vec![
+                "합계".into(),
+                payments
+                    .payment_methods_breakdown
+                    .iter()
+                    .map(|m| m.transaction_count)
+                    .sum::<u32>()
+                    .into(),
+                payments
+                    .payment_methods_breakdown
+                    .iter()
+                    .map(|m| m.total_amount)
+                    .sum::<Decimal>()
+                    .into(),
+                CellValue::Empty,
+            ]);
+
+        ReportDocument::new("재무 개요 보고서", report.generated_at)
+            .with_subtitle(format!("기간: {} ~ {}", start_date, end_date))
+            .section(
+                ReportSection::new("수익 요약")
+                    .item("매출 성장률(%)", revenue.revenue_growth_rate)
+                    .table(revenue_table)
+                    .chart(BarChart::new(
+                        "매출 구성",
+                        vec![
+                            ("제품".to_string(), decimal_to_f64(revenue.product_revenue)),
+                            (
+                                "서비스".to_string(),
+                                decimal_to_f64(revenue.service_revenue),
+                            ),
+                            (
+                                "반복".to_string(),
+                                decimal_to_f64(revenue.recurring_revenue),
+                            ),
+                            (
+                                "일회성".to_string(),
+                                decimal_to_f64(revenue.one_time_revenue),
+                            ),
+                        ],
+                    )),
+            )
+            .section(
+                ReportSection::new("비용 요약")
+                    .table(expense_table)
+                    .chart(BarChart::new(
+                        "비용 구성",
+                        vec![
+                            (
+                                "매출원가".to_string(),
+                                decimal_to_f64(expenses.cost_of_goods_sold),
+                            ),
+                            (
+                                "운영".to_string(),
+                                decimal_to_f64(expenses.operating_expenses),
+                            ),
+                            (
+                                "마케팅".to_string(),
+                                decimal_to_f64(expenses.marketing_expenses),
+                            ),
+                            (
+                                "관리".to_string(),
+                                decimal_to_f64(expenses.administrative_expenses),
+                            ),
+                        ],
+                    )),
+            )
+            .section(
+                ReportSection::new("수익성 분석")
+                    .table(profit_table)
+                    .chart(BarChart::new(
+                        "매출 / 비용 / 순이익",
+                        vec![
+                            ("매출".to_string(), decimal_to_f64(revenue.total_revenue)),
+                            ("비용".to_string(), decimal_to_f64(expenses.total_expenses)),
+                            ("순이익".to_string(), decimal_to_f64(profit.net_profit)),
+                        ],
+                    )),
+            )
+            .section(ReportSection::new("현금 흐름").table(cash_flow_table))
+            .section(
+                ReportSection::new("결제 분석")
+                    .item("수금액", payments.payments_received)
+                    .item("미수금", payments.outstanding_receivables)
+                    .item("연체 금액", payments.overdue_payments)
+                    .item("평균 결제 기간(일)", payments.average_payment_terms_days)
+                    .table(payment_methods)
+                    .chart(BarChart::new(
+                        "결제 방법별 금액",
+                        payments
+                            .payment_methods_breakdown
+                            .iter()
+                            .map(|m| (m.payment_method.clone(), decimal_to_f64(m.total_amount))),
+                    )),
+            )
+            .section(
+                ReportSection::new("재무 비율")
+                    .item("유동 비율", ratios.current_ratio)
+                    .item("당좌 비율", ratios.quick_ratio)
+                    .item("부채 비율", ratios.debt_to_equity_ratio)
+                    .item("투자 수익률(%)", ratios.return_on_investment)
+                    .item("재고 회전율", ratios.inventory_turnover)
+                    .item("매출채권 회전율", ratios.receivables_turnover),
+            )
+    }
+
+    // HTML 생성 메서드들
+    fn generate_sales_summary_html(
+        &self,
+        report: &SalesSummaryReport,
+        include_charts: bool,
+    ) -> ErpResult<String> {
+        Ok(templates::render_html(
+            &self.sales_summary_document(report),
+            include_charts,
+        ))
+    }
+
+    fn generate_inventory_status_html(
+        &self,
+        report: &InventoryStatusReport,
+        include_charts: bool,
+    ) -> ErpResult<String> {
+        Ok(templates::render_html(
+            &self.inventory_status_document(report),
+            include_charts,
+        ))
     }
 
     fn generate_customer_analysis_html(
         &self,
-        _report: &CustomerAnalysisReport,
+        report: &CustomerAnalysisReport,
+        include_charts: bool,
     ) -> ErpResult<String> {
-        // 기본 HTML 구현
-        Ok(
-            "<html><body><h1>고객 분석 보고서</h1><p>HTML 형식 구현 중...</p></body></html>"
-                .to_string(),
-        )
+        Ok(templates::render_html(
+            &self.customer_analysis_document(report),
+            include_charts,
+        ))
     }
 
     fn generate_financial_overview_html(
         &self,
         report: &FinancialOverviewReport,
+        include_charts: bool,
     ) -> ErpResult<String> {
-        let html = format!(
-            r#"
-<!DOCTYPE html>
-<html>
-<head>
-    <title>재무 개요 보고서</title>
-    <meta charset="UTF-8">
-    <style>
-        body {{ font-family: Arial, sans-serif; margin: 20px; }}
-        table {{ border-collapse: collapse; width: 100%; margin: 20px 0; }}
-        th, td {{ border: 1px solid #ddd; padding: 8px; text-align: left; }}
-        th {{ background-color: #f2f2f2; }}
-        .summary {{ margin-bottom: 20px; }}
-        .summary h2 {{ color: #333; }}
-        .chart-placeholder {{
-            width: 100%;
-            height: 300px;
-            background: #f0f0f0;
-            display: flex;
-            align-items: center;
-            justify-content: center;
-            margin: 20px 0;
-            border: 1px solid #ddd;
-        }}
-    </style>
-</head>
-<body>
-    <h1>재무 개요 보고서</h1>
-    <div class="summary">
-        <p><strong>생성 시간:</strong> {}</p>
-    </div>
-
-    <h2>수익 요약</h2>
-    <table>
-        <tr><th>항목</th><th>금액</th></tr>
-        <tr><td>총 매출</td><td>{}</td></tr>
-        <tr><td>제품 매출</td><td>{}</td></tr>
-        <tr><td>서비스 매출</td><td>{}</td></tr>
-        <tr><td>반복 매출</td><td>{}</td></tr>
-        <tr><td>일회성 매출</td><td>{}</td></tr>
-    </table>
-
-    <h2>비용 요약</h2>
-    <table>
-        <tr><th>항목</th><th>금액</th></tr>
-        <tr><td>총 비용</td><td>{}</td></tr>
-        <tr><td>매출원가</td><td>{}</td></tr>
-        <tr><td>운영비용</td><td>{}</td></tr>
-        <tr><td>마케팅비용</td><td>{}</td></tr>
-        <tr><td>관리비용</td><td>{}</td></tr>
-    </table>
-
-    <h2>수익성 분석</h2>
-    <table>
-        <tr><th>항목</th><th>값</th></tr>
-        <tr><td>총 이익</td><td>{}</td></tr>
-        <tr><td>순 이익</td><td>{}</td></tr>
-        <tr><td>영업 이익</td><td>{}</td></tr>
-        <tr><td>총 이익률</td><td>{}%</td></tr>
-        <tr><td>순 이익률</td><td>{}%</td></tr>
-        <tr><td>영업 이익률</td><td>{}%</td></tr>
-    </table>
-
-    <h2>현금 흐름</h2>
-    <table>
-        <tr><th>항목</th><th>금액</th></tr>
-        <tr><td>현금 유입</td><td>{}</td></tr>
-        <tr><td>현금 유출</td><td>{}</td></tr>
-        <tr><td>순 현금 흐름</td><td>{}</td></tr>
-        <tr><td>영업 현금 흐름</td><td>{}</td></tr>
-    </table>
-
-    <div class="chart-placeholder">
-        <p>차트 영역 (차트 라이브러리 통합 예정)</p>
-    </div>
-</body>
-</html>"#,
-            report.generated_at.format("%Y-%m-%d %H:%M:%S"),
-            report.revenue_summary.total_revenue,
-            report.revenue_summary.product_revenue,
-            report.revenue_summary.service_revenue,
-            report.revenue_summary.recurring_revenue,
-            report.revenue_summary.one_time_revenue,
-            report.expense_summary.total_expenses,
-            report.expense_summary.cost_of_goods_sold,
-            report.expense_summary.operating_expenses,
-            report.expense_summary.marketing_expenses,
-            report.expense_summary.administrative_expenses,
-            report.profit_analysis.gross_profit,
-            report.profit_analysis.net_profit,
-            report.profit_analysis.operating_profit,
-            report.profit_analysis.gross_margin,
-            report.profit_analysis.net_margin,
-            report.profit_analysis.operating_margin,
-            report.cash_flow.cash_inflow,
-            report.cash_flow.cash_outflow,
-            report.cash_flow.net_cash_flow,
-            report.cash_flow.operating_cash_flow,
-        );
-        Ok(html)
+        Ok(templates::render_html(
+            &self.financial_overview_document(report),
+            include_charts,
+        ))
     }
 
     // PDF 생성 메서드들
-    fn generate_sales_summary_pdf(&self, report: &SalesSummaryReport) -> ErpResult<Vec<u8>> {
-        let (doc, page1, layer1) =
-            PdfDocument::new("매출 요약 보고서", Mm(210.0), Mm(297.0), "Layer 1");
-        let font = doc
-            .add_builtin_font(BuiltinFont::Helvetica)
-            .map_err(|e| ErpError::internal(format!("PDF 폰트 로드 실패: {:?}", e)))?;
-        let current_layer = doc.get_page(page1).get_layer(layer1);
-
-        // 제목
-        current_layer.use_text("매출 요약 보고서", 24.0, Mm(20.0), Mm(270.0), &font);
-
-        // 생성 시간
-        let generated_at = format!(
-            "생성 시간: {}",
-            report.generated_at.format("%Y-%m-%d %H:%M:%S")
-        );
-        current_layer.use_text(&generated_at, 12.0, Mm(20.0), Mm(250.0), &font);
-
-        // 요약 정보
-        let y_pos = 230.0;
-        current_layer.use_text(
-            format!("총 주문 수: {}", report.total_orders),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos),
-            &font,
-        );
-        current_layer.use_text(
-            format!("총 매출: {}", report.total_revenue),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos - 10.0),
-            &font,
-        );
-        current_layer.use_text(
-            format!("총 판매 수량: {}", report.total_items_sold),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos - 20.0),
-            &font,
-        );
-        current_layer.use_text(
-            format!("평균 주문 금액: {}", report.average_order_value),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos - 30.0),
-            &font,
-        );
-
-        // 상위 판매 제품
-        let mut y = y_pos - 50.0;
-        current_layer.use_text("상위 판매 제품", 14.0, Mm(20.0), Mm(y), &font);
-        y -= 15.0;
-
-        for product in &report.top_selling_products {
-            let product_line = format!(
-                "{} ({}): {} 판매, 매출 {}",
-                product.name, product.sku, product.quantity_sold, product.total_revenue
-            );
-            current_layer.use_text(&product_line, 10.0, Mm(25.0), Mm(y), &font);
-            y -= 12.0;
-            if y < 30.0 {
-                break;
-            } // 페이지 하단 근처면 중지
-        }
+    fn generate_sales_summary_pdf(
+        &self,
+        report: &SalesSummaryReport,
+        include_charts: bool,
+    ) -> ErpResult<Vec<u8>> {
+        templates::render_pdf(&self.sales_summary_document(report), include_charts)
+    }
 
-        doc.save_to_bytes()
-            .map_err(|e| ErpError::internal(format!("PDF 저장 실패: {:?}", e)))
+    fn generate_inventory_status_pdf(
+        &self,
+        report: &InventoryStatusReport,
+        include_charts: bool,
+    ) -> ErpResult<Vec<u8>> {
+        templates::render_pdf(&self.inventory_status_document(report), include_charts)
     }
 
-    fn generate_inventory_status_pdf(&self, report: &InventoryStatusReport) -> ErpResult<Vec<u8>> {
-        let (doc, page1, layer1) =
-            PdfDocument::new("재고 상태 보고서", Mm(210.0), Mm(297.0), "Layer 1");
-        let font = doc
-            .add_builtin_font(BuiltinFont::Helvetica)
-            .map_err(|e| ErpError::internal(format!("PDF 폰트 로드 실패: {:?}", e)))?;
-        let current_layer = doc.get_page(page1).get_layer(layer1);
+    fn generate_customer_analysis_pdf(
+        &self,
+        report: &CustomerAnalysisReport,
+        include_charts: bool,
+    ) -> ErpResult<Vec<u8>> {
+        templates::render_pdf(&self.customer_analysis_document(report), include_charts)
+    }
 
-        // 제목
-        current_layer.use_text("재고 상태 보고서", 24.0, Mm(20.0), Mm(270.0), &font);
+    fn generate_financial_overview_pdf(
+        &self,
+        report: &FinancialOverviewReport,
+        include_charts: bool,
+    ) -> ErpResult<Vec<u8>> {
+        templates::render_pdf(&self.financial_overview_document(report), include_charts)
+    }
 
-        // 생성 시간
-        let generated_at = format!(
-            "생성 시간: {}",
-            report.generated_at.format("%Y-%m-%d %H:%M:%S")
-        );
-        current_layer.use_text(&generated_at, 12.0, Mm(20.0), Mm(250.0), &font);
-
-        // 요약 정보
-        let y_pos = 230.0;
-        current_layer.use_text(
-            format!("총 제품 수: {}", report.total_products),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos),
-            &font,
-        );
-        current_layer.use_text(
-            format!("총 재고 가치: {}", report.total_stock_value),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos - 10.0),
-            &font,
-        );
-        current_layer.use_text(
-            format!("저재고 아이템: {}", report.low_stock_items.len()),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos - 20.0),
-            &font,
-        );
-        current_layer.use_text(
-            format!("품절 아이템: {}", report.out_of_stock_items.len()),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos - 30.0),
-            &font,
-        );
-
-        // 저재고 아이템
-        if !report.low_stock_items.is_empty() {
-            let mut y = y_pos - 50.0;
-            current_layer.use_text("저재고 아이템", 14.0, Mm(20.0), Mm(y), &font);
-            y -= 15.0;
-
-            for item in &report.low_stock_items {
-                let item_line = format!(
-                    "{} ({}): 현재 {}, 재주문 레벨 {}",
-                    item.name, item.sku, item.current_stock, item.reorder_level
-                );
-                current_layer.use_text(&item_line, 10.0, Mm(25.0), Mm(y), &font);
-                y -= 12.0;
-                if y < 30.0 {
-                    break;
-                }
-            }
-        }
+    // XLSX 생성 메서드들
+    fn generate_sales_summary_xlsx(&self, report: &SalesSummaryReport) -> ErpResult<Vec<u8>> {
+        templates::render_xlsx(&self.sales_summary_document(report))
+    }
 
-        doc.save_to_bytes()
-            .map_err(|e| ErpError::internal(format!("PDF 저장 실패: {:?}", e)))
+    fn generate_inventory_status_xlsx(&self, report: &InventoryStatusReport) -> ErpResult<Vec<u8>> {
+        templates::render_xlsx(&self.inventory_status_document(report))
     }
 
-    fn generate_customer_analysis_pdf(
+    fn generate_customer_analysis_xlsx(
         &self,
         report: &CustomerAnalysisReport,
     ) -> ErpResult<Vec<u8>> {
-        let (doc, page1, layer1) =
-            PdfDocument::new("고객 분석 보고서", Mm(210.0), Mm(297.0), "Layer 1");
-        let font = doc
-            .add_builtin_font(BuiltinFont::Helvetica)
-            .map_err(|e| ErpError::internal(format!("PDF 폰트 로드 실패: {:?}", e)))?;
-        let current_layer = doc.get_page(page1).get_layer(layer1);
-
-        // 제목
-        current_layer.use_text("고객 분석 보고서", 24.0, Mm(20.0), Mm(270.0), &font);
-
-        // 생성 시간
-        let generated_at = format!(
-            "생성 시간: {}",
-            report.generated_at.format("%Y-%m-%d %H:%M:%S")
-        );
-        current_layer.use_text(&generated_at, 12.0, Mm(20.0), Mm(250.0), &font);
-
-        // 요약 정보
-        let y_pos = 230.0;
-        current_layer.use_text(
-            format!("분석 기간: {}개월", report.analysis_period_months),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos),
-            &font,
-        );
-        current_layer.use_text(
-            format!("총 고객 수: {}", report.total_customers),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos - 10.0),
-            &font,
-        );
-        current_layer.use_text(
-            format!("활성 고객: {}", report.active_customers),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos - 20.0),
-            &font,
-        );
-        current_layer.use_text(
-            format!("신규 고객: {}", report.new_customers),
-            12.0,
-            Mm(20.0),
-            Mm(y_pos - 30.0),
-            &font,
-        );
-
-        // 상위 고객
-        if !report.top_customers.is_empty() {
-            let mut y = y_pos - 50.0;
-            current_layer.use_text("상위 고객", 14.0, Mm(20.0), Mm(y), &font);
-            y -= 15.0;
-
-            for customer in &report.top_customers {
-                let customer_line = format!(
-                    "{}: {} 주문, 총 {}",
-                    customer.name, customer.total_orders, customer.total_spent
-                );
-                current_layer.use_text(&customer_line, 10.0, Mm(25.0), Mm(y), &font);
-                y -= 12.0;
-                if y < 30.0 {
-                    break;
-                }
-            }
-        }
-
-        doc.save_to_bytes()
-            .map_err(|e| ErpError::internal(format!("PDF 저장 실패: {:?}", e)))
+        templates::render_xlsx(&self.customer_analysis_document(report))
     }
 
-    fn generate_financial_overview_pdf(
+    fn generate_financial_overview_xlsx(
         &self,
         report: &FinancialOverviewReport,
     ) -> ErpResult<Vec<u8>> {
-        let (doc, page1, layer1) =
-            PdfDocument::new("재무 개요 보고서", Mm(210.0), Mm(297.0), "Layer 1");
-        let font = doc
-            .add_builtin_font(BuiltinFont::Helvetica)
-            .map_err(|e| ErpError::internal(format!("PDF 폰트 로드 실패: {:?}", e)))?;
-        let current_layer = doc.get_page(page1).get_layer(layer1);
-
-        // 제목
-        current_layer.use_text("재무 개요 보고서", 24.0, Mm(20.0), Mm(270.0), &font);
-
-        // 생성 시간
-        let generated_at = format!(
-            "생성 시간: {}",
-            report.generated_at.format("%Y-%m-%d %H:%M:%S")
-        );
-        current_layer.use_text(&generated_at, 12.0, Mm(20.0), Mm(250.0), &font);
-
-        // 수익 요약
-        let y_pos = 230.0;
-        current_layer.use_text("수익 요약", 14.0, Mm(20.0), Mm(y_pos), &font);
-        current_layer.use_text(
-            format!("총 수익: {}", report.revenue_summary.total_revenue),
-            12.0,
-            Mm(25.0),
-            Mm(y_pos - 15.0),
-            &font,
-        );
-        current_layer.use_text(
-            format!("제품 수익: {}", report.revenue_summary.product_revenue),
-            12.0,
-            Mm(25.0),
-            Mm(y_pos - 25.0),
-            &font,
-        );
-        current_layer.use_text(
-            format!("서비스 수익: {}", report.revenue_summary.service_revenue),
-            12.0,
-            Mm(25.0),
-            Mm(y_pos - 35.0),
-            &font,
-        );
-
-        // 수익성 분석
-        let y = y_pos - 55.0;
-        current_layer.use_text("수익성 분석", 14.0, Mm(20.0), Mm(y), &font);
-        current_layer.use_text(
-            format!("총 이익: {}", report.profit_analysis.gross_profit),
-            12.0,
-            Mm(25.0),
-            Mm(y - 15.0),
-            &font,
-        );
-        current_layer.use_text(
-            format!("순이익: {}", report.profit_analysis.net_profit),
-            12.0,
-            Mm(25.0),
-            Mm(y - 25.0),
-            &font,
-        );
-        current_layer.use_text(
-            format!("영업 이익: {}", report.profit_analysis.operating_profit),
-            12.0,
-            Mm(25.0),
-            Mm(y - 35.0),
-            &font,
-        );
-
-        doc.save_to_bytes()
-            .map_err(|e| ErpError::internal(format!("PDF 저장 실패: {:?}", e)))
+        templates::render_xlsx(&self.financial_overview_document(report))
     }
 }
 
+/// 차트 렌더링용 변환
+fn decimal_to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(0.0)
+}
+
 // 추가 데이터 구조체들
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ReportSummary {
     pub period_months: u32,
     pub generated_at: chrono::DateTime<Utc>,
@@ -1280,4 +1813,154 @@ mod tests {
             assert!(reason.contains("1-120개월"));
         }
     }
+
+    #[tokio::test]
+    async fn test_customer_analysis_includes_rfm_scores() {
+        let repo = Arc::new(MockReportsRepository::new());
+        let service = ReportsService::new(repo);
+
+        let request = ReportRequest {
+            report_type: ReportType::CustomerAnalysis { months: 12 },
+            period: ReportPeriod::Monthly,
+            format: ReportFormat::Console,
+            output_path: None,
+            filters: ReportFilters::default(),
+            include_charts: false,
+        };
+
+        let report = service
+            .generate_customer_analysis(12, &request)
+            .await
+            .unwrap();
+        assert_eq!(report.rfm_scores.len(), 2);
+        assert!(report.rfm_scores.iter().any(|s| s.churn_predicted));
+        assert!(!report.customer_segments.is_empty());
+
+        let csv = service.generate_customer_analysis_csv(&report).unwrap();
+        assert!(csv.contains("RFM 점수"));
+    }
+
+    #[tokio::test]
+    async fn test_html_and_pdf_exports_for_all_reports() {
+        let repo = Arc::new(MockReportsRepository::new());
+        let service = ReportsService::new(repo);
+
+        let request = ReportRequest {
+            report_type: ReportType::InventoryStatus,
+            period: ReportPeriod::Monthly,
+            format: ReportFormat::Html,
+            output_path: None,
+            filters: ReportFilters::default(),
+            include_charts: true,
+        };
+
+        let inventory = service.generate_inventory_status(&request).await.unwrap();
+        let html = service
+            .generate_inventory_status_html(&inventory, true)
+            .unwrap();
+        assert!(html.contains("카테고리별 재고"));
+        assert!(html.contains("<svg"));
+        assert!(!html.contains("구현 중"));
+
+        let customers = service
+            .generate_customer_analysis(12, &request)
+            .await
+            .unwrap();
+        let html = service
+            .generate_customer_analysis_html(&customers, false)
+            .unwrap();
+        assert!(html.contains("고객 세그먼트"));
+        assert!(!html.contains("<svg"));
+
+        let sales = service.generate_sales_summary(&request).await.unwrap();
+        let financial = service.generate_financial_overview(&request).await.unwrap();
+        for pdf in [
+            service.generate_sales_summary_pdf(&sales, true).unwrap(),
+            service
+                .generate_inventory_status_pdf(&inventory, true)
+                .unwrap(),
+            service
+                .generate_customer_analysis_pdf(&customers, true)
+                .unwrap(),
+            service
+                .generate_financial_overview_pdf(&financial, true)
+                .unwrap(),
+        ] {
+            assert!(pdf.starts_with(b"%PDF"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_report_xlsx() {
+        let repo = Arc::new(MockReportsRepository::new());
+        let service = ReportsService::new(repo);
+        let dir = tempfile::tempdir().unwrap();
+        let output_path = dir.path().join("financial.xlsx");
+
+        let request = ReportRequest {
+            report_type: ReportType::FinancialOverview,
+            period: ReportPeriod::Monthly,
+            format: ReportFormat::Xlsx,
+            output_path: Some(output_path.to_string_lossy().to_string()),
+            filters: ReportFilters::default(),
+            include_charts: false,
+        };
+
+        let path = service
+            .export_report(&ReportType::FinancialOverview, &request)
+            .await
+            .unwrap();
+        let bytes = std::fs::read(path).unwrap();
+        assert!(bytes.starts_with(b"PK"));
+        assert!(String::from_utf8_lossy(&bytes).contains("수익성 분석"));
+    }
+
+    #[tokio::test]
+    async fn test_export_custom_report() {
+        use crate::modules::reports::custom::CustomReportSpec;
+
+        let repo = Arc::new(MockReportsRepository::new());
+        let service = ReportsService::new(repo);
+        let dir = tempfile::tempdir().unwrap();
+
+        let spec = CustomReportSpec::from_yaml(
+            "name: stock\ndataset: products\ndimensions: [category]\nmeasures:\n  - function: sum\n    field: quantity\n",
+        )
+        .unwrap();
+        let query = spec.build_query().unwrap();
+        let result = CustomReportResult {
+            name: spec.name.clone(),
+            title: spec.display_title().to_string(),
+            dataset: spec.dataset,
+            generated_at: Utc::now(),
+            date_range: query.date_range,
+            columns: query.columns,
+            rows: vec![
+                vec!["Electronics".into(), 12i64.into()],
+                vec!["Furniture".into(), 3i64.into()],
+            ],
+        };
+
+        for (format, file) in [
+            (ReportFormat::Csv, "stock.csv"),
+            (ReportFormat::Html, "stock.html"),
+            (ReportFormat::Xlsx, "stock.xlsx"),
+        ] {
+            let path = dir.path().join(file);
+            let written = service
+                .export_custom_report(&result, &format, path.to_str(), true)
+                .await
+                .unwrap();
+            let bytes = std::fs::read(written).unwrap();
+            assert!(String::from_utf8_lossy(&bytes).contains("Electronics"));
+        }
+
+        let csv = std::fs::read_to_string(dir.path().join("stock.csv")).unwrap();
+        assert!(csv.ends_with("합계,15\n"));
+
+        assert!(service
+            .export_custom_report(&result, &ReportFormat::Console, None, false)
+            .await
+            .is_err());
+    }
 }
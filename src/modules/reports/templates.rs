@@ -0,0 +1,833 @@
+//! 보고서 템플릿 계층
+//!
+//! 보고서 데이터를 형식 중립적인 [`ReportDocument`]로 구성한 뒤 HTML, PDF 또는 XLSX로 렌더링합니다.
+//! 모든 보고서 타입이 같은 섹션/표/합계/페이지 구성을 공유하도록 하기 위한 모듈입니다.
+
+use chrono::{DateTime, Utc};
+use printpdf::*;
+use std::fs::File;
+use std::io::BufReader;
+
+use crate::utils::error::{ErpError, ErpResult};
+use crate::utils::xlsx::{CellValue, Workbook, Worksheet};
+
+/// HTML 표 한 페이지에 들어가는 최대 행 수
+const HTML_ROWS_PER_PAGE: usize = 40;
+
+/// PDF 페이지 레이아웃 (A4, mm 단위)
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN_LEFT: f32 = 20.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - MARGIN_LEFT * 2.0;
+const CONTENT_TOP: f32 = PAGE_HEIGHT - 20.0;
+const CONTENT_BOTTOM: f32 = 22.0;
+const TABLE_ROW_HEIGHT: f32 = 6.5;
+const TABLE_FONT_SIZE: f32 = 8.5;
+const CHART_BAR_HEIGHT: f32 = 5.0;
+
+/// 한글 출력을 위해 우선 사용할 시스템 폰트 경로 (없으면 내장 Helvetica 사용)
+const PDF_FONT_CANDIDATES: &[&str] = &[
+    "/usr/share/fonts/truetype/nanum/NanumGothic.ttf",
+    "/usr/share/fonts/nanum/NanumGothic.ttf",
+    "/Library/Fonts/NanumGothic.ttf",
+    "C:\\Windows\\Fonts\\malgun.ttf",
+];
+
+/// 렌더링 대상 보고서 문서
+#[derive(Debug, Clone)]
+pub struct ReportDocument {
+    pub title: String,
+    pub generated_at: DateTime<Utc>,
+    pub subtitle: Option<String>,
+    pub sections: Vec<ReportSection>,
+}
+
+/// 보고서 섹션 (제목 + 요약 항목 + 표 + 차트)
+#[derive(Debug, Clone, Default)]
+pub struct ReportSection {
+    pub heading: String,
+    pub summary: Vec<(String, CellValue)>,
+    pub table: Option<ReportTable>,
+    pub chart: Option<BarChart>,
+}
+
+/// 보고서 표
+#[derive(Debug, Clone, Default)]
+pub struct ReportTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<CellValue>>,
+    pub totals: Option<Vec<CellValue>>,
+    /// 오른쪽 정렬할 숫자 컬럼 인덱스
+    pub numeric_columns: Vec<usize>,
+}
+
+/// 가로 막대 차트
+#[derive(Debug, Clone, Default)]
+pub struct BarChart {
+    pub title: String,
+    pub points: Vec<(String, f64)>,
+}
+
+impl ReportDocument {
+    pub fn new(title: impl Into<String>, generated_at: DateTime<Utc>) -> Self {
+        Self {
+            title: title.into(),
+            generated_at,
+            subtitle: None,
+            sections: Vec::new(),
+        }
+    }
+
+    pub fn with_subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+
+    pub fn section(mut self, section: ReportSection) -> Self {
+        self.sections.push(section);
+        self
+    }
+}
+
+impl ReportSection {
+    pub fn new(heading: impl Into<String>) -> Self {
+        Self {
+            heading: heading.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn item(mut self, label: impl Into<String>, value: impl Into<CellValue>) -> Self {
+        self.summary.push((label.into(), value.into()));
+        self
+    }
+
+    pub fn table(mut self, table: ReportTable) -> Self {
+        self.table = Some(table);
+        self
+    }
+
+    pub fn chart(mut self, chart: BarChart) -> Self {
+        self.chart = Some(chart);
+        self
+    }
+}
+
+impl ReportTable {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn numeric(mut self, columns: &[usize]) -> Self {
+        self.numeric_columns = columns.to_vec();
+        self
+    }
+
+    pub fn row(mut self, cells: Vec<CellValue>) -> Self {
+        self.rows.push(cells);
+        self
+    }
+
+    pub fn rows<I>(mut self, rows: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<CellValue>>,
+    {
+        self.rows.extend(rows);
+        self
+    }
+
+    pub fn totals(mut self, cells: Vec<CellValue>) -> Self {
+        self.totals = Some(cells);
+        self
+    }
+
+    fn is_numeric(&self, column: usize) -> bool {
+        self.numeric_columns.contains(&column)
+    }
+}
+
+impl BarChart {
+    pub fn new<I>(title: impl Into<String>, points: I) -> Self
+    where
+        I: IntoIterator<Item = (String, f64)>,
+    {
+        Self {
+            title: title.into(),
+            points: points.into_iter().collect(),
+        }
+    }
+
+    fn max_value(&self) -> f64 {
+        self.points
+            .iter()
+            .map(|(_, value)| value.abs())
+            .fold(0.0, f64::max)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTML
+// ---------------------------------------------------------------------------
+
+const HTML_STYLE: &str = r#"
+        body { font-family: 'Malgun Gothic', 'Nanum Gothic', Arial, sans-serif; margin: 20px; color: #222; }
+        h1 { margin-bottom: 4px; }
+        h2 { color: #333; border-bottom: 2px solid #4e79a7; padding-bottom: 4px; margin-top: 32px; }
+        .meta { color: #666; margin-bottom: 20px; }
+        table { border-collapse: collapse; width: 100%; margin: 12px 0; }
+        th, td { border: 1px solid #ddd; padding: 6px 8px; text-align: left; }
+        th { background-color: #f2f2f2; }
+        td.num, th.num { text-align: right; }
+        tfoot td { font-weight: bold; background-color: #fafafa; }
+        table.summary { width: auto; min-width: 40%; }
+        .page-label { color: #888; font-size: 0.85em; text-align: right; }
+        .page-break { page-break-after: always; break-after: page; }
+        .chart { margin: 16px 0; }
+        footer { margin-top: 40px; color: #888; font-size: 0.85em; }
+        @media print { thead { display: table-header-group; } tfoot { display: table-footer-group; } }
+"#;
+
+/// 문서를 HTML로 렌더링
+pub fn render_html(document: &ReportDocument, include_charts: bool) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n");
+    html.push_str(&format!(
+        "    <title>{}</title>\n    <meta charset=\"UTF-8\">\n    <style>{}    </style>\n",
+        escape_html(&document.title),
+        HTML_STYLE
+    ));
+    html.push_str("</head>\n<body>\n");
+    html.push_str(&format!("    <h1>{}</h1>\n", escape_html(&document.title)));
+    html.push_str(&format!(
+        "    <div class=\"meta\">생성 시간: {}",
+        document.generated_at.format("%Y-%m-%d %H:%M:%S")
+    ));
+    if let Some(subtitle) = &document.subtitle {
+        html.push_str(&format!(" · {}", escape_html(subtitle)));
+    }
+    html.push_str("</div>\n");
+
+    for section in &document.sections {
+        html.push_str(&format!(
+            "\n    <h2>{}</h2>\n",
+            escape_html(&section.heading)
+        ));
+
+        if !section.summary.is_empty() {
+            html.push_str("    <table class=\"summary\">\n");
+            for (label, value) in &section.summary {
+                html.push_str(&format!(
+                    "        <tr><th>{}</th><td class=\"num\">{}</td></tr>\n",
+                    escape_html(label),
+                    escape_html(&value.to_string())
+                ));
+            }
+            html.push_str("    </table>\n");
+        }
+
+        if let Some(table) = &section.table {
+            render_html_table(&mut html, table);
+        }
+
+        if include_charts {
+            if let Some(chart) = &section.chart {
+                html.push_str("    <div class=\"chart\">\n");
+                html.push_str(&render_svg_bar_chart(chart));
+                html.push_str("\n    </div>\n");
+            }
+        }
+    }
+
+    html.push_str(&format!(
+        "\n    <footer>{} · ERP CLI</footer>\n</body>\n</html>\n",
+        escape_html(&document.title)
+    ));
+    html
+}
+
+fn render_html_table(html: &mut String, table: &ReportTable) {
+    let header_cells = table
+        .headers
+        .iter()
+        .enumerate()
+        .map(|(i, h)| format!("<th{}>{}</th>", align_class(table, i), escape_html(h)))
+        .collect::<String>();
+
+    if table.rows.is_empty() {
+        html.push_str(&format!(
+            "    <table>\n        <thead><tr>{}</tr></thead>\n        <tbody><tr><td colspan=\"{}\">데이터가 없습니다</td></tr></tbody>\n    </table>\n",
+            header_cells,
+            table.headers.len().max(1)
+        ));
+        return;
+    }
+
+    let pages: Vec<&[Vec<CellValue>]> = table.rows.chunks(HTML_ROWS_PER_PAGE).collect();
+    let page_count = pages.len();
+
+    for (index, rows) in pages.iter().enumerate() {
+        let is_last = index + 1 == page_count;
+        if page_count > 1 {
+            html.push_str(&format!(
+                "    <div class=\"page-label\">페이지 {} / {}</div>\n",
+                index + 1,
+                page_count
+            ));
+        }
+        html.push_str("    <table>\n");
+        html.push_str(&format!(
+            "        <thead><tr>{}</tr></thead>\n",
+            header_cells
+        ));
+        html.push_str("        <tbody>\n");
+        for row in rows.iter() {
+            html.push_str("            <tr>");
+            for (i, cell) in row.iter().enumerate() {
+                html.push_str(&format!(
+                    "<td{}>{}</td>",
+                    align_class(table, i),
+                    escape_html(&cell.to_string())
+                ));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("        </tbody>\n");
+        if is_last {
+            if let Some(totals) = &table.totals {
+                html.push_str("        <tfoot><tr>");
+                for (i, cell) in totals.iter().enumerate() {
+                    html.push_str(&format!(
+                        "<td{}>{}</td>",
+                        align_class(table, i),
+                        escape_html(&cell.to_string())
+                    ));
+                }
+                html.push_str("</tr></tfoot>\n");
+            }
+        }
+        html.push_str("    </table>\n");
+        if !is_last {
+            html.push_str("    <div class=\"page-break\"></div>\n");
+        }
+    }
+}
+
+fn align_class(table: &ReportTable, column: usize) -> &'static str {
+    if table.is_numeric(column) {
+        " class=\"num\""
+    } else {
+        ""
+    }
+}
+
+/// 막대 차트를 인라인 SVG로 렌더링
+pub fn render_svg_bar_chart(chart: &BarChart) -> String {
+    const WIDTH: f64 = 720.0;
+    const LABEL_WIDTH: f64 = 180.0;
+    const VALUE_WIDTH: f64 = 110.0;
+    const BAR_HEIGHT: f64 = 22.0;
+    const BAR_GAP: f64 = 8.0;
+    const TOP: f64 = 36.0;
+
+    let plot_width = WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+    let height = TOP + chart.points.len() as f64 * (BAR_HEIGHT + BAR_GAP) + 10.0;
+    let max_value = chart.max_value();
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" role=\"img\" aria-label=\"{title}\">\n",
+        w = WIDTH,
+        h = height,
+        title = escape_html(&chart.title)
+    );
+    svg.push_str(&format!(
+        "  <text x=\"0\" y=\"20\" font-size=\"15\" font-weight=\"bold\">{}</text>\n",
+        escape_html(&chart.title)
+    ));
+
+    for (index, (label, value)) in chart.points.iter().enumerate() {
+        let y = TOP + index as f64 * (BAR_HEIGHT + BAR_GAP);
+        let bar_width = if max_value > 0.0 {
+            (value.abs() / max_value) * plot_width
+        } else {
+            0.0
+        };
+        let color = if *value < 0.0 { "#e15759" } else { "#4e79a7" };
+        svg.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\" text-anchor=\"end\">{}</text>\n",
+            LABEL_WIDTH - 8.0,
+            y + BAR_HEIGHT * 0.7,
+            escape_html(label)
+        ));
+        svg.push_str(&format!(
+            "  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" />\n",
+            LABEL_WIDTH, y, bar_width, BAR_HEIGHT, color
+        ));
+        svg.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\">{}</text>\n",
+            LABEL_WIDTH + bar_width + 6.0,
+            y + BAR_HEIGHT * 0.7,
+            format_chart_value(*value)
+        ));
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn format_chart_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// ---------------------------------------------------------------------------
+// XLSX
+// ---------------------------------------------------------------------------
+
+/// 문서를 XLSX로 렌더링 (보고서 정보 시트 + 섹션별 시트)
+pub fn render_xlsx(document: &ReportDocument) -> ErpResult<Vec<u8>> {
+    let mut workbook = Workbook::new();
+
+    let mut info = Worksheet::new("보고서 정보");
+    info.header(["항목", "값"])
+        .row(vec!["보고서".into(), document.title.as_str().into()])
+        .row(vec!["생성 시간".into(), document.generated_at.into()]);
+    if let Some(subtitle) = &document.subtitle {
+        info.row(vec!["조건".into(), subtitle.into()]);
+    }
+    workbook.add_sheet(info);
+
+    for section in &document.sections {
+        let mut sheet = Worksheet::new(section.heading.as_str());
+
+        // 표가 있으면 표 헤더를 첫 행(고정)으로, 요약 항목은 표 아래에 배치
+        if let Some(table) = &section.table {
+            sheet.header(table.headers.iter().cloned());
+            for row in &table.rows {
+                sheet.row(row.clone());
+            }
+            if let Some(totals) = &table.totals {
+                sheet.total_row(totals.clone());
+            }
+            if !section.summary.is_empty() {
+                sheet.blank_row();
+            }
+        } else {
+            sheet.header(["항목", "값"]);
+        }
+
+        for (label, value) in &section.summary {
+            sheet.row(vec![label.into(), value.clone()]);
+        }
+
+        workbook.add_sheet(sheet);
+    }
+
+    workbook.to_bytes()
+}
+
+// ---------------------------------------------------------------------------
+// PDF
+// ---------------------------------------------------------------------------
+
+/// 문서를 PDF로 렌더링
+pub fn render_pdf(document: &ReportDocument, include_charts: bool) -> ErpResult<Vec<u8>> {
+    let mut writer = PdfWriter::new(&document.title)?;
+
+    writer.text(&document.title, 20.0, MARGIN_LEFT, writer.y, true);
+    writer.y -= 9.0;
+    let mut meta = format!(
+        "생성 시간: {}",
+        document.generated_at.format("%Y-%m-%d %H:%M:%S")
+    );
+    if let Some(subtitle) = &document.subtitle {
+        meta.push_str(&format!(" / {}", subtitle));
+    }
+    writer.text(&meta, 10.0, MARGIN_LEFT, writer.y, false);
+    writer.y -= 6.0;
+
+    for section in &document.sections {
+        writer.heading(&section.heading);
+        if !section.summary.is_empty() {
+            writer.summary(&section.summary);
+        }
+        if let Some(table) = &section.table {
+            writer.table(table);
+        }
+        if include_charts {
+            if let Some(chart) = &section.chart {
+                writer.chart(chart);
+            }
+        }
+    }
+
+    writer.finish(&document.title)
+}
+
+struct PdfWriter {
+    doc: PdfDocumentReference,
+    font: IndirectFontRef,
+    bold_font: IndirectFontRef,
+    pages: Vec<(PdfPageIndex, PdfLayerIndex)>,
+    layer: PdfLayerReference,
+    y: f32,
+}
+
+impl PdfWriter {
+    fn new(title: &str) -> ErpResult<Self> {
+        let (doc, page, layer) =
+            PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+
+        let (font, bold_font) = match load_external_font(&doc) {
+            Some(font) => (font.clone(), font),
+            None => (
+                doc.add_builtin_font(BuiltinFont::Helvetica)
+                    .map_err(|e| ErpError::internal(format!("PDF 폰트 로드 실패: {:?}", e)))?,
+                doc.add_builtin_font(BuiltinFont::HelveticaBold)
+                    .map_err(|e| ErpError::internal(format!("PDF 폰트 로드 실패: {:?}", e)))?,
+            ),
+        };
+
+        let current_layer = doc.get_page(page).get_layer(layer);
+        Ok(Self {
+            doc,
+            font,
+            bold_font,
+            pages: vec![(page, layer)],
+            layer: current_layer,
+            y: CONTENT_TOP,
+        })
+    }
+
+    fn new_page(&mut self) {
+        let (page, layer) = self
+            .doc
+            .add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.pages.push((page, layer));
+        self.y = CONTENT_TOP;
+    }
+
+    /// 남은 공간이 부족하면 새 페이지를 시작하고 true 반환
+    fn ensure_space(&mut self, height: f32) -> bool {
+        if self.y - height < CONTENT_BOTTOM {
+            self.new_page();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn text(&self, text: &str, size: f32, x: f32, y: f32, bold: bool) {
+        let font = if bold { &self.bold_font } else { &self.font };
+        self.layer.use_text(text, size, Mm(x), Mm(y), font);
+    }
+
+    fn heading(&mut self, heading: &str) {
+        self.ensure_space(18.0);
+        self.y -= 8.0;
+        self.text(heading, 13.0, MARGIN_LEFT, self.y, true);
+        self.y -= 2.0;
+        self.rule(self.y, 0.8);
+        self.y -= 6.0;
+    }
+
+    fn summary(&mut self, items: &[(String, CellValue)]) {
+        for (label, value) in items {
+            self.ensure_space(TABLE_ROW_HEIGHT);
+            self.text(
+                &fit_text(label, 10.0, 70.0),
+                10.0,
+                MARGIN_LEFT + 2.0,
+                self.y,
+                false,
+            );
+            self.text(
+                &fit_text(&value.to_string(), 10.0, 80.0),
+                10.0,
+                95.0,
+                self.y,
+                true,
+            );
+            self.y -= TABLE_ROW_HEIGHT;
+        }
+        self.y -= 2.0;
+    }
+
+    fn table(&mut self, table: &ReportTable) {
+        let columns = table.headers.len().max(1);
+        let column_width = CONTENT_WIDTH / columns as f32;
+
+        self.ensure_space(TABLE_ROW_HEIGHT * 2.0);
+        self.table_header(table, column_width);
+
+        if table.rows.is_empty() {
+            self.text(
+                "데이터가 없습니다",
+                TABLE_FONT_SIZE,
+                MARGIN_LEFT + 1.5,
+                self.y,
+                false,
+            );
+            self.y -= TABLE_ROW_HEIGHT;
+        }
+
+        for row in &table.rows {
+            if self.ensure_space(TABLE_ROW_HEIGHT) {
+                self.table_header(table, column_width);
+            }
+            self.table_row(table, row, column_width, false);
+        }
+
+        if let Some(totals) = &table.totals {
+            if self.ensure_space(TABLE_ROW_HEIGHT) {
+                self.table_header(table, column_width);
+            }
+            self.rule(self.y + TABLE_ROW_HEIGHT - 1.5, 0.6);
+            self.table_row(table, totals, column_width, true);
+        }
+        self.y -= 3.0;
+    }
+
+    fn table_header(&mut self, table: &ReportTable, column_width: f32) {
+        let top = self.y + TABLE_ROW_HEIGHT - 1.8;
+        self.layer
+            .set_fill_color(Color::Greyscale(Greyscale::new(0.9, None)));
+        self.layer.add_rect(Rect::new(
+            Mm(MARGIN_LEFT),
+            Mm(top - TABLE_ROW_HEIGHT),
+            Mm(MARGIN_LEFT + CONTENT_WIDTH),
+            Mm(top),
+        ));
+        self.layer
+            .set_fill_color(Color::Greyscale(Greyscale::new(0.0, None)));
+        let headers: Vec<CellValue> = table.headers.iter().map(CellValue::from).collect();
+        self.table_row(table, &headers, column_width, true);
+    }
+
+    fn table_row(
+        &mut self,
+        table: &ReportTable,
+        cells: &[CellValue],
+        column_width: f32,
+        bold: bool,
+    ) {
+        for (i, cell) in cells.iter().enumerate() {
+            let text = fit_text(&cell.to_string(), TABLE_FONT_SIZE, column_width - 3.0);
+            let left = MARGIN_LEFT + column_width * i as f32;
+            let x = if table.is_numeric(i) {
+                left + column_width - 1.5 - text_width_mm(&text, TABLE_FONT_SIZE)
+            } else {
+                left + 1.5
+            };
+            self.text(&text, TABLE_FONT_SIZE, x, self.y, bold);
+        }
+        self.y -= TABLE_ROW_HEIGHT;
+    }
+
+    fn chart(&mut self, chart: &BarChart) {
+        const LABEL_WIDTH: f32 = 45.0;
+        const VALUE_WIDTH: f32 = 30.0;
+        let plot_width = CONTENT_WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+        let max_value = chart.max_value();
+
+        self.ensure_space(CHART_BAR_HEIGHT * 3.0);
+        self.y -= 2.0;
+        self.text(&chart.title, 10.0, MARGIN_LEFT, self.y, true);
+        self.y -= 7.0;
+
+        for (label, value) in &chart.points {
+            self.ensure_space(CHART_BAR_HEIGHT + 2.0);
+            let bar_width = if max_value > 0.0 {
+                (value.abs() / max_value) as f32 * plot_width
+            } else {
+                0.0
+            };
+            self.text(
+                &fit_text(label, TABLE_FONT_SIZE, LABEL_WIDTH - 2.0),
+                TABLE_FONT_SIZE,
+                MARGIN_LEFT,
+                self.y,
+                false,
+            );
+            let (r, g, b) = if *value < 0.0 {
+                (0.88, 0.34, 0.35)
+            } else {
+                (0.31, 0.47, 0.65)
+            };
+            self.layer
+                .set_fill_color(Color::Rgb(Rgb::new(r, g, b, None)));
+            self.layer.add_rect(Rect::new(
+                Mm(MARGIN_LEFT + LABEL_WIDTH),
+                Mm(self.y - 1.0),
+                Mm(MARGIN_LEFT + LABEL_WIDTH + bar_width.max(0.3)),
+                Mm(self.y - 1.0 + CHART_BAR_HEIGHT - 1.0),
+            ));
+            self.layer
+                .set_fill_color(Color::Greyscale(Greyscale::new(0.0, None)));
+            self.text(
+                &format_chart_value(*value),
+                TABLE_FONT_SIZE,
+                MARGIN_LEFT + LABEL_WIDTH + bar_width + 2.0,
+                self.y,
+                false,
+            );
+            self.y -= CHART_BAR_HEIGHT + 2.0;
+        }
+        self.y -= 3.0;
+    }
+
+    fn rule(&self, y: f32, thickness: f32) {
+        self.layer.set_outline_thickness(thickness);
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(MARGIN_LEFT), Mm(y)), false),
+                (Point::new(Mm(MARGIN_LEFT + CONTENT_WIDTH), Mm(y)), false),
+            ],
+            is_closed: false,
+        });
+    }
+
+    fn finish(self, title: &str) -> ErpResult<Vec<u8>> {
+        let page_count = self.pages.len();
+        for (index, (page, layer)) in self.pages.iter().enumerate() {
+            let layer = self.doc.get_page(*page).get_layer(*layer);
+            let footer = format!("{} - {} / {}", title, index + 1, page_count);
+            let x = PAGE_WIDTH - MARGIN_LEFT - text_width_mm(&footer, 8.0);
+            layer.use_text(footer, 8.0, Mm(x), Mm(10.0), &self.font);
+        }
+
+        self.doc
+            .save_to_bytes()
+            .map_err(|e| ErpError::internal(format!("PDF 저장 실패: {:?}", e)))
+    }
+}
+
+fn load_external_font(doc: &PdfDocumentReference) -> Option<IndirectFontRef> {
+    PDF_FONT_CANDIDATES.iter().find_map(|path| {
+        let file = File::open(path).ok()?;
+        doc.add_external_font(BufReader::new(file)).ok()
+    })
+}
+
+/// 대략적인 텍스트 폭 (mm) - 전각 문자는 1em, 그 외는 0.55em으로 계산
+fn text_width_mm(text: &str, font_size: f32) -> f32 {
+    let em = font_size * 0.3528;
+    text.chars()
+        .map(|c| if c.is_ascii() { em * 0.55 } else { em })
+        .sum()
+}
+
+/// 주어진 폭에 맞도록 텍스트를 잘라냄
+fn fit_text(text: &str, font_size: f32, max_width_mm: f32) -> String {
+    if text_width_mm(text, font_size) <= max_width_mm {
+        return text.to_string();
+    }
+
+    let ellipsis_width = text_width_mm("..", font_size);
+    let mut result = String::new();
+    let mut width = 0.0;
+    for c in text.chars() {
+        let char_width = text_width_mm(c.encode_utf8(&mut [0; 4]), font_size);
+        if width + char_width + ellipsis_width > max_width_mm {
+            break;
+        }
+        width += char_width;
+        result.push(c);
+    }
+    result.push_str("..");
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_document(rows: usize) -> ReportDocument {
+        let table = ReportTable::new(&["제품", "수량"])
+            .numeric(&[1])
+            .rows((0..rows).map(|i| vec![format!("제품 <{}>", i).into(), i.into()]))
+            .totals(vec!["합계".into(), rows.into()]);
+
+        ReportDocument::new("테스트 보고서", Utc::now()).section(
+            ReportSection::new("제품 목록")
+                .item("총 제품 수", rows)
+                .table(table)
+                .chart(BarChart::new(
+                    "수량",
+                    vec![("A".to_string(), 10.0), ("B".to_string(), 5.0)],
+                )),
+        )
+    }
+
+    #[test]
+    fn test_render_html_escapes_and_totals() {
+        let html = render_html(&sample_document(3), false);
+        assert!(html.contains("<h2>제품 목록</h2>"));
+        assert!(html.contains("제품 &lt;1&gt;"));
+        assert!(html.contains("<tfoot>"));
+        assert!(!html.contains("<svg"));
+    }
+
+    #[test]
+    fn test_render_html_paginates_long_tables() {
+        let html = render_html(&sample_document(HTML_ROWS_PER_PAGE * 2 + 1), false);
+        assert!(html.contains("페이지 1 / 3"));
+        assert!(html.contains("페이지 3 / 3"));
+        assert_eq!(html.matches("<tfoot>").count(), 1);
+    }
+
+    #[test]
+    fn test_render_html_includes_svg_charts() {
+        let html = render_html(&sample_document(2), true);
+        assert!(html.contains("<svg"));
+        assert_eq!(html.matches("<rect").count(), 2);
+    }
+
+    #[test]
+    fn test_render_pdf_adds_pages() {
+        let short = render_pdf(&sample_document(5), true).unwrap();
+        assert!(short.starts_with(b"%PDF"));
+
+        let long = render_pdf(&sample_document(120), true).unwrap();
+        assert!(long.len() > short.len());
+    }
+
+    #[test]
+    fn test_render_xlsx_sheet_per_section() {
+        let bytes = render_xlsx(&sample_document(3)).unwrap();
+        assert!(bytes.starts_with(b"PK"));
+        // 정보 시트 + 섹션 시트 1개
+        let workbook = String::from_utf8_lossy(&bytes);
+        assert!(workbook.contains("name=\"보고서 정보\""));
+        assert!(workbook.contains("name=\"제품 목록\""));
+    }
+
+    #[test]
+    fn test_fit_text_truncates() {
+        assert_eq!(fit_text("short", 8.0, 100.0), "short");
+        let fitted = fit_text(&"x".repeat(200), 8.0, 20.0);
+        assert!(fitted.ends_with(".."));
+        assert!(text_width_mm(&fitted, 8.0) <= 20.0);
+    }
+}
@@ -3,5 +3,7 @@ pub mod repository;
 pub mod service;
 
 pub use models::*;
-pub use repository::{MockSalesRepository, PostgresSalesRepository, SalesRepository};
+pub use repository::{
+    MockSalesRepository, PostgresSalesRepository, SalesRepository, SqliteSalesRepository,
+};
 pub use service::SalesService;
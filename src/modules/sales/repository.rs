@@ -1,16 +1,43 @@
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use sqlx::{PgPool, Row};
+use shaku::Interface;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{PgPool, Postgres, Row, Sqlite, SqlitePool};
 use uuid::Uuid;
 
 use super::models::*;
+use crate::core::database::{sqlite, MockUnitOfWork, ReadRouter, UnitOfWork};
 use crate::utils::error::{ErpError, ErpResult};
 
 #[async_trait]
-pub trait SalesRepository: Send + Sync {
-    async fn create_order(&self, order: &SalesOrder) -> ErpResult<()>;
-    async fn create_order_items(&self, items: &[SalesOrderItem]) -> ErpResult<()>;
+pub trait SalesRepository: Interface {
+    /// Starts a unit of work other repositories on the same backend can join
+    async fn begin(&self) -> ErpResult<UnitOfWork>;
+    async fn create_order_in(&self, uow: &mut UnitOfWork, order: &SalesOrder) -> ErpResult<()>;
+    async fn create_order_items_in(
+        &self,
+        uow: &mut UnitOfWork,
+        items: &[SalesOrderItem],
+    ) -> ErpResult<()>;
+    async fn delete_order_in(&self, uow: &mut UnitOfWork, id: Uuid) -> ErpResult<()>;
+    async fn update_order_status_in(
+        &self,
+        uow: &mut UnitOfWork,
+        id: Uuid,
+        status: OrderStatus,
+    ) -> ErpResult<()>;
+
+    async fn create_order(&self, order: &SalesOrder) -> ErpResult<()> {
+        let mut uow = self.begin().await?;
+        let result = self.create_order_in(&mut uow, order).await;
+        uow.finish(result).await
+    }
+    async fn create_order_items(&self, items: &[SalesOrderItem]) -> ErpResult<()> {
+        let mut uow = self.begin().await?;
+        let result = self.create_order_items_in(&mut uow, items).await;
+        uow.finish(result).await
+    }
     async fn get_order_by_id(&self, id: Uuid) -> ErpResult<Option<SalesOrder>>;
     async fn get_order_by_number(&self, order_number: &str) -> ErpResult<Option<SalesOrder>>;
     async fn get_order_items(&self, order_id: Uuid) -> ErpResult<Vec<SalesOrderItem>>;
@@ -19,10 +46,18 @@ pub trait SalesRepository: Send + Sync {
         order_id: Uuid,
     ) -> ErpResult<Vec<OrderItemWithProduct>>;
     async fn update_order(&self, id: Uuid, updates: &UpdateOrderRequest) -> ErpResult<()>;
-    async fn update_order_status(&self, id: Uuid, status: OrderStatus) -> ErpResult<()>;
+    async fn update_order_status(&self, id: Uuid, status: OrderStatus) -> ErpResult<()> {
+        let mut uow = self.begin().await?;
+        let result = self.update_order_status_in(&mut uow, id, status).await;
+        uow.finish(result).await
+    }
     async fn update_payment_status(&self, id: Uuid, payment_status: PaymentStatus)
         -> ErpResult<()>;
-    async fn delete_order(&self, id: Uuid) -> ErpResult<()>;
+    async fn delete_order(&self, id: Uuid) -> ErpResult<()> {
+        let mut uow = self.begin().await?;
+        let result = self.delete_order_in(&mut uow, id).await;
+        uow.finish(result).await
+    }
     async fn list_orders(
         &self,
         limit: Option<i64>,
@@ -44,7 +79,8 @@ pub trait SalesRepository: Send + Sync {
         start_date: Option<DateTime<Utc>>,
         end_date: Option<DateTime<Utc>>,
     ) -> ErpResult<SalesStatistics>;
-    async fn get_next_order_number(&self) -> ErpResult<String>;
+    /// Next `{prefix}-000001` style number after the highest one with `prefix`
+    async fn get_next_order_number(&self, prefix: &str) -> ErpResult<String>;
     async fn calculate_order_totals(
         &self,
         order_id: Uuid,
@@ -52,19 +88,42 @@ pub trait SalesRepository: Send + Sync {
     ) -> ErpResult<(Decimal, Decimal, Decimal)>;
 }
 
+/// Number following `last` (e.g. `ORD-000041` -> `ORD-000042`)
+fn next_order_number(prefix: &str, last: Option<&str>) -> String {
+    let next = last
+        .and_then(|number| number.strip_prefix(prefix)?.strip_prefix('-'))
+        .and_then(|sequence| sequence.parse::<u32>().ok())
+        .map_or(1, |sequence| sequence + 1);
+    format!("{}-{:06}", prefix, next)
+}
+
 pub struct PostgresSalesRepository {
     pool: PgPool,
+    reads: ReadRouter<Postgres>,
 }
 
 impl PostgresSalesRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            reads: ReadRouter::primary_only(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Sends order listings and searches to read replicas
+    pub fn with_read_router(mut self, reads: ReadRouter<Postgres>) -> Self {
+        self.reads = reads;
+        self
     }
 }
 
 #[async_trait]
 impl SalesRepository for PostgresSalesRepository {
-    async fn create_order(&self, order: &SalesOrder) -> ErpResult<()> {
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        UnitOfWork::begin_postgres(&self.pool).await
+    }
+
+    async fn create_order_in(&self, uow: &mut UnitOfWork, order: &SalesOrder) -> ErpResult<()> {
         let query = r#"
             INSERT INTO sales_orders (
                 id, order_number, customer_id, order_date, status, total_amount,
@@ -89,14 +148,18 @@ impl SalesRepository for PostgresSalesRepository {
             .bind(&order.notes)
             .bind(order.created_at)
             .bind(order.updated_at)
-            .execute(&self.pool)
+            .execute(uow.postgres()?)
             .await
             .map_err(ErpError::Database)?;
 
         Ok(())
     }
 
-    async fn create_order_items(&self, items: &[SalesOrderItem]) -> ErpResult<()> {
+    async fn create_order_items_in(
+        &self,
+        uow: &mut UnitOfWork,
+        items: &[SalesOrderItem],
+    ) -> ErpResult<()> {
         let query = r#"
             INSERT INTO sales_order_items (
                 id, order_id, product_id, quantity, unit_price, discount, line_total, created_at
@@ -113,7 +176,7 @@ impl SalesRepository for PostgresSalesRepository {
                 .bind(item.discount)
                 .bind(item.line_total)
                 .bind(item.created_at)
-                .execute(&self.pool)
+                .execute(uow.postgres()?)
                 .await
                 .map_err(ErpError::Database)?;
         }
@@ -282,14 +345,19 @@ impl SalesRepository for PostgresSalesRepository {
         Ok(())
     }
 
-    async fn update_order_status(&self, id: Uuid, status: OrderStatus) -> ErpResult<()> {
+    async fn update_order_status_in(
+        &self,
+        uow: &mut UnitOfWork,
+        id: Uuid,
+        status: OrderStatus,
+    ) -> ErpResult<()> {
         let query = "UPDATE sales_orders SET status = $1, updated_at = $2 WHERE id = $3";
 
         sqlx::query(query)
             .bind(status)
             .bind(Utc::now())
             .bind(id)
-            .execute(&self.pool)
+            .execute(uow.postgres()?)
             .await
             .map_err(ErpError::Database)?;
 
@@ -314,23 +382,19 @@ impl SalesRepository for PostgresSalesRepository {
         Ok(())
     }
 
-    async fn delete_order(&self, id: Uuid) -> ErpResult<()> {
-        let mut tx = self.pool.begin().await.map_err(ErpError::Database)?;
-
+    async fn delete_order_in(&self, uow: &mut UnitOfWork, id: Uuid) -> ErpResult<()> {
         sqlx::query("DELETE FROM sales_order_items WHERE order_id = $1")
             .bind(id)
-            .execute(&mut *tx)
+            .execute(uow.postgres()?)
             .await
             .map_err(ErpError::Database)?;
 
         sqlx::query("DELETE FROM sales_orders WHERE id = $1")
             .bind(id)
-            .execute(&mut *tx)
+            .execute(uow.postgres()?)
             .await
             .map_err(ErpError::Database)?;
 
-        tx.commit().await.map_err(ErpError::Database)?;
-
         Ok(())
     }
 
@@ -360,7 +424,7 @@ impl SalesRepository for PostgresSalesRepository {
         );
 
         let orders = sqlx::query_as::<_, SalesOrder>(&query)
-            .fetch_all(&self.pool)
+            .fetch_all(&self.reads.pool().await)
             .await
             .map_err(ErpError::Database)?;
 
@@ -400,7 +464,7 @@ impl SalesRepository for PostgresSalesRepository {
         }
 
         let orders = sqlx_query
-            .fetch_all(&self.pool)
+            .fetch_all(&self.reads.pool().await)
             .await
             .map_err(ErpError::Database)?;
 
@@ -419,7 +483,7 @@ impl SalesRepository for PostgresSalesRepository {
 
         let orders = sqlx::query_as::<_, SalesOrder>(query)
             .bind(customer_id)
-            .fetch_all(&self.pool)
+            .fetch_all(&self.reads.pool().await)
             .await
             .map_err(ErpError::Database)?;
 
@@ -443,7 +507,7 @@ impl SalesRepository for PostgresSalesRepository {
         let orders = sqlx::query_as::<_, SalesOrder>(query)
             .bind(start_date)
             .bind(end_date)
-            .fetch_all(&self.pool)
+            .fetch_all(&self.reads.pool().await)
             .await
             .map_err(ErpError::Database)?;
 
@@ -533,29 +597,23 @@ impl SalesRepository for PostgresSalesRepository {
         })
     }
 
-    async fn get_next_order_number(&self) -> ErpResult<String> {
+    async fn get_next_order_number(&self, prefix: &str) -> ErpResult<String> {
         let query = r#"
             SELECT order_number
             FROM sales_orders
+            WHERE order_number LIKE $1
             ORDER BY order_number DESC
             LIMIT 1
         "#;
 
         let result = sqlx::query(query)
+            .bind(format!("{}-%", prefix))
             .fetch_optional(&self.pool)
             .await
             .map_err(ErpError::Database)?;
 
-        if let Some(row) = result {
-            let last_number: String = row.get("order_number");
-            if let Some(number_part) = last_number.strip_prefix("ORD-") {
-                if let Ok(num) = number_part.parse::<u32>() {
-                    return Ok(format!("ORD-{:06}", num + 1));
-                }
-            }
-        }
-
-        Ok("ORD-000001".to_string())
+        let last_number: Option<String> = result.map(|row| row.get("order_number"));
+        Ok(next_order_number(prefix, last_number.as_deref()))
     }
 
     async fn calculate_order_totals(
@@ -585,11 +643,469 @@ impl SalesRepository for PostgresSalesRepository {
     }
 }
 
+pub struct SqliteSalesRepository {
+    pool: SqlitePool,
+    reads: ReadRouter<Sqlite>,
+}
+
+const SQLITE_ORDER_COLUMNS: &str = "id, order_number, customer_id, order_date, status, \
+     total_amount, tax_amount, discount_amount, shipping_address, billing_address, \
+     payment_method, payment_status, notes, created_at, updated_at";
+
+impl SqliteSalesRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            reads: ReadRouter::primary_only(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Sends order listings and searches to read replicas
+    pub fn with_read_router(mut self, reads: ReadRouter<Sqlite>) -> Self {
+        self.reads = reads;
+        self
+    }
+
+    fn order_from_row(row: &SqliteRow) -> Result<SalesOrder, sqlx::Error> {
+        Ok(SalesOrder {
+            id: row.try_get("id")?,
+            order_number: row.try_get("order_number")?,
+            customer_id: row.try_get("customer_id")?,
+            order_date: row.try_get("order_date")?,
+            status: row.try_get("status")?,
+            total_amount: sqlite::decimal(row, "total_amount")?,
+            tax_amount: sqlite::decimal(row, "tax_amount")?,
+            discount_amount: sqlite::decimal(row, "discount_amount")?,
+            shipping_address: row.try_get("shipping_address")?,
+            billing_address: row.try_get("billing_address")?,
+            payment_method: row.try_get("payment_method")?,
+            payment_status: row.try_get("payment_status")?,
+            notes: row.try_get("notes")?,
+            created_at: row.try_get("created_at")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    fn item_from_row(row: &SqliteRow) -> Result<SalesOrderItem, sqlx::Error> {
+        Ok(SalesOrderItem {
+            id: row.try_get("id")?,
+            order_id: row.try_get("order_id")?,
+            product_id: row.try_get("product_id")?,
+            quantity: row.try_get("quantity")?,
+            unit_price: sqlite::decimal(row, "unit_price")?,
+            discount: sqlite::decimal(row, "discount")?,
+            line_total: sqlite::decimal(row, "line_total")?,
+            created_at: row.try_get("created_at")?,
+        })
+    }
+
+    async fn fetch_orders<'q>(
+        &self,
+        query: sqlx::query::Query<'q, Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    ) -> ErpResult<Vec<SalesOrder>> {
+        Self::fetch_orders_from(&self.pool, query).await
+    }
+
+    async fn fetch_orders_from<'q>(
+        pool: &SqlitePool,
+        query: sqlx::query::Query<'q, Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    ) -> ErpResult<Vec<SalesOrder>> {
+        let rows = query.fetch_all(pool).await.map_err(ErpError::Database)?;
+
+        rows.iter()
+            .map(Self::order_from_row)
+            .collect::<Result<_, _>>()
+            .map_err(ErpError::Database)
+    }
+
+    fn push_date_range<'q>(
+        builder: &mut sqlx::QueryBuilder<'q, Sqlite>,
+        start_date: Option<DateTime<Utc>>,
+        end_date: Option<DateTime<Utc>>,
+    ) {
+        builder.push(" WHERE 1 = 1");
+        if let Some(start) = start_date {
+            builder.push(" AND order_date >= ");
+            builder.push_bind(start);
+        }
+        if let Some(end) = end_date {
+            builder.push(" AND order_date <= ");
+            builder.push_bind(end);
+        }
+    }
+}
+
+#[async_trait]
+impl SalesRepository for SqliteSalesRepository {
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        UnitOfWork::begin_sqlite(&self.pool).await
+    }
+
+    async fn create_order_in(&self, uow: &mut UnitOfWork, order: &SalesOrder) -> ErpResult<()> {
+        let query = format!(
+            "INSERT INTO sales_orders ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            SQLITE_ORDER_COLUMNS
+        );
+
+        sqlx::query(&query)
+            .bind(order.id)
+            .bind(&order.order_number)
+            .bind(order.customer_id)
+            .bind(order.order_date)
+            .bind(order.status)
+            .bind(sqlite::decimal_text(order.total_amount))
+            .bind(sqlite::decimal_text(order.tax_amount))
+            .bind(sqlite::decimal_text(order.discount_amount))
+            .bind(&order.shipping_address)
+            .bind(&order.billing_address)
+            .bind(order.payment_method)
+            .bind(order.payment_status)
+            .bind(&order.notes)
+            .bind(order.created_at)
+            .bind(order.updated_at)
+            .execute(uow.sqlite()?)
+            .await
+            .map_err(ErpError::Database)?;
+
+        Ok(())
+    }
+
+    async fn create_order_items_in(
+        &self,
+        uow: &mut UnitOfWork,
+        items: &[SalesOrderItem],
+    ) -> ErpResult<()> {
+        for item in items {
+            sqlx::query(
+                "INSERT INTO sales_order_items \
+                 (id, order_id, product_id, quantity, unit_price, discount, line_total, created_at) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(item.id)
+            .bind(item.order_id)
+            .bind(item.product_id)
+            .bind(item.quantity)
+            .bind(sqlite::decimal_text(item.unit_price))
+            .bind(sqlite::decimal_text(item.discount))
+            .bind(sqlite::decimal_text(item.line_total))
+            .bind(item.created_at)
+            .execute(uow.sqlite()?)
+            .await
+            .map_err(ErpError::Database)?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_order_by_id(&self, id: Uuid) -> ErpResult<Option<SalesOrder>> {
+        let query = format!(
+            "SELECT {} FROM sales_orders WHERE id = ?",
+            SQLITE_ORDER_COLUMNS
+        );
+        let orders = self.fetch_orders(sqlx::query(&query).bind(id)).await?;
+        Ok(orders.into_iter().next())
+    }
+
+    async fn get_order_by_number(&self, order_number: &str) -> ErpResult<Option<SalesOrder>> {
+        let query = format!(
+            "SELECT {} FROM sales_orders WHERE order_number = ?",
+            SQLITE_ORDER_COLUMNS
+        );
+        let orders = self
+            .fetch_orders(sqlx::query(&query).bind(order_number))
+            .await?;
+        Ok(orders.into_iter().next())
+    }
+
+    async fn get_order_items(&self, order_id: Uuid) -> ErpResult<Vec<SalesOrderItem>> {
+        let rows = sqlx::query(
+            "SELECT id, order_id, product_id, quantity, unit_price, discount, line_total, created_at \
+             FROM sales_order_items WHERE order_id = ? ORDER BY created_at",
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(ErpError::Database)?;
+
+        rows.iter()
+            .map(Self::item_from_row)
+            .collect::<Result<_, _>>()
+            .map_err(ErpError::Database)
+    }
+
+    async fn get_order_items_with_products(
+        &self,
+        order_id: Uuid,
+    ) -> ErpResult<Vec<OrderItemWithProduct>> {
+        let rows = sqlx::query(
+            "SELECT soi.id, soi.order_id, soi.product_id, soi.quantity, soi.unit_price, \
+                    soi.discount, soi.line_total, soi.created_at, \
+                    p.name as product_name, p.sku as product_sku \
+             FROM sales_order_items soi \
+             JOIN products p ON soi.product_id = p.id \
+             WHERE soi.order_id = ? \
+             ORDER BY soi.created_at",
+        )
+        .bind(order_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(ErpError::Database)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(OrderItemWithProduct {
+                    item: Self::item_from_row(row)?,
+                    product_name: row.try_get("product_name")?,
+                    product_sku: row.try_get("product_sku")?,
+                })
+            })
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(ErpError::Database)
+    }
+
+    async fn update_order(&self, id: Uuid, updates: &UpdateOrderRequest) -> ErpResult<()> {
+        let mut builder = sqlx::QueryBuilder::<Sqlite>::new("UPDATE sales_orders SET ");
+        let mut fields = builder.separated(", ");
+
+        if let Some(status) = updates.status {
+            fields.push("status = ");
+            fields.push_bind_unseparated(status);
+        }
+        if let Some(payment_status) = updates.payment_status {
+            fields.push("payment_status = ");
+            fields.push_bind_unseparated(payment_status);
+        }
+        if let Some(payment_method) = updates.payment_method {
+            fields.push("payment_method = ");
+            fields.push_bind_unseparated(payment_method);
+        }
+        if let Some(shipping_address) = &updates.shipping_address {
+            fields.push("shipping_address = ");
+            fields.push_bind_unseparated(shipping_address.clone());
+        }
+        if let Some(billing_address) = &updates.billing_address {
+            fields.push("billing_address = ");
+            fields.push_bind_unseparated(billing_address.clone());
+        }
+        if let Some(notes) = &updates.notes {
+            fields.push("notes = ");
+            fields.push_bind_unseparated(notes.clone());
+        }
+
+        let has_updates = updates.status.is_some()
+            || updates.payment_status.is_some()
+            || updates.payment_method.is_some()
+            || updates.shipping_address.is_some()
+            || updates.billing_address.is_some()
+            || updates.notes.is_some();
+        if !has_updates {
+            return Ok(());
+        }
+
+        fields.push("updated_at = ");
+        fields.push_bind_unseparated(Utc::now());
+        builder.push(" WHERE id = ");
+        builder.push_bind(id);
+
+        builder
+            .build()
+            .execute(&self.pool)
+            .await
+            .map_err(ErpError::Database)?;
+
+        Ok(())
+    }
+
+    async fn update_order_status_in(
+        &self,
+        uow: &mut UnitOfWork,
+        id: Uuid,
+        status: OrderStatus,
+    ) -> ErpResult<()> {
+        sqlx::query("UPDATE sales_orders SET status = ?, updated_at = ? WHERE id = ?")
+            .bind(status)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(uow.sqlite()?)
+            .await
+            .map_err(ErpError::Database)?;
+
+        Ok(())
+    }
+
+    async fn update_payment_status(
+        &self,
+        id: Uuid,
+        payment_status: PaymentStatus,
+    ) -> ErpResult<()> {
+        sqlx::query("UPDATE sales_orders SET payment_status = ?, updated_at = ? WHERE id = ?")
+            .bind(payment_status)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(ErpError::Database)?;
+
+        Ok(())
+    }
+
+    async fn delete_order_in(&self, uow: &mut UnitOfWork, id: Uuid) -> ErpResult<()> {
+        // Order items go with the order through ON DELETE CASCADE
+        sqlx::query("DELETE FROM sales_orders WHERE id = ?")
+            .bind(id)
+            .execute(uow.sqlite()?)
+            .await
+            .map_err(ErpError::Database)?;
+
+        Ok(())
+    }
+
+    async fn list_orders(
+        &self,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> ErpResult<Vec<SalesOrder>> {
+        // LIMIT -1 means no limit in SQLite
+        let query = format!(
+            "SELECT {} FROM sales_orders ORDER BY order_date DESC, created_at DESC LIMIT ? OFFSET ?",
+            SQLITE_ORDER_COLUMNS
+        );
+
+        Self::fetch_orders_from(
+            &self.reads.pool().await,
+            sqlx::query(&query)
+                .bind(limit.unwrap_or(-1))
+                .bind(if limit.is_some() {
+                    offset.unwrap_or(0)
+                } else {
+                    0
+                }),
+        )
+        .await
+    }
+
+    async fn search_orders(
+        &self,
+        query: &str,
+        status: Option<OrderStatus>,
+    ) -> ErpResult<Vec<SalesOrder>> {
+        let search_query = format!(
+            "SELECT {} FROM sales_orders \
+             WHERE (order_number LIKE ? OR notes LIKE ?) AND (? IS NULL OR status = ?) \
+             ORDER BY order_date DESC",
+            SQLITE_ORDER_COLUMNS
+        );
+        let search_pattern = format!("%{}%", query);
+
+        Self::fetch_orders_from(
+            &self.reads.pool().await,
+            sqlx::query(&search_query)
+                .bind(search_pattern.clone())
+                .bind(search_pattern)
+                .bind(status)
+                .bind(status),
+        )
+        .await
+    }
+
+    async fn get_orders_by_customer(&self, customer_id: Uuid) -> ErpResult<Vec<SalesOrder>> {
+        let query = format!(
+            "SELECT {} FROM sales_orders WHERE customer_id = ? ORDER BY order_date DESC",
+            SQLITE_ORDER_COLUMNS
+        );
+        Self::fetch_orders_from(
+            &self.reads.pool().await,
+            sqlx::query(&query).bind(customer_id),
+        )
+        .await
+    }
+
+    async fn get_orders_by_date_range(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> ErpResult<Vec<SalesOrder>> {
+        let mut builder =
+            sqlx::QueryBuilder::new(format!("SELECT {} FROM sales_orders", SQLITE_ORDER_COLUMNS));
+        Self::push_date_range(&mut builder, Some(start_date), Some(end_date));
+        builder.push(" ORDER BY order_date DESC");
+
+        Self::fetch_orders_from(&self.reads.pool().await, builder.build()).await
+    }
+
+    async fn get_sales_statistics(
+        &self,
+        start_date: Option<DateTime<Utc>>,
+        end_date: Option<DateTime<Utc>>,
+    ) -> ErpResult<SalesStatistics> {
+        let mut builder =
+            sqlx::QueryBuilder::new(format!("SELECT {} FROM sales_orders", SQLITE_ORDER_COLUMNS));
+        Self::push_date_range(&mut builder, start_date, end_date);
+        let orders = self.fetch_orders(builder.build()).await?;
+
+        let total_orders = orders.len() as i64;
+        let total_revenue: Decimal = orders.iter().map(|order| order.total_amount).sum();
+        let average_order_value = if total_orders > 0 {
+            total_revenue / Decimal::from(total_orders)
+        } else {
+            Decimal::ZERO
+        };
+
+        let mut orders_by_status: Vec<(OrderStatus, i64)> = Vec::new();
+        for order in &orders {
+            match orders_by_status
+                .iter_mut()
+                .find(|(status, _)| *status == order.status)
+            {
+                Some((_, count)) => *count += 1,
+                None => orders_by_status.push((order.status, 1)),
+            }
+        }
+
+        Ok(SalesStatistics {
+            total_orders,
+            total_revenue,
+            average_order_value,
+            orders_by_status,
+            top_customers: Vec::new(),
+            top_products: Vec::new(),
+        })
+    }
+
+    async fn get_next_order_number(&self, prefix: &str) -> ErpResult<String> {
+        let last_number: Option<String> = sqlx::query_scalar(
+            "SELECT order_number FROM sales_orders WHERE order_number LIKE ? \
+             ORDER BY order_number DESC LIMIT 1",
+        )
+        .bind(format!("{}-%", prefix))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(ErpError::Database)?;
+
+        Ok(next_order_number(prefix, last_number.as_deref()))
+    }
+
+    async fn calculate_order_totals(
+        &self,
+        order_id: Uuid,
+        tax_rate: Decimal,
+    ) -> ErpResult<(Decimal, Decimal, Decimal)> {
+        let items = self.get_order_items(order_id).await?;
+
+        let subtotal: Decimal = items.iter().map(|item| item.line_total).sum();
+        let total_discount: Decimal = items.iter().map(|item| item.discount).sum();
+        let tax_amount = subtotal * tax_rate / Decimal::from(100);
+
+        Ok((subtotal, total_discount, tax_amount))
+    }
+}
+
 pub struct MockSalesRepository {
     orders: std::sync::Arc<tokio::sync::RwLock<std::collections::HashMap<Uuid, SalesOrder>>>,
     items:
         std::sync::Arc<tokio::sync::RwLock<std::collections::HashMap<Uuid, Vec<SalesOrderItem>>>>,
     order_counter: std::sync::Arc<tokio::sync::RwLock<u32>>,
+    fail_unit_of_work_after: Option<usize>,
 }
 
 impl MockSalesRepository {
@@ -598,8 +1114,15 @@ impl MockSalesRepository {
             orders: std::sync::Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
             items: std::sync::Arc::new(tokio::sync::RwLock::new(std::collections::HashMap::new())),
             order_counter: std::sync::Arc::new(tokio::sync::RwLock::new(1)),
+            fail_unit_of_work_after: None,
         }
     }
+
+    /// Units of work begun by this repository fail after `writes` writes
+    pub fn failing_unit_of_work_after(mut self, writes: usize) -> Self {
+        self.fail_unit_of_work_after = Some(writes);
+        self
+    }
 }
 
 impl Default for MockSalesRepository {
@@ -610,18 +1133,36 @@ impl Default for MockSalesRepository {
 
 #[async_trait]
 impl SalesRepository for MockSalesRepository {
-    async fn create_order(&self, order: &SalesOrder) -> ErpResult<()> {
-        let mut orders = self.orders.write().await;
-        orders.insert(order.id, order.clone());
-        Ok(())
+    async fn begin(&self) -> ErpResult<UnitOfWork> {
+        let mock = match self.fail_unit_of_work_after {
+            Some(writes) => MockUnitOfWork::failing_after(writes),
+            None => MockUnitOfWork::new(),
+        };
+        Ok(UnitOfWork::mock(mock))
     }
 
-    async fn create_order_items(&self, items: &[SalesOrderItem]) -> ErpResult<()> {
-        let mut items_map = self.items.write().await;
-        if let Some(first_item) = items.first() {
-            items_map.insert(first_item.order_id, items.to_vec());
-        }
-        Ok(())
+    async fn create_order_in(&self, uow: &mut UnitOfWork, order: &SalesOrder) -> ErpResult<()> {
+        let orders = self.orders.clone();
+        let order = order.clone();
+        uow.as_mock()?.stage(async move {
+            orders.write().await.insert(order.id, order);
+        })
+    }
+
+    async fn create_order_items_in(
+        &self,
+        uow: &mut UnitOfWork,
+        items: &[SalesOrderItem],
+    ) -> ErpResult<()> {
+        let Some(first_item) = items.first() else {
+            return Ok(());
+        };
+        let items_map = self.items.clone();
+        let order_id = first_item.order_id;
+        let items = items.to_vec();
+        uow.as_mock()?.stage(async move {
+            items_map.write().await.insert(order_id, items);
+        })
     }
 
     async fn get_order_by_id(&self, id: Uuid) -> ErpResult<Option<SalesOrder>> {
@@ -684,13 +1225,19 @@ impl SalesRepository for MockSalesRepository {
         Ok(())
     }
 
-    async fn update_order_status(&self, id: Uuid, status: OrderStatus) -> ErpResult<()> {
-        let mut orders = self.orders.write().await;
-        if let Some(order) = orders.get_mut(&id) {
-            order.status = status;
-            order.updated_at = Utc::now();
-        }
-        Ok(())
+    async fn update_order_status_in(
+        &self,
+        uow: &mut UnitOfWork,
+        id: Uuid,
+        status: OrderStatus,
+    ) -> ErpResult<()> {
+        let orders = self.orders.clone();
+        uow.as_mock()?.stage(async move {
+            if let Some(order) = orders.write().await.get_mut(&id) {
+                order.status = status;
+                order.updated_at = Utc::now();
+            }
+        })
     }
 
     async fn update_payment_status(
@@ -706,12 +1253,13 @@ impl SalesRepository for MockSalesRepository {
         Ok(())
     }
 
-    async fn delete_order(&self, id: Uuid) -> ErpResult<()> {
-        let mut orders = self.orders.write().await;
-        let mut items = self.items.write().await;
-        orders.remove(&id);
-        items.remove(&id);
-        Ok(())
+    async fn delete_order_in(&self, uow: &mut UnitOfWork, id: Uuid) -> ErpResult<()> {
+        let orders = self.orders.clone();
+        let items = self.items.clone();
+        uow.as_mock()?.stage(async move {
+            orders.write().await.remove(&id);
+            items.write().await.remove(&id);
+        })
     }
 
     async fn list_orders(
@@ -721,7 +1269,7 @@ impl SalesRepository for MockSalesRepository {
     ) -> ErpResult<Vec<SalesOrder>> {
         let orders = self.orders.read().await;
         let mut order_list: Vec<SalesOrder> = orders.values().cloned().collect();
-        order_list.sort_by(|a, b| b.order_date.cmp(&a.order_date));
+        order_list.sort_by_key(|x| std::cmp::Reverse(x.order_date));
         Ok(order_list)
     }
 
@@ -792,9 +1340,9 @@ impl SalesRepository for MockSalesRepository {
         })
     }
 
-    async fn get_next_order_number(&self) -> ErpResult<String> {
+    async fn get_next_order_number(&self, prefix: &str) -> ErpResult<String> {
         let mut counter = self.order_counter.write().await;
-        let number = format!("ORD-{:06}", *counter);
+        let number = format!("{}-{:06}", prefix, *counter);
         *counter += 1;
         Ok(number)
     }
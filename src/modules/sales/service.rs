@@ -1,9 +1,12 @@
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use std::sync::Arc;
 use uuid::Uuid;
 
 use super::models::*;
 use super::repository::SalesRepository;
+use crate::core::cache::{CacheScope, QueryCache};
+use crate::core::config::SalesConfig;
 use crate::modules::config::service::ConfigService;
 use crate::modules::customers::service::CustomerService;
 use crate::modules::inventory::service::InventoryService;
@@ -11,16 +14,18 @@ use crate::utils::error::{ErpError, ErpResult};
 use crate::utils::validation::ValidationService;
 
 pub struct SalesService {
-    repository: Box<dyn SalesRepository>,
+    repository: Arc<dyn SalesRepository>,
     _validation_service: ValidationService,
     config_service: Option<ConfigService>,
     customer_service: Option<CustomerService>,
-    inventory_service: Option<Box<dyn InventoryService>>,
+    inventory_service: Option<Arc<dyn InventoryService>>,
+    cache: QueryCache,
+    settings: SalesConfig,
 }
 
 impl SalesService {
     pub fn new(
-        repository: Box<dyn SalesRepository>,
+        repository: Arc<dyn SalesRepository>,
         validation_service: ValidationService,
     ) -> Self {
         Self {
@@ -29,9 +34,38 @@ impl SalesService {
             config_service: None,
             customer_service: None,
             inventory_service: None,
+            cache: QueryCache::disabled(),
+            settings: SalesConfig::default(),
         }
     }
 
+    /// Order/invoice numbering, payment terms and the fallback tax rate
+    pub fn with_settings(mut self, settings: SalesConfig) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Cache whose report summaries are invalidated by order changes
+    pub fn with_cache(mut self, cache: QueryCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Tax rate percentage from the config store, or `sales.default_tax_rate`
+    async fn tax_rate(&self) -> Decimal {
+        match &self.config_service {
+            Some(config_service) => config_service
+                .get_tax_rate()
+                .await
+                .unwrap_or_else(|_| self.settings.default_tax_rate_percent()),
+            None => self.settings.default_tax_rate_percent(),
+        }
+    }
+
+    async fn invalidate_reports(&self) {
+        self.cache.invalidate(CacheScope::Reports).await;
+    }
+
     pub fn with_config_service(mut self, config_service: ConfigService) -> Self {
         self.config_service = Some(config_service);
         self
@@ -42,7 +76,7 @@ impl SalesService {
         self
     }
 
-    pub fn with_inventory_service(mut self, inventory_service: Box<dyn InventoryService>) -> Self {
+    pub fn with_inventory_service(mut self, inventory_service: Arc<dyn InventoryService>) -> Self {
         self.inventory_service = Some(inventory_service);
         self
     }
@@ -57,7 +91,10 @@ impl SalesService {
         }
 
         let order_id = Uuid::new_v4();
-        let order_number = self.repository.get_next_order_number().await?;
+        let order_number = self
+            .repository
+            .get_next_order_number(&self.settings.order_prefix)
+            .await?;
         let now = Utc::now();
 
         let mut order_items = Vec::new();
@@ -113,15 +150,7 @@ impl SalesService {
         let order_discount = request.discount_amount.unwrap_or(Decimal::ZERO);
         let subtotal_after_discount = subtotal - order_discount;
 
-        // Get tax rate from config service or use default
-        let tax_rate = if let Some(config_service) = &self.config_service {
-            config_service
-                .get_tax_rate()
-                .await
-                .unwrap_or_else(|_| Decimal::from_f64_retain(10.0).unwrap())
-        } else {
-            Decimal::from_f64_retain(10.0).unwrap() // Default 10%
-        };
+        let tax_rate = self.tax_rate().await;
 
         let tax_amount = subtotal_after_discount * tax_rate / Decimal::from(100);
         let grand_total = subtotal_after_discount + tax_amount;
@@ -144,8 +173,17 @@ impl SalesService {
             updated_at: now,
         };
 
-        self.repository.create_order(&order).await?;
-        self.repository.create_order_items(&order_items).await?;
+        // Header and lines land together or not at all
+        let mut uow = self.repository.begin().await?;
+        let result = async {
+            self.repository.create_order_in(&mut uow, &order).await?;
+            self.repository
+                .create_order_items_in(&mut uow, &order_items)
+                .await
+        }
+        .await;
+        uow.finish(result).await?;
+        self.invalidate_reports().await;
 
         let items_with_products = self
             .repository
@@ -170,15 +208,7 @@ impl SalesService {
 
         let items = self.repository.get_order_items_with_products(id).await?;
 
-        // Get tax rate from config service or use default
-        let tax_rate = if let Some(config_service) = &self.config_service {
-            config_service
-                .get_tax_rate()
-                .await
-                .unwrap_or_else(|_| Decimal::from_f64_retain(10.0).unwrap())
-        } else {
-            Decimal::from_f64_retain(10.0).unwrap() // Default 10%
-        };
+        let tax_rate = self.tax_rate().await;
 
         let (subtotal, total_discount, tax_amount) =
             self.repository.calculate_order_totals(id, tax_rate).await?;
@@ -204,15 +234,7 @@ impl SalesService {
             .get_order_items_with_products(order.id)
             .await?;
 
-        // Get tax rate from config service or use default
-        let tax_rate = if let Some(config_service) = &self.config_service {
-            config_service
-                .get_tax_rate()
-                .await
-                .unwrap_or_else(|_| Decimal::from_f64_retain(10.0).unwrap())
-        } else {
-            Decimal::from_f64_retain(10.0).unwrap() // Default 10%
-        };
+        let tax_rate = self.tax_rate().await;
 
         let (subtotal, total_discount, tax_amount) = self
             .repository
@@ -266,6 +288,7 @@ impl SalesService {
         }
 
         self.repository.update_order(id, &updates).await?;
+        self.invalidate_reports().await;
 
         let updated_order_summary = self.get_order_by_id(id).await?;
         match updated_order_summary {
@@ -284,53 +307,56 @@ impl SalesService {
             None => return Err(ErpError::not_found("Order", id.to_string())),
         };
 
-        if let Some(inventory_service) = &self.inventory_service {
-            if status == OrderStatus::Confirmed {
-                // Deduct inventory when confirming order
-                let items = self.repository.get_order_items(id).await?;
-                for item in &items {
-                    inventory_service
-                        .adjust_stock(
-                            &item.product_id.to_string(),
-                            -item.quantity,
-                            format!("Order {} confirmed", id),
-                            Uuid::new_v4(),
-                        )
-                        .await?;
-                }
-            } else if status == OrderStatus::Returned {
-                // Restore inventory when order is returned
+        // Confirming takes stock out; returns and cancellations of confirmed
+        // or processing orders put it back
+        let adjustment = match status {
+            OrderStatus::Confirmed => Some((-1, "confirmed")),
+            OrderStatus::Returned => Some((1, "returned")),
+            OrderStatus::Cancelled
+                if order.status == OrderStatus::Confirmed
+                    || order.status == OrderStatus::Processing =>
+            {
+                Some((1, "cancelled"))
+            }
+            _ => None,
+        };
+        let stock_changes = match (&self.inventory_service, adjustment) {
+            (Some(inventory_service), Some((sign, action))) => {
                 let items = self.repository.get_order_items(id).await?;
-                for item in &items {
+                Some((inventory_service, items, sign, action))
+            }
+            _ => None,
+        };
+
+        // Stock movements and the new status land together or not at all
+        let mut uow = self.repository.begin().await?;
+        let result = async {
+            self.repository
+                .update_order_status_in(&mut uow, id, status)
+                .await?;
+            if let Some((inventory_service, items, sign, action)) = &stock_changes {
+                for item in items {
                     inventory_service
-                        .adjust_stock(
-                            &item.product_id.to_string(),
-                            item.quantity,
-                            format!("Order {} returned", id),
+                        .adjust_stock_in(
+                            &mut uow,
+                            item.product_id,
+                            sign * item.quantity,
+                            format!("Order {} {}", id, action),
                             Uuid::new_v4(),
                         )
                         .await?;
                 }
-            } else if status == OrderStatus::Cancelled {
-                // Restore inventory only if order was previously confirmed or processing
-                if order.status == OrderStatus::Confirmed || order.status == OrderStatus::Processing
-                {
-                    let items = self.repository.get_order_items(id).await?;
-                    for item in &items {
-                        inventory_service
-                            .adjust_stock(
-                                &item.product_id.to_string(),
-                                item.quantity,
-                                format!("Order {} cancelled", id),
-                                Uuid::new_v4(),
-                            )
-                            .await?;
-                    }
-                }
             }
+            Ok(())
         }
+        .await;
+        uow.finish(result).await?;
 
-        self.repository.update_order_status(id, status).await
+        if stock_changes.is_some() {
+            self.cache.invalidate(CacheScope::Products).await;
+        }
+        self.invalidate_reports().await;
+        Ok(())
     }
 
     pub async fn update_payment_status(
@@ -345,7 +371,9 @@ impl SalesService {
 
         self.repository
             .update_payment_status(id, payment_status)
-            .await
+            .await?;
+        self.invalidate_reports().await;
+        Ok(())
     }
 
     pub async fn cancel_order(&self, id: Uuid) -> ErpResult<()> {
@@ -365,25 +393,7 @@ impl SalesService {
             ));
         }
 
-        if let Some(inventory_service) = &self.inventory_service {
-            if order.status == OrderStatus::Confirmed || order.status == OrderStatus::Processing {
-                let items = self.repository.get_order_items(id).await?;
-                for item in &items {
-                    inventory_service
-                        .adjust_stock(
-                            &item.product_id.to_string(),
-                            item.quantity,
-                            format!("Order {} cancelled", id),
-                            Uuid::new_v4(),
-                        )
-                        .await?;
-                }
-            }
-        }
-
-        self.repository
-            .update_order_status(id, OrderStatus::Cancelled)
-            .await
+        self.update_order_status(id, OrderStatus::Cancelled).await
     }
 
     pub async fn delete_order(&self, id: Uuid) -> ErpResult<()> {
@@ -392,7 +402,9 @@ impl SalesService {
             return Err(ErpError::not_found("Order", id.to_string()));
         }
 
-        self.repository.delete_order(id).await
+        self.repository.delete_order(id).await?;
+        self.invalidate_reports().await;
+        Ok(())
     }
 
     pub async fn get_orders_by_customer(&self, customer_id: Uuid) -> ErpResult<Vec<SalesOrder>> {
@@ -461,9 +473,13 @@ impl SalesService {
             tax_id: Some("TAX123456789".to_string()),
         };
 
-        let invoice_number = format!("INV-{}", order_summary.order.order_number);
+        let invoice_number = format!(
+            "{}-{}",
+            self.settings.invoice_prefix, order_summary.order.order_number
+        );
         let issue_date = Utc::now();
-        let due_date = issue_date + chrono::Duration::days(30);
+        let due_date =
+            issue_date + chrono::Duration::days(i64::from(self.settings.payment_terms_days));
 
         Ok(Invoice {
             order_id,
@@ -594,3 +610,169 @@ impl SalesService {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::database::models::product::CreateProductRequest;
+    use crate::core::database::sqlite;
+    use crate::modules::inventory::repository::{InventoryRepository, MockInventoryRepository};
+    use crate::modules::inventory::service::InventoryServiceImpl;
+    use crate::modules::sales::repository::{MockSalesRepository, SqliteSalesRepository};
+
+    fn order_request(customer_id: Uuid) -> CreateOrderRequest {
+        CreateOrderRequest {
+            customer_id,
+            items: vec![OrderItemRequest {
+                product_id: Uuid::new_v4(),
+                quantity: 2,
+                unit_price: Some(Decimal::from(15)),
+                discount: None,
+            }],
+            shipping_address: None,
+            billing_address: None,
+            payment_method: None,
+            notes: None,
+            discount_amount: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_order_leaves_no_header_when_items_fail() {
+        // The second staged write (the items) fails
+        let service = SalesService::new(
+            Arc::new(MockSalesRepository::new().failing_unit_of_work_after(1)),
+            ValidationService::new(),
+        );
+        let customer_id = Uuid::new_v4();
+
+        assert!(service
+            .create_order(order_request(customer_id))
+            .await
+            .is_err());
+        assert!(service
+            .get_orders_by_customer(customer_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_order_uses_configured_prefix() {
+        let service = SalesService::new(
+            Arc::new(MockSalesRepository::new()),
+            ValidationService::new(),
+        )
+        .with_settings(SalesConfig {
+            order_prefix: "SO".to_string(),
+            ..SalesConfig::default()
+        });
+
+        let first = service
+            .create_order(order_request(Uuid::new_v4()))
+            .await
+            .unwrap();
+        let second = service
+            .create_order(order_request(Uuid::new_v4()))
+            .await
+            .unwrap();
+
+        assert_eq!(first.order.order_number, "SO-000001");
+        assert_eq!(second.order.order_number, "SO-000002");
+    }
+
+    #[tokio::test]
+    async fn test_create_order_rolls_back_sqlite_header_on_item_failure() {
+        let pool = sqlite::test_pool().await;
+        let customer_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO customers (id, name, created_at, updated_at) VALUES (?, 'Kim', ?, ?)",
+        )
+        .bind(customer_id)
+        .bind(Utc::now())
+        .bind(Utc::now())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // The item points at a product that does not exist
+        let service = SalesService::new(
+            Arc::new(SqliteSalesRepository::new(pool.clone())),
+            ValidationService::new(),
+        );
+        assert!(service
+            .create_order(order_request(customer_id))
+            .await
+            .is_err());
+
+        let orders: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sales_orders")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(orders, 0);
+    }
+
+    #[tokio::test]
+    async fn test_failing_stock_adjustment_rolls_back_status_change() {
+        let inventory_repository = Arc::new(MockInventoryRepository::new());
+        let mut product_ids = Vec::new();
+        for _ in 0..2 {
+            let product = inventory_repository
+                .create_product(CreateProductRequest {
+                    sku: format!("UOW-{}", Uuid::new_v4().simple()),
+                    name: "Unit of work widget".to_string(),
+                    description: None,
+                    category: "test".to_string(),
+                    price: Decimal::from(15),
+                    cost: Decimal::from(10),
+                    quantity: 10,
+                    min_stock_level: 1,
+                    max_stock_level: None,
+                    is_taxable: true,
+                    weight: None,
+                    dimensions: None,
+                    barcode: None,
+                    supplier_id: None,
+                })
+                .await
+                .unwrap();
+            product_ids.push(product.id);
+        }
+
+        // Status update and the first adjustment are staged, the second fails
+        let service = SalesService::new(
+            Arc::new(MockSalesRepository::new().failing_unit_of_work_after(2)),
+            ValidationService::new(),
+        )
+        .with_inventory_service(Arc::new(InventoryServiceImpl::new(
+            inventory_repository.clone(),
+        )));
+        let mut request = order_request(Uuid::new_v4());
+        request.items = product_ids
+            .iter()
+            .map(|&product_id| OrderItemRequest {
+                product_id,
+                quantity: 2,
+                unit_price: Some(Decimal::from(15)),
+                discount: None,
+            })
+            .collect();
+        let order_id = service.create_order(request).await.unwrap().order.id;
+
+        assert!(service
+            .update_order_status(order_id, OrderStatus::Confirmed)
+            .await
+            .is_err());
+
+        let order = service.get_order_by_id(order_id).await.unwrap().unwrap();
+        assert_eq!(order.order.status, OrderStatus::Draft);
+        for product_id in product_ids {
+            let product = inventory_repository
+                .get_product_by_id(product_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(product.quantity, 10);
+        }
+    }
+}
@@ -0,0 +1,434 @@
+//! cron 표현식 파서
+//!
+//! 표준 5필드 형식(분 시 일 월 요일)과 `@daily` 같은 별칭을 지원합니다.
+//! 각 필드는 `*`, 값, 범위(`1-5`), 목록(`1,15`), 간격(`*/15`, `9-18/3`)을 사용할 수 있고,
+//! 월/요일에는 `jan`, `mon` 같은 영문 약어를 쓸 수 있습니다.
+//!
+//! 해석 규칙은 Vixie cron을 따릅니다.
+//! - 간격은 범위에도 적용됩니다. `1-10/3`은 1,4,7,10이고, `5/15`처럼 시작값만 쓰면
+//!   필드 최대값까지 반복합니다.
+//! - 일과 요일이 모두 지정되면 둘 중 하나만 만족해도 실행됩니다(OR). 따라서
+//!   `0 9 1-7 * mon`은 "첫째 주 월요일"이 아니라 1~7일과 모든 월요일에 실행됩니다.
+//! - `*`로 시작하는 필드(`*/2` 포함)는 지정되지 않은 것으로 보므로, `0 0 */2 * mon`은
+//!   월요일에만 실행됩니다.
+//! - 범위를 벗어난 값, 역순 범위(`5-1`), 0이거나 숫자가 아닌 간격, 빈 목록 항목,
+//!   해당 필드에 없는 이름(분 필드의 `mon` 등)은 모두 검증 오류입니다.
+
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc,
+};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::utils::error::{ErpError, ErpResult};
+
+/// 다음 실행 시각을 찾을 때 탐색하는 최대 기간 (존재하지 않는 날짜 조합 방지)
+const MAX_SEARCH_YEARS: i32 = 5;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// 파싱된 cron 스케줄
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+struct FieldSpec {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+    names_offset: u32,
+}
+
+const MINUTE: FieldSpec = FieldSpec {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+    names_offset: 0,
+};
+const HOUR: FieldSpec = FieldSpec {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+    names_offset: 0,
+};
+const DAY_OF_MONTH: FieldSpec = FieldSpec {
+    name: "day_of_month",
+    min: 1,
+    max: 31,
+    names: &[],
+    names_offset: 0,
+};
+const MONTH: FieldSpec = FieldSpec {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: &MONTH_NAMES,
+    names_offset: 1,
+};
+// 요일은 7도 일요일로 허용
+const DAY_OF_WEEK: FieldSpec = FieldSpec {
+    name: "day_of_week",
+    min: 0,
+    max: 7,
+    names: &WEEKDAY_NAMES,
+    names_offset: 0,
+};
+
+impl CronSchedule {
+    /// 원본 표현식
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// 주어진 시각(분 단위)이 스케줄과 일치하는지 확인
+    pub fn matches(&self, at: NaiveDateTime) -> bool {
+        self.minutes & bit(at.minute()) != 0
+            && self.hours & bit(at.hour()) != 0
+            && self.months & bit(at.month()) != 0
+            && self.matches_day(at.date())
+    }
+
+    /// `after` 이후(같은 분 제외)의 다음 실행 시각
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = start.year() + MAX_SEARCH_YEARS;
+        let mut current = start;
+
+        while current.year() <= limit {
+            if self.months & bit(current.month()) == 0 {
+                let (year, month) = if current.month() == 12 {
+                    (current.year() + 1, 1)
+                } else {
+                    (current.year(), current.month() + 1)
+                };
+                current = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.matches_day(current.date()) {
+                current = current.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if self.hours & bit(current.hour()) == 0 {
+                current = current.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if self.minutes & bit(current.minute()) == 0 {
+                current += Duration::minutes(1);
+                continue;
+            }
+            return Some(current);
+        }
+
+        None
+    }
+
+    /// 로컬 시간대 기준으로 다음 실행 시각을 계산해 UTC로 반환
+    pub fn next_run(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut local = after.with_timezone(&Local).naive_local();
+        for _ in 0..=60 {
+            let next = self.next_after(local)?;
+            // 서머타임 전환으로 존재하지 않는 시각은 건너뜀
+            if let Some(resolved) = Local.from_local_datetime(&next).earliest() {
+                return Some(resolved.with_timezone(&Utc));
+            }
+            local = next;
+        }
+        None
+    }
+
+    /// 일/요일 조건 확인: 둘 다 지정되면 OR, 하나만 지정되면 그 필드만 확인
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let dom = self.days_of_month & bit(date.day()) != 0;
+        let dow = self.days_of_week & bit(date.weekday().num_days_from_sunday()) != 0;
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = ErpError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = s.trim();
+        let expanded = match expression.to_lowercase().as_str() {
+            "@yearly" | "@annually" => "0 0 1 1 *".to_string(),
+            "@monthly" => "0 0 1 * *".to_string(),
+            "@weekly" => "0 0 * * 0".to_string(),
+            "@daily" | "@midnight" => "0 0 * * *".to_string(),
+            "@hourly" => "0 * * * *".to_string(),
+            other if other.starts_with('@') => {
+                return Err(ErpError::validation(
+                    "cron",
+                    format!("지원되지 않는 cron 별칭입니다: {}", expression),
+                ))
+            }
+            _ => expression.to_string(),
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(ErpError::validation(
+                "cron",
+                format!(
+                    "cron 표현식은 5개 필드(분 시 일 월 요일)여야 합니다: {}",
+                    expression
+                ),
+            ));
+        }
+
+        let mut days_of_week = parse_field(fields[4], &DAY_OF_WEEK)?;
+        // 7(일요일)을 0으로 정규화
+        if days_of_week & bit(7) != 0 {
+            days_of_week = (days_of_week & !bit(7)) | bit(0);
+        }
+
+        Ok(Self {
+            expression: expression.to_string(),
+            minutes: parse_field(fields[0], &MINUTE)?,
+            hours: parse_field(fields[1], &HOUR)?,
+            days_of_month: parse_field(fields[2], &DAY_OF_MONTH)?,
+            months: parse_field(fields[3], &MONTH)?,
+            days_of_week,
+            day_of_month_restricted: !fields[2].starts_with('*'),
+            day_of_week_restricted: !fields[4].starts_with('*'),
+        })
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+fn bit(value: u32) -> u64 {
+    1u64 << value
+}
+
+fn parse_field(field: &str, spec: &FieldSpec) -> ErpResult<u64> {
+    let mut mask = 0u64;
+
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|s| *s > 0)
+                    .ok_or_else(|| invalid(spec, item))?;
+                (range, step)
+            }
+            None => (item, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (spec.min, spec.max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (parse_value(start, spec)?, parse_value(end, spec)?)
+        } else {
+            let start = parse_value(range, spec)?;
+            // `5/15`처럼 시작값에 간격만 붙은 경우 최대값까지 반복
+            let end = if item.contains('/') { spec.max } else { start };
+            (start, end)
+        };
+
+        if start > end {
+            return Err(invalid(spec, item));
+        }
+
+        let mut value = start;
+        while value <= end {
+            mask |= bit(value);
+            value += step;
+        }
+    }
+
+    Ok(mask)
+}
+
+fn parse_value(value: &str, spec: &FieldSpec) -> ErpResult<u32> {
+    let lower = value.to_lowercase();
+    let parsed = match spec.names.iter().position(|name| *name == lower) {
+        Some(index) => index as u32 + spec.names_offset,
+        None => value.parse().map_err(|_| invalid(spec, value))?,
+    };
+
+    if parsed < spec.min || parsed > spec.max {
+        return Err(invalid(spec, value));
+    }
+    Ok(parsed)
+}
+
+fn invalid(spec: &FieldSpec, value: &str) -> ErpError {
+    ErpError::validation(
+        "cron",
+        format!(
+            "{} 필드 값이 잘못되었습니다: {} (허용 범위 {}-{})",
+            spec.name, value, spec.min, spec.max
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let cron: CronSchedule = "*/15 9-18/3 1,15 jan-mar mon-fri".parse().unwrap();
+        assert_eq!(cron.minutes, bit(0) | bit(15) | bit(30) | bit(45));
+        assert_eq!(cron.hours, bit(9) | bit(12) | bit(15) | bit(18));
+        assert_eq!(cron.days_of_month, bit(1) | bit(15));
+        assert_eq!(cron.months, bit(1) | bit(2) | bit(3));
+        assert_eq!(cron.days_of_week, 0b0111110);
+        assert_eq!(cron.to_string(), "*/15 9-18/3 1,15 jan-mar mon-fri");
+
+        let sunday: CronSchedule = "0 0 * * 7".parse().unwrap();
+        assert_eq!(sunday.days_of_week, bit(0));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expression in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "@every5m",
+            "a * * * *",
+            "* * * * * *",
+            "1,,2 * * * *",
+            "1- * * * *",
+            "-5 * * * *",
+            "*-5 * * * *",
+            "1-2-3 * * * *",
+            "1-10/ * * * *",
+            "1-10/a * * * *",
+            "mon * * * *",
+            "* * * jan-feb/0 *",
+            "* * * * sun-sat/-1",
+        ] {
+            assert!(
+                expression.parse::<CronSchedule>().is_err(),
+                "{} should be rejected",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn test_steps_on_ranges() {
+        let cron: CronSchedule = "1-10/3 5/6 */10 jan-dec/3 mon-fri/2".parse().unwrap();
+        assert_eq!(cron.minutes, bit(1) | bit(4) | bit(7) | bit(10));
+        assert_eq!(cron.hours, bit(5) | bit(11) | bit(17) | bit(23));
+        assert_eq!(cron.days_of_month, bit(1) | bit(11) | bit(21) | bit(31));
+        assert_eq!(cron.months, bit(1) | bit(4) | bit(7) | bit(10));
+        assert_eq!(cron.days_of_week, bit(1) | bit(3) | bit(5));
+
+        let list: CronSchedule = "0-10/5,30-40/10 * * * *".parse().unwrap();
+        assert_eq!(list.minutes, bit(0) | bit(5) | bit(10) | bit(30) | bit(40));
+    }
+
+    #[test]
+    fn test_next_after() {
+        // 매주 월요일 08:00
+        let weekly: CronSchedule = "0 8 * * mon".parse().unwrap();
+        // 2024-06-05는 수요일
+        assert_eq!(
+            weekly.next_after(at(2024, 6, 5, 10, 30)),
+            Some(at(2024, 6, 10, 8, 0))
+        );
+        // 같은 분은 제외
+        assert_eq!(
+            weekly.next_after(at(2024, 6, 10, 8, 0)),
+            Some(at(2024, 6, 17, 8, 0))
+        );
+
+        let every_quarter_hour: CronSchedule = "*/15 * * * *".parse().unwrap();
+        assert_eq!(
+            every_quarter_hour.next_after(at(2024, 12, 31, 23, 50)),
+            Some(at(2025, 1, 1, 0, 0))
+        );
+
+        let monthly: CronSchedule = "@monthly".parse().unwrap();
+        assert_eq!(
+            monthly.next_after(at(2024, 1, 31, 12, 0)),
+            Some(at(2024, 2, 1, 0, 0))
+        );
+
+        let leap_day: CronSchedule = "0 0 29 2 *".parse().unwrap();
+        assert_eq!(
+            leap_day.next_after(at(2024, 3, 1, 0, 0)),
+            Some(at(2028, 2, 29, 0, 0))
+        );
+
+        let impossible: CronSchedule = "0 0 31 2 *".parse().unwrap();
+        assert_eq!(impossible.next_after(at(2024, 1, 1, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        // 1일 또는 금요일
+        let cron: CronSchedule = "0 9 1 * fri".parse().unwrap();
+        assert!(cron.matches(at(2024, 6, 1, 9, 0))); // 토요일, 1일
+        assert!(cron.matches(at(2024, 6, 7, 9, 0))); // 금요일
+        assert!(!cron.matches(at(2024, 6, 8, 9, 0))); // 토요일
+        assert_eq!(
+            cron.next_after(at(2024, 6, 1, 9, 0)),
+            Some(at(2024, 6, 7, 9, 0))
+        );
+
+        // 범위도 OR로 결합되므로 "첫째 주 월요일"이 아님
+        let first_week: CronSchedule = "0 9 1-7 * mon".parse().unwrap();
+        assert!(first_week.matches(at(2024, 6, 4, 9, 0))); // 화요일, 4일
+        assert!(first_week.matches(at(2024, 6, 17, 9, 0))); // 셋째 주 월요일
+    }
+
+    #[test]
+    fn test_starred_day_field_with_step_is_unrestricted() {
+        // `*/2`는 지정되지 않은 일로 취급되어 요일만 확인
+        let cron: CronSchedule = "0 0 */2 * mon".parse().unwrap();
+        assert!(cron.matches(at(2024, 6, 3, 0, 0))); // 월요일, 3일
+        assert!(cron.matches(at(2024, 6, 10, 0, 0))); // 월요일, 10일
+        assert!(!cron.matches(at(2024, 6, 5, 0, 0))); // 수요일, 5일
+    }
+
+    #[test]
+    fn test_next_run_is_in_future() {
+        let cron: CronSchedule = "@hourly".parse().unwrap();
+        let now = Utc::now();
+        let next = cron.next_run(now).unwrap();
+        assert!(next > now);
+        assert!(next - now <= Duration::hours(2));
+    }
+}
@@ -77,6 +77,28 @@ pub fn hash_string(data: &str) -> String {
     hash_data(data.as_bytes())
 }
 
+/// HMAC-SHA256 (RFC 2104), 16진수 문자열 반환
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> String {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut block_key = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block_key[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block_key[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(block_key.map(|b| b ^ 0x36));
+    inner.update(data);
+    let inner_hash = inner.finalize();
+
+    let mut outer = Sha256::new();
+    outer.update(block_key.map(|b| b ^ 0x5c));
+    outer.update(inner_hash);
+    hex::encode(outer.finalize())
+}
+
 pub fn secure_compare(a: &str, b: &str) -> bool {
     if a.len() != b.len() {
         return false;
@@ -219,6 +241,23 @@ mod tests {
         assert!(!verify_password("wrong_password", &hashed).unwrap());
     }
 
+    #[test]
+    fn test_hmac_sha256_rfc4231() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            hmac_sha256(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // RFC 4231 test case 6 (키가 블록 크기보다 긴 경우)
+        assert_eq!(
+            hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
     #[test]
     fn test_hash_data() {
         let data = b"hello world";
@@ -1,5 +1,6 @@
 use crate::modules::inventory::InventoryListResponse;
 use crate::utils::error::{ErpError, ErpResult};
+use crate::utils::xlsx::{Workbook, Worksheet};
 use serde_json;
 
 /// 재고 데이터를 다양한 형식으로 출력하는 포맷터
@@ -51,6 +52,52 @@ impl InventoryFormatter {
         Ok(csv)
     }
 
+    /// 재고 목록을 XLSX 워크북으로 변환
+    pub fn to_xlsx(response: &InventoryListResponse) -> Workbook {
+        let mut sheet = Worksheet::new("제품 목록");
+        sheet.header([
+            "SKU",
+            "제품명",
+            "카테고리",
+            "가격",
+            "원가",
+            "총수량",
+            "사용가능수량",
+            "예약수량",
+            "최소재고",
+            "상태",
+            "재고상태",
+            "위치",
+            "마진율(%)",
+            "최근 이동일",
+            "등록일",
+        ]);
+
+        for item in &response.items {
+            sheet.row(vec![
+                item.sku.as_str().into(),
+                item.name.as_str().into(),
+                item.category.as_str().into(),
+                item.price.into(),
+                item.cost.into(),
+                item.quantity.into(),
+                item.available_quantity.into(),
+                item.reserved_quantity.into(),
+                item.min_stock_level.into(),
+                item.status.to_string().into(),
+                item.stock_status.to_string().into(),
+                item.location.as_deref().into(),
+                item.margin_percentage.round_dp(2).into(),
+                item.last_movement_date.into(),
+                item.created_at.into(),
+            ]);
+        }
+
+        let mut workbook = Workbook::new();
+        workbook.add_sheet(sheet);
+        workbook
+    }
+
     /// 재고 목록을 YAML 형식으로 변환
     pub fn to_yaml(response: &InventoryListResponse) -> ErpResult<String> {
         match serde_yaml::to_string(response) {
@@ -158,6 +205,15 @@ mod tests {
         assert!(csv_content.contains("TEST001"));
     }
 
+    #[test]
+    fn test_to_xlsx() {
+        let response = create_test_response();
+        let workbook = InventoryFormatter::to_xlsx(&response);
+        assert_eq!(workbook.sheet_count(), 1);
+        let bytes = workbook.to_bytes().unwrap();
+        assert!(String::from_utf8_lossy(&bytes).contains("TEST001"));
+    }
+
     #[test]
     fn test_to_yaml() {
         let response = create_test_response();
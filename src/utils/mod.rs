@@ -1,11 +1,13 @@
 // 유틸리티 모듈
 // 에러 처리, 검증, 암호화 등 공통 유틸리티 기능들
 
+pub mod cron;
 pub mod crypto;
 pub mod error;
 pub mod inventory_formatter;
 pub mod output_formats;
 pub mod validation;
+pub mod xlsx;
 
 pub use crypto::*;
 pub use error::{ErpError, ErpResult};
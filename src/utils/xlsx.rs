@@ -0,0 +1,657 @@
+//! XLSX(Office Open XML) 스프레드시트 생성 유틸리티
+//!
+//! 최소한의 워크북을 생성합니다. 각 시트는 첫 행을 고정된 헤더로 사용하며,
+//! 숫자/날짜 셀은 타입을 유지하고 `Decimal` 값은 문자열 표현 그대로 기록해 정밀도를 보존합니다.
+//! 패키지는 `zip` 크레이트로 압축하지 않은(stored) ZIP 형식으로 작성됩니다.
+
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{Cursor, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, DateTime as ZipDateTime, ZipWriter};
+
+use crate::utils::error::{ErpError, ErpResult};
+
+/// 시트 이름 최대 길이 (Excel 제한)
+const MAX_SHEET_NAME_LEN: usize = 31;
+
+/// 셀 스타일 인덱스 (styles.xml의 cellXfs 순서와 일치)
+const STYLE_DEFAULT: u32 = 0;
+const STYLE_HEADER: u32 = 1;
+const STYLE_DATE: u32 = 2;
+const STYLE_DATETIME: u32 = 3;
+const STYLE_DECIMAL: u32 = 4;
+const STYLE_INTEGER: u32 = 5;
+const STYLE_TOTAL: u32 = 6;
+const STYLE_TOTAL_DECIMAL: u32 = 7;
+const STYLE_TOTAL_INTEGER: u32 = 8;
+
+/// 타입이 지정된 셀 값
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    Empty,
+    Text(String),
+    Integer(i64),
+    Decimal(Decimal),
+    Number(f64),
+    Bool(bool),
+    Date(NaiveDate),
+    DateTime(DateTime<Utc>),
+}
+
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CellValue::Empty => Ok(()),
+            CellValue::Text(text) => write!(f, "{}", text),
+            CellValue::Integer(value) => write!(f, "{}", value),
+            CellValue::Decimal(value) => write!(f, "{}", value),
+            CellValue::Number(value) => write!(f, "{}", value),
+            CellValue::Bool(value) => write!(f, "{}", if *value { "Y" } else { "N" }),
+            CellValue::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            CellValue::DateTime(datetime) => {
+                write!(f, "{}", datetime.format("%Y-%m-%d %H:%M:%S"))
+            }
+        }
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(value: String) -> Self {
+        CellValue::Text(value)
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(value: &str) -> Self {
+        CellValue::Text(value.to_string())
+    }
+}
+
+impl From<&String> for CellValue {
+    fn from(value: &String) -> Self {
+        CellValue::Text(value.clone())
+    }
+}
+
+impl From<Decimal> for CellValue {
+    fn from(value: Decimal) -> Self {
+        CellValue::Decimal(value)
+    }
+}
+
+impl From<i32> for CellValue {
+    fn from(value: i32) -> Self {
+        CellValue::Integer(value as i64)
+    }
+}
+
+impl From<i64> for CellValue {
+    fn from(value: i64) -> Self {
+        CellValue::Integer(value)
+    }
+}
+
+impl From<u32> for CellValue {
+    fn from(value: u32) -> Self {
+        CellValue::Integer(value as i64)
+    }
+}
+
+impl From<usize> for CellValue {
+    fn from(value: usize) -> Self {
+        CellValue::Integer(value as i64)
+    }
+}
+
+impl From<f64> for CellValue {
+    fn from(value: f64) -> Self {
+        CellValue::Number(value)
+    }
+}
+
+impl From<bool> for CellValue {
+    fn from(value: bool) -> Self {
+        CellValue::Bool(value)
+    }
+}
+
+impl From<NaiveDate> for CellValue {
+    fn from(value: NaiveDate) -> Self {
+        CellValue::Date(value)
+    }
+}
+
+impl From<DateTime<Utc>> for CellValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        CellValue::DateTime(value)
+    }
+}
+
+impl<T: Into<CellValue>> From<Option<T>> for CellValue {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(CellValue::Empty)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RowStyle {
+    Normal,
+    Header,
+    Total,
+}
+
+#[derive(Debug, Clone)]
+struct Row {
+    cells: Vec<CellValue>,
+    style: RowStyle,
+}
+
+/// 워크시트
+#[derive(Debug, Clone)]
+pub struct Worksheet {
+    name: String,
+    rows: Vec<Row>,
+}
+
+impl Worksheet {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// 헤더 행 추가 (첫 행이 헤더이면 고정됨)
+    pub fn header<I, S>(&mut self, headers: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(Row {
+            cells: headers
+                .into_iter()
+                .map(|h| CellValue::Text(h.into()))
+                .collect(),
+            style: RowStyle::Header,
+        });
+        self
+    }
+
+    pub fn row(&mut self, cells: Vec<CellValue>) -> &mut Self {
+        self.rows.push(Row {
+            cells,
+            style: RowStyle::Normal,
+        });
+        self
+    }
+
+    /// 합계 행 추가 (굵게 표시)
+    pub fn total_row(&mut self, cells: Vec<CellValue>) -> &mut Self {
+        self.rows.push(Row {
+            cells,
+            style: RowStyle::Total,
+        });
+        self
+    }
+
+    pub fn blank_row(&mut self) -> &mut Self {
+        self.row(Vec::new())
+    }
+
+    fn has_frozen_header(&self) -> bool {
+        self.rows
+            .first()
+            .is_some_and(|row| row.style == RowStyle::Header)
+    }
+
+    fn column_widths(&self) -> Vec<f64> {
+        let mut widths: Vec<f64> = Vec::new();
+        for row in &self.rows {
+            for (i, cell) in row.cells.iter().enumerate() {
+                let width = display_width(&cell.to_string()) + 2.0;
+                if i >= widths.len() {
+                    widths.resize(i + 1, 10.0);
+                }
+                widths[i] = widths[i].max(width.min(60.0));
+            }
+        }
+        widths
+    }
+
+    fn to_xml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">",
+        );
+
+        if self.has_frozen_header() {
+            xml.push_str(
+                "<sheetViews><sheetView workbookViewId=\"0\">\
+                 <pane ySplit=\"1\" topLeftCell=\"A2\" activePane=\"bottomLeft\" state=\"frozen\"/>\
+                 <selection pane=\"bottomLeft\"/>\
+                 </sheetView></sheetViews>",
+            );
+        }
+
+        let widths = self.column_widths();
+        if !widths.is_empty() {
+            xml.push_str("<cols>");
+            for (i, width) in widths.iter().enumerate() {
+                xml.push_str(&format!(
+                    "<col min=\"{n}\" max=\"{n}\" width=\"{w:.1}\" customWidth=\"1\"/>",
+                    n = i + 1,
+                    w = width
+                ));
+            }
+            xml.push_str("</cols>");
+        }
+
+        xml.push_str("<sheetData>");
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let row_number = row_index + 1;
+            if row.cells.is_empty() {
+                continue;
+            }
+            xml.push_str(&format!("<row r=\"{}\">", row_number));
+            for (col_index, cell) in row.cells.iter().enumerate() {
+                let reference = format!("{}{}", column_name(col_index), row_number);
+                write_cell(&mut xml, &reference, cell, row.style);
+            }
+            xml.push_str("</row>");
+        }
+        xml.push_str("</sheetData></worksheet>");
+        xml
+    }
+}
+
+/// 워크북
+#[derive(Debug, Clone, Default)]
+pub struct Workbook {
+    sheets: Vec<Worksheet>,
+}
+
+impl Workbook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 시트 추가 (이름은 Excel 규칙에 맞게 정리되고 중복 시 번호가 붙음)
+    pub fn add_sheet(&mut self, mut sheet: Worksheet) -> &mut Self {
+        let used: HashSet<String> = self.sheets.iter().map(|s| s.name.to_lowercase()).collect();
+        sheet.name = unique_sheet_name(&sheet.name, &used);
+        self.sheets.push(sheet);
+        self
+    }
+
+    pub fn sheet_count(&self) -> usize {
+        self.sheets.len()
+    }
+
+    /// XLSX 파일 바이트 생성
+    pub fn to_bytes(&self) -> ErpResult<Vec<u8>> {
+        if self.sheets.is_empty() {
+            return Err(ErpError::internal("XLSX 워크북에 시트가 없습니다"));
+        }
+
+        let mut parts: Vec<(String, String)> = vec![
+            ("[Content_Types].xml".to_string(), self.content_types_xml()),
+            ("_rels/.rels".to_string(), ROOT_RELS_XML.to_string()),
+            ("xl/workbook.xml".to_string(), self.workbook_xml()),
+            (
+                "xl/_rels/workbook.xml.rels".to_string(),
+                self.workbook_rels_xml(),
+            ),
+            ("xl/styles.xml".to_string(), STYLES_XML.to_string()),
+        ];
+        for (i, sheet) in self.sheets.iter().enumerate() {
+            parts.push((format!("xl/worksheets/sheet{}.xml", i + 1), sheet.to_xml()));
+        }
+
+        // 재현 가능한 출력을 위해 수정 시각을 1980-01-01로 고정
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .last_modified_time(ZipDateTime::default());
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            zip.start_file(name.as_str(), options)
+                .map_err(|e| ErpError::internal(format!("XLSX 패키지 작성 실패: {}", e)))?;
+            zip.write_all(content.as_bytes())
+                .map_err(|e| ErpError::io(format!("XLSX 패키지 작성 실패: {}", e)))?;
+        }
+        let cursor = zip
+            .finish()
+            .map_err(|e| ErpError::internal(format!("XLSX 패키지 작성 실패: {}", e)))?;
+        Ok(cursor.into_inner())
+    }
+
+    /// XLSX 파일로 저장
+    pub fn save(&self, path: impl AsRef<Path>) -> ErpResult<()> {
+        let bytes = self.to_bytes()?;
+        std::fs::write(path.as_ref(), bytes)
+            .map_err(|e| ErpError::io(format!("파일 쓰기 실패: {}", e)))
+    }
+
+    fn content_types_xml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+             <Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+             <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+             <Override PartName=\"/xl/workbook.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml\"/>\
+             <Override PartName=\"/xl/styles.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml\"/>",
+        );
+        for i in 1..=self.sheets.len() {
+            xml.push_str(&format!(
+                "<Override PartName=\"/xl/worksheets/sheet{}.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml\"/>",
+                i
+            ));
+        }
+        xml.push_str("</Types>");
+        xml
+    }
+
+    fn workbook_xml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" \
+             xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\"><sheets>",
+        );
+        for (i, sheet) in self.sheets.iter().enumerate() {
+            xml.push_str(&format!(
+                "<sheet name=\"{}\" sheetId=\"{}\" r:id=\"rId{}\"/>",
+                escape_xml(&sheet.name),
+                i + 1,
+                i + 1
+            ));
+        }
+        xml.push_str("</sheets></workbook>");
+        xml
+    }
+
+    fn workbook_rels_xml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">",
+        );
+        for i in 1..=self.sheets.len() {
+            xml.push_str(&format!(
+                "<Relationship Id=\"rId{i}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet\" Target=\"worksheets/sheet{i}.xml\"/>"
+            ));
+        }
+        xml.push_str(&format!(
+            "<Relationship Id=\"rId{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles\" Target=\"styles.xml\"/>",
+            self.sheets.len() + 1
+        ));
+        xml.push_str("</Relationships>");
+        xml
+    }
+}
+
+const ROOT_RELS_XML: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+<Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"xl/workbook.xml\"/>\
+</Relationships>";
+
+const STYLES_XML: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+<styleSheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">\
+<numFmts count=\"2\">\
+<numFmt numFmtId=\"164\" formatCode=\"yyyy-mm-dd\"/>\
+<numFmt numFmtId=\"165\" formatCode=\"yyyy-mm-dd hh:mm:ss\"/>\
+</numFmts>\
+<fonts count=\"2\">\
+<font><sz val=\"11\"/><name val=\"Calibri\"/></font>\
+<font><b/><sz val=\"11\"/><name val=\"Calibri\"/></font>\
+</fonts>\
+<fills count=\"3\">\
+<fill><patternFill patternType=\"none\"/></fill>\
+<fill><patternFill patternType=\"gray125\"/></fill>\
+<fill><patternFill patternType=\"solid\"><fgColor rgb=\"FFF2F2F2\"/><bgColor indexed=\"64\"/></patternFill></fill>\
+</fills>\
+<borders count=\"1\"><border><left/><right/><top/><bottom/><diagonal/></border></borders>\
+<cellStyleXfs count=\"1\"><xf numFmtId=\"0\" fontId=\"0\" fillId=\"0\" borderId=\"0\"/></cellStyleXfs>\
+<cellXfs count=\"9\">\
+<xf numFmtId=\"0\" fontId=\"0\" fillId=\"0\" borderId=\"0\" xfId=\"0\"/>\
+<xf numFmtId=\"0\" fontId=\"1\" fillId=\"2\" borderId=\"0\" xfId=\"0\" applyFont=\"1\" applyFill=\"1\"/>\
+<xf numFmtId=\"164\" fontId=\"0\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyNumberFormat=\"1\"/>\
+<xf numFmtId=\"165\" fontId=\"0\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyNumberFormat=\"1\"/>\
+<xf numFmtId=\"4\" fontId=\"0\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyNumberFormat=\"1\"/>\
+<xf numFmtId=\"3\" fontId=\"0\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyNumberFormat=\"1\"/>\
+<xf numFmtId=\"0\" fontId=\"1\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyFont=\"1\"/>\
+<xf numFmtId=\"4\" fontId=\"1\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyFont=\"1\" applyNumberFormat=\"1\"/>\
+<xf numFmtId=\"3\" fontId=\"1\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyFont=\"1\" applyNumberFormat=\"1\"/>\
+</cellXfs>\
+<cellStyles count=\"1\"><cellStyle name=\"Normal\" xfId=\"0\" builtinId=\"0\"/></cellStyles>\
+</styleSheet>";
+
+fn write_cell(xml: &mut String, reference: &str, cell: &CellValue, row_style: RowStyle) {
+    let total = row_style == RowStyle::Total;
+    let text_style = match row_style {
+        RowStyle::Header => STYLE_HEADER,
+        RowStyle::Total => STYLE_TOTAL,
+        RowStyle::Normal => STYLE_DEFAULT,
+    };
+
+    match cell {
+        CellValue::Empty => {}
+        CellValue::Text(text) => xml.push_str(&format!(
+            "<c r=\"{}\" s=\"{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+            reference,
+            text_style,
+            escape_xml(text)
+        )),
+        CellValue::Integer(value) => xml.push_str(&format!(
+            "<c r=\"{}\" s=\"{}\"><v>{}</v></c>",
+            reference,
+            if total {
+                STYLE_TOTAL_INTEGER
+            } else {
+                STYLE_INTEGER
+            },
+            value
+        )),
+        CellValue::Decimal(value) => xml.push_str(&format!(
+            "<c r=\"{}\" s=\"{}\"><v>{}</v></c>",
+            reference,
+            if total {
+                STYLE_TOTAL_DECIMAL
+            } else {
+                STYLE_DECIMAL
+            },
+            value
+        )),
+        CellValue::Number(value) if value.is_finite() => xml.push_str(&format!(
+            "<c r=\"{}\" s=\"{}\"><v>{}</v></c>",
+            reference, text_style, value
+        )),
+        CellValue::Number(_) => {}
+        CellValue::Bool(value) => xml.push_str(&format!(
+            "<c r=\"{}\" s=\"{}\" t=\"b\"><v>{}</v></c>",
+            reference,
+            text_style,
+            if *value { 1 } else { 0 }
+        )),
+        CellValue::Date(date) => xml.push_str(&format!(
+            "<c r=\"{}\" s=\"{}\"><v>{}</v></c>",
+            reference,
+            STYLE_DATE,
+            excel_date_serial(*date)
+        )),
+        CellValue::DateTime(datetime) => {
+            let naive = datetime.naive_utc();
+            let seconds = naive.time().num_seconds_from_midnight();
+            let serial = excel_date_serial(naive.date()) as f64 + seconds as f64 / 86_400.0;
+            xml.push_str(&format!(
+                "<c r=\"{}\" s=\"{}\"><v>{}</v></c>",
+                reference, STYLE_DATETIME, serial
+            ))
+        }
+    }
+}
+
+/// Excel 날짜 일련번호 (1900 날짜 체계, 1899-12-30 기준)
+fn excel_date_serial(date: NaiveDate) -> i64 {
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30).unwrap_or_default();
+    (date - epoch).num_days()
+}
+
+/// 0 기반 컬럼 인덱스를 A, B, ..., Z, AA 형식으로 변환
+fn column_name(index: usize) -> String {
+    let mut name = String::new();
+    let mut n = index + 1;
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        name.insert(0, (b'A' + rem as u8) as char);
+        n = (n - 1) / 26;
+    }
+    name
+}
+
+fn display_width(text: &str) -> f64 {
+    text.chars()
+        .map(|c| if c.is_ascii() { 1.0 } else { 2.0 })
+        .sum()
+}
+
+fn unique_sheet_name(name: &str, used: &HashSet<String>) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            _ => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim_matches('\'').trim();
+    let base: String = if cleaned.is_empty() {
+        "Sheet".to_string()
+    } else {
+        cleaned.chars().take(MAX_SHEET_NAME_LEN).collect()
+    };
+
+    if !used.contains(&base.to_lowercase()) {
+        return base;
+    }
+
+    (2..)
+        .map(|n| {
+            let suffix = format!(" ({})", n);
+            let prefix: String = base
+                .chars()
+                .take(MAX_SHEET_NAME_LEN - suffix.chars().count())
+                .collect();
+            format!("{}{}", prefix, suffix)
+        })
+        .find(|candidate| !used.contains(&candidate.to_lowercase()))
+        .unwrap_or(base)
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0에서 허용되지 않는 제어 문자 제거
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn sample_workbook() -> Workbook {
+        let mut sheet = Worksheet::new("매출/요약");
+        sheet
+            .header(["제품", "수량", "금액", "일자"])
+            .row(vec![
+                "A&B".into(),
+                3u32.into(),
+                Decimal::from_str("1234.5678").unwrap().into(),
+                NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().into(),
+            ])
+            .total_row(vec![
+                "합계".into(),
+                3u32.into(),
+                Decimal::from_str("1234.5678").unwrap().into(),
+                CellValue::Empty,
+            ]);
+
+        let mut workbook = Workbook::new();
+        workbook.add_sheet(sheet);
+        workbook
+    }
+
+    #[test]
+    fn test_column_name() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+    }
+
+    #[test]
+    fn test_excel_date_serial() {
+        assert_eq!(
+            excel_date_serial(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+            45292
+        );
+    }
+
+    #[test]
+    fn test_sheet_xml_typed_cells_and_frozen_header() {
+        let workbook = sample_workbook();
+        let xml = workbook.sheets[0].to_xml();
+        assert!(xml.contains("state=\"frozen\""));
+        assert!(xml.contains("<c r=\"C2\" s=\"4\"><v>1234.5678</v></c>"));
+        assert!(xml.contains("<c r=\"D2\" s=\"2\"><v>45292</v></c>"));
+        assert!(xml.contains("A&amp;B"));
+        assert_eq!(workbook.sheets[0].name, "매출_요약");
+    }
+
+    #[test]
+    fn test_unique_sheet_names() {
+        let mut workbook = Workbook::new();
+        workbook.add_sheet(Worksheet::new("요약"));
+        workbook.add_sheet(Worksheet::new("요약"));
+        workbook.add_sheet(Worksheet::new("x".repeat(40)));
+        assert_eq!(workbook.sheets[1].name, "요약 (2)");
+        assert_eq!(workbook.sheets[2].name.chars().count(), MAX_SHEET_NAME_LEN);
+    }
+
+    #[test]
+    fn test_workbook_zip_structure() {
+        let workbook = sample_workbook();
+        let bytes = workbook.to_bytes().unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+
+        // [Content_Types], rels, workbook, workbook rels, styles, sheet1
+        assert_eq!(archive.len(), 6);
+        assert!(archive.by_name("[Content_Types].xml").is_ok());
+        assert!(archive.by_name("xl/styles.xml").is_ok());
+
+        // 항목을 끝까지 읽으면 zip 크레이트가 CRC-32를 검증합니다
+        let mut sheet = String::new();
+        std::io::Read::read_to_string(
+            &mut archive.by_name("xl/worksheets/sheet1.xml").unwrap(),
+            &mut sheet,
+        )
+        .unwrap();
+        assert_eq!(sheet, workbook.sheets[0].to_xml());
+    }
+
+    #[test]
+    fn test_empty_workbook_is_error() {
+        assert!(Workbook::new().to_bytes().is_err());
+    }
+}